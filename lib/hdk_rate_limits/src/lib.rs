@@ -0,0 +1,152 @@
+/**
+ * Per-agent record creation limits, enforced during integrity validation.
+ *
+ * Limits are configured via the `rate_limits` DNA property, keyed by entry type
+ * name, as the maximum number of records of that type a single agent may create
+ * within a rolling hour:
+ *
+ * ```yaml
+ * properties:
+ *   rate_limits:
+ *     intent: 100
+ * ```
+ *
+ * Entry types without a configured limit are unrestricted. Since the count is
+ * taken from the author's source chain preceding the action being validated,
+ * every validator arrives at the same result.
+ *
+ * @package hdk_rate_limits
+ * @since   2026-10-16
+ */
+use std::collections::BTreeMap;
+use hdi::prelude::*;
+
+/// Length of the rolling window over which creations are counted
+pub const RATE_LIMIT_WINDOW_MICROS: i64 = 60 * 60 * 1_000_000;
+
+/// Number of actions requested from the author's chain per read
+const ACTIVITY_PAGE_SIZE: u32 = 50;
+
+/// DNA properties read by this module. All other properties are ignored.
+///
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, Default)]
+pub struct RateLimitProperties {
+    #[serde(default)]
+    pub rate_limits: BTreeMap<String, u32>,
+}
+
+/// Read the configured creation limit for the given entry type, if any.
+///
+pub fn read_creation_limit(entry_type_name: &str) -> ExternResult<Option<u32>> {
+    let props: RateLimitProperties = dna_info()?.modifiers.properties.try_into()
+        .map_err(|e| wasm_error!(WasmErrorInner::Serialize(e)))?;
+    Ok(props.rate_limits.get(entry_type_name).copied())
+}
+
+/// Validate an action against the creation limit configured for `entry_type_name`.
+///
+/// `entry_def` identifies the limited entry type within the integrity zome. Actions
+/// which are not `Create`s of this type are always valid.
+///
+pub fn validate_creation_rate(
+    action: &SignedActionHashed,
+    entry_def: &ScopedEntryDefIndex,
+    entry_type_name: &str,
+) -> ExternResult<ValidateCallbackResult> {
+    let action = action.action();
+    if !creates_entry_type(action, entry_def) {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let limit = match read_creation_limit(entry_type_name)? {
+        Some(limit) => limit,
+        None => return Ok(ValidateCallbackResult::Valid),
+    };
+
+    let window_start = action.timestamp().as_micros() - RATE_LIMIT_WINDOW_MICROS;
+    let prior_count = count_recent_creations(action.author(), action.prev_action(), entry_def, window_start)?;
+
+    match check_creation_rate(limit, prior_count, entry_type_name) {
+        Ok(()) => Ok(ValidateCallbackResult::Valid),
+        Err(e) => Ok(ValidateCallbackResult::Invalid(e)),
+    }
+}
+
+/// Determine whether creating another record is permissible, given the number
+/// of records of the same type already created within the window.
+///
+pub fn check_creation_rate(limit: u32, prior_count: usize, entry_type_name: &str) -> Result<(), String> {
+    if prior_count >= limit as usize {
+        return Err(format!(
+            "Creation rate limit exceeded: agents may create at most {} '{}' records per hour",
+            limit, entry_type_name,
+        ));
+    }
+    Ok(())
+}
+
+/// Determine whether `action` creates an entry of the type identified by `entry_def`.
+///
+pub fn creates_entry_type(action: &Action, entry_def: &ScopedEntryDefIndex) -> bool {
+    match action {
+        Action::Create(Create { entry_type: EntryType::App(def), .. }) =>
+            def.zome_index == entry_def.zome_index && def.entry_index == entry_def.zome_type,
+        _ => false,
+    }
+}
+
+/// Walk back through the author's chain from `chain_top`, counting creations of
+/// `entry_def` made at or after `window_start`.
+///
+fn count_recent_creations(
+    author: &AgentPubKey,
+    chain_top: Option<&ActionHash>,
+    entry_def: &ScopedEntryDefIndex,
+    window_start: i64,
+) -> ExternResult<usize> {
+    let mut count = 0;
+    let mut next_top = chain_top.cloned();
+
+    while let Some(top) = next_top.take() {
+        let activity = must_get_agent_activity(author.to_owned(), ChainFilter::new(top).take(ACTIVITY_PAGE_SIZE))?;
+
+        let mut reached_window_start = false;
+        let mut oldest: Option<&Action> = None;
+        for item in activity.iter() {
+            let action = item.action.action();
+            if oldest.map_or(true, |o| action.action_seq() < o.action_seq()) {
+                oldest = Some(action);
+            }
+            if action.timestamp().as_micros() < window_start {
+                reached_window_start = true;
+                continue;
+            }
+            if creates_entry_type(action, entry_def) {
+                count += 1;
+            }
+        }
+
+        if !reached_window_start && activity.len() == ACTIVITY_PAGE_SIZE as usize {
+            next_top = oldest.and_then(|a| a.prev_action().cloned());
+        }
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_creation_under_limit() {
+        assert_eq!(check_creation_rate(3, 0, "intent"), Ok(()));
+        assert_eq!(check_creation_rate(3, 2, "intent"), Ok(()));
+    }
+
+    #[test]
+    fn test_creation_over_limit() {
+        assert!(check_creation_rate(3, 3, "intent").is_err());
+        assert!(check_creation_rate(3, 10, "intent").is_err());
+        assert!(check_creation_rate(0, 0, "intent").is_err());
+    }
+}
@@ -24,12 +24,14 @@ use crate::{
     records::{
         create_record,
         read_record_entry_by_identity,
+        entry_data_unchanged,
     },
     entries::{
         get_entry_by_action,
         update_entry,
         delete_entry,
     },
+    validation_helpers::run_validation_hooks,
 };
 
 //--------------------------------[ READ ]--------------------------------------
@@ -137,7 +139,7 @@ pub fn create_anchored_record<LT, I, B, A, C, R, T, E, S, F, G>(
         B: DnaAddressable<EntryHash>,
         A: DnaIdentifiable<String>,
         C: TryInto<I, Error = DataIntegrityError> + UniquelyIdentifiable,
-        I: Identifiable<R>,
+        I: Identifiable<R> + Serialize,
         WasmError: From<E>,
         Entry: TryFrom<R, Error = E> + TryFrom<T, Error = E>,
         R: Clone + Identified<I, B>,
@@ -181,7 +183,7 @@ pub fn update_anchored_record<LT, I, R, A, B, U, E>(
 ) -> RecordAPIResult<(SignedActionHashed, B, I, I)>
     where A: DnaAddressable<EntryHash>,
         B: DnaIdentifiable<String>,
-        I: std::fmt::Debug + Identifiable<R> + Updateable<U>,
+        I: std::fmt::Debug + Identifiable<R> + Updateable<U> + Serialize,
         U: UpdateableIdentifier,
         WasmError: From<E>,
         Entry: TryFrom<R, Error = E>,
@@ -191,7 +193,7 @@ pub fn update_anchored_record<LT, I, R, A, B, U, E>(
         ScopedLinkType: TryFrom<LT, Error = E>,
 {
     // get referenced entry and identifiers for the given action
-    let (_meta, previous): (_, R) = get_entry_by_action(revision_id)?;
+    let (previous_meta, previous): (_, R) = get_entry_by_action(revision_id)?;
 
     let prev_entry = previous.entry();
     let identity = previous.identity()?;
@@ -206,7 +208,15 @@ pub fn update_anchored_record<LT, I, R, A, B, U, E>(
             let mut final_id = current_id.clone();
 
             // apply update payload
+            prev_entry.check_immutable_fields(&update_payload)?;
             let new_entry = prev_entry.update_with(update_payload)?;
+            run_validation_hooks(&new_entry, &new_entry.validation_hooks())?;
+
+            // identical entry data implies an unchanged anchor key, so there is nothing to write
+            if entry_data_unchanged(&prev_entry, &new_entry)? {
+                return Ok((previous_meta, DnaIdentifiable::new(dna_info()?.hash, final_id), new_entry, prev_entry));
+            }
+
             let storage: R = new_entry.with_identity(Some(identity_hash.clone()));
 
             // perform regular entry update using internal address
@@ -0,0 +1,316 @@
+/**
+ * Helpers for exporting records as portable bundles, and recreating them elsewhere.
+ *
+ * A bundle contains every revision of a record's entry data along with the identifiers
+ * of records it is linked to via semantic indexes. Entry data and identifiers are held
+ * in their JSON representation, so that the same bundle format can carry any record type
+ * and be inspected or transformed outside of Holochain.
+ *
+ * Records imported into a different DNA receive new identities. Any references to records
+ * in the source DNA are rewritten to point at the destination DNA during import, so that
+ * bundles for a set of related records can be moved together.
+ *
+ * @package hdk_records
+ * @since   2026-10-16
+ */
+use std::collections::{BTreeMap, HashSet};
+use hdk::prelude::*;
+use hdk::info::dna_info;
+use serde_json::Value;
+
+use crate::{
+    RecordAPIResult, DataIntegrityError,
+    DnaAddressable,
+    record_interface::{Identifiable, Identified},
+    entries::{
+        get_entry_by_action,
+        create_entry,
+        update_entry,
+        throw_if_entry_too_large,
+    },
+    identity_helpers::create_entry_identity,
+    metadata_helpers::get_action_hash,
+};
+
+/// Portable representation of a record, its revision history and index memberships.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordBundle {
+    pub record_type: String,
+    pub source_dna: DnaHash,
+    pub id: Value,
+    /// entry data of each revision, oldest first
+    pub revisions: Vec<Value>,
+    /// identifiers of related records, keyed by index relationship name
+    pub indexes: BTreeMap<String, Vec<Value>>,
+}
+
+impl RecordBundle {
+    /// Read the identifiers indexed for `relationship`, rewritten to reference records in `to_dna`
+    /// where they previously referenced the bundle's source DNA.
+    ///
+    pub fn index_targets<A>(&self, relationship: &str, to_dna: &DnaHash) -> RecordAPIResult<Vec<A>>
+        where A: serde::de::DeserializeOwned,
+    {
+        self.indexes.get(relationship).cloned().unwrap_or_default()
+            .into_iter()
+            .map(|v| from_json(remap_dna_references(v, &self.source_dna, to_dna)))
+            .collect()
+    }
+}
+
+//--------------------------------[ EXPORT ]--------------------------------------
+
+/// Export the record identified by `address` along with all of its revisions.
+///
+/// Index memberships are provided by the caller as pairs of relationship names and
+/// related record identifiers, since only the record's own zome knows which indexes it maintains.
+///
+pub fn export_record_bundle<T, R, B, S>(
+    record_type: S,
+    address: &B,
+    indexes: Vec<(&str, Vec<Value>)>,
+) -> RecordAPIResult<RecordBundle>
+    where S: AsRef<str>,
+        T: Serialize,
+        B: DnaAddressable<EntryHash>,
+        SerializedBytes: TryInto<R, Error = SerializedBytesError>,
+        Entry: TryFrom<R>,
+        R: std::fmt::Debug + Identified<T, B>,
+{
+    let revisions = read_revision_history::<R>(address.as_ref())?
+        .into_iter()
+        .map(|(_meta, storage)| to_json(&storage.entry()))
+        .collect::<RecordAPIResult<Vec<Value>>>()?;
+
+    Ok(RecordBundle {
+        record_type: record_type.as_ref().to_string(),
+        source_dna: AsRef::<DnaHash>::as_ref(address).to_owned(),
+        id: to_json(address)?,
+        revisions,
+        indexes: indexes.into_iter().map(|(rel, ids)| (rel.to_string(), ids)).collect(),
+    })
+}
+
+/// Convert a set of identifiers for inclusion in a `RecordBundle`
+///
+pub fn bundle_index_targets<A: Serialize>(ids: &[A]) -> RecordAPIResult<Vec<Value>> {
+    ids.iter().map(to_json).collect()
+}
+
+/// Read every revision of the record with the given initial `EntryHash`, oldest first.
+///
+/// The update chain is walked by `ActionHash`, so that revisions reverting to prior entry data
+/// are followed rather than mistaken for the revision they repeat.
+///
+/// :TODO: follows the most recent update at each step, in line with `get_latest_action_hash`.
+///        Revisions on any other update branches are not returned.
+///
+pub fn read_revision_history<R>(initial_entry_hash: &EntryHash) -> RecordAPIResult<Vec<(SignedActionHashed, R)>>
+    where SerializedBytes: TryInto<R, Error = SerializedBytesError>,
+{
+    let mut history = vec![];
+    let mut visited = HashSet::new();
+    let mut next = Some(read_creation_action(initial_entry_hash)?);
+
+    while let Some(action_hash) = next {
+        // guards against malformed update chains; well-formed chains never revisit an action
+        if !visited.insert(action_hash.to_owned()) { break; }
+        history.push(get_entry_by_action(&action_hash)?);
+        next = read_next_revision(&action_hash)?;
+    }
+
+    Ok(history)
+}
+
+/// Determine the `ActionHash` of the action which created the record with the given initial `EntryHash`.
+/// Where identical entries were created more than once, the earliest creation is used.
+///
+pub (crate) fn read_creation_action(initial_entry_hash: &EntryHash) -> RecordAPIResult<ActionHash> {
+    match get_details(initial_entry_hash.to_owned(), GetOptions { strategy: GetStrategy::Latest })? {
+        Some(Details::Entry(details)) => details.actions.iter()
+            .filter(|action| matches!(action.action(), Action::Create(_)))
+            .min_by_key(|action| action.action().timestamp())
+            .map(get_action_hash)
+            .ok_or(DataIntegrityError::EntryNotFound),
+        _ => Err(DataIntegrityError::EntryNotFound),
+    }
+}
+
+/// Iterate the actions which wrote each revision of the record with the given initial `EntryHash`,
+/// oldest first, following update chains in the same manner as `read_revision_history`.
+///
+/// Revisions are read lazily and entry data is not loaded, so that callers interested in
+/// only part of a record's history can stop walking once they have what they need.
+///
+pub (crate) fn iter_revision_actions(initial_entry_hash: &EntryHash) -> RecordAPIResult<impl Iterator<Item = RecordAPIResult<SignedActionHashed>>> {
+    let mut visited = HashSet::new();
+    let mut next = Some(read_creation_action(initial_entry_hash)?);
+
+    Ok(std::iter::from_fn(move || {
+        let action_hash = next.take()?;
+        // guards against malformed update chains; well-formed chains never revisit an action
+        if !visited.insert(action_hash.to_owned()) { return None; }
+        Some(read_revision_action(&action_hash).map(|(action, following)| {
+            next = following;
+            action
+        }))
+    }))
+}
+
+/// Determine the `ActionHash` of the revision following the one written by `action_hash`, if any.
+///
+pub (crate) fn read_next_revision(action_hash: &ActionHash) -> RecordAPIResult<Option<ActionHash>> {
+    Ok(read_revision_action(action_hash)?.1)
+}
+
+/// Read the action written by `action_hash` along with the `ActionHash` of the revision following it, if any.
+///
+fn read_revision_action(action_hash: &ActionHash) -> RecordAPIResult<(SignedActionHashed, Option<ActionHash>)> {
+    match get_details(action_hash.to_owned(), GetOptions { strategy: GetStrategy::Latest })? {
+        Some(Details::Record(details)) => Ok((
+            details.record.signed_action().to_owned(),
+            details.updates.iter()
+                .max_by_key(|update| update.action().timestamp())
+                .map(get_action_hash),
+        )),
+        _ => Err(DataIntegrityError::EntryNotFound),
+    }
+}
+
+//--------------------------------[ IMPORT ]--------------------------------------
+
+/// Recreate the revisions of a bundled record in the current DNA, returning the new record's
+/// latest revision, identity and entry data.
+///
+/// References to records in the bundle's source DNA are rewritten to reference the current DNA.
+/// Index memberships are not restored here; use `RecordBundle::index_targets` to re-link them.
+///
+pub fn import_record_bundle<T, I, R: Clone, B, E, S, F, G>(
+    indexing_zome_name_from_config: F,
+    entry_def_id: S,
+    bundle: &RecordBundle,
+) -> RecordAPIResult<(SignedActionHashed, B, I)>
+    where S: AsRef<str> + std::fmt::Display,
+        B: DnaAddressable<EntryHash>,
+        I: Identifiable<R> + serde::de::DeserializeOwned,
+        WasmError: From<E>,
+        Entry: TryFrom<R, Error = E>,
+        T: From<R>,
+        ScopedEntryDefIndex: for<'a> TryFrom<&'a T, Error = E>,
+        EntryVisibility: for<'a> From<&'a T>,
+        F: FnOnce(G) -> Option<String>,
+        G: std::fmt::Debug,
+        SerializedBytes: TryInto<G, Error = SerializedBytesError>,
+{
+    let dna_hash = dna_info()?.hash;
+    let mut revisions = bundle.revisions.iter()
+        .map(|r| from_json::<I>(remap_dna_references(r.to_owned(), &bundle.source_dna, &dna_hash)));
+
+    // write the initial revision and register its identity, as with `create_record`
+    let initial: I = revisions.next().ok_or(DataIntegrityError::EntryNotFound)??;
+    let storage = initial.with_identity(None);
+    throw_if_entry_too_large(&storage)?;
+    let (mut meta, entry_hash) = create_entry::<T,_,_>(storage)?;
+    let identity = B::new(dna_hash, entry_hash.to_owned());
+    create_entry_identity(indexing_zome_name_from_config, &entry_def_id, &identity)?;
+
+    // replay subsequent revisions as updates
+    let mut latest = initial;
+    for revision in revisions {
+        let revision: I = revision?;
+        let storage: R = revision.with_identity(Some(entry_hash.to_owned()));
+        throw_if_entry_too_large(&storage)?;
+        meta = update_entry(meta.as_hash(), storage)?.0;
+        latest = revision;
+    }
+
+    Ok((meta, identity, latest))
+}
+
+/// Rewrite any occurrences of the `from` DNA hash within a JSON value to `to`.
+///
+pub fn remap_dna_references(value: Value, from: &DnaHash, to: &DnaHash) -> Value {
+    let from_json = to_json(from).unwrap_or_default();
+    if from == to || value != from_json {
+        return match value {
+            Value::Array(items) => Value::Array(items.into_iter().map(|v| remap_dna_references(v, from, to)).collect()),
+            Value::Object(fields) => Value::Object(fields.into_iter().map(|(k, v)| (k, remap_dna_references(v, from, to))).collect()),
+            other => other,
+        };
+    }
+    to_json(to).unwrap_or(value)
+}
+
+//--------------------------[ UTILITIES  / INTERNALS ]---------------------
+
+fn to_json<T: Serialize>(data: &T) -> RecordAPIResult<Value> {
+    serde_json::to_value(data)
+        .map_err(|e| DataIntegrityError::Serialization(SerializedBytesError::Serialize(e.to_string())))
+}
+
+fn from_json<T: serde::de::DeserializeOwned>(value: Value) -> RecordAPIResult<T> {
+    serde_json::from_value(value)
+        .map_err(|e| DataIntegrityError::Serialization(SerializedBytesError::Deserialize(e.to_string())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hdk_uuid_types::addressable_identifier;
+
+    addressable_identifier!(TestAddress => EntryHash);
+
+    #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+    struct TestEntry {
+        name: String,
+        related: Option<TestAddress>,
+        others: Vec<TestAddress>,
+    }
+
+    #[test]
+    fn test_remap_dna_references() {
+        let source = DnaHash::from_raw_36(vec![0xdb; 36]);
+        let dest = DnaHash::from_raw_36(vec![0xdc; 36]);
+        let foreign = DnaHash::from_raw_36(vec![0xdd; 36]);
+        let entry = |dna: &DnaHash| TestEntry {
+            name: "bundled".to_string(),
+            related: Some(TestAddress(dna.to_owned(), EntryHash::from_raw_36(vec![0x01; 36]))),
+            others: vec![
+                TestAddress(dna.to_owned(), EntryHash::from_raw_36(vec![0x02; 36])),
+                TestAddress(foreign.to_owned(), EntryHash::from_raw_36(vec![0x03; 36])),
+            ],
+        };
+
+        let remapped = remap_dna_references(to_json(&entry(&source)).unwrap(), &source, &dest);
+        let remapped: TestEntry = from_json(remapped).unwrap();
+
+        assert_eq!(remapped.related, entry(&dest).related);
+        assert_eq!(remapped.others[0], entry(&dest).others[0]);
+        assert_eq!(remapped.others[1], entry(&source).others[1], "references to other DNAs are retained");
+        assert_eq!(remapped.name, "bundled");
+    }
+
+    #[test]
+    fn test_bundle_index_targets() {
+        let source = DnaHash::from_raw_36(vec![0xdb; 36]);
+        let dest = DnaHash::from_raw_36(vec![0xdc; 36]);
+        let target = TestAddress(source.to_owned(), EntryHash::from_raw_36(vec![0x01; 36]));
+
+        let mut indexes = BTreeMap::new();
+        indexes.insert("related".to_string(), bundle_index_targets(&[target.to_owned()]).unwrap());
+        let bundle = RecordBundle {
+            record_type: "test".to_string(),
+            source_dna: source,
+            id: Value::Null,
+            revisions: vec![],
+            indexes,
+        };
+
+        let targets: Vec<TestAddress> = bundle.index_targets("related", &dest).unwrap();
+        assert_eq!(targets, vec![TestAddress(dest, target.1)]);
+        let missing: Vec<TestAddress> = bundle.index_targets("unknown", &dest).unwrap();
+        assert!(missing.is_empty());
+    }
+}
@@ -0,0 +1,159 @@
+/**
+ * Helpers for guarding record deletion against dependent records.
+ *
+ * Deletion handlers use these to refuse removing a record whilst other records
+ * still reference it, or to remove all such dependents first when the caller
+ * requests a cascading delete. Cascading deletes are reported in full- both
+ * the dependents which were removed and any which could not be.
+ *
+ * @package hdk_records
+ * @since   2026-10-16
+ */
+use hdk::prelude::*;
+use hdk_uuid_types::DnaAddressable;
+
+use crate::{
+    RecordAPIResult, DataIntegrityError,
+};
+
+/// Maximum number of dependent record addresses to include in error responses
+///
+pub const MAX_REPORTED_DEPENDENTS: usize = 10;
+
+/// Input parameters for deletion handlers which check for dependent records
+///
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteParams {
+    pub revision_id: ActionHash,
+    /// if set, dependent records are deleted prior to the target record
+    #[serde(default)]
+    pub cascade: bool,
+}
+
+/// Summary of records of a single type which depend on a record being deleted
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DependentRecords {
+    pub record_type: String,
+    pub count: usize,
+    /// the first `MAX_REPORTED_DEPENDENTS` addresses of dependent records
+    pub addresses: Vec<EntryHash>,
+}
+
+impl std::fmt::Display for DependentRecords {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} ({}{})",
+            self.count, self.record_type,
+            self.addresses.iter().map(|a| a.to_string()).collect::<Vec<String>>().join(", "),
+            if self.count > self.addresses.len() { ", ..." } else { "" },
+        )
+    }
+}
+
+/// Reference to a dependent record removed, or failing to be removed, in a cascading delete
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DependentRecordResult {
+    pub record_type: String,
+    pub address: EntryHash,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl std::fmt::Display for DependentRecordResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.error {
+            Some(e) => write!(f, "{} {} ({})", self.record_type, self.address, e),
+            None => write!(f, "{} {}", self.record_type, self.address),
+        }
+    }
+}
+
+/// Response from deletion handlers which check for dependent records
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletionReport {
+    pub deleted: bool,
+    /// dependent records removed prior to deletion, if `cascade` was requested
+    pub removed_dependents: Vec<DependentRecordResult>,
+}
+
+/// Summarise a list of records of type `record_type` which depend on some record pending deletion.
+/// Returns `None` if there are no such dependents.
+///
+pub fn summarize_dependents<A, S>(record_type: S, addresses: &Vec<A>) -> Option<DependentRecords>
+    where A: DnaAddressable<EntryHash>,
+        S: AsRef<str>,
+{
+    if addresses.len() == 0 {
+        return None;
+    }
+    Some(DependentRecords {
+        record_type: record_type.as_ref().to_string(),
+        count: addresses.len(),
+        addresses: addresses.iter()
+            .take(MAX_REPORTED_DEPENDENTS)
+            .map(|a| { let h: &EntryHash = a.as_ref(); h.to_owned() })
+            .collect(),
+    })
+}
+
+/// Refuse deletion if any dependent records were found.
+///
+pub fn throw_if_dependents(dependents: Vec<Option<DependentRecords>>) -> RecordAPIResult<()> {
+    let found: Vec<DependentRecords> = dependents.into_iter().flatten().collect();
+    if found.len() > 0 {
+        return Err(DataIntegrityError::DependentRecordsExist(found));
+    }
+    Ok(())
+}
+
+/// Delete all records of type `record_type` at `addresses` via `delete_fn`, returning separate lists
+/// of dependents removed successfully and those which failed.
+///
+/// All deletions are attempted regardless of earlier failures, so that the outcome for every
+/// dependent can be reported to the caller.
+///
+pub fn cascade_delete_dependents<A, S, F, E>(
+    record_type: S,
+    addresses: Vec<A>,
+    delete_fn: F,
+) -> (Vec<DependentRecordResult>, Vec<DependentRecordResult>)
+    where A: DnaAddressable<EntryHash>,
+        S: AsRef<str>,
+        F: Fn(&A) -> Result<bool, E>,
+        E: std::fmt::Display,
+{
+    let (removed, failed): (Vec<DependentRecordResult>, Vec<DependentRecordResult>) = addresses.iter()
+        .map(|address| {
+            let hash: &EntryHash = address.as_ref();
+            DependentRecordResult {
+                record_type: record_type.as_ref().to_string(),
+                address: hash.to_owned(),
+                error: match delete_fn(address) {
+                    Ok(true) => None,
+                    Ok(false) => Some("record was not deleted".to_string()),
+                    Err(e) => Some(e.to_string()),
+                },
+            }
+        })
+        .partition(|r| r.error.is_none());
+
+    (removed, failed)
+}
+
+/// Abort a cascading delete if any dependents failed to be removed, reporting all outcomes.
+///
+pub fn throw_if_cascade_failed(
+    removed: Vec<DependentRecordResult>,
+    failed: Vec<DependentRecordResult>,
+) -> RecordAPIResult<Vec<DependentRecordResult>> {
+    if failed.len() > 0 {
+        return Err(DataIntegrityError::CascadeDeleteFailed(removed, failed));
+    }
+    Ok(removed)
+}
@@ -0,0 +1,217 @@
+/**
+ * Helpers for comparing revisions of a record.
+ *
+ * Entries are compared via their JSON representation, so any record type which
+ * implements `Serialize` can be diffed without further configuration. Fields are
+ * compared at the top level only; a change anywhere within a nested value is
+ * reported as a change to the containing field. Field names are reported in camelCase,
+ * matching the field names of record API responses.
+ *
+ * @package hdk_records
+ * @since   2026-10-16
+ */
+use hdk::prelude::*;
+use serde_json::{Map, Value};
+use crate::{
+    RecordAPIResult, DataIntegrityError,
+    DnaAddressable,
+    record_interface::Identified,
+    record_helpers::read_record_entry_by_action,
+};
+
+/// Parameters for requesting the differences between two revisions of a record
+///
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RevisionDiffParams {
+    pub from_revision_id: ActionHash,
+    pub to_revision_id: ActionHash,
+}
+
+/// A single field which differs between two revisions.
+/// Fields absent from one revision are reported with a `null` value.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FieldChange {
+    pub field: String,
+    pub old_value: Value,
+    pub new_value: Value,
+}
+
+/// Read two revisions of the same record and return the fields which differ between them.
+///
+pub fn diff_record_revisions<T, R, B>(
+    from_action: &ActionHash,
+    to_action: &ActionHash,
+) -> RecordAPIResult<Vec<FieldChange>>
+    where T: std::fmt::Debug + Serialize,
+        B: DnaAddressable<EntryHash>,
+        SerializedBytes: TryInto<R, Error = SerializedBytesError>,
+        Entry: TryFrom<R>,
+        R: std::fmt::Debug + Identified<T, B>,
+{
+    let (_, from_identity, from_entry): (_, B, T) = read_record_entry_by_action(from_action)?;
+    let (_, to_identity, to_entry): (_, B, T) = read_record_entry_by_action(to_action)?;
+
+    if from_identity != to_identity {
+        return Err(DataIntegrityError::RevisionsOfDifferentRecords(from_action.to_owned(), to_action.to_owned()));
+    }
+
+    diff_entries(&from_entry, &to_entry)
+}
+
+/// Compare two entries field-by-field, returning changes ordered by field name.
+///
+pub fn diff_entries<T>(from: &T, to: &T) -> RecordAPIResult<Vec<FieldChange>>
+    where T: Serialize,
+{
+    Ok(diff_values(to_json(from)?, to_json(to)?))
+}
+
+/// Compare an entry with the `previous` revision of the same record, field-by-field.
+/// Where there is no previous revision, every field set by `entry` is reported as a change.
+///
+/// `excluded_fields` names entry fields which are never reported, such as those derived
+/// from other records rather than edited directly.
+///
+pub fn diff_against_previous<T>(previous: Option<&T>, entry: &T, excluded_fields: &[&str]) -> RecordAPIResult<Vec<FieldChange>>
+    where T: Serialize,
+{
+    let to = without_fields(to_json(entry)?, excluded_fields);
+    let from = match (previous, &to) {
+        (Some(previous), _) => without_fields(to_json(previous)?, excluded_fields),
+        (None, Value::Object(_)) => Value::Object(Map::new()),
+        (None, _) => Value::Null,
+    };
+    Ok(diff_values(from, to))
+}
+
+fn without_fields(mut value: Value, fields: &[&str]) -> Value {
+    if let Value::Object(map) = &mut value {
+        for field in fields {
+            map.remove(*field);
+        }
+    }
+    value
+}
+
+fn to_json<T: Serialize>(entry: &T) -> RecordAPIResult<Value> {
+    serde_json::to_value(entry)
+        .map_err(|e| DataIntegrityError::Serialization(SerializedBytesError::Serialize(e.to_string())))
+}
+
+fn diff_values(from: Value, to: Value) -> Vec<FieldChange> {
+    match (from, to) {
+        (Value::Object(from), Value::Object(to)) => diff_objects(from, to),
+        (from, to) if from == to => vec![],
+        // non-struct entries are compared as a single unnamed field
+        (from, to) => vec![FieldChange { field: "".to_string(), old_value: from, new_value: to }],
+    }
+}
+
+fn diff_objects(mut from: Map<String, Value>, mut to: Map<String, Value>) -> Vec<FieldChange> {
+    let mut fields: Vec<String> = from.keys().chain(to.keys()).cloned().collect();
+    fields.sort();
+    fields.dedup();
+
+    let mut changes: Vec<FieldChange> = fields.into_iter()
+        .filter_map(|field| {
+            let old_value = from.remove(&field).unwrap_or(Value::Null);
+            let new_value = to.remove(&field).unwrap_or(Value::Null);
+            if old_value == new_value {
+                None
+            } else {
+                Some(FieldChange { field: camel_case_field(&field), old_value, new_value })
+            }
+        })
+        .collect();
+    changes.sort_by(|a, b| a.field.cmp(&b.field));
+    changes
+}
+
+/// Convert a snake_case entry field name to the camelCase name used by API responses.
+/// Leading underscores, which mark internal fields, are retained.
+///
+fn camel_case_field(field: &str) -> String {
+    let mut name = String::with_capacity(field.len());
+    let mut upper_next = false;
+    for c in field.chars() {
+        if c == '_' && !name.is_empty() {
+            upper_next = true;
+        } else if upper_next {
+            name.extend(c.to_uppercase());
+            upper_next = false;
+        } else {
+            name.push(c);
+        }
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct TestEntry {
+        name: String,
+        note: Option<String>,
+        classified_as: Option<Vec<String>>,
+    }
+
+    fn entry(note: Option<&str>) -> TestEntry {
+        TestEntry {
+            name: "Alice".to_string(),
+            note: note.map(|n| n.to_string()),
+            classified_as: Some(vec!["person".to_string()]),
+        }
+    }
+
+    #[test]
+    fn test_diff_single_field() {
+        let changes = diff_entries(&entry(Some("before")), &entry(Some("after"))).unwrap();
+        assert_eq!(changes, vec![FieldChange {
+            field: "note".to_string(),
+            old_value: Value::String("before".to_string()),
+            new_value: Value::String("after".to_string()),
+        }]);
+    }
+
+    #[test]
+    fn test_diff_cleared_field() {
+        let changes = diff_entries(&entry(Some("before")), &entry(None)).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].new_value, Value::Null);
+    }
+
+    #[test]
+    fn test_diff_initial_revision() {
+        let changes = diff_against_previous(None, &entry(None), &[]).unwrap();
+        let fields: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["classifiedAs", "name"], "unset fields not reported");
+        assert!(changes.iter().all(|c| c.old_value == Value::Null));
+    }
+
+    #[test]
+    fn test_diff_excluded_fields() {
+        let changes = diff_against_previous(Some(&entry(Some("before"))), &entry(Some("after")), &["note"]).unwrap();
+        assert!(changes.is_empty(), "changes to excluded fields not reported");
+
+        let changes = diff_against_previous(None, &entry(None), &["classified_as"]).unwrap();
+        assert_eq!(changes.iter().map(|c| c.field.as_str()).collect::<Vec<&str>>(), vec!["name"]);
+    }
+
+    #[test]
+    fn test_camel_case_field() {
+        assert_eq!(camel_case_field("classified_as"), "classifiedAs");
+        assert_eq!(camel_case_field("has_point_in_time"), "hasPointInTime");
+        assert_eq!(camel_case_field("note"), "note");
+        assert_eq!(camel_case_field("_nonce"), "_nonce");
+    }
+
+    #[test]
+    fn test_diff_identical() {
+        assert!(diff_entries(&entry(Some("same")), &entry(Some("same"))).unwrap().is_empty());
+    }
+}
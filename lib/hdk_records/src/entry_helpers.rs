@@ -44,6 +44,60 @@ pub (crate) fn try_decode_entry<T>(entry: Entry) -> RecordAPIResult<T>
     }
 }
 
+//-----------------------------[ SIZE LIMITS ]----------------------------------
+
+/// Default upper bound on the serialized size of an entry, matching the limit
+/// enforced by the Holochain conductor on app entries.
+///
+pub const DEFAULT_MAX_ENTRY_BYTES: usize = 4 * 1000 * 1000;
+
+// DNA properties wrapper for reading the configured limit under the `max_entry_bytes` key
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone)]
+struct EntrySizeProperties {
+    #[serde(default)]
+    pub max_entry_bytes: Option<usize>,
+}
+
+/// Read the maximum serialized entry size for the current DNA from its `max_entry_bytes`
+/// property. If not configured, `DEFAULT_MAX_ENTRY_BYTES` is used.
+///
+pub fn read_max_entry_bytes() -> RecordAPIResult<usize> {
+    let props: Result<EntrySizeProperties, SerializedBytesError> = dna_info()?.modifiers.properties.try_into();
+    Ok(props.ok().and_then(|p| p.max_entry_bytes).unwrap_or(DEFAULT_MAX_ENTRY_BYTES))
+}
+
+/// Determine the size of an entry once serialized for storage.
+///
+pub fn serialized_entry_size<R: Clone, E>(entry: &R) -> RecordAPIResult<usize>
+    where WasmError: From<E>,
+        Entry: TryFrom<R, Error = E>,
+{
+    let entry: Entry = entry.to_owned().try_into().map_err(|e: E| DataIntegrityError::Wasm(e.into()))?;
+    Ok(match entry {
+        Entry::App(bytes) => bytes.0.bytes().len(),
+        _ => SerializedBytes::try_from(entry)?.bytes().len(),
+    })
+}
+
+/// Check a serialized entry size against a limit, erroring if it is exceeded.
+///
+pub fn check_entry_size(size: usize, limit: usize) -> RecordAPIResult<()> {
+    if size > limit {
+        return Err(DataIntegrityError::EntryTooLarge(size, limit));
+    }
+    Ok(())
+}
+
+/// Prevent oversized entries from being written, by checking their serialized size
+/// against the limit configured for the current DNA.
+///
+pub fn throw_if_entry_too_large<R: Clone, E>(entry: &R) -> RecordAPIResult<()>
+    where WasmError: From<E>,
+        Entry: TryFrom<R, Error = E>,
+{
+    check_entry_size(serialized_entry_size(entry)?, read_max_entry_bytes()?)
+}
+
 //--------------------------------[ READ ]--------------------------------------
 
 /// Reads an entry from the DHT by its `EntryHash`. The latest live version of the entry will be returned.
@@ -92,6 +146,34 @@ pub fn get_entry_by_action<R>(address: &ActionHash) -> RecordAPIResult<(SignedAc
 
 //-------------------------------[ CREATE ]-------------------------------------
 
+/// How the `_nonce` of newly created entries is generated, read from zome config.
+///
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NonceMode {
+    // every entry is distinct, even where its content matches another
+    Random,
+    // entries are addressed by their content alone, so identical entries share a hash
+    ContentAddressed,
+}
+
+impl Default for NonceMode {
+    fn default() -> Self {
+        NonceMode::Random
+    }
+}
+
+/// Generate the `_nonce` for a newly created entry under the given `NonceMode`.
+/// Content-addressed entries receive an empty nonce, leaving their hash to be
+/// determined by their other fields.
+///
+pub fn create_nonce(mode: NonceMode) -> RecordAPIResult<Bytes> {
+    match mode {
+        NonceMode::Random => Ok(random_bytes(32)?),
+        NonceMode::ContentAddressed => Ok(Bytes::default()),
+    }
+}
+
 /// Creates a new entry in the DHT and returns a tuple of
 /// the `action address` and `entry address`.
 ///
@@ -217,6 +299,23 @@ mod tests {
         field: Option<String>,
     }
 
+    #[test]
+    fn test_entry_size_limit() {
+        let entry = TestEntry { field: Some("x".repeat(64)) };
+        let size = serialized_entry_size(&entry).unwrap();
+        assert!(size >= 64, "serialized size must include field data");
+
+        assert!(check_entry_size(size, DEFAULT_MAX_ENTRY_BYTES).is_ok(), "normal entries are accepted");
+        assert!(check_entry_size(size, size).is_ok(), "entries at the limit are accepted");
+        match check_entry_size(size, 32) {
+            Err(DataIntegrityError::EntryTooLarge(s, limit)) => {
+                assert_eq!(s, size);
+                assert_eq!(limit, 32);
+            },
+            _ => panic!("oversized entries must be rejected"),
+        }
+    }
+
     #[test]
     fn test_roundtrip() {
         let entry = TestEntry { field: None };
@@ -0,0 +1,160 @@
+/**
+ * Helpers for maintaining secondary indexes over exact scalar field values,
+ * similar to non-unique indexes in relational databases.
+ *
+ * Each distinct value of an indexed field is represented by a `Path` composed
+ * of the index name and the value itself. Records are linked from the path for
+ * their current value, and relinked when the value changes between revisions.
+ *
+ * Values are extracted from entry data via closures, so that the same helpers
+ * can be reused across record types and fields.
+ *
+ * @package hdk_records
+ * @since   2026-10-16
+ */
+use hdk::prelude::*;
+use hdk_uuid_types::DnaAddressable;
+
+use crate::{
+    RecordAPIResult,
+    identity_helpers::{
+        calculate_identity_address_checked,
+        infer_local_entry_identity,
+    },
+};
+
+//--------------------------------[ READ ]--------------------------------------
+
+/// Calculate the `Path` under which records having `value` for the field indexed
+/// as `index_name` are linked.
+///
+fn field_value_path_for<S, V>(
+    index_name: S,
+    value: V,
+) -> Path
+    where S: AsRef<str>,
+        V: AsRef<str>,
+{
+    Path::from(vec![
+        Component::from(index_name.as_ref().as_bytes().to_vec()),
+        Component::from(value.as_ref().as_bytes().to_vec()),
+    ])
+}
+
+/// Retrieve the identities of all records whose field indexed as `index_name`
+/// exactly matches `value`.
+///
+pub fn find_by_field_value<A, LT, S, V>(
+    link_type: LT,
+    index_name: S,
+    value: V,
+) -> RecordAPIResult<Vec<A>>
+    where A: DnaAddressable<EntryHash>,
+        LT: LinkTypeFilterExt,
+        S: AsRef<str>,
+        V: AsRef<str>,
+{
+    let path_hash = field_value_path_for(index_name, value).path_entry_hash()?;
+
+    get_links(path_hash, link_type, Some(LinkTag::new(crate::identifiers::RECORD_FIELD_VALUE_LINK_TAG)))?
+        .iter()
+        .filter_map(|l| l.target.to_owned().into_entry_hash())
+        .map(|identity_hash| infer_local_entry_identity(&identity_hash))
+        .collect()
+}
+
+//-------------------------------[ UPDATE ]-------------------------------------
+
+/// Maintain the field value index `index_name` for the record identified by `identity`.
+///
+/// `new_entry` and `prev_entry` are the record data after and before the write being
+/// indexed- pass `None` for `prev_entry` upon creation, and `None` for `new_entry`
+/// upon deletion. `extract_value` determines the indexed value from each entry,
+/// returning `None` where the field is unset.
+///
+/// The record is only relinked if the extracted value differs between revisions.
+///
+pub fn sync_field_value_index<A, T, F, LT, S, E>(
+    link_type: LT,
+    index_name: S,
+    identity: &A,
+    new_entry: Option<&T>,
+    prev_entry: Option<&T>,
+    extract_value: F,
+) -> RecordAPIResult<()>
+    where A: DnaAddressable<EntryHash>,
+        F: Fn(&T) -> Option<String>,
+        LT: Clone + LinkTypeFilterExt,
+        ScopedLinkType: TryFrom<LT, Error = E>,
+        WasmError: From<E>,
+        S: AsRef<str>,
+{
+    let new_value = new_entry.and_then(&extract_value);
+    let prev_value = prev_entry.and_then(&extract_value);
+
+    if new_value == prev_value {
+        return Ok(());
+    }
+
+    let identity_hash = calculate_identity_address_checked(identity)?;
+
+    if let Some(prev) = prev_value {
+        unlink_field_value(link_type.to_owned(), &index_name, &prev, &identity_hash)?;
+    }
+    if let Some(new) = new_value {
+        link_field_value(link_type, &index_name, &new, &identity_hash)?;
+    }
+
+    Ok(())
+}
+
+//--------------------------[ UTILITIES  / INTERNALS ]---------------------
+
+/// Link the record at `identity_hash` from the path for `value`, if not already present
+///
+fn link_field_value<LT, S, E>(
+    link_type: LT,
+    index_name: &S,
+    value: &String,
+    identity_hash: &EntryHash,
+) -> RecordAPIResult<()>
+    where LT: Clone + LinkTypeFilterExt,
+        ScopedLinkType: TryFrom<LT, Error = E>,
+        WasmError: From<E>,
+        S: AsRef<str>,
+{
+    let path_hash = field_value_path_for(index_name, value).path_entry_hash()?;
+    let tag = LinkTag::new(crate::identifiers::RECORD_FIELD_VALUE_LINK_TAG);
+
+    let already_linked = get_links(path_hash.to_owned(), link_type.to_owned(), Some(tag.to_owned()))?
+        .iter()
+        .any(|l| l.target.to_owned().into_entry_hash().as_ref() == Some(identity_hash));
+
+    if !already_linked {
+        create_link(path_hash, identity_hash.to_owned(), link_type, tag)?;
+    }
+
+    Ok(())
+}
+
+/// Remove any links to the record at `identity_hash` from the path for `value`
+///
+fn unlink_field_value<LT, S>(
+    link_type: LT,
+    index_name: &S,
+    value: &String,
+    identity_hash: &EntryHash,
+) -> RecordAPIResult<()>
+    where LT: LinkTypeFilterExt,
+        S: AsRef<str>,
+{
+    let path_hash = field_value_path_for(index_name, value).path_entry_hash()?;
+
+    for link in get_links(path_hash, link_type, Some(LinkTag::new(crate::identifiers::RECORD_FIELD_VALUE_LINK_TAG)))? {
+        if link.target.to_owned().into_entry_hash().as_ref() == Some(identity_hash) {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+
+    Ok(())
+}
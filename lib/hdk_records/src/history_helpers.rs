@@ -0,0 +1,278 @@
+/**
+ * Helpers for reading a window of a record's revision history.
+ *
+ * Revisions are filtered by the timestamps of the actions which wrote them, so that
+ * frequently-edited records can be audited over a period without transferring every
+ * revision. Each window also references the revision immediately preceding it, which
+ * allows the first revision within the window to be diffed against its predecessor.
+ *
+ * Edit logs summarise each revision of a record as the fields it changed, so that clients
+ * can attribute changes to their authors without fetching and comparing full revisions.
+ *
+ * @package hdk_records
+ * @since   2026-10-16
+ */
+use chrono::{ DateTime, Utc };
+use hdk::prelude::*;
+
+use crate::{
+    RecordAPIResult,
+    DnaAddressable,
+    record_interface::Identified,
+    entries::get_entry_by_action,
+    metadata_helpers::{ RevisionMeta, get_action_hash },
+    bundle_helpers::{ read_revision_history, iter_revision_actions },
+    diff_helpers::{ FieldChange, diff_against_previous },
+};
+
+/// Parameters for reading the revisions of a record written within a period of time.
+///
+/// `since` is inclusive and `until` exclusive; either may be omitted to leave the window open.
+/// `limit` caps the number of revisions returned, counting from the start of the window.
+///
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RevisionHistoryParams<A> {
+    pub address: A,
+    #[serde(default)]
+    pub since: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub until: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// A single revision of a record along with the metadata of the action which wrote it.
+///
+/// `version` numbers the revisions of a record in the order they were written, starting from 1
+/// for the revision which created it.
+///
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoricalRevision<T> {
+    pub revision: RevisionMeta,
+    pub version: usize,
+    pub entry: T,
+}
+
+/// Revisions of a record within a window of time, oldest first.
+///
+/// `preceding_revision` is the latest revision written prior to the window, if any, and
+/// `preceding_version` its version number.
+/// `has_more` is set where further revisions within the window were omitted due to `limit`.
+///
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RevisionHistory<T> {
+    pub revisions: Vec<HistoricalRevision<T>>,
+    pub preceding_revision: Option<RevisionMeta>,
+    pub preceding_version: Option<usize>,
+    pub has_more: bool,
+}
+
+/// The fields changed by a single revision of a record, along with the metadata of the
+/// action which wrote it. The initial revision reports every field it set.
+///
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RevisionEdit {
+    pub revision: RevisionMeta,
+    pub changes: Vec<FieldChange>,
+}
+
+/// Read the revisions of the record identified by `address` which were written within
+/// the window described by `since`, `until` and `limit`.
+///
+/// Entry data is only loaded for revisions within the window, and the record's update chain
+/// is not walked beyond the end of it. Each revision is passed to `construct` along with the
+/// action which wrote it, so that zomes can return revisions in the shape of their API responses.
+///
+pub fn read_revision_window<T, R, B, O, F>(
+    address: &B,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    limit: Option<usize>,
+    construct: F,
+) -> RecordAPIResult<RevisionHistory<O>>
+    where B: DnaAddressable<EntryHash>,
+        SerializedBytes: TryInto<R, Error = SerializedBytesError>,
+        Entry: TryFrom<R>,
+        R: Identified<T, B>,
+        F: Fn(&SignedActionHashed, &T) -> RecordAPIResult<O>,
+{
+    let revisions = iter_revision_actions(address.as_ref())?
+        .map(|action| action.map(|action| ((&action).into(), get_action_hash(&action))));
+
+    window_revisions(revisions, since, until, limit, |action_hash| {
+        let (action, storage) = get_entry_by_action::<R>(&action_hash)?;
+        construct(&action, &storage.entry())
+    })
+}
+
+/// Select the revisions from `history` (ordered oldest first) which fall within the given window,
+/// passing each selected revision to `load` to read its entry data.
+///
+/// `history` is consumed only as far as the end of the window.
+///
+pub fn window_revisions<T, L, I, F>(
+    history: I,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+    limit: Option<usize>,
+    mut load: F,
+) -> RecordAPIResult<RevisionHistory<T>>
+    where I: IntoIterator<Item = RecordAPIResult<(RevisionMeta, L)>>,
+        F: FnMut(L) -> RecordAPIResult<T>,
+{
+    let mut preceding_revision = None;
+    let mut preceding_version = None;
+    let mut revisions = vec![];
+    let mut has_more = false;
+
+    for (index, rev) in history.into_iter().enumerate() {
+        let (revision, pending) = rev?;
+        let version = index + 1;
+        let time = revision.time;
+        if since.map_or(false, |since| time < since) {
+            preceding_revision = Some(revision);
+            preceding_version = Some(version);
+            continue;
+        }
+        if until.map_or(false, |until| time >= until) {
+            break;
+        }
+        if limit.map_or(false, |limit| revisions.len() >= limit) {
+            has_more = true;
+            break;
+        }
+        revisions.push(HistoricalRevision { revision, version, entry: load(pending)? });
+    }
+
+    Ok(RevisionHistory { revisions, preceding_revision, preceding_version, has_more })
+}
+
+/// Read every revision of the record identified by `address`, oldest first,
+/// as the changes each made relative to the revision before it.
+/// Changes to any of `excluded_fields` are omitted, @see `diff_against_previous`.
+///
+pub fn read_edit_log<T, R, B>(address: &B, excluded_fields: &[&str]) -> RecordAPIResult<Vec<RevisionEdit>>
+    where T: Serialize,
+        B: DnaAddressable<EntryHash>,
+        SerializedBytes: TryInto<R, Error = SerializedBytesError>,
+        Entry: TryFrom<R>,
+        R: Identified<T, B>,
+{
+    let history = read_revision_history::<R>(address.as_ref())?
+        .into_iter()
+        .enumerate()
+        .map(|(index, (action, storage))| HistoricalRevision {
+            revision: (&action).into(),
+            version: index + 1,
+            entry: storage.entry(),
+        })
+        .collect();
+
+    edit_log(history, excluded_fields)
+}
+
+/// Summarise `history` (ordered oldest first) as the changes made by each revision.
+/// Revisions which changed no fields are retained, so that every edit remains attributed.
+///
+pub fn edit_log<T>(history: Vec<HistoricalRevision<T>>, excluded_fields: &[&str]) -> RecordAPIResult<Vec<RevisionEdit>>
+    where T: Serialize,
+{
+    let mut previous: Option<T> = None;
+    let mut edits = vec![];
+
+    for HistoricalRevision { revision, entry, .. } in history {
+        edits.push(RevisionEdit {
+            revision,
+            changes: diff_against_previous(previous.as_ref(), &entry, excluded_fields)?,
+        });
+        previous = Some(entry);
+    }
+
+    Ok(edits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn revision(n: u8, day: u32) -> HistoricalRevision<u8> {
+        HistoricalRevision {
+            revision: RevisionMeta {
+                id: ActionHash::from_raw_36(vec![n; 36]),
+                time: Utc.ymd(2026, 10, day).and_hms(0, 0, 0),
+                agent_pub_key: AgentPubKey::from_raw_36(vec![0; 36]),
+            },
+            version: n as usize,
+            entry: n,
+        }
+    }
+
+    fn history() -> Vec<HistoricalRevision<u8>> {
+        vec![revision(1, 1), revision(2, 5), revision(3, 10), revision(4, 15)]
+    }
+
+    fn window(since: Option<DateTime<Utc>>, until: Option<DateTime<Utc>>, limit: Option<usize>) -> RevisionHistory<u8> {
+        let revisions = history().into_iter().map(|r| Ok((r.revision, r.entry)));
+        window_revisions(revisions, since, until, limit, Ok).unwrap()
+    }
+
+    fn day(d: u32) -> Option<DateTime<Utc>> {
+        Some(Utc.ymd(2026, 10, d).and_hms(0, 0, 0))
+    }
+
+    fn entries(result: &RevisionHistory<u8>) -> Vec<u8> {
+        result.revisions.iter().map(|r| r.entry).collect()
+    }
+
+    #[test]
+    fn test_unbounded_window() {
+        let result = window(None, None, None);
+        assert_eq!(entries(&result), vec![1, 2, 3, 4]);
+        assert!(result.preceding_revision.is_none());
+        assert!(!result.has_more);
+    }
+
+    #[test]
+    fn test_bounded_window() {
+        let result = window(day(5), day(15), None);
+        assert_eq!(entries(&result), vec![2, 3], "since is inclusive and until exclusive");
+        assert_eq!(result.preceding_revision.map(|r| r.id), Some(ActionHash::from_raw_36(vec![1; 36])));
+        assert_eq!(result.preceding_version, Some(1));
+        assert_eq!(result.revisions.iter().map(|r| r.version).collect::<Vec<usize>>(), vec![2, 3], "versions count from the creating revision");
+    }
+
+    #[test]
+    fn test_empty_window_references_preceding_revision() {
+        let result = window(day(11), day(14), None);
+        assert!(result.revisions.is_empty());
+        assert_eq!(result.preceding_revision.map(|r| r.id), Some(ActionHash::from_raw_36(vec![3; 36])));
+    }
+
+    #[test]
+    fn test_edit_log_diffs_consecutive_revisions() {
+        let edits = edit_log(vec![revision(1, 1), revision(1, 5), revision(4, 10)], &[]).unwrap();
+        assert_eq!(edits.len(), 3, "every revision attributed");
+        assert_eq!(edits[0].changes.len(), 1, "initial revision reports its value");
+        assert_eq!(edits[0].changes[0].old_value, serde_json::Value::Null);
+        assert!(edits[1].changes.is_empty(), "unchanged revision reports no changes");
+        assert_eq!(edits[2].changes[0].old_value, serde_json::json!(1));
+        assert_eq!(edits[2].changes[0].new_value, serde_json::json!(4));
+        assert_eq!(edits[2].revision.id, ActionHash::from_raw_36(vec![4; 36]));
+    }
+
+    #[test]
+    fn test_limited_window() {
+        let result = window(day(2), None, Some(2));
+        assert_eq!(entries(&result), vec![2, 3]);
+        assert!(result.has_more);
+
+        let result = window(day(2), None, Some(3));
+        assert_eq!(entries(&result), vec![2, 3, 4]);
+        assert!(!result.has_more, "not flagged where the limit is exactly met");
+    }
+}
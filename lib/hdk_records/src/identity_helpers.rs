@@ -30,17 +30,192 @@ use hdk_semantic_indexes_zome_rpc::{
     AppendAddress,
 };
 
+//------------------------------[ SCHEMES ]-------------------------------------
+
+/// Root `Path` component for identities constructed under versioned schemes
+pub const IDENTITY_PATH_ROOT: &str = "identity";
+
+/// Versions of the scheme used to derive an identity address from a record identifier.
+///
+/// New schemes must only ever be appended, since existing data remains addressed by
+/// the scheme it was written under.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentityScheme {
+    /// Identity is the initial `EntryHash` of the record, unmodified.
+    V1,
+    /// Identity is a `Path` scoped by scheme version and the `DnaHash` of the originating cell,
+    /// so that identities of records from different cells cannot collide.
+    V2,
+}
+
+impl Default for IdentityScheme {
+    fn default() -> Self {
+        Self::V1
+    }
+}
+
+/// Configures which `IdentityScheme`s are in use for a DNA.
+///
+/// `current` is used for all newly written identities. `fallbacks` are attempted in order
+/// when reading identities which have not been written under the `current` scheme.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct IdentitySchemeConfig {
+    #[serde(default)]
+    pub current: IdentityScheme,
+    #[serde(default)]
+    pub fallbacks: Vec<IdentityScheme>,
+}
+
+impl IdentitySchemeConfig {
+    /// All schemes to attempt when resolving an identity, in order of preference and without duplicates
+    pub fn read_order(&self) -> Vec<IdentityScheme> {
+        let mut schemes = vec![self.current];
+        for scheme in self.fallbacks.iter() {
+            if !schemes.contains(scheme) {
+                schemes.push(*scheme);
+            }
+        }
+        schemes
+    }
+}
+
+// DNA properties wrapper for reading `IdentitySchemeConfig` under the `identity_schemes` key
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone)]
+struct IdentitySchemeProperties {
+    #[serde(default)]
+    pub identity_schemes: IdentitySchemeConfig,
+}
+
+/// Read the `IdentitySchemeConfig` for the current DNA from its `identity_schemes` property.
+/// If not configured, only the original `IdentityScheme::V1` is used.
+///
+pub fn read_identity_scheme_config() -> RecordAPIResult<IdentitySchemeConfig> {
+    let props: Result<IdentitySchemeProperties, SerializedBytesError> = dna_info()?.modifiers.properties.try_into();
+    Ok(props.map(|p| p.identity_schemes).unwrap_or_default())
+}
+
+/// Construct the identity `Path` for a record under the given `scheme`, or `None` if the
+/// scheme does not use `Path`-based identities.
+///
+pub fn identity_path_for_scheme<A>(
+    base_address: &A,
+    scheme: IdentityScheme,
+) -> Option<Path>
+    where A: DnaAddressable<EntryHash>,
+{
+    match scheme {
+        IdentityScheme::V1 => None,
+        IdentityScheme::V2 => {
+            let dna: &DnaHash = base_address.as_ref();
+            let hash: &EntryHash = base_address.as_ref();
+            Some(Path::from(vec![
+                Component::from(IDENTITY_PATH_ROOT),
+                Component::from("v2"),
+                Component::from(dna.get_raw_39().to_vec()),
+                Component::from(hash.get_raw_39().to_vec()),
+            ]))
+        },
+    }
+}
+
+/// Decode the record identifier from an identity `Path` previously constructed via `identity_path_for_scheme`.
+///
+pub fn decode_identity_path<A>(
+    path: &Path,
+) -> RecordAPIResult<A>
+    where A: DnaAddressable<EntryHash>,
+{
+    let components: &Vec<Component> = path.as_ref();
+    match components.as_slice() {
+        [root, version, dna, hash] if *root == Component::from(IDENTITY_PATH_ROOT) && *version == Component::from("v2") => {
+            Ok(A::new(
+                DnaHash::from_raw_39(dna.as_ref().to_vec())
+                    .map_err(|e| DataIntegrityError::BadIdentityPath(e.to_string()))?,
+                EntryHash::from_raw_39(hash.as_ref().to_vec())
+                    .map_err(|e| DataIntegrityError::BadIdentityPath(e.to_string()))?,
+            ))
+        },
+        _ => Err(DataIntegrityError::BadIdentityPath(format!("{:?}", path))),
+    }
+}
+
 //--------------------------------[ READ ]--------------------------------------
 
 /// Determine the underlying `EntryHash` for a given `base_address` identifier, without querying the DHT.
 ///
+/// Records are always stored and read by their initial `EntryHash`, so this always uses `IdentityScheme::V1`.
+/// Indexes referencing records may be keyed by other schemes- @see `calculate_identity_address_for_scheme`.
+///
 pub fn calculate_identity_address<A>(
     base_address: &A,
 ) -> RecordAPIResult<EntryHash>
     where A: DnaAddressable<EntryHash>,
 {
-    let base_hash: &EntryHash = base_address.as_ref();
-    Ok(base_hash.to_owned())
+    calculate_identity_address_for_scheme(base_address, IdentityScheme::V1)
+}
+
+/// As `calculate_identity_address`, but errors with `DataIntegrityError::ForeignIdentityAddress`
+/// if `base_address` does not reference a record in the local DNA.
+///
+/// Use this wherever the resulting identity is expected to resolve within the current cell,
+/// since identities calculated for foreign addresses are valid hashes which point nowhere locally.
+///
+pub fn calculate_identity_address_checked<A>(
+    base_address: &A,
+) -> RecordAPIResult<EntryHash>
+    where A: DnaAddressable<EntryHash>,
+{
+    assert_local_address(base_address, &dna_info()?.hash)?;
+    calculate_identity_address(base_address)
+}
+
+/// Ensure that `base_address` references a record in the DNA identified by `local_dna`.
+///
+pub fn assert_local_address<A>(
+    base_address: &A,
+    local_dna: &DnaHash,
+) -> RecordAPIResult<()>
+    where A: DnaAddressable<EntryHash>,
+{
+    let dna: &DnaHash = base_address.as_ref();
+    if dna != local_dna {
+        return Err(DataIntegrityError::ForeignIdentityAddress(dna.to_owned(), local_dna.to_owned()));
+    }
+    Ok(())
+}
+
+/// Determine the identity `EntryHash` for a given `base_address` under a specific `IdentityScheme`,
+/// without querying the DHT.
+///
+pub fn calculate_identity_address_for_scheme<A>(
+    base_address: &A,
+    scheme: IdentityScheme,
+) -> RecordAPIResult<EntryHash>
+    where A: DnaAddressable<EntryHash>,
+{
+    match identity_path_for_scheme(base_address, scheme) {
+        Some(path) => Ok(path.path_entry_hash()?),
+        None => {
+            let base_hash: &EntryHash = base_address.as_ref();
+            Ok(base_hash.to_owned())
+        },
+    }
+}
+
+/// Determine all candidate identity addresses for `base_address` under the given config,
+/// in the order they should be attempted when reading.
+///
+pub fn identity_address_candidates<A>(
+    base_address: &A,
+    config: &IdentitySchemeConfig,
+) -> RecordAPIResult<Vec<(IdentityScheme, EntryHash)>>
+    where A: DnaAddressable<EntryHash>,
+{
+    config.read_order().into_iter()
+        .map(|scheme| Ok((scheme, calculate_identity_address_for_scheme(base_address, scheme)?)))
+        .collect()
 }
 
 /// Given an identity `EntryHash` (ie. the result of `calculate_identity_address`),
@@ -83,11 +258,84 @@ pub fn create_entry_identity<A, S, F, C>(
 
     // request addition to index in companion zome
     // :TODO: move this to postcommit hook of coordinator zome, @see #264
-    Ok(call_local_zome_method(
+    match call_local_zome_method(
         zome_name_from_config, append_fn_name,
         AppendAddress {
             address: initial_address.to_owned(),
             timestamp: now_stamp,
         },
-    ).map_err(|e| { DataIntegrityError::LocalIndexNotConfigured(entry_def_id.to_string(), e.to_string()) })?)
+    ) {
+        Ok(appended) => Ok(appended),
+        // read-only follower index zomes deliberately refuse writes, the record is simply not indexed
+        Err(e) if DataIntegrityError::is_read_only_index(&e) => Ok(false),
+        Err(e) => Err(DataIntegrityError::LocalIndexNotConfigured(entry_def_id.to_string(), e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hdk_uuid_types::addressable_identifier;
+
+    addressable_identifier!(TestAddress => EntryHash);
+
+    fn test_address() -> TestAddress {
+        TestAddress(DnaHash::from_raw_36(vec![0xdb; 36]), EntryHash::from_raw_36(vec![0xab; 36]))
+    }
+
+    #[test]
+    fn test_v1_identity_roundtrip() {
+        let address = test_address();
+        let identity = calculate_identity_address_for_scheme(&address, IdentityScheme::V1).unwrap();
+        let hash: &EntryHash = address.as_ref();
+
+        assert_eq!(identity, *hash, "V1 identities should be the record EntryHash");
+        assert_eq!(identity, calculate_identity_address(&address).unwrap(), "record identities should use V1 scheme");
+        assert!(identity_path_for_scheme(&address, IdentityScheme::V1).is_none(), "V1 identities are not Path-based");
+    }
+
+    #[test]
+    fn test_v2_identity_roundtrip() {
+        let address = test_address();
+        let path = identity_path_for_scheme(&address, IdentityScheme::V2).unwrap();
+        let decoded: TestAddress = decode_identity_path(&path).unwrap();
+
+        assert_eq!(decoded, address, "V2 identity Path should decode to original identifier");
+        assert_ne!(
+            calculate_identity_address_for_scheme(&address, IdentityScheme::V2).unwrap(),
+            calculate_identity_address_for_scheme(&address, IdentityScheme::V1).unwrap(),
+            "schemes should produce distinct identities",
+        );
+    }
+
+    #[test]
+    fn test_local_address_check() {
+        let address = test_address();
+        let local_dna = DnaHash::from_raw_36(vec![0xdb; 36]);
+        let foreign_dna = DnaHash::from_raw_36(vec![0xdc; 36]);
+
+        assert!(assert_local_address(&address, &local_dna).is_ok(), "local addresses should pass");
+        assert!(
+            matches!(assert_local_address(&address, &foreign_dna), Err(DataIntegrityError::ForeignIdentityAddress(from, local)) if from == local_dna && local == foreign_dna),
+            "foreign addresses should be rejected",
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_foreign_paths() {
+        let decoded: RecordAPIResult<TestAddress> = decode_identity_path(&Path::from("some.other.path"));
+        assert!(matches!(decoded, Err(DataIntegrityError::BadIdentityPath(_))), "non-identity Paths should be rejected");
+    }
+
+    #[test]
+    fn test_scheme_read_order() {
+        let config = IdentitySchemeConfig { current: IdentityScheme::V2, fallbacks: vec![IdentityScheme::V2, IdentityScheme::V1] };
+        assert_eq!(config.read_order(), vec![IdentityScheme::V2, IdentityScheme::V1], "current scheme read first, without duplicates");
+
+        let candidates = identity_address_candidates(&test_address(), &config).unwrap();
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[1], (IdentityScheme::V1, calculate_identity_address(&test_address()).unwrap()));
+
+        assert_eq!(IdentitySchemeConfig::default().read_order(), vec![IdentityScheme::V1], "unconfigured DNAs use original scheme only");
+    }
 }
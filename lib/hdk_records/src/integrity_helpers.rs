@@ -0,0 +1,170 @@
+/**
+ * Helpers for checking the consistency of records replicated between two DNAs.
+ *
+ * The DNA holding the "origin" copy of a record walks its records in batches and asks the
+ * DNA holding the other copy to report on the status of each. Discrepancies found are
+ * accumulated into an `IntegrityReport`, the lists of which can be passed directly to the
+ * repair APIs of the appropriate DNA.
+ *
+ * @package hdk_records
+ * @since   2026-10-16
+ */
+use hdk::prelude::*;
+use hdk_uuid_types::DnaAddressable;
+
+/// Parameters for checking the records of `record_type` against their copies in another DNA.
+/// `cursor` is the `next_cursor` returned by a previous invocation.
+///
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityCheckParams {
+    pub record_type: String,
+    #[serde(default)]
+    pub cursor: Option<EntryHash>,
+    #[serde(default)]
+    pub batch_size: Option<usize>,
+}
+
+/// Status of the copy of a single record held by a remote DNA, addressed in the remote DNA.
+///
+/// `index_exists` is set where the record is linked from the index of the record it references.
+/// `related` lists the other records linked from that index which reference the origin DNA.
+///
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReferenceStatus<A> {
+    pub address: A,
+    pub record_exists: bool,
+    pub index_exists: bool,
+    pub related: Vec<A>,
+}
+
+/// Discrepancies found by a single invocation of a batched integrity check.
+///
+/// `missing_remote_record` is addressed in the origin DNA and lists records to re-replicate.
+/// `missing_remote_index` and `remote_has_extra` are addressed in the remote DNA, and list
+/// records to resync the indexes of and records unknown to the origin DNA respectively.
+/// `failures` lists records which could not be checked, addressed in the origin DNA.
+/// `next_cursor` is `None` once every record has been checked.
+///
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityReport<A> {
+    pub record_type: String,
+    pub checked: u32,
+    pub missing_remote_record: Vec<A>,
+    pub missing_remote_index: Vec<A>,
+    pub remote_has_extra: Vec<A>,
+    pub failures: Vec<IntegrityFailure<A>>,
+    pub next_cursor: Option<EntryHash>,
+}
+
+/// A record which could not be checked, because it could not be read in the origin DNA
+/// or the remote DNA could not be queried.
+///
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IntegrityFailure<A> {
+    pub address: A,
+    pub error: String,
+}
+
+impl<A> IntegrityReport<A>
+    where A: DnaAddressable<EntryHash>,
+{
+    pub fn new<S: AsRef<str>>(record_type: S, next_cursor: Option<EntryHash>) -> Self {
+        IntegrityReport {
+            record_type: record_type.as_ref().to_string(),
+            checked: 0,
+            missing_remote_record: vec![],
+            missing_remote_index: vec![],
+            remote_has_extra: vec![],
+            failures: vec![],
+            next_cursor,
+        }
+    }
+
+    /// Record a failure to check the record at `address` in the origin DNA.
+    ///
+    pub fn record_failure<E: std::fmt::Display>(&mut self, address: A, error: E) {
+        self.checked += 1;
+        self.failures.push(IntegrityFailure { address, error: error.to_string() });
+    }
+
+    /// Accumulate the `statuses` reported by a remote DNA into this report.
+    ///
+    /// Records are matched between DNAs by identity hash and addressed in `local_dna` where
+    /// missing remotely. `exists_locally` determines whether a related record also exists in
+    /// the origin DNA; those which do not are reported once as extra.
+    ///
+    pub fn record_statuses<F>(&mut self, statuses: Vec<ReferenceStatus<A>>, local_dna: &DnaHash, exists_locally: F)
+        where F: Fn(&A) -> bool,
+    {
+        for status in statuses {
+            self.checked += 1;
+            if !status.record_exists {
+                let identity_hash: &EntryHash = status.address.as_ref();
+                self.missing_remote_record.push(A::new(local_dna.to_owned(), identity_hash.to_owned()));
+            } else if !status.index_exists {
+                self.missing_remote_index.push(status.address);
+            }
+            for related in status.related {
+                let identity_hash: &EntryHash = related.as_ref();
+                let local_address = A::new(local_dna.to_owned(), identity_hash.to_owned());
+                if !self.remote_has_extra.contains(&related) && !exists_locally(&local_address) {
+                    self.remote_has_extra.push(related);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hdk_uuid_types::addressable_identifier;
+
+    addressable_identifier!(TestAddress => EntryHash);
+
+    fn dna(n: u8) -> DnaHash {
+        DnaHash::from_raw_36(vec![n; 36])
+    }
+
+    fn address(dna_id: u8, n: u8) -> TestAddress {
+        TestAddress::new(dna(dna_id), EntryHash::from_raw_36(vec![n; 36]))
+    }
+
+    fn status(n: u8, record_exists: bool, index_exists: bool, related: Vec<u8>) -> ReferenceStatus<TestAddress> {
+        ReferenceStatus {
+            address: address(2, n),
+            record_exists,
+            index_exists,
+            related: related.into_iter().map(|r| address(2, r)).collect(),
+        }
+    }
+
+    #[test]
+    fn test_discrepancies_classified() {
+        let mut report = IntegrityReport::new("record", None);
+        report.record_statuses(vec![
+            status(1, true, true, vec![2, 9]),
+            status(2, false, false, vec![]),
+            status(3, true, false, vec![1, 9]),
+        ], &dna(1), |a| a != &address(1, 9));
+
+        assert_eq!(report.checked, 3);
+        assert_eq!(report.missing_remote_record, vec![address(1, 2)], "missing records addressed in the local DNA");
+        assert_eq!(report.missing_remote_index, vec![address(2, 3)], "missing indexes addressed in the remote DNA");
+        assert_eq!(report.remote_has_extra, vec![address(2, 9)], "extra records reported once");
+    }
+
+    #[test]
+    fn test_failures_counted_as_checked() {
+        let mut report = IntegrityReport::new("record", None);
+        report.record_failure(address(1, 1), "remote DNA unreachable");
+        report.record_statuses(vec![status(2, true, true, vec![])], &dna(1), |_| true);
+
+        assert_eq!(report.checked, 2);
+        assert_eq!(report.failures, vec![IntegrityFailure { address: address(1, 1), error: "remote DNA unreachable".to_string() }]);
+    }
+}
@@ -18,7 +18,7 @@ pub use hdk_uuid_types::DnaAddressable;
 // re-expose MaybeUndefined module
 pub use serde_maybe_undefined as maybe_undefined;
 pub use serde_maybe_undefined::MaybeUndefined as MaybeUndefined;
-pub use hdk_rpc_errors::{ OtherCellResult, CrossCellError };
+pub use hdk_rpc_errors::{ OtherCellResult, CrossCellError, ErrorCode, ErrorEnvelope, ErrorCoded };
 
 mod entry_helpers;
 mod identity_helpers;
@@ -26,6 +26,21 @@ mod record_helpers;
 mod anchored_record_helpers;
 mod rpc_helpers;
 mod metadata_helpers;
+mod patch_helpers;
+mod field_index_helpers;
+mod dependency_helpers;
+mod diff_helpers;
+mod bundle_helpers;
+mod name_index_helpers;
+mod log_helpers;
+mod validation_helpers;
+mod slug_index_helpers;
+mod history_helpers;
+mod integrity_helpers;
+mod serde_alias_helpers;
+mod projection_helpers;
+mod update_semantics_helpers;
+mod rate_limit_helpers;
 
 // API interfaces
 
@@ -39,10 +54,37 @@ pub mod records { pub use crate::record_helpers::*; }
 pub mod records_anchored { pub use crate::anchored_record_helpers::*; }
 pub mod rpc { pub use crate::rpc_helpers::*; }
 pub mod metadata { pub use crate::metadata_helpers::*; }
+pub mod patch { pub use crate::patch_helpers::*; }
+pub mod field_indexes { pub use crate::field_index_helpers::*; }
+pub mod dependencies { pub use crate::dependency_helpers::*; }
+pub mod diffs { pub use crate::diff_helpers::*; }
+pub mod bundles { pub use crate::bundle_helpers::*; }
+pub mod name_indexes { pub use crate::name_index_helpers::*; }
+pub mod logging { pub use crate::log_helpers::*; }
+pub mod validation { pub use crate::validation_helpers::*; }
+pub mod slugs { pub use crate::slug_index_helpers::*; }
+pub mod history { pub use crate::history_helpers::*; }
+pub mod integrity { pub use crate::integrity_helpers::*; }
+pub mod serde_aliases { pub use crate::serde_alias_helpers::*; }
+pub mod projections { pub use crate::projection_helpers::*; }
+pub mod update_semantics { pub use crate::update_semantics_helpers::*; }
+pub mod rate_limits { pub use crate::rate_limit_helpers::*; }
 
 // externally-facing structs
 
 pub use metadata_helpers::{ RevisionMeta, RecordMeta };
+pub use patch_helpers::{ PatchOp, PatchOpError, PatchParams };
+pub use validation_helpers::ValidationHook;
+pub use slug_index_helpers::{ SlugParams, SlugResolution };
+pub use dependency_helpers::{ DeleteParams, DeletionReport, DependentRecords, DependentRecordResult };
+pub use diff_helpers::{ FieldChange, RevisionDiffParams };
+pub use history_helpers::{ RevisionHistoryParams, RevisionHistory, HistoricalRevision, RevisionEdit };
+pub use integrity_helpers::{ IntegrityCheckParams, IntegrityReport, IntegrityFailure, ReferenceStatus };
+pub use bundle_helpers::RecordBundle;
+pub use name_index_helpers::NameIndexParams;
+pub use entry_helpers::NonceMode;
+pub use log_helpers::LogLevel;
+pub use projection_helpers::{ RecordRole, RecordProjection };
 
 // :TODO: these error types may just be duplicating enums from the HDK,
 // revisit this once result handling & serialisation have stabilised.
@@ -81,21 +123,229 @@ pub enum DataIntegrityError {
     RemoteIndexingError(String),
     #[error("No index found at address {0}")]
     IndexNotFound(EntryHash),
+    #[error("Revisions {0} and {1} belong to different records")]
+    RevisionsOfDifferentRecords(ActionHash, ActionHash),
+    #[error("Field {0} cannot be changed once a record is created")]
+    FieldImmutable(String),
+    #[error("Malformed identity Path: {0}")]
+    BadIdentityPath(String),
+    #[error("Cannot calculate local identity for address from foreign DNA {0}, local DNA is {1}")]
+    ForeignIdentityAddress(DnaHash, DnaHash),
     #[error("DNA misconfiguration detected- local index zome request error for '{0}': {1}")]
     LocalIndexNotConfigured(String, String),
+    #[error("Patch rejected, no operations were applied: {}", .0.iter().map(|e| e.to_string()).collect::<Vec<String>>().join("; "))]
+    PatchRejected(Vec<PatchOpError>),
+    #[error("Record has dependents and cannot be deleted without cascade: {}", .0.iter().map(|d| d.to_string()).collect::<Vec<String>>().join("; "))]
+    DependentRecordsExist(Vec<DependentRecords>),
+    #[error("Cascading delete failed, record not deleted. Removed: [{}]. Failed: [{}]",
+        .0.iter().map(|d| d.to_string()).collect::<Vec<String>>().join(", "),
+        .1.iter().map(|d| d.to_string()).collect::<Vec<String>>().join(", ")
+    )]
+    CascadeDeleteFailed(Vec<DependentRecordResult>, Vec<DependentRecordResult>),
+    #[error("Dependent records in DNA {0} could not be checked, record not deleted: {1}")]
+    DependentsUnverifiable(DnaHash, String),
+    #[error("The current agent is not a network admin")]
+    NotNetworkAdmin,
+    #[error("Queries in this network are restricted to agents with an associated Agent profile")]
+    NotNetworkMember,
+    #[error("Only the author of a record may perform this operation")]
+    NotRecordAuthor,
+    #[error("This operation may only be called from the agent's own cell")]
+    NotLocalCaller,
+    #[error("Network settings have already been created")]
+    NetworkSettingsExist,
+    #[error("Network settings require quantities to specify a unit, but {0} has no unit")]
+    QuantityUnitRequired(String),
+    #[error("Network settings require units to be registered, but the unit {1} of {0} was not found")]
+    QuantityUnitNotRegistered(String, String),
+    #[error("Network settings require quantities to use the units of their ResourceSpecification, but {0} is measured in {1} rather than {2}")]
+    QuantityUnitMismatch(String, String, String),
+    #[error("Update was made against an outdated revision, the latest revision is {0}")]
+    StaleRevision(ActionHash, serde_json::Value),
+    #[error("Record was modified in the remote DNA since it was last synchronised, the versions must be merged")]
+    RemoteRevisionConflict(serde_json::Value, serde_json::Value),
+    #[error("Entry of {0} bytes exceeds the maximum entry size of {1} bytes")]
+    EntryTooLarge(usize, usize),
+    #[error("A record with external reference '{0}' already exists")]
+    ExternalReferenceExists(String),
+    #[error("Unknown action '{0}'")]
+    UnknownAction(String),
+    #[error("A record cannot be merged into itself")]
+    MergeIntoSelf,
+    #[error("Reciprocal commitments of an exchange must swap provider and receiver")]
+    ExchangeNotReciprocal,
+    #[error("An agent of type '{1}' cannot be a member of an agent of type '{0}'")]
+    InvalidMembership(String, String),
+    #[error("Invalid composition: {0}")]
+    InvalidComposition(String),
+    #[error("Index was built under schema version {0} but version {1} is expected; the index must be rebuilt")]
+    IndexSchemaMismatch(u8, u8),
     #[error("Mismatching units in arithmetic operation. Attempting to add or subtract {0:?} with {1:?}")]
     MismatchingUnits(Option<String>, Option<String>),
+    #[error("Validation failed ({0}): {1}")]
+    ValidationHookFailed(String, String),
+    #[error("Invalid slug '{0}': slugs may contain only lowercase letters, digits and single hyphens between them")]
+    InvalidSlug(String),
+    #[error("The slug '{0}' is already in use")]
+    SlugTaken(String),
+    #[error("Record type '{0}' is not supported by this operation")]
+    UnsupportedRecordType(String),
+    #[error("Entry {1} at position {0} could not be resolved: {2}")]
+    UnresolvedBatchEntry(usize, EntryHash, Box<DataIntegrityError>),
+    #[error("Unknown time index '{name}', known time indexes are: {}", .known.join(", "))]
+    UnknownTimeIndex { name: String, known: Vec<String> },
+    #[error("Index zome '{0}' is a read-only follower and does not accept index writes")]
+    ReadOnlyIndex(String),
+    #[error("Attachment of {0} bytes exceeds the maximum attachment size of {1} bytes")]
+    AttachmentTooLarge(u64, u64),
+    #[error("Attachments must contain at least one byte of data")]
+    EmptyAttachment,
+    #[error("No attachment found with hash {0}")]
+    AttachmentNotFound(EntryHash),
+    #[error("Field {0} is required")]
+    FieldRequired(String),
+    #[error("Cursor '{0}' does not identify a result of this query")]
+    UnknownCursor(String),
+    #[error("{0}")]
+    CreationRateExceeded(String),
+    #[error("{0}; records created in other DNAs before the failure could not be removed: {}", .1.join(", "))]
+    RollbackIncomplete(Box<DataIntegrityError>, Vec<String>),
 }
 
 pub type RecordAPIResult<T> = Result<T, DataIntegrityError>;
 
+impl DataIntegrityError {
+    /// Reject an update made against an outdated revision, returning the `current` state of the record.
+    ///
+    pub fn stale_revision<T: Serialize>(latest_revision: ActionHash, current: &T) -> Self {
+        DataIntegrityError::StaleRevision(latest_revision, serde_json::to_value(current).unwrap_or_default())
+    }
+
+    /// Determine whether an error from a replicated update was caused by the remote copy of the
+    /// record having been modified. If so, returns a `RemoteRevisionConflict` carrying both the
+    /// `local` version and the remote version for merging.
+    ///
+    pub fn remote_revision_conflict<T: Serialize>(error: &CrossCellError, local: &T) -> Option<Self> {
+        let envelope = error.error_envelope();
+        match (envelope.code, envelope.details.get("current")) {
+            (ErrorCode::Conflict, Some(remote)) => Some(DataIntegrityError::RemoteRevisionConflict(
+                serde_json::to_value(local).unwrap_or_default(),
+                remote.to_owned(),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Convert an error from a replicated update into the error failing the local update. This is
+    /// a `RemoteRevisionConflict` where the remote copy was modified, or else the remote error itself.
+    ///
+    pub fn replicated_update_failed<T: Serialize>(error: CrossCellError, local: &T) -> Self {
+        Self::remote_revision_conflict(&error, local).unwrap_or_else(|| error.into())
+    }
+
+    /// Determine whether an error from an index update was caused by the index zome being
+    /// a read-only follower. Such refusals are deliberate, and should not be treated as failures.
+    ///
+    pub fn is_read_only_index(error: &CrossCellError) -> bool {
+        error.error_envelope().code == ErrorCode::ReadOnly
+    }
+}
+
+// classify errors for output to clients
+
+impl ErrorCoded for DataIntegrityError {
+    fn error_envelope(&self) -> ErrorEnvelope {
+        match self {
+            DataIntegrityError::Serialization(_) => ErrorEnvelope::new(ErrorCode::Internal, self),
+            DataIntegrityError::Infallible(_) => ErrorEnvelope::new(ErrorCode::Internal, self),
+            DataIntegrityError::EntryError(_) => ErrorEnvelope::new(ErrorCode::Validation, self),
+            DataIntegrityError::Wasm(e) => e.error_envelope(),
+            DataIntegrityError::SemanticIndexingError(e) => e.error_envelope(),
+
+            DataIntegrityError::AgentAlreadyLinked => ErrorEnvelope::new(ErrorCode::Conflict, self),
+            DataIntegrityError::AgentNotLinked => ErrorEnvelope::new(ErrorCode::NotFound, self),
+            DataIntegrityError::EntryNotFound => ErrorEnvelope::new(ErrorCode::NotFound, self),
+            DataIntegrityError::EntryWrongType => ErrorEnvelope::new(ErrorCode::Validation, self),
+            DataIntegrityError::UpdateConflict(revisions) => ErrorEnvelope::new(ErrorCode::Conflict, self)
+                .with_details(serde_json::json!({ "revisions": revisions.iter().map(|r| r.to_string()).collect::<Vec<String>>() })),
+
+            // remote errors retain the classification assigned by the remote cell
+            DataIntegrityError::RemoteRequestError(msg) => ErrorEnvelope::parse(msg)
+                .unwrap_or_else(|| ErrorEnvelope::new(ErrorCode::RemoteUnreachable, self)),
+            DataIntegrityError::RemoteResponseFormatError(_) => ErrorEnvelope::new(ErrorCode::Internal, self),
+            DataIntegrityError::RemoteIndexingError(_) => ErrorEnvelope::new(ErrorCode::Internal, self),
+            DataIntegrityError::IndexNotFound(_) => ErrorEnvelope::new(ErrorCode::NotFound, self),
+            DataIntegrityError::RevisionsOfDifferentRecords(_, _) => ErrorEnvelope::new(ErrorCode::Validation, self),
+            DataIntegrityError::FieldImmutable(field) => ErrorEnvelope::new(ErrorCode::Validation, self).with_field(field),
+            DataIntegrityError::BadIdentityPath(_) => ErrorEnvelope::new(ErrorCode::Validation, self),
+            DataIntegrityError::ForeignIdentityAddress(_, _) => ErrorEnvelope::new(ErrorCode::Validation, self),
+            DataIntegrityError::LocalIndexNotConfigured(_, _) => ErrorEnvelope::new(ErrorCode::Misconfigured, self),
+            DataIntegrityError::PatchRejected(errors) => ErrorEnvelope::new(ErrorCode::Validation, self)
+                .with_details(serde_json::json!({ "errors": errors.iter().map(|e| e.to_string()).collect::<Vec<String>>() })),
+            DataIntegrityError::DependentRecordsExist(deps) => ErrorEnvelope::new(ErrorCode::Conflict, self)
+                .with_details(serde_json::json!({ "dependents": deps.iter().map(|d| d.to_string()).collect::<Vec<String>>() })),
+            DataIntegrityError::CascadeDeleteFailed(removed, failed) => ErrorEnvelope::new(ErrorCode::Conflict, self)
+                .with_details(serde_json::json!({
+                    "removed": removed.iter().map(|d| d.to_string()).collect::<Vec<String>>(),
+                    "failed": failed.iter().map(|d| d.to_string()).collect::<Vec<String>>(),
+                })),
+            DataIntegrityError::DependentsUnverifiable(_, _) => ErrorEnvelope::new(ErrorCode::RemoteUnreachable, self),
+            DataIntegrityError::NotNetworkAdmin => ErrorEnvelope::new(ErrorCode::Unauthorized, self),
+            DataIntegrityError::NotNetworkMember => ErrorEnvelope::new(ErrorCode::Unauthorized, self),
+            DataIntegrityError::NotRecordAuthor => ErrorEnvelope::new(ErrorCode::Unauthorized, self),
+            DataIntegrityError::NotLocalCaller => ErrorEnvelope::new(ErrorCode::Unauthorized, self),
+            DataIntegrityError::NetworkSettingsExist => ErrorEnvelope::new(ErrorCode::Conflict, self),
+            DataIntegrityError::QuantityUnitRequired(field) => ErrorEnvelope::new(ErrorCode::Validation, self).with_field(field),
+            DataIntegrityError::QuantityUnitNotRegistered(field, _) => ErrorEnvelope::new(ErrorCode::Validation, self).with_field(field),
+            DataIntegrityError::QuantityUnitMismatch(field, _, _) => ErrorEnvelope::new(ErrorCode::Validation, self).with_field(field),
+            DataIntegrityError::StaleRevision(latest, current) => ErrorEnvelope::new(ErrorCode::Conflict, self)
+                .with_details(serde_json::json!({ "latestRevision": latest.to_string(), "current": current })),
+            DataIntegrityError::RemoteRevisionConflict(local, remote) => ErrorEnvelope::new(ErrorCode::Conflict, self)
+                .with_details(serde_json::json!({ "local": local, "remote": remote })),
+            DataIntegrityError::EntryTooLarge(_, _) => ErrorEnvelope::new(ErrorCode::Validation, self),
+            DataIntegrityError::ExternalReferenceExists(_) => ErrorEnvelope::new(ErrorCode::Conflict, self).with_field("externalReference"),
+            DataIntegrityError::UnknownAction(_) => ErrorEnvelope::new(ErrorCode::Validation, self).with_field("action"),
+            DataIntegrityError::MergeIntoSelf => ErrorEnvelope::new(ErrorCode::Validation, self),
+            DataIntegrityError::ExchangeNotReciprocal => ErrorEnvelope::new(ErrorCode::Validation, self),
+            DataIntegrityError::InvalidMembership(_, _) => ErrorEnvelope::new(ErrorCode::Validation, self),
+            DataIntegrityError::InvalidComposition(_) => ErrorEnvelope::new(ErrorCode::Validation, self),
+            DataIntegrityError::IndexSchemaMismatch(_, _) => ErrorEnvelope::new(ErrorCode::Misconfigured, self),
+            DataIntegrityError::MismatchingUnits(_, _) => ErrorEnvelope::new(ErrorCode::Validation, self),
+            DataIntegrityError::ValidationHookFailed(_, _) => ErrorEnvelope::new(ErrorCode::Validation, self),
+            DataIntegrityError::InvalidSlug(_) => ErrorEnvelope::new(ErrorCode::Validation, self).with_field("slug"),
+            DataIntegrityError::SlugTaken(_) => ErrorEnvelope::new(ErrorCode::Conflict, self).with_field("slug"),
+            DataIntegrityError::UnsupportedRecordType(_) => ErrorEnvelope::new(ErrorCode::Validation, self).with_field("recordType"),
+            DataIntegrityError::UnresolvedBatchEntry(index, _, e) => ErrorEnvelope {
+                message: self.to_string(),
+                details: serde_json::json!({ "index": index }),
+                ..e.error_envelope()
+            },
+            DataIntegrityError::UnknownTimeIndex { known, .. } => ErrorEnvelope::new(ErrorCode::Misconfigured, self)
+                .with_details(serde_json::json!({ "known": known })),
+            DataIntegrityError::ReadOnlyIndex(_) => ErrorEnvelope::new(ErrorCode::ReadOnly, self),
+            DataIntegrityError::AttachmentTooLarge(_, _) => ErrorEnvelope::new(ErrorCode::Validation, self),
+            DataIntegrityError::EmptyAttachment => ErrorEnvelope::new(ErrorCode::Validation, self),
+            DataIntegrityError::AttachmentNotFound(_) => ErrorEnvelope::new(ErrorCode::NotFound, self).with_field("attachments"),
+            DataIntegrityError::FieldRequired(field) => ErrorEnvelope::new(ErrorCode::Validation, self).with_field(field),
+            DataIntegrityError::UnknownCursor(_) => ErrorEnvelope::new(ErrorCode::Validation, self).with_field("after"),
+            DataIntegrityError::CreationRateExceeded(_) => ErrorEnvelope::new(ErrorCode::Throttled, self),
+            // classified as the original failure, listing the records left behind
+            DataIntegrityError::RollbackIncomplete(e, orphaned) => ErrorEnvelope {
+                message: self.to_string(),
+                details: serde_json::json!({ "orphaned": orphaned }),
+                ..e.error_envelope()
+            },
+        }
+    }
+}
+
 // convert internal cell errors for passing to remote cell
 
 impl From<DataIntegrityError> for CrossCellError {
     fn from(e: DataIntegrityError) -> CrossCellError {
         match e {
             DataIntegrityError::IndexNotFound(entry) => CrossCellError::IndexNotFound(entry),
-            _ => CrossCellError::Internal(e.to_string()),
+            _ => CrossCellError::Internal(e.error_envelope().to_json()),
         }
     }
 }
@@ -104,13 +354,13 @@ impl From<DataIntegrityError> for CrossCellError {
 
 impl From<DataIntegrityError> for WasmError {
     fn from(e: DataIntegrityError) -> WasmError {
-        wasm_error!(WasmErrorInner::Guest(e.to_string()))
+        e.error_envelope().into_wasm_error()
     }
 }
 
 impl From<CrossCellError> for DataIntegrityError {
     fn from(e: CrossCellError) -> DataIntegrityError {
-        DataIntegrityError::RemoteRequestError(e.to_string())
+        DataIntegrityError::RemoteRequestError(e.error_envelope().to_json())
     }
 }
 
@@ -120,4 +370,179 @@ pub mod identifiers {
     // Holochain DHT storage type IDs
     pub const RECORD_INITIAL_ENTRY_LINK_TAG: &'static [u8] = b"initial_entry";
     pub const RECORD_IDENTITY_ANCHOR_LINK_TAG: &'static [u8] = b"id|";  // :WARNING: byte length is important here. @see anchored_record_helpers::read_entry_anchor_id
+    pub const RECORD_FIELD_VALUE_LINK_TAG: &'static [u8] = b"fv|";
+    pub const RECORD_NAME_INDEX_LINK_TAG: &'static [u8] = b"nm|";
+    pub const RECORD_SLUG_LINK_TAG: &'static [u8] = b"sl|";
+    pub const RECORD_SLUG_REDIRECT_LINK_TAG: &'static [u8] = b"sr|";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn envelope_json(e: DataIntegrityError) -> serde_json::Value {
+        serde_json::from_str(&e.error_envelope().to_json()).unwrap()
+    }
+
+    #[test]
+    fn test_error_envelope_codes() {
+        let cases = vec![
+            (DataIntegrityError::EntryNotFound, "NOT_FOUND"),
+            (DataIntegrityError::AgentNotLinked, "NOT_FOUND"),
+            (DataIntegrityError::IndexNotFound(EntryHash::from_raw_36(vec![0xdb; 36])), "NOT_FOUND"),
+            (DataIntegrityError::EntryWrongType, "VALIDATION"),
+            (DataIntegrityError::MismatchingUnits(Some("m".to_string()), None), "VALIDATION"),
+            (DataIntegrityError::ValidationHookFailed("note_length".to_string(), "note too long".to_string()), "VALIDATION"),
+            (DataIntegrityError::InvalidSlug("Bad Slug".to_string()), "VALIDATION"),
+            (DataIntegrityError::SlugTaken("alice".to_string()), "CONFLICT"),
+            (DataIntegrityError::UnsupportedRecordType("unicorn".to_string()), "VALIDATION"),
+            (DataIntegrityError::UnresolvedBatchEntry(2, EntryHash::from_raw_36(vec![0xdb; 36]), Box::new(DataIntegrityError::EntryNotFound)), "NOT_FOUND"),
+            (DataIntegrityError::UnknownTimeIndex { name: "process.finshed".to_string(), known: vec!["process.created".to_string()] }, "MISCONFIGURED"),
+            (DataIntegrityError::EntryTooLarge(5_000_000, 4_000_000), "VALIDATION"),
+            (DataIntegrityError::MergeIntoSelf, "VALIDATION"),
+            (DataIntegrityError::ExchangeNotReciprocal, "VALIDATION"),
+            (DataIntegrityError::InvalidMembership("Person".to_string(), "Organization".to_string()), "VALIDATION"),
+            (DataIntegrityError::InvalidComposition("a process cannot be composed within itself".to_string()), "VALIDATION"),
+            (DataIntegrityError::UpdateConflict(vec![]), "CONFLICT"),
+            (DataIntegrityError::NetworkSettingsExist, "CONFLICT"),
+            (DataIntegrityError::NotNetworkAdmin, "UNAUTHORIZED"),
+            (DataIntegrityError::NotNetworkMember, "UNAUTHORIZED"),
+            (DataIntegrityError::NotRecordAuthor, "UNAUTHORIZED"),
+            (DataIntegrityError::NotLocalCaller, "UNAUTHORIZED"),
+            (DataIntegrityError::CreationRateExceeded("limit exceeded".to_string()), "THROTTLED"),
+            (DataIntegrityError::RemoteRequestError("timeout".to_string()), "REMOTE_UNREACHABLE"),
+            (DataIntegrityError::DependentsUnverifiable(DnaHash::from_raw_36(vec![0xdb; 36]), "timeout".to_string()), "REMOTE_UNREACHABLE"),
+            (DataIntegrityError::LocalIndexNotConfigured("index".to_string(), "err".to_string()), "MISCONFIGURED"),
+            (DataIntegrityError::IndexSchemaMismatch(0, 1), "MISCONFIGURED"),
+            (DataIntegrityError::BadIdentityPath("x".to_string()), "VALIDATION"),
+            (DataIntegrityError::RevisionsOfDifferentRecords(ActionHash::from_raw_36(vec![0xdb; 36]), ActionHash::from_raw_36(vec![0xdc; 36])), "VALIDATION"),
+            (DataIntegrityError::ReadOnlyIndex("economic_event_index".to_string()), "READ_ONLY"),
+            (DataIntegrityError::AttachmentTooLarge(20_000_000, 10_485_760), "VALIDATION"),
+            (DataIntegrityError::EmptyAttachment, "VALIDATION"),
+        ];
+        for (err, code) in cases {
+            let message = err.to_string();
+            let field = match &err {
+                DataIntegrityError::InvalidSlug(_) | DataIntegrityError::SlugTaken(_) => serde_json::Value::String("slug".to_string()),
+                DataIntegrityError::UnsupportedRecordType(_) => serde_json::Value::String("recordType".to_string()),
+                _ => serde_json::Value::Null,
+            };
+            let json = envelope_json(err);
+            let obj = json.as_object().unwrap();
+            assert_eq!(obj.len(), 4);
+            assert_eq!(obj["code"], serde_json::Value::String(code.to_string()));
+            assert_eq!(obj["message"], serde_json::Value::String(message));
+            assert_eq!(obj["field"], field);
+        }
+    }
+
+    #[test]
+    fn test_error_envelope_field() {
+        let json = envelope_json(DataIntegrityError::FieldImmutable("agent_type".to_string()));
+        assert_eq!(json["code"], "VALIDATION");
+        assert_eq!(json["field"], "agent_type");
+
+        let json = envelope_json(DataIntegrityError::ExternalReferenceExists("scale-7:reading:1".to_string()));
+        assert_eq!(json["code"], "CONFLICT");
+        assert_eq!(json["field"], "externalReference");
+
+        let json = envelope_json(DataIntegrityError::FieldRequired("planningDna".to_string()));
+        assert_eq!(json["code"], "VALIDATION");
+        assert_eq!(json["field"], "planningDna");
+
+        let json = envelope_json(DataIntegrityError::UnknownCursor("uhCEk".to_string()));
+        assert_eq!(json["code"], "VALIDATION");
+        assert_eq!(json["field"], "after");
+
+        let json = envelope_json(DataIntegrityError::RollbackIncomplete(
+            Box::new(DataIntegrityError::FieldImmutable("agent_type".to_string())),
+            vec!["commitment-1".to_string()],
+        ));
+        assert_eq!(json["code"], "VALIDATION");
+        assert_eq!(json["field"], "agent_type");
+        assert_eq!(json["details"]["orphaned"][0], "commitment-1");
+
+        let json = envelope_json(DataIntegrityError::UnresolvedBatchEntry(
+            1,
+            EntryHash::from_raw_36(vec![0xdb; 36]),
+            Box::new(DataIntegrityError::RemoteRequestError("timeout".to_string())),
+        ));
+        assert_eq!(json["code"], "REMOTE_UNREACHABLE", "code of the underlying failure retained");
+        assert_eq!(json["details"]["index"], 1);
+
+        let json = envelope_json(DataIntegrityError::UnknownAction("teleport".to_string()));
+        assert_eq!(json["code"], "VALIDATION");
+        assert_eq!(json["field"], "action");
+
+        let json = envelope_json(DataIntegrityError::QuantityUnitRequired("resourceQuantity".to_string()));
+        assert_eq!(json["field"], "resourceQuantity");
+
+        let json = envelope_json(DataIntegrityError::QuantityUnitNotRegistered("effortQuantity".to_string(), "hours".to_string()));
+        assert_eq!(json["code"], "VALIDATION");
+        assert_eq!(json["field"], "effortQuantity");
+
+        let json = envelope_json(DataIntegrityError::QuantityUnitMismatch("resourceQuantity".to_string(), "kg".to_string(), "each".to_string()));
+        assert_eq!(json["code"], "VALIDATION");
+        assert_eq!(json["field"], "resourceQuantity");
+
+        let json = envelope_json(DataIntegrityError::AttachmentNotFound(EntryHash::from_raw_36(vec![0xdb; 36])));
+        assert_eq!(json["code"], "NOT_FOUND");
+        assert_eq!(json["field"], "attachments");
+    }
+
+    #[test]
+    fn test_error_envelope_remote_roundtrip() {
+        // errors relayed through another cell keep their original classification
+        let remote: CrossCellError = DataIntegrityError::EntryNotFound.into();
+        let local: DataIntegrityError = remote.into();
+        let envelope = local.error_envelope();
+        assert_eq!(envelope.code, ErrorCode::NotFound);
+        assert_eq!(envelope.message, DataIntegrityError::EntryNotFound.to_string());
+
+        let offline: DataIntegrityError = CrossCellError::NetworkError("offline".to_string()).into();
+        assert_eq!(offline.error_envelope().code, ErrorCode::RemoteUnreachable);
+    }
+
+    #[test]
+    fn test_remote_revision_conflict() {
+        let latest = ActionHash::from_raw_36(vec![0xdb; 36]);
+        let remote: CrossCellError = DataIntegrityError::stale_revision(latest, &serde_json::json!({ "note": "remote" })).into();
+        match DataIntegrityError::remote_revision_conflict(&remote, &serde_json::json!({ "note": "local" })) {
+            Some(DataIntegrityError::RemoteRevisionConflict(local, remote)) => {
+                assert_eq!(local["note"], "local");
+                assert_eq!(remote["note"], "remote");
+            },
+            _ => panic!("stale remote revisions must be reported as conflicts"),
+        }
+
+        let other: CrossCellError = DataIntegrityError::UpdateConflict(vec![]).into();
+        assert!(DataIntegrityError::remote_revision_conflict(&other, &()).is_none(), "other conflicts are not remote revision conflicts");
+    }
+
+    #[test]
+    fn test_replicated_update_failed() {
+        let latest = ActionHash::from_raw_36(vec![0xdb; 36]);
+        let stale: CrossCellError = DataIntegrityError::stale_revision(latest, &serde_json::json!({ "note": "remote" })).into();
+        assert!(matches!(DataIntegrityError::replicated_update_failed(stale, &()), DataIntegrityError::RemoteRevisionConflict(_, _)));
+
+        let unauthorized: CrossCellError = DataIntegrityError::NotRecordAuthor.into();
+        let err = DataIntegrityError::replicated_update_failed(unauthorized, &());
+        assert_eq!(err.error_envelope().code, ErrorCode::Unauthorized, "other remote errors are propagated with their own code");
+    }
+
+    #[test]
+    fn test_read_only_index() {
+        let remote: CrossCellError = DataIntegrityError::ReadOnlyIndex("economic_event_index".to_string()).into();
+        assert!(DataIntegrityError::is_read_only_index(&remote), "read-only refusals are recognised after crossing cells");
+
+        let other: CrossCellError = DataIntegrityError::EntryNotFound.into();
+        assert!(!DataIntegrityError::is_read_only_index(&other), "other errors are not read-only refusals");
+    }
+
+    #[test]
+    fn test_error_envelope_wasm_output() {
+        let err: WasmError = DataIntegrityError::EntryNotFound.into();
+        let envelope = ErrorEnvelope::from_wasm_error(&err).unwrap();
+        assert_eq!(envelope.code, ErrorCode::NotFound);
+    }
 }
@@ -0,0 +1,214 @@
+/**
+ * Lightweight logging facade with runtime-configurable verbosity.
+ *
+ * Messages are written via the `log_debug!`, `log_info!` and `log_warn!` macros, which
+ * accept structured key-value context ahead of the message and discard anything below
+ * the log level configured for the calling zome. Levels are configured per zome name
+ * under the `log_levels` DNA property, eg.
+ *
+ *     log_levels:
+ *       economic_event_index: debug
+ *
+ * Zomes which are not listed log at `LogLevel::Warn`. Zomes reading the network settings
+ * record additionally apply any level set there, @see `override_log_level`.
+ *
+ * @package hdk_records
+ * @since   2026-10-16
+ */
+use std::cell::Cell;
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use hdk::prelude::*;
+
+/// Verbosity of log output, in increasing order of severity
+///
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Off,
+}
+
+impl Default for LogLevel {
+    fn default() -> Self {
+        LogLevel::Warn
+    }
+}
+
+// DNA properties wrapper for reading configured levels under the `log_levels` key
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone)]
+struct LogLevelProperties {
+    #[serde(default)]
+    pub log_levels: BTreeMap<String, LogLevel>,
+}
+
+thread_local! {
+    static LOG_LEVEL_OVERRIDE: Cell<Option<LogLevel>> = Cell::new(None);
+    // level read from DNA properties, which cannot change for the lifetime of a zome call
+    static CONFIGURED_LOG_LEVEL: Cell<Option<LogLevel>> = Cell::new(None);
+}
+
+/// Override the configured log level for the remainder of the current zome call.
+/// Pass `None` to revert to the level configured in DNA properties.
+///
+pub fn override_log_level(level: Option<LogLevel>) {
+    LOG_LEVEL_OVERRIDE.with(|o| o.set(level));
+}
+
+/// Determine the log level in effect for the calling zome.
+/// DNA properties are only read on first use, subsequent lines within the same call reuse the result.
+///
+pub fn current_log_level() -> LogLevel {
+    if let Some(level) = LOG_LEVEL_OVERRIDE.with(|o| o.get()) {
+        return level;
+    }
+    if let Some(level) = CONFIGURED_LOG_LEVEL.with(|c| c.get()) {
+        return level;
+    }
+    let level = read_configured_log_level();
+    CONFIGURED_LOG_LEVEL.with(|c| c.set(Some(level)));
+    level
+}
+
+fn read_configured_log_level() -> LogLevel {
+    let configured = || -> ExternResult<Option<LogLevel>> {
+        let props: Result<LogLevelProperties, SerializedBytesError> = dna_info()?.modifiers.properties.try_into();
+        let zome_name = zome_info()?.name.0.to_string();
+        Ok(props.ok().and_then(|p| p.log_levels.get(&zome_name).cloned()))
+    };
+    configured().ok().flatten().unwrap_or_default()
+}
+
+/// Whether a message at `level` should be output where `configured` is in effect
+///
+pub fn log_enabled(configured: LogLevel, level: LogLevel) -> bool {
+    level != LogLevel::Off && level >= configured
+}
+
+/// Render a log line from its structured context and message, eg. `[index_sync] zome=x address=uhCEk…1a2b3c: message`
+///
+pub fn format_log_line(operation: &str, context: &[(&str, String)], message: String) -> String {
+    let fields: Vec<String> = context.iter().map(|(k, v)| format!(" {}={}", k, v)).collect();
+    format!("[{}]{}: {}", operation, fields.concat(), message)
+}
+
+/// Abbreviate a hash (or any other long identifier) for inclusion in log context
+///
+pub fn short_hash<H: Display>(hash: &H) -> String {
+    let full = hash.to_string();
+    let chars: Vec<char> = full.chars().collect();
+    if chars.len() <= 16 {
+        return full;
+    }
+    format!("{}…{}", chars[..6].iter().collect::<String>(), chars[chars.len() - 6..].iter().collect::<String>())
+}
+
+/// Output a log line at `level`, if enabled for the calling zome.
+/// `render` is only evaluated for lines which will be output.
+///
+/// Use via the `log_debug!`, `log_info!` & `log_warn!` macros rather than directly.
+///
+pub fn write_log<F>(level: LogLevel, render: F)
+    where F: FnOnce() -> String,
+{
+    if !log_enabled(current_log_level(), level) {
+        return;
+    }
+    match level {
+        LogLevel::Warn => warn!("{}", render()),
+        // levels are filtered here rather than by the conductor, so enabled lines are always written
+        _ => info!("{:?} {}", level, render()),
+    }
+}
+
+/// Log at `LogLevel::Debug`. Takes an operation name, optional `key = value` context and a format string.
+///
+///     log_debug!("sync_index", zome = zome_name, address = short_hash(&hash); "created {} links", count);
+///
+#[macro_export]
+macro_rules! log_debug {
+    ($($args:tt)+) => { $crate::__log_at!($crate::logging::LogLevel::Debug, $($args)+) };
+}
+
+/// Log at `LogLevel::Info`, @see `log_debug!`
+///
+#[macro_export]
+macro_rules! log_info {
+    ($($args:tt)+) => { $crate::__log_at!($crate::logging::LogLevel::Info, $($args)+) };
+}
+
+/// Log at `LogLevel::Warn`, @see `log_debug!`
+///
+#[macro_export]
+macro_rules! log_warn {
+    ($($args:tt)+) => { $crate::__log_at!($crate::logging::LogLevel::Warn, $($args)+) };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_at {
+    ($level:expr, $op:expr, $($key:ident = $value:expr),+ ; $($msg:tt)+) => {
+        $crate::logging::write_log($level, || $crate::logging::format_log_line(
+            $op,
+            &[$((stringify!($key), format!("{}", $value))),+],
+            format!($($msg)+),
+        ))
+    };
+    ($level:expr, $op:expr ; $($msg:tt)+) => {
+        $crate::logging::write_log($level, || $crate::logging::format_log_line($op, &[], format!($($msg)+)))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages_suppressed_below_level() {
+        assert!(!log_enabled(LogLevel::Warn, LogLevel::Debug));
+        assert!(!log_enabled(LogLevel::Warn, LogLevel::Info));
+        assert!(log_enabled(LogLevel::Warn, LogLevel::Warn));
+        assert!(log_enabled(LogLevel::Debug, LogLevel::Debug));
+        assert!(log_enabled(LogLevel::Debug, LogLevel::Warn));
+        assert!(!log_enabled(LogLevel::Off, LogLevel::Warn), "all output suppressed when disabled");
+    }
+
+    #[test]
+    fn test_level_override() {
+        override_log_level(Some(LogLevel::Debug));
+        assert_eq!(current_log_level(), LogLevel::Debug);
+        override_log_level(None);
+    }
+
+    #[test]
+    fn test_configured_level_cached() {
+        CONFIGURED_LOG_LEVEL.with(|c| c.set(Some(LogLevel::Info)));
+        assert_eq!(current_log_level(), LogLevel::Info, "properties not re-read once cached");
+        override_log_level(Some(LogLevel::Debug));
+        assert_eq!(current_log_level(), LogLevel::Debug, "overrides take precedence over the cached level");
+        override_log_level(None);
+        CONFIGURED_LOG_LEVEL.with(|c| c.set(None));
+    }
+
+    #[test]
+    fn test_format_log_line() {
+        let line = format_log_line("index_sync", &[("zome", "event_index".to_string()), ("links", 3.to_string())], "done".to_string());
+        assert_eq!(line, "[index_sync] zome=event_index links=3: done");
+        assert_eq!(format_log_line("op", &[], "msg".to_string()), "[op]: msg");
+    }
+
+    #[test]
+    fn test_short_hash() {
+        assert_eq!(short_hash(&"short"), "short");
+        assert_eq!(short_hash(&"uhCEkABCDEFGHIJKLMNOPQRSTUVWXYZ"), "uhCEkA…UVWXYZ");
+    }
+
+    #[test]
+    fn test_level_names() {
+        let level: LogLevel = serde_json::from_str("\"debug\"").unwrap();
+        assert_eq!(level, LogLevel::Debug);
+        assert_eq!(LogLevel::default(), LogLevel::Warn);
+    }
+}
@@ -23,8 +23,14 @@ pub struct RecordMeta {
     // pub latest_revision: RevisionMeta,
     // pub future_revisions_count: u32,
     pub retrieved_revision: RevisionMeta,
+    // `false` in responses to updates which matched the existing revision, where no new revision was written
+    #[serde(default = "default_changed", skip_serializing_if = "is_changed")]
+    pub changed: bool,
 }
 
+fn default_changed() -> bool { true }
+fn is_changed(changed: &bool) -> bool { *changed }
+
 /// Retrieve minimal revision metadata for a record needed by UIs to retrieve version history
 ///
 pub fn read_revision_metadata_abbreviated(header: &SignedActionHashed) -> RecordAPIResult<RecordMeta>
@@ -38,9 +44,43 @@ pub fn read_revision_metadata_abbreviated(header: &SignedActionHashed) -> Record
         // future_revisions_count: 0,
         // latest_revision: e.clone().into(),
         retrieved_revision: header.into(),
+        changed: true,
     })
 }
 
+/// Determine the time at which a record was initially created, from the `Create` action
+/// which wrote the initial entry referenced by its `identity_address`.
+///
+/// This timestamp remains stable across updates to the record, and does not depend upon
+/// any creation time field being stored in the entry data.
+///
+pub fn read_record_creation_time(identity_address: &EntryHash) -> RecordAPIResult<DateTime<Utc>>
+{
+    match get_details(identity_address.to_owned(), GetOptions { strategy: GetStrategy::Latest })? {
+        Some(Details::Entry(details)) => details.actions.iter()
+            .filter(|a| match a.action() { Action::Create(_) => true, _ => false })
+            .min_by_key(|a| by_action_time(a))
+            .map(|a| RevisionMeta::from(a).time)
+            .ok_or(DataIntegrityError::EntryNotFound),
+        _ => Err(DataIntegrityError::EntryNotFound),
+    }
+}
+
+/// Determine the agent which created a record, from the `Create` action which wrote the
+/// initial entry referenced by its `identity_address`.
+///
+pub fn read_record_author(identity_address: &EntryHash) -> RecordAPIResult<AgentPubKey>
+{
+    match get_details(identity_address.to_owned(), GetOptions { strategy: GetStrategy::Latest })? {
+        Some(Details::Entry(details)) => details.actions.iter()
+            .filter(|a| match a.action() { Action::Create(_) => true, _ => false })
+            .min_by_key(|a| by_action_time(a))
+            .map(|a| a.action().author().to_owned())
+            .ok_or(DataIntegrityError::EntryNotFound),
+        _ => Err(DataIntegrityError::EntryNotFound),
+    }
+}
+
 /**
  * Derive metadata for a record's full revision history by querying the DHT
  *
@@ -74,6 +114,7 @@ pub fn read_revision_metadata_full(header: &SignedActionHashed) -> RecordAPIResu
                             // future_revisions_count: 0,
                             // latest_revision: e.clone().into(),
                             retrieved_revision: header.into(),
+                            changed: true,
                         })
                     },
                     // updates found, recurse to determine latest
@@ -86,6 +127,7 @@ pub fn read_revision_metadata_full(header: &SignedActionHashed) -> RecordAPIResu
                             // future_revisions_count,
                             // latest_revision: (&latest).into(),
                             retrieved_revision: header.into(),
+                            changed: true,
                         })
                     },
                 }
@@ -168,6 +210,22 @@ fn find_earliest_revision(signed_action: &SignedActionHashed, revisions_before:
     }
 }
 
+/// Follow the update chain forward from the revision with the given `ActionHash`,
+/// returning the action of the latest revision.
+///
+pub (crate) fn read_latest_revision(action_hash: &ActionHash) -> RecordAPIResult<SignedActionHashed> {
+    match get_details(action_hash.to_owned(), GetOptions { strategy: GetStrategy::Latest })? {
+        Some(Details::Record(details)) => match details.validation_status {
+            ValidationStatus::Valid => match details.updates.len() {
+                0 => Ok(details.record.signed_action().to_owned()),
+                _ => Ok(find_latest_revision(details.updates.as_slice(), 0)?.0),
+            },
+            _ => Err(DataIntegrityError::EntryNotFound),
+        },
+        _ => Err(DataIntegrityError::EntryNotFound),
+    }
+}
+
 /**
  * Recursive helper for determining latest revision in chain, and count of subsequent revisions.
  *
@@ -0,0 +1,314 @@
+/**
+ * Helpers for maintaining name-ordered indexes over records, for alphabetical listings.
+ *
+ * Records are linked from a `Path` for the first letter of their normalised name, with
+ * the normalised name itself carried in the link tag. Listings walk the letter buckets
+ * in order and sort the (comparatively small) contents of each bucket in memory, which
+ * avoids having to maintain any ordering of links in the DHT.
+ *
+ * Names beginning with anything other than an ASCII letter or digit are all placed in a
+ * single trailing bucket, so that ordering remains deterministic for any input.
+ *
+ * @package hdk_records
+ * @since   2026-10-16
+ */
+use hdk::prelude::*;
+use hdk_uuid_types::DnaAddressable;
+
+use crate::{
+    RecordAPIResult,
+    identity_helpers::{
+        calculate_identity_address_checked,
+        infer_local_entry_identity,
+    },
+};
+
+/// Bucket holding all names which do not begin with an ASCII letter or digit
+pub const OTHER_NAME_BUCKET: &str = "other";
+
+/// Maximum number of bytes of a normalised name retained in link tags.
+/// Names sharing a longer prefix are ordered by identity within that prefix.
+const MAX_TAGGED_NAME_BYTES: usize = 512;
+
+/// Parameters for reading a page of records in name order.
+/// `cursor` is the cursor of the last record of the previous page, as returned in its edge.
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct NameIndexParams {
+    #[serde(default)]
+    pub starting_with: Option<char>,
+    #[serde(default)]
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// One page of record identities read from a name index, in order.
+/// Each entry pairs the record's pagination cursor with its identity.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct NameIndexPage<A> {
+    pub entries: Vec<(String, A)>,
+    pub has_previous_page: bool,
+    pub has_next_page: bool,
+}
+
+//--------------------------------[ READ ]--------------------------------------
+
+/// Normalise a name for ordering- surrounding whitespace is ignored, and case is folded.
+///
+pub fn normalize_name<S: AsRef<str>>(name: S) -> String {
+    name.as_ref().trim().to_lowercase()
+}
+
+/// Determine the bucket which records named `name` are indexed under.
+///
+pub fn name_bucket<S: AsRef<str>>(name: S) -> String {
+    match normalize_name(name).chars().next() {
+        Some(c) if c.is_ascii_alphanumeric() => c.to_string(),
+        _ => OTHER_NAME_BUCKET.to_string(),
+    }
+}
+
+/// All buckets in listing order: digits, then letters, then everything else.
+///
+pub fn name_buckets() -> Vec<String> {
+    ('0'..='9').chain('a'..='z')
+        .map(|c| c.to_string())
+        .chain(std::iter::once(OTHER_NAME_BUCKET.to_string()))
+        .collect()
+}
+
+/// Read up to `limit` record identities from the name index `index_name`, in name order.
+///
+/// Listing begins at the bucket for `starting_with` if provided, and continues through
+/// all following buckets. Where `cursor` is given, only records ordered after it are returned.
+///
+pub fn list_by_name<A, LT, S>(
+    link_type: LT,
+    index_name: S,
+    starting_with: Option<char>,
+    cursor: Option<String>,
+    limit: usize,
+) -> RecordAPIResult<NameIndexPage<A>>
+    where A: DnaAddressable<EntryHash>,
+        LT: Clone + LinkTypeFilterExt,
+        S: AsRef<str>,
+{
+    let after = cursor.as_ref().and_then(|c| decode_name_cursor(c));
+    let first_bucket = match (&after, starting_with) {
+        (Some((name, _)), _) => name_bucket(name),
+        (None, Some(c)) => name_bucket(c.to_string()),
+        (None, None) => name_buckets().remove(0),
+    };
+
+    let mut page = vec![];
+    let mut has_next_page = false;
+
+    for bucket in name_buckets().into_iter().skip_while(|b| b != &first_bucket) {
+        let mut entries = read_name_bucket(link_type.to_owned(), &index_name, &bucket)?;
+        entries.sort();
+        entries.dedup();
+
+        for entry in entries.into_iter().filter(|e| after.as_ref().map_or(true, |a| e > a)) {
+            if page.len() == limit {
+                has_next_page = true;
+                break;
+            }
+            page.push(entry);
+        }
+        if has_next_page { break; }
+    }
+
+    Ok(NameIndexPage {
+        entries: page.into_iter()
+            .map(|(name, identity_hash)| {
+                let cursor = encode_name_cursor(&name, &identity_hash);
+                Ok((cursor, infer_local_entry_identity(&identity_hash)?))
+            })
+            .collect::<RecordAPIResult<Vec<(String, A)>>>()?,
+        has_previous_page: after.is_some(),
+        has_next_page,
+    })
+}
+
+//-------------------------------[ UPDATE ]-------------------------------------
+
+/// Maintain the name index `index_name` for the record identified by `identity`.
+///
+/// `new_entry` and `prev_entry` are the record data after and before the write being
+/// indexed- pass `None` for `prev_entry` upon creation, and `None` for `new_entry`
+/// upon deletion. `extract_name` determines the indexed name from each entry.
+///
+/// Renamed records are unlinked from the bucket for their previous name and relinked
+/// under their new name, even where both names fall in the same bucket.
+///
+pub fn sync_name_index<A, T, F, LT, S, E>(
+    link_type: LT,
+    index_name: S,
+    identity: &A,
+    new_entry: Option<&T>,
+    prev_entry: Option<&T>,
+    extract_name: F,
+) -> RecordAPIResult<()>
+    where A: DnaAddressable<EntryHash>,
+        F: Fn(&T) -> String,
+        LT: Clone + LinkTypeFilterExt,
+        ScopedLinkType: TryFrom<LT, Error = E>,
+        WasmError: From<E>,
+        S: AsRef<str>,
+{
+    let new_name = new_entry.map(&extract_name).map(normalize_name);
+    let prev_name = prev_entry.map(&extract_name).map(normalize_name);
+
+    if new_name == prev_name {
+        return Ok(());
+    }
+
+    let identity_hash = calculate_identity_address_checked(identity)?;
+
+    if let Some(prev) = prev_name {
+        unlink_name(link_type.to_owned(), &index_name, &prev, &identity_hash)?;
+    }
+    if let Some(new) = new_name {
+        let path_hash = name_bucket_path_for(&index_name, name_bucket(&new)).path_entry_hash()?;
+        let tag = name_tag_for(&new);
+        // records are relinked when their indexes are resynced
+        let already_linked = get_links(path_hash.to_owned(), link_type.to_owned(), Some(tag.to_owned()))?
+            .iter()
+            .any(|l| l.target.to_owned().into_entry_hash().as_ref() == Some(&identity_hash));
+        if !already_linked {
+            create_link(path_hash, identity_hash, link_type, tag)?;
+        }
+    }
+
+    Ok(())
+}
+
+//--------------------------[ UTILITIES  / INTERNALS ]---------------------
+
+/// Calculate the `Path` under which records in `bucket` of the name index `index_name` are linked.
+///
+fn name_bucket_path_for<S, B>(
+    index_name: S,
+    bucket: B,
+) -> Path
+    where S: AsRef<str>,
+        B: AsRef<str>,
+{
+    Path::from(vec![
+        Component::from(index_name.as_ref().as_bytes().to_vec()),
+        Component::from(bucket.as_ref().as_bytes().to_vec()),
+    ])
+}
+
+/// Truncate a normalised name to the portion stored in link tags
+///
+fn tagged_name(name: &str) -> &str {
+    let mut end = std::cmp::min(name.len(), MAX_TAGGED_NAME_BYTES);
+    while !name.is_char_boundary(end) { end -= 1; }
+    &name[..end]
+}
+
+fn name_tag_for(name: &str) -> LinkTag {
+    let mut tag = crate::identifiers::RECORD_NAME_INDEX_LINK_TAG.to_vec();
+    tag.extend_from_slice(tagged_name(name).as_bytes());
+    LinkTag::new(tag)
+}
+
+/// Read the (name, identity) pairs linked from a single bucket
+///
+fn read_name_bucket<LT, S>(
+    link_type: LT,
+    index_name: &S,
+    bucket: &String,
+) -> RecordAPIResult<Vec<(String, EntryHash)>>
+    where LT: LinkTypeFilterExt,
+        S: AsRef<str>,
+{
+    let path_hash = name_bucket_path_for(index_name, bucket).path_entry_hash()?;
+    let prefix = crate::identifiers::RECORD_NAME_INDEX_LINK_TAG;
+
+    Ok(get_links(path_hash, link_type, Some(LinkTag::new(prefix)))?
+        .into_iter()
+        .filter_map(|l| {
+            let name = String::from_utf8(l.tag.0.get(prefix.len()..)?.to_vec()).ok()?;
+            Some((name, l.target.into_entry_hash()?))
+        })
+        .collect())
+}
+
+/// Remove any links to the record at `identity_hash` from the bucket for `name`
+///
+fn unlink_name<LT, S>(
+    link_type: LT,
+    index_name: &S,
+    name: &String,
+    identity_hash: &EntryHash,
+) -> RecordAPIResult<()>
+    where LT: LinkTypeFilterExt,
+        S: AsRef<str>,
+{
+    let path_hash = name_bucket_path_for(index_name, name_bucket(name)).path_entry_hash()?;
+
+    for link in get_links(path_hash, link_type, Some(name_tag_for(name)))? {
+        if link.target.to_owned().into_entry_hash().as_ref() == Some(identity_hash) {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Cursors are composed of the tagged name and identity of a record, and are opaque to clients.
+///
+fn encode_name_cursor(name: &str, identity_hash: &EntryHash) -> String {
+    format!("{}|{}", name, identity_hash)
+}
+
+fn decode_name_cursor(cursor: &str) -> Option<(String, EntryHash)> {
+    let (name, hash) = cursor.rsplit_once('|')?;
+    Some((name.to_string(), EntryHash::try_from(hash.to_string()).ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_bucket() {
+        assert_eq!(name_bucket("Apples"), "a");
+        assert_eq!(name_bucket("  zucchini "), "z");
+        assert_eq!(name_bucket("3-ply cardboard"), "3");
+        assert_eq!(name_bucket("Éclair"), OTHER_NAME_BUCKET);
+        assert_eq!(name_bucket("_hidden"), OTHER_NAME_BUCKET);
+        assert_eq!(name_bucket(""), OTHER_NAME_BUCKET);
+    }
+
+    #[test]
+    fn test_name_bucket_order() {
+        let buckets = name_buckets();
+        assert_eq!(buckets.len(), 37);
+        assert_eq!(buckets.first().unwrap(), "0");
+        assert_eq!(buckets[10], "a");
+        assert_eq!(buckets.last().unwrap(), OTHER_NAME_BUCKET);
+    }
+
+    #[test]
+    fn test_tagged_name_truncation() {
+        let long = "é".repeat(MAX_TAGGED_NAME_BYTES);
+        let tagged = tagged_name(&long);
+        assert!(tagged.len() <= MAX_TAGGED_NAME_BYTES);
+        assert!(long.starts_with(tagged));
+        assert_eq!(tagged_name("short"), "short");
+    }
+
+    #[test]
+    fn test_name_cursor_roundtrip() {
+        let hash = EntryHash::from_raw_36(vec![0x01; 36]);
+        let cursor = encode_name_cursor("salt | pepper", &hash);
+        assert_eq!(decode_name_cursor(&cursor), Some(("salt | pepper".to_string(), hash)));
+        assert_eq!(decode_name_cursor("not a cursor"), None);
+    }
+}
@@ -0,0 +1,183 @@
+/**
+ * Helpers for applying partial "patch" style updates to records.
+ *
+ * Allows clients to update individual fields of a record without constructing
+ * the full `UpdateRequest` for the record type, or needing to know the
+ * `MaybeUndefined` conventions of each zome. A set of `PatchOp`s is converted
+ * into the equivalent `UpdateRequest` via a field registry generated with
+ * `generate_patch_builder!`, and then routed through the record's standard
+ * update logic so that validation & index maintenance still apply.
+ *
+ * Values are passed as JSON-compatible data. Hash-based identifiers must be
+ * provided as arrays of byte values, since JSON has no native binary type.
+ *
+ * @package hdk_records
+ * @since   2026-10-16
+ */
+use hdk::prelude::*;
+use crate::{
+    MaybeUndefined,
+    RecordAPIResult, DataIntegrityError,
+};
+
+/// The operation to perform against a single field
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum PatchOpType {
+    /// assign `value` to the field
+    Set,
+    /// erase any value stored in the field
+    Clear,
+}
+
+/// A single field operation to apply as part of a patch request
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchOp {
+    pub op: PatchOpType,
+    pub field: String,
+    #[serde(default)]
+    pub value: serde_json::Value,
+}
+
+/// Input parameters for zome `patch_record` API methods
+///
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PatchParams<T> {
+    pub address: T,
+    pub ops: Vec<PatchOp>,
+}
+
+/// Error details for a `PatchOp` which could not be applied
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchOpError {
+    /// index of the failed operation in the input list
+    pub index: usize,
+    pub field: String,
+    pub error: String,
+}
+
+impl std::fmt::Display for PatchOpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "op {} ({}): {}", self.index, self.field, self.error)
+    }
+}
+
+/// Normalize an externally provided field name to its Rust struct field equivalent,
+/// such that `camelCase` names used by the external API and `snake_case` names are
+/// both accepted.
+///
+pub fn normalize_field_name<S>(field: S) -> String
+    where S: AsRef<str>,
+{
+    field.as_ref().chars().fold(String::new(), |mut name, c| {
+        if c.is_ascii_uppercase() {
+            name.push('_');
+            name.push(c.to_ascii_lowercase());
+        } else {
+            name.push(c);
+        }
+        name
+    })
+}
+
+/// Decode the value of a `PatchOp` into the `MaybeUndefined` wrapper used by update payloads.
+///
+/// `Clear` operations always translate to `MaybeUndefined::None`. `Set` operations must
+/// provide a non-null value which can be decoded into the field's type `T`.
+///
+pub fn decode_patch_value<T>(op: &PatchOp) -> Result<MaybeUndefined<T>, String>
+    where T: serde::de::DeserializeOwned,
+{
+    match op.op {
+        PatchOpType::Clear => Ok(MaybeUndefined::None),
+        PatchOpType::Set => {
+            if op.value.is_null() {
+                return Err("no value provided for 'set' operation, use 'clear' to erase fields".to_string());
+            }
+            serde_json::from_value(op.value.to_owned())
+                .map(MaybeUndefined::Some)
+                .map_err(|e| format!("type mismatch: {}", e))
+        },
+    }
+}
+
+/// Helper for patch builders to collect per-operation errors and abort if any were encountered.
+///
+pub fn throw_patch_errors(errors: Vec<PatchOpError>) -> RecordAPIResult<()> {
+    if errors.len() > 0 {
+        return Err(DataIntegrityError::PatchRejected(errors));
+    }
+    Ok(())
+}
+
+/// Generate a `build_patch_update` function which converts a list of `PatchOp`s into
+/// an update payload of type `$update_type`, which must have a `revision_id` field and
+/// otherwise consist of `MaybeUndefined` fields.
+///
+/// All listed fields form the registry of patchable fields for the record type. Any
+/// operation referencing an unknown field, or providing a value of the wrong type,
+/// causes the entire patch to be rejected with errors for each failed operation.
+///
+#[macro_export]
+macro_rules! generate_patch_builder {
+    ( $update_type:ident { $( $field:ident ),* $(,)? } ) => {
+        /// Convert a set of `PatchOp`s into an update payload for the record at `revision_id`.
+        /// Operations are applied all-or-nothing; see `hdk_records::patch`.
+        pub fn build_patch_update(revision_id: ::hdk::prelude::ActionHash, ops: Vec<$crate::patch::PatchOp>) -> $crate::RecordAPIResult<$update_type> {
+            let mut update = $update_type {
+                revision_id,
+                $( $field: $crate::MaybeUndefined::Undefined, )*
+            };
+
+            let errors = ops.iter().enumerate()
+                .filter_map(|(index, op)| {
+                    let result: Result<(), String> = match $crate::patch::normalize_field_name(&op.field).as_str() {
+                        $(
+                            stringify!($field) => $crate::patch::decode_patch_value(op)
+                                .map(|value| { update.$field = value; }),
+                        )*
+                        _ => Err(format!("unknown field '{}'", op.field)),
+                    };
+                    result.err().map(|error| $crate::patch::PatchOpError {
+                        index, error,
+                        field: op.field.to_owned(),
+                    })
+                })
+                .collect();
+
+            $crate::patch::throw_patch_errors(errors)?;
+
+            Ok(update)
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_name_normalization() {
+        assert_eq!(normalize_field_name("note"), "note");
+        assert_eq!(normalize_field_name("resourceClassifiedAs"), "resource_classified_as");
+        assert_eq!(normalize_field_name("resource_classified_as"), "resource_classified_as");
+    }
+
+    #[test]
+    fn test_decode_values() {
+        let set = PatchOp { op: PatchOpType::Set, field: "note".into(), value: serde_json::Value::String("val".into()) };
+        let clear = PatchOp { op: PatchOpType::Clear, field: "note".into(), value: serde_json::Value::Null };
+        let empty_set = PatchOp { op: PatchOpType::Set, field: "note".into(), value: serde_json::Value::Null };
+        let bad_type = PatchOp { op: PatchOpType::Set, field: "note".into(), value: serde_json::Value::Bool(true) };
+
+        assert_eq!(decode_patch_value::<String>(&set), Ok(MaybeUndefined::Some("val".to_string())));
+        assert_eq!(decode_patch_value::<String>(&clear), Ok(MaybeUndefined::None));
+        assert!(decode_patch_value::<String>(&empty_set).is_err(), "set operations require a value");
+        assert!(decode_patch_value::<String>(&bad_type).is_err(), "mismatched value types are rejected");
+    }
+}
@@ -0,0 +1,182 @@
+/**
+ * Helpers for reading records with fields restricted by the role of the requester.
+ *
+ * Record types declare the fields visible to each `RecordRole` by implementing
+ * `RoleVisibility` via `role_visible_fields!`. Fields are declared by their entry names and
+ * projected from the record's camelCase response, so hidden fields are omitted from the result
+ * rather than nulled, and requesters can tell a hidden field apart from an unset one.
+ * Identifying fields (`id`, `revisionId` & `meta`) are visible to every role.
+ *
+ * Roles are not an access control mechanism on their own- zomes must determine the
+ * role of the requester themselves rather than accepting it as a parameter, based on the
+ * record's accountable agents rather than on who happened to author its latest revision.
+ *
+ * @package hdk_records
+ * @since   2026-10-16
+ */
+use hdk::prelude::*;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use crate::{
+    RecordAPIResult, DataIntegrityError,
+    record_interface::RoleVisibility,
+};
+
+/// Response fields visible regardless of role
+const IDENTIFYING_FIELDS: [&str; 3] = ["id", "revisionId", "meta"];
+
+/// Relationship of the requesting agent to a record, determining which of its fields are visible
+///
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordRole {
+    Owner,
+    Public,
+}
+
+/// The fields of a record response visible to `role`
+///
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordProjection<B> {
+    pub id: B,
+    pub revision_id: ActionHash,
+    pub role: RecordRole,
+    pub fields: Value,
+}
+
+/// Build the projection of a record's `response`, retaining only those fields which
+/// the type of `entry` declares visible to `role`.
+///
+pub fn project_record<T, R, B>(
+    base_address: B,
+    revision_id: ActionHash,
+    entry: &T,
+    response: &R,
+    role: RecordRole,
+) -> RecordAPIResult<RecordProjection<B>>
+    where T: RoleVisibility,
+        R: Serialize,
+{
+    Ok(RecordProjection {
+        id: base_address,
+        revision_id,
+        role,
+        fields: project_response(response, entry.visible_fields(&role))?,
+    })
+}
+
+/// Strip the fields of `response` which the type of `entry` declares hidden from `role`,
+/// for returning from the record's standard read APIs. Hidden fields must be optional
+/// in the response type.
+///
+pub fn redact_for_role<T, R>(entry: &T, response: R, role: RecordRole) -> RecordAPIResult<R>
+    where T: RoleVisibility,
+        R: Serialize + DeserializeOwned,
+{
+    let visible = match entry.visible_fields(&role) {
+        None => return Ok(response),
+        visible => visible,
+    };
+    serde_json::from_value(project_response(&response, visible)?)
+        .map_err(|e| DataIntegrityError::Serialization(SerializedBytesError::Deserialize(e.to_string())))
+}
+
+/// Project the JSON representation of `response` down to the `visible` entry fields.
+///
+pub fn project_response<R>(response: &R, visible: Option<Vec<&'static str>>) -> RecordAPIResult<Value>
+    where R: Serialize,
+{
+    let value = serde_json::to_value(response)
+        .map_err(|e| DataIntegrityError::Serialization(SerializedBytesError::Serialize(e.to_string())))?;
+
+    Ok(match (visible, value) {
+        (Some(visible), Value::Object(mut fields)) => {
+            let visible: Vec<String> = visible.iter().map(|f| camel_case(f)).collect();
+            fields.retain(|field, _| IDENTIFYING_FIELDS.contains(&field.as_str()) || visible.contains(field));
+            Value::Object(fields)
+        },
+        (_, value) => value,
+    })
+}
+
+/// Response field name for an entry field declared in `role_visible_fields!`
+fn camel_case(field: &str) -> String {
+    let mut parts = field.split('_');
+    let mut name = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            name.extend(first.to_uppercase());
+            name.push_str(chars.as_str());
+        }
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::role_visible_fields;
+
+    struct TestEntry;
+
+    impl RoleVisibility for TestEntry {
+        role_visible_fields!(Public { name, note });
+    }
+
+    struct UndeclaredEntry;
+
+    impl RoleVisibility for UndeclaredEntry {}
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    struct TestResponse {
+        id: String,
+        name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        note: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        unit_cost: Option<f64>,
+    }
+
+    fn response() -> TestResponse {
+        TestResponse {
+            id: "resource".to_string(),
+            name: "bicycle".to_string(),
+            note: Some("spares".to_string()),
+            unit_cost: Some(250.0),
+        }
+    }
+
+    #[test]
+    fn test_owner_sees_all_fields() {
+        let fields = project_response(&response(), TestEntry.visible_fields(&RecordRole::Owner)).unwrap();
+        assert_eq!(fields, serde_json::json!({ "id": "resource", "name": "bicycle", "note": "spares", "unitCost": 250.0 }));
+    }
+
+    #[test]
+    fn test_public_sees_declared_fields() {
+        let fields = project_response(&response(), TestEntry.visible_fields(&RecordRole::Public)).unwrap();
+        assert_eq!(fields, serde_json::json!({ "id": "resource", "name": "bicycle", "note": "spares" }), "hidden fields omitted, identifying fields retained");
+    }
+
+    #[test]
+    fn test_undeclared_record_types_unrestricted() {
+        let fields = project_response(&response(), UndeclaredEntry.visible_fields(&RecordRole::Public)).unwrap();
+        assert_eq!(fields["unitCost"], serde_json::json!(250.0));
+    }
+
+    #[test]
+    fn test_redacted_responses_omit_hidden_fields() {
+        let redacted = redact_for_role(&TestEntry, response(), RecordRole::Public).unwrap();
+        assert_eq!(redacted, TestResponse { unit_cost: None, ..response() });
+        assert_eq!(redact_for_role(&TestEntry, response(), RecordRole::Owner).unwrap(), response());
+    }
+
+    #[test]
+    fn test_camel_case() {
+        assert_eq!(camel_case("onhand_quantity"), "onhandQuantity");
+        assert_eq!(camel_case("name"), "name");
+    }
+}
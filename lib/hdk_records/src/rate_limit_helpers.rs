@@ -0,0 +1,43 @@
+/**
+ * Coordinator-side checks for the per-agent creation limits enforced by `hdk_rate_limits`.
+ *
+ * Integrity validation remains the authoritative check. Checking the caller's own chain
+ * before writing allows zome APIs to report an exceeded limit as a `THROTTLED` error,
+ * rather than the write being rejected by validation after the fact.
+ *
+ * @package hdk_records
+ * @since   2026-10-16
+ */
+use hdk::prelude::*;
+use hdk_rate_limits::{
+    RATE_LIMIT_WINDOW_MICROS,
+    read_creation_limit,
+    check_creation_rate,
+    creates_entry_type,
+};
+
+use crate::{RecordAPIResult, DataIntegrityError};
+
+/// Reject the creation of another record of `entry_type` where the calling agent has already
+/// created as many records of that type within the rolling window as the DNA permits.
+///
+pub fn throw_if_creation_rate_exceeded<U>(entry_type: &U, entry_type_name: &str) -> RecordAPIResult<()>
+    where for<'a> ScopedEntryDefIndex: TryFrom<&'a U, Error = WasmError>,
+{
+    let limit = match read_creation_limit(entry_type_name)? {
+        Some(limit) => limit,
+        None => return Ok(()),
+    };
+    let entry_def = ScopedEntryDefIndex::try_from(entry_type)?;
+    let window_start = sys_time()?.as_micros() - RATE_LIMIT_WINDOW_MICROS;
+
+    let prior_count = query(ChainQueryFilter::new().action_type(ActionType::Create))?
+        .iter()
+        .map(|record| record.action())
+        .filter(|action| action.timestamp().as_micros() >= window_start)
+        .filter(|action| creates_entry_type(action, &entry_def))
+        .count();
+
+    check_creation_rate(limit, prior_count, entry_type_name)
+        .map_err(DataIntegrityError::CreationRateExceeded)
+}
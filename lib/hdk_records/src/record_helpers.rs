@@ -10,17 +10,21 @@
  */
 use hdk::prelude::*;
 use hdk::info::dna_info;
+use hdk::hdk::{HDK, HdkT};
 
 use crate::{
     DnaAddressable,
     RecordAPIResult, DataIntegrityError,
     record_interface::{Identifiable, Identified, Updateable},
     entries::{
+        get_entry_by_address,
         get_entry_by_action,
         create_entry,
         update_entry,
         delete_entry,
+        throw_if_entry_too_large,
     },
+    validation_helpers::run_validation_hooks,
     identity_helpers::{
         create_entry_identity,
         infer_local_entry_identity,
@@ -28,6 +32,8 @@ use crate::{
     },
     metadata_helpers::{
         get_action_hash,
+        read_latest_revision,
+        read_record_creation_time,
     },
 };
 
@@ -43,7 +49,15 @@ use crate::{
 /// changes outlined in issue https://github.com/h-REA/hREA/issues/196
 ///
 pub fn get_latest_action_hash(entry_hash: EntryHash) -> RecordAPIResult<ActionHash> {
-    match get_details(entry_hash.clone(), GetOptions { strategy: GetStrategy::Latest })? {
+    let details = get_details(entry_hash.clone(), GetOptions { strategy: GetStrategy::Latest })?;
+    latest_action_hash_from_details(entry_hash, details)
+}
+
+/// Resolve the latest available ActionHash for `entry_hash` from the `Details` of its entry,
+/// following any updates as with `get_latest_action_hash`.
+///
+fn latest_action_hash_from_details(entry_hash: EntryHash, details: Option<Details>) -> RecordAPIResult<ActionHash> {
+    match details {
         Some(Details::Entry(details)) => match details.entry_dht_status {
             metadata::EntryDhtStatus::Live => match details.updates.len() {
                 0 => {
@@ -71,6 +85,76 @@ pub fn get_latest_action_hash(entry_hash: EntryHash) -> RecordAPIResult<ActionHa
     }
 }
 
+/// Retrieve the `ActionHash` of the latest revision of the record whose initial entry is at `address`.
+///
+/// Unlike `get_latest_action_hash`, updates are followed revision by revision from the action which
+/// created the record. The full update chain is therefore traversed even where an update restores
+/// the content (and so the `EntryHash`) of an earlier revision.
+///
+pub fn get_latest_revision(address: &EntryHash) -> RecordAPIResult<ActionHash> {
+    let details = get_details(address.to_owned(), GetOptions { strategy: GetStrategy::Latest })?;
+    latest_revision_from_creation(details)
+}
+
+/// Retrieve the `ActionHash` of the latest revision of each of many records, as with `get_latest_revision`.
+///
+/// The initial entries of all records are retrieved in a single batch. Results are returned in
+/// the same order as `addresses`, with records which cannot be resolved returned as errors.
+///
+pub fn get_latest_revisions(addresses: &[EntryHash]) -> RecordAPIResult<Vec<RecordAPIResult<ActionHash>>> {
+    let inputs: Vec<GetInput> = addresses.iter()
+        .map(|address| GetInput::new(address.to_owned().into(), GetOptions { strategy: GetStrategy::Latest }))
+        .collect();
+    let details = HDK.with(|h| h.borrow().get_details(inputs))?;
+
+    Ok(details.into_iter()
+        .map(latest_revision_from_creation)
+        .collect())
+}
+
+/// Follow the updates to a record from the creation action found in the `Details` of its initial entry.
+///
+fn latest_revision_from_creation(details: Option<Details>) -> RecordAPIResult<ActionHash> {
+    let creation = match details {
+        Some(Details::Entry(details)) => match details.entry_dht_status {
+            metadata::EntryDhtStatus::Live => details.actions.into_iter()
+                .filter(|action| matches!(action.action(), Action::Create(_)))
+                .min_by_key(|action| action.action().timestamp()),
+            _ => None,
+        },
+        _ => None,
+    }.ok_or(DataIntegrityError::EntryNotFound)?;
+
+    Ok(get_action_hash(&read_latest_revision(&get_action_hash(&creation))?))
+}
+
+/// Retrieve the latest available ActionHash for each of many EntryHashes, as with `get_latest_action_hash`.
+///
+/// The entries themselves are retrieved in a single batch. Updated entries are then followed to their
+/// latest revisions one at a time. Results are returned in the same order as `entry_hashes`. Errors with
+/// `DataIntegrityError::UnresolvedBatchEntry` identifying the position of the first entry which could not
+/// be resolved, and carrying the error it failed with.
+///
+pub fn get_latest_action_hashes(entry_hashes: &[EntryHash]) -> RecordAPIResult<Vec<ActionHash>> {
+    let inputs: Vec<GetInput> = entry_hashes.iter()
+        .map(|entry_hash| GetInput::new(entry_hash.to_owned().into(), GetOptions { strategy: GetStrategy::Latest }))
+        .collect();
+    let details = HDK.with(|h| h.borrow().get_details(inputs))?;
+
+    resolve_in_order(entry_hashes, details, |entry_hash, details| latest_action_hash_from_details(entry_hash.to_owned(), details))
+}
+
+/// Apply `resolve` to each of `inputs` along with its `Details`, reporting the position of the first input which fails.
+///
+fn resolve_in_order<R, F>(inputs: &[EntryHash], details: Vec<Option<Details>>, resolve: F) -> RecordAPIResult<Vec<R>>
+    where F: Fn(&EntryHash, Option<Details>) -> RecordAPIResult<R>,
+{
+    inputs.iter().zip(details.into_iter()).enumerate()
+        .map(|(index, (input, details))| resolve(input, details)
+            .map_err(|e| DataIntegrityError::UnresolvedBatchEntry(index, input.to_owned(), Box::new(e))))
+        .collect()
+}
+
 /// Retrive the specific version of an entry specified by the given `ActionHash`
 ///
 pub fn read_record_entry_by_action<T, R, B>(
@@ -86,6 +170,27 @@ pub fn read_record_entry_by_action<T, R, B>(
     Ok((meta, storage_entry.identity()?, storage_entry.entry()))
 }
 
+/// Read a record's entry data directly by the `EntryHash` of one of its revisions (the
+/// "data address", as found in the action returned by a prior read), skipping the
+/// lookup of the record's identity index.
+///
+/// Only use this where the caller accepts that the data may be stale- updates made to the
+/// record after the given revision are not followed. Use `read_record_entry` to load the
+/// latest version of a record.
+///
+pub fn read_record_entry_direct<T, R, B>(
+    data_address: &EntryHash,
+) -> RecordAPIResult<(SignedActionHashed, B, T)>
+    where T: std::fmt::Debug,
+        B: DnaAddressable<EntryHash>,
+        SerializedBytes: TryInto<R, Error = SerializedBytesError>,
+        Entry: TryFrom<R>,
+        R: std::fmt::Debug + Identified<T, B>,
+{
+    let (meta, storage_entry): (_, R) = get_entry_by_address(data_address)?;
+    Ok((meta, storage_entry.identity()?, storage_entry.entry()))
+}
+
 /// Read a record's entry data by its identity index
 ///
 /// :TODO: Currently, the most recent version of the given entry will
@@ -107,7 +212,7 @@ pub (crate) fn read_record_entry_by_identity<T, R, B>(
     let identifier: B = infer_local_entry_identity(identity_address)?;
     // pull details of the current version, to ensure we have the most recent
     let entry_hash: &EntryHash = identifier.as_ref();
-    let latest_action_hash = get_latest_action_hash(entry_hash.to_owned())?;
+    let latest_action_hash = get_latest_revision(entry_hash)?;
 
     let (meta, read_entry_hash, entry_data) = read_record_entry_by_action(&latest_action_hash)?;
 
@@ -133,6 +238,25 @@ pub fn read_record_entry<T, R, B>(
     read_record_entry_by_identity::<T, R, B>(&identity_address)
 }
 
+/// Read a record's entry data as with `read_record_entry`, additionally returning the
+/// time at which the record was initially created.
+///
+pub fn read_record_entry_with_creation_time<T, R, B>(
+    address: &EntryHash,
+) -> RecordAPIResult<(SignedActionHashed, B, T, chrono::DateTime<chrono::Utc>)>
+    where T: std::fmt::Debug,
+        B: DnaAddressable<EntryHash>,
+        SerializedBytes: TryInto<R, Error = SerializedBytesError> + TryInto<B, Error = SerializedBytesError>,
+        Entry: TryFrom<R>,
+        R: std::fmt::Debug + Identified<T, B>,
+{
+    let identity_address = calculate_identity_address(&B::new(dna_info()?.hash, address.clone()))?;
+    let (meta, base_address, entry_data) = read_record_entry_by_identity::<T, R, B>(&identity_address)?;
+    let created_at = read_record_creation_time(&identity_address)?;
+
+    Ok((meta, base_address, entry_data, created_at))
+}
+
 //-------------------------------[ CREATE ]-------------------------------------
 
 /// Creates a new record in the DHT, assigns it an identity index (@see identity_helpers.rs)
@@ -146,7 +270,7 @@ pub fn create_record<T, I, R: Clone, B, C, E, S, F, G>(
     where S: AsRef<str> + std::fmt::Display,
         B: DnaAddressable<EntryHash>,
         C: TryInto<I, Error = DataIntegrityError>,
-        I: Identifiable<R>,
+        I: Identifiable<R> + Serialize,
         WasmError: From<E>,
         Entry: TryFrom<R, Error = E>,
         T: From<R>,
@@ -159,8 +283,10 @@ pub fn create_record<T, I, R: Clone, B, C, E, S, F, G>(
 {
     // convert the type's CREATE payload into internal storage struct
     let entry_data: I = create_payload.try_into()?;
+    run_validation_hooks(&entry_data, &entry_data.validation_hooks())?;
     // wrap data with null identity for origin record
     let storage = entry_data.with_identity(None);
+    throw_if_entry_too_large(&storage)?;
 
     // write underlying entry
     let (meta, entry_hash) = create_entry::<T,_,_>(storage)?;
@@ -185,6 +311,10 @@ pub fn create_record<T, I, R: Clone, B, C, E, S, F, G>(
 ///
 /// :TODO: prevent multiple updates to the same ActionHash under standard operations
 ///
+/// Where the updated entry data is identical to that of the revision at `address`, no update
+/// is written and the action of that revision is returned in place of a new one.
+/// Callers should skip any index maintenance in this case, see `return_if_unchanged!`.
+///
 /// @see hdk_records::record_interface::Updateable
 ///
 pub fn update_record<I, R: Clone, B, U, E>(
@@ -192,21 +322,30 @@ pub fn update_record<I, R: Clone, B, U, E>(
     update_payload: U,
 ) -> RecordAPIResult<(SignedActionHashed, B, I, I)>
     where B: DnaAddressable<EntryHash>,
-        I: Identifiable<R> + Updateable<U>,
+        I: Identifiable<R> + Updateable<U> + Serialize + std::fmt::Debug,
         WasmError: From<E>,
         Entry: TryFrom<R, Error = E>,
         R: Clone + Identified<I, B>,
         SerializedBytes: TryInto<R, Error = SerializedBytesError>,
 {
     // get referenced entry for the given action
-    let (_meta, previous): (_, R) = get_entry_by_action(address)?;
+    let (previous_meta, previous): (_, R) = get_entry_by_action(address)?;
     let prev_entry = previous.entry();
     let identity = previous.identity()?;
     let identity_hash: &EntryHash = identity.as_ref();
 
     // apply update payload
+    prev_entry.check_immutable_fields(&update_payload)?;
     let new_entry = prev_entry.update_with(update_payload)?;
+    run_validation_hooks(&new_entry, &new_entry.validation_hooks())?;
+
+    // avoid writing revisions which change nothing, eg. where a form is resubmitted as-is
+    if entry_data_unchanged(&prev_entry, &new_entry)? {
+        return Ok((previous_meta, identity, new_entry, prev_entry));
+    }
+
     let storage: R = new_entry.with_identity(Some(identity_hash.clone()));
+    throw_if_entry_too_large(&storage)?;
 
     // perform regular entry update using internal address
     let (meta, _entry_addr) = update_entry(address, storage)?;
@@ -214,6 +353,38 @@ pub fn update_record<I, R: Clone, B, U, E>(
     Ok((meta, identity, new_entry, prev_entry))
 }
 
+/// Determine whether `update_record` wrote a new revision for an update made against `revision_id`,
+/// given the action it returned. Returns `false` where the update matched the existing revision.
+///
+pub fn revision_changed(meta: &SignedActionHashed, revision_id: &ActionHash) -> bool {
+    meta.as_hash() != revision_id
+}
+
+/// Return early from an update handler where `update_record` wrote no new revision for an update
+/// made against `revision_id`, responding with the `RecordAPIResult` of `response`, flagged unchanged.
+///
+/// Nothing was written, so indexes are already up to date and need no maintenance, and no changes
+/// need replicating to other DNAs. `record` names the response field holding the record.
+///
+#[macro_export]
+macro_rules! return_if_unchanged {
+    ($meta:expr, $revision_id:expr, $response:expr, $record:ident) => {
+        if !$crate::records::revision_changed(&$meta, &$revision_id) {
+            let mut response = $response?;
+            response.$record.meta.changed = false;
+            return Ok(response);
+        }
+    };
+}
+
+/// Compare the serialized bytes of two versions of some entry data, including any nonce fields.
+///
+pub(crate) fn entry_data_unchanged<I>(previous: &I, next: &I) -> RecordAPIResult<bool>
+    where I: Serialize + std::fmt::Debug,
+{
+    Ok(encode(previous)? == encode(next)?)
+}
+
 //-------------------------------[ DELETE ]-------------------------------------
 
 /// Removes a record of the given `ActionHash` from the DHT by marking it as deleted.
@@ -237,6 +408,22 @@ mod tests {
 
     simple_alias!(EntryId => EntryHash);
 
+    #[test]
+    fn test_resolve_in_order() {
+        let hashes: Vec<EntryHash> = (1..=3).map(|n| EntryHash::from_raw_36(vec![n; 36])).collect();
+
+        let resolved = resolve_in_order(hashes.as_slice(), vec![None, None, None], |h, _| Ok(h.get_raw_36()[0])).unwrap();
+        assert_eq!(resolved, vec![1, 2, 3], "results preserve input order");
+
+        let dangling = hashes[1].to_owned();
+        let err = resolve_in_order(hashes.as_slice(), vec![None, None, None], |h, _| match *h == dangling {
+            true => Err(DataIntegrityError::NotRecordAuthor),
+            false => Ok(()),
+        }).unwrap_err();
+        assert!(matches!(err, DataIntegrityError::UnresolvedBatchEntry(1, ref h, _) if *h == dangling), "failing entry identified by position");
+        assert!(matches!(err, DataIntegrityError::UnresolvedBatchEntry(_, _, ref e) if matches!(**e, DataIntegrityError::NotRecordAuthor)), "underlying error retained");
+    }
+
     #[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
     pub struct Entry {
         field: Option<String>,
@@ -273,6 +460,13 @@ mod tests {
         Some("".to_string())
     }
 
+    #[test]
+    fn test_entry_data_unchanged() {
+        let entry = Entry { field: Some("value".into()) };
+        assert!(entry_data_unchanged(&entry, &Entry { field: Some("value".into()) }).unwrap(), "identical update detected");
+        assert!(!entry_data_unchanged(&entry, &Entry { field: None }).unwrap(), "changed update detected");
+    }
+
     #[test]
     fn test_roundtrip() {
         let entry_type: String = "testing".to_string();
@@ -286,6 +480,13 @@ mod tests {
         assert_eq!(base_address.as_ref(), returned_address.as_ref(), "record should have same identifier ID on read as for creation");
         assert_eq!(initial_entry, first_entry, "record from creation output should be same as read data");
 
+        // Verify direct read of unchanged record
+        let data_address = action_addr.action().entry_hash().unwrap().to_owned();
+        let (action_addr_direct, returned_address_direct, direct_entry) = read_record_entry_direct::<Entry, EntryWithIdentity, EntryId>(&data_address).unwrap();
+        assert_eq!(action_addr, action_addr_direct, "direct read should return same action as indirect read");
+        assert_eq!(base_address.as_ref(), returned_address_direct.as_ref(), "direct read should return same identifier as indirect read");
+        assert_eq!(first_entry, direct_entry, "direct read should return same entry data as indirect read");
+
         // UPDATE
         let (updated_action_addr, identity_address, updated_entry): (_, EntryId, Entry) = update_record(&action_addr, UpdateRequest { field: Some("value".into()) }).unwrap();
 
@@ -13,6 +13,9 @@ use hdk_uuid_types::DnaAddressable;
 
 use crate::{
     RecordAPIResult,
+    MaybeUndefined,
+    validation_helpers::ValidationHook,
+    projection_helpers::RecordRole,
 };
 
 /// A trait for managing records associated with a consistent "base" identifier.
@@ -41,6 +44,15 @@ pub trait Identified<T, A>
 ///
 pub trait Identifiable<T> {
     fn with_identity(&self, id_hash: Option<EntryHash>) -> T;
+
+    /// Validators registered for the entry type, run prior to the entry being written.
+    /// Record types opt in to validators via the `validated_by` clause of `generate_record_entry!`.
+    ///
+    /// @see hdk_records::validation
+    ///
+    fn validation_hooks(&self) -> Vec<ValidationHook> {
+        vec![]
+    }
 }
 
 /// Compose an `Identified` structure around the provided entry struct, in order to provide
@@ -49,9 +61,12 @@ pub trait Identifiable<T> {
 /// In addition, the original entry struct receives an `Identifiable` trait impl that can be used
 /// to generate the storage data struct by assigning the previously known unique entry identifier.
 ///
+/// Validators to be run against the entry before it is written may be registered by appending
+/// `validated_by [HOOK, ...]`, @see hdk_records::validation.
+///
 #[macro_export]
 macro_rules! generate_record_entry {
-    ( $( $t:ident, $id:ident, $to:ident );+ ) => {
+    ( $( $t:ident, $id:ident, $to:ident $(, validated_by [ $( $hook:expr ),* $(,)? ] )? );+ $(;)? ) => {
         $(
             // $crate::paste::paste! {
 
@@ -92,6 +107,13 @@ macro_rules! generate_record_entry {
                             id_hash,
                         }
                     }
+
+                    $(
+                        fn validation_hooks(&self) -> Vec<$crate::ValidationHook>
+                        {
+                            vec![$( $hook ),*]
+                        }
+                    )?
                 }
 
             // }
@@ -112,6 +134,108 @@ pub trait Updateable<T> {
     /// @see hdk_records::record_helpers::update_record
     ///
     fn update_with(&self, e: T) -> RecordAPIResult<Self> where Self: Sized;
+
+    /// Checks an update payload against any fields of the entry which may not be changed
+    /// after creation, erroring with `DataIntegrityError::FieldImmutable` if any would be.
+    /// Called prior to `update_with` by `update_record`.
+    ///
+    /// Fields are declared as immutable by implementing this method via `immutable_fields!`.
+    ///
+    fn check_immutable_fields(&self, _e: &T) -> RecordAPIResult<()> {
+        Ok(())
+    }
+}
+
+/// Compares the current value of an entry field against a `MaybeUndefined` update payload field,
+/// to determine whether the update would modify it.
+///
+/// Omitting a field or resending its current value does not count as a change.
+///
+pub trait ImmutableValue<U> {
+    fn is_changed_by(&self, update: &MaybeUndefined<U>) -> bool;
+}
+
+impl<T> ImmutableValue<T> for T
+    where T: PartialEq,
+{
+    fn is_changed_by(&self, update: &MaybeUndefined<T>) -> bool {
+        match update {
+            MaybeUndefined::Undefined => false,
+            MaybeUndefined::Some(value) => value != self,
+            MaybeUndefined::None => true,
+        }
+    }
+}
+
+impl<T> ImmutableValue<T> for Option<T>
+    where T: PartialEq,
+{
+    fn is_changed_by(&self, update: &MaybeUndefined<T>) -> bool {
+        match (update, self) {
+            (MaybeUndefined::Undefined, _) => false,
+            (MaybeUndefined::Some(value), Some(current)) => value != current,
+            (MaybeUndefined::None, None) => false,
+            _ => true,
+        }
+    }
+}
+
+/// Declare fields of an entry which cannot be changed once the record is created.
+/// Use within an `Updateable` implementation, passing the update payload type and the
+/// names of immutable fields (which must be named identically in both structs).
+///
+/// Usage:
+///  impl Updateable<UpdateRequest> for EntryData {
+///      immutable_fields!(UpdateRequest { agent_type });
+///      fn update_with(...)
+///  }
+///
+#[macro_export]
+macro_rules! immutable_fields {
+    ( $update_type:ident { $( $field:ident ),* $(,)? } ) => {
+        fn check_immutable_fields(&self, e: &$update_type) -> $crate::RecordAPIResult<()> {
+            $(
+                if $crate::record_interface::ImmutableValue::is_changed_by(&self.$field, &e.$field) {
+                    return Err($crate::DataIntegrityError::FieldImmutable(stringify!($field).to_string()));
+                }
+            )*
+            Ok(())
+        }
+    };
+}
+
+/// Declares which fields of an entry are visible to each `RecordRole` when the record is read
+/// via `project_record` or `redact_for_role`. Roles without a declaration see every field.
+///
+/// Fields are declared by implementing this trait via `role_visible_fields!`.
+///
+/// @see hdk_records::projections
+///
+pub trait RoleVisibility {
+    fn visible_fields(&self, _role: &RecordRole) -> Option<Vec<&'static str>> {
+        None
+    }
+}
+
+/// Declare the fields of an entry visible to some `RecordRole`s.
+/// Use within a `RoleVisibility` implementation, passing the names of visible fields per role.
+///
+/// Usage:
+///  impl RoleVisibility for EntryData {
+///      role_visible_fields!(Public { name, note });
+///  }
+///
+#[macro_export]
+macro_rules! role_visible_fields {
+    ( $( $role:ident { $( $field:ident ),* $(,)? } ),* $(,)? ) => {
+        fn visible_fields(&self, role: &$crate::RecordRole) -> Option<Vec<&'static str>> {
+            match role {
+                $( $crate::RecordRole::$role => Some(vec![$( stringify!($field) ),*]), )*
+                #[allow(unreachable_patterns)]
+                _ => None,
+            }
+        }
+    };
 }
 
 /// Interface for obtaining identity information from any data type.
@@ -159,4 +283,18 @@ mod tests {
             entry,
         );
     }
+
+    #[test]
+    fn test_immutable_value_changes() {
+        let required = "Person".to_string();
+        assert!(!required.is_changed_by(&MaybeUndefined::Undefined), "omitted fields are unchanged");
+        assert!(!required.is_changed_by(&MaybeUndefined::Some("Person".to_string())), "resending current value is not a change");
+        assert!(required.is_changed_by(&MaybeUndefined::Some("Organization".to_string())), "new values are changes");
+        assert!(required.is_changed_by(&MaybeUndefined::None), "clearing values is a change");
+
+        let optional: Option<String> = None;
+        assert!(!optional.is_changed_by(&MaybeUndefined::<String>::None), "clearing empty fields is not a change");
+        assert!(optional.is_changed_by(&MaybeUndefined::Some("value".to_string())), "setting empty fields is a change");
+        assert!(Some("value".to_string()).is_changed_by(&MaybeUndefined::<String>::None), "clearing set fields is a change");
+    }
 }
@@ -8,6 +8,7 @@
  * @since   2021-01-31
  */
 
+use std::collections::BTreeMap;
 use hdk::prelude::*;
 use holo_hash::DnaHash;
 use hc_zome_dna_auth_resolver_lib::{DNAConnectionAuth, ensure_authed, AvailableCapability};
@@ -90,6 +91,36 @@ pub fn call_local_zome_method<C, F, R, I, S>(
 
 }
 
+// DNA properties wrapper for reading the configuration of any zome by name
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone)]
+struct ZomeProperties(BTreeMap<String, serde_json::Value>);
+
+/**
+ * Determine whether the local index zome named by `zome_name_from_config` is configured
+ * as a read-only follower (via `read_only` in its `IndexingZomeConfig`), in which case
+ * index writes should not be requested of it.
+ */
+pub fn local_index_read_only<C, F>(
+    zome_name_from_config: F,
+) -> bool
+    where C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError>,
+        F: FnOnce(C) -> Option<String>,
+{
+    let properties = match dna_info() {
+        Ok(info) => info.modifiers.properties,
+        Err(_) => return false,
+    };
+    let zome_name = match properties.to_owned().try_into().ok().and_then(zome_name_from_config) {
+        Some(name) => name,
+        None => return false,
+    };
+    let all_zomes: Result<ZomeProperties, SerializedBytesError> = properties.try_into();
+    all_zomes.ok()
+        .and_then(|ZomeProperties(zomes)| zomes.get(&zome_name).and_then(|conf| conf.get("read_only")).and_then(|v| v.as_bool()))
+        .unwrap_or(false)
+}
+
 fn handle_resp<R>(
     resp: ZomeCallResponse,
 ) -> OtherCellResult<R>
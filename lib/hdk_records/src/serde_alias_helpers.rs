@@ -0,0 +1,185 @@
+/**
+ * Test helpers for zome RPC structs which accept legacy snake_case field names
+ * alongside the camelCase names they serialize with.
+ *
+ * Field names are read from a struct's `Deserialize` implementation, so that checks
+ * need not be updated as fields are added. Each camelCase field is then deserialized
+ * under both spellings with a value that no field accepts, and the two outcomes compared.
+ * A field without its alias is ignored under the snake_case spelling and so fails differently.
+ *
+ * @package hdk_records
+ * @since   2026-10-16
+ */
+use std::fmt;
+use serde::de::{self, Deserialize, DeserializeOwned, Deserializer, Visitor};
+
+/// List the field names accepted by the `Deserialize` implementation of struct `T`,
+/// including any aliases.
+///
+pub fn deserialized_field_names<T: DeserializeOwned>() -> Vec<&'static str> {
+    match T::deserialize(FieldNameCapture) {
+        Err(CaptureError::Fields(fields)) => fields.to_vec(),
+        _ => vec![],
+    }
+}
+
+/// Determine whether every camelCase field of struct `T` can also be deserialized
+/// using its snake_case name. Returns the snake_case names which do not map to a field.
+///
+pub fn check_snake_case_aliases<T: DeserializeOwned>() -> Result<(), Vec<String>> {
+    let unmapped: Vec<String> = deserialized_field_names::<T>().into_iter()
+        .filter(|name| !name.contains('_') && name.chars().any(char::is_uppercase))
+        .filter_map(|name| {
+            let snake_name = to_snake_case(name);
+            match try_field::<T>(name) == try_field::<T>(&snake_name) {
+                true => None,
+                false => Some(snake_name),
+            }
+        })
+        .collect();
+
+    match unmapped.is_empty() {
+        true => Ok(()),
+        false => Err(unmapped),
+    }
+}
+
+/// Generate a test asserting that each of the given RPC structs accepts
+/// snake_case field names, @see `check_snake_case_aliases`.
+///
+///     snake_case_alias_tests!(CreateRequest, UpdateRequest, Response);
+///
+#[macro_export]
+macro_rules! snake_case_alias_tests {
+    ($($struct_type:ty),+ $(,)?) => {
+        #[test]
+        fn test_snake_case_field_aliases() {
+            let mut failures: Vec<String> = vec![];
+            $(
+                if let Err(fields) = $crate::serde_aliases::check_snake_case_aliases::<$struct_type>() {
+                    failures.push(format!("{}: {}", stringify!($struct_type), fields.join(", ")));
+                }
+            )+
+            assert!(failures.is_empty(), "fields without snake_case aliases- {}", failures.join("; "));
+        }
+    };
+}
+
+/// Attempt to deserialize `T` from a single field `name`, holding a value which
+/// is invalid for any field type in use.
+///
+fn try_field<T: DeserializeOwned>(name: &str) -> Result<(), String> {
+    let mut input = serde_json::Map::new();
+    input.insert(name.to_string(), serde_json::json!([[[true]]]));
+    serde_json::from_value::<T>(serde_json::Value::Object(input))
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+pub(crate) fn to_snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+    for c in name.chars() {
+        if c.is_uppercase() {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+/// Deserializer which reports the field names of the struct requested from it
+///
+struct FieldNameCapture;
+
+#[derive(Debug)]
+enum CaptureError {
+    Fields(&'static [&'static str]),
+    NotAStruct(String),
+}
+
+impl fmt::Display for CaptureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CaptureError::Fields(fields) => write!(f, "fields: {}", fields.join(", ")),
+            CaptureError::NotAStruct(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for CaptureError {}
+
+impl de::Error for CaptureError {
+    fn custom<M: fmt::Display>(msg: M) -> Self {
+        CaptureError::NotAStruct(msg.to_string())
+    }
+}
+
+impl<'de> Deserializer<'de> for FieldNameCapture {
+    type Error = CaptureError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(CaptureError::NotAStruct("only structs have field names".to_string()))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        Err(CaptureError::Fields(fields))
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{Serialize, Deserialize};
+
+    #[allow(dead_code)]
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Aliased {
+        id: String,
+        #[serde(alias = "resource_quantity")]
+        resource_quantity: Option<f64>,
+        #[serde(default)]
+        #[serde(alias = "has_point_in_time")]
+        has_point_in_time: Option<String>,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct PartlyAliased {
+        #[serde(alias = "resource_quantity")]
+        resource_quantity: Option<f64>,
+        #[serde(default)]
+        effort_quantity: Option<f64>,
+    }
+
+    #[test]
+    fn test_field_names_captured() {
+        let names = deserialized_field_names::<PartlyAliased>();
+        assert!(names.contains(&"resourceQuantity"));
+        assert!(names.contains(&"effortQuantity"));
+    }
+
+    #[test]
+    fn test_missing_aliases_reported() {
+        assert_eq!(check_snake_case_aliases::<Aliased>(), Ok(()));
+        assert_eq!(check_snake_case_aliases::<PartlyAliased>(), Err(vec!["effort_quantity".to_string()]));
+    }
+
+    #[test]
+    fn test_snake_case_conversion() {
+        assert_eq!(to_snake_case("hasPointInTime"), "has_point_in_time");
+        assert_eq!(to_snake_case("id"), "id");
+    }
+}
@@ -0,0 +1,354 @@
+/**
+ * Helpers for maintaining unique, human-readable slugs for records, for use in shareable URLs.
+ *
+ * Each slug is represented by a `Path` composed of the index name and the slug itself, linked
+ * to the record currently holding it. When a record's slug changes, the old slug may be kept
+ * as a redirect to the record so that previously shared URLs continue to resolve. A slug is
+ * unavailable to other records while it is either held or redirecting.
+ *
+ * Each claim link is tagged with the action which created the claiming record, and is only
+ * valid where written by that record's author (@see `validate_slug_claim_link`). Uniqueness is
+ * checked against the links visible to the writing agent; concurrent claims of the same slug
+ * by disconnected agents resolve to the record created first, so that a slug cannot be taken
+ * from its holder by a later claim.
+ *
+ * @package hdk_records
+ * @since   2026-10-16
+ */
+use hdk::prelude::*;
+use hdk_uuid_types::DnaAddressable;
+
+use crate::{
+    RecordAPIResult, DataIntegrityError,
+    identity_helpers::{
+        calculate_identity_address_checked,
+        infer_local_entry_identity,
+    },
+    bundle_helpers::read_creation_action,
+};
+
+/// Maximum length of a slug, in characters
+pub const MAX_SLUG_LENGTH: usize = 64;
+
+/// Result of resolving a slug to the record holding it.
+/// `redirected` is set where the slug was formerly held by the record, which has since changed slug.
+///
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SlugResolution<A> {
+    pub address: A,
+    pub redirected: bool,
+}
+
+/// Parameters for resolving a record by its slug
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SlugParams {
+    pub slug: String,
+}
+
+//--------------------------------[ READ ]--------------------------------------
+
+/// Ensure `slug` is non-empty, at most `MAX_SLUG_LENGTH` characters and composed only of
+/// lowercase ASCII letters, digits and non-consecutive hyphens which do not begin or end it.
+///
+pub fn validate_slug<S: AsRef<str>>(slug: S) -> RecordAPIResult<()> {
+    let slug = slug.as_ref();
+    let valid = !slug.is_empty()
+        && slug.len() <= MAX_SLUG_LENGTH
+        && slug.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !slug.starts_with('-') && !slug.ends_with('-')
+        && !slug.contains("--");
+    if !valid {
+        return Err(DataIntegrityError::InvalidSlug(slug.to_string()));
+    }
+    Ok(())
+}
+
+/// Determine the record holding `slug` in the slug index `index_name`, following redirects
+/// from slugs which the record has since changed away from.
+///
+pub fn resolve_by_slug<A, LT, S, V>(
+    link_type: LT,
+    index_name: S,
+    slug: V,
+) -> RecordAPIResult<Option<SlugResolution<A>>>
+    where A: DnaAddressable<EntryHash>,
+        LT: Clone + LinkTypeFilterExt,
+        S: AsRef<str>,
+        V: AsRef<str>,
+{
+    match read_slug_holder(link_type, &index_name, slug.as_ref())? {
+        None => Ok(None),
+        Some((identity_hash, redirected)) => Ok(Some(SlugResolution {
+            address: infer_local_entry_identity(&identity_hash)?,
+            redirected,
+        })),
+    }
+}
+
+/// Refuse use of `slug` if it is held by, or redirects to, any record other than `identity`.
+/// Pass `None` for `identity` prior to creating a record.
+///
+pub fn throw_if_slug_taken<A, LT, S, V>(
+    link_type: LT,
+    index_name: S,
+    slug: V,
+    identity: Option<&A>,
+) -> RecordAPIResult<()>
+    where A: DnaAddressable<EntryHash>,
+        LT: Clone + LinkTypeFilterExt,
+        S: AsRef<str>,
+        V: AsRef<str>,
+{
+    validate_slug(&slug)?;
+    let own_hash = match identity {
+        Some(identity) => Some(calculate_identity_address_checked(identity)?),
+        None => None,
+    };
+    match read_slug_holder(link_type, &index_name, slug.as_ref())? {
+        Some((holder, _)) if Some(&holder) != own_hash.as_ref() => Err(DataIntegrityError::SlugTaken(slug.as_ref().to_string())),
+        _ => Ok(()),
+    }
+}
+
+//-------------------------------[ UPDATE ]-------------------------------------
+
+/// Maintain the slug index `index_name` for the record identified by `identity`.
+///
+/// `new_entry` and `prev_entry` are the record data after and before the write being
+/// indexed- pass `None` for `prev_entry` upon creation, and `None` for `new_entry`
+/// upon deletion. `extract_slug` determines the slug of each entry, returning `None`
+/// where the record has no slug.
+///
+/// Where the slug has changed and `keep_redirect` is set, the previous slug is retained
+/// as a redirect to the record. Errors with `DataIntegrityError::SlugTaken` if the new
+/// slug belongs to another record.
+///
+pub fn sync_slug_index<A, T, F, LT, S, E>(
+    link_type: LT,
+    index_name: S,
+    identity: &A,
+    new_entry: Option<&T>,
+    prev_entry: Option<&T>,
+    extract_slug: F,
+    keep_redirect: bool,
+) -> RecordAPIResult<()>
+    where A: DnaAddressable<EntryHash>,
+        F: Fn(&T) -> Option<String>,
+        LT: Clone + LinkTypeFilterExt,
+        ScopedLinkType: TryFrom<LT, Error = E>,
+        WasmError: From<E>,
+        S: AsRef<str>,
+{
+    let new_slug = new_entry.and_then(&extract_slug);
+    let prev_slug = prev_entry.and_then(&extract_slug);
+
+    if new_slug == prev_slug {
+        return Ok(());
+    }
+
+    let identity_hash = calculate_identity_address_checked(identity)?;
+
+    if let Some(new) = &new_slug {
+        throw_if_slug_taken(link_type.to_owned(), &index_name, new, Some(identity))?;
+        let creation = read_creation_action(identity.as_ref())?;
+        // reclaiming a previous slug replaces its redirect
+        unlink_slug(link_type.to_owned(), &index_name, new, &identity_hash, crate::identifiers::RECORD_SLUG_REDIRECT_LINK_TAG)?;
+        let path_hash = slug_path_for(&index_name, new).path_entry_hash()?;
+        let tag = slug_claim_tag(crate::identifiers::RECORD_SLUG_LINK_TAG, &creation);
+        // records are relinked when their indexes are resynced
+        let already_linked = get_links(path_hash.to_owned(), link_type.to_owned(), Some(tag.to_owned()))?
+            .iter()
+            .any(|l| l.target.to_owned().into_entry_hash().as_ref() == Some(&identity_hash));
+        if !already_linked {
+            create_link(path_hash, identity_hash.to_owned(), link_type.to_owned(), tag)?;
+        }
+    }
+    if let Some(prev) = &prev_slug {
+        unlink_slug(link_type.to_owned(), &index_name, prev, &identity_hash, crate::identifiers::RECORD_SLUG_LINK_TAG)?;
+        if keep_redirect && new_slug.is_some() {
+            let creation = read_creation_action(identity.as_ref())?;
+            let path_hash = slug_path_for(&index_name, prev).path_entry_hash()?;
+            create_link(path_hash, identity_hash, link_type, slug_claim_tag(crate::identifiers::RECORD_SLUG_REDIRECT_LINK_TAG, &creation))?;
+        }
+    }
+
+    Ok(())
+}
+
+//-----------------------------[ VALIDATION ]-----------------------------------
+
+/// Validate a link claiming a slug for, or redirecting a slug to, the record at `target`.
+/// For use in the integrity zome of the records' DNA, for links of the slug index type.
+///
+/// The link tag must reference the action which created the record at `target`, and the
+/// link must be written by the author of that action. Agents are thus unable to claim slugs
+/// on behalf of records authored by others.
+///
+pub fn validate_slug_claim_link(
+    author: &AgentPubKey,
+    target: &AnyLinkableHash,
+    tag: &LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let creation = match read_slug_claim_action(tag) {
+        Some(creation) => creation,
+        None => return Ok(ValidateCallbackResult::Invalid("Slug link tag does not reference the creation of the claiming record".to_string())),
+    };
+    let action = must_get_action(creation)?;
+    match action.action() {
+        Action::Create(create) if Some(&create.entry_hash) == target.to_owned().into_entry_hash().as_ref() => {
+            if create.author != *author {
+                return Ok(ValidateCallbackResult::Invalid("Slugs may only be claimed by the author of the claiming record".to_string()));
+            }
+            Ok(ValidateCallbackResult::Valid)
+        },
+        _ => Ok(ValidateCallbackResult::Invalid("Slug link tag does not reference the creation of the claiming record".to_string())),
+    }
+}
+
+/// Validate removal of a slug claim, which may only be removed by the agent which made it.
+///
+pub fn validate_slug_claim_unlink(
+    author: &AgentPubKey,
+    claim_author: &AgentPubKey,
+) -> ExternResult<ValidateCallbackResult> {
+    if author != claim_author {
+        return Ok(ValidateCallbackResult::Invalid("Slug claims may only be removed by the agent which made them".to_string()));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+//--------------------------[ UTILITIES  / INTERNALS ]---------------------
+
+/// Build the tag for a slug claim of kind `prefix`, referencing the `creation` of the claiming record.
+///
+fn slug_claim_tag(prefix: &[u8], creation: &ActionHash) -> LinkTag {
+    LinkTag::new([prefix, creation.get_raw_39()].concat())
+}
+
+/// Read the action which created the claiming record from the tag of a slug claim of either kind.
+/// Returns `None` for malformed tags and for claims made before tags referenced the record's creation.
+///
+fn read_slug_claim_action(tag: &LinkTag) -> Option<ActionHash> {
+    [crate::identifiers::RECORD_SLUG_LINK_TAG, crate::identifiers::RECORD_SLUG_REDIRECT_LINK_TAG].iter()
+        .find_map(|prefix| tag.0.strip_prefix(*prefix))
+        .and_then(|raw| ActionHash::from_raw_39(raw.to_vec()).ok())
+}
+
+/// Calculate the `Path` from which the record holding `slug` in the slug index `index_name` is linked.
+///
+fn slug_path_for<S, V>(
+    index_name: S,
+    slug: V,
+) -> Path
+    where S: AsRef<str>,
+        V: AsRef<str>,
+{
+    Path::from(vec![
+        Component::from(index_name.as_ref().as_bytes().to_vec()),
+        Component::from(slug.as_ref().as_bytes().to_vec()),
+    ])
+}
+
+/// Read the identity of the record holding `slug`, and whether it is held via a redirect.
+///
+/// Should more than one claim be present, the claim by the earliest-created record is authoritative.
+/// Claims made before tags referenced the record's creation are only considered where no other
+/// claims exist, earliest first.
+///
+fn read_slug_holder<LT, S>(
+    link_type: LT,
+    index_name: &S,
+    slug: &str,
+) -> RecordAPIResult<Option<(EntryHash, bool)>>
+    where LT: Clone + LinkTypeFilterExt,
+        S: AsRef<str>,
+{
+    let path_hash = slug_path_for(index_name, slug).path_entry_hash()?;
+    for (tag, redirected) in [
+        (crate::identifiers::RECORD_SLUG_LINK_TAG, false),
+        (crate::identifiers::RECORD_SLUG_REDIRECT_LINK_TAG, true),
+    ] {
+        let links = get_links(path_hash.to_owned(), link_type.to_owned(), Some(LinkTag::new(tag)))?;
+        let mut claims = vec![];
+        for link in links {
+            let identity_hash = match link.target.to_owned().into_entry_hash() {
+                Some(identity_hash) => identity_hash,
+                None => continue,
+            };
+            let order = match read_slug_claim_action(&link.tag) {
+                Some(creation) => match get(creation.to_owned(), GetOptions::default())? {
+                    Some(record) => (false, record.action().timestamp(), creation.get_raw_39().to_vec()),
+                    // claiming record not yet visible
+                    None => continue,
+                },
+                None => (true, link.timestamp, vec![]),
+            };
+            claims.push((order, identity_hash));
+        }
+        claims.sort_by(|a, b| a.0.cmp(&b.0));
+        if let Some((_, identity_hash)) = claims.into_iter().next() {
+            return Ok(Some((identity_hash, redirected)));
+        }
+    }
+    Ok(None)
+}
+
+/// Remove any links tagged `tag` to the record at `identity_hash` from the path for `slug`
+///
+fn unlink_slug<LT, S>(
+    link_type: LT,
+    index_name: &S,
+    slug: &String,
+    identity_hash: &EntryHash,
+    tag: &'static [u8],
+) -> RecordAPIResult<()>
+    where LT: LinkTypeFilterExt,
+        S: AsRef<str>,
+{
+    let path_hash = slug_path_for(index_name, slug).path_entry_hash()?;
+
+    for link in get_links(path_hash, link_type, Some(LinkTag::new(tag)))? {
+        if link.target.to_owned().into_entry_hash().as_ref() == Some(identity_hash) {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slug_validation() {
+        assert!(validate_slug("alice").is_ok());
+        assert!(validate_slug("acme-co-op-2").is_ok());
+        assert!(validate_slug("").is_err());
+        assert!(validate_slug("Alice").is_err(), "uppercase rejected");
+        assert!(validate_slug("alice smith").is_err(), "whitespace rejected");
+        assert!(validate_slug("-alice").is_err());
+        assert!(validate_slug("alice-").is_err());
+        assert!(validate_slug("alice--smith").is_err());
+        assert!(validate_slug("émile").is_err(), "non-ASCII rejected");
+        assert!(validate_slug("a".repeat(MAX_SLUG_LENGTH)).is_ok());
+        assert!(validate_slug("a".repeat(MAX_SLUG_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_slug_claim_tags() {
+        let creation = ActionHash::from_raw_36(vec![0xac; 36]);
+        let claim = slug_claim_tag(crate::identifiers::RECORD_SLUG_LINK_TAG, &creation);
+        assert!(claim.0.starts_with(crate::identifiers::RECORD_SLUG_LINK_TAG), "prefix retained for filtering by kind");
+        assert_eq!(read_slug_claim_action(&claim), Some(creation.to_owned()));
+
+        let redirect = slug_claim_tag(crate::identifiers::RECORD_SLUG_REDIRECT_LINK_TAG, &creation);
+        assert_eq!(read_slug_claim_action(&redirect), Some(creation));
+
+        assert_eq!(read_slug_claim_action(&LinkTag::new(crate::identifiers::RECORD_SLUG_LINK_TAG)), None, "legacy claims reference no action");
+        assert_eq!(read_slug_claim_action(&LinkTag::new(b"xx|".to_vec())), None);
+    }
+}
@@ -0,0 +1,288 @@
+/**
+ * Test helpers for checking how `Updateable` entries merge the `MaybeUndefined`
+ * fields of their update payloads.
+ *
+ * Every field accepted by an update payload is expected to behave the same way:
+ * omitting it preserves the current value, a value sets it, and `null` clears it
+ * where the entry field is optional. Required entry fields may preserve their value
+ * or reject the update when sent `null`, but must not panic.
+ *
+ * Fields are enumerated from the update payload's `Deserialize` implementation
+ * (@see `serde_aliases::deserialized_field_names`) and payloads are built from JSON,
+ * so a field added to the payload without a test value or matching entry field
+ * fails the check rather than going untested.
+ *
+ * @package hdk_records
+ * @since   2026-10-16
+ */
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use hdk::prelude::*;
+use serde::de::DeserializeOwned;
+pub use serde_json::Value;
+use serde_json::Map;
+use crate::{
+    record_interface::Updateable,
+    serde_alias_helpers::{deserialized_field_names, to_snake_case},
+};
+
+/// Update payload field holding the revision being updated, which is never merged
+/// into entry data.
+///
+const REVISION_FIELD: &str = "revisionId";
+
+/// Convert a test value for an update payload field to its JSON representation.
+///
+pub fn sample<S: Serialize>(value: S) -> Value {
+    serde_json::to_value(value).expect("test value must serialize")
+}
+
+/// Check the handling of each field of update payload `U` when merged into `entry`,
+/// given a value for every field which differs from that held by `entry`.
+/// Returns a description of each misbehaving field.
+///
+/// `entry` should have all of its optional fields set, so that clearing them is observable.
+///
+pub fn check_update_semantics<E, U>(entry: &E, samples: &[(&str, Value)]) -> Result<(), Vec<String>>
+    where E: Serialize + DeserializeOwned + Updateable<U>,
+        U: DeserializeOwned,
+{
+    let current = match serde_json::to_value(entry) {
+        Ok(Value::Object(fields)) => fields,
+        _ => return Err(vec!["entry fixture does not serialize to a JSON object".to_string()]),
+    };
+    let mut failures = vec![];
+
+    // an empty update preserves every field
+    match apply_update::<E, U>(entry, None) {
+        Outcome::Updated(updated) => {
+            for (field, value) in current.iter() {
+                if updated.get(field) != Some(value) {
+                    failures.push(format!("{}: not preserved when undefined", field));
+                }
+            }
+        },
+        outcome => failures.push(format!("empty update {}", outcome)),
+    }
+
+    for name in deserialized_field_names::<U>().into_iter().filter(|name| *name != REVISION_FIELD && !name.contains('_')) {
+        let field = to_snake_case(name);
+        let current_value = match current.get(&field) {
+            Some(value) => value,
+            None => { failures.push(format!("{}: no matching entry field", name)); continue; },
+        };
+        let sample_value = match samples.iter().find(|(sample_name, _)| *sample_name == name) {
+            Some((_, value)) => value,
+            None => { failures.push(format!("{}: no test value given", name)); continue; },
+        };
+        if sample_value == current_value {
+            failures.push(format!("{}: test value matches entry fixture", name));
+            continue;
+        }
+
+        // a value sets the field, unless it is immutable and the update is rejected
+        match apply_update::<E, U>(entry, Some((name, sample_value.to_owned()))) {
+            Outcome::Updated(updated) => {
+                if updated.get(&field) != Some(sample_value) {
+                    failures.push(format!("{}: not set by a value", name));
+                }
+            },
+            Outcome::Rejected(_) => continue,
+            outcome => { failures.push(format!("{}: update with a value {}", name, outcome)); continue; },
+        }
+
+        // `null` clears optional fields, and required fields are left alone
+        let optional = is_optional::<E>(&current, &field);
+        match (apply_update::<E, U>(entry, Some((name, Value::Null))), optional) {
+            (Outcome::Updated(updated), true) => if updated.get(&field) != Some(&Value::Null) {
+                failures.push(format!("{}: not cleared by null", name));
+            },
+            (Outcome::Updated(updated), false) => if updated.get(&field) != Some(current_value) {
+                failures.push(format!("{}: required field changed by null", name));
+            },
+            (Outcome::Rejected(_), false) => {},
+            (outcome, _) => failures.push(format!("{}: update with null {}", name, outcome)),
+        }
+    }
+
+    match failures.is_empty() {
+        true => Ok(()),
+        false => Err(failures),
+    }
+}
+
+/// Generate a test asserting that the fields of an update payload are merged into
+/// an entry as expected, @see `check_update_semantics`.
+///
+///     update_semantics_tests!(EntryData, UpdateRequest, entry_fixture(), {
+///         "note" => "updated note",
+///         "classifiedAs" => vec![ExternalURL::from("http://example.com/updated")],
+///     });
+///
+#[macro_export]
+macro_rules! update_semantics_tests {
+    ($entry_type:ty, $update_type:ty, $entry:expr, { $($field:literal => $value:expr),* $(,)? }) => {
+        #[test]
+        fn test_update_field_semantics() {
+            let entry: $entry_type = $entry;
+            let samples = vec![$(($field, $crate::update_semantics::sample($value))),*];
+            if let Err(failures) = $crate::update_semantics::check_update_semantics::<$entry_type, $update_type>(&entry, &samples) {
+                panic!("{} fields mishandled by update- {}", stringify!($update_type), failures.join("; "));
+            }
+        }
+    };
+}
+
+enum Outcome {
+    Updated(Map<String, Value>),
+    Rejected(String),
+    Invalid(String),
+    Panicked,
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Updated(_) => write!(f, "succeeded"),
+            Outcome::Rejected(e) => write!(f, "was rejected: {}", e),
+            Outcome::Invalid(e) => write!(f, "could not be built: {}", e),
+            Outcome::Panicked => write!(f, "panicked"),
+        }
+    }
+}
+
+/// Build an update payload from JSON holding at most one field, and merge it into `entry`
+/// as `update_record` would.
+///
+fn apply_update<E, U>(entry: &E, field: Option<(&str, Value)>) -> Outcome
+    where E: Serialize + Updateable<U>,
+        U: DeserializeOwned,
+{
+    let mut payload = Map::new();
+    payload.insert(REVISION_FIELD.to_string(), sample(ActionHash::from_raw_36(vec![0xdb; 36])));
+    if let Some((name, value)) = field {
+        payload.insert(name.to_string(), value);
+    }
+    let update: U = match serde_json::from_value(Value::Object(payload)) {
+        Ok(update) => update,
+        Err(e) => return Outcome::Invalid(e.to_string()),
+    };
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        entry.check_immutable_fields(&update)?;
+        entry.update_with(update)
+    }));
+    match result {
+        Ok(Ok(updated)) => match serde_json::to_value(updated) {
+            Ok(Value::Object(fields)) => Outcome::Updated(fields),
+            _ => Outcome::Invalid("updated entry does not serialize to a JSON object".to_string()),
+        },
+        Ok(Err(e)) => Outcome::Rejected(e.to_string()),
+        Err(_) => Outcome::Panicked,
+    }
+}
+
+/// Determine whether an entry field accepts `null`, by attempting to deserialize the entry with it cleared.
+///
+fn is_optional<E: DeserializeOwned>(current: &Map<String, Value>, field: &str) -> bool {
+    let mut cleared = current.to_owned();
+    cleared.insert(field.to_string(), Value::Null);
+    serde_json::from_value::<E>(Value::Object(cleared)).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MaybeUndefined, RecordAPIResult};
+
+    #[derive(Serialize, Deserialize, Clone)]
+    struct TestEntry {
+        name: String,
+        note: Option<String>,
+        image: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    #[allow(dead_code)]
+    struct TestUpdate {
+        revision_id: ActionHash,
+        #[serde(default)]
+        name: MaybeUndefined<String>,
+        #[serde(default)]
+        note: MaybeUndefined<String>,
+        #[serde(default)]
+        image: MaybeUndefined<String>,
+    }
+
+    impl Updateable<TestUpdate> for TestEntry {
+        fn update_with(&self, e: TestUpdate) -> RecordAPIResult<TestEntry> {
+            Ok(TestEntry {
+                name: if !e.name.is_some() { self.name.to_owned() } else { e.name.to_owned().unwrap() },
+                note: if e.note.is_undefined() { self.note.to_owned() } else { e.note.to_owned().into() },
+                image: if e.image.is_undefined() { self.image.to_owned() } else { e.image.to_owned().into() },
+            })
+        }
+    }
+
+    /// Copies the wrong field when merging, and preserves rather than clears on `null`
+    ///
+    #[derive(Serialize, Deserialize, Clone)]
+    struct MisassignedEntry {
+        name: String,
+        note: Option<String>,
+        image: Option<String>,
+    }
+
+    impl Updateable<TestUpdate> for MisassignedEntry {
+        fn update_with(&self, e: TestUpdate) -> RecordAPIResult<MisassignedEntry> {
+            Ok(MisassignedEntry {
+                name: if !e.name.is_some() { self.name.to_owned() } else { e.name.to_owned().unwrap() },
+                note: if !e.note.is_some() { self.note.to_owned() } else { e.note.to_owned().into() },
+                image: if e.image.is_undefined() { self.image.to_owned() } else { e.note.to_owned().into() },
+            })
+        }
+    }
+
+    fn samples() -> Vec<(&'static str, Value)> {
+        vec![
+            ("name", sample("renamed")),
+            ("note", sample("updated note")),
+            ("image", sample("http://example.com/updated.png")),
+        ]
+    }
+
+    #[test]
+    fn test_conforming_update() {
+        let entry = TestEntry {
+            name: "bicycle".to_string(),
+            note: Some("a note".to_string()),
+            image: Some("http://example.com/bicycle.png".to_string()),
+        };
+        assert_eq!(check_update_semantics::<_, TestUpdate>(&entry, &samples()), Ok(()));
+    }
+
+    #[test]
+    fn test_mishandled_fields_reported() {
+        let entry = MisassignedEntry {
+            name: "bicycle".to_string(),
+            note: Some("a note".to_string()),
+            image: Some("http://example.com/bicycle.png".to_string()),
+        };
+        assert_eq!(check_update_semantics::<_, TestUpdate>(&entry, &samples()), Err(vec![
+            "note: not cleared by null".to_string(),
+            "image: not set by a value".to_string(),
+        ]));
+    }
+
+    #[test]
+    fn test_untested_fields_reported() {
+        let entry = TestEntry {
+            name: "bicycle".to_string(),
+            note: Some("a note".to_string()),
+            image: None,
+        };
+        assert_eq!(check_update_semantics::<_, TestUpdate>(&entry, &samples()[..2]), Err(vec![
+            "image: no test value given".to_string(),
+        ]));
+    }
+}
@@ -0,0 +1,124 @@
+/**
+ * Registry of cross-cutting validators which can be applied to any record type.
+ *
+ * Record types opt in to validators by listing them when generating their storage
+ * wrapper, eg.
+ *
+ *     generate_record_entry!(EntryData, IntentAddress, EntryStorage, validated_by [VALIDATE_NOTE_LENGTH]);
+ *
+ * Registered validators are run against the entry data by `create_record` and `update_record`
+ * prior to anything being written, and should also be run by integrity zomes via
+ * `check_validation_hooks` so that the same rules are enforced by the network.
+ *
+ * Validators receive the JSON representation of the entry, so that they may be shared
+ * between record types which have fields in common.
+ *
+ * @package hdk_records
+ * @since   2026-10-16
+ */
+use hdk::prelude::*;
+
+use crate::{
+    RecordAPIResult, DataIntegrityError,
+};
+
+/// JSON representation of an entry, as passed to validators
+pub use serde_json::Value as EntryValue;
+
+/// A named validation rule, applied to the JSON representation of an entry
+///
+#[derive(Clone, Copy)]
+pub struct ValidationHook {
+    pub name: &'static str,
+    pub check: fn(&EntryValue) -> Result<(), String>,
+}
+
+impl std::fmt::Debug for ValidationHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ValidationHook({})", self.name)
+    }
+}
+
+/// Run each of `hooks` against `entry`, returning the message of the first to fail.
+/// For use in integrity zome validation callbacks.
+///
+pub fn check_validation_hooks<T>(entry: &T, hooks: &[ValidationHook]) -> Result<(), String>
+    where T: Serialize,
+{
+    if hooks.is_empty() {
+        return Ok(());
+    }
+    let value = serde_json::to_value(entry).map_err(|e| e.to_string())?;
+    hooks.iter().try_for_each(|hook| (hook.check)(&value))
+}
+
+/// Run each of `hooks` against `entry`, erroring with `DataIntegrityError::ValidationHookFailed`
+/// for the first to fail.
+///
+pub fn run_validation_hooks<T>(entry: &T, hooks: &[ValidationHook]) -> RecordAPIResult<()>
+    where T: Serialize,
+{
+    if hooks.is_empty() {
+        return Ok(());
+    }
+    let value = serde_json::to_value(entry)
+        .map_err(|e| DataIntegrityError::ValidationHookFailed("serialization".to_string(), e.to_string()))?;
+    for hook in hooks {
+        (hook.check)(&value).map_err(|e| DataIntegrityError::ValidationHookFailed(hook.name.to_string(), e))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hdk_uuid_types::addressable_identifier;
+    use crate::{
+        generate_record_entry,
+        record_interface::Identifiable,
+    };
+
+    addressable_identifier!(TestAddress => EntryHash);
+
+    fn reject_empty_field(value: &EntryValue) -> Result<(), String> {
+        match value.get("field").and_then(|f| f.as_str()) {
+            Some("") => Err("field must not be empty".to_string()),
+            _ => Ok(()),
+        }
+    }
+
+    const REJECT_EMPTY_FIELD: ValidationHook = ValidationHook { name: "reject_empty_field", check: reject_empty_field };
+
+    #[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
+    pub struct OptedInEntry {
+        field: String,
+    }
+    generate_record_entry!(OptedInEntry, TestAddress, OptedInStorage, validated_by [REJECT_EMPTY_FIELD]);
+
+    #[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
+    pub struct OtherEntry {
+        field: String,
+    }
+    generate_record_entry!(OtherEntry, TestAddress, OtherStorage);
+
+    #[test]
+    fn test_registered_hooks_run() {
+        let invalid = OptedInEntry { field: "".to_string() };
+        let result = run_validation_hooks(&invalid, &invalid.validation_hooks());
+        assert_eq!(
+            result.err().map(|e| e.to_string()),
+            Some("Validation failed (reject_empty_field): field must not be empty".to_string()),
+        );
+        assert!(check_validation_hooks(&invalid, &invalid.validation_hooks()).is_err());
+
+        let valid = OptedInEntry { field: "value".to_string() };
+        assert!(run_validation_hooks(&valid, &valid.validation_hooks()).is_ok());
+    }
+
+    #[test]
+    fn test_hooks_only_run_for_opted_in_types() {
+        let entry = OtherEntry { field: "".to_string() };
+        assert!(entry.validation_hooks().is_empty());
+        assert!(run_validation_hooks(&entry, &entry.validation_hooks()).is_ok());
+    }
+}
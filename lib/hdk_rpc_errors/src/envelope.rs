@@ -0,0 +1,218 @@
+/**
+ * Structured error payloads for communicating failures to UI clients
+ *
+ * All errors leaving a zome extern are serialized as JSON into the guest error
+ * string, so that consumers can classify them by `code` rather than by matching
+ * against human-readable messages (which are free to change).
+ *
+ * `ErrorEnvelope` and `ErrorCode` are re-exported by `hdk_semantic_indexes_zome_rpc`
+ * and each zome's RPC crate, for use by clients without the zome-side error types.
+ */
+use hdk::prelude::*;
+use hdk_semantic_indexes_error::SemanticIndexError;
+use crate::CrossCellError;
+
+/// Stable error classification codes. Clients may rely on these values.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    NotFound,
+    Validation,
+    Conflict,
+    RemoteUnreachable,
+    Unauthorized,
+    Misconfigured,
+    Throttled,
+    ReadOnly,
+    Internal,
+}
+
+/// Error payload serialized into `WasmErrorInner::Guest` for all extern errors.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ErrorEnvelope {
+    pub code: ErrorCode,
+    pub message: String,
+    pub field: Option<String>,
+    #[serde(default)]
+    pub details: serde_json::Value,
+}
+
+impl ErrorEnvelope {
+    pub fn new<S: ToString>(code: ErrorCode, message: S) -> Self {
+        Self { code, message: message.to_string(), field: None, details: serde_json::Value::Null }
+    }
+
+    pub fn with_field<S: ToString>(mut self, field: S) -> Self {
+        self.field = Some(field.to_string());
+        self
+    }
+
+    pub fn with_details(mut self, details: serde_json::Value) -> Self {
+        self.details = details;
+        self
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self)
+            .unwrap_or_else(|_| format!("{{\"code\":\"INTERNAL\",\"message\":{:?},\"field\":null,\"details\":null}}", self.message))
+    }
+
+    /// Parse an envelope back out of an error string.
+    ///
+    /// The envelope may be the entire string or embedded within a longer
+    /// message (eg. where the conductor has wrapped the guest error).
+    ///
+    pub fn parse(error: &str) -> Option<Self> {
+        if let Ok(envelope) = serde_json::from_str(error) {
+            return Some(envelope);
+        }
+        let start = error.find('{')?;
+        let end = error.rfind('}')?;
+        if end <= start {
+            return None;
+        }
+        serde_json::from_str(&error[start..=end]).ok()
+    }
+
+    /// Parse an envelope from an error returned by a zome call.
+    ///
+    pub fn from_wasm_error(error: &WasmError) -> Option<Self> {
+        match &error.error {
+            WasmErrorInner::Guest(msg) | WasmErrorInner::CallError(msg) | WasmErrorInner::Host(msg) => Self::parse(msg),
+            _ => None,
+        }
+    }
+
+    pub fn into_wasm_error(self) -> WasmError {
+        wasm_error!(WasmErrorInner::Guest(self.to_json()))
+    }
+}
+
+/// Implemented by error types which can be classified into an `ErrorEnvelope`.
+///
+pub trait ErrorCoded {
+    fn error_envelope(&self) -> ErrorEnvelope;
+}
+
+impl ErrorCoded for WasmError {
+    fn error_envelope(&self) -> ErrorEnvelope {
+        if let Some(envelope) = ErrorEnvelope::from_wasm_error(self) {
+            return envelope;
+        }
+        match &self.error {
+            // guest errors not already enveloped are raised by validation logic within zome code
+            WasmErrorInner::Guest(msg) => ErrorEnvelope::new(ErrorCode::Validation, msg),
+            _ => ErrorEnvelope::new(ErrorCode::Internal, self.error.to_string()),
+        }
+    }
+}
+
+impl ErrorCoded for SemanticIndexError {
+    fn error_envelope(&self) -> ErrorEnvelope {
+        let code = match self {
+            SemanticIndexError::EmptyQuery => ErrorCode::Validation,
+            SemanticIndexError::IndexNotFound(_) => ErrorCode::NotFound,
+            SemanticIndexError::CorruptIndexError(_, _) => ErrorCode::Internal,
+            SemanticIndexError::BadStringIndexError(_) => ErrorCode::Internal,
+            SemanticIndexError::BadTimeIndexError(_) => ErrorCode::Internal,
+            SemanticIndexError::WriteRateExceeded(_, _, _) => ErrorCode::Throttled,
+            SemanticIndexError::UnknownIndexRelationship(_) => ErrorCode::Validation,
+            SemanticIndexError::IndexNameTooLong(_, _) => ErrorCode::Validation,
+            SemanticIndexError::ConflictingRecordType(_, _, _) => ErrorCode::Conflict,
+        };
+        ErrorEnvelope::new(code, self)
+    }
+}
+
+impl ErrorCoded for CrossCellError {
+    fn error_envelope(&self) -> ErrorEnvelope {
+        match self {
+            CrossCellError::Serialization(_) => ErrorEnvelope::new(ErrorCode::Internal, self),
+            CrossCellError::Wasm(e) => e.error_envelope(),
+            CrossCellError::EntryTooLarge(size) => ErrorEnvelope::new(ErrorCode::Validation, self)
+                .with_details(serde_json::json!({ "size": size })),
+            CrossCellError::IndexNotFound(_) => ErrorEnvelope::new(ErrorCode::NotFound, self),
+            CrossCellError::NetworkError(_) => ErrorEnvelope::new(ErrorCode::RemoteUnreachable, self),
+            CrossCellError::Unauthorized(_, zome, func, _) => ErrorEnvelope::new(ErrorCode::Unauthorized, self)
+                .with_details(serde_json::json!({ "zome": zome.to_string(), "function": func.to_string() })),
+            CrossCellError::CellAuthFailed(dna, _) => ErrorEnvelope::new(ErrorCode::Unauthorized, self)
+                .with_details(serde_json::json!({ "dna": dna.to_string() })),
+            // errors passed back from the remote cell retain their original classification
+            CrossCellError::Internal(msg) => ErrorEnvelope::parse(msg)
+                .unwrap_or_else(|| ErrorEnvelope::new(ErrorCode::Internal, self)),
+            CrossCellError::InternalIndexError(_) => ErrorEnvelope::new(ErrorCode::Internal, self),
+            CrossCellError::NotConfigured(_, _) => ErrorEnvelope::new(ErrorCode::Misconfigured, self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_shape(json: &str, code: &str) {
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        let obj = value.as_object().unwrap();
+        assert_eq!(obj.len(), 4);
+        assert_eq!(obj["code"], serde_json::Value::String(code.to_string()));
+        assert!(obj["message"].is_string());
+        assert!(obj.contains_key("field"));
+        assert!(obj.contains_key("details"));
+    }
+
+    #[test]
+    fn test_envelope_json_shape() {
+        let envelope = ErrorEnvelope::new(ErrorCode::Validation, "bad value").with_field("note");
+        let json = envelope.to_json();
+        assert_eq!(json, "{\"code\":\"VALIDATION\",\"message\":\"bad value\",\"field\":\"note\",\"details\":null}");
+        assert_eq!(ErrorEnvelope::parse(&json), Some(envelope));
+    }
+
+    #[test]
+    fn test_envelope_parse() {
+        let envelope = ErrorEnvelope::new(ErrorCode::NotFound, "No entry at this address");
+        let wrapped = format!("Wasm error: Guest({})", envelope.to_json());
+        assert_eq!(ErrorEnvelope::parse(&wrapped), Some(envelope.clone()));
+        assert_eq!(ErrorEnvelope::from_wasm_error(&envelope.clone().into_wasm_error()), Some(envelope));
+        assert_eq!(ErrorEnvelope::parse("No entry at this address"), None);
+    }
+
+    #[test]
+    fn test_cross_cell_error_codes() {
+        let hash = EntryHash::from_raw_36(vec![0xdb; 36]);
+        let cases = vec![
+            (CrossCellError::EntryTooLarge(10), "VALIDATION"),
+            (CrossCellError::IndexNotFound(hash), "NOT_FOUND"),
+            (CrossCellError::NetworkError("timeout".to_string()), "REMOTE_UNREACHABLE"),
+            (CrossCellError::CellAuthFailed(DnaHash::from_raw_36(vec![0xdb; 36]), "denied".to_string()), "UNAUTHORIZED"),
+            (CrossCellError::Internal("failed".to_string()), "INTERNAL"),
+            (CrossCellError::InternalIndexError("failed".to_string()), "INTERNAL"),
+            (CrossCellError::NotConfigured("zome".into(), "method".into()), "MISCONFIGURED"),
+            (CrossCellError::Wasm(wasm_error!(WasmErrorInner::Guest("invalid".to_string()))), "VALIDATION"),
+        ];
+        for (err, code) in cases {
+            let envelope = err.error_envelope();
+            assert_eq!(envelope.message, err.to_string());
+            assert_shape(&envelope.to_json(), code);
+        }
+    }
+
+    #[test]
+    fn test_remote_envelope_passthrough() {
+        let remote = ErrorEnvelope::new(ErrorCode::Conflict, "Conflicting revisions found");
+        let err = CrossCellError::Internal(remote.to_json());
+        assert_eq!(err.error_envelope(), remote);
+    }
+
+    #[test]
+    fn test_semantic_index_error_codes() {
+        assert_eq!(SemanticIndexError::EmptyQuery.error_envelope().code, ErrorCode::Validation);
+        assert_eq!(SemanticIndexError::BadTimeIndexError("x".to_string()).error_envelope().code, ErrorCode::Internal);
+        assert_eq!(SemanticIndexError::WriteRateExceeded("x".to_string(), 1, 1000).error_envelope().code, ErrorCode::Throttled);
+        assert_eq!(SemanticIndexError::UnknownIndexRelationship("x".to_string()).error_envelope().code, ErrorCode::Validation);
+        assert_eq!(SemanticIndexError::IndexNameTooLong(513, 512).error_envelope().code, ErrorCode::Validation);
+        assert_eq!(SemanticIndexError::ConflictingRecordType(EntryHash::from_raw_36(vec![0xdb; 36]), "x".to_string(), "y".to_string()).error_envelope().code, ErrorCode::Conflict);
+    }
+}
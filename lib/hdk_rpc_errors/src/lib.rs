@@ -3,6 +3,9 @@ use hdk::prelude::*;
 use hdk_semantic_indexes_error::*;
 use crate::holo_hash::{EntryHash, DnaHash};
 
+mod envelope;
+pub use envelope::{ErrorCode, ErrorEnvelope, ErrorCoded};
+
 // serializable error and result type for communicating errors between cells
 
 #[derive(Error, Serialize, Deserialize, SerializedBytes, Debug, Clone)]
@@ -34,7 +37,7 @@ pub type OtherCellResult<T> = Result<T, CrossCellError>;
 
 impl From<CrossCellError> for WasmError {
     fn from(e: CrossCellError) -> WasmError {
-        wasm_error!(WasmErrorInner::CallError(e.to_string()))
+        e.error_envelope().into_wasm_error()
     }
 }
 
@@ -30,17 +30,21 @@ use std::collections::HashMap;
 use hdk::prelude::*;
 use holo_hash::DnaHash;
 use hdk_records::{
-    RecordAPIResult, OtherCellResult, SemanticIndexError,
+    RecordAPIResult, OtherCellResult, SemanticIndexError, DataIntegrityError,
     DnaAddressable,
+    log_debug,
     rpc::{
         call_local_zome_method,
         call_zome_method,
+        local_index_read_only,
     },
 };
 use hdk_semantic_indexes_zome_rpc::{
     ByAddress,
     RemoteEntryLinkRequest, RemoteEntryLinkResponse,
+    ResponseMode,
 };
+pub use hdk_semantic_indexes_zome_rpc::RecordRevisionId;
 use hc_zome_dna_auth_resolver_lib::AvailableCapability;
 
 //-------------------------------[ MACRO LAYER ]-------------------------------------
@@ -116,7 +120,7 @@ macro_rules! update_string_index {
     (
         $record_type:ident($record_id:expr).$rel:ident.not($remove_string_ids:expr)<$addressable_type:ident>
     ) => { {
-        let string_hashes: Vec<$addressable_type> = string_index_hashes($dest_string_ids)?;
+        let string_hashes: Vec<$addressable_type> = string_index_hashes($remove_string_ids)?;
         paste! {
             manage_index::<EntryTypes, _, _, _, _, _, _, _, _, _>(
                 [<read_ $record_type:lower:snake _index_zome>],
@@ -125,8 +129,9 @@ macro_rules! update_string_index {
                 |_| { None }, // specify none for destination index
                 &"", // ignored, since no index zome name is returned
                 &"", // ignored, since no index zome name is returned
-                &vec![].as_slice(),
+                vec![].as_slice(),
                 string_hashes.as_slice(),
+                LinkTypes::AvailableCapability
             )
         }
     } };
@@ -146,11 +151,117 @@ macro_rules! update_string_index {
                 &"", // ignored, since no index zome name is returned
                 dest_string_hashes.as_slice(),
                 remove_string_hashes.as_slice(),
+                LinkTypes::AvailableCapability
             )
         }
     } };
 }
 
+/// Declare an index derived from a field of a record's `EntryData`.
+///
+/// `path` maps the field's value to the string identifiers the record should be indexed
+/// under; see `IntoDerivedPaths` for the supported return types. The relationship must be
+/// declared as a `String` index (plus its loopback) in the record's index zome, which
+/// provides querying via the standard `query_<record_type>s` API.
+///
+/// Generates `create_<record_type>_<index>_index`, `update_<record_type>_<index>_index`
+/// and `delete_<record_type>_<index>_index` helpers for maintaining the index over the
+/// record's lifecycle. Updates only write the differences between revisions.
+///
+/// `resync_<record_type>_<index>_index` rebuilds the index of a record from its latest
+/// revision, removing any identifiers it is no longer derived to, and should be called
+/// by the record's `resync_record_indexes` API so that `reindex_all` repairs the index.
+///
+/// A `query_<record_type>s_by_<index>` zome API is also declared, returning the identifiers
+/// of records indexed under the given `value`.
+///
+/// The relationship is named for the field unless given as `index`, eg. to index a
+/// `resource_classified_as` field as `classified_as`.
+///
+/// $addressable_type must be declared as the inner `DnaAddressable` type to use for the internal hash-based reference
+///
+#[macro_export]
+macro_rules! derived_index {
+    (
+        $record_type:ident { field: $field:ident, path: $path:expr }<$addressable_type:ident>
+    ) => {
+        $crate::derived_index!($record_type { field: $field, index: $field, path: $path }<$addressable_type>);
+    };
+    (
+        $record_type:ident { field: $field:ident, index: $index:ident, path: $path:expr }<$addressable_type:ident>
+    ) => {
+        paste! {
+            fn [<$record_type _ $index _derived_paths>](entry: &EntryData) -> Vec<String> {
+                $crate::derive_index_paths(&entry.$field, $path)
+            }
+
+            #[allow(dead_code)]
+            fn [<create_ $record_type _ $index _index>]<A>(address: &A, entry: &EntryData) -> $crate::IndexUpdateResult
+                where A: DnaAddressable<EntryHash>,
+            {
+                $crate::update_string_index!($record_type(address).$index([<$record_type _ $index _derived_paths>](entry))<$addressable_type>)
+            }
+
+            #[allow(dead_code)]
+            fn [<update_ $record_type _ $index _index>]<A>(address: &A, new_entry: &EntryData, prev_entry: &EntryData) -> $crate::IndexUpdateResult
+                where A: DnaAddressable<EntryHash>,
+            {
+                let (added, removed) = $crate::diff_derived_paths(
+                    [<$record_type _ $index _derived_paths>](new_entry),
+                    [<$record_type _ $index _derived_paths>](prev_entry),
+                );
+                if added.is_empty() && removed.is_empty() {
+                    return Ok(vec![]);
+                }
+                $crate::update_string_index!($record_type(address).$index(added).not(removed)<$addressable_type>)
+            }
+
+            #[allow(dead_code)]
+            fn [<delete_ $record_type _ $index _index>]<A>(address: &A, entry: &EntryData) -> $crate::IndexUpdateResult
+                where A: DnaAddressable<EntryHash>,
+            {
+                $crate::update_string_index!($record_type(address).$index.not([<$record_type _ $index _derived_paths>](entry))<$addressable_type>)
+            }
+
+            #[allow(dead_code)]
+            fn [<resync_ $record_type _ $index _index>]<A>(address: &A, entry: &EntryData) -> $crate::IndexUpdateResult
+                where A: DnaAddressable<EntryHash>,
+            {
+                let expected: Vec<$addressable_type> = $crate::string_index_hashes([<$record_type _ $index _derived_paths>](entry))?;
+                let indexed: Vec<$addressable_type> = $crate::read_index!($record_type(address).$index)?;
+                let stale: Vec<$addressable_type> = indexed.into_iter().filter(|id| !expected.contains(id)).collect();
+                $crate::manage_index::<EntryTypes, _, _, _, _, _, _, _, _, _>(
+                    [<read_ $record_type:lower:snake _index_zome>],
+                    &stringify!([<_internal_index_ $record_type:lower:snake _ $index:lower:snake>]),
+                    address,
+                    |_| { None }, // specify none for destination index
+                    &"", // ignored, since no index zome name is returned
+                    &"", // ignored, since no index zome name is returned
+                    expected.as_slice(),
+                    stale.as_slice(),
+                    LinkTypes::AvailableCapability
+                )
+            }
+
+            // declared within a module so that the zome API macros are in scope
+            mod [<__ $record_type _ $index _query_api>] {
+                use hdk::prelude::*;
+                use super::*;
+
+                #[hdk_extern]
+                fn [<query_ $record_type s_by_ $index>](query: $crate::DerivedIndexQuery) -> ExternResult<Vec<$crate::RecordRevisionId<[<$record_type:camel Address>]>>> {
+                    Ok($crate::query_derived_index(
+                        [<read_ $record_type:lower:snake _index_zome>],
+                        &stringify!([<query_ $record_type:lower:snake s>]),
+                        stringify!($index),
+                        query,
+                    )?)
+                }
+            }
+        }
+    };
+}
+
 /// Fetch the identifiers stored for a referenced relationship
 ///
 #[macro_export]
@@ -168,6 +279,24 @@ macro_rules! read_index {
     };
 }
 
+/// Fetch the identifiers stored for a referenced relationship, returning `None`
+/// if the record has never been indexed.
+///
+#[macro_export]
+macro_rules! read_index_if_exists {
+    (
+        $record_type:ident($record_id:expr).$rel:ident
+    ) => {
+        paste! {
+            read_local_index_if_exists(
+                [<read_ $record_type:lower:snake _index_zome>],
+                &stringify!([<_internal_read_ $record_type:lower:snake _ $rel:lower:snake _if_exists>]),
+                $record_id,
+            )
+        }
+    };
+}
+
 /// Update indexes by defining added and removed identifiers.
 /// Local / remote determination is managed by DnaHash of target addresses, and
 /// you can freely mix identifiers from disparate DNAs in the same input.
@@ -435,6 +564,27 @@ pub fn read_local_index<'a, O, A, S, F, C>(
     )?)
 }
 
+/// Variant of `read_local_index` distinguishing records which have never been
+/// indexed (`None`) from those with no entries in the index (`Some(vec![])`).
+///
+pub fn read_local_index_if_exists<'a, O, A, S, F, C>(
+    zome_name_from_config: F,
+    query_fn_name: &S,
+    base_address: &A,
+) -> RecordAPIResult<Option<Vec<O>>>
+    where S: AsRef<str>,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError>,
+        F: FnOnce(C) -> Option<String>,
+        A: DnaAddressable<EntryHash>,
+        O: serde::de::DeserializeOwned + DnaAddressable<EntryHash>,
+{
+    Ok(call_local_zome_method(
+        zome_name_from_config, query_fn_name,
+        ByAddress { address: base_address.to_owned() },
+    )?)
+}
+
 //-------------------------------[ UPDATE ]-------------------------------------
 
 /// Ask another bridged cell to build a 'destination query index' to match the
@@ -473,14 +623,14 @@ fn request_sync_remote_index<EN, A, B, I, LT, E, E2>(
 
     // Call into remote DNA to enable target entries to setup data structures
     // for querying the associated remote entry records back out.
-    Ok(call_zome_method::<EN, _, _, _, _, _, _, _>(
+    skip_read_only_index(call_zome_method::<EN, _, _, _, _, _, _, _>(
         &context_dna, remote_permission_id,
         RemoteEntryLinkRequest::new(
             source,
             dest_addresses, removed_addresses,
         ),
         capability_link_type
-    )?)
+    ))
 }
 
 /// Request for another cell to sync its indexes for a record updated within this cell
@@ -495,17 +645,34 @@ fn request_sync_local_index<C, F, A, B, S>(
     where S: AsRef<str>,
         C: std::fmt::Debug,
         SerializedBytes: TryInto<C, Error = SerializedBytesError>,
-        F: FnOnce(C) -> Option<String>,
+        F: Copy + FnOnce(C) -> Option<String>,
         A: DnaAddressable<EntryHash>,
         B: DnaAddressable<EntryHash>,
 {
-    Ok(call_local_zome_method(
+    if local_index_read_only(zome_name_from_config) {
+        return Ok(RemoteEntryLinkResponse::default());
+    }
+    skip_read_only_index(call_local_zome_method(
         zome_name_from_config, foreign_fn_name,
         RemoteEntryLinkRequest::new(
             source,
             dest_addresses, removed_addresses,
         )
-    )?)
+    ))
+}
+
+/// Treat an index update refused by a read-only follower index zome as having made no changes,
+/// so that record writes in the originating cell are not failed by the follower.
+///
+fn skip_read_only_index(result: OtherCellResult<RemoteEntryLinkResponse>) -> OtherCellResult<RemoteEntryLinkResponse>
+{
+    match result {
+        Err(e) if DataIntegrityError::is_read_only_index(&e) => {
+            log_debug!("manage_index"; "index update skipped by read-only follower {:?}", e);
+            Ok(RemoteEntryLinkResponse::default())
+        },
+        result => result,
+    }
 }
 
 
@@ -568,6 +735,171 @@ fn prefilter_target_dnas<'a, B>(
     )
 }
 
+/// Outcome of an index update made via `manage_index`
+pub type IndexUpdateResult = RecordAPIResult<Vec<OtherCellResult<RemoteEntryLinkResponse>>>;
+
+/// Fail with the first error encountered in an index update, including failures to create or
+/// remove individual links. Use where an index must stay consistent with its record, so that
+/// returning the error from a zome call discards the record write alongside the partial update.
+///
+pub fn throw_if_index_update_failed(result: IndexUpdateResult) -> RecordAPIResult<()>
+{
+    for response in result? {
+        let RemoteEntryLinkResponse { indexes_created, indexes_removed } = response?;
+        for link in indexes_created.into_iter().chain(indexes_removed) {
+            link?;
+        }
+    }
+    Ok(())
+}
+
+/// Outcomes of the index writes made for a record from the field values of one of its revisions,
+/// labelled with the name of each relationship indexed.
+pub type RecordIndexResults = Vec<(&'static str, IndexUpdateResult)>;
+
+/// Log the outcome of each index write made when creating a record. Failed writes do not fail
+/// record creation, and are repaired by resyncing the record's indexes.
+///
+pub fn log_index_results(operation: &str, results: RecordIndexResults)
+{
+    for (index, result) in results {
+        log_debug!(operation, index = index; "{:?}", result);
+    }
+}
+
+/// Fail with the first error encountered when rebuilding the indexes of a record, given the
+/// outcomes of re-running the index writes made on creation against its latest revision.
+/// Index writes are idempotent, so links which already exist are left in place.
+///
+pub fn throw_if_resync_failed(results: RecordIndexResults) -> RecordAPIResult<bool>
+{
+    for (_index, result) in results {
+        throw_if_index_update_failed(result)?;
+    }
+    Ok(true)
+}
+
+/// Conversion of the output of a `derived_index!` path function into index identifiers.
+/// Empty strings are never indexed.
+///
+pub trait IntoDerivedPaths {
+    fn into_derived_paths(self) -> Vec<String>;
+}
+
+impl IntoDerivedPaths for String {
+    fn into_derived_paths(self) -> Vec<String> {
+        vec![self].into_derived_paths()
+    }
+}
+
+impl IntoDerivedPaths for Option<String> {
+    fn into_derived_paths(self) -> Vec<String> {
+        self.into_iter().collect::<Vec<String>>().into_derived_paths()
+    }
+}
+
+impl IntoDerivedPaths for Vec<String> {
+    fn into_derived_paths(self) -> Vec<String> {
+        let mut paths: Vec<String> = vec![];
+        for path in self.into_iter().filter(|p| !p.is_empty()) {
+            if !paths.contains(&path) {
+                paths.push(path);
+            }
+        }
+        paths
+    }
+}
+
+impl IntoDerivedPaths for Option<Vec<String>> {
+    fn into_derived_paths(self) -> Vec<String> {
+        self.unwrap_or_default().into_derived_paths()
+    }
+}
+
+/// Parameters of the `query_<record_type>s_by_<index>` API declared by `derived_index!`
+///
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DerivedIndexQuery {
+    pub value: String,
+}
+
+#[derive(Debug, Serialize)]
+struct DerivedIndexSearch {
+    params: HashMap<String, String>,
+    response_mode: ResponseMode,
+}
+
+#[derive(Debug, Deserialize)]
+struct DerivedIndexResults<A> {
+    #[serde(default)]
+    edges: Vec<DerivedIndexEdge<A>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DerivedIndexEdge<A> {
+    node: RecordRevisionId<A>,
+}
+
+/// Query the index zome of a record type for the records indexed under `value` by a
+/// `derived_index!`, via the `index` parameter of its standard query API.
+///
+pub fn query_derived_index<A, C, F>(
+    zome_name_from_config: F,
+    query_fn_name: &str,
+    index: &str,
+    query: DerivedIndexQuery,
+) -> RecordAPIResult<Vec<RecordRevisionId<A>>>
+    where C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError>,
+        F: FnOnce(C) -> Option<String>,
+        A: serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    let mut params = HashMap::new();
+    params.insert(query_param_name(index), query.value);
+    let results: DerivedIndexResults<A> = call_local_zome_method(
+        zome_name_from_config, query_fn_name,
+        DerivedIndexSearch { params, response_mode: ResponseMode::IdsOnly },
+    )?;
+    Ok(results.edges.into_iter().map(|e| e.node).collect())
+}
+
+/// Convert the snake_case name of an index to the camelCase name of its query parameter.
+///
+fn query_param_name(index: &str) -> String {
+    let mut name = String::with_capacity(index.len());
+    let mut upper = false;
+    for c in index.chars() {
+        if c == '_' {
+            upper = !name.is_empty();
+        } else if upper {
+            name.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            name.push(c);
+        }
+    }
+    name
+}
+
+/// Apply a `derived_index!` path function to a record field.
+/// Declared as a function so that the closure's argument type is inferred from the field.
+///
+pub fn derive_index_paths<T, R, F>(value: &T, derive: F) -> Vec<String>
+    where R: IntoDerivedPaths,
+        F: Fn(&T) -> R,
+{
+    derive(value).into_derived_paths()
+}
+
+/// Determine the derived index identifiers to add and remove when a record changes
+/// from having `prev_paths` to `new_paths`.
+///
+pub fn diff_derived_paths(new_paths: Vec<String>, prev_paths: Vec<String>) -> (Vec<String>, Vec<String>) {
+    let added = new_paths.iter().filter(|p| !prev_paths.contains(p)).cloned().collect();
+    let removed = prev_paths.into_iter().filter(|p| !new_paths.contains(p)).collect();
+    (added, removed)
+}
+
 pub fn string_index_hashes<T>(dest_string_ids: Vec<String>) -> RecordAPIResult<Vec<T>>
     where T: DnaAddressable<EntryHash>,
 {
@@ -589,3 +921,38 @@ fn string_index_hash<T>(index_value: &String) -> RecordAPIResult<T>
     let index_path: Path = index_value.try_into()?;
     Ok(T::new(dna_info()?.hash, index_path.path_entry_hash()?))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_param_name() {
+        assert_eq!(query_param_name("classified_as"), "classifiedAs");
+        assert_eq!(query_param_name("action"), "action");
+        assert_eq!(query_param_name("resource_classified_as"), "resourceClassifiedAs");
+    }
+
+    fn paths(p: &[&str]) -> Vec<String> {
+        p.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_derived_paths_conversion() {
+        assert_eq!("work".to_string().into_derived_paths(), paths(&["work"]));
+        assert_eq!(String::new().into_derived_paths(), paths(&[]));
+        assert_eq!(None::<String>.into_derived_paths(), paths(&[]));
+        assert_eq!(Some(paths(&["a", "b", "a", ""])).into_derived_paths(), paths(&["a", "b"]));
+        assert_eq!(None::<Vec<String>>.into_derived_paths(), paths(&[]));
+    }
+
+    #[test]
+    fn test_diff_derived_paths() {
+        let (added, removed) = diff_derived_paths(paths(&["b", "c"]), paths(&["a", "b"]));
+        assert_eq!(added, paths(&["c"]));
+        assert_eq!(removed, paths(&["a"]));
+
+        let (added, removed) = diff_derived_paths(paths(&["a"]), paths(&["a"]));
+        assert!(added.is_empty() && removed.is_empty(), "unchanged paths require no index update");
+    }
+}
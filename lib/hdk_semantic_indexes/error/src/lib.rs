@@ -17,6 +17,14 @@ pub enum SemanticIndexError {
     BadStringIndexError(Vec<u8>),
     #[error("Time indexing error {0}")]
     BadTimeIndexError(String),
+    #[error("Index write rate exceeded for {0}: at most {1} writes are permitted per {2}ms")]
+    WriteRateExceeded(String, u32, u64),
+    #[error("No index is managed for the relationship {0}")]
+    UnknownIndexRelationship(String),
+    #[error("Index name of {0} bytes exceeds the maximum of {1} bytes")]
+    IndexNameTooLong(usize, usize),
+    #[error("Record at {0} is already indexed as '{1}' and cannot be indexed as '{2}'")]
+    ConflictingRecordType(EntryHash, String, String),
 }
 
 impl From<FromUtf8Error> for SemanticIndexError {
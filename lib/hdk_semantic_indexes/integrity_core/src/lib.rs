@@ -12,9 +12,171 @@ use hdi::prelude::*;
 //     hdk_semantic_indexes_core::LinkTypes::len()
 // }
 
+/// Record of an index link written or removed directly by a network admin, kept for accountability.
+/// The acting agent and time are those of the action which commits the entry.
+///
+#[hdk_entry_helper]
+#[derive(Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexAdminAction {
+    pub operation: IndexAdminOperation,
+    pub source_type: String,
+    pub source: (DnaHash, EntryHash),
+    pub dest_type: String,
+    pub dest: (DnaHash, EntryHash),
+    pub link_tag: String,
+    pub link_tag_reciprocal: String,
+    // whether the link from `source` to `dest` was changed by the operation
+    pub forward_changed: bool,
+    // whether the link from `dest` to `source` was changed by the operation
+    pub reciprocal_changed: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum IndexAdminOperation {
+    Link,
+    Unlink,
+}
+
+impl IndexAdminAction {
+    /// Check that the action names the records and index links it operated on.
+    ///
+    pub fn validate(&self) -> Result<(), String> {
+        if self.source_type.is_empty() || self.dest_type.is_empty() {
+            return Err("Index admin actions must name the types of the records they affect".to_string());
+        }
+        if self.link_tag.is_empty() || self.link_tag_reciprocal.is_empty() {
+            return Err("Index admin actions must name the index links they affect".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Anchor `Path` which all `IndexAdminAction` entries are linked from.
+///
+pub const INDEX_ADMIN_LOG_ANCHOR: &str = "index_admin_log";
+
+// as with `LinkTypes`, entry type externs are exported by the integrity zome @see hc_zome_indexing_integrity
+#[hdk_entry_defs(skip_hdk_extern = true)]
+#[unit_enum(EntryTypesUnit)]
+pub enum EntryTypes {
+    IndexAdminAction(IndexAdminAction),
+}
+
 #[hdk_link_types(skip_no_mangle = true)]
 pub enum LinkTypes {
     EntryUUID,
     SemanticIndex,
     TimeIndex,
+    // links identities written under a newer `IdentityScheme` to the legacy identity holding their index data
+    // @see hdk_records::identities
+    IdentityAlias,
+    // links the admin log anchor to `IndexAdminAction` entries
+    IndexAdminLog,
+}
+
+/// Tag prefix of `IdentityAlias` links. The prefix is followed by the raw `DnaHash` and `EntryHash`
+/// of the record whose identities the link aliases, so that validators can check the link.
+///
+pub const IDENTITY_ALIAS_LINK_TAG: &'static [u8] = b"identity_alias";
+
+/// Build the tag of an `IdentityAlias` link between identities of the record `(dna, entry)`.
+///
+pub fn identity_alias_tag(dna: &DnaHash, entry: &EntryHash) -> LinkTag {
+    LinkTag::new([IDENTITY_ALIAS_LINK_TAG, dna.get_raw_39(), entry.get_raw_39()].concat())
+}
+
+fn decode_identity_alias_tag(tag: &LinkTag) -> Option<(DnaHash, EntryHash)> {
+    let bytes = tag.0.strip_prefix(IDENTITY_ALIAS_LINK_TAG)?;
+    if bytes.len() != 78 {
+        return None;
+    }
+    Some((
+        DnaHash::from_raw_39(bytes[..39].to_vec()).ok()?,
+        EntryHash::from_raw_39(bytes[39..].to_vec()).ok()?,
+    ))
+}
+
+/// Every identity address the record `(dna, entry)` may be indexed under, one per identity scheme.
+/// Must be kept in sync with `hdk_records::identities::calculate_identity_address_for_scheme`.
+///
+fn identity_addresses(dna: &DnaHash, entry: &EntryHash) -> ExternResult<Vec<EntryHash>> {
+    let v2 = Path::from(vec![
+        Component::from("identity"),
+        Component::from("v2"),
+        Component::from(dna.get_raw_39().to_vec()),
+        Component::from(entry.get_raw_39().to_vec()),
+    ]);
+    Ok(vec![entry.to_owned(), v2.path_entry_hash()?])
+}
+
+/// Whether an `IdentityAlias` link from `base` to `target` with the given `tag` links two different
+/// identities of the same record. Aliases cannot then redirect an identity to another record.
+///
+pub fn is_valid_identity_alias(base: &EntryHash, target: &EntryHash, tag: &LinkTag) -> ExternResult<bool> {
+    let (dna, entry) = match decode_identity_alias_tag(tag) {
+        Some(record) => record,
+        None => return Ok(false),
+    };
+    let identities = identity_addresses(&dna, &entry)?;
+    Ok(base != target && identities.contains(base) && identities.contains(target))
+}
+
+/// Validate the creation of an `IdentityAlias` link, @see `is_valid_identity_alias`
+///
+pub fn validate_identity_alias_link(base: &AnyLinkableHash, target: &AnyLinkableHash, tag: &LinkTag) -> ExternResult<ValidateCallbackResult> {
+    let valid = match (base.to_owned().into_entry_hash(), target.to_owned().into_entry_hash()) {
+        (Some(base), Some(target)) => is_valid_identity_alias(&base, &target, tag)?,
+        _ => false,
+    };
+    Ok(if valid {
+        ValidateCallbackResult::Valid
+    } else {
+        ValidateCallbackResult::Invalid("identity aliases must link two identities of the record named by their tag".to_string())
+    })
+}
+
+/// Validate the content of an `IndexAdminAction` entry. Other entries are not checked.
+///
+pub fn validate_index_admin_action_entry(entry: &Entry) -> ExternResult<ValidateCallbackResult> {
+    match IndexAdminAction::try_from(entry) {
+        Ok(action) => Ok(match action.validate() {
+            Ok(()) => ValidateCallbackResult::Valid,
+            Err(e) => ValidateCallbackResult::Invalid(e),
+        }),
+        _ => Ok(ValidateCallbackResult::Valid),
+    }
+}
+
+/// The index admin log is append-only, so `IndexAdminAction` entries may not be updated or deleted.
+///
+pub fn validate_index_admin_action_unmodified(original_entry_address: &EntryHash) -> ExternResult<ValidateCallbackResult> {
+    let original = must_get_entry(original_entry_address.to_owned())?;
+    Ok(match IndexAdminAction::try_from(original.as_content()) {
+        Ok(_) => ValidateCallbackResult::Invalid("Index admin log entries may not be modified".to_string()),
+        _ => ValidateCallbackResult::Valid,
+    })
+}
+
+/// Index admin log links must be based from the log anchor and point at an `IndexAdminAction`
+/// committed by the agent creating the link, so that every logged operation is attributed to its author.
+///
+pub fn validate_index_admin_log_link(author: &AgentPubKey, base: &AnyLinkableHash, target: &AnyLinkableHash) -> ExternResult<ValidateCallbackResult> {
+    let anchor = Path::from(INDEX_ADMIN_LOG_ANCHOR).path_entry_hash()?;
+    if *base != AnyLinkableHash::from(anchor) {
+        return Ok(ValidateCallbackResult::Invalid("Index admin log links may only be based from the log anchor".to_string()));
+    }
+    let action_hash = match target.to_owned().into_action_hash() {
+        Some(action_hash) => action_hash,
+        None => return Ok(ValidateCallbackResult::Invalid("Index admin log links must point to the action committing an index admin action".to_string())),
+    };
+    let logged = must_get_valid_record(action_hash)?;
+    let is_admin_action = logged.entry().as_option().map_or(false, |entry| IndexAdminAction::try_from(entry).is_ok());
+    if !is_admin_action {
+        return Ok(ValidateCallbackResult::Invalid("Index admin log links must point to the action committing an index admin action".to_string()));
+    }
+    if logged.action().author() != author {
+        return Ok(ValidateCallbackResult::Invalid("Only the author of an index admin action may log it".to_string()));
+    }
+    Ok(ValidateCallbackResult::Valid)
 }
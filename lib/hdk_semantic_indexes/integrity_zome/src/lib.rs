@@ -7,6 +7,28 @@
  *
  * @package hREA
  */
+use hdi::prelude::*;
+use hdk_semantic_indexes_core::{
+    EntryTypes, EntryTypesUnit, LinkTypes,
+    validate_identity_alias_link,
+    validate_index_admin_action_entry,
+    validate_index_admin_action_unmodified,
+    validate_index_admin_log_link,
+};
+
+#[hdk_extern]
+pub fn entry_defs(_: ()) -> ExternResult<EntryDefsCallbackResult> {
+    let defs: Vec<EntryDef> = EntryTypes::ENTRY_DEFS
+        .iter()
+        .map(|a| EntryDef::from(a.clone()))
+        .collect();
+    Ok(EntryDefsCallbackResult::from(defs))
+}
+
+#[no_mangle]
+pub fn __num_entry_types() -> u8 {
+    EntryTypesUnit::len()
+}
 
 // Add the extern function that tells Holochain how many links this zome has.
 // this is the equivalent of the thing that we prevent from happening in the 'core' itself
@@ -14,4 +36,31 @@
 #[no_mangle]
 pub fn __num_link_types() -> u8 {
     hdk_semantic_indexes_core::LinkTypes::len()
-}
\ No newline at end of file
+}
+#[hdk_extern]
+pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+    match op {
+        Op::StoreEntry(StoreEntry { entry, .. }) => validate_index_admin_action_entry(&entry),
+        Op::RegisterUpdate(RegisterUpdate { update, .. }) => validate_index_admin_action_unmodified(
+            &update.hashed.content.original_entry_address,
+        ),
+        Op::RegisterDelete(RegisterDelete { delete }) => validate_index_admin_action_unmodified(
+            &delete.hashed.content.deletes_entry_address,
+        ),
+        Op::RegisterCreateLink(RegisterCreateLink { create_link }) => {
+            let link = &create_link.hashed.content;
+            match LinkTypes::from_type(link.zome_index, link.link_type)? {
+                Some(LinkTypes::IdentityAlias) => validate_identity_alias_link(&link.base_address, &link.target_address, &link.tag),
+                Some(LinkTypes::IndexAdminLog) => validate_index_admin_log_link(&link.author, &link.base_address, &link.target_address),
+                _ => Ok(ValidateCallbackResult::Valid),
+            }
+        },
+        Op::RegisterDeleteLink(RegisterDeleteLink { create_link, .. }) => {
+            match LinkTypes::from_type(create_link.zome_index, create_link.link_type)? {
+                Some(LinkTypes::IndexAdminLog) => Ok(ValidateCallbackResult::Invalid("Index admin log links may not be removed".to_string())),
+                _ => Ok(ValidateCallbackResult::Valid),
+            }
+        },
+        _ => Ok(ValidateCallbackResult::Valid),
+    }
+}
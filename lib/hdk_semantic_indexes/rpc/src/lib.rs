@@ -8,7 +8,8 @@
 use chrono::{DateTime, Utc};
 use holochain_serialized_bytes::prelude::*;
 pub use hdk_uuid_types::{DnaAddressable, EntryHash, ActionHash};
-pub use hdk_rpc_errors::{OtherCellResult, CrossCellError};
+use hdk_uuid_types::{DnaHash, AgentPubKey};
+pub use hdk_rpc_errors::{OtherCellResult, CrossCellError, ErrorCode, ErrorEnvelope};
 
 //--------------- API I/O STRUCTS ----------------
 
@@ -29,6 +30,41 @@ pub struct ByAddress<T> {
     pub address: T,
 }
 
+/// Shared parameter struct for endpoints operating on many records at once
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ByAddresses<T> {
+    pub addresses: Vec<T>,
+}
+
+/// Outcome of reading one of the records requested from an endpoint operating on many
+/// records at once. Exactly one of `record` or `error` is present.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ByAddressResult<T, R> {
+    pub address: T,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub record: Option<R>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl<T, R> ByAddressResult<T, R> {
+    pub fn new<E: std::fmt::Display>(address: T, result: Result<R, E>) -> Self {
+        match result {
+            Ok(record) => ByAddressResult { address, record: Some(record), error: None },
+            Err(e) => ByAddressResult { address, record: None, error: Some(e.to_string()) },
+        }
+    }
+
+    /// Convert into the record read, or a description of why it could not be read.
+    pub fn into_result(self) -> Result<R, String> {
+        match (self.record, self.error) {
+            (Some(record), _) => Ok(record),
+            (None, Some(error)) => Err(error),
+            (None, None) => Err("record not returned".to_string()),
+        }
+    }
+}
+
 /// Shared parameter struct for indexing endpoints to respond to record creation
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AppendAddress<T> {
@@ -36,6 +72,208 @@ pub struct AppendAddress<T> {
     pub timestamp: DateTime<Utc>,
 }
 
+/// Parameters for reading an index, optionally including links which have been deprecated.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadIndexParams<T> {
+    pub address: T,
+    #[serde(default)]
+    pub include_deprecated: bool,
+}
+
+/// Parameters for reading only the index entries which were linked by `author`.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReadIndexByAuthorParams<T> {
+    pub address: T,
+    pub author: AgentPubKey,
+}
+
+/// Parameters for deprecating the index links between a record and one of its `target`s.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DeprecateIndexParams<A, B> {
+    pub address: A,
+    pub target: B,
+}
+
+/// Content of the results returned by index queries.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseMode {
+    // full record data, as returned by the record's read API
+    Full,
+    // identifiers of each record and its latest revision only, @see `RecordRevisionId`
+    IdsOnly,
+}
+
+impl Default for ResponseMode {
+    fn default() -> Self {
+        ResponseMode::Full
+    }
+}
+
+/// Identifiers of a record and its latest revision, returned by queries in `ResponseMode::IdsOnly`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordRevisionId<T> {
+    pub id: T,
+    pub revision_id: ActionHash,
+}
+
+/// Signal payload carrying one chunk of the results of a streamed index query.
+///
+/// Chunks are emitted in result order and numbered by `sequence` from 0. Signals are
+/// delivered at most once, and may arrive after the query's `QueryStreamSummary`.
+///
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryStreamChunk<T> {
+    // identifier given by the client, distinguishing concurrent streams
+    pub stream_id: String,
+    pub sequence: u32,
+    pub results: Vec<T>,
+    // descriptions of the results in this chunk which could not be read
+    pub errors: Vec<String>,
+}
+
+/// Response to a streamed index query, returned once every chunk has been emitted.
+///
+/// Clients have received the results of the call once they hold `chunks` distinct chunks.
+/// Chunks listed in `chunks_failed` were never sent and will not arrive.
+/// `next_cursor` is `None` once every result has been streamed. Otherwise, pass it as `after`
+/// to stream the remaining results.
+///
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryStreamSummary {
+    pub stream_id: String,
+    pub chunks: u32,
+    pub chunks_failed: Vec<u32>,
+    pub total_results: u32,
+    pub total_errors: u32,
+    pub next_cursor: Option<EntryHash>,
+}
+
+/// Paging parameters for administrative operations which walk every record in an index.
+/// `after` is the `next_cursor` returned by a previous invocation.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReindexParams {
+    #[serde(default)]
+    pub after: Option<EntryHash>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Progress report for a single invocation of a batched reindexing operation.
+/// `next_cursor` is `None` once every record in the index has been visited.
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReindexReport {
+    pub reindexed: u32,
+    pub failures: Vec<ReindexFailure>,
+    pub next_cursor: Option<EntryHash>,
+}
+
+/// A batch of record addresses read from a time-ordered index.
+/// `next_cursor` is `None` once every record in the index has been visited.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedAddresses<T> {
+    pub addresses: Vec<T>,
+    pub next_cursor: Option<EntryHash>,
+}
+
+/// A record which could not be reindexed, identified by its identity hash in the index
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReindexFailure {
+    pub identity: EntryHash,
+    pub error: String,
+}
+
+/// Parameters for pre-fetching the time indexes of a set of record types, eg. `economic_event`.
+/// `depth` is the number of most recent entries to touch in each index.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WarmIndexesParams {
+    pub types: Vec<String>,
+    #[serde(default)]
+    pub depth: Option<usize>,
+}
+
+/// Counts of index links touched per record type when warming indexes.
+/// `skipped` lists any requested types beyond the batch limit, to be passed to a subsequent call.
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WarmIndexesReport {
+    pub touched: Vec<WarmedIndex>,
+    pub skipped: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct WarmedIndex {
+    pub record_type: String,
+    pub links_touched: u32,
+}
+
+/// An entry read from the full history of an index. `removed_at` is `None` for entries
+/// which are currently indexed, or the time of removal for those retained as tombstones.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexHistoryEntry<T> {
+    pub address: T,
+    pub removed_at: Option<DateTime<Utc>>,
+}
+
+/// Description of a single index managed by an index zome, for documentation and diagnostics.
+/// Link tag names are given prior to versioning & encoding via `index_link_tag`.
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexDescriptor {
+    /// relationship name, as declared on the index zome
+    pub name: String,
+    /// type of the records managed by the zome
+    pub record_type: String,
+    /// type of the records on the other side of the relationship
+    pub related_record_type: String,
+    /// tag of links from managed records to related records
+    pub link_tag: String,
+    /// tag of links from related records back to managed records
+    pub reciprocal_link_tag: String,
+    /// time index used to order related records
+    pub time_index: String,
+    /// zome API method which updates the index
+    pub update_method: String,
+}
+
+/// Parameters identifying a single bidirectional index link, for administrative repair of index data.
+/// Record types and link tags are those reported by `IndexDescriptor`, in either orientation.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexLinkAdminParams {
+    pub source_type: String,
+    pub source_address: (DnaHash, EntryHash),
+    pub dest_type: String,
+    pub dest_address: (DnaHash, EntryHash),
+    pub link_tag: String,
+    pub link_tag_reciprocal: String,
+}
+
+/// Outcome of an administrative link or unlink operation. `warning` is set where
+/// neither direction of the link was changed by the operation.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexLinkAdminReport {
+    /// whether the link from source to destination was changed
+    pub forward: bool,
+    /// whether the link from destination back to source was changed
+    pub reciprocal: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
 /// Common request format (zome trait) for linking remote entries in cooperating DNAs
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct RemoteEntryLinkRequest<A, B>
@@ -85,8 +323,27 @@ impl<A, B> RemoteEntryLinkRequest<A, B>
 }
 
 /// Common response format for zomes handling indexes to report status to calling integrity zomes
-#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone)]
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, Default)]
 pub struct RemoteEntryLinkResponse {
     pub indexes_created: Vec<OtherCellResult<ActionHash>>,
     pub indexes_removed: Vec<OtherCellResult<ActionHash>>,
 }
+
+/// Signal payload emitted by index zomes after an index update, where signals are enabled
+/// via `IndexingZomeConfig.emit_index_signals`.
+///
+/// Intended for clients needing to invalidate cached query results. Addresses are
+/// provided in their string-encoded form, so that listeners need not know the index's types.
+///
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexChangeSignal {
+    pub index_name: String,
+    pub reciprocal_index_name: String,
+    pub source: String,
+    pub targets_added: Vec<String>,
+    pub targets_removed: Vec<String>,
+    // number of index links successfully written & deleted
+    pub links_created: usize,
+    pub links_removed: usize,
+}
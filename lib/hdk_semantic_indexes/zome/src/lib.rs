@@ -5,42 +5,319 @@
  * @package hdk_semantic_indexes
  * @since   2021-09-30
  */
+use std::cell::Cell;
+use std::collections::{BTreeSet, HashSet};
 use chrono::{DateTime, Utc};
+use unicode_normalization::UnicodeNormalization;
 use hdk::prelude::*;
-use holo_hash::{DnaHash, HOLO_HASH_FULL_LEN};
+use holo_hash::HOLO_HASH_FULL_LEN;
+pub use holo_hash::DnaHash;
 use hdk_records::{
-    identities::calculate_identity_address,
+    identities::{
+        IdentityScheme,
+        read_identity_scheme_config,
+        identity_address_candidates,
+        calculate_identity_address_for_scheme,
+    },
     rpc::call_local_zome_method,
+    records::get_latest_revisions,
+    metadata::read_record_author,
+    logging::short_hash,
+    log_debug,
+    log_warn,
 };
-use hdk_time_indexing::{ index_entry };
+use hdk_time_indexing::{ index_entry, remove_entry, remove_entries, read_entry_hashes_before, read_entry_hashes_between, get_entry_index_time, get_latest_entry_hashes, get_older_entry_hashes };
 pub use hdk_semantic_indexes_error::*;
 pub use hdk_time_indexing::{
     TimeIndex,
     TimeIndexingError,
     read_all_entry_hashes,
+    read_oldest_entry_hashes,
     // get_latest_entry_hashes,
     // get_older_entry_hashes,
     sort_entries_by_time_index,
 };
 pub use hdk_records::{
-    RecordAPIResult, DnaAddressable,
+    RecordAPIResult, DataIntegrityError, DnaAddressable,
 };
 pub use hdk_semantic_indexes_zome_rpc::*;
 pub use hdk_relay_pagination::PageInfo;
-pub use hdk_semantic_indexes_core::LinkTypes;
+pub use hdk_semantic_indexes_core::{LinkTypes, IndexAdminAction, IndexAdminOperation, INDEX_ADMIN_LOG_ANCHOR};
+use hdk_semantic_indexes_core::{EntryTypes, identity_alias_tag, is_valid_identity_alias};
 
 // temporary: @see query_root_index()
 pub const RECORD_GLOBAL_INDEX_LINK_TAG: &'static [u8] = b"all_entries";
 
+/// Separator between the record type and name of a time-ordered index, eg. `economic_resource.expiry`
+///
+pub const TIME_INDEX_NAMESPACE_SEPARATOR: char = '.';
+
+/// Name of the time index ordering records of each type by creation, eg. `economic_event.created`
+///
+pub const CREATED_TIME_INDEX_NAME: &'static str = "created";
+
+/// Name of the time indexes ordering records referenced from remote DNAs, eg. `economic_event_input_of.indexed`.
+/// These are namespaced by relationship rather than by record type.
+///
+pub const REMOTE_RECORD_TIME_INDEX_NAME: &'static str = "indexed";
+
+/// Build the name of the time-ordered index `name` for records of `record_type`.
+///
+/// Time indexes are identified by name alone, so all time index names should be built
+/// via this method to prevent record types using the same `name` from sharing an index.
+///
+pub fn time_index_name<R, N>(record_type: R, name: N) -> String
+    where R: AsRef<str>,
+        N: AsRef<str>,
+{
+    format!("{}{}{}", record_type.as_ref(), TIME_INDEX_NAMESPACE_SEPARATOR, name.as_ref())
+}
+
+/// Ensure `index_name` is namespaced by record type, as built by `time_index_name`.
+///
+/// Where the current zome has registered its time indexes via `register_time_indexes`,
+/// also ensure `index_name` is one of them and is namespaced by the zome's record type.
+///
+pub fn validate_time_index_name<I>(index_name: &I) -> RecordAPIResult<()>
+    where I: AsRef<str> + ?Sized,
+{
+    match index_name.as_ref().split_once(TIME_INDEX_NAMESPACE_SEPARATOR) {
+        Some((record_type, name)) if !record_type.is_empty() && !name.is_empty() => (),
+        _ => return Err(SemanticIndexError::BadTimeIndexError(
+            format!("time index name '{}' is not namespaced by record type", index_name.as_ref())
+        ).into()),
+    };
+    match REGISTERED_TIME_INDEXES.with(Cell::get) {
+        Some(registry) => registry.check(index_name.as_ref()),
+        None => Ok(()),
+    }
+}
+
+/// The time-ordered indexes known to an index zome.
+///
+/// `names` and `prefixes` are given without the `record_type` namespace that all of the zome's
+/// time indexes are built with. `names` are matched exactly. `prefixes` match indexes named
+/// dynamically, for example `state.` for a set of indexes named by process state.
+/// `remote_namespaces` are the relationships whose records referenced from remote DNAs
+/// are ordered by a `REMOTE_RECORD_TIME_INDEX_NAME` index.
+///
+#[derive(Debug)]
+pub struct TimeIndexRegistry {
+    pub record_type: &'static str,
+    pub names: &'static [&'static str],
+    pub prefixes: &'static [&'static str],
+    pub remote_namespaces: &'static [&'static str],
+    // ordering applied to entries missing from a time index, read from the zome's configuration
+    pub unindexed_order: fn() -> UnindexedOrder,
+    // encoding of record addresses in identity link tags, read from the zome's configuration
+    pub tag_address_mode: fn() -> TagAddressMode,
+    // zomes storing each type of record retrieved by the zome, read from the zome's configuration
+    pub record_storage_zomes: fn() -> Option<Vec<(String, String)>>,
+}
+
+impl TimeIndexRegistry {
+    /// Ensure `index_name` is known to this registry, or else list the names which are.
+    ///
+    pub fn check(&self, index_name: &str) -> RecordAPIResult<()> {
+        let known = match index_name.split_once(TIME_INDEX_NAMESPACE_SEPARATOR) {
+            Some((namespace, name)) if namespace == self.record_type =>
+                self.names.iter().any(|n| *n == name)
+                    || self.prefixes.iter().any(|p| name.starts_with(p) && name.len() > p.len()),
+            Some((namespace, REMOTE_RECORD_TIME_INDEX_NAME)) => self.remote_namespaces.iter().any(|ns| *ns == namespace),
+            _ => false,
+        };
+        match known {
+            true => Ok(()),
+            false => Err(DataIntegrityError::UnknownTimeIndex {
+                name: index_name.to_string(),
+                known: self.names.iter().map(|n| time_index_name(self.record_type, n))
+                    .chain(self.remote_namespaces.iter().map(|ns| time_index_name(ns, REMOTE_RECORD_TIME_INDEX_NAME)))
+                    .chain(self.prefixes.iter().map(|p| format!("{}*", time_index_name(self.record_type, p))))
+                    .collect(),
+            }),
+        }
+    }
+}
+
+thread_local! {
+    static REGISTERED_TIME_INDEXES: Cell<Option<&'static TimeIndexRegistry>> = Cell::new(None);
+    static QUERY_ACCESS_GRANTED: Cell<bool> = Cell::new(false);
+}
+
+/// Declare the time indexes managed by the current zome, so that index names passed to
+/// the time index APIs of this module are checked against them. Registration lasts for the
+/// lifetime of the WASM instance, so should be repeated at the start of each zome call.
+///
+pub fn register_time_indexes(registry: &'static TimeIndexRegistry) {
+    REGISTERED_TIME_INDEXES.with(|r| r.set(Some(registry)));
+    QUERY_ACCESS_GRANTED.with(|g| g.set(false));
+}
+
+/// Read the `UnindexedOrder` of the current zome's registered time indexes, or the default
+/// ordering where none have been registered.
+///
+fn registered_unindexed_order() -> UnindexedOrder {
+    REGISTERED_TIME_INDEXES.with(Cell::get)
+        .map_or_else(UnindexedOrder::default, |registry| (registry.unindexed_order)())
+}
+
+/// Read the `TagAddressMode` of the current zome, or the default mode where the zome
+/// has not registered its indexes.
+///
+fn registered_tag_address_mode() -> TagAddressMode {
+    REGISTERED_TIME_INDEXES.with(Cell::get)
+        .map_or_else(TagAddressMode::default, |registry| (registry.tag_address_mode)())
+}
+
+/// Read the zomes storing each type of record retrieved by the current zome, or `None` where
+/// the zome retrieves all records from its single `record_storage_zome`.
+///
+fn registered_record_storage_zomes() -> Option<Vec<(String, String)>> {
+    REGISTERED_TIME_INDEXES.with(Cell::get)
+        .and_then(|registry| (registry.record_storage_zomes)())
+}
+
 pub const RECORD_IDENTITY_LINK_TAG: &'static [u8] = b"id|"; // :WARNING: byte length is important here. @see read_remote_entry_identity
 
+/// Tag prefix of the link from a record's identity to itself which records the type of the record,
+/// being the record type namespace of the time indexes it was appended to. @see time_index_name
+///
+pub const RECORD_IDENTITY_TYPE_LINK_TAG: &'static [u8] = b"entry_type|";
+
+pub use hdk_semantic_indexes_core::IDENTITY_ALIAS_LINK_TAG as RECORD_IDENTITY_ALIAS_LINK_TAG;
+
+/// Schema version byte prefixed to all newly written `SemanticIndex` link tags.
+///
+/// Increment this when the meaning of an index tag changes, so that links written under
+/// the previous semantics can be told apart. Legacy (unversioned) tags are still read.
+///
+pub const SEMANTIC_INDEX_TAG_VERSION: u8 = 1;
+
+/// Tag prefix of the link from a record's identity to itself which records the
+/// `SEMANTIC_INDEX_TAG_VERSION` its index links were written under.
+///
+pub const RECORD_INDEX_SCHEMA_LINK_TAG: &'static [u8] = b"schema|";
+
+/// Maximum byte length of the relationship name encoded into a `SemanticIndex` link tag.
+/// Longer names are rejected by `encode_index_tag_name`.
+///
+pub const MAX_INDEX_TAG_NAME_BYTES: usize = 512;
+
+/// Prefix of the tags of tombstone links, which replace removed `SemanticIndex` links where
+/// `IndexingZomeConfig.tombstone_removed_links` is set.
+///
+/// The prefix is followed by the removal time (as big-endian microseconds) and then by the
+/// tag of the removed link, so tombstones never match the tag prefixes of live index reads.
+///
+pub const RECORD_TOMBSTONE_LINK_TAG_PREFIX: &'static [u8] = b"deleted.";
+
+/// Prefix of the tags of deprecated links, which replace `SemanticIndex` links for relationships
+/// which have been superseded but should be retained as history.
+///
+/// The prefix is followed by the tag of the deprecated link.
+///
+pub const RECORD_DEPRECATED_LINK_TAG_PREFIX: &'static [u8] = b"deprecated.";
+
 //--------------- ZOME CONFIGURATION ATTRIBUTES ----------------
 
 /// Configuration attributes from indexing zomes which link to records in other zomes
 #[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq)]
 pub struct IndexingZomeConfig {
     // Index zome will call to the specified zome to retrieve records by identity hash.
+    #[serde(default)]
     pub record_storage_zome: String,
+    // If set, records are instead retrieved from the zome paired with the first entry type prefix matching
+    // the type of each record, falling back to `record_storage_zome` for records of other or unknown type.
+    #[serde(default)]
+    pub record_storage_zomes: Option<Vec<(String, String)>>,
+    // If set, an `IndexChangeSignal` is emitted whenever an index managed by the zome is updated.
+    #[serde(default)]
+    pub emit_index_signals: bool,
+    // If set, index updates are rejected once a single source exceeds the configured write rate.
+    #[serde(default)]
+    pub write_rate_limit: Option<IndexWriteRateLimit>,
+    // If set, removed index links are replaced with tombstone links recording the time of removal.
+    #[serde(default)]
+    pub tombstone_removed_links: bool,
+    // If set, administrative operations on index links are permitted for the network admins listed in this zome's settings.
+    #[serde(default)]
+    pub network_settings_zome: Option<String>,
+    // If set, the zome serves queries only and refuses all index writes, eg. for cells following another network.
+    #[serde(default)]
+    pub read_only: bool,
+    // Ordering of query results which are missing from the time index being sorted by, eg. while it is being populated.
+    #[serde(default)]
+    pub unindexed_order: UnindexedOrder,
+    // Encoding of record addresses written into identity link tags.
+    #[serde(default)]
+    pub tag_address_mode: TagAddressMode,
+    // Zome holding the associations between agents and their `Agent` profiles, checked by members-only queries.
+    #[serde(default)]
+    pub agent_zome: Option<String>,
+}
+
+/// Access to the query APIs of index zomes, read from the `query_access` DNA property.
+///
+/// Index writes are unaffected by this setting.
+///
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryAccess {
+    // any agent in the network may query
+    Open,
+    // only agents with an associated `Agent` profile, or network admins, may query
+    MembersOnly,
+}
+
+impl Default for QueryAccess {
+    fn default() -> Self {
+        QueryAccess::Open
+    }
+}
+
+/// Fallback ordering for index reads, applied to entries which have no time in the time index being sorted by.
+///
+/// Such entries always follow those which are time indexed.
+///
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UnindexedOrder {
+    // by the bytes of each entry's hash, ascending; stable across agents and reads
+    EntryHash,
+    // by the time each entry was first linked into the index being read, most recent first
+    LinkCreated,
+}
+
+impl Default for UnindexedOrder {
+    fn default() -> Self {
+        UnindexedOrder::EntryHash
+    }
+}
+
+/// Encoding of the `DnaAddressable` record addresses written into identity link tags.
+///
+/// Tags written in either mode are decoded regardless of the zome's current mode.
+///
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TagAddressMode {
+    // the `DnaHash` and `EntryHash` of the address
+    FullAddress,
+    // the `EntryHash` only; decoded addresses are assigned the DNA of the reading cell
+    EntryHashOnly,
+}
+
+impl Default for TagAddressMode {
+    fn default() -> Self {
+        TagAddressMode::FullAddress
+    }
+}
+
+/// Maximum number of index updates accepted for any one source record within a rolling window
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, Copy, PartialEq)]
+pub struct IndexWriteRateLimit {
+    pub max_writes: u32,
+    pub window_ms: u64,
 }
 
 //--------------------------------[ READ ]--------------------------------------
@@ -64,9 +341,47 @@ pub fn read_index<'a, O, A, S, I>(
         O: DnaAddressable<EntryHash>,
         SerializedBytes: TryInto<O, Error = SerializedBytesError>,
 {
-    let index_address = calculate_identity_address(base_address)?;
-    let mut refd_index_addresses = get_linked_addresses(&index_address, LinkTag::new(link_tag.as_ref()))?;
-    refd_index_addresses.sort_by(sort_entries_by_time_index(order_by_time_index));
+    let index_address = resolve_identity_address(base_address)?;
+    read_index_at(&index_address, link_tag, order_by_time_index)
+}
+
+/// Variant of `read_index` which distinguishes an index base that has never been
+/// written from one which exists but has no entries for `link_tag`.
+///
+/// Returns `None` where no identity has been created for `base_address`, and
+/// `Some(vec![])` where the base exists but has nothing linked via this index.
+///
+pub fn read_index_if_exists<'a, O, A, S, I>(
+    base_address: &A,
+    link_tag: &S,
+    order_by_time_index: &I,
+) -> RecordAPIResult<Option<Vec<O>>>
+    where S: 'a + AsRef<[u8]> + ?Sized + std::fmt::Debug,
+        I: AsRef<str> + std::fmt::Debug,
+        A: DnaAddressable<EntryHash>,
+        O: DnaAddressable<EntryHash>,
+        SerializedBytes: TryInto<O, Error = SerializedBytesError>,
+{
+    let index_address = resolve_identity_address(base_address)?;
+    if read_identity_state(&index_address)? == IdentityState::Missing {
+        return Ok(None);
+    }
+    Ok(Some(read_index_at(&index_address, link_tag, order_by_time_index)?))
+}
+
+fn read_index_at<'a, O, S, I>(
+    index_address: &EntryHash,
+    link_tag: &S,
+    order_by_time_index: &I,
+) -> RecordAPIResult<Vec<O>>
+    where S: 'a + AsRef<[u8]> + ?Sized + std::fmt::Debug,
+        I: AsRef<str> + std::fmt::Debug,
+        O: DnaAddressable<EntryHash>,
+        SerializedBytes: TryInto<O, Error = SerializedBytesError>,
+{
+    validate_time_index_name(order_by_time_index)?;
+    verify_index_schema(index_address)?;
+    let refd_index_addresses = sort_links_by_time_index(get_index_links(index_address, link_tag)?, order_by_time_index);
 
     let (existing_link_results, read_errors): (Vec<RecordAPIResult<O>>, Vec<RecordAPIResult<O>>) = refd_index_addresses.iter()
         .map(read_remote_entry_identity)
@@ -80,11 +395,119 @@ pub fn read_index<'a, O, A, S, I>(
         .collect())
 }
 
+/// Variant of `read_index` which also returns entries whose links have been deprecated
+/// via `deprecate_index` if `include_deprecated` is set.
+///
+/// Current entries are returned first, followed by deprecated entries; each in reverse creation order.
+///
+pub fn read_index_with_deprecated<'a, O, A, S, I>(
+    base_address: &A,
+    link_tag: &S,
+    order_by_time_index: &I,
+    include_deprecated: bool,
+) -> RecordAPIResult<Vec<O>>
+    where S: 'a + AsRef<[u8]> + ?Sized + std::fmt::Debug,
+        I: AsRef<str> + std::fmt::Debug,
+        A: DnaAddressable<EntryHash>,
+        O: DnaAddressable<EntryHash>,
+        SerializedBytes: TryInto<O, Error = SerializedBytesError>,
+{
+    let index_address = resolve_identity_address(base_address)?;
+    let mut results: Vec<O> = read_index_at(&index_address, link_tag, order_by_time_index)?;
+    if !include_deprecated {
+        return Ok(results);
+    }
+
+    let mut deprecated = get_deprecated_links(&index_address, link_tag)?;
+    deprecated.sort_by(sort_entries_by_time_index(order_by_time_index));
+    for identity in deprecated.iter() {
+        let address: O = read_remote_entry_identity(identity)?;
+        if !results.contains(&address) {
+            results.push(address);
+        }
+    }
+
+    Ok(results)
+}
+
+/// Variant of `read_index` which only returns entries linked by `author`, as determined
+/// by the provenance of each link's `CreateLink` action.
+///
+/// Use this to attribute the contents of shared indexes to the agents who added them.
+/// The returned identities are sorted in reverse creation order.
+///
+pub fn read_index_by_author<'a, O, A, S, I>(
+    base_address: &A,
+    link_tag: &S,
+    order_by_time_index: &I,
+    author: &AgentPubKey,
+) -> RecordAPIResult<Vec<O>>
+    where S: 'a + AsRef<[u8]> + ?Sized + std::fmt::Debug,
+        I: AsRef<str> + std::fmt::Debug,
+        A: DnaAddressable<EntryHash>,
+        O: DnaAddressable<EntryHash>,
+        SerializedBytes: TryInto<O, Error = SerializedBytesError>,
+{
+    validate_time_index_name(order_by_time_index)?;
+    let index_address = resolve_identity_address(base_address)?;
+
+    let mut authored: Vec<Link> = Vec::new();
+    for link in get_index_links(&index_address, link_tag)? {
+        if is_link_authored_by(&link, author)? {
+            authored.push(link);
+        }
+    }
+    let addresses = sort_links_by_time_index(authored, order_by_time_index);
+
+    addresses.iter()
+        .map(read_remote_entry_identity)
+        .collect()
+}
+
+/// Reads all entry identities ever referenced by the given index from `base_address` via
+/// `link_tag`, including those whose links have since been replaced by tombstones.
+///
+/// Current entries are returned first, in reverse creation order, followed by removed
+/// entries from most to least recently removed. An identity may appear more than once
+/// if it has been removed and re-added.
+///
+pub fn read_index_history<'a, O, A, S, I>(
+    base_address: &A,
+    link_tag: &S,
+    order_by_time_index: &I,
+) -> RecordAPIResult<Vec<IndexHistoryEntry<O>>>
+    where S: 'a + AsRef<[u8]> + ?Sized + std::fmt::Debug,
+        I: AsRef<str> + std::fmt::Debug,
+        A: DnaAddressable<EntryHash>,
+        O: DnaAddressable<EntryHash>,
+        SerializedBytes: TryInto<O, Error = SerializedBytesError>,
+{
+    let index_address = resolve_identity_address(base_address)?;
+
+    let mut history: Vec<IndexHistoryEntry<O>> = read_index_at(&index_address, link_tag, order_by_time_index)?
+        .into_iter()
+        .map(|address| IndexHistoryEntry { address, removed_at: None })
+        .collect();
+
+    for (identity, removed_at) in get_tombstone_links(&index_address, link_tag)? {
+        let removed_at: DateTime<Utc> = removed_at.try_into()
+            .map_err(|e: TimestampError| SemanticIndexError::BadTimeIndexError(e.to_string()))?;
+        history.push(IndexHistoryEntry {
+            address: read_remote_entry_identity(&identity)?,
+            removed_at: Some(removed_at),
+        });
+    }
+
+    Ok(history)
+}
+
 /// Given a base address to query from, returns a Vec of tuples of all target
 /// `EntryHash`es referenced via the given link tag, bound to the result of
 /// attempting to decode each referenced entry into the requested type `R`.
 ///
 /// Use this method to query associated records for a query edge in full.
+/// Records whose index links have been replaced by tombstones are only included if
+/// `include_tombstones` is set.
 ///
 pub fn query_index<'a, T, O, C, F, A, S, I, J>(
     base_address: &A,
@@ -92,6 +515,7 @@ pub fn query_index<'a, T, O, C, F, A, S, I, J>(
     order_by_time_index: &I,
     foreign_zome_name_from_config: &F,
     foreign_read_method_name: &J,
+    include_tombstones: bool,
 ) -> RecordAPIResult<Vec<RecordAPIResult<T>>>
     where I: AsRef<str> + std::fmt::Debug,
         J: AsRef<str>,
@@ -103,9 +527,7 @@ pub fn query_index<'a, T, O, C, F, A, S, I, J>(
         SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<O, Error = SerializedBytesError>,
         F: Fn(C) -> Option<String>,
 {
-    let index_address = calculate_identity_address(base_address)?;
-    let mut addrs_result = get_linked_addresses(&index_address, LinkTag::new(link_tag.as_ref()))?;
-    addrs_result.sort_by(sort_entries_by_time_index(order_by_time_index));
+    let addrs_result = query_index_addresses(base_address, link_tag, order_by_time_index, include_tombstones)?;
 
     let entries = retrieve_foreign_records::<T, O, C, F, J>(
         foreign_zome_name_from_config,
@@ -115,434 +537,2726 @@ pub fn query_index<'a, T, O, C, F, A, S, I, J>(
     Ok(entries)
 }
 
-/// Query foreign entries pointers from a time-ordered index, in order from most recent to oldest.
-///
-/// If `start_from` is provided, the given `EntryHash` is used to determine the starting location
-/// for reading results. Otherwise the newest entries (as determined by their ordering in the time
-/// index) are returned.
-///
-/// Full entry data is returned by querying from the associated record storage zome determined by
-/// `zome_name_from_config` and `read_method_name`.
+/// Variant of `query_index` which returns the identifiers of each record and its latest
+/// revision in place of full record data, in the same order.
 ///
-pub fn query_time_index<'a, T, B, C, F, I>(
-    zome_name_from_config: &'a F,
-    read_method_name: &I,
-    index_name: &I,
-    _start_from: Option<EntryHash>,
-    _limit: usize,
-) -> RecordAPIResult<Vec<RecordAPIResult<T>>>
-    where T: serde::de::DeserializeOwned + std::fmt::Debug,
-        B: DnaAddressable<EntryHash> + TryFrom<SerializedBytes, Error = SerializedBytesError>,
-        I: AsRef<str> + std::fmt::Display + std::fmt::Debug,
-        C: std::fmt::Debug,
-        SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<B, Error = SerializedBytesError>,
-        F: Fn(C) -> Option<String>,
+pub fn query_index_ids<'a, O, A, S, I>(
+    base_address: &A,
+    link_tag: &S,
+    order_by_time_index: &I,
+    include_tombstones: bool,
+) -> RecordAPIResult<Vec<RecordAPIResult<RecordRevisionId<O>>>>
+    where I: AsRef<str> + std::fmt::Debug,
+        S: 'a + AsRef<[u8]> + ?Sized + std::fmt::Debug,
+        A: DnaAddressable<EntryHash>,
+        O: DnaAddressable<EntryHash>,
+        SerializedBytes: TryInto<O, Error = SerializedBytesError>,
 {
-    // this algorithm is the 'make it work' current pass, pending the full implementation mentioned
-    // in the TODO below, regarding efficiency and completeness
-    let linked_records = read_all_entry_hashes(index_name)
-        .map_err(|e| { SemanticIndexError::BadTimeIndexError(e.to_string()) })?;
-
-    // :TODO: efficient paginated retrieval
-    // let linked_records = match start_from {
-    //     None => get_latest_entry_hashes(index_name, limit),
-    //     Some(cursor) => get_older_entry_hashes(index_name, cursor, limit),
-    // }.map_err(|e| { SemanticIndexError::BadTimeIndexError(e.to_string()) })?;
-
-    let read_single_record = retrieve_foreign_record::<T, B, _,_,_>(zome_name_from_config, read_method_name);
-
-    Ok(linked_records.iter()
-        .map(|addr| {
-            // query full record from the associated CRUD zome
-            read_single_record(addr)
-        })
-        .collect())
+    let addrs_result = query_index_addresses(base_address, link_tag, order_by_time_index, include_tombstones)?;
+    retrieve_revision_ids(&addrs_result)
 }
 
-/// Fetches all referenced record entries found corresponding to the input
-/// identity addresses.
+/// Maximum number of records read by a single call to `query_index_stream`.
 ///
-/// Useful in loading the results of indexed data, where indexes link identity `Path`s for different records.
+pub const MAX_STREAM_RESULTS: usize = 300;
+
+/// Variant of `query_index` which emits its results to the client as `QueryStreamChunk` signals of
+/// at most `chunk_size` records each, returning a `QueryStreamSummary` once all chunks are sent.
 ///
-fn retrieve_foreign_records<'a, T, B, C, F, S>(
-    zome_name_from_config: &'a F,
-    method_name: &S,
-    addresses: &'a Vec<EntryHash>,
-) -> Vec<RecordAPIResult<T>>
-    where S: AsRef<str>,
+/// Up to `limit` records following the `after` cursor are streamed per call, capped at
+/// `MAX_STREAM_RESULTS`. Pass the returned `next_cursor` as `after` to stream the remainder.
+/// Records are retrieved one chunk at a time, so the full result set is never held in memory.
+/// Each record is passed through `format_result` before being emitted.
+///
+pub fn query_index_stream<'a, T, O, C, F, A, S, I, J, E, P>(
+    base_address: &A,
+    link_tag: &S,
+    order_by_time_index: &I,
+    foreign_zome_name_from_config: &F,
+    foreign_read_method_name: &J,
+    include_tombstones: bool,
+    stream_id: &str,
+    chunk_size: usize,
+    after: Option<&EntryHash>,
+    limit: usize,
+    format_result: &P,
+) -> RecordAPIResult<QueryStreamSummary>
+    where I: AsRef<str> + std::fmt::Debug,
+        J: AsRef<str>,
+        S: 'a + AsRef<[u8]> + ?Sized + std::fmt::Debug,
+        A: DnaAddressable<EntryHash>,
+        O: DnaAddressable<EntryHash>,
         T: serde::de::DeserializeOwned + std::fmt::Debug,
-        B: DnaAddressable<EntryHash>,
         C: std::fmt::Debug,
-        SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<B, Error = SerializedBytesError>,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<O, Error = SerializedBytesError>,
         F: Fn(C) -> Option<String>,
+        E: serde::Serialize + std::fmt::Debug,
+        P: Fn(T) -> E,
 {
-    let read_single_record = retrieve_foreign_record::<T, B, _,_,_>(zome_name_from_config, &method_name);
+    let addrs_result = query_index_addresses(base_address, link_tag, order_by_time_index, include_tombstones)?;
+    let (window, next_cursor) = select_stream_window(addrs_result.as_slice(), after, limit.clamp(1, MAX_STREAM_RESULTS))?;
+    let read_single_record = retrieve_foreign_record::<T, O, _,_,_>(foreign_zome_name_from_config, foreign_read_method_name);
 
-    addresses.iter()
-        .map(read_single_record)
-        .collect()
+    let summary = emit_in_chunks(
+        stream_id,
+        window,
+        chunk_size,
+        |addr| read_single_record(addr).map(format_result),
+        |chunk| emit_signal(chunk),
+    );
+    Ok(QueryStreamSummary { next_cursor, ..summary })
 }
 
-fn retrieve_foreign_record<'a, T, B, C, F, S>(
-    zome_name_from_config: &'a F,
-    method_name: &'a S,
-) -> impl Fn(&EntryHash) -> RecordAPIResult<T> + 'a
-    where S: AsRef<str>,
-        T: serde::de::DeserializeOwned + std::fmt::Debug,
-        B: DnaAddressable<EntryHash>,
-        C: std::fmt::Debug,
-        SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<B, Error = SerializedBytesError>,
-        F: Fn(C) -> Option<String>,
-{
-    move |addr| {
-        let address: B = read_remote_entry_identity(addr)?;
-        let entry_res: T = call_local_zome_method(zome_name_from_config.to_owned(), method_name, ByAddress { address })?;
-        Ok(entry_res)
-    }
+/// Select up to `limit` of `addresses` following the `after` cursor, along with the cursor from
+/// which to continue if any addresses remain. Errors if `after` is not among `addresses`.
+///
+fn select_stream_window<'a>(
+    addresses: &'a [EntryHash],
+    after: Option<&EntryHash>,
+    limit: usize,
+) -> RecordAPIResult<(&'a [EntryHash], Option<EntryHash>)> {
+    let start = match after {
+        None => 0,
+        Some(cursor) => match addresses.iter().position(|a| a == cursor) {
+            Some(pos) => pos + 1,
+            None => return Err(DataIntegrityError::UnknownCursor(cursor.to_string())),
+        },
+    };
+    let end = (start + limit).min(addresses.len());
+    let window = &addresses[start..end];
+    let next_cursor = if end < addresses.len() { window.last().cloned() } else { None };
+    Ok((window, next_cursor))
 }
 
-//--------------------------------[ UPDATE ]--------------------------------------
-
+/// Read the records at `addresses` in chunks of `chunk_size`, passing each chunk to `emit` before
+/// reading the next. Failure to emit a chunk is recorded in the summary rather than ending the stream.
+///
+fn emit_in_chunks<E, R, M>(
+    stream_id: &str,
+    addresses: &[EntryHash],
+    chunk_size: usize,
+    read: R,
+    mut emit: M,
+) -> QueryStreamSummary
+    where R: Fn(&EntryHash) -> RecordAPIResult<E>,
+        M: FnMut(&QueryStreamChunk<E>) -> ExternResult<()>,
+{
+    let mut summary = QueryStreamSummary {
+        stream_id: stream_id.to_string(),
+        chunks: 0,
+        chunks_failed: vec![],
+        total_results: 0,
+        total_errors: 0,
+        next_cursor: None,
+    };
+
+    for chunk_addresses in addresses.chunks(chunk_size.max(1)) {
+        let mut chunk = QueryStreamChunk {
+            stream_id: stream_id.to_string(),
+            sequence: summary.chunks,
+            results: vec![],
+            errors: vec![],
+        };
+        for result in chunk_addresses.iter().map(&read) {
+            match result {
+                Ok(record) => chunk.results.push(record),
+                Err(e) => chunk.errors.push(e.to_string()),
+            }
+        }
+        summary.total_results += chunk.results.len() as u32;
+        summary.total_errors += chunk.errors.len() as u32;
+
+        if let Err(e) = emit(&chunk) {
+            log_warn!("query_index_stream", stream = stream_id, sequence = chunk.sequence; "failed to emit query result chunk: {:?}", e);
+            summary.chunks_failed.push(chunk.sequence);
+        }
+        summary.chunks += 1;
+    }
+
+    summary
+}
+
+/// Identity addresses of the records queried by `query_index`, in result order.
+///
+fn query_index_addresses<'a, A, S, I>(
+    base_address: &A,
+    link_tag: &S,
+    order_by_time_index: &I,
+    include_tombstones: bool,
+) -> RecordAPIResult<Vec<EntryHash>>
+    where I: AsRef<str> + std::fmt::Debug,
+        S: 'a + AsRef<[u8]> + ?Sized + std::fmt::Debug,
+        A: DnaAddressable<EntryHash>,
+{
+    validate_time_index_name(order_by_time_index)?;
+    let index_address = resolve_identity_address(base_address)?;
+    verify_index_schema(&index_address)?;
+    let mut linked = linked_targets(get_index_links(&index_address, link_tag)?);
+    if include_tombstones {
+        for (address, removed_at) in get_tombstone_links(&index_address, link_tag)? {
+            if !linked.iter().any(|(a, _)| *a == address) {
+                linked.push((address, removed_at));
+            }
+        }
+    }
+    Ok(sort_by_time_index_or_fallback(linked, order_by_time_index))
+}
+
+/// Query the records referenced via `link_tag` from each of several `base_addresses`, as a single
+/// stream ordered by `order_by_time_index` from most recent to oldest.
+///
+/// Records referenced from more than one base are returned once. Pagination spans all bases: if
+/// `before` is provided, only records ordered after it are returned. At most `limit` are returned.
+///
+pub fn query_indexes_merged<'a, T, O, C, F, A, S, I, J>(
+    base_addresses: &[A],
+    link_tag: &S,
+    order_by_time_index: &I,
+    foreign_zome_name_from_config: &F,
+    foreign_read_method_name: &J,
+    before: Option<EntryHash>,
+    limit: usize,
+) -> RecordAPIResult<Vec<RecordAPIResult<T>>>
+    where I: AsRef<str> + std::fmt::Debug,
+        J: AsRef<str>,
+        S: 'a + AsRef<[u8]> + ?Sized + std::fmt::Debug,
+        A: DnaAddressable<EntryHash>,
+        O: DnaAddressable<EntryHash>,
+        T: serde::de::DeserializeOwned + std::fmt::Debug,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<O, Error = SerializedBytesError>,
+        F: Fn(C) -> Option<String>,
+{
+    let addrs_result = merged_index_addresses(base_addresses, link_tag, order_by_time_index, before, limit)?;
+
+    let entries = retrieve_foreign_records::<T, O, C, F, J>(
+        foreign_zome_name_from_config,
+        foreign_read_method_name,
+        &addrs_result,
+    );
+    Ok(entries)
+}
+
+/// Variant of `query_indexes_merged` which returns the identifiers of each record and its
+/// latest revision in place of full record data, in the same order.
+///
+pub fn query_indexes_merged_ids<'a, O, A, S, I>(
+    base_addresses: &[A],
+    link_tag: &S,
+    order_by_time_index: &I,
+    before: Option<EntryHash>,
+    limit: usize,
+) -> RecordAPIResult<Vec<RecordAPIResult<RecordRevisionId<O>>>>
+    where I: AsRef<str> + std::fmt::Debug,
+        S: 'a + AsRef<[u8]> + ?Sized + std::fmt::Debug,
+        A: DnaAddressable<EntryHash>,
+        O: DnaAddressable<EntryHash>,
+        SerializedBytes: TryInto<O, Error = SerializedBytesError>,
+{
+    let addrs_result = merged_index_addresses(base_addresses, link_tag, order_by_time_index, before, limit)?;
+    retrieve_revision_ids(&addrs_result)
+}
+
+/// Identity addresses of the page of records queried by `query_indexes_merged`, in result order.
+///
+fn merged_index_addresses<'a, A, S, I>(
+    base_addresses: &[A],
+    link_tag: &S,
+    order_by_time_index: &I,
+    before: Option<EntryHash>,
+    limit: usize,
+) -> RecordAPIResult<Vec<EntryHash>>
+    where I: AsRef<str> + std::fmt::Debug,
+        S: 'a + AsRef<[u8]> + ?Sized + std::fmt::Debug,
+        A: DnaAddressable<EntryHash>,
+{
+    validate_time_index_name(order_by_time_index)?;
+    // bases are compared by full address, so identical local hashes in different DNAs remain distinct
+    let bases: BTreeSet<&A> = base_addresses.iter().collect();
+    let mut linked = vec![];
+    for base_address in bases {
+        let index_address = resolve_identity_address(base_address)?;
+        linked.push(get_linked_addresses(&index_address, link_tag)?);
+    }
+    let mut addrs_result = merge_linked_addresses(linked);
+    if registered_record_storage_zomes().is_some() {
+        addrs_result = sort_by_typed_time_index(addrs_result, order_by_time_index);
+    } else {
+        addrs_result.sort_by(sort_entries_by_time_index(order_by_time_index));
+    }
+
+    Ok(page_before(addrs_result, before, limit))
+}
+
+/// Count the distinct targets referenced via `link_tag` from any of several `base_addresses`.
+///
+/// Targets linked from more than one base are counted once. Only index links are read;
+/// the referenced records themselves are not retrieved.
+///
+pub fn count_distinct_targets<'a, A, S>(
+    base_addresses: &[A],
+    link_tag: &S,
+) -> RecordAPIResult<usize>
+    where S: 'a + AsRef<[u8]> + ?Sized + std::fmt::Debug,
+        A: DnaAddressable<EntryHash>,
+{
+    let bases: BTreeSet<&A> = base_addresses.iter().collect();
+    let mut linked = vec![];
+    for base_address in bases {
+        let index_address = resolve_identity_address(base_address)?;
+        linked.push(get_linked_addresses(&index_address, link_tag)?);
+    }
+    Ok(count_distinct_linked_addresses(linked))
+}
+
+fn count_distinct_linked_addresses(linked: Vec<Vec<EntryHash>>) -> usize {
+    linked.into_iter()
+        .flatten()
+        .collect::<HashSet<EntryHash>>()
+        .len()
+}
+
+/// Combine the targets of several indexes into one list, retaining only the first
+/// occurrence of any target linked from multiple bases.
+///
+fn merge_linked_addresses(linked: Vec<Vec<EntryHash>>) -> Vec<EntryHash> {
+    let mut seen: HashSet<EntryHash> = HashSet::new();
+    linked.into_iter()
+        .flatten()
+        .filter(|address| seen.insert(address.to_owned()))
+        .collect()
+}
+
+/// Sort the distinct targets of `links` by their time in `order_by_time_index`, most recent first.
+/// @see sort_by_time_index_or_fallback
+///
+fn sort_links_by_time_index<I>(links: Vec<Link>, order_by_time_index: &I) -> Vec<EntryHash>
+    where I: AsRef<str>,
+{
+    sort_by_time_index_or_fallback(linked_targets(links), order_by_time_index)
+}
+
+/// List the distinct targets of `links`, each with the time at which it was first linked.
+///
+fn linked_targets(links: Vec<Link>) -> Vec<(EntryHash, Timestamp)> {
+    let mut targets: Vec<(EntryHash, Timestamp)> = Vec::new();
+    for link in links {
+        let address = match link.target.into_entry_hash() {
+            Some(address) => address,
+            None => continue,
+        };
+        match targets.iter_mut().find(|(a, _)| *a == address) {
+            Some((_, linked_at)) => if link.timestamp < *linked_at { *linked_at = link.timestamp },
+            None => targets.push((address, link.timestamp)),
+        }
+    }
+    targets
+}
+
+/// Sort `targets` by their time in `order_by_time_index`, most recent first.
+///
+/// Targets missing from the time index (eg. while it is being populated) follow all indexed
+/// targets, ordered by the zome's configured `UnindexedOrder` so that results remain deterministic.
+/// A warning is logged whenever the fallback ordering is applied.
+///
+fn sort_by_time_index_or_fallback<I>(targets: Vec<(EntryHash, Timestamp)>, order_by_time_index: &I) -> Vec<EntryHash>
+    where I: AsRef<str>,
+{
+    let mixed_types = registered_record_storage_zomes().is_some();
+    let (mut indexed, unindexed): (Vec<_>, Vec<_>) = targets.into_iter()
+        .map(|(address, linked_at)| {
+            let indexed_at = read_entry_index_time(order_by_time_index, &address, mixed_types);
+            (address, linked_at, indexed_at)
+        })
+        .partition(|(_, _, indexed_at)| indexed_at.is_some());
+    indexed.sort_by(|a, b| b.2.cmp(&a.2));
+
+    let mut sorted: Vec<EntryHash> = indexed.into_iter().map(|(address, _, _)| address).collect();
+    if unindexed.is_empty() {
+        return sorted;
+    }
+
+    let order = registered_unindexed_order();
+    log_warn!("read_index", index = order_by_time_index.as_ref(), unindexed = unindexed.len(), fallback = format!("{:?}", order);
+        "entries missing from time index, ordered by fallback");
+    let mut unindexed: Vec<(EntryHash, Timestamp)> = unindexed.into_iter().map(|(address, linked_at, _)| (address, linked_at)).collect();
+    sort_unindexed(&mut unindexed, order);
+    sorted.extend(unindexed.into_iter().map(|(address, _)| address));
+    sorted
+}
+
+/// Sort `addresses` by their time in `order_by_time_index` or the equivalent index of their own
+/// record type, most recent first. Entries missing from both follow all others.
+///
+fn sort_by_typed_time_index<I>(addresses: Vec<EntryHash>, order_by_time_index: &I) -> Vec<EntryHash>
+    where I: AsRef<str>,
+{
+    sort_by_index_time(addresses, |address| read_entry_index_time(order_by_time_index, address, true))
+}
+
+/// Sort `addresses` by the time read for each via `read_time`, most recent first. Entries without a
+/// time follow all others, in their original order.
+///
+fn sort_by_index_time<F>(addresses: Vec<EntryHash>, read_time: F) -> Vec<EntryHash>
+    where F: Fn(&EntryHash) -> Option<DateTime<Utc>>,
+{
+    let mut timed: Vec<(EntryHash, Option<DateTime<Utc>>)> = addresses.into_iter()
+        .map(|address| {
+            let indexed_at = read_time(&address);
+            (address, indexed_at)
+        })
+        .collect();
+    timed.sort_by(|a, b| b.1.cmp(&a.1));
+    timed.into_iter().map(|(address, _)| address).collect()
+}
+
+/// Read the time of `address` in `order_by_time_index`.
+///
+/// Where the zome retrieves records of `mixed_types`, entries of other record types are read from the
+/// equivalent time index of their own type, so that results of several types interleave in time order.
+///
+fn read_entry_index_time<I>(order_by_time_index: &I, address: &EntryHash, mixed_types: bool) -> Option<DateTime<Utc>>
+    where I: AsRef<str>,
+{
+    let indexed_at = get_entry_index_time(order_by_time_index, address).ok().flatten();
+    if indexed_at.is_some() || !mixed_types {
+        return indexed_at;
+    }
+    let entry_type = read_identity_entry_type(address).ok().flatten()?;
+    let typed_index = typed_time_index_name(order_by_time_index.as_ref(), &entry_type)?;
+    get_entry_index_time(&typed_index, address).ok().flatten()
+}
+
+/// Name of the time index equivalent to `index_name` for records of `entry_type`, or `None` if
+/// `index_name` already indexes records of that type.
+///
+fn typed_time_index_name(index_name: &str, entry_type: &str) -> Option<String> {
+    match index_name.split_once(TIME_INDEX_NAMESPACE_SEPARATOR) {
+        Some((record_type, name)) if record_type != entry_type => Some(time_index_name(entry_type, name)),
+        _ => None,
+    }
+}
+
+/// Order entries which have no time index data by the given `UnindexedOrder`.
+///
+fn sort_unindexed(entries: &mut [(EntryHash, Timestamp)], order: UnindexedOrder) {
+    match order {
+        UnindexedOrder::EntryHash => entries.sort_by(|a, b| a.0.get_raw_39().cmp(b.0.get_raw_39())),
+        UnindexedOrder::LinkCreated => entries.sort_by(|a, b| b.1.cmp(&a.1)
+            .then_with(|| a.0.get_raw_39().cmp(b.0.get_raw_39()))),
+    }
+}
+
+/// Select a page of at most `limit` sorted addresses, following the `before` cursor if given.
+/// An unknown cursor yields an empty page.
+///
+fn page_before(addresses: Vec<EntryHash>, before: Option<EntryHash>, limit: usize) -> Vec<EntryHash> {
+    let start = match before {
+        None => 0,
+        Some(cursor) => match addresses.iter().position(|a| *a == cursor) {
+            Some(pos) => pos + 1,
+            None => return vec![],
+        },
+    };
+    addresses.into_iter().skip(start).take(limit).collect()
+}
+
+/// Maximum depth of related record edges which may be resolved alongside query results.
+///
+/// Resolution beyond the immediate edges of each result is not supported, as the
+/// number of reads grows multiplicatively with each level.
+///
+pub const MAX_QUERY_DEPTH: u8 = 1;
+
+/// Normalise a requested edge resolution depth for `query_index` results,
+/// defaulting to no resolution and capping at `MAX_QUERY_DEPTH`.
+///
+pub fn clamp_query_depth(depth: Option<u8>) -> u8 {
+    depth.unwrap_or(0).min(MAX_QUERY_DEPTH)
+}
+
+/// Query foreign entries pointers from a time-ordered index, in order from most recent to oldest.
+///
+/// If `start_from` is provided, the given `EntryHash` is used to determine the starting location
+/// for reading results. Otherwise the newest entries (as determined by their ordering in the time
+/// index) are returned.
+///
+/// Full entry data is returned by querying from the associated record storage zome determined by
+/// `zome_name_from_config` and `read_method_name`.
+///
+pub fn query_time_index<'a, T, B, C, F, I>(
+    zome_name_from_config: &'a F,
+    read_method_name: &I,
+    index_name: &I,
+    _start_from: Option<EntryHash>,
+    _limit: usize,
+) -> RecordAPIResult<Vec<RecordAPIResult<T>>>
+    where T: serde::de::DeserializeOwned + std::fmt::Debug,
+        B: DnaAddressable<EntryHash> + TryFrom<SerializedBytes, Error = SerializedBytesError>,
+        I: AsRef<str> + std::fmt::Display + std::fmt::Debug,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<B, Error = SerializedBytesError>,
+        F: Fn(C) -> Option<String>,
+{
+    validate_time_index_name(index_name)?;
+    // this algorithm is the 'make it work' current pass, pending the full implementation mentioned
+    // in the TODO below, regarding efficiency and completeness
+    let linked_records = read_all_entry_hashes(index_name)
+        .map_err(|e| { SemanticIndexError::BadTimeIndexError(e.to_string()) })?;
+
+    // :TODO: efficient paginated retrieval
+    // let linked_records = match start_from {
+    //     None => get_latest_entry_hashes(index_name, limit),
+    //     Some(cursor) => get_older_entry_hashes(index_name, cursor, limit),
+    // }.map_err(|e| { SemanticIndexError::BadTimeIndexError(e.to_string()) })?;
+
+    let read_single_record = retrieve_foreign_record::<T, B, _,_,_>(zome_name_from_config, read_method_name);
+
+    Ok(linked_records.iter()
+        .map(|addr| {
+            // query full record from the associated CRUD zome
+            read_single_record(addr)
+        })
+        .collect())
+}
+
+/// Variant of `query_time_index` which returns the identifiers of each record and its latest
+/// revision in place of full record data, in the same order.
+///
+pub fn query_time_index_ids<O, I>(
+    index_name: &I,
+    _start_from: Option<EntryHash>,
+    _limit: usize,
+) -> RecordAPIResult<Vec<RecordAPIResult<RecordRevisionId<O>>>>
+    where O: DnaAddressable<EntryHash>,
+        I: AsRef<str> + std::fmt::Display + std::fmt::Debug,
+        SerializedBytes: TryInto<O, Error = SerializedBytesError>,
+{
+    validate_time_index_name(index_name)?;
+    // :TODO: efficient paginated retrieval, as for `query_time_index`
+    let linked_records = read_all_entry_hashes(index_name)
+        .map_err(|e| { SemanticIndexError::BadTimeIndexError(e.to_string()) })?;
+
+    retrieve_revision_ids(&linked_records)
+}
+
+/// Query foreign entries from a time-ordered index which are indexed earlier than `before`,
+/// in order from most recent to oldest.
+///
+/// Useful for indexes ordered by some time associated with the record (eg. an expiry date)
+/// rather than by creation time.
+///
+pub fn query_time_index_before<'a, T, B, C, F, I>(
+    zome_name_from_config: &'a F,
+    read_method_name: &I,
+    index_name: &I,
+    before: DateTime<Utc>,
+) -> RecordAPIResult<Vec<RecordAPIResult<T>>>
+    where T: serde::de::DeserializeOwned + std::fmt::Debug,
+        B: DnaAddressable<EntryHash> + TryFrom<SerializedBytes, Error = SerializedBytesError>,
+        I: AsRef<str> + std::fmt::Display + std::fmt::Debug,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<B, Error = SerializedBytesError>,
+        F: Fn(C) -> Option<String>,
+{
+    validate_time_index_name(index_name)?;
+    let linked_records = read_entry_hashes_before(index_name, before)
+        .map_err(|e| { SemanticIndexError::BadTimeIndexError(e.to_string()) })?;
+
+    Ok(retrieve_foreign_records::<T, B, C, F, I>(zome_name_from_config, read_method_name, &linked_records))
+}
+
+/// A page of records matching a filtered time index query.
+///
+/// `next_cursor` is the last entry examined for this page, from which the next page should
+/// continue, or `None` once the range is exhausted.
+///
+#[derive(Debug)]
+pub struct TimeRangeMatches<T> {
+    pub records: Vec<RecordAPIResult<T>>,
+    pub next_cursor: Option<EntryHash>,
+}
+
+/// Query foreign entries from a time-ordered index which are indexed from `from` (inclusive)
+/// until `until` (not inclusive) and satisfy `predicate`, in order from most recent to oldest.
+///
+/// Only the parts of the index overlapping the range are traversed, but every record within
+/// the range must be fetched from its CRUD zome in order to test it against `predicate`, up
+/// until `limit` matches are found. Records which cannot be read are returned as errors.
+///
+/// Pagination is over the filtered set: pass the returned `next_cursor` as `after` to
+/// continue from the last record examined.
+///
+pub fn query_time_index_where<'a, T, B, C, F, I, P>(
+    zome_name_from_config: &'a F,
+    read_method_name: &I,
+    index_name: &I,
+    from: DateTime<Utc>,
+    until: DateTime<Utc>,
+    predicate: P,
+    after: Option<EntryHash>,
+    limit: usize,
+) -> RecordAPIResult<TimeRangeMatches<T>>
+    where T: serde::de::DeserializeOwned + std::fmt::Debug,
+        B: DnaAddressable<EntryHash> + TryFrom<SerializedBytes, Error = SerializedBytesError>,
+        I: AsRef<str> + std::fmt::Display + std::fmt::Debug,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<B, Error = SerializedBytesError>,
+        F: Fn(C) -> Option<String>,
+        P: Fn(&T) -> bool,
+{
+    validate_time_index_name(index_name)?;
+    let mut linked_records = read_entry_hashes_between(index_name, from, until)
+        .map_err(|e| { SemanticIndexError::BadTimeIndexError(e.to_string()) })?;
+    linked_records.reverse();
+
+    let read_single_record = retrieve_foreign_record::<T, B, _,_,_>(zome_name_from_config, read_method_name);
+
+    select_matching_page(linked_records.as_slice(), after.as_ref(), limit, |addr| {
+        match read_single_record(addr) {
+            Ok(record) if !predicate(&record) => None,
+            result => Some(result),
+        }
+    })
+}
+
+/// Query foreign entries from a time-ordered index which satisfy `predicate`, in order from
+/// most recent to oldest.
+///
+/// The index is read a page at a time following the `after` cursor, and each record fetched
+/// from its CRUD zome in order to test it against `predicate`, until `limit` matches are found
+/// or the index is exhausted. Records which cannot be read are returned as errors.
+///
+/// Pagination is over the filtered set: pass the returned `next_cursor` as `after` to
+/// continue from the last record examined. Unknown cursors are rejected.
+///
+pub fn query_time_index_page_where<'a, T, B, C, F, I, P>(
+    zome_name_from_config: &'a F,
+    read_method_name: &I,
+    index_name: &I,
+    predicate: P,
+    after: Option<EntryHash>,
+    limit: usize,
+) -> RecordAPIResult<TimeRangeMatches<T>>
+    where T: serde::de::DeserializeOwned + std::fmt::Debug,
+        B: DnaAddressable<EntryHash> + TryFrom<SerializedBytes, Error = SerializedBytesError>,
+        I: AsRef<str> + std::fmt::Display + std::fmt::Debug,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<B, Error = SerializedBytesError>,
+        F: Fn(C) -> Option<String>,
+        P: Fn(&T) -> bool,
+{
+    validate_time_index_name(index_name)?;
+    let limit = limit.max(1);
+    let read_single_record = retrieve_foreign_record::<T, B, _,_,_>(zome_name_from_config, read_method_name);
+
+    let mut records = vec![];
+    let mut cursor = after;
+    loop {
+        let (page, next_page) = read_time_index_page(index_name, cursor, limit)?;
+        for (i, addr) in page.iter().enumerate() {
+            match read_single_record(addr) {
+                Ok(record) if !predicate(&record) => {},
+                result => records.push(result),
+            }
+            if records.len() >= limit {
+                let remaining = i + 1 < page.len() || next_page.is_some();
+                return Ok(TimeRangeMatches {
+                    records,
+                    next_cursor: if remaining { Some(addr.to_owned()) } else { None },
+                });
+            }
+        }
+        match next_page {
+            None => return Ok(TimeRangeMatches { records, next_cursor: None }),
+            next_page => cursor = next_page,
+        }
+    }
+}
+
+/// Examine `addresses` in order from the entry following `after`, until `limit` records
+/// have been selected by `read_matching`, which returns `None` for records to skip.
+/// Errors if `after` is not among `addresses`.
+///
+fn select_matching_page<T, R>(
+    addresses: &[EntryHash],
+    after: Option<&EntryHash>,
+    limit: usize,
+    read_matching: R,
+) -> RecordAPIResult<TimeRangeMatches<T>>
+    where R: Fn(&EntryHash) -> Option<RecordAPIResult<T>>,
+{
+    let start = match after {
+        None => 0,
+        Some(cursor) => match addresses.iter().position(|a| a == cursor) {
+            Some(pos) => pos + 1,
+            None => return Err(DataIntegrityError::UnknownCursor(cursor.to_string())),
+        },
+    };
+
+    let mut records = vec![];
+    let mut next_cursor = None;
+    for (i, addr) in addresses.iter().enumerate().skip(start) {
+        if records.len() >= limit {
+            break;
+        }
+        if let Some(record) = read_matching(addr) {
+            records.push(record);
+        }
+        if records.len() >= limit && i + 1 < addresses.len() {
+            next_cursor = Some(addr.to_owned());
+        }
+    }
+
+    Ok(TimeRangeMatches { records, next_cursor })
+}
+
+/// Fetches all referenced record entries found corresponding to the input
+/// identity addresses.
+///
+/// Useful in loading the results of indexed data, where indexes link identity `Path`s for different records.
+///
+fn retrieve_foreign_records<'a, T, B, C, F, S>(
+    zome_name_from_config: &'a F,
+    method_name: &S,
+    addresses: &'a Vec<EntryHash>,
+) -> Vec<RecordAPIResult<T>>
+    where S: AsRef<str>,
+        T: serde::de::DeserializeOwned + std::fmt::Debug,
+        B: DnaAddressable<EntryHash>,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<B, Error = SerializedBytesError>,
+        F: Fn(C) -> Option<String>,
+{
+    let read_single_record = retrieve_foreign_record::<T, B, _,_,_>(zome_name_from_config, &method_name);
+
+    addresses.iter()
+        .map(read_single_record)
+        .collect()
+}
+
+/// Resolves the record identifiers and latest revisions corresponding to the input identity addresses,
+/// without retrieving the records themselves. Revisions are looked up in a single batch.
+///
+fn retrieve_revision_ids<B>(
+    addresses: &[EntryHash],
+) -> RecordAPIResult<Vec<RecordAPIResult<RecordRevisionId<B>>>>
+    where B: DnaAddressable<EntryHash>,
+        SerializedBytes: TryInto<B, Error = SerializedBytesError>,
+{
+    let ids: Vec<RecordAPIResult<B>> = addresses.iter()
+        .map(read_remote_entry_identity)
+        .collect();
+    let resolved: Vec<EntryHash> = ids.iter()
+        .filter_map(|id| id.as_ref().ok())
+        .map(|id| { let hash: &EntryHash = id.as_ref(); hash.to_owned() })
+        .collect();
+    let mut revisions = get_latest_revisions(resolved.as_slice())?.into_iter();
+
+    // revisions were only requested for resolved identities, so are consumed by those alone
+    Ok(ids.into_iter()
+        .map(|id| -> RecordAPIResult<RecordRevisionId<B>> {
+            let id = id?;
+            let revision_id = revisions.next().ok_or(DataIntegrityError::EntryNotFound)??;
+            Ok(RecordRevisionId { id, revision_id })
+        })
+        .collect())
+}
+
+fn retrieve_foreign_record<'a, T, B, C, F, S>(
+    zome_name_from_config: &'a F,
+    method_name: &'a S,
+) -> impl Fn(&EntryHash) -> RecordAPIResult<T> + 'a
+    where S: AsRef<str>,
+        T: serde::de::DeserializeOwned + std::fmt::Debug,
+        B: DnaAddressable<EntryHash>,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<B, Error = SerializedBytesError>,
+        F: Fn(C) -> Option<String>,
+{
+    let record_storage_zomes = registered_record_storage_zomes();
+
+    move |addr| {
+        let address: B = read_remote_entry_identity(addr)?;
+        let typed_zome = match &record_storage_zomes {
+            Some(zomes) => select_record_storage_zome(zomes.as_slice(), read_identity_entry_type(addr)?.as_deref()),
+            None => None,
+        };
+        let entry_res: T = call_local_zome_method(
+            |conf: C| typed_zome.or_else(|| zome_name_from_config(conf)),
+            method_name, ByAddress { address },
+        )?;
+        Ok(entry_res)
+    }
+}
+
+//--------------------------------[ UPDATE ]--------------------------------------
+
 /// Respond to a request from a remote source to build a 'destination' link index for some externally linking content.
 ///
-/// This essentially ensures an identity `Path` for the remote `source` and then links it to every
-/// `dest_addresses` found locally within this DNA before removing any links to `removed_addresses`.
+/// This essentially ensures an identity `Path` for the remote `source` and then links it to every
+/// `dest_addresses` found locally within this DNA before removing any links to `removed_addresses`.
+///
+/// The returned `RemoteEntryLinkResponse` provides an appropriate format for responding to indexing
+/// requests that originate from calls to `create/update/delete_remote_index` in a foreign DNA.
+///
+/// Every destination and removal is attempted regardless of earlier failures, except that no
+/// destinations are linked if the source's identifier cannot be stored. Errors are reported
+/// per-item in the response, rather than failing the entire update.
+///
+pub fn sync_index<A, B, S, I>(
+    source: &A,
+    dest_addresses: &[B],
+    removed_addresses: &[B],
+    link_tag: &S,
+    link_tag_reciprocal: &S,
+    order_by_time_index: &I,
+) -> OtherCellResult<RemoteEntryLinkResponse>
+    where S: AsRef<[u8]> + ?Sized + std::fmt::Debug,
+        I: AsRef<str> + std::fmt::Display + std::fmt::Debug,
+        A: DnaAddressable<EntryHash>,
+        B: DnaAddressable<EntryHash>,
+{
+    sync_index_with_signals(source, dest_addresses, removed_addresses, link_tag, link_tag_reciprocal, order_by_time_index, false)
+}
+
+/// Variant of `sync_index` which additionally emits an `IndexChangeSignal` describing the
+/// update to any listening clients if `emit_signal` is set.
+///
+/// Signals are emitted for all index updates, regardless of whether they originate from the
+/// `create_index!`, `update_index!` or `delete_index!` client macros. No signal is sent if
+/// the update resulted in no links being written or removed.
+///
+pub fn sync_index_with_signals<A, B, S, I>(
+    source: &A,
+    dest_addresses: &[B],
+    removed_addresses: &[B],
+    link_tag: &S,
+    link_tag_reciprocal: &S,
+    order_by_time_index: &I,
+    emit_signal: bool,
+) -> OtherCellResult<RemoteEntryLinkResponse>
+    where S: AsRef<[u8]> + ?Sized + std::fmt::Debug,
+        I: AsRef<str> + std::fmt::Display + std::fmt::Debug,
+        A: DnaAddressable<EntryHash>,
+        B: DnaAddressable<EntryHash>,
+{
+    sync_index_with_options(
+        source, dest_addresses, removed_addresses, link_tag, link_tag_reciprocal, order_by_time_index,
+        IndexSyncOptions { emit_signal, ..IndexSyncOptions::default() },
+    )
+}
+
+/// Optional behaviours of an index update performed via `sync_index_with_options`
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct IndexSyncOptions {
+    /// emit an `IndexChangeSignal` describing the update to any listening clients
+    pub emit_signal: bool,
+    /// replace removed links with tombstone links rather than deleting them outright
+    pub tombstone_removed: bool,
+}
+
+/// Variant of `sync_index` accepting `IndexSyncOptions`.
+///
+/// Where `tombstone_removed` is set, each removed link is replaced by a tombstone link between
+/// the same records which carries the removal time. Tombstones are skipped by normal index reads,
+/// but returned by `read_index_history` and by `query_index` when requested.
+///
+pub fn sync_index_with_options<A, B, S, I>(
+    source: &A,
+    dest_addresses: &[B],
+    removed_addresses: &[B],
+    link_tag: &S,
+    link_tag_reciprocal: &S,
+    order_by_time_index: &I,
+    options: IndexSyncOptions,
+) -> OtherCellResult<RemoteEntryLinkResponse>
+    where S: AsRef<[u8]> + ?Sized + std::fmt::Debug,
+        I: AsRef<str> + std::fmt::Display + std::fmt::Debug,
+        A: DnaAddressable<EntryHash>,
+        B: DnaAddressable<EntryHash>,
+{
+    let now = sys_time()?;
+    let timestamp: DateTime<Utc> = now.try_into()
+        .map_err(|e: TimestampError| SemanticIndexError::BadTimeIndexError(e.to_string()))?;
+
+    let (indexes_created, indexes_removed) = run_index_sync_phases(
+        // ensure there is a fully-qualified identifier stored for the remote source record
+        || ensure_id_tag(source).map(|_| ()),
+        // create any new indexes
+        || create_remote_index_destination(source, dest_addresses, link_tag, link_tag_reciprocal),
+        // :SHONK: add remote source address to its own time series for retrieval.
+        // This should be updated to be determined and passed with the request, so that the
+        // query time is based on (externally determined) record creation time, rather
+        // then "indexed" time, which isn't really useful as it doesn't even correlate with
+        // record updates. (Indexes only change if the indexed field is updated.)
+        || {
+            let time_index_created = append_to_time_index(order_by_time_index, source, timestamp);
+            log_debug!("sync_index",
+                time_index = order_by_time_index,
+                source = short_hash(AsRef::<EntryHash>::as_ref(source)),
+                tag = String::from_utf8_lossy(link_tag.as_ref());
+                "appended to time index: {:?}", time_index_created
+            );
+            time_index_created
+        },
+        // remove passed stale indexes
+        || remove_remote_index_links(
+            source, removed_addresses, link_tag, link_tag_reciprocal,
+            if options.tombstone_removed { Some(now) } else { None },
+        ),
+    );
+
+    let response = RemoteEntryLinkResponse { indexes_created, indexes_removed };
+
+    if options.emit_signal {
+        if let Some(signal) = build_index_change_signal(source, dest_addresses, removed_addresses, link_tag, link_tag_reciprocal, &response) {
+            // failure to notify listeners should not fail the index update itself
+            let e = emit_signal(&signal);
+            log_debug!("sync_index", index = signal.index_name, source = short_hash(AsRef::<EntryHash>::as_ref(source)); "emitted index change signal: {:?}", e);
+        }
+    }
+
+    Ok(response)
+}
+
+/// Variant of `sync_index_with_options` which takes the complete set of destinations the `source`
+/// should be linked to, rather than the changes to make.
+///
+/// The current destinations are read from the index and compared with `desired_dest_addresses`.
+/// Links to any missing destinations are created and links to any not in the desired set removed.
+///
+pub fn sync_index_reconcile<A, B, S, I>(
+    source: &A,
+    desired_dest_addresses: &[B],
+    link_tag: &S,
+    link_tag_reciprocal: &S,
+    order_by_time_index: &I,
+    options: IndexSyncOptions,
+) -> OtherCellResult<RemoteEntryLinkResponse>
+    where S: AsRef<[u8]> + ?Sized + std::fmt::Debug,
+        I: AsRef<str> + std::fmt::Display + std::fmt::Debug,
+        A: DnaAddressable<EntryHash>,
+        B: DnaAddressable<EntryHash>,
+        SerializedBytes: TryInto<B, Error = SerializedBytesError>,
+{
+    let current: Vec<B> = read_index_if_exists(source, link_tag, order_by_time_index)?.unwrap_or_default();
+    let (dest_addresses, removed_addresses) = diff_index_targets(current.as_slice(), desired_dest_addresses);
+
+    sync_index_with_options(
+        source, dest_addresses.as_slice(), removed_addresses.as_slice(),
+        link_tag, link_tag_reciprocal, order_by_time_index, options,
+    )
+}
+
+/// Determine the destinations to add and remove in order to bring the `current` targets of
+/// an index to the `desired` set. Ordering of each list follows that of its input.
+///
+pub fn diff_index_targets<B>(current: &[B], desired: &[B]) -> (Vec<B>, Vec<B>)
+    where B: DnaAddressable<EntryHash>,
+{
+    let current_set: HashSet<&B> = current.iter().collect();
+    let desired_set: HashSet<&B> = desired.iter().collect();
+
+    let mut added = vec![];
+    for dest in desired {
+        if !current_set.contains(dest) && !added.contains(dest) {
+            added.push(dest.to_owned());
+        }
+    }
+    let removed = current.iter()
+        .filter(|dest| !desired_set.contains(dest))
+        .cloned()
+        .collect();
+
+    (added, removed)
+}
+
+/// Run the phases of an index update, returning the results of link creation and removal.
+///
+/// Destinations are only linked and the source only appended to its time index once the source's
+/// identifier has been stored, since links to an unidentified source cannot be read back. Removals
+/// are always attempted. Failures are reported per-item rather than failing the whole update.
+///
+fn run_index_sync_phases<IdF, CrF, TiF, RmF, T>(
+    identify_source: IdF,
+    create_indexes: CrF,
+    append_time_index: TiF,
+    remove_indexes: RmF,
+) -> (Vec<OtherCellResult<ActionHash>>, Vec<OtherCellResult<ActionHash>>)
+    where IdF: FnOnce() -> RecordAPIResult<()>,
+        CrF: FnOnce() -> RecordAPIResult<Vec<RecordAPIResult<ActionHash>>>,
+        TiF: FnOnce() -> RecordAPIResult<T>,
+        RmF: FnOnce() -> RecordAPIResult<Vec<RecordAPIResult<ActionHash>>>,
+{
+    let indexes_created = match identify_source() {
+        Ok(()) => collect_index_results(vec![
+            create_indexes(),
+            append_time_index().map(|_| vec![]),
+        ]),
+        Err(e) => collect_index_results(vec![Err(e)]),
+    };
+    let indexes_removed = collect_index_results(vec![remove_indexes()]);
+    (indexes_created, indexes_removed)
+}
+
+/// Flatten the outcomes of the phases of an index update into per-item results.
+/// A phase which failed outright is reported as a single error, without discarding
+/// the results of other phases.
+///
+fn collect_index_results(phases: Vec<RecordAPIResult<Vec<RecordAPIResult<ActionHash>>>>) -> Vec<OtherCellResult<ActionHash>>
+{
+    phases.into_iter()
+        .flat_map(|phase| match phase {
+            Ok(results) => results.iter().map(convert_errors).collect(),
+            Err(e) => vec![Err(e.into())],
+        })
+        .collect()
+}
+
+/// Build an `IndexChangeSignal` describing the outcome of an index update,
+/// or `None` if the update did not affect any links.
+///
+fn build_index_change_signal<A, B, S>(
+    source: &A,
+    dest_addresses: &[B],
+    removed_addresses: &[B],
+    link_tag: &S,
+    link_tag_reciprocal: &S,
+    response: &RemoteEntryLinkResponse,
+) -> Option<IndexChangeSignal>
+    where S: AsRef<[u8]> + ?Sized,
+        A: DnaAddressable<EntryHash>,
+        B: DnaAddressable<EntryHash>,
+{
+    let links_created = response.indexes_created.iter().filter(|r| r.is_ok()).count();
+    let links_removed = response.indexes_removed.iter().filter(|r| r.is_ok()).count();
+
+    if links_created == 0 && links_removed == 0 {
+        return None;
+    }
+
+    Some(IndexChangeSignal {
+        index_name: String::from_utf8_lossy(link_tag_reciprocal.as_ref()).to_string(),
+        reciprocal_index_name: String::from_utf8_lossy(link_tag.as_ref()).to_string(),
+        source: source.to_string(),
+        targets_added: dest_addresses.iter().map(|a| a.to_string()).collect(),
+        targets_removed: removed_addresses.iter().map(|a| a.to_string()).collect(),
+        links_created,
+        links_removed,
+    })
+}
+
+/// Determine whether the calling index zome has opted in to emitting `IndexChangeSignal`s.
+/// `signals_enabled_from_config` should read the flag from the zome's `IndexingZomeConfig`.
+///
+pub fn index_signals_enabled<C, F>(signals_enabled_from_config: F) -> bool
+    where F: FnOnce(C) -> bool,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError>,
+{
+    dna_info().ok()
+        .and_then(|info| info.modifiers.properties.try_into().ok())
+        .map(signals_enabled_from_config)
+        .unwrap_or(false)
+}
+
+/// Determine whether the calling index zome has opted in to replacing removed index links with tombstones.
+/// `tombstones_enabled_from_config` should read the flag from the zome's `IndexingZomeConfig`.
+///
+pub fn index_tombstones_enabled<C, F>(tombstones_enabled_from_config: F) -> bool
+    where F: FnOnce(C) -> bool,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError>,
+{
+    dna_info().ok()
+        .and_then(|info| info.modifiers.properties.try_into().ok())
+        .map(tombstones_enabled_from_config)
+        .unwrap_or(false)
+}
+
+/// Read the calling index zome's fallback ordering for entries missing from a time index.
+/// `unindexed_order_from_config` should read the setting from the zome's `IndexingZomeConfig`.
+///
+pub fn index_unindexed_order<C, F>(unindexed_order_from_config: F) -> UnindexedOrder
+    where F: FnOnce(C) -> UnindexedOrder,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError>,
+{
+    dna_info().ok()
+        .and_then(|info| info.modifiers.properties.try_into().ok())
+        .map(unindexed_order_from_config)
+        .unwrap_or_default()
+}
+
+/// Read the calling index zome's encoding of addresses in identity link tags.
+/// `tag_address_mode_from_config` should read the setting from the zome's `IndexingZomeConfig`.
+///
+pub fn index_tag_address_mode<C, F>(tag_address_mode_from_config: F) -> TagAddressMode
+    where F: FnOnce(C) -> TagAddressMode,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError>,
+{
+    dna_info().ok()
+        .and_then(|info| info.modifiers.properties.try_into().ok())
+        .map(tag_address_mode_from_config)
+        .unwrap_or_default()
+}
+
+/// Read the zomes storing each type of record retrieved by the calling index zome.
+/// `record_storage_zomes_from_config` should read them from the zome's `IndexingZomeConfig`.
+///
+pub fn index_record_storage_zomes<C, F>(record_storage_zomes_from_config: F) -> Option<Vec<(String, String)>>
+    where F: FnOnce(C) -> Option<Vec<(String, String)>>,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError>,
+{
+    dna_info().ok()
+        .and_then(|info| info.modifiers.properties.try_into().ok())
+        .and_then(record_storage_zomes_from_config)
+}
+
+/// Reject index writes if the calling index zome is configured as a read-only follower.
+/// `read_only_from_config` should read the flag from the zome's `IndexingZomeConfig`.
+///
+pub fn throw_if_index_read_only<C, F>(read_only_from_config: F) -> RecordAPIResult<()>
+    where F: FnOnce(C) -> bool,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError>,
+{
+    let read_only = dna_info().ok()
+        .and_then(|info| info.modifiers.properties.try_into().ok())
+        .map(read_only_from_config)
+        .unwrap_or(false);
+    if read_only {
+        return Err(DataIntegrityError::ReadOnlyIndex(zome_info()?.name.to_string()));
+    }
+    Ok(())
+}
+
+/// Read the calling index zome's `IndexWriteRateLimit`, if one is configured.
+/// `rate_limit_from_config` should read the limit from the zome's `IndexingZomeConfig`.
+///
+/// Errors if the zome's configuration cannot be read, so that a malformed limit does not
+/// silently disable rate limiting.
+///
+pub fn index_write_rate_limit<C, F>(rate_limit_from_config: F) -> RecordAPIResult<Option<IndexWriteRateLimit>>
+    where F: FnOnce(C) -> Option<IndexWriteRateLimit>,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError>,
+{
+    let conf: Result<C, SerializedBytesError> = dna_info()?.modifiers.properties.try_into();
+    match conf {
+        Ok(conf) => Ok(rate_limit_from_config(conf)),
+        Err(e) => Err(DataIntegrityError::LocalIndexNotConfigured(zome_info()?.name.to_string(), e.to_string())),
+    }
+}
+
+/// Reject an index update for `source` if it has already been written to `limit.max_writes`
+/// times within the last `limit.window_ms`, otherwise log the write and allow it to proceed.
+///
+/// Writes are logged to a time index scoped to the source record, from which writes
+/// falling outside the window are removed as each new write is logged. No checks are made
+/// where `limit` is `None`.
+///
+pub fn guard_index_write_rate<A>(
+    source: &A,
+    limit: Option<IndexWriteRateLimit>,
+) -> RecordAPIResult<()>
+    where A: DnaAddressable<EntryHash>,
+{
+    let limit = match limit {
+        Some(limit) => limit,
+        None => return Ok(()),
+    };
+
+    let now: DateTime<Utc> = sys_time()?.try_into()
+        .map_err(|e: TimestampError| SemanticIndexError::BadTimeIndexError(e.to_string()))?;
+    let write_log = format!("index_writes.{}", resolve_identity_address(source)?);
+
+    let recent_writes = get_latest_entry_hashes(&write_log, limit.max_writes as usize)
+        .and_then(|hashes| hashes.iter()
+            .map(|hash| get_entry_index_time(&write_log, hash))
+            .collect::<Result<Vec<Option<DateTime<Utc>>>, TimeIndexingError>>()
+        )
+        .map_err(|e| SemanticIndexError::BadTimeIndexError(e.to_string()))?;
+
+    if exceeds_write_rate(recent_writes.iter().flatten(), &now, &limit) {
+        return Err(SemanticIndexError::WriteRateExceeded(source.to_string(), limit.max_writes, limit.window_ms).into());
+    }
+
+    let write_marker = Path::from(format!("{}.{}", write_log, now.timestamp_nanos())).path_entry_hash()?;
+    index_entry(&write_log, write_marker, now)
+        .map_err(|e| SemanticIndexError::BadTimeIndexError(e.to_string()))?;
+
+    // writes outside the window no longer count towards the limit
+    read_entry_hashes_before(&write_log, write_window_start(&now, &limit))
+        .and_then(|expired| remove_entries(&write_log, expired.as_slice()))
+        .map_err(|e| SemanticIndexError::BadTimeIndexError(e.to_string()))?;
+    Ok(())
+}
+
+/// Determine whether another write at `now` would exceed `limit`, given the times of prior writes.
+///
+fn exceeds_write_rate<'a, T>(prior_writes: T, now: &DateTime<Utc>, limit: &IndexWriteRateLimit) -> bool
+    where T: Iterator<Item = &'a DateTime<Utc>>,
+{
+    let window_start = write_window_start(now, limit);
+    prior_writes.filter(|time| **time > window_start).count() >= limit.max_writes as usize
+}
+
+/// Start of the rolling window of `limit` ending at `now`.
+///
+fn write_window_start(now: &DateTime<Utc>, limit: &IndexWriteRateLimit) -> DateTime<Utc> {
+    *now - chrono::Duration::milliseconds(limit.window_ms as i64)
+}
+
+/// Determines how time index writes respond to finding the target entry already indexed.
+///
+/// This most commonly occurs when a concurrent call indexes the same entry between the
+/// existence check and the write of the index pointer.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeIndexConflictBehavior {
+    /// Treat the write as successful, since the goal is merely to ensure the entry is indexed
+    Ignore,
+    /// Propagate the conflict to the caller as a `SemanticIndexError::BadTimeIndexError`
+    Fail,
+}
+
+impl Default for TimeIndexConflictBehavior {
+    fn default() -> Self {
+        Self::Ignore
+    }
+}
+
+/// Indexes an entry pointer (which may reference the local DNA, or a remote one)
+/// into the time-ordered index `index_name` at the given `timestamp` for subsequent
+/// ordered retrieval.
+///
+/// Multiple indexes may be created per entry, where multiple orderings are appropriate.
+///
+/// Returns `true` if the index was created, `false` if the entry was already indexed and thus skipped.
+///
+pub fn append_to_time_index<'a, A, I>(
+    index_name: &I,
+    entry_address: &A,
+    timestamp: DateTime<Utc>,
+) -> RecordAPIResult<bool>
+    where A: DnaAddressable<EntryHash>,
+        I: AsRef<str> + std::fmt::Display,
+{
+    append_to_time_index_with_conflict_behavior(index_name, entry_address, timestamp, TimeIndexConflictBehavior::default())
+}
+
+/// Variant of `append_to_time_index` allowing callers to determine how to respond to the
+/// entry having already been indexed.
+///
+pub fn append_to_time_index_with_conflict_behavior<'a, A, I>(
+    index_name: &I,
+    entry_address: &A,
+    timestamp: DateTime<Utc>,
+    on_conflict: TimeIndexConflictBehavior,
+) -> RecordAPIResult<bool>
+    where A: DnaAddressable<EntryHash>,
+        I: AsRef<str> + std::fmt::Display,
+{
+    validate_time_index_name(index_name)?;
+    // determine hash for index pointer
+    let identity = resolve_identity_address(entry_address)?;
+
+    // store fully-qualified target identifier in a loopback link
+    link_id_tag(entry_address, identity.to_owned())?;
+    // store the record type for index zomes retrieving records of several types
+    link_type_tag(index_name.as_ref(), identity.to_owned())?;
+
+    // populate a date-based index for the entry
+    let result = index_entry(index_name, identity, timestamp);
+
+    handle_time_index_result(result, on_conflict)
+}
+
+/// Removes an entry pointer from the time-ordered index `index_name`, so that it may be
+/// re-indexed at a different time.
+///
+/// Returns `true` if the entry was removed, `false` if it was not present in the index.
+///
+pub fn remove_from_time_index<'a, A, I>(
+    index_name: &I,
+    entry_address: &A,
+) -> RecordAPIResult<bool>
+    where A: DnaAddressable<EntryHash>,
+        I: AsRef<str> + std::fmt::Display,
+{
+    validate_time_index_name(index_name)?;
+    let identity = resolve_identity_address(entry_address)?;
+
+    remove_entry(index_name, identity)
+        .map_err(|e| SemanticIndexError::BadTimeIndexError(e.to_string()).into())
+}
+
+/// Determine the time at which an entry pointer was indexed into the time-ordered index
+/// `index_name`, or `None` if it is not present in the index.
+///
+pub fn read_time_index_entry_time<'a, A, I>(
+    index_name: &I,
+    entry_address: &A,
+) -> RecordAPIResult<Option<DateTime<Utc>>>
+    where A: DnaAddressable<EntryHash>,
+        I: AsRef<str> + std::fmt::Display,
+{
+    validate_time_index_name(index_name)?;
+    let identity = resolve_identity_address(entry_address)?;
+
+    get_entry_index_time(index_name, &identity)
+        .map_err(|e| SemanticIndexError::BadTimeIndexError(e.to_string()).into())
+}
+
+/// Removes many entry pointers from the time-ordered index `index_name` in a single pass.
+/// `entry_addresses` are identity hashes as stored in the index. The ordering of remaining
+/// entries is unaffected.
+///
+/// Returns the identities which were removed; those not present in the index are skipped.
+///
+pub fn remove_from_time_index_batch<I>(
+    index_name: &I,
+    entry_addresses: &[EntryHash],
+) -> RecordAPIResult<Vec<EntryHash>>
+    where I: AsRef<str> + std::fmt::Display,
+{
+    validate_time_index_name(index_name)?;
+    remove_entries(index_name, entry_addresses)
+        .map_err(|e| SemanticIndexError::BadTimeIndexError(e.to_string()).into())
+}
+
+/// Maximum number of records visited by a single call to `reindex_time_index_batch`.
+///
+pub const MAX_REINDEX_BATCH_SIZE: usize = 50;
+
+/// Rebuild the indexes of records listed in the time-ordered index `index_name`, by calling
+/// `resync_method_name` in the associated record storage zome for each of them.
+///
+/// Up to `limit` records following the `after` cursor are visited from most recent to oldest,
+/// capped at `MAX_REINDEX_BATCH_SIZE`. Only the index links needed to fill the batch are read.
+/// Records which fail to resync are reported and skipped rather than aborting the batch.
+/// Pass the returned `next_cursor` as `after` to continue from where the batch ended.
+///
+/// Each successfully resynced record is restamped with the current `SEMANTIC_INDEX_TAG_VERSION`,
+/// which clears any `IndexSchemaMismatch` raised when reading its indexes. Records which fail
+/// to resync keep their outdated stamps, so that they continue to be reported.
+///
+pub fn reindex_time_index_batch<B, C, F, I>(
+    zome_name_from_config: &F,
+    resync_method_name: &I,
+    index_name: &I,
+    after: Option<EntryHash>,
+    limit: usize,
+) -> RecordAPIResult<ReindexReport>
+    where B: DnaAddressable<EntryHash>,
+        I: AsRef<str> + std::fmt::Display + std::fmt::Debug,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError> + TryInto<B, Error = SerializedBytesError>,
+        F: Fn(C) -> Option<String>,
+{
+    validate_time_index_name(index_name)?;
+    let (batch, next_cursor) = read_time_index_page(index_name, after, limit)?;
+
+    let resync_single_record = retrieve_foreign_record::<bool, B, _,_,_>(zome_name_from_config, resync_method_name);
+
+    let mut report = ReindexReport { reindexed: 0, failures: vec![], next_cursor };
+    for identity in batch {
+        match resync_single_record(identity).and_then(|_| restamp_index_schema(identity)) {
+            Ok(_) => report.reindexed += 1,
+            Err(e) => report.failures.push(ReindexFailure { identity: identity.to_owned(), error: e.to_string() }),
+        }
+    }
+    Ok(report)
+}
+
+/// Read the addresses of records listed in the time-ordered index `index_name`, in resumable
+/// batches for administrative operations which must visit every record.
+///
+/// Up to `limit` records following the `after` cursor are read from most recent to oldest, capped
+/// at `MAX_REINDEX_BATCH_SIZE`. Pass the returned `next_cursor` as `after` to continue from where
+/// the batch ended.
+///
+pub fn read_time_index_batch<B, I>(
+    index_name: &I,
+    after: Option<EntryHash>,
+    limit: usize,
+) -> RecordAPIResult<IndexedAddresses<B>>
+    where B: DnaAddressable<EntryHash>,
+        I: AsRef<str> + std::fmt::Display + std::fmt::Debug,
+        SerializedBytes: TryInto<B, Error = SerializedBytesError>,
+{
+    validate_time_index_name(index_name)?;
+    let (batch, next_cursor) = read_time_index_page(index_name, after, limit)?;
+
+    Ok(IndexedAddresses {
+        addresses: batch.iter().map(read_remote_entry_identity).collect::<RecordAPIResult<Vec<B>>>()?,
+        next_cursor,
+    })
+}
+
+/// Maximum number of record types warmed by a single call to `warm_time_indexes`.
+///
+pub const MAX_WARM_INDEX_TYPES: usize = 10;
+
+/// Maximum number of recent entries touched per record type by `warm_time_indexes`.
+///
+pub const MAX_WARM_INDEX_DEPTH: usize = 50;
+
+/// Pre-fetch the creation time indexes of the given `record_types` (eg. `economic_event`),
+/// so that subsequent queries against them are served from locally cached link data.
+///
+/// The root links of each index are read along with the `depth` most recent entries, capped at
+/// `MAX_WARM_INDEX_DEPTH`. Linked records themselves are not retrieved. Types beyond the first
+/// `MAX_WARM_INDEX_TYPES` are returned as `skipped`. No data is written, so this is safe to repeat.
+///
+pub fn warm_time_indexes(
+    record_types: &[String],
+    depth: usize,
+) -> RecordAPIResult<WarmIndexesReport>
+{
+    let (batch, skipped) = select_warm_index_types(record_types);
+    let depth = depth.clamp(1, MAX_WARM_INDEX_DEPTH);
+
+    let mut report = WarmIndexesReport { touched: vec![], skipped };
+    for record_type in batch {
+        let index_name = time_index_name(&record_type, CREATED_TIME_INDEX_NAME);
+        let root_hash = Path::from(index_name.as_str()).path_entry_hash()?;
+        let root_links = get_links(root_hash, LinkTypes::TimeIndex, None)?;
+        let recent = get_latest_entry_hashes(&index_name, depth)
+            .map_err(|e| { SemanticIndexError::BadTimeIndexError(e.to_string()) })?;
+
+        report.touched.push(WarmedIndex {
+            record_type,
+            links_touched: (root_links.len() + recent.len()) as u32,
+        });
+    }
+    Ok(report)
+}
+
+/// Split requested record types into those warmed by this call and those deferred to the
+/// next. Duplicate and empty type names are ignored.
+///
+fn select_warm_index_types(record_types: &[String]) -> (Vec<String>, Vec<String>) {
+    let mut types: Vec<String> = vec![];
+    for record_type in record_types.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        if !types.iter().any(|t| t == record_type) {
+            types.push(record_type.to_string());
+        }
+    }
+    let skipped = types.split_off(usize::min(types.len(), MAX_WARM_INDEX_TYPES));
+    (types, skipped)
+}
+
+/// Read a page of up to `limit` entries from the time-ordered index `index_name`, from most recent
+/// to oldest, following the `after` cursor. Only the index links needed to fill the page are read.
+/// Returns the page along with the cursor for the next page, if any entries remain.
+///
+fn read_time_index_page<I>(
+    index_name: &I,
+    after: Option<EntryHash>,
+    limit: usize,
+) -> RecordAPIResult<(Vec<EntryHash>, Option<EntryHash>)>
+    where I: AsRef<str> + std::fmt::Display,
+{
+    let limit = limit.clamp(1, MAX_REINDEX_BATCH_SIZE);
+    // read one more entry than requested to determine whether any remain
+    let page = match &after {
+        None => get_latest_entry_hashes(index_name, limit + 1),
+        Some(cursor) => get_older_entry_hashes(index_name, cursor.to_owned(), limit + 1),
+    }.map_err(|e| match e {
+        TimeIndexingError::NotIndexed(_, _) => SemanticIndexError::BadTimeIndexError(format!("cursor {:?} not found in {}", after, index_name)),
+        e => SemanticIndexError::BadTimeIndexError(e.to_string()),
+    })?;
+    Ok(split_index_page(page, limit))
+}
+
+/// Truncate a page of entries read with one more than `limit` entries, returning the
+/// cursor for the next page if the extra entry was found.
+///
+fn split_index_page(mut page: Vec<EntryHash>, limit: usize) -> (Vec<EntryHash>, Option<EntryHash>) {
+    if page.len() <= limit {
+        return (page, None);
+    }
+    page.truncate(limit);
+    let next_cursor = page.last().cloned();
+    (page, next_cursor)
+}
+
+/// Interpret the result of a time index write according to the requested conflict behaviour.
+///
+fn handle_time_index_result(
+    result: Result<(), TimeIndexingError>,
+    on_conflict: TimeIndexConflictBehavior,
+) -> RecordAPIResult<bool> {
+    match (result, on_conflict) {
+        (Ok(_), _) => Ok(true),
+        (Err(TimeIndexingError::AlreadyIndexed(_, _)), TimeIndexConflictBehavior::Ignore) => Ok(false),
+        (Err(e), _) => Err(SemanticIndexError::BadTimeIndexError(e.to_string()).into())
+    }
+}
+
+/// Creates a 'destination' query index used for following a link from some external record
+/// into records contained within the current DNA / zome.
+///
+/// This basically consists of bidirectional links between the identity `Path` of the remote
+/// content and its `dest_addresses`. The identifier of the remote content itself is stored
+/// separately, by the caller.
+///
+fn create_remote_index_destination<A, B, S>(
+    source: &A,
+    dest_addresses: &[B],
+    link_tag: &S,
+    link_tag_reciprocal: &S,
+) -> RecordAPIResult<Vec<RecordAPIResult<ActionHash>>>
+    where S: AsRef<[u8]> + ?Sized,
+        A: DnaAddressable<EntryHash>,
+        B: DnaAddressable<EntryHash>,
+{
+    // link all referenced records to this pointer to the remote origin record
+    Ok(dest_addresses.iter()
+        .flat_map(create_dest_identities_and_indexes(source, link_tag, link_tag_reciprocal))
+        .collect()
+    )
+}
+
+fn create_dest_identities_and_indexes<'a, A, B, S>(
+    source: &'a A,
+    link_tag: &'a S,
+    link_tag_reciprocal: &'a S,
+) -> Box<dyn for<'r> Fn(&B) -> Vec<RecordAPIResult<ActionHash>> + 'a>
+    where S: 'a + AsRef<[u8]> + ?Sized,
+        A: DnaAddressable<EntryHash>,
+        B: 'a + DnaAddressable<EntryHash>,
+{
+    let base_method = create_dest_indexes(source, link_tag, link_tag_reciprocal);
+
+    Box::new(move |dest| {
+        match ensure_id_tag(dest) {
+            Ok(_hash) => {
+                base_method(dest)
+            },
+            Err(e) => vec![Err(e)],
+        }
+    })
+}
+
+/// Helper for index update to add multiple destination links from some source.
+fn create_dest_indexes<'a, A, B, S>(
+    source: &'a A,
+    link_tag: &'a S,
+    link_tag_reciprocal: &'a S,
+) -> Box<dyn for<'r> Fn(&B) -> Vec<RecordAPIResult<ActionHash>> + 'a>
+    where S: 'a + AsRef<[u8]> + ?Sized,
+        A: DnaAddressable<EntryHash>,
+        B: DnaAddressable<EntryHash>,
+{
+    Box::new(move |dest| {
+        // write bidirectional links between `source` and `dest` entry hashes
+        match create_index(source, dest, link_tag, link_tag_reciprocal) {
+            // links OK. iterate over newly created index links
+            Ok(created) => created.iter().cloned()
+                .filter(|r| (r.is_ok() && r.as_ref().unwrap().is_some()) || r.is_err())
+                .map(|r| if r.is_err() {
+                    // propagate any link creation errors which may have failed integrity zome validation or otherwise errored
+                    Err(r.err().unwrap())
+                } else {
+                    // return all `ActionHash`es of newly created links
+                    Ok(r.unwrap().unwrap())
+                })
+                .collect(),
+            // index creation failed. Return an error to the caller with the destination link target as metadata.
+            Err(_) => {
+                let h: &EntryHash = dest.as_ref();
+                vec![Err(SemanticIndexError::IndexNotFound(h.clone()).into())]
+            },
+        }
+    })
+}
+
+/// Creates a bidirectional link between two entry addresses, and returns a vector
+/// of the `ActionHash`es of the (respectively) forward & reciprocal links created.
+/// Failure to create one link does not prevent creation of the other.
+fn create_index<A, B, S>(
+    source: &A,
+    dest: &B,
+    link_tag: &S,
+    link_tag_reciprocal: &S,
+) -> RecordAPIResult<Vec<RecordAPIResult<Option<ActionHash>>>>
+    where S: AsRef<[u8]> + ?Sized,
+        A: DnaAddressable<EntryHash>,
+        B: DnaAddressable<EntryHash>,
+{
+    let source_hash = resolve_identity_address(source)?;
+    let dest_hash = resolve_identity_address(dest)?;
+
+    Ok(vec! [
+        link_index_if_not_linked(&source_hash, &dest_hash, link_tag),
+        link_index_if_not_linked(&dest_hash, &source_hash, link_tag_reciprocal),
+    ])
+}
+
+//-------------------------------[ DELETE ]-------------------------------------
+
+/// Deletes a set of links between a remote record reference and some set
+/// of local target EntryHashes.
+///
+/// The `Path` representing the remote target is not
+/// affected in the removal, and is simply left dangling in the
+/// DHT space as an indicator of previously linked items.
+///
+/// If `removed_at` is given, removed links are replaced with tombstones recording that time.
+///
+fn remove_remote_index_links<A, B, S>(
+    source: &A,
+    remove_addresses: &[B],
+    link_tag: &S,
+    link_tag_reciprocal: &S,
+    removed_at: Option<Timestamp>,
+) -> RecordAPIResult<Vec<RecordAPIResult<ActionHash>>>
+    where S: AsRef<[u8]> + ?Sized,
+        A: DnaAddressable<EntryHash>,
+        B: DnaAddressable<EntryHash>,
+{
+    Ok(remove_addresses.iter()
+        .flat_map(delete_dest_indexes(
+            source, link_tag, link_tag_reciprocal, removed_at,
+        ))
+        .collect()
+    )
+}
+
+/// Helper for index update to remove multiple destination links from some source.
+fn delete_dest_indexes<'a, A, B, S>(
+    source: &'a A,
+    link_tag: &'a S,
+    link_tag_reciprocal: &'a S,
+    removed_at: Option<Timestamp>,
+) -> Box<dyn for<'r> Fn(&B) -> Vec<RecordAPIResult<ActionHash>> + 'a>
+    where S: 'a + AsRef<[u8]> + ?Sized,
+        A: DnaAddressable<EntryHash>,
+        B: DnaAddressable<EntryHash>,
+{
+    Box::new(move |dest_addr| {
+        match delete_index(source, dest_addr, link_tag, link_tag_reciprocal, removed_at) {
+            Ok(deleted) => deleted,
+            Err(_) => {
+                let dest_hash: &EntryHash = dest_addr.as_ref();
+                vec![Err(SemanticIndexError::IndexNotFound(dest_hash.clone()).into())]
+            },
+        }
+    })
+}
+
+/// Deletes a bidirectional link between two entry addresses. Any active links between
+/// the given addresses using the given tags will be deleted, and replaced with tombstones
+/// if `removed_at` is given.
+/// Failure to read links in one direction does not prevent removal of the other.
+///
+fn delete_index<'a, A, B, S>(
+    source: &A,
+    dest: &B,
+    link_tag: &S,
+    link_tag_reciprocal: &S,
+    removed_at: Option<Timestamp>,
+) -> RecordAPIResult<Vec<RecordAPIResult<ActionHash>>>
+    where S: 'a + AsRef<[u8]> + ?Sized,
+        A: DnaAddressable<EntryHash>,
+        B: DnaAddressable<EntryHash>,
+{
+    let address_source = resolve_identity_address(source)?;
+    let address_dest = resolve_identity_address(dest)?;
+
+    let mut links = remove_index_links_between(&address_source, &address_dest, link_tag, removed_at);
+    links.append(&mut remove_index_links_between(&address_dest, &address_source, link_tag_reciprocal, removed_at));
+
+    Ok(links)
+}
+
+/// Deletes one direction of a bidirectional index link, from `base_address` to `target_address`.
+///
+fn remove_index_links_between<S>(
+    base_address: &EntryHash,
+    target_address: &EntryHash,
+    link_tag: &S,
+    removed_at: Option<Timestamp>,
+) -> Vec<RecordAPIResult<ActionHash>>
+    where S: AsRef<[u8]> + ?Sized,
+{
+    walk_links_matching_entry(
+        base_address,
+        target_address,
+        link_tag,
+        |l| remove_index_link(l, base_address, link_tag, removed_at),
+    ).unwrap_or_else(|e| vec![Err(e)])
+}
+
+/// Marks a bidirectional link between two entry addresses as deprecated. Active links between
+/// the given addresses using the given tags are replaced with deprecated links, which are skipped
+/// by normal index reads but returned by `read_index_with_deprecated`.
+/// Failure to read links in one direction does not prevent deprecation of the other.
+///
+pub fn deprecate_index<A, B, S>(
+    source: &A,
+    dest: &B,
+    link_tag: &S,
+    link_tag_reciprocal: &S,
+) -> RecordAPIResult<Vec<RecordAPIResult<ActionHash>>>
+    where S: AsRef<[u8]> + ?Sized,
+        A: DnaAddressable<EntryHash>,
+        B: DnaAddressable<EntryHash>,
+{
+    let address_source = resolve_identity_address(source)?;
+    let address_dest = resolve_identity_address(dest)?;
+
+    let mut links = walk_links_matching_entry(
+        &address_source,
+        &address_dest,
+        link_tag,
+        |l| deprecate_index_link(l, &address_source, link_tag),
+    ).unwrap_or_else(|e| vec![Err(e)]);
+    links.append(&mut walk_links_matching_entry(
+        &address_dest,
+        &address_source,
+        link_tag_reciprocal,
+        |l| deprecate_index_link(l, &address_dest, link_tag_reciprocal),
+    ).unwrap_or_else(|e| vec![Err(e)]));
+
+    Ok(links)
+}
+
+//------------------------------[ ADMINISTRATION ]------------------------------
+
+/// An operation read from the index admin log, with the agent who performed it and when.
+///
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexAdminLogEntry {
+    pub author: AgentPubKey,
+    pub timestamp: Timestamp,
+    pub action: IndexAdminAction,
+}
+
+// minimal view of the network settings record, as returned by `get_network_settings`
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NetworkAdminSettings {
+    #[serde(default)]
+    admin_agents: Vec<AgentPubKey>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MaybeNetworkAdminSettings {
+    network_settings: Option<NetworkAdminSettings>,
+}
+
+/// Ensure the calling agent is a network admin, as listed in the network settings held by the zome
+/// named by `settings_zome_from_config`. Operations gated on this check are unavailable to everyone
+/// where no settings zome is configured for the index zome, or no settings have been created.
+///
+pub fn throw_if_not_network_admin<C, F>(settings_zome_from_config: F) -> RecordAPIResult<()>
+    where F: FnOnce(C) -> Option<String>,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError>,
+{
+    if !is_network_admin(settings_zome_from_config, &agent_info()?.agent_latest_pubkey)? {
+        return Err(DataIntegrityError::NotNetworkAdmin);
+    }
+    Ok(())
+}
+
+/// Ensure the calling agent either created the record identified by `address`, or is a network admin.
+///
+pub fn throw_if_not_record_author_or_network_admin<A, C, F>(address: &A, settings_zome_from_config: F) -> RecordAPIResult<()>
+    where A: DnaAddressable<EntryHash>,
+        F: FnOnce(C) -> Option<String>,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError>,
+{
+    if read_record_author(address.as_ref())? == agent_info()?.agent_latest_pubkey {
+        return Ok(());
+    }
+    throw_if_not_network_admin(settings_zome_from_config)
+}
+
+/// Determine whether `agent` is a network admin, as listed in the network settings held by the zome
+/// named by `settings_zome_from_config`. Nobody is an admin where settings are not in use.
+///
+fn is_network_admin<C, F>(settings_zome_from_config: F, agent: &AgentPubKey) -> RecordAPIResult<bool>
+    where F: FnOnce(C) -> Option<String>,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError>,
+{
+    let resp: OtherCellResult<MaybeNetworkAdminSettings> = call_local_zome_method(
+        settings_zome_from_config,
+        String::from("get_network_settings"),
+        (),
+    );
+    match resp {
+        Ok(MaybeNetworkAdminSettings { network_settings: Some(s) }) => Ok(s.admin_agents.contains(agent)),
+        Ok(_) | Err(CrossCellError::NotConfigured(_, _)) => Ok(false),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// API method of the agent zome reporting whether a given agent has an associated `Agent` profile.
+///
+pub const HAS_ASSOCIATED_AGENT_API_METHOD: &str = "has_associated_agent";
+
+/// Refuse queries from agents who are not network members where the DNA's `QueryAccess` is `MembersOnly`,
+/// erroring with `DataIntegrityError::NotNetworkMember`.
+///
+/// The agent checked is the caller of the current zome call, which differs from the cell's own agent
+/// for calls made under capability grants. Members are agents with an associated `Agent` profile in
+/// the zome read by `agent_zome_from_config`, and network admins as read by `settings_zome_from_config`.
+/// Where no agent zome is configured membership cannot be determined, and callers other than admins
+/// receive a `DataIntegrityError::LocalIndexNotConfigured`. A successful check is remembered for the
+/// remainder of the zome call.
+///
+pub fn throw_if_query_not_permitted<C, F, G, H>(
+    query_access_from_config: F,
+    agent_zome_from_config: G,
+    settings_zome_from_config: H,
+) -> RecordAPIResult<()>
+    where F: FnOnce(C) -> QueryAccess,
+        G: FnOnce(C) -> Option<String>,
+        H: FnOnce(C) -> Option<String>,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError>,
+{
+    let query_access = dna_info().ok()
+        .and_then(|info| info.modifiers.properties.try_into().ok())
+        .map(query_access_from_config)
+        .unwrap_or_default();
+    if query_access == QueryAccess::Open || QUERY_ACCESS_GRANTED.with(Cell::get) {
+        return Ok(());
+    }
+
+    let caller = call_info()?.provenance;
+    if !is_network_admin(settings_zome_from_config, &caller)? {
+        let associated: OtherCellResult<bool> = call_local_zome_method(
+            agent_zome_from_config,
+            HAS_ASSOCIATED_AGENT_API_METHOD.to_string(),
+            caller,
+        );
+        match associated {
+            Ok(true) => {},
+            Ok(false) => return Err(DataIntegrityError::NotNetworkMember),
+            Err(CrossCellError::NotConfigured(_, _)) => return Err(DataIntegrityError::LocalIndexNotConfigured(
+                "agent_zome".to_string(),
+                "members-only queries require an agent zome in the same DNA".to_string(),
+            )),
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    QUERY_ACCESS_GRANTED.with(|g| g.set(true));
+    Ok(())
+}
+
+/// Refuse reads of individual index entries by non-members where the DNA's `QueryAccess` is `MembersOnly`,
+/// as for `throw_if_query_not_permitted`.
+///
+/// Reads made by the cell's own agent are always permitted, as these back the by-ID read APIs of
+/// record storage zomes which agents need in order to create and associate their `Agent` profile.
+///
+pub fn throw_if_index_read_not_permitted<C, F, G, H>(
+    query_access_from_config: F,
+    agent_zome_from_config: G,
+    settings_zome_from_config: H,
+) -> RecordAPIResult<()>
+    where F: FnOnce(C) -> QueryAccess,
+        G: FnOnce(C) -> Option<String>,
+        H: FnOnce(C) -> Option<String>,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError>,
+{
+    if call_info()?.provenance == agent_info()?.agent_latest_pubkey {
+        return Ok(());
+    }
+    throw_if_query_not_permitted(query_access_from_config, agent_zome_from_config, settings_zome_from_config)
+}
+
+/// Removes the bidirectional index link between `source` and `dest`, for repair of index data
+/// independently of the records involved. The operation is recorded in the index admin log.
+///
+/// Finding no links to remove is not an error, but is reported as a warning.
+/// If `removed_at` is given, removed links are replaced with tombstones recording that time.
+///
+/// This is an administrative operation, callers must check permissions via `throw_if_not_network_admin`.
+///
+pub fn unlink_index<A, B>(
+    source_type: &str,
+    source: &A,
+    dest_type: &str,
+    dest: &B,
+    link_tag: &str,
+    link_tag_reciprocal: &str,
+    removed_at: Option<Timestamp>,
+) -> RecordAPIResult<IndexLinkAdminReport>
+    where A: DnaAddressable<EntryHash>,
+        B: DnaAddressable<EntryHash>,
+{
+    let address_source = resolve_identity_address(source)?;
+    let address_dest = resolve_identity_address(dest)?;
+
+    let forward = remove_index_links_between(&address_source, &address_dest, link_tag, removed_at);
+    let reciprocal = remove_index_links_between(&address_dest, &address_source, link_tag_reciprocal, removed_at);
+
+    let report = index_admin_report(
+        IndexAdminOperation::Unlink,
+        forward.iter().any(Result::is_ok),
+        reciprocal.iter().any(Result::is_ok),
+    );
+    // record any removals made prior to reporting failures, since they cannot be rolled back
+    write_index_admin_action(IndexAdminAction {
+        operation: IndexAdminOperation::Unlink,
+        source_type: source_type.to_string(),
+        source: address_pair(source),
+        dest_type: dest_type.to_string(),
+        dest: address_pair(dest),
+        link_tag: link_tag.to_string(),
+        link_tag_reciprocal: link_tag_reciprocal.to_string(),
+        forward_changed: report.forward,
+        reciprocal_changed: report.reciprocal,
+    })?;
+    throw_any_error(forward.into_iter().chain(reciprocal).filter(Result::is_err).collect())?;
+
+    Ok(report)
+}
+
+/// Creates the bidirectional index link between `source` and `dest`, for repair of index data
+/// independently of the records involved. The inverse of `unlink_index`.
+///
+/// Finding both directions already linked is not an error, but is reported as a warning.
+///
+/// This is an administrative operation, callers must check permissions via `throw_if_not_network_admin`.
+///
+pub fn link_index<A, B>(
+    source_type: &str,
+    source: &A,
+    dest_type: &str,
+    dest: &B,
+    link_tag: &str,
+    link_tag_reciprocal: &str,
+) -> RecordAPIResult<IndexLinkAdminReport>
+    where A: DnaAddressable<EntryHash>,
+        B: DnaAddressable<EntryHash>,
+{
+    ensure_id_tag(source)?;
+    ensure_id_tag(dest)?;
+    let address_source = resolve_identity_address(source)?;
+    let address_dest = resolve_identity_address(dest)?;
+
+    let forward = link_index_if_not_linked(&address_source, &address_dest, link_tag)?;
+    let reciprocal = link_index_if_not_linked(&address_dest, &address_source, link_tag_reciprocal)?;
+
+    let report = index_admin_report(IndexAdminOperation::Link, forward.is_some(), reciprocal.is_some());
+    write_index_admin_action(IndexAdminAction {
+        operation: IndexAdminOperation::Link,
+        source_type: source_type.to_string(),
+        source: address_pair(source),
+        dest_type: dest_type.to_string(),
+        dest: address_pair(dest),
+        link_tag: link_tag.to_string(),
+        link_tag_reciprocal: link_tag_reciprocal.to_string(),
+        forward_changed: report.forward,
+        reciprocal_changed: report.reciprocal,
+    })?;
+
+    Ok(report)
+}
+
+/// Reads all operations recorded in the index admin log, oldest first.
+///
+pub fn read_index_admin_log() -> RecordAPIResult<Vec<IndexAdminLogEntry>> {
+    let anchor = Path::from(INDEX_ADMIN_LOG_ANCHOR).path_entry_hash()?;
+    let mut links = get_links(anchor, LinkTypes::IndexAdminLog, None)?;
+    links.sort_by_key(|l| l.timestamp);
+
+    links.iter()
+        .filter_map(|l| l.target.to_owned().into_action_hash())
+        .map(|action_hash| {
+            let record = get(action_hash, GetOptions::default())?
+                .ok_or(DataIntegrityError::EntryNotFound)?;
+            let action = record.entry().to_app_option::<IndexAdminAction>().ok().flatten()
+                .ok_or(DataIntegrityError::EntryWrongType)?;
+            Ok(IndexAdminLogEntry {
+                author: record.action().author().to_owned(),
+                timestamp: record.action().timestamp(),
+                action,
+            })
+        })
+        .collect()
+}
+
+/// Build the response to an administrative link or unlink operation, given which directions
+/// of the link were changed.
+///
+fn index_admin_report(operation: IndexAdminOperation, forward: bool, reciprocal: bool) -> IndexLinkAdminReport {
+    let warning = match (forward || reciprocal, operation) {
+        (true, _) => None,
+        (false, IndexAdminOperation::Unlink) => Some("No index links were found between the given records".to_string()),
+        (false, IndexAdminOperation::Link) => Some("The given records were already linked in both directions".to_string()),
+    };
+    IndexLinkAdminReport { forward, reciprocal, warning }
+}
+
+/// Commit an `IndexAdminAction` to the admin log. The log links target the committing action,
+/// so that identical operations performed more than once are each attributed correctly.
+///
+fn write_index_admin_action(action: IndexAdminAction) -> RecordAPIResult<ActionHash> {
+    let action_hash = create_entry(EntryTypes::IndexAdminAction(action))?;
+    let anchor = Path::from(INDEX_ADMIN_LOG_ANCHOR).path_entry_hash()?;
+    create_link(anchor, action_hash.to_owned(), LinkTypes::IndexAdminLog, ())?;
+    Ok(action_hash)
+}
+
+fn address_pair<A>(address: &A) -> (DnaHash, EntryHash)
+    where A: DnaAddressable<EntryHash>,
+{
+    (AsRef::<DnaHash>::as_ref(address).to_owned(), AsRef::<EntryHash>::as_ref(address).to_owned())
+}
+
+//--------------------------[ UTILITIES  / INTERNALS ]---------------------
+
+/// Generate a link tag for the identity anchor of a record by encoding the ID string into the tag
+/// so that it can be retreived by querying the DHT later.
+///
+fn ensure_id_tag<A>(ident: &A) -> RecordAPIResult<Option<ActionHash>>
+    where A: DnaAddressable<EntryHash>,
+{
+    let identity = resolve_identity_address(ident)?;
+    link_id_tag(ident, identity)
+}
+
+fn link_id_tag<A>(ident: &A, identity: EntryHash) -> RecordAPIResult<Option<ActionHash>>
+    where A: DnaAddressable<EntryHash>,
+{
+    let mode = match registered_tag_address_mode() {
+        TagAddressMode::FullAddress => TagAddressMode::FullAddress,
+        mode => tag_address_mode_for(mode, ident.as_ref(), &dna_info()?.hash),
+    };
+    let id_tag = encode_identity_link_tag(ident, mode);
+
+    let created = link_if_not_linked(identity.to_owned(), identity.to_owned(), LinkTypes::EntryUUID, id_tag)?;
+    // identities are stamped when first indexed, so existing identities need no further reads
+    if created.is_some() {
+        stamp_index_schema(&identity)?;
+    }
+    Ok(created)
+}
+
+/// Record the type of the entry at `identity`, being the record type namespace of `index_name`.
+///
+/// Errors if the entry has already been recorded as a different type.
+///
+fn link_type_tag(index_name: &str, identity: EntryHash) -> RecordAPIResult<Option<ActionHash>> {
+    let record_type = match time_index_record_type(index_name) {
+        Some(record_type) => record_type,
+        None => return Ok(None),
+    };
+    let existing = read_identity_entry_type(&identity)?;
+    check_identity_entry_type(&identity, existing.as_deref(), record_type)?;
+    link_if_not_linked(identity.to_owned(), identity, LinkTypes::EntryUUID, encode_identity_type_tag(record_type))
+}
+
+/// The record type namespacing `index_name`, or `None` for unnamespaced indexes and those ordering
+/// records referenced from remote DNAs, whose namespace names a relationship.
+///
+fn time_index_record_type(index_name: &str) -> Option<&str> {
+    match index_name.split_once(TIME_INDEX_NAMESPACE_SEPARATOR) {
+        Some((_, REMOTE_RECORD_TIME_INDEX_NAME)) => None,
+        Some((record_type, _)) if !record_type.is_empty() => Some(record_type),
+        _ => None,
+    }
+}
+
+/// Ensure the record at `identity`, `existing`ly typed, may be recorded as `record_type`.
+///
+fn check_identity_entry_type(identity: &EntryHash, existing: Option<&str>, record_type: &str) -> Result<(), SemanticIndexError> {
+    match existing {
+        Some(existing) if existing != record_type => Err(SemanticIndexError::ConflictingRecordType(
+            identity.to_owned(), existing.to_string(), record_type.to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Build the tag of the link recording the type of the record at an identity.
+///
+fn encode_identity_type_tag(entry_type: &str) -> LinkTag {
+    LinkTag::new([RECORD_IDENTITY_TYPE_LINK_TAG, entry_type.as_bytes()].concat())
+}
+
+/// Decode the record type from an identity type link tag, if it is one.
+///
+pub fn decode_identity_type_tag(tag: &LinkTag) -> Option<String> {
+    tag.0.strip_prefix(RECORD_IDENTITY_TYPE_LINK_TAG)
+        .and_then(|bytes| String::from_utf8(bytes.to_vec()).ok())
+}
+
+/// Read the type of the record at `identity_address`, if it has been recorded.
+///
+fn read_identity_entry_type(identity_address: &EntryHash) -> RecordAPIResult<Option<String>> {
+    Ok(get_links(identity_address.to_owned(), LinkTypes::EntryUUID, Some(LinkTag::new(RECORD_IDENTITY_TYPE_LINK_TAG)))?
+        .iter()
+        .find_map(|link| decode_identity_type_tag(&link.tag)))
+}
+
+/// Select the zome storing records of `entry_type` from `record_storage_zomes`, being the zome paired
+/// with the first entry type prefix which `entry_type` begins with.
+///
+/// Returns `None` for records of unknown type or types not listed, which should be retrieved from
+/// the zome's `record_storage_zome`.
+///
+pub fn select_record_storage_zome(record_storage_zomes: &[(String, String)], entry_type: Option<&str>) -> Option<String> {
+    let entry_type = entry_type?;
+    record_storage_zomes.iter()
+        .find(|(prefix, _)| entry_type.starts_with(prefix.as_str()))
+        .map(|(_, zome_name)| zome_name.to_owned())
+}
+
+/// Build the tag of the link recording the index schema `version` of a record's identity.
+///
+fn encode_index_schema_tag(version: u8) -> LinkTag {
+    LinkTag::new([RECORD_INDEX_SCHEMA_LINK_TAG, &[version]].concat())
+}
+
+/// Decode the schema version from an index schema link tag, if it is one.
+///
+pub fn parse_index_schema_tag(tag: &LinkTag) -> Option<u8> {
+    match tag.0.strip_prefix(RECORD_INDEX_SCHEMA_LINK_TAG) {
+        Some([version]) => Some(*version),
+        _ => None,
+    }
+}
+
+/// Check the schema versions stamped on an index against `SEMANTIC_INDEX_TAG_VERSION`.
+///
+/// Indexes without any stamp predate schema versioning and are read as-is.
+///
+pub fn check_index_schema_versions(found: &[u8]) -> RecordAPIResult<()> {
+    match found.iter().find(|v| **v != SEMANTIC_INDEX_TAG_VERSION) {
+        Some(version) => Err(DataIntegrityError::IndexSchemaMismatch(*version, SEMANTIC_INDEX_TAG_VERSION)),
+        None => Ok(()),
+    }
+}
+
+fn read_index_schema_versions(identity: &EntryHash) -> RecordAPIResult<Vec<(ActionHash, u8)>> {
+    Ok(get_links(identity.to_owned(), LinkTypes::EntryUUID, Some(LinkTag::new(RECORD_INDEX_SCHEMA_LINK_TAG)))?
+        .into_iter()
+        .filter_map(|l| parse_index_schema_tag(&l.tag).map(|version| (l.create_link_hash, version)))
+        .collect())
+}
+
+/// Error if the indexes of `identity` were written under a different schema version,
+/// rather than returning results which may be incomplete or misread.
+///
+fn verify_index_schema(identity: &EntryHash) -> RecordAPIResult<()> {
+    let versions: Vec<u8> = read_index_schema_versions(identity)?.into_iter().map(|(_, v)| v).collect();
+    check_index_schema_versions(versions.as_slice())
+}
+
+/// Stamp the current schema version onto `identity` when it is first indexed.
+/// Existing stamps are left alone so that outdated indexes continue to be reported.
+///
+fn stamp_index_schema(identity: &EntryHash) -> RecordAPIResult<()> {
+    if read_index_schema_versions(identity)?.is_empty() {
+        create_link(identity.to_owned(), identity.to_owned(), LinkTypes::EntryUUID, encode_index_schema_tag(SEMANTIC_INDEX_TAG_VERSION))?;
+    }
+    Ok(())
+}
+
+/// Replace any outdated schema stamps on `identity` with the current version, prior to rebuilding its indexes.
+///
+fn restamp_index_schema(identity: &EntryHash) -> RecordAPIResult<()> {
+    let versions = read_index_schema_versions(identity)?;
+    for (link, _) in versions.iter().filter(|(_, v)| *v != SEMANTIC_INDEX_TAG_VERSION) {
+        delete_link(link.to_owned())?;
+    }
+    if !versions.iter().any(|(_, v)| *v == SEMANTIC_INDEX_TAG_VERSION) {
+        create_link(identity.to_owned(), identity.to_owned(), LinkTypes::EntryUUID, encode_index_schema_tag(SEMANTIC_INDEX_TAG_VERSION))?;
+    }
+    Ok(())
+}
+
+/// The `TagAddressMode` used for the identity tag of a record in `record_dna`, given the zome's
+/// configured `mode`. Records in DNAs other than `local_dna` always have their full address
+/// encoded, since addresses decoded without a `DnaHash` are assigned the local DNA.
+///
+fn tag_address_mode_for(mode: TagAddressMode, record_dna: &DnaHash, local_dna: &DnaHash) -> TagAddressMode {
+    match mode {
+        TagAddressMode::EntryHashOnly if record_dna != local_dna => TagAddressMode::FullAddress,
+        mode => mode,
+    }
+}
+
+/// Build the tag of the link from a record's identity to itself, which encodes the record's address.
+///
+pub fn encode_identity_link_tag<A>(address: &A, mode: TagAddressMode) -> LinkTag
+    where A: DnaAddressable<EntryHash>,
+{
+    let dna: &DnaHash = address.as_ref();
+    let hash: &EntryHash = address.as_ref();
+    match mode {
+        TagAddressMode::FullAddress => LinkTag::new([crate::RECORD_IDENTITY_LINK_TAG, dna.as_ref(), hash.as_ref()].concat()),
+        TagAddressMode::EntryHashOnly => LinkTag::new([crate::RECORD_IDENTITY_LINK_TAG, hash.as_ref()].concat()),
+    }
+}
+
+/// Decode the record address from an identity link tag written in either `TagAddressMode`.
+/// Addresses written without a `DnaHash` are assigned `local_dna`.
+///
+pub fn decode_identity_link_tag(tag: &LinkTag, local_dna: &DnaHash) -> Result<(DnaHash, EntryHash), String> {
+    let bytes = tag.0.strip_prefix(crate::RECORD_IDENTITY_LINK_TAG)
+        .ok_or_else(|| "not an identity link tag".to_string())?;
+    match bytes.len() {
+        len if len == HOLO_HASH_FULL_LEN * 2 => Ok((
+            DnaHash::from_raw_39(bytes[0..HOLO_HASH_FULL_LEN].to_vec()).map_err(|e| e.to_string())?,
+            EntryHash::from_raw_39(bytes[HOLO_HASH_FULL_LEN..].to_vec()).map_err(|e| e.to_string())?,
+        )),
+        len if len == HOLO_HASH_FULL_LEN => Ok((
+            local_dna.to_owned(),
+            EntryHash::from_raw_39(bytes.to_vec()).map_err(|e| e.to_string())?,
+        )),
+        len => Err(format!("identity link tag has unexpected address length {}", len)),
+    }
+}
+
+/// Known state of an identity address under some `IdentityScheme`, used for identity resolution
+///
+#[derive(Debug, Clone, PartialEq)]
+enum IdentityState {
+    /// identity has been migrated and aliases the given identity
+    Aliased(EntryHash),
+    /// identity has been written under this scheme
+    Exists,
+    /// no identity has been written under this scheme
+    Missing,
+}
+
+fn read_identity_state(identity: &EntryHash) -> RecordAPIResult<IdentityState> {
+    // aliases not linking two identities of the same record are disregarded, so cannot redirect an identity
+    for link in get_links(identity.to_owned(), LinkTypes::IdentityAlias, Some(LinkTag::new(RECORD_IDENTITY_ALIAS_LINK_TAG)))? {
+        if let Some(aliased) = link.target.into_entry_hash() {
+            if is_valid_identity_alias(identity, &aliased, &link.tag)? {
+                return Ok(IdentityState::Aliased(aliased));
+            }
+        }
+    }
+
+    let has_id = get_links(identity.to_owned(), LinkTypes::EntryUUID, Some(LinkTag::new(crate::RECORD_IDENTITY_LINK_TAG)))?.len() > 0;
+    Ok(if has_id { IdentityState::Exists } else { IdentityState::Missing })
+}
+
+/// Select the identity to use from a set of `(scheme, identity)` candidates, ordered by preference.
+///
+/// The first candidate which is aliased or known to exist is used, with aliases followed
+/// a single hop. If no identity exists yet, the first (current) scheme's identity is used.
+///
+fn select_identity_address<F>(
+    candidates: Vec<(IdentityScheme, EntryHash)>,
+    read_state: F,
+) -> RecordAPIResult<EntryHash>
+    where F: Fn(&EntryHash) -> RecordAPIResult<IdentityState>,
+{
+    let default_identity = candidates.first().map(|(_, id)| id.to_owned());
+
+    for (_scheme, identity) in candidates {
+        match read_state(&identity)? {
+            IdentityState::Aliased(aliased) => return Ok(aliased),
+            IdentityState::Exists => return Ok(identity),
+            IdentityState::Missing => (),
+        }
+    }
+
+    default_identity.ok_or(DataIntegrityError::EntryNotFound)
+}
+
+/// Determine the identity `EntryHash` which index links for `base_address` are based from.
+///
+/// Identities are attempted under the current `IdentityScheme` first, before falling back to
+/// any legacy schemes configured via `identity_schemes` in the DNA properties.
+///
+pub fn resolve_identity_address<A>(
+    base_address: &A,
+) -> RecordAPIResult<EntryHash>
+    where A: DnaAddressable<EntryHash>,
+{
+    let config = read_identity_scheme_config()?;
+    let candidates = identity_address_candidates(base_address, &config)?;
+
+    // skip DHT reads where only a single scheme is in use
+    if candidates.len() == 1 {
+        return Ok(candidates[0].1.to_owned());
+    }
+
+    select_identity_address(candidates, read_identity_state)
+}
+
+/// Write the identity of `address` under the current `IdentityScheme` and alias it to
+/// the legacy identity holding its index data, so that subsequent reads resolve in a single hop.
+///
+/// Returns the identity address which the record's index data resides at.
+///
+/// This is an administrative operation and should not be exposed to remote callers.
+///
+pub fn migrate_identity<A>(
+    address: &A,
+) -> RecordAPIResult<EntryHash>
+    where A: DnaAddressable<EntryHash>,
+{
+    let config = read_identity_scheme_config()?;
+    let current_identity = calculate_identity_address_for_scheme(address, config.current)?;
+
+    // find the legacy identity which holds existing index data
+    let legacy_identity = config.read_order().into_iter()
+        .skip(1)
+        .map(|scheme| calculate_identity_address_for_scheme(address, scheme))
+        .collect::<RecordAPIResult<Vec<EntryHash>>>()?
+        .into_iter()
+        .find(|identity| matches!(read_identity_state(identity), Ok(IdentityState::Exists)));
+
+    match (read_identity_state(&current_identity)?, legacy_identity) {
+        // already migrated, or nothing to migrate from
+        (IdentityState::Aliased(aliased), _) => Ok(aliased),
+        (IdentityState::Exists, _) | (IdentityState::Missing, None) => {
+            link_id_tag(address, current_identity.to_owned())?;
+            Ok(current_identity)
+        },
+        (IdentityState::Missing, Some(legacy)) => {
+            link_if_not_linked(current_identity.to_owned(), legacy.to_owned(), LinkTypes::IdentityAlias, identity_alias_tag(address.as_ref(), address.as_ref()))?;
+            Ok(legacy)
+        },
+    }
+}
+
+/// Rewrite any legacy (unversioned) `SemanticIndex` links between `address` and its
+/// related records into versioned form, in both directions of the index.
+///
+/// Returns the number of links rewritten.
 ///
-/// The returned `RemoteEntryLinkResponse` provides an appropriate format for responding to indexing
-/// requests that originate from calls to `create/update/delete_remote_index` in a foreign DNA.
+/// This is an administrative operation and should not be exposed to remote callers.
 ///
-pub fn sync_index<A, B, S, I>(
-    source: &A,
-    dest_addresses: &[B],
-    removed_addresses: &[B],
+pub fn migrate_index_links<A, S>(
+    address: &A,
     link_tag: &S,
     link_tag_reciprocal: &S,
-    order_by_time_index: &I,
-) -> OtherCellResult<RemoteEntryLinkResponse>
-    where S: AsRef<[u8]> + ?Sized + std::fmt::Debug,
-        I: AsRef<str> + std::fmt::Display + std::fmt::Debug,
+) -> RecordAPIResult<u32>
+    where S: AsRef<[u8]> + ?Sized,
         A: DnaAddressable<EntryHash>,
-        B: DnaAddressable<EntryHash>,
 {
-    // create any new indexes
-    let indexes_created = create_remote_index_destination(
-        source, dest_addresses, link_tag, link_tag_reciprocal,
-    ).map_err(CrossCellError::from)?.iter()
-        .map(convert_errors)
-        .collect();
+    let identity = resolve_identity_address(address)?;
+    let mut migrated = migrate_legacy_links(&identity, None, link_tag)?;
 
-    // :SHONK: add remote source address to its own time series for retrieval.
-    // This should be updated to be determined and passed with the request, so that the
-    // query time is based on (externally determined) record creation time, rather
-    // then "indexed" time, which isn't really useful as it doesn't even correlate with
-    // record updates. (Indexes only change if the indexed field is updated.)
-    let timestamp: DateTime<Utc> = sys_time()?.try_into()
-        .map_err(|e: TimestampError| SemanticIndexError::BadTimeIndexError(e.to_string()))?;
-    let time_index_created = append_to_time_index(order_by_time_index, source, timestamp);
-    // :TODO: handle errors
-    debug!("created {:?} time indexes in {:?} index zome for remote {:?} index target {:?}", order_by_time_index, zome_info()?.name, link_tag, time_index_created);
-
-    // remove passed stale indexes
-    let indexes_removed = remove_remote_index_links(
-        source, removed_addresses, link_tag, link_tag_reciprocal,
-    ).map_err(CrossCellError::from)?.iter()
-        .map(convert_errors)
-        .collect();
+    for related in get_linked_addresses(&identity, link_tag)? {
+        migrated += migrate_legacy_links(&related, Some(&identity), link_tag_reciprocal)?;
+    }
 
-    Ok(RemoteEntryLinkResponse { indexes_created, indexes_removed })
+    Ok(migrated)
 }
 
-/// Indexes an entry pointer (which may reference the local DNA, or a remote one)
-/// into the time-ordered index `index_name` at the given `timestamp` for subsequent
-/// ordered retrieval.
+/// Replace legacy links from `base_address` exactly matching `link_tag` (and optionally
+/// only those pointing to `target_address`) with versioned ones.
 ///
-/// Multiple indexes may be created per entry, where multiple orderings are appropriate.
-///
-/// Returns `true` if the index was created, `false` if the entry was already indexed and thus skipped.
+/// Tombstone tags never match exactly, so removed links are not revived as live ones.
 ///
-pub fn append_to_time_index<'a, A, I>(
-    index_name: &I,
-    entry_address: &A,
-    timestamp: DateTime<Utc>,
-) -> RecordAPIResult<bool>
-    where A: DnaAddressable<EntryHash>,
-        I: AsRef<str> + std::fmt::Display,
+fn migrate_legacy_links<S>(
+    base_address: &EntryHash,
+    target_address: Option<&EntryHash>,
+    link_tag: &S,
+) -> RecordAPIResult<u32>
+    where S: AsRef<[u8]> + ?Sized,
 {
-    // determine hash for index pointer
-    let entry_hash: &EntryHash = entry_address.as_ref();
-
-    // store fully-qualified target identifier in a loopback link
-    ensure_id_tag(entry_address)?;
-
-    // populate a date-based index for the entry
-    let result = index_entry(index_name, entry_hash.to_owned(), timestamp);
+    // legacy tags are matched by prefix, so filter out any other indexes sharing the prefix
+    let legacy_links: Vec<Link> = get_links(base_address.to_owned(), LinkTypes::SemanticIndex, Some(legacy_index_link_tag(link_tag)))?
+        .into_iter()
+        .filter(|l| l.tag.0.as_slice() == link_tag.as_ref())
+        .collect();
 
-    match result {
-        Ok(_) => Ok(true),
-        Err(TimeIndexingError::AlreadyIndexed(_, _)) => Ok(false),
-        Err(e) => Err(SemanticIndexError::BadTimeIndexError(e.to_string()).into())
+    let mut migrated = 0;
+    for link in legacy_links {
+        let target = match link.target.to_owned().into_entry_hash() {
+            Some(target) => target,
+            None => continue,
+        };
+        if target_address.map_or(false, |t| *t != target) {
+            continue;
+        }
+        // write the replacement before removing the legacy link, so that reads never miss the target
+        create_link(base_address.to_owned(), target, LinkTypes::SemanticIndex, index_link_tag(link_tag)?)?;
+        delete_link(link.create_link_hash)?;
+        migrated += 1;
     }
+
+    Ok(migrated)
 }
 
-/// Creates a 'destination' query index used for following a link from some external record
-/// into records contained within the current DNA / zome.
+/// Remove redundant `SemanticIndex` links between `address` and its related records, in
+/// both directions of the index. Links are redundant where they share a base, target and tag
+/// with another link; the earliest of each such group is retained.
 ///
-/// This basically consists of an identity `Path` for the remote content and bidirectional
-/// links between it and its `dest_addresses`.
+/// Returns the number of links removed.
 ///
-fn create_remote_index_destination<A, B, S>(
-    source: &A,
-    dest_addresses: &[B],
+/// Indexes written prior to `link_index_if_not_linked` guarding against duplicate writes may
+/// contain such links. This is an administrative operation and should not be exposed to remote callers.
+///
+pub fn dedup_index<A, S>(
+    address: &A,
     link_tag: &S,
     link_tag_reciprocal: &S,
-) -> RecordAPIResult<Vec<RecordAPIResult<ActionHash>>>
+) -> RecordAPIResult<u32>
     where S: AsRef<[u8]> + ?Sized,
         A: DnaAddressable<EntryHash>,
-        B: DnaAddressable<EntryHash>,
 {
-    // ensure there is a fully-qualified identifier stored for the remote source record
-    ensure_id_tag(source)?;
+    let identity = resolve_identity_address(address)?;
+    let mut removed = dedup_links(&identity, None, link_tag)?;
 
-    // link all referenced records to this pointer to the remote origin record
-    Ok(dest_addresses.iter()
-        .flat_map(create_dest_identities_and_indexes(source, link_tag, link_tag_reciprocal))
-        .collect()
-    )
+    for related in get_linked_addresses(&identity, link_tag)? {
+        removed += dedup_links(&related, Some(&identity), link_tag_reciprocal)?;
+    }
+
+    Ok(removed)
 }
 
-fn create_dest_identities_and_indexes<'a, A, B, S>(
-    source: &'a A,
-    link_tag: &'a S,
-    link_tag_reciprocal: &'a S,
-) -> Box<dyn for<'r> Fn(&B) -> Vec<RecordAPIResult<ActionHash>> + 'a>
-    where S: 'a + AsRef<[u8]> + ?Sized,
-        A: DnaAddressable<EntryHash>,
-        B: 'a + DnaAddressable<EntryHash>,
+/// Delete redundant links from `base_address` for `link_tag` (and optionally only those
+/// pointing to `target_address`), in both versioned and legacy form.
+///
+fn dedup_links<S>(
+    base_address: &EntryHash,
+    target_address: Option<&EntryHash>,
+    link_tag: &S,
+) -> RecordAPIResult<u32>
+    where S: AsRef<[u8]> + ?Sized,
 {
-    let base_method = create_dest_indexes(source, link_tag, link_tag_reciprocal);
+    let links = get_index_links(base_address, link_tag)?
+        .into_iter()
+        .filter_map(|l| {
+            let target = l.target.into_entry_hash()?;
+            match target_address {
+                Some(t) if *t != target => None,
+                _ => Some((target, l.tag, l.timestamp, l.create_link_hash)),
+            }
+        })
+        .collect();
 
-    Box::new(move |dest| {
-        match ensure_id_tag(dest) {
-            Ok(_hash) => {
-                base_method(dest)
-            },
-            Err(e) => vec![Err(e)],
+    let mut removed = 0;
+    for link_hash in select_duplicate_links(links) {
+        delete_link(link_hash)?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+/// Determine which of a set of `(target, tag, timestamp, link hash)` link descriptors are
+/// redundant, returning the hashes of every link except the earliest for each target & tag.
+///
+fn select_duplicate_links(mut links: Vec<(EntryHash, LinkTag, Timestamp, ActionHash)>) -> Vec<ActionHash> {
+    links.sort_by(|a, b| a.2.cmp(&b.2).then_with(|| a.3.cmp(&b.3)));
+
+    let mut seen: Vec<(EntryHash, LinkTag)> = vec![];
+    let mut duplicates = vec![];
+    for (target, tag, _timestamp, link_hash) in links {
+        let key = (target, tag);
+        if seen.contains(&key) {
+            duplicates.push(link_hash);
+        } else {
+            seen.push(key);
         }
-    })
+    }
+    duplicates
 }
 
-/// Helper for index update to add multiple destination links from some source.
-fn create_dest_indexes<'a, A, B, S>(
-    source: &'a A,
-    link_tag: &'a S,
-    link_tag_reciprocal: &'a S,
-) -> Box<dyn for<'r> Fn(&B) -> Vec<RecordAPIResult<ActionHash>> + 'a>
-    where S: 'a + AsRef<[u8]> + ?Sized,
-        A: DnaAddressable<EntryHash>,
-        B: DnaAddressable<EntryHash>,
+// :DUPE: link_if_not_linked
+fn link_if_not_linked(
+    origin_hash: EntryHash,
+    dest_hash: EntryHash,
+    link_type: LinkTypes,
+    link_tag: LinkTag,
+) -> RecordAPIResult<Option<ActionHash>> {
+    if false == get_links(origin_hash.to_owned(), link_type, Some(link_tag.to_owned()))?
+        .iter().any(|l| { l.target.to_owned().into_entry_hash().unwrap() == dest_hash })
+    {
+        Ok(Some(create_link(
+            origin_hash.to_owned(),
+            dest_hash.to_owned(),
+            link_type,
+            link_tag,
+        )?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Given an identity `EntryHash` (ie. the result of `resolve_identity_address`),
+/// query the `DnaHash` and `AnyDhtHash` of the record.
+///
+fn read_remote_entry_identity<A>(
+    identity_address: &EntryHash,
+) -> RecordAPIResult<A>
+    where A: DnaAddressable<EntryHash>,
+        SerializedBytes: TryInto<A, Error = SerializedBytesError>,
 {
-    Box::new(move |dest| {
-        // write bidirectional links between `source` and `dest` entry hashes
-        match create_index(source, dest, link_tag, link_tag_reciprocal) {
-            // links OK. iterate over newly created index links
-            Ok(created) => created.iter().cloned()
-                .filter(|r| (r.is_ok() && r.as_ref().unwrap().is_some()) || r.is_err())
-                .map(|r| if r.is_err() {
-                    // propagate any link creation errors which may have failed integrity zome validation or otherwise errored
-                    Err(r.err().unwrap())
-                } else {
-                    // return all `ActionHash`es of newly created links
-                    Ok(r.unwrap().unwrap())
-                })
-                .collect(),
-            // index creation failed. Return an error to the caller with the destination link target as metadata.
-            Err(_) => {
-                let h: &EntryHash = dest.as_ref();
-                vec![Err(SemanticIndexError::IndexNotFound(h.clone()).into())]
-            },
-        }
+    get_links(
+        identity_address.to_owned(),
+        LinkTypes::EntryUUID,
+        Some(LinkTag::new(crate::RECORD_IDENTITY_LINK_TAG))
+    )?
+    .first()
+    .map(|link| {
+        let (dna, hash) = decode_identity_link_tag(&link.tag, &dna_info()?.hash)
+            .map_err(|e| SemanticIndexError::CorruptIndexError(identity_address.to_owned(), e))?;
+        Ok(A::new(dna, hash))
     })
+    .ok_or(SemanticIndexError::IndexNotFound((*identity_address).clone()))?
 }
 
-/// Creates a bidirectional link between two entry addresses, and returns a vector
-/// of the `ActionHash`es of the (respectively) forward & reciprocal links created.
-fn create_index<A, B, S>(
-    source: &A,
-    dest: &B,
+/// Load any set of linked `EntryHash`es being referenced from the
+/// provided `base_address` with the given `link_tag`.
+///
+/// Both versioned and legacy links are read, so targets are deduplicated in case
+/// a link exists in both forms during migration.
+///
+pub fn get_linked_addresses<S>(
+    base_address: &EntryHash,
     link_tag: &S,
-    link_tag_reciprocal: &S,
-) -> RecordAPIResult<Vec<RecordAPIResult<Option<ActionHash>>>>
+) -> RecordAPIResult<Vec<EntryHash>>
     where S: AsRef<[u8]> + ?Sized,
-        A: DnaAddressable<EntryHash>,
-        B: DnaAddressable<EntryHash>,
 {
-    let source_hash = calculate_identity_address(source)?;
-    let dest_hash = calculate_identity_address(dest)?;
-
-    Ok(vec! [
-        Ok(link_if_not_linked(source_hash.clone(), dest_hash.clone(), LinkTypes::SemanticIndex, LinkTag::new(link_tag.as_ref()))?),
-        Ok(link_if_not_linked(dest_hash, source_hash, LinkTypes::SemanticIndex, LinkTag::new(link_tag_reciprocal.as_ref()))?),
-    ])
+    let mut addresses: Vec<EntryHash> = Vec::new();
+    for address in get_index_links(base_address, link_tag)?.iter().filter_map(|l| l.target.to_owned().into_entry_hash()) {
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
+    }
+    Ok(addresses)
 }
 
-//-------------------------------[ DELETE ]-------------------------------------
+/// Build the `LinkTag` for a newly written `SemanticIndex` link, prefixed with
+/// the current `SEMANTIC_INDEX_TAG_VERSION`.
+///
+/// All `SemanticIndex` tags should be constructed via this method rather than by `LinkTag::new`.
+///
+pub fn index_link_tag<S>(link_tag: &S) -> Result<LinkTag, SemanticIndexError>
+    where S: AsRef<[u8]> + ?Sized,
+{
+    let name = encode_index_tag_name(link_tag.as_ref(), MAX_INDEX_TAG_NAME_BYTES)?;
+    Ok(LinkTag::new([&[SEMANTIC_INDEX_TAG_VERSION], name.as_slice()].concat()))
+}
 
-/// Deletes a set of links between a remote record reference and some set
-/// of local target EntryHashes.
+/// Canonically encode a relationship name for use in a `SemanticIndex` link tag, so that
+/// equivalent names always produce identical tags.
 ///
-/// The `Path` representing the remote target is not
-/// affected in the removal, and is simply left dangling in the
-/// DHT space as an indicator of previously linked items.
+/// UTF-8 names are NFC-normalized. Names longer than `max_len` bytes once encoded are rejected,
+/// since truncating them would allow distinct names to share an index.
 ///
-fn remove_remote_index_links<A, B, S>(
-    source: &A,
-    remove_addresses: &[B],
-    link_tag: &S,
-    link_tag_reciprocal: &S,
-) -> RecordAPIResult<Vec<RecordAPIResult<ActionHash>>>
+pub fn encode_index_tag_name(name: &[u8], max_len: usize) -> Result<Vec<u8>, SemanticIndexError> {
+    let encoded = match std::str::from_utf8(name) {
+        Ok(name) => name.nfc().collect::<String>().into_bytes(),
+        Err(_) => name.to_vec(),
+    };
+    if encoded.len() > max_len {
+        return Err(SemanticIndexError::IndexNameTooLong(encoded.len(), max_len));
+    }
+    Ok(encoded)
+}
+
+/// Build the `LinkTag` which `SemanticIndex` links were written with prior to tag versioning.
+/// The name is used verbatim, as it was when legacy links were written.
+///
+fn legacy_index_link_tag<S>(link_tag: &S) -> LinkTag
     where S: AsRef<[u8]> + ?Sized,
-        A: DnaAddressable<EntryHash>,
-        B: DnaAddressable<EntryHash>,
 {
-    Ok(remove_addresses.iter()
-        .flat_map(delete_dest_indexes(
-            source, link_tag, link_tag_reciprocal,
-        ))
-        .collect()
-    )
+    LinkTag::new(link_tag.as_ref())
 }
 
-/// Helper for index update to remove multiple destination links from some source.
-fn delete_dest_indexes<'a, A, B, S>(
-    source: &'a A,
-    link_tag: &'a S,
-    link_tag_reciprocal: &'a S,
-) -> Box<dyn for<'r> Fn(&B) -> Vec<RecordAPIResult<ActionHash>> + 'a>
-    where S: 'a + AsRef<[u8]> + ?Sized,
-        A: DnaAddressable<EntryHash>,
-        B: DnaAddressable<EntryHash>,
+/// Decoded `SemanticIndex` link tag, for inspecting index link metadata.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexLinkTag {
+    /// Schema version of the link, or `None` for legacy links written prior to tag versioning.
+    pub version: Option<u8>,
+    pub name: Vec<u8>,
+}
+
+/// Decode a `SemanticIndex` link tag into its schema version and semantic name.
+///
+/// Index names are always printable, so a leading byte in the ASCII control range
+/// can only be a version prefix.
+///
+pub fn parse_index_link_tag(tag: &LinkTag) -> IndexLinkTag {
+    let bytes = tag.0.as_slice();
+    match bytes.first() {
+        Some(version) if *version < 0x20 => IndexLinkTag { version: Some(*version), name: bytes[1..].to_vec() },
+        _ => IndexLinkTag { version: None, name: bytes.to_vec() },
+    }
+}
+
+/// Build the `LinkTag` for a tombstone replacing a `SemanticIndex` link removed at `removed_at`.
+///
+/// The tag of the removed link precedes the time of removal, so that the tombstones of an index
+/// can be read by tag prefix via `tombstone_link_tag_prefix`.
+///
+pub fn tombstone_link_tag<S>(link_tag: &S, removed_at: Timestamp) -> Result<LinkTag, SemanticIndexError>
+    where S: AsRef<[u8]> + ?Sized,
 {
-    Box::new(move |dest_addr| {
-        match delete_index(source, dest_addr, link_tag, link_tag_reciprocal) {
-            Ok(deleted) => deleted,
-            Err(_) => {
-                let dest_hash: &EntryHash = dest_addr.as_ref();
-                vec![Err(SemanticIndexError::IndexNotFound(dest_hash.clone()).into())]
-            },
-        }
-    })
+    Ok(LinkTag::new([
+        tombstone_link_tag_prefix(link_tag)?.0.as_slice(),
+        &removed_at.as_micros().to_be_bytes(),
+    ].concat()))
 }
 
-/// Deletes a bidirectional link between two entry addresses. Any active links between
-/// the given addresses using the given tags will be deleted.
+/// Build the `LinkTag` prefix shared by all tombstones replacing `SemanticIndex` links for `link_tag`.
 ///
-fn delete_index<'a, A, B, S>(
-    source: &A,
-    dest: &B,
-    link_tag: &S,
-    link_tag_reciprocal: &S,
-) -> RecordAPIResult<Vec<RecordAPIResult<ActionHash>>>
-    where S: 'a + AsRef<[u8]> + ?Sized,
-        A: DnaAddressable<EntryHash>,
-        B: DnaAddressable<EntryHash>,
+fn tombstone_link_tag_prefix<S>(link_tag: &S) -> Result<LinkTag, SemanticIndexError>
+    where S: AsRef<[u8]> + ?Sized,
 {
-    let tag_source = LinkTag::new(link_tag.as_ref());
-    let tag_dest = LinkTag::new(link_tag_reciprocal.as_ref());
-    let address_source = calculate_identity_address(source)?;
-    let address_dest = calculate_identity_address(dest)?;
+    Ok(LinkTag::new([
+        RECORD_TOMBSTONE_LINK_TAG_PREFIX,
+        index_link_tag(link_tag)?.0.as_slice(),
+    ].concat()))
+}
 
-    let mut links = walk_links_matching_entry(
-        &address_source,
-        &address_dest,
-        tag_source,
-        delete_link_target_action,
-    )?;
-    links.append(& mut walk_links_matching_entry(
-        &address_dest,
-        &address_source,
-        tag_dest,
-        delete_link_target_action,
-    )?);
+/// Determine whether `tag` belongs to a tombstone link rather than a live index link.
+///
+pub fn is_tombstone_link_tag(tag: &LinkTag) -> bool {
+    tag.0.starts_with(RECORD_TOMBSTONE_LINK_TAG_PREFIX)
+}
 
-    Ok(links)
+/// Decode a tombstone link tag into the time of removal and the tag of the removed link.
+/// Returns `None` for tags which are not tombstones.
+///
+pub fn parse_tombstone_link_tag(tag: &LinkTag) -> Option<(Timestamp, IndexLinkTag)> {
+    let rest = tag.0.strip_prefix(RECORD_TOMBSTONE_LINK_TAG_PREFIX)?;
+    if rest.len() <= 8 {
+        return None;
+    }
+    let (removed_tag, time_bytes) = rest.split_at(rest.len() - 8);
+    let mut micros = [0u8; 8];
+    micros.copy_from_slice(time_bytes);
+    Some((
+        Timestamp::from_micros(i64::from_be_bytes(micros)),
+        parse_index_link_tag(&LinkTag::new(removed_tag)),
+    ))
+}
+
+/// Build the `LinkTag` for a deprecated link replacing a `SemanticIndex` link.
+///
+pub fn deprecated_link_tag<S>(link_tag: &S) -> Result<LinkTag, SemanticIndexError>
+    where S: AsRef<[u8]> + ?Sized,
+{
+    Ok(LinkTag::new([
+        RECORD_DEPRECATED_LINK_TAG_PREFIX,
+        index_link_tag(link_tag)?.0.as_slice(),
+    ].concat()))
 }
 
-//--------------------------[ UTILITIES  / INTERNALS ]---------------------
-
-/// Generate a link tag for the identity anchor of a record by encoding the ID string into the tag
-/// so that it can be retreived by querying the DHT later.
+/// Determine whether `tag` belongs to a deprecated link rather than a live index link.
 ///
-fn ensure_id_tag<A>(ident: &A) -> RecordAPIResult<Option<ActionHash>>
-    where A: DnaAddressable<EntryHash>,
-{
-    let dna: &DnaHash = ident.as_ref();
-    let hash: &EntryHash = ident.as_ref();
-    let id_tag = LinkTag::new([crate::RECORD_IDENTITY_LINK_TAG, dna.as_ref(), hash.as_ref()].concat());
+pub fn is_deprecated_link_tag(tag: &LinkTag) -> bool {
+    tag.0.starts_with(RECORD_DEPRECATED_LINK_TAG_PREFIX)
+}
 
-    link_if_not_linked(hash.to_owned(), hash.to_owned(), LinkTypes::EntryUUID, id_tag)
+/// Decode a deprecated link tag into the tag of the link it replaced.
+/// Returns `None` for tags which are not deprecated links.
+///
+pub fn parse_deprecated_link_tag(tag: &LinkTag) -> Option<IndexLinkTag> {
+    let rest = tag.0.strip_prefix(RECORD_DEPRECATED_LINK_TAG_PREFIX)?;
+    if rest.is_empty() {
+        return None;
+    }
+    Some(parse_index_link_tag(&LinkTag::new(rest)))
 }
 
-// :DUPE: link_if_not_linked
-fn link_if_not_linked(
-    origin_hash: EntryHash,
-    dest_hash: EntryHash,
-    link_type: LinkTypes,
-    link_tag: LinkTag,
-) -> RecordAPIResult<Option<ActionHash>> {
-    if false == get_links(origin_hash.to_owned(), link_type, Some(link_tag.to_owned()))?
-        .iter().any(|l| { l.target.to_owned().into_entry_hash().unwrap() == dest_hash })
+/// Read the targets of deprecated links replacing `SemanticIndex` links from `base_address`
+/// for the given `link_tag`.
+///
+fn get_deprecated_links<S>(
+    base_address: &EntryHash,
+    link_tag: &S,
+) -> RecordAPIResult<Vec<EntryHash>>
+    where S: AsRef<[u8]> + ?Sized,
+{
+    let mut addresses: Vec<EntryHash> = Vec::new();
+    for address in get_links(base_address.to_owned(), LinkTypes::SemanticIndex, Some(deprecated_link_tag(link_tag)?))?
+        .into_iter()
+        .filter_map(|l| l.target.into_entry_hash())
     {
-        Ok(Some(create_link(
-            origin_hash.to_owned(),
-            dest_hash.to_owned(),
-            link_type,
-            link_tag,
-        )?))
-    } else {
-        Ok(None)
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
     }
+    Ok(addresses)
 }
 
-/// Given an identity `EntryHash` (ie. the result of `calculate_identity_address`),
-/// query the `DnaHash` and `AnyDhtHash` of the record.
+/// Read the targets of tombstones left by `SemanticIndex` links removed from `base_address`
+/// for the given `link_tag`, along with their removal times, most recently removed first.
 ///
-fn read_remote_entry_identity<A>(
-    identity_address: &EntryHash,
-) -> RecordAPIResult<A>
-    where A: DnaAddressable<EntryHash>,
-        SerializedBytes: TryInto<A, Error = SerializedBytesError>,
+fn get_tombstone_links<S>(
+    base_address: &EntryHash,
+    link_tag: &S,
+) -> RecordAPIResult<Vec<(EntryHash, Timestamp)>>
+    where S: AsRef<[u8]> + ?Sized,
 {
-    get_links(
-        identity_address.to_owned(),
-        LinkTypes::EntryUUID,
-        Some(LinkTag::new(crate::RECORD_IDENTITY_LINK_TAG))
-    )?
-    .first()
-    .map(|link| {
-        let bytes = &link.tag.to_owned().into_inner()[3..];
-        Ok(A::new(
-            DnaHash::from_raw_39(bytes[0..HOLO_HASH_FULL_LEN].to_vec())
-                .map_err(|e| SemanticIndexError::CorruptIndexError(identity_address.to_owned(), e.to_string()))?,
-            EntryHash::from_raw_39(bytes[HOLO_HASH_FULL_LEN..].to_vec())
-                .map_err(|e| SemanticIndexError::CorruptIndexError(identity_address.to_owned(), e.to_string()))?,
-        ))
-    })
-    .ok_or(SemanticIndexError::IndexNotFound((*identity_address).clone()))?
+    let removed = parse_index_link_tag(&index_link_tag(link_tag)?);
+
+    // tags are matched by prefix, so tombstones of longer index names sharing this prefix are also returned
+    let mut tombstones: Vec<(EntryHash, Timestamp)> = get_links(base_address.to_owned(), LinkTypes::SemanticIndex, Some(tombstone_link_tag_prefix(link_tag)?))?
+        .into_iter()
+        .filter_map(|l| {
+            let (removed_at, removed_tag) = parse_tombstone_link_tag(&l.tag)?;
+            if removed_tag != removed {
+                return None;
+            }
+            Some((l.target.into_entry_hash()?, removed_at))
+        })
+        .collect();
+    tombstones.sort_by(|a, b| b.1.cmp(&a.1));
+
+    Ok(tombstones)
 }
 
-/// Load any set of linked `EntryHash`es being referenced from the
-/// provided `base_address` with the given `link_tag`.
+/// Read all `SemanticIndex` links from `base_address` for the given `link_tag`,
+/// in both versioned and legacy form.
 ///
-pub fn get_linked_addresses(
+fn get_index_links<S>(
     base_address: &EntryHash,
-    link_tag: LinkTag,
-) -> RecordAPIResult<Vec<EntryHash>> {
-    Ok(
-        get_links((*base_address).clone(), LinkTypes::SemanticIndex, Some(link_tag))?
-            .iter()
-            .filter_map(|l| l.target.to_owned().into_entry_hash())
-            .collect()
-    )
+    link_tag: &S,
+) -> RecordAPIResult<Vec<Link>>
+    where S: AsRef<[u8]> + ?Sized,
+{
+    let mut links = get_links(base_address.to_owned(), LinkTypes::SemanticIndex, Some(index_link_tag(link_tag)?))?;
+    links.append(&mut get_links(base_address.to_owned(), LinkTypes::SemanticIndex, Some(legacy_index_link_tag(link_tag)))?);
+    // legacy tags are matched by prefix, so never treat a tombstone or deprecated link as a live link
+    links.retain(|l| !is_tombstone_link_tag(&l.tag) && !is_deprecated_link_tag(&l.tag));
+    Ok(links)
+}
+
+/// Determine whether `link` was created by `author`, by loading the `CreateLink` action which wrote it.
+///
+fn is_link_authored_by(link: &Link, author: &AgentPubKey) -> RecordAPIResult<bool> {
+    Ok(get(link.create_link_hash.to_owned(), GetOptions::default())?
+        .map_or(false, |record| record.action().author() == author))
+}
+
+/// Create a versioned `SemanticIndex` link between two identities, unless they are
+/// already linked by a versioned or legacy link.
+///
+fn link_index_if_not_linked<S>(
+    origin_hash: &EntryHash,
+    dest_hash: &EntryHash,
+    link_tag: &S,
+) -> RecordAPIResult<Option<ActionHash>>
+    where S: AsRef<[u8]> + ?Sized,
+{
+    if get_index_links(origin_hash, link_tag)?.iter().any(|l| l.target.to_owned().into_entry_hash().as_ref() == Some(dest_hash)) {
+        return Ok(None);
+    }
+    Ok(Some(create_link(
+        origin_hash.to_owned(),
+        dest_hash.to_owned(),
+        LinkTypes::SemanticIndex,
+        index_link_tag(link_tag)?,
+    )?))
 }
 
 /// Execute the provided `link_map` function against the set of links
@@ -551,15 +3265,16 @@ pub fn get_linked_addresses(
 /// If you have a bidirectional link between two `EntryHash`es, you must
 /// run this method twice (once to remove each direction of the paired link).
 ///
-fn walk_links_matching_entry<T, F>(
+fn walk_links_matching_entry<T, F, S>(
     base_address: &EntryHash,
     target_address: &EntryHash,
-    link_tag: LinkTag,
+    link_tag: &S,
     link_map: F,
 ) -> RecordAPIResult<Vec<T>>
     where F: Fn(&Link) -> T,
+        S: AsRef<[u8]> + ?Sized,
 {
-    let links_result = get_links(base_address.to_owned(), LinkTypes::SemanticIndex, Some(link_tag))?;
+    let links_result = get_index_links(base_address, link_tag)?;
 
     Ok(links_result
         .iter()
@@ -573,6 +3288,40 @@ fn delete_link_target_action(l: &Link) -> RecordAPIResult<ActionHash> {
     Ok(delete_link(l.create_link_hash.to_owned())?)
 }
 
+/// Delete the index link `l` from `base_address`, replacing it with a tombstone link
+/// to the same target if `removed_at` is given.
+///
+fn remove_index_link<S>(
+    l: &Link,
+    base_address: &EntryHash,
+    link_tag: &S,
+    removed_at: Option<Timestamp>,
+) -> RecordAPIResult<ActionHash>
+    where S: AsRef<[u8]> + ?Sized,
+{
+    let deleted = delete_link_target_action(l)?;
+    if let (Some(removed_at), Some(target)) = (removed_at, l.target.to_owned().into_entry_hash()) {
+        create_link(base_address.to_owned(), target, LinkTypes::SemanticIndex, tombstone_link_tag(link_tag, removed_at)?)?;
+    }
+    Ok(deleted)
+}
+
+/// Delete the index link `l` from `base_address`, replacing it with a deprecated link to the same target.
+///
+fn deprecate_index_link<S>(
+    l: &Link,
+    base_address: &EntryHash,
+    link_tag: &S,
+) -> RecordAPIResult<ActionHash>
+    where S: AsRef<[u8]> + ?Sized,
+{
+    let deleted = delete_link_target_action(l)?;
+    if let Some(target) = l.target.to_owned().into_entry_hash() {
+        create_link(base_address.to_owned(), target, LinkTypes::SemanticIndex, deprecated_link_tag(link_tag)?)?;
+    }
+    Ok(deleted)
+}
+
 /// Returns the first error encountered (if any). Best used with the `?` operator.
 fn throw_any_error<T>(mut errors: Vec<RecordAPIResult<T>>) -> RecordAPIResult<()> {
     if errors.len() == 0 {
@@ -591,3 +3340,686 @@ fn convert_errors<E: Clone, F>(r: &Result<ActionHash, E>) -> Result<ActionHash,
         Err(e) => Err(F::from((*e).clone())),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn concurrently_indexed() -> Result<(), TimeIndexingError> {
+        // simulates another call having indexed the entry after it was checked for, but before it was written
+        Err(TimeIndexingError::AlreadyIndexed("test_index".to_string(), EntryHash::from_raw_36(vec![0xdb; 36])))
+    }
+
+    fn mock_address(byte: u8) -> ActionHash {
+        ActionHash::from_raw_36(vec![byte; 36])
+    }
+
+    hdk_uuid_types::addressable_identifier!(TestAddress => EntryHash);
+
+    fn test_address(byte: u8) -> TestAddress {
+        TestAddress(DnaHash::from_raw_36(vec![0xdb; 36]), EntryHash::from_raw_36(vec![byte; 36]))
+    }
+
+    #[test]
+    fn test_merge_linked_addresses_deduplicates() {
+        let hash = |b: u8| EntryHash::from_raw_36(vec![b; 36]);
+        let merged = merge_linked_addresses(vec![vec![hash(1), hash(2)], vec![hash(2), hash(3)], vec![]]);
+        assert_eq!(merged, vec![hash(1), hash(2), hash(3)]);
+    }
+
+    #[test]
+    fn test_unindexed_fallback_order_deterministic() {
+        let hash = |b: u8| EntryHash::from_raw_36(vec![b; 36]);
+        let at = |micros: i64| Timestamp::from_micros(micros);
+        let linked = vec![(hash(3), at(20)), (hash(1), at(30)), (hash(2), at(10))];
+
+        let mut by_hash = linked.clone();
+        sort_unindexed(&mut by_hash, UnindexedOrder::EntryHash);
+        let mut reversed: Vec<_> = linked.iter().rev().cloned().collect();
+        sort_unindexed(&mut reversed, UnindexedOrder::EntryHash);
+        assert_eq!(by_hash.iter().map(|e| e.0.clone()).collect::<Vec<_>>(), vec![hash(1), hash(2), hash(3)]);
+        assert_eq!(by_hash, reversed, "same order regardless of link read order");
+
+        let mut by_link = linked.clone();
+        sort_unindexed(&mut by_link, UnindexedOrder::LinkCreated);
+        assert_eq!(by_link.iter().map(|e| e.0.clone()).collect::<Vec<_>>(), vec![hash(1), hash(3), hash(2)], "most recently linked first");
+
+        assert_eq!(UnindexedOrder::default(), UnindexedOrder::EntryHash);
+    }
+
+    #[test]
+    fn test_identity_link_tag_roundtrip() {
+        let local_dna = DnaHash::from_raw_36(vec![0xdc; 36]);
+        let address = test_address(7);
+
+        let full = encode_identity_link_tag(&address, TagAddressMode::FullAddress);
+        assert_eq!(decode_identity_link_tag(&full, &local_dna), Ok((address.0.clone(), address.1.clone())), "DNA preserved in full addresses");
+
+        let short = encode_identity_link_tag(&address, TagAddressMode::EntryHashOnly);
+        assert!(short.0.len() < full.0.len());
+        assert_eq!(decode_identity_link_tag(&short, &local_dna), Ok((local_dna.clone(), address.1.clone())), "entry hash preserved, DNA assigned locally");
+
+        assert!(decode_identity_link_tag(&LinkTag::new(crate::RECORD_IDENTITY_LINK_TAG), &local_dna).is_err());
+        assert!(decode_identity_link_tag(&LinkTag::new(b"identity_alias".to_vec()), &local_dna).is_err());
+        assert_eq!(TagAddressMode::default(), TagAddressMode::FullAddress);
+    }
+
+    #[test]
+    fn test_remote_records_keep_full_address() {
+        let local_dna = DnaHash::from_raw_36(vec![0xdc; 36]);
+        let remote_dna = DnaHash::from_raw_36(vec![0xdb; 36]);
+        assert_eq!(tag_address_mode_for(TagAddressMode::EntryHashOnly, &local_dna, &local_dna), TagAddressMode::EntryHashOnly);
+        assert_eq!(tag_address_mode_for(TagAddressMode::EntryHashOnly, &remote_dna, &local_dna), TagAddressMode::FullAddress, "remote DNA must be encoded");
+        assert_eq!(tag_address_mode_for(TagAddressMode::FullAddress, &local_dna, &local_dna), TagAddressMode::FullAddress);
+
+        let remote = test_address(7);
+        let tag = encode_identity_link_tag(&remote, tag_address_mode_for(TagAddressMode::EntryHashOnly, &remote.0, &local_dna));
+        assert_eq!(decode_identity_link_tag(&tag, &local_dna), Ok((remote_dna, remote.1.clone())), "remote records decode to their own DNA");
+    }
+
+    #[test]
+    fn test_identity_type_tag() {
+        let tag = encode_identity_type_tag("economic_event");
+        assert_eq!(decode_identity_type_tag(&tag), Some("economic_event".to_string()));
+        assert_eq!(decode_identity_type_tag(&encode_identity_link_tag(&test_address(7), TagAddressMode::FullAddress)), None, "identity tags are not type tags");
+        assert!(!tag.0.starts_with(crate::RECORD_IDENTITY_LINK_TAG), "type tags must not match identity tag reads");
+    }
+
+    #[test]
+    fn test_record_storage_zome_selection() {
+        let zomes = vec![
+            ("economic_event".to_string(), "economic_event".to_string()),
+            ("economic_resource".to_string(), "economic_resource".to_string()),
+        ];
+
+        assert_eq!(select_record_storage_zome(&zomes, Some("economic_event")), Some("economic_event".to_string()));
+        assert_eq!(select_record_storage_zome(&zomes, Some("economic_resource")), Some("economic_resource".to_string()));
+        assert_eq!(select_record_storage_zome(&zomes, Some("process")), None, "unlisted types use the legacy zome");
+        assert_eq!(select_record_storage_zome(&zomes, None), None, "untyped identities use the legacy zome");
+        assert_eq!(select_record_storage_zome(&[], Some("economic_event")), None);
+    }
+
+    #[test]
+    fn test_identity_entry_type_conflicts() {
+        let identity = EntryHash::from_raw_36(vec![7; 36]);
+        assert!(check_identity_entry_type(&identity, None, "economic_event").is_ok(), "untyped records may be typed");
+        assert!(check_identity_entry_type(&identity, Some("economic_event"), "economic_event").is_ok(), "retagging the same type is permitted");
+        assert!(matches!(
+            check_identity_entry_type(&identity, Some("economic_event"), "economic_resource"),
+            Err(SemanticIndexError::ConflictingRecordType(_, existing, requested)) if existing == "economic_event" && requested == "economic_resource"
+        ));
+    }
+
+    #[test]
+    fn test_time_index_record_type() {
+        assert_eq!(time_index_record_type("economic_event.created"), Some("economic_event"));
+        assert_eq!(time_index_record_type("economic_event_input_of.indexed"), None, "remote indexes are namespaced by relationship");
+        assert_eq!(time_index_record_type("unnamespaced"), None);
+        assert_eq!(time_index_record_type(".created"), None);
+    }
+
+    #[test]
+    fn test_mixed_type_time_ordering() {
+        let hash = |b: u8| EntryHash::from_raw_36(vec![b; 36]);
+        let now = Utc::now();
+        // events are found in the queried index, resources only in the equivalent index of their own type
+        let queried_index = vec![(hash(1), now - chrono::Duration::seconds(40)), (hash(3), now - chrono::Duration::seconds(20))];
+        let resource_index = vec![(hash(2), now - chrono::Duration::seconds(30)), (hash(4), now - chrono::Duration::seconds(10))];
+        let entry_type = |address: &EntryHash| if queried_index.iter().any(|(a, _)| a == address) { "economic_event" } else { "economic_resource" };
+
+        let sorted = sort_by_index_time(vec![hash(5), hash(1), hash(2), hash(3), hash(4)], |address| {
+            if let Some((_, t)) = queried_index.iter().find(|(a, _)| a == address) {
+                return Some(*t);
+            }
+            assert_eq!(typed_time_index_name("economic_event.created", entry_type(address)), Some("economic_resource.created".to_string()));
+            resource_index.iter().find(|(a, _)| a == address).map(|(_, t)| *t)
+        });
+
+        assert_eq!(sorted, vec![hash(4), hash(3), hash(2), hash(1), hash(5)], "record types interleave by time, unindexed entries last");
+    }
+
+    #[test]
+    fn test_typed_time_index_name() {
+        assert_eq!(typed_time_index_name("economic_event.created", "economic_resource"), Some("economic_resource.created".to_string()));
+        assert_eq!(typed_time_index_name("economic_event.created", "economic_event"), None, "records of the indexed type are not re-read");
+        assert_eq!(typed_time_index_name("unnamespaced", "economic_event"), None);
+    }
+
+    #[test]
+    fn test_index_schema_mismatch() {
+        let stamp = encode_index_schema_tag(SEMANTIC_INDEX_TAG_VERSION);
+        assert_eq!(parse_index_schema_tag(&stamp), Some(SEMANTIC_INDEX_TAG_VERSION));
+        assert_eq!(parse_index_schema_tag(&encode_identity_link_tag(&test_address(7), TagAddressMode::FullAddress)), None, "identity tags are not schema stamps");
+
+        assert!(check_index_schema_versions(&[]).is_ok(), "unstamped indexes predate versioning");
+        assert!(check_index_schema_versions(&[SEMANTIC_INDEX_TAG_VERSION]).is_ok());
+
+        let old_stamp = encode_index_schema_tag(SEMANTIC_INDEX_TAG_VERSION - 1);
+        let found = parse_index_schema_tag(&old_stamp).unwrap();
+        assert!(matches!(
+            check_index_schema_versions(&[SEMANTIC_INDEX_TAG_VERSION, found]),
+            Err(DataIntegrityError::IndexSchemaMismatch(f, e)) if f == SEMANTIC_INDEX_TAG_VERSION - 1 && e == SEMANTIC_INDEX_TAG_VERSION
+        ), "index stamped by an older version must be rebuilt");
+    }
+
+    #[test]
+    fn test_count_distinct_linked_addresses() {
+        let hash = |b: u8| EntryHash::from_raw_36(vec![b; 36]);
+        assert_eq!(count_distinct_linked_addresses(vec![vec![hash(1), hash(2)], vec![hash(2)], vec![hash(3)]]), 3, "target linked from two sources counted once");
+        assert_eq!(count_distinct_linked_addresses(vec![vec![], vec![]]), 0);
+    }
+
+    #[test]
+    fn test_filtered_range_paginates_over_matches() {
+        let hash = |b: u8| EntryHash::from_raw_36(vec![b; 36]);
+        let addresses: Vec<EntryHash> = (1..=6).map(hash).collect();
+        // even-numbered records match
+        let read_matching = |addr: &EntryHash| {
+            let n = addr.get_raw_36()[0];
+            if n % 2 == 0 { Some(Ok(n)) } else { None }
+        };
+
+        let page = select_matching_page(&addresses, None, 2, read_matching).unwrap();
+        assert_eq!(page.records.into_iter().map(Result::unwrap).collect::<Vec<u8>>(), vec![2, 4]);
+        assert_eq!(page.next_cursor, Some(hash(4)), "cursor at last record examined");
+
+        let page = select_matching_page(&addresses, page.next_cursor.as_ref(), 2, read_matching).unwrap();
+        assert_eq!(page.records.into_iter().map(Result::unwrap).collect::<Vec<u8>>(), vec![6]);
+        assert_eq!(page.next_cursor, None, "range exhausted");
+
+        let page = select_matching_page(&addresses, Some(&hash(5)), 1, read_matching).unwrap();
+        assert_eq!(page.records.len(), 1);
+        assert_eq!(page.next_cursor, None, "no cursor when the final record fills the page");
+
+        assert!(matches!(
+            select_matching_page(&addresses, Some(&hash(9)), 2, read_matching),
+            Err(DataIntegrityError::UnknownCursor(_))
+        ), "unknown cursors rejected");
+    }
+
+    #[test]
+    fn test_reconcile_diff_adds_missing_and_removes_extra() {
+        let current = vec![test_address(1), test_address(2), test_address(3)];
+        let desired = vec![test_address(3), test_address(4), test_address(1), test_address(4)];
+
+        let (added, removed) = diff_index_targets(&current, &desired);
+        assert_eq!(added, vec![test_address(4)], "missing destinations linked once");
+        assert_eq!(removed, vec![test_address(2)], "destinations outside the desired set unlinked");
+
+        let (added, removed) = diff_index_targets(&current, &current);
+        assert!(added.is_empty() && removed.is_empty(), "no changes when already reconciled");
+
+        let (added, removed) = diff_index_targets(&current, &[]);
+        assert!(added.is_empty());
+        assert_eq!(removed, current, "empty desired set clears the index");
+    }
+
+    #[test]
+    fn test_time_index_names_namespaced_by_record_type() {
+        let events = time_index_name("economic_event", "by_date");
+        let processes = time_index_name("process", "by_date");
+        assert_eq!(events, "economic_event.by_date");
+        assert_ne!(events, processes);
+
+        let segment = TimeIndex::leafmost_link(&Utc::now());
+        assert_ne!(segment.tag_for_index(&events), segment.tag_for_index(&processes), "records of each type are linked separately");
+
+        assert!(validate_time_index_name(&events).is_ok());
+        assert!(validate_time_index_name("by_date").is_err(), "unscoped names rejected");
+        assert!(validate_time_index_name(".by_date").is_err());
+        assert!(validate_time_index_name("economic_event.").is_err());
+    }
+
+    static TEST_TIME_INDEXES: TimeIndexRegistry = TimeIndexRegistry {
+        record_type: "economic_resource",
+        names: &[CREATED_TIME_INDEX_NAME, "expiry"],
+        prefixes: &["state."],
+        remote_namespaces: &["economic_resource_affected_by"],
+        unindexed_order: UnindexedOrder::default,
+        tag_address_mode: TagAddressMode::default,
+        record_storage_zomes: Option::default,
+    };
+
+    #[test]
+    fn test_unregistered_time_index_rejected() {
+        register_time_indexes(&TEST_TIME_INDEXES);
+
+        assert!(validate_time_index_name("economic_resource.expiry").is_ok());
+        match validate_time_index_name("economic_resource.expirey") {
+            Err(DataIntegrityError::UnknownTimeIndex { name, known }) => {
+                assert_eq!(name, "economic_resource.expirey");
+                assert_eq!(known, vec![
+                    "economic_resource.created", "economic_resource.expiry",
+                    "economic_resource_affected_by.indexed", "economic_resource.state.*",
+                ], "known indexes listed");
+            },
+            other => panic!("typo'd index name accepted: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_registered_time_index_prefixes() {
+        register_time_indexes(&TEST_TIME_INDEXES);
+
+        assert!(validate_time_index_name("economic_resource.state.finished").is_ok(), "dynamic names matched by prefix");
+        assert!(validate_time_index_name("economic_resource.state.").is_err(), "prefix alone is not an index");
+        assert!(validate_time_index_name("economic_resource.stat.finished").is_err());
+    }
+
+    #[test]
+    fn test_registered_time_index_record_type() {
+        register_time_indexes(&TEST_TIME_INDEXES);
+
+        assert!(validate_time_index_name(&time_index_name("economic_resource", "expiry")).is_ok());
+        assert!(validate_time_index_name("economic_event.expiry").is_err(), "names of other record types rejected");
+        assert!(validate_time_index_name("process.state.finished").is_err(), "prefixes of other record types rejected");
+        assert!(validate_time_index_name("economic_resource_affected_by.indexed").is_ok(), "remote records namespaced by relationship");
+        assert!(validate_time_index_name("economic_resource_contained_in.indexed").is_err());
+    }
+
+    #[test]
+    fn test_page_before() {
+        let hash = |b: u8| EntryHash::from_raw_36(vec![b; 36]);
+        let sorted = vec![hash(1), hash(2), hash(3), hash(4)];
+        assert_eq!(page_before(sorted.clone(), None, 2), vec![hash(1), hash(2)]);
+        assert_eq!(page_before(sorted.clone(), Some(hash(2)), 2), vec![hash(3), hash(4)]);
+        assert_eq!(page_before(sorted.clone(), Some(hash(4)), 2), vec![], "no results after the last cursor");
+        assert_eq!(page_before(sorted, Some(hash(9)), 2), vec![], "unknown cursors yield no results");
+    }
+
+    #[test]
+    fn test_index_change_signal_describes_update() {
+        let response = RemoteEntryLinkResponse {
+            indexes_created: vec![Ok(mock_address(1)), Ok(mock_address(2))],
+            indexes_removed: vec![Ok(mock_address(3)), Ok(mock_address(4)), Err(CrossCellError::Internal("failed".to_string()))],
+        };
+        let signal = build_index_change_signal(
+            &test_address(0), &[test_address(1)], &[test_address(2)],
+            "process_committed_inputs", "commitment_input_of", &response,
+        ).expect("signal should be built for modified index");
+
+        assert_eq!(signal.index_name, "commitment_input_of");
+        assert_eq!(signal.reciprocal_index_name, "process_committed_inputs");
+        assert_eq!(signal.source, test_address(0).to_string());
+        assert_eq!(signal.targets_added, vec![test_address(1).to_string()]);
+        assert_eq!(signal.targets_removed, vec![test_address(2).to_string()]);
+        assert_eq!(signal.links_created, 2);
+        assert_eq!(signal.links_removed, 2, "failed link removals should not be counted");
+    }
+
+    #[test]
+    fn test_unidentified_source_not_linked() {
+        let (created, removed) = run_index_sync_phases(
+            || Err(SemanticIndexError::IndexNotFound(EntryHash::from_raw_36(vec![0xdb; 36])).into()),
+            || -> RecordAPIResult<Vec<RecordAPIResult<ActionHash>>> { panic!("destinations must not be linked to an unidentified source") },
+            || -> RecordAPIResult<()> { panic!("unidentified sources must not be time indexed") },
+            || Ok(vec![Ok(mock_address(3))]),
+        );
+        assert_eq!(created.len(), 1, "identity failure reported");
+        assert!(created[0].is_err());
+        assert_eq!(removed.len(), 1, "removals still processed");
+        assert!(removed[0].is_ok());
+    }
+
+    #[test]
+    fn test_identified_source_linked() {
+        let (created, removed) = run_index_sync_phases(
+            || Ok(()),
+            || Ok(vec![Ok(mock_address(1)), Ok(mock_address(2))]),
+            || Err(SemanticIndexError::BadTimeIndexError("clock".to_string()).into()),
+            || Ok(vec![]),
+        );
+        assert_eq!(created.len(), 3, "destinations linked and time index failure reported");
+        assert!(created[0].is_ok() && created[1].is_ok());
+        assert!(created[2].is_err());
+        assert!(removed.is_empty());
+    }
+
+    #[test]
+    fn test_index_results_continue_past_failures() {
+        let created = collect_index_results(vec![
+            Ok(vec![]),
+            Ok(vec![
+                Ok(mock_address(1)),
+                Err(SemanticIndexError::IndexNotFound(EntryHash::from_raw_36(vec![0xdb; 36])).into()),
+                Ok(mock_address(2)),
+            ]),
+            Err(SemanticIndexError::BadTimeIndexError("clock".to_string()).into()),
+        ]);
+        assert_eq!(created.len(), 4, "a failed phase is reported alongside the results of the others");
+        assert_eq!(created.iter().filter(|r| r.is_ok()).count(), 2, "valid destinations are processed");
+        assert!(created[1].is_err());
+        assert!(created[3].is_err());
+
+        let removed = collect_index_results(vec![Ok(vec![Ok(mock_address(3)), Ok(mock_address(4))])]);
+        assert_eq!(removed.len(), 2, "removals are processed");
+        assert!(removed.iter().all(|r| r.is_ok()));
+    }
+
+    #[test]
+    fn test_index_change_signal_skipped_for_noop_update() {
+        let response = RemoteEntryLinkResponse { indexes_created: vec![], indexes_removed: vec![] };
+        let signal = build_index_change_signal::<TestAddress, TestAddress, _>(
+            &test_address(0), &[], &[], "process_committed_inputs", "commitment_input_of", &response,
+        );
+        assert!(signal.is_none(), "no signal should be sent when no links changed");
+    }
+
+    #[test]
+    fn test_stream_chunks_cover_all_results() {
+        let addresses: Vec<EntryHash> = (0..25).map(|i| EntryHash::from_raw_36(vec![i; 36])).collect();
+        let mut emitted = vec![];
+        let summary = emit_in_chunks(
+            "stream 1", addresses.as_slice(), 10,
+            |addr| if addr.get_raw_36()[0] == 12 { Err(DataIntegrityError::EntryNotFound) } else { Ok(addr.get_raw_36()[0]) },
+            |chunk| { emitted.push(chunk.to_owned()); Ok(()) },
+        );
+
+        assert_eq!(emitted.iter().map(|c| c.sequence).collect::<Vec<u32>>(), vec![0, 1, 2], "chunks emitted in order");
+        assert_eq!(emitted.iter().map(|c| c.results.len() + c.errors.len()).collect::<Vec<usize>>(), vec![10, 10, 5]);
+        let results: Vec<u8> = emitted.iter().flat_map(|c| c.results.to_owned()).collect();
+        assert_eq!(results, (0..25).filter(|i| *i != 12).collect::<Vec<u8>>(), "results emitted in query order");
+        assert_eq!(emitted[1].errors.len(), 1, "unreadable records reported in their chunk");
+        assert!(emitted.iter().all(|c| c.stream_id == "stream 1"));
+
+        assert_eq!(summary.chunks, 3);
+        assert!(summary.chunks_failed.is_empty());
+        assert_eq!(summary.total_results, 24);
+        assert_eq!(summary.total_errors, 1);
+    }
+
+    #[test]
+    fn test_stream_continues_past_failed_chunks() {
+        let addresses: Vec<EntryHash> = (0..5).map(|i| EntryHash::from_raw_36(vec![i; 36])).collect();
+        let mut emitted = vec![];
+        let summary = emit_in_chunks(
+            "stream 2", addresses.as_slice(), 2,
+            |addr| Ok(addr.get_raw_36()[0]),
+            |chunk| match chunk.sequence {
+                1 => Err(wasm_error!(WasmErrorInner::Guest("signal not sent".to_string()))),
+                _ => { emitted.push(chunk.sequence); Ok(()) },
+            },
+        );
+
+        assert_eq!(emitted, vec![0, 2], "later chunks still emitted");
+        assert_eq!(summary.chunks, 3);
+        assert_eq!(summary.chunks_failed, vec![1], "failed chunks reported to the client");
+        assert_eq!(summary.total_results, 5);
+    }
+
+    #[test]
+    fn test_stream_window_continuation() {
+        let addresses: Vec<EntryHash> = (0..5).map(|i| EntryHash::from_raw_36(vec![i; 36])).collect();
+
+        let (window, next_cursor) = select_stream_window(&addresses, None, 2).unwrap();
+        assert_eq!(window, &addresses[0..2]);
+        assert_eq!(next_cursor, Some(addresses[1].to_owned()), "cursor at last record streamed");
+
+        let (window, next_cursor) = select_stream_window(&addresses, next_cursor.as_ref(), 2).unwrap();
+        assert_eq!(window, &addresses[2..4]);
+        let (window, next_cursor) = select_stream_window(&addresses, next_cursor.as_ref(), 2).unwrap();
+        assert_eq!(window, &addresses[4..5]);
+        assert_eq!(next_cursor, None, "no cursor once all records are streamed");
+
+        let (_, next_cursor) = select_stream_window(&addresses, None, 5).unwrap();
+        assert_eq!(next_cursor, None, "no cursor for a window ending exactly at the last record");
+
+        let unknown = EntryHash::from_raw_36(vec![0xff; 36]);
+        assert!(matches!(select_stream_window(&addresses, Some(&unknown), 2), Err(DataIntegrityError::UnknownCursor(_))));
+    }
+
+    #[test]
+    fn test_stream_of_empty_query() {
+        let summary = emit_in_chunks::<u8, _, _>("stream 3", &[], 0, |_| Ok(0), |_| panic!("no chunks expected"));
+        assert_eq!(summary.chunks, 0);
+        assert_eq!(summary.total_results, 0);
+    }
+
+    #[test]
+    fn test_write_rate_under_limit() {
+        let now = Utc::now();
+        let limit = IndexWriteRateLimit { max_writes: 3, window_ms: 60_000 };
+        let writes = vec![now - chrono::Duration::seconds(10), now - chrono::Duration::seconds(20)];
+        assert!(!exceeds_write_rate(writes.iter(), &now, &limit), "writes under the limit should proceed");
+        assert!(!exceeds_write_rate(vec![].iter(), &now, &limit), "first write should proceed");
+    }
+
+    #[test]
+    fn test_write_rate_exceeded() {
+        let now = Utc::now();
+        let limit = IndexWriteRateLimit { max_writes: 2, window_ms: 60_000 };
+        let writes = vec![now - chrono::Duration::seconds(10), now - chrono::Duration::seconds(20)];
+        assert!(exceeds_write_rate(writes.iter(), &now, &limit), "writes at the limit should be throttled");
+    }
+
+    #[test]
+    fn test_write_rate_window_expiry() {
+        let now = Utc::now();
+        let limit = IndexWriteRateLimit { max_writes: 2, window_ms: 60_000 };
+        let writes = vec![now - chrono::Duration::seconds(10), now - chrono::Duration::seconds(90)];
+        assert!(!exceeds_write_rate(writes.iter(), &now, &limit), "writes outside the window should not count");
+    }
+
+    #[test]
+    fn test_write_window_start() {
+        let now = Utc::now();
+        let limit = IndexWriteRateLimit { max_writes: 2, window_ms: 60_000 };
+        assert_eq!(write_window_start(&now, &limit), now - chrono::Duration::seconds(60), "writes before this time are pruned");
+    }
+
+    fn identity(byte: u8) -> EntryHash {
+        EntryHash::from_raw_36(vec![byte; 36])
+    }
+
+    #[test]
+    fn test_identity_fallback_to_legacy_scheme() {
+        // record indexed prior to V2 identities being introduced, so only the legacy identity exists
+        let candidates = vec![(IdentityScheme::V2, identity(2)), (IdentityScheme::V1, identity(1))];
+        let resolved = select_identity_address(candidates, |id| Ok(
+            if *id == identity(1) { IdentityState::Exists } else { IdentityState::Missing }
+        )).unwrap();
+
+        assert_eq!(resolved, identity(1), "reads should fall back to legacy identity");
+    }
+
+    #[test]
+    fn test_identity_prefers_current_scheme() {
+        let candidates = vec![(IdentityScheme::V2, identity(2)), (IdentityScheme::V1, identity(1))];
+        let resolved = select_identity_address(candidates, |_id| Ok(IdentityState::Exists)).unwrap();
+
+        assert_eq!(resolved, identity(2), "current scheme identity should be read first");
+    }
+
+    #[test]
+    fn test_identity_follows_migration_alias() {
+        let candidates = vec![(IdentityScheme::V2, identity(2)), (IdentityScheme::V1, identity(1))];
+        let resolved = select_identity_address(candidates, |id| Ok(
+            if *id == identity(2) { IdentityState::Aliased(identity(1)) } else { panic!("migrated identities should resolve in a single hop") }
+        )).unwrap();
+
+        assert_eq!(resolved, identity(1), "migrated identity should resolve to aliased legacy identity");
+    }
+
+    #[test]
+    fn test_new_identities_use_current_scheme() {
+        let candidates = vec![(IdentityScheme::V2, identity(2)), (IdentityScheme::V1, identity(1))];
+        let resolved = select_identity_address(candidates, |_id| Ok(IdentityState::Missing)).unwrap();
+
+        assert_eq!(resolved, identity(2), "unwritten identities should be created under current scheme");
+    }
+
+    #[test]
+    fn test_time_index_conflicts_ignored_by_default() {
+        let result = handle_time_index_result(concurrently_indexed(), TimeIndexConflictBehavior::default());
+        assert!(matches!(result, Ok(false)), "conflicting write should be treated as success");
+    }
+
+    #[test]
+    fn test_time_index_conflicts_propagated_if_requested() {
+        let result = handle_time_index_result(concurrently_indexed(), TimeIndexConflictBehavior::Fail);
+        assert!(matches!(result, Err(DataIntegrityError::SemanticIndexingError(SemanticIndexError::BadTimeIndexError(_)))), "conflicting write should error");
+    }
+
+    #[test]
+    fn test_time_index_other_errors_propagated() {
+        let result = handle_time_index_result(Err(TimeIndexingError::Malformed(vec![])), TimeIndexConflictBehavior::Ignore);
+        assert!(result.is_err(), "unrelated errors should not be swallowed");
+
+        let result = handle_time_index_result(Ok(()), TimeIndexConflictBehavior::Fail);
+        assert!(matches!(result, Ok(true)), "successful writes unaffected by conflict behaviour");
+    }
+
+    #[test]
+    fn test_index_link_tag_versioned() {
+        let tag = index_link_tag("commitment_fulfilled_by").unwrap();
+        assert_eq!(tag.0[0], SEMANTIC_INDEX_TAG_VERSION);
+        assert_eq!(parse_index_link_tag(&tag), IndexLinkTag {
+            version: Some(SEMANTIC_INDEX_TAG_VERSION),
+            name: b"commitment_fulfilled_by".to_vec(),
+        });
+    }
+
+    #[test]
+    fn test_index_link_tag_normalized() {
+        // "café" with precomposed and combining acute accents
+        let composed = "caf\u{e9}_index";
+        let decomposed = "cafe\u{301}_index";
+        assert_ne!(composed.as_bytes(), decomposed.as_bytes());
+        assert_eq!(index_link_tag(composed).unwrap(), index_link_tag(decomposed).unwrap(), "equivalent names produce identical tags");
+        assert_eq!(parse_index_link_tag(&index_link_tag(decomposed).unwrap()).name, composed.as_bytes().to_vec());
+    }
+
+    #[test]
+    fn test_index_tag_name_capped() {
+        assert!(matches!(encode_index_tag_name(b"commitment_fulfilled_by", 10), Err(SemanticIndexError::IndexNameTooLong(23, 10))));
+        assert_eq!(encode_index_tag_name("ab\u{e9}".as_bytes(), 4).unwrap(), "ab\u{e9}".as_bytes().to_vec(), "length is measured in bytes");
+        assert!(encode_index_tag_name("ab\u{e9}".as_bytes(), 3).is_err());
+        assert!(encode_index_tag_name(&[0xff, 0xfe, 0xfd], 2).is_err(), "non-UTF-8 names are also capped");
+
+        let max_name = "x".repeat(MAX_INDEX_TAG_NAME_BYTES);
+        assert_eq!(parse_index_link_tag(&index_link_tag(&max_name).unwrap()).name.len(), MAX_INDEX_TAG_NAME_BYTES);
+        let long_name = "x".repeat(MAX_INDEX_TAG_NAME_BYTES + 1);
+        assert!(index_link_tag(&long_name).is_err(), "names are rejected rather than truncated");
+        assert!(tombstone_link_tag(&long_name, Timestamp::from_micros(0)).is_err());
+        assert!(deprecated_link_tag(&long_name).is_err());
+    }
+
+    #[test]
+    fn test_legacy_index_link_tag_parsed() {
+        let tag = legacy_index_link_tag("commitment_fulfilled_by");
+        assert_eq!(parse_index_link_tag(&tag), IndexLinkTag {
+            version: None,
+            name: b"commitment_fulfilled_by".to_vec(),
+        });
+    }
+
+    #[test]
+    fn test_tombstone_link_tag_parsed() {
+        let removed_at = Timestamp::from_micros(1_700_000_000_123_456);
+        let tag = tombstone_link_tag("agreement_commitments", removed_at).unwrap();
+        assert!(is_tombstone_link_tag(&tag));
+        assert_eq!(parse_tombstone_link_tag(&tag), Some((removed_at, IndexLinkTag {
+            version: Some(SEMANTIC_INDEX_TAG_VERSION),
+            name: b"agreement_commitments".to_vec(),
+        })));
+        assert!(tag.0.starts_with(&tombstone_link_tag_prefix("agreement_commitments").unwrap().0), "index name precedes the time of removal");
+        assert_eq!(parse_tombstone_link_tag(&LinkTag::new(RECORD_TOMBSTONE_LINK_TAG_PREFIX)), None, "truncated tags rejected");
+    }
+
+    #[test]
+    fn test_deprecated_link_tag_parsed() {
+        let tag = deprecated_link_tag("agreement_commitments").unwrap();
+        assert!(is_deprecated_link_tag(&tag));
+        assert!(!is_tombstone_link_tag(&tag));
+        assert_eq!(parse_deprecated_link_tag(&tag), Some(IndexLinkTag {
+            version: Some(SEMANTIC_INDEX_TAG_VERSION),
+            name: b"agreement_commitments".to_vec(),
+        }));
+        assert_eq!(parse_deprecated_link_tag(&LinkTag::new(RECORD_DEPRECATED_LINK_TAG_PREFIX)), None, "truncated tags rejected");
+    }
+
+    #[test]
+    fn test_deprecated_links_excluded_from_live_reads() {
+        let tag = deprecated_link_tag("agreement_commitments").unwrap();
+        assert!(!tag.0.starts_with(&index_link_tag("agreement_commitments").unwrap().0), "deprecated links never match versioned reads");
+        assert!(!tag.0.starts_with(&legacy_index_link_tag("agreement_commitments").0), "deprecated links never match legacy reads");
+        assert!(!is_deprecated_link_tag(&index_link_tag("agreement_commitments").unwrap()));
+        assert_eq!(parse_deprecated_link_tag(&index_link_tag("agreement_commitments").unwrap()), None);
+    }
+
+    #[test]
+    fn test_tombstones_distinct_from_live_tags() {
+        let tag = tombstone_link_tag("agreement_commitments", Timestamp::from_micros(0)).unwrap();
+        assert!(!tag.0.starts_with(&index_link_tag("agreement_commitments").unwrap().0), "not matched by versioned reads");
+        assert!(!tag.0.starts_with(&legacy_index_link_tag("agreement_commitments").0), "not matched by legacy reads");
+        assert!(!is_tombstone_link_tag(&index_link_tag("agreement_commitments").unwrap()));
+        assert_eq!(parse_tombstone_link_tag(&index_link_tag("agreement_commitments").unwrap()), None);
+        assert_eq!(parse_tombstone_link_tag(&LinkTag::new(RECORD_TOMBSTONE_LINK_TAG_PREFIX)), None, "truncated tombstones rejected");
+    }
+
+    #[test]
+    fn test_duplicate_links_collapsed() {
+        let hash = |b: u8| EntryHash::from_raw_36(vec![b; 36]);
+        let link = |b: u8| ActionHash::from_raw_36(vec![b; 36]);
+        let at = Timestamp::from_micros;
+        let tag = index_link_tag("commitment_fulfilled_by").unwrap();
+        let other_tag = index_link_tag("commitment_satisfied_by").unwrap();
+
+        let duplicates = select_duplicate_links(vec![
+            (hash(1), tag.to_owned(), at(30), link(3)),
+            (hash(1), tag.to_owned(), at(10), link(1)),
+            (hash(1), tag.to_owned(), at(20), link(2)),
+            (hash(2), tag.to_owned(), at(40), link(4)),
+            (hash(1), other_tag, at(50), link(5)),
+        ]);
+        assert_eq!(duplicates, vec![link(2), link(3)], "all but the earliest identical link removed");
+        assert!(select_duplicate_links(vec![(hash(1), tag, at(10), link(1))]).is_empty(), "distinct links untouched");
+    }
+
+    #[test]
+    fn test_warm_index_types_batched() {
+        let requested: Vec<String> = ["economic_event", " economic_event ", "", "process"].iter()
+            .map(|t| t.to_string())
+            .chain((0..MAX_WARM_INDEX_TYPES).map(|i| format!("type_{}", i)))
+            .collect();
+
+        let (batch, skipped) = select_warm_index_types(&requested);
+        assert_eq!(batch.len(), MAX_WARM_INDEX_TYPES);
+        assert_eq!(&batch[0..2], &["economic_event".to_string(), "process".to_string()], "duplicate and empty types ignored");
+        assert_eq!(skipped, vec![format!("type_{}", MAX_WARM_INDEX_TYPES - 2), format!("type_{}", MAX_WARM_INDEX_TYPES - 1)], "remaining types deferred");
+    }
+
+    #[test]
+    fn test_split_index_page() {
+        let entries: Vec<EntryHash> = (1..=3).map(identity).collect();
+
+        let (page, next) = split_index_page(entries.to_owned(), 2);
+        assert_eq!(page, &entries[0..2]);
+        assert_eq!(next, Some(identity(2)), "cursor returned while entries remain");
+
+        let (page, next) = split_index_page(entries[0..2].to_vec(), 2);
+        assert_eq!(page.len(), 2);
+        assert_eq!(next, None, "final page should not return a cursor");
+
+        let (page, next) = split_index_page(vec![], 2);
+        assert!(page.is_empty());
+        assert_eq!(next, None, "empty index should not return a cursor");
+    }
+
+    #[test]
+    fn test_clamp_query_depth() {
+        assert_eq!(clamp_query_depth(None), 0);
+        assert_eq!(clamp_query_depth(Some(0)), 0);
+        assert_eq!(clamp_query_depth(Some(1)), 1);
+        assert_eq!(clamp_query_depth(Some(255)), MAX_QUERY_DEPTH);
+    }
+
+    #[test]
+    fn test_index_admin_report_warns_when_unchanged() {
+        let report = index_admin_report(IndexAdminOperation::Unlink, true, false);
+        assert_eq!((report.forward, report.reciprocal, report.warning), (true, false, None));
+
+        assert!(index_admin_report(IndexAdminOperation::Unlink, false, false).warning.is_some(), "nothing removed");
+        assert!(index_admin_report(IndexAdminOperation::Link, false, false).warning.is_some(), "nothing created");
+    }
+}
@@ -35,6 +35,18 @@ struct MacroArgs {
     // retrieve associated records. Useful for record types with nonstandard (non-`DnaAddressable`) identifiers.
     #[darling(default)]
     record_read_fn_name: Option<String>,
+    // Comma-separated names of additional time indexes managed by custom zome API methods, without
+    // the record type namespace (eg. `occurred`).
+    #[darling(default)]
+    time_indexes: Option<String>,
+    // Comma-separated name prefixes of additional time indexes named at runtime (eg. `state.` for indexes
+    // named by record state), without the record type namespace.
+    #[darling(default)]
+    time_index_prefixes: Option<String>,
+    // Name of a zome function `fn(&QueryParams, &ResponseData) -> bool` deciding whether a record is
+    // returned from the query API. Applied to full responses only, identifier-only results are unfiltered.
+    #[darling(default)]
+    result_filter: Option<String>,
 }
 
 #[proc_macro_attribute]
@@ -66,13 +78,27 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
         None => format_ident!("query_{}s", record_type_str_attribute),
         Some(query_fn) => format_ident!("{}", query_fn),
     };
+    let exposed_merged_query_api_method_name = format_ident!("{}_merged", exposed_query_api_method_name);
+    let exposed_stream_query_api_method_name = format_ident!("{}_stream", exposed_query_api_method_name);
     let exposed_read_api_method_name = match &args.read_all_fn_name {
         None => format_ident!("read_all_{}s", record_type_str_attribute),
         Some(read_fn) => format_ident!("{}", read_fn),
     };
     let exposed_append_api_name = format_ident!("record_new_{}", record_type_str_attribute);
-    let creation_time_index_name = [record_type_str_attribute.clone(), ".created".to_string()].concat();
     let record_index_field_type = format_ident!("{}Address", record_type.to_string().to_case(Case::UpperCamel));
+    let extra_time_index_names = split_arg_list(&args.time_indexes);
+    let time_index_prefixes = split_arg_list(&args.time_index_prefixes);
+    let apply_result_filter = match &args.result_filter {
+        None => quote! {},
+        Some(filter_fn) => {
+            let filter_fn = format_ident!("{}", filter_fn);
+            quote! {
+                let entries_result = entries_result.map(|entries| entries.into_iter()
+                    .filter(|r| r.as_ref().map_or(true, |record| #filter_fn(&params, record)))
+                    .collect::<Vec<RecordAPIResult<ResponseData>>>());
+            }
+        },
+    };
 
     // build iterators for generating index update methods and query conditions
     let all_indexes = fields.iter()
@@ -113,7 +139,9 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
             let related_index_name = format_ident!("{}_{}", record_type_str_attribute, relationship_name);
             let related_record_type_str_attribute = related_record_type.to_case(Case::Snake);
             let reciprocal_index_name = format_ident!("{}_{}", related_record_type_str_attribute, related_relationship_name);
-            let remote_record_time_index_id: String = format!("{}_{}.indexed", record_type_str_attribute, relationship_name);
+            // namespaced by relationship, @see hdk_semantic_indexes_zome_lib::REMOTE_RECORD_TIME_INDEX_NAME
+            let remote_time_index_namespace = format!("{}_{}", record_type_str_attribute, relationship_name);
+            let remote_record_time_index_id = quote! { time_index_name(#remote_time_index_namespace, REMOTE_RECORD_TIME_INDEX_NAME) };
 
             (
                 index_type, index_datatype, relationship_name,
@@ -129,19 +157,215 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
             _index_type, _index_datatype, relationship_name,
             _related_record_type_str_attribute,
             related_index_field_type, related_index_name,
-            _reciprocal_index_name, remote_record_time_index_id,
+            reciprocal_index_name, remote_record_time_index_id,
         )| {
             let local_dna_read_method_name = format_ident!("_internal_read_{}_{}", record_type_str_attribute, relationship_name);
+            let local_dna_read_existing_method_name = format_ident!("_internal_read_{}_{}_if_exists", record_type_str_attribute, relationship_name);
+            let local_dna_read_history_method_name = format_ident!("_internal_read_{}_{}_history", record_type_str_attribute, relationship_name);
+            let local_dna_read_deprecated_method_name = format_ident!("_internal_read_{}_{}_with_deprecated", record_type_str_attribute, relationship_name);
+            let local_dna_read_by_author_method_name = format_ident!("_internal_read_{}_{}_by_author", record_type_str_attribute, relationship_name);
+            let local_dna_deprecate_method_name = format_ident!("_internal_deprecate_{}_{}", record_type_str_attribute, relationship_name);
 
             quote! {
                 #[hdk_extern]
                 fn #local_dna_read_method_name(ByAddress { address }: ByAddress<#record_index_field_type>) -> ExternResult<Vec<#related_index_field_type>> {
+                    register_time_indexes(&TIME_INDEXES);
+                    throw_if_index_read_not_permitted(read_query_access, read_agent_zome, read_network_settings_zome)?;
                     Ok(read_index(
                         &address,
                         &stringify!(#related_index_name),
                         &#remote_record_time_index_id,
                     )?)
                 }
+
+                #[hdk_extern]
+                fn #local_dna_read_existing_method_name(ByAddress { address }: ByAddress<#record_index_field_type>) -> ExternResult<Option<Vec<#related_index_field_type>>> {
+                    register_time_indexes(&TIME_INDEXES);
+                    throw_if_index_read_not_permitted(read_query_access, read_agent_zome, read_network_settings_zome)?;
+                    Ok(read_index_if_exists(
+                        &address,
+                        &stringify!(#related_index_name),
+                        &#remote_record_time_index_id,
+                    )?)
+                }
+
+                // audit API including entries whose links have been replaced by tombstones
+                #[hdk_extern]
+                fn #local_dna_read_history_method_name(ByAddress { address }: ByAddress<#record_index_field_type>) -> ExternResult<Vec<IndexHistoryEntry<#related_index_field_type>>> {
+                    register_time_indexes(&TIME_INDEXES);
+                    throw_if_index_read_not_permitted(read_query_access, read_agent_zome, read_network_settings_zome)?;
+                    Ok(read_index_history(
+                        &address,
+                        &stringify!(#related_index_name),
+                        &#remote_record_time_index_id,
+                    )?)
+                }
+
+                #[hdk_extern]
+                fn #local_dna_read_deprecated_method_name(ReadIndexParams { address, include_deprecated }: ReadIndexParams<#record_index_field_type>) -> ExternResult<Vec<#related_index_field_type>> {
+                    register_time_indexes(&TIME_INDEXES);
+                    throw_if_index_read_not_permitted(read_query_access, read_agent_zome, read_network_settings_zome)?;
+                    Ok(read_index_with_deprecated(
+                        &address,
+                        &stringify!(#related_index_name),
+                        &#remote_record_time_index_id,
+                        include_deprecated,
+                    )?)
+                }
+
+                // moderation API to attribute links in shared indexes to the agents who created them
+                #[hdk_extern]
+                fn #local_dna_read_by_author_method_name(ReadIndexByAuthorParams { address, author }: ReadIndexByAuthorParams<#record_index_field_type>) -> ExternResult<Vec<#related_index_field_type>> {
+                    register_time_indexes(&TIME_INDEXES);
+                    throw_if_index_read_not_permitted(read_query_access, read_agent_zome, read_network_settings_zome)?;
+                    Ok(read_index_by_author(
+                        &address,
+                        &stringify!(#related_index_name),
+                        &#remote_record_time_index_id,
+                        &author,
+                    )?)
+                }
+
+                // retain a superseded relationship as history, hidden from normal index reads
+                #[hdk_extern]
+                fn #local_dna_deprecate_method_name(DeprecateIndexParams { address, target }: DeprecateIndexParams<#record_index_field_type, #related_index_field_type>) -> ExternResult<u32> {
+                    register_time_indexes(&TIME_INDEXES);
+                    throw_if_index_read_only(read_index_read_only)?;
+                    let deprecated = deprecate_index(
+                        &address, &target,
+                        &stringify!(#related_index_name),
+                        &stringify!(#reciprocal_index_name),
+                    )?;
+                    Ok(deprecated.into_iter().collect::<RecordAPIResult<Vec<ActionHash>>>()?.len() as u32)
+                }
+            }
+        });
+
+    // generate fields & readers for eager resolution of a record's own edges in query results
+    let nested_edge_fields = all_indexes.clone()
+        .map(|(
+            _index_type, _index_datatype, relationship_name,
+            _related_record_type_str_attribute,
+            related_index_field_type, _related_index_name,
+            _reciprocal_index_name, _remote_record_time_index_id,
+        )| {
+            let field_ident = format_ident!("{}", relationship_name);
+            quote! {
+                pub #field_ident: Vec<#related_index_field_type>,
+            }
+        });
+    let nested_edge_readers = all_indexes.clone()
+        .map(|(
+            _index_type, _index_datatype, relationship_name,
+            _related_record_type_str_attribute,
+            _related_index_field_type, related_index_name,
+            _reciprocal_index_name, remote_record_time_index_id,
+        )| {
+            let field_ident = format_ident!("{}", relationship_name);
+            quote! {
+                #field_ident: read_index(
+                    address,
+                    &stringify!(#related_index_name),
+                    &#remote_record_time_index_id,
+                )?,
+            }
+        });
+
+    // generate calls to rewrite legacy link tags for each index of a record
+    let remote_time_index_namespaces = all_indexes.clone()
+        .map(|(
+            _index_type, _index_datatype, relationship_name,
+            _related_record_type_str_attribute,
+            _related_index_field_type, _related_index_name,
+            _reciprocal_index_name, _remote_record_time_index_id,
+        )| format!("{}_{}", record_type_str_attribute, relationship_name));
+
+    let index_link_migrations = all_indexes.clone()
+        .map(|(
+            _index_type, _index_datatype, _relationship_name,
+            _related_record_type_str_attribute,
+            _related_index_field_type, related_index_name,
+            reciprocal_index_name, _remote_record_time_index_id,
+        )| {
+            quote! {
+                migrated += hdk_semantic_indexes_zome_lib::migrate_index_links(
+                    address,
+                    &stringify!(#related_index_name), &stringify!(#reciprocal_index_name),
+                )?;
+            }
+        });
+
+    // generate calls to remove duplicate links for each index of a record
+    let index_link_dedups = all_indexes.clone()
+        .map(|(
+            _index_type, _index_datatype, _relationship_name,
+            _related_record_type_str_attribute,
+            _related_index_field_type, related_index_name,
+            reciprocal_index_name, _remote_record_time_index_id,
+        )| {
+            quote! {
+                removed += hdk_semantic_indexes_zome_lib::dedup_index(
+                    address,
+                    &stringify!(#related_index_name), &stringify!(#reciprocal_index_name),
+                )?;
+            }
+        });
+
+    // generate descriptors of all managed indexes for diagnostics
+    let index_descriptors = all_indexes.clone()
+        .map(|(
+            index_type, _index_datatype, relationship_name,
+            related_record_type_str_attribute,
+            _related_index_field_type, related_index_name,
+            reciprocal_index_name, remote_record_time_index_id,
+        )| {
+            let dna_update_method_name = index_update_method_name(index_type, &record_type_str_attribute, &relationship_name);
+            quote! {
+                IndexDescriptor {
+                    name: #relationship_name.to_string(),
+                    record_type: #record_type_str_attribute.to_string(),
+                    related_record_type: #related_record_type_str_attribute.to_string(),
+                    link_tag: stringify!(#related_index_name).to_string(),
+                    reciprocal_link_tag: stringify!(#reciprocal_index_name).to_string(),
+                    time_index: #remote_record_time_index_id,
+                    update_method: stringify!(#dna_update_method_name).to_string(),
+                },
+            }
+        });
+
+    // generate administrative link & unlink handlers for each index, matching either orientation of its links
+    let index_link_admin_handlers = all_indexes.clone()
+        .map(|(
+            _index_type, index_datatype, _relationship_name,
+            related_record_type_str_attribute,
+            related_index_field_type, related_index_name,
+            reciprocal_index_name, _remote_record_time_index_id,
+        )| {
+            // String-based indexes link to `Path` anchors rather than records
+            if index_datatype.is_some() {
+                return quote! {};
+            }
+            quote! {
+                if (source_type.as_str(), dest_type.as_str(), link_tag.as_str(), link_tag_reciprocal.as_str())
+                    == (#record_type_str_attribute, #related_record_type_str_attribute, stringify!(#related_index_name), stringify!(#reciprocal_index_name))
+                {
+                    let source: #record_index_field_type = DnaAddressable::new(source_address.0.to_owned(), source_address.1.to_owned());
+                    let dest: #related_index_field_type = DnaAddressable::new(dest_address.0.to_owned(), dest_address.1.to_owned());
+                    return match operation {
+                        IndexAdminOperation::Link => hdk_semantic_indexes_zome_lib::link_index(&source_type, &source, &dest_type, &dest, &link_tag, &link_tag_reciprocal),
+                        IndexAdminOperation::Unlink => hdk_semantic_indexes_zome_lib::unlink_index(&source_type, &source, &dest_type, &dest, &link_tag, &link_tag_reciprocal, removed_at),
+                    };
+                }
+                if (source_type.as_str(), dest_type.as_str(), link_tag.as_str(), link_tag_reciprocal.as_str())
+                    == (#related_record_type_str_attribute, #record_type_str_attribute, stringify!(#reciprocal_index_name), stringify!(#related_index_name))
+                {
+                    let source: #related_index_field_type = DnaAddressable::new(source_address.0.to_owned(), source_address.1.to_owned());
+                    let dest: #record_index_field_type = DnaAddressable::new(dest_address.0.to_owned(), dest_address.1.to_owned());
+                    return match operation {
+                        IndexAdminOperation::Link => hdk_semantic_indexes_zome_lib::link_index(&source_type, &source, &dest_type, &dest, &link_tag, &link_tag_reciprocal),
+                        IndexAdminOperation::Unlink => hdk_semantic_indexes_zome_lib::unlink_index(&source_type, &source, &dest_type, &dest, &link_tag, &link_tag_reciprocal, removed_at),
+                    };
+                }
             }
         });
 
@@ -153,12 +377,7 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
             related_index_field_type, related_index_name,
             reciprocal_index_name, remote_record_time_index_id,
         )| {
-            // :TODO: differentiate Local/Remote indexes as necessitated by final HC core APIs
-            let dna_update_method_name = match index_type.to_string().as_ref() {
-                "Local" => format_ident!("_internal_index_{}_{}", record_type_str_attribute, relationship_name),
-                "Remote" => format_ident!("index_{}_{}", record_type_str_attribute, relationship_name),
-                _ => panic!("expected index type of Local or Remote"),
-            };
+            let dna_update_method_name = index_update_method_name(index_type, &record_type_str_attribute, &relationship_name);
 
             // Standard logic for *Addressable-based indexes.
             // Note that String-based indexes are transparently converted to *Addressable ones in the client
@@ -166,19 +385,130 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
             quote! {
                 #[hdk_extern]
                 fn #dna_update_method_name(indexes: RemoteEntryLinkRequest<#related_index_field_type, #record_index_field_type>) -> ExternResult<RemoteEntryLinkResponse> {
+                    register_time_indexes(&TIME_INDEXES);
                     let RemoteEntryLinkRequest { remote_entry, target_entries, removed_entries } = indexes;
 
-                    Ok(sync_index(
+                    throw_if_index_read_only(read_index_read_only)?;
+                    guard_index_write_rate(&remote_entry, index_write_rate_limit(read_index_write_rate_limit)?)?;
+
+                    Ok(sync_index_with_options(
                         &remote_entry,
                         target_entries.as_slice(),
                         removed_entries.as_slice(),
                         &stringify!(#reciprocal_index_name), &stringify!(#related_index_name),
                         &#remote_record_time_index_id,
+                        IndexSyncOptions {
+                            emit_signal: index_signals_enabled(read_index_signals_enabled),
+                            tombstone_removed: index_tombstones_enabled(read_index_tombstones_enabled),
+                        },
                     )?)
                 }
             }
         });
 
+    // generate query API method code to handle reads of an index from multiple base records
+    let merged_query_handlers = all_indexes.clone()
+        .map(|(
+            _index_type, index_datatype, relationship_name,
+            _related_record_type_str_attribute,
+            related_index_field_type, _related_index_name,
+            reciprocal_index_name, _remote_record_time_index_id,
+        )| {
+            // String-based indexes are queried by identifier rather than record address
+            if index_datatype.is_some() {
+                return quote! {};
+            }
+            quote! {
+                if index == #relationship_name {
+                    let bases: Vec<#related_index_field_type> = base_addresses.iter()
+                        .map(|(dna, hash)| DnaAddressable::new(dna.to_owned(), hash.to_owned()))
+                        .collect();
+                    match response_mode {
+                        ResponseMode::Full => entries_result = query_indexes_merged::<ResponseData, #record_index_field_type, _,_,_,_,_,_>(
+                            bases.as_slice(),
+                            &stringify!(#reciprocal_index_name),
+                            &local_time_index_id(),
+                            &read_index_target_zome,
+                            &QUERY_FN_NAME,
+                            before.to_owned(),
+                            last.unwrap_or(PAGE_SIZE),
+                        ),
+                        ResponseMode::IdsOnly => ids_result = query_indexes_merged_ids::<#record_index_field_type, _,_,_>(
+                            bases.as_slice(),
+                            &stringify!(#reciprocal_index_name),
+                            &local_time_index_id(),
+                            before.to_owned(),
+                            last.unwrap_or(PAGE_SIZE),
+                        ),
+                    };
+                }
+            }
+        });
+
+    // generate code to count the distinct records referenced by an index from multiple base records
+    let distinct_count_handlers = all_indexes.clone()
+        .map(|(
+            _index_type, index_datatype, relationship_name,
+            _related_record_type_str_attribute,
+            related_index_field_type, _related_index_name,
+            reciprocal_index_name, _remote_record_time_index_id,
+        )| {
+            if index_datatype.is_some() {
+                return quote! {};
+            }
+            quote! {
+                if index == #relationship_name {
+                    let bases: Vec<#related_index_field_type> = base_addresses.iter()
+                        .map(|(dna, hash)| DnaAddressable::new(dna.to_owned(), hash.to_owned()))
+                        .collect();
+                    count_result = hdk_semantic_indexes_zome_lib::count_distinct_targets(bases.as_slice(), &stringify!(#reciprocal_index_name));
+                }
+            }
+        });
+
+    // generate query API method code to handle filtered read requests streamed to the client via signals
+    let stream_query_handlers = all_indexes.clone()
+        .map(|(
+            _index_type, index_datatype, relationship_name,
+            _related_record_type_str_attribute,
+            related_index_field_type, _related_index_name,
+            reciprocal_index_name, _remote_record_time_index_id,
+        )| {
+            let query_field_ident = format_ident!("{}", relationship_name);
+
+            let base_address = match index_datatype {
+                Some(_) => quote! {
+                    &{
+                        let index_anchor_path = Path::from(#query_field_ident);
+                        let index_anchor_id: #related_index_field_type = DnaAddressable::new(dna_info()?.hash, index_anchor_path.path_entry_hash()?);
+                        index_anchor_id
+                    }
+                },
+                None => quote! { #query_field_ident },
+            };
+            quote! {
+                // only a single parameter is streamed, so that results are not emitted more than once
+                match &params.#query_field_ident {
+                    Some(#query_field_ident) if stream_result.is_none() => {
+                        stream_result = Some(query_index_stream::<ResponseData, #record_index_field_type, _,_,_,_,_,_,_,_>(
+                            #base_address,
+                            &stringify!(#reciprocal_index_name),
+                            &local_time_index_id(),
+                            &read_index_target_zome,
+                            &QUERY_FN_NAME,
+                            include_tombstones,
+                            &stream_id,
+                            chunk_size.unwrap_or(PAGE_SIZE),
+                            after.as_ref(),
+                            limit.unwrap_or(MAX_STREAM_RESULTS),
+                            &format_stream_edge,
+                        ));
+                    },
+                    _ => (),
+                };
+            }
+        });
+
     // generate query API method code to handle filtered read requests
     let query_handlers = all_indexes
         .map(|(
@@ -199,13 +529,22 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
                                 let index_anchor_path = Path::from(#query_field_ident);
                                 let index_anchor_id: #related_index_field_type = DnaAddressable::new(dna_info()?.hash, index_anchor_path.path_entry_hash()?);
 
-                                entries_result = query_index::<ResponseData, #record_index_field_type, _,_,_,_,_,_>(
-                                    &index_anchor_id,
-                                    &stringify!(#reciprocal_index_name),
-                                    &LOCAL_TIME_INDEX_ID,
-                                    &read_index_target_zome,
-                                    &QUERY_FN_NAME,
-                                );
+                                match response_mode {
+                                    ResponseMode::Full => entries_result = query_index::<ResponseData, #record_index_field_type, _,_,_,_,_,_>(
+                                        &index_anchor_id,
+                                        &stringify!(#reciprocal_index_name),
+                                        &local_time_index_id(),
+                                        &read_index_target_zome,
+                                        &QUERY_FN_NAME,
+                                        include_tombstones,
+                                    ),
+                                    ResponseMode::IdsOnly => ids_result = query_index_ids::<#record_index_field_type, _,_,_>(
+                                        &index_anchor_id,
+                                        &stringify!(#reciprocal_index_name),
+                                        &local_time_index_id(),
+                                        include_tombstones,
+                                    ),
+                                };
                             },
                             _ => (),
                         };
@@ -216,13 +555,22 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
                 None => quote! {
                     match &params.#query_field_ident {
                         Some(#query_field_ident) => {
-                            entries_result = query_index::<ResponseData, #record_index_field_type, _,_,_,_,_,_>(
-                                #query_field_ident,
-                                &stringify!(#reciprocal_index_name),
-                                &LOCAL_TIME_INDEX_ID,
-                                &read_index_target_zome,
-                                &QUERY_FN_NAME,
-                            );
+                            match response_mode {
+                                ResponseMode::Full => entries_result = query_index::<ResponseData, #record_index_field_type, _,_,_,_,_,_>(
+                                    #query_field_ident,
+                                    &stringify!(#reciprocal_index_name),
+                                    &local_time_index_id(),
+                                    &read_index_target_zome,
+                                    &QUERY_FN_NAME,
+                                    include_tombstones,
+                                ),
+                                ResponseMode::IdsOnly => ids_result = query_index_ids::<#record_index_field_type, _,_,_>(
+                                    #query_field_ident,
+                                    &stringify!(#reciprocal_index_name),
+                                    &local_time_index_id(),
+                                    include_tombstones,
+                                ),
+                            };
                         },
                         _ => (),
                     };
@@ -241,22 +589,120 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
         #[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
         pub struct DnaConfigSlice {
             pub #record_type_index_attribute: IndexingZomeConfig,
+            #[serde(default)]
+            pub query_access: QueryAccess,
         }
 
         // zome properties access helper
         fn read_index_target_zome(conf: DnaConfigSlice) -> Option<String> {
-            Some(conf.#record_type_index_attribute.record_storage_zome)
+            Some(conf.#record_type_index_attribute.record_storage_zome).filter(|zome| !zome.is_empty())
+        }
+
+        // zome properties access helper
+        fn read_index_target_zomes(conf: DnaConfigSlice) -> Option<Vec<(String, String)>> {
+            conf.#record_type_index_attribute.record_storage_zomes
+        }
+
+        // zome properties access helper
+        fn read_index_signals_enabled(conf: DnaConfigSlice) -> bool {
+            conf.#record_type_index_attribute.emit_index_signals
+        }
+
+        // zome properties access helper
+        fn read_index_unindexed_order(conf: DnaConfigSlice) -> UnindexedOrder {
+            conf.#record_type_index_attribute.unindexed_order
+        }
+
+        // zome properties access helper
+        fn read_index_tag_address_mode(conf: DnaConfigSlice) -> TagAddressMode {
+            conf.#record_type_index_attribute.tag_address_mode
+        }
+
+        // zome properties access helper
+        fn read_index_tombstones_enabled(conf: DnaConfigSlice) -> bool {
+            conf.#record_type_index_attribute.tombstone_removed_links
+        }
+
+        // zome properties access helper
+        fn read_index_write_rate_limit(conf: DnaConfigSlice) -> Option<IndexWriteRateLimit> {
+            conf.#record_type_index_attribute.write_rate_limit
+        }
+
+        // zome properties access helper
+        fn read_network_settings_zome(conf: DnaConfigSlice) -> Option<String> {
+            conf.#record_type_index_attribute.network_settings_zome
+        }
+
+        // zome properties access helper
+        fn read_index_read_only(conf: DnaConfigSlice) -> bool {
+            conf.#record_type_index_attribute.read_only
+        }
+
+        // DNA properties access helper
+        fn read_query_access(conf: DnaConfigSlice) -> QueryAccess {
+            conf.query_access
+        }
+
+        // zome properties access helper
+        fn read_agent_zome(conf: DnaConfigSlice) -> Option<String> {
+            conf.#record_type_index_attribute.agent_zome
         }
 
         // define struct to wrap query parameter inputs, so that other meta-args (eg. pagination) can be added later
         #[derive(Debug, Serialize, Deserialize)]
         struct SearchInputs {
             pub params: QueryParams,
+            // depth of related record edges to resolve for each result, capped at `MAX_QUERY_DEPTH`
+            #[serde(default)]
+            pub depth: Option<u8>,
+            // also return records whose index links have been replaced by tombstones
+            #[serde(default)]
+            pub include_tombstones: bool,
+            // return only the identifiers of each record and its latest revision; `depth` is ignored
+            #[serde(default)]
+            #[serde(alias = "responseMode")]
+            pub response_mode: ResponseMode,
         }
 
         // define zome API function name to read indexed records
         const QUERY_FN_NAME: &str = stringify!(#record_read_api_method_name);
-        const LOCAL_TIME_INDEX_ID: &str = #creation_time_index_name;
+
+        // record type namespacing the time indexes of this zome, @see hdk_semantic_indexes_zome_lib::time_index_name
+        const TIME_INDEX_RECORD_TYPE: &str = #record_type_str_attribute;
+
+        // time index ordering records by creation
+        fn local_time_index_id() -> String {
+            time_index_name(TIME_INDEX_RECORD_TYPE, CREATED_TIME_INDEX_NAME)
+        }
+
+        // all time indexes managed by this zome, @see hdk_semantic_indexes_zome_lib::validate_time_index_name
+        static TIME_INDEXES: TimeIndexRegistry = TimeIndexRegistry {
+            record_type: TIME_INDEX_RECORD_TYPE,
+            names: &[
+                CREATED_TIME_INDEX_NAME,
+                #(#extra_time_index_names,)*
+            ],
+            prefixes: &[#(#time_index_prefixes,)*],
+            remote_namespaces: &[#(#remote_time_index_namespaces,)*],
+            unindexed_order: read_unindexed_order,
+            tag_address_mode: read_tag_address_mode,
+            record_storage_zomes: read_record_storage_zomes,
+        };
+
+        fn read_unindexed_order() -> UnindexedOrder {
+            index_unindexed_order(read_index_unindexed_order)
+        }
+
+        fn read_tag_address_mode() -> TagAddressMode {
+            index_tag_address_mode(read_index_tag_address_mode)
+        }
+
+        fn read_record_storage_zomes() -> Option<Vec<(String, String)>> {
+            index_record_storage_zomes(read_index_target_zomes)
+        }
+
+        // zome API function name in the record storage zome which rebuilds a record's indexes
+        const RESYNC_FN_NAME: &str = "resync_record_indexes";
 
         // pagination constants
         const PAGE_SIZE: usize = 30;
@@ -282,6 +728,8 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
             // after: Option<EntryHash>,
             last: Option<usize>,
             before: Option<EntryHash>,
+            #[serde(default)]
+            response_mode: ResponseMode,
         }
 
         // query results structure mimicing Relay's pagination format
@@ -299,73 +747,396 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
         #[derive(Debug, Serialize, Deserialize)]
         #[serde(rename_all = "camelCase")]
         struct Edge {
-            node: Response,
+            node: EdgeNode,
             cursor: String,
+            #[serde(default)]
+            #[serde(skip_serializing_if = "Option::is_none")]
+            edges: Option<NestedEdges>,
+        }
+
+        // query result record, in full or as identifiers only depending on the requested `ResponseMode`
+        #[derive(Debug, Serialize, Deserialize)]
+        #[serde(untagged)]
+        enum EdgeNode {
+            Record(Response),
+            Revision(RecordRevisionId<#record_index_field_type>),
+        }
+
+        // identifiers of all records related to a query result, populated when `depth` is requested
+        #[derive(Debug, Serialize, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct NestedEdges {
+            #(
+                #nested_edge_fields
+            )*
+        }
+
+        fn read_nested_edges(address: &#record_index_field_type) -> RecordAPIResult<NestedEdges> {
+            Ok(NestedEdges {
+                #(
+                    #nested_edge_readers
+                )*
+            })
         }
 
         // declare public list API
         #[hdk_extern]
-        fn #exposed_read_api_method_name(PagingParams { /*first, after,*/ last, before }: PagingParams) -> ExternResult<QueryResults> {
+        fn #exposed_read_api_method_name(PagingParams { /*first, after,*/ last, before, response_mode }: PagingParams) -> ExternResult<QueryResults> {
+            register_time_indexes(&TIME_INDEXES);
+            throw_if_query_not_permitted(read_query_access, read_agent_zome, read_network_settings_zome)?;
+
+            if response_mode == ResponseMode::IdsOnly {
+                let ids_result = query_time_index_ids::<#record_index_field_type, _>(
+                    &local_time_index_id(),
+                    before,
+                    last.unwrap_or(PAGE_SIZE),
+                );
+                return Ok(handle_id_list_output(ids_result?.as_slice())?);
+            }
+
             let mut entries_result: RecordAPIResult<Vec<RecordAPIResult<ResponseData>>> = Err(SemanticIndexError::EmptyQuery.into());
 
             entries_result = query_time_index::<ResponseData, #record_index_field_type,_,_,_>(
                 &read_index_target_zome,
                 &QUERY_FN_NAME,
-                &LOCAL_TIME_INDEX_ID,
+                &local_time_index_id(),
                 before,
                 last.unwrap_or(PAGE_SIZE),
             );
 
-            Ok(handle_list_output(entries_result?.as_slice())?)
+            Ok(handle_list_output(entries_result?.as_slice(), 0)?)
         }
 
         // declare API for global list API management
         #[hdk_extern]
         fn #exposed_append_api_name(AppendAddress { address, timestamp }: AppendAddress<#record_index_field_type>) -> ExternResult<bool> {
-            Ok(append_to_time_index(&LOCAL_TIME_INDEX_ID, &address, timestamp)?)
+            register_time_indexes(&TIME_INDEXES);
+            throw_if_index_read_only(read_index_read_only)?;
+            Ok(append_to_time_index(&local_time_index_id(), &address, timestamp)?)
+        }
+
+        // declare API for migrating record identities to the current identity scheme
+        // :NOTE: administrative operation, should not be exposed to remote DNAs
+        #[hdk_extern]
+        fn migrate_identity(ByAddress { address }: ByAddress<#record_index_field_type>) -> ExternResult<EntryHash> {
+            register_time_indexes(&TIME_INDEXES);
+            throw_if_index_read_only(read_index_read_only)?;
+            throw_if_not_network_admin(read_network_settings_zome)?;
+            Ok(hdk_semantic_indexes_zome_lib::migrate_identity(&address)?)
+        }
+
+        // declare API for rewriting legacy index link tags to the current tag version, in batches of records
+        // :NOTE: administrative operation, should not be exposed to remote DNAs
+        #[hdk_extern]
+        fn migrate_index_links(ByAddresses { addresses }: ByAddresses<#record_index_field_type>) -> ExternResult<u32> {
+            register_time_indexes(&TIME_INDEXES);
+            throw_if_index_read_only(read_index_read_only)?;
+            let mut migrated = 0;
+            for address in addresses.iter() {
+                #(
+                    #index_link_migrations
+                )*
+            }
+            Ok(migrated)
+        }
+
+        // declare API for removing duplicate index links written prior to deduplication of index writes, in batches of records
+        // :NOTE: administrative operation, should not be exposed to remote DNAs
+        #[hdk_extern]
+        fn dedup_index_links(ByAddresses { addresses }: ByAddresses<#record_index_field_type>) -> ExternResult<u32> {
+            register_time_indexes(&TIME_INDEXES);
+            throw_if_index_read_only(read_index_read_only)?;
+            let mut removed = 0;
+            for address in addresses.iter() {
+                #(
+                    #index_link_dedups
+                )*
+            }
+            Ok(removed)
+        }
+
+        // declare API for bulk removal of records from the creation time index
+        // :NOTE: administrative operation, should not be exposed to remote DNAs
+        #[hdk_extern]
+        fn remove_from_time_index_batch(ByAddresses { addresses }: ByAddresses<#record_index_field_type>) -> ExternResult<u32> {
+            register_time_indexes(&TIME_INDEXES);
+            throw_if_index_read_only(read_index_read_only)?;
+            throw_if_not_network_admin(read_network_settings_zome)?;
+            let identities = addresses.iter()
+                .map(resolve_identity_address)
+                .collect::<RecordAPIResult<Vec<EntryHash>>>()?;
+            let removed = hdk_semantic_indexes_zome_lib::remove_from_time_index_batch(&local_time_index_id(), identities.as_slice())?;
+            Ok(removed.len() as u32)
+        }
+
+        // declare API for rebuilding the indexes of every record in the creation time index, in resumable batches
+        // :NOTE: administrative operation, should not be exposed to remote DNAs
+        #[hdk_extern]
+        fn reindex_all(ReindexParams { after, limit }: ReindexParams) -> ExternResult<ReindexReport> {
+            register_time_indexes(&TIME_INDEXES);
+            throw_if_index_read_only(read_index_read_only)?;
+            Ok(reindex_time_index_batch::<#record_index_field_type, _,_,_>(
+                &read_index_target_zome,
+                &RESYNC_FN_NAME,
+                &local_time_index_id(),
+                after,
+                limit.unwrap_or(MAX_REINDEX_BATCH_SIZE),
+            )?)
+        }
+
+        // declare API for listing every record in the creation time index, in resumable batches
+        #[hdk_extern]
+        fn read_indexed_addresses(ReindexParams { after, limit }: ReindexParams) -> ExternResult<IndexedAddresses<#record_index_field_type>> {
+            register_time_indexes(&TIME_INDEXES);
+            throw_if_query_not_permitted(read_query_access, read_agent_zome, read_network_settings_zome)?;
+            Ok(read_time_index_batch::<#record_index_field_type, _>(
+                &local_time_index_id(),
+                after,
+                limit.unwrap_or(MAX_REINDEX_BATCH_SIZE),
+            )?)
+        }
+
+        // declare API for pre-fetching the time indexes of the named record types, for use when first joining a network
+        #[hdk_extern]
+        fn warm_indexes(WarmIndexesParams { types, depth }: WarmIndexesParams) -> ExternResult<WarmIndexesReport> {
+            register_time_indexes(&TIME_INDEXES);
+            Ok(hdk_semantic_indexes_zome_lib::warm_time_indexes(
+                types.as_slice(),
+                depth.unwrap_or(PAGE_SIZE),
+            )?)
+        }
+
+        // declare API for removing a single index link, for repair of index data independently of records
+        // :NOTE: administrative operation, restricted to network admins
+        #[hdk_extern]
+        fn unlink_index(params: IndexLinkAdminParams) -> ExternResult<IndexLinkAdminReport> {
+            register_time_indexes(&TIME_INDEXES);
+            Ok(handle_index_link_admin(params, IndexAdminOperation::Unlink)?)
+        }
+
+        // declare API for restoring a single index link, the inverse of `unlink_index`
+        // :NOTE: administrative operation, restricted to network admins
+        #[hdk_extern]
+        fn link_index(params: IndexLinkAdminParams) -> ExternResult<IndexLinkAdminReport> {
+            register_time_indexes(&TIME_INDEXES);
+            Ok(handle_index_link_admin(params, IndexAdminOperation::Link)?)
+        }
+
+        fn handle_index_link_admin(params: IndexLinkAdminParams, operation: IndexAdminOperation) -> RecordAPIResult<IndexLinkAdminReport> {
+            throw_if_index_read_only(read_index_read_only)?;
+            throw_if_not_network_admin(read_network_settings_zome)?;
+
+            let IndexLinkAdminParams { source_type, source_address, dest_type, dest_address, link_tag, link_tag_reciprocal } = params;
+            let removed_at = if index_tombstones_enabled(read_index_tombstones_enabled) { Some(sys_time()?) } else { None };
+
+            #(
+                #index_link_admin_handlers
+            )*
+
+            Err(SemanticIndexError::UnknownIndexRelationship(
+                format!("{} -[{}]-> {} -[{}]-> {}", source_type, link_tag, dest_type, link_tag_reciprocal, source_type)
+            ).into())
+        }
+
+        // declare API for reading the log of administrative link & unlink operations
+        #[hdk_extern]
+        fn read_index_admin_log(_: ()) -> ExternResult<Vec<IndexAdminLogEntry>> {
+            register_time_indexes(&TIME_INDEXES);
+            Ok(hdk_semantic_indexes_zome_lib::read_index_admin_log()?)
+        }
+
+        // declare API for describing the indexes managed by this zome
+        #[hdk_extern]
+        fn list_managed_indexes(_: ()) -> ExternResult<Vec<IndexDescriptor>> {
+            register_time_indexes(&TIME_INDEXES);
+            Ok(vec![
+                #(
+                    #index_descriptors
+                )*
+            ])
         }
 
         // declare public query method with injected handler logic
         #[hdk_extern]
-        fn #exposed_query_api_method_name(SearchInputs { params }: SearchInputs) -> ExternResult<QueryResults>
+        fn #exposed_query_api_method_name(SearchInputs { params, depth, include_tombstones, response_mode }: SearchInputs) -> ExternResult<QueryResults>
         {
+            register_time_indexes(&TIME_INDEXES);
+            throw_if_query_not_permitted(read_query_access, read_agent_zome, read_network_settings_zome)?;
             let mut entries_result: RecordAPIResult<Vec<RecordAPIResult<ResponseData>>> = Err(SemanticIndexError::EmptyQuery.into());
+            let mut ids_result: RecordAPIResult<Vec<RecordAPIResult<RecordRevisionId<#record_index_field_type>>>> = Err(SemanticIndexError::EmptyQuery.into());
 
             // :TODO: proper search combinator logic, this just does exclusive boolean ops
             #(
                 #query_handlers
             )*
+            #apply_result_filter
+
+            match response_mode {
+                ResponseMode::Full => Ok(handle_list_output(entries_result?.as_slice(), clamp_query_depth(depth))?),
+                ResponseMode::IdsOnly => Ok(handle_id_list_output(ids_result?.as_slice())?),
+            }
+        }
+
+        // query inputs for streaming the results of a filtered query to the client in chunks
+        #[derive(Debug, Serialize, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct StreamSearchInputs {
+            pub params: QueryParams,
+            // identifier for the stream, returned in every emitted `QueryStreamChunk`
+            pub stream_id: String,
+            // maximum number of records in each chunk, defaulting to the page size
+            #[serde(default)]
+            pub chunk_size: Option<usize>,
+            // `next_cursor` of a previous call streaming the same query, to continue from
+            #[serde(default)]
+            pub after: Option<EntryHash>,
+            // maximum number of records streamed by this call, capped at `MAX_STREAM_RESULTS`
+            #[serde(default)]
+            pub limit: Option<usize>,
+            #[serde(default)]
+            pub include_tombstones: bool,
+        }
+
+        // declare public query method which emits its results as signals, for result sets too large to return at once
+        #[hdk_extern]
+        fn #exposed_stream_query_api_method_name(StreamSearchInputs { params, stream_id, chunk_size, after, limit, include_tombstones }: StreamSearchInputs) -> ExternResult<QueryStreamSummary>
+        {
+            register_time_indexes(&TIME_INDEXES);
+            throw_if_query_not_permitted(read_query_access, read_agent_zome, read_network_settings_zome)?;
+            let mut stream_result: Option<RecordAPIResult<QueryStreamSummary>> = None;
+
+            #(
+                #stream_query_handlers
+            )*
+
+            Ok(stream_result.unwrap_or(Err(SemanticIndexError::EmptyQuery.into()))?)
+        }
+
+        // format streamed query results as for the edges of `QueryResults`
+        fn format_stream_edge(node: ResponseData) -> Edge {
+            Edge {
+                cursor: node.#record_type_str_ident.id.to_string(),
+                node: EdgeNode::Record(node.#record_type_str_ident),
+                edges: None,
+            }
+        }
+
+        // query inputs for reading one index from several base records as a single time-ordered stream
+        #[derive(Debug, Serialize, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct MergedSearchInputs {
+            // name of the queried relationship, as for `QueryParams`
+            pub index: String,
+            pub base_addresses: Vec<(DnaHash, EntryHash)>,
+            pub last: Option<usize>,
+            pub before: Option<EntryHash>,
+            #[serde(default)]
+            pub response_mode: ResponseMode,
+        }
+
+        // declare public query method for merging the results of an index across several records
+        #[hdk_extern]
+        fn #exposed_merged_query_api_method_name(MergedSearchInputs { index, base_addresses, last, before, response_mode }: MergedSearchInputs) -> ExternResult<QueryResults>
+        {
+            register_time_indexes(&TIME_INDEXES);
+            throw_if_query_not_permitted(read_query_access, read_agent_zome, read_network_settings_zome)?;
+            let mut entries_result: RecordAPIResult<Vec<RecordAPIResult<ResponseData>>> = Err(SemanticIndexError::EmptyQuery.into());
+            let mut ids_result: RecordAPIResult<Vec<RecordAPIResult<RecordRevisionId<#record_index_field_type>>>> = Err(SemanticIndexError::EmptyQuery.into());
+
+            #(
+                #merged_query_handlers
+            )*
+
+            match response_mode {
+                ResponseMode::Full => Ok(handle_list_output(entries_result?.as_slice(), 0)?),
+                ResponseMode::IdsOnly => Ok(handle_id_list_output(ids_result?.as_slice())?),
+            }
+        }
+
+        // inputs for counting the distinct records referenced by one index from several base records
+        #[derive(Debug, Serialize, Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct DistinctCountInputs {
+            // name of the counted relationship, as for `QueryParams`
+            pub index: String,
+            pub base_addresses: Vec<(DnaHash, EntryHash)>,
+        }
+
+        // declare public method for counting the distinct records referenced by an index across several records
+        #[hdk_extern]
+        fn count_distinct_targets(DistinctCountInputs { index, base_addresses }: DistinctCountInputs) -> ExternResult<usize>
+        {
+            register_time_indexes(&TIME_INDEXES);
+            throw_if_query_not_permitted(read_query_access, read_agent_zome, read_network_settings_zome)?;
+            let mut count_result: RecordAPIResult<usize> = Err(SemanticIndexError::EmptyQuery.into());
+
+            #(
+                #distinct_count_handlers
+            )*
 
-            Ok(handle_list_output(entries_result?.as_slice())?)
+            Ok(count_result?)
         }
 
-        fn handle_list_output(entries: &[RecordAPIResult<ResponseData>]) -> RecordAPIResult<QueryResults>
+        fn handle_list_output(entries: &[RecordAPIResult<ResponseData>], depth: u8) -> RecordAPIResult<QueryResults>
         {
-            let valid_edges = entries.iter()
+            let mut errors: Vec<WasmError> = entries.iter()
                 .cloned()
-                .filter_map(Result::ok);
+                .filter_map(Result::err)
+                .map(|err| { WasmError::from(err) })
+                .collect();
 
-            let edge_cursors = valid_edges
-                .clone()
-                .map(|node| {
-                    node.#record_type_str_ident.id.to_string()
+            let mut formatted_edges = vec![];
+            for node in entries.iter().cloned().filter_map(Result::ok) {
+                let cursor = node.#record_type_str_ident.id.to_string();
+                let edges = match depth {
+                    0 => None,
+                    _ => match read_nested_edges(&node.#record_type_str_ident.id) {
+                        Ok(edges) => Some(edges),
+                        Err(err) => {
+                            errors.push(err.into());
+                            None
+                        },
+                    },
+                };
+                formatted_edges.push(Edge {
+                    node: EdgeNode::Record(node.#record_type_str_ident),
+                    cursor,
+                    edges,
                 });
+            }
 
-            let formatted_edges = valid_edges.zip(edge_cursors)
-                .map(|(node, cursor)| {
-                    Edge {
-                        node: node.#record_type_str_ident,
-                        cursor,
-                    }
-                });
+            Ok(build_query_results(formatted_edges, errors))
+        }
+
+        fn handle_id_list_output(entries: &[RecordAPIResult<RecordRevisionId<#record_index_field_type>>]) -> RecordAPIResult<QueryResults>
+        {
+            let errors: Vec<WasmError> = entries.iter()
+                .cloned()
+                .filter_map(Result::err)
+                .map(|err| { WasmError::from(err) })
+                .collect();
+
+            let formatted_edges = entries.iter().cloned().filter_map(Result::ok)
+                .map(|node| Edge {
+                    cursor: node.id.to_string(),
+                    node: EdgeNode::Revision(node),
+                    edges: None,
+                })
+                .collect();
+
+            Ok(build_query_results(formatted_edges, errors))
+        }
 
-            let mut edge_cursors = formatted_edges.clone().map(|e| { e.cursor });
+        fn build_query_results(formatted_edges: Vec<Edge>, errors: Vec<WasmError>) -> QueryResults
+        {
+            let mut edge_cursors = formatted_edges.iter().map(|e| { e.cursor.clone() });
             let first_cursor = edge_cursors.next().unwrap_or("0".to_string());
+            let end_cursor = edge_cursors.last().unwrap_or(first_cursor.clone());
 
-            Ok(QueryResults {
-                edges: formatted_edges.collect(),
+            QueryResults {
+                edges: formatted_edges,
                 page_info: PageInfo {
-                    end_cursor: edge_cursors.last().unwrap_or(first_cursor.clone()),
+                    end_cursor,
                     start_cursor: first_cursor,
                     // :TODO:
                     has_next_page: true,
@@ -373,19 +1144,31 @@ pub fn index_zome(attribs: TokenStream, input: TokenStream) -> TokenStream {
                     page_limit: None,
                     total_count: None,
                 },
-                errors: entries.iter()
-                    .cloned()
-                    .filter_map(Result::err)
-                    .map(|err| { WasmError::from(err) })
-                    .collect(),
-            })
+                errors,
+            }
         }
     })
 }
 
+// :TODO: differentiate Local/Remote indexes as necessitated by final HC core APIs
+fn index_update_method_name(index_type: &syn::Ident, record_type: &str, relationship_name: &str) -> syn::Ident {
+    match index_type.to_string().as_ref() {
+        "Local" => format_ident!("_internal_index_{}_{}", record_type, relationship_name),
+        "Remote" => format_ident!("index_{}_{}", record_type, relationship_name),
+        _ => panic!("expected index type of Local or Remote"),
+    }
+}
+
 fn next_generic_type_as_string(args: &mut Punctuated<GenericArgument, Comma>) -> String {
     match args.pop().unwrap().value() {
         GenericArgument::Type(Type::Path(TypePath { path, .. })) => path.get_ident().unwrap().to_string(),
         _ => panic!("expecting a Type argument of length 1"),
     }
 }
+
+fn split_arg_list(arg: &Option<String>) -> Vec<String> {
+    match arg {
+        None => vec![],
+        Some(list) => list.split(',').map(|s| s.trim().to_string()).filter(|s| !s.is_empty()).collect(),
+    }
+}
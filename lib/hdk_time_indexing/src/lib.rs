@@ -42,11 +42,16 @@ pub mod reading;
 pub mod sorting;
 
 pub use index_tree::IndexSegment as TimeIndex;
-pub use writing::index_entry;
+pub use writing::{index_entry, remove_entry, remove_entries};
 pub use reading::{
     read_all_entry_hashes,
+    read_entry_hashes_before,
+    read_entry_hashes_between,
     get_latest_entry_hashes,
     get_older_entry_hashes,
+    read_oldest_entry_hashes,
+    get_entry_index_time,
+    dedup_entry_hashes,
 };
 pub use sorting::sort_entries_by_time_index;
 
@@ -20,11 +20,149 @@ pub fn read_all_entry_hashes<I>(index_name: &I) -> TimeIndexResult<Vec<EntryHash
     match root_hash {
         None => Ok(vec![]),
         Some(hash) => {
-            Ok(collect_leaf_index_hashes(index_name, hash, (*INDEX_DEPTH).len() as isize)?)
+            Ok(dedup_entry_hashes(collect_leaf_index_hashes(index_name, hash, (*INDEX_DEPTH).len() as isize)?))
         }
     }
 }
 
+/// Remove repeated `EntryHash`es from a set of index results, keeping the first occurrence of each.
+///
+/// Concurrent writes may index the same entry more than once (@see `writing::index_entry`),
+/// so results read from the index tree are de-duplicated before being returned.
+///
+pub fn dedup_entry_hashes(hashes: Vec<EntryHash>) -> Vec<EntryHash> {
+    let mut unique: Vec<EntryHash> = Vec::with_capacity(hashes.len());
+    for hash in hashes {
+        if !unique.contains(&hash) {
+            unique.push(hash);
+        }
+    }
+    unique
+}
+
+/**
+ * Retrieve all `EntryHash`es in the `index_name` index which are indexed at a time
+ * earlier than `before` (not inclusive), in order from most recent to oldest.
+ *
+ * As with `read_all_entry_hashes`, this traverses the entire index and should only
+ * be used with indexes known to be of a small size.
+ */
+pub fn read_entry_hashes_before<I>(index_name: &I, before: DateTime<Utc>) -> TimeIndexResult<Vec<EntryHash>>
+    where I: AsRef<str>,
+{
+    let mut timed_entries = read_all_entry_hashes(index_name)?
+        .into_iter()
+        .map(|hash| Ok((get_entry_index_time(index_name, &hash)?, hash)))
+        .collect::<TimeIndexResult<Vec<(Option<DateTime<Utc>>, EntryHash)>>>()?
+        .into_iter()
+        .filter_map(|(time, hash)| match time {
+            Some(t) if t < before => Some((t, hash)),
+            _ => None,
+        })
+        .collect::<Vec<(DateTime<Utc>, EntryHash)>>();
+
+    timed_entries.sort_by(|a, b| b.0.cmp(&a.0));
+
+    Ok(timed_entries.into_iter().map(|(_, hash)| hash).collect())
+}
+
+/**
+ * Retrieve all `EntryHash`es in the `index_name` index which are indexed at a time
+ * from `from` (inclusive) until `until` (not inclusive), in order from oldest to most recent.
+ *
+ * Only those branches of the index tree which overlap the range are traversed.
+ */
+pub fn read_entry_hashes_between<I>(index_name: &I, from: DateTime<Utc>, until: DateTime<Utc>) -> TimeIndexResult<Vec<EntryHash>>
+    where I: AsRef<str>,
+{
+    match get_root_hash(index_name)? {
+        None => Ok(vec![]),
+        Some(hash) => Ok(dedup_entry_hashes(collect_leaf_hashes_between(
+            hash,
+            (*INDEX_DEPTH).len() as isize,
+            if *HAS_CHUNK_LEAVES { -1 } else { 0 },
+            &from,
+            &until,
+            &|node_hash| get_timed_child_hashes_of_node(index_name, node_hash),
+        )?)),
+    }
+}
+
+/// Performs a depth-first traversal of a time index tree, returning the `EntryHash`es of
+/// the leafmost nodes (i.e. indexed entries) indexed from `from` until `until`, in chronological order.
+///
+/// `read_children` loads the child node hashes of a node along with the times they begin at,
+/// ordered from oldest to newest. Each node is taken to span until the start of its next sibling,
+/// so that nodes entirely outside the range are never read.
+/// Nodes at `leaf_depth` link to indexed entries rather than further index nodes.
+///
+pub fn collect_leaf_hashes_between<F>(
+    context_hash: EntryHash,
+    context_depth: isize,
+    leaf_depth: isize,
+    from: &DateTime<Utc>,
+    until: &DateTime<Utc>,
+    read_children: &F,
+) -> TimeIndexResult<Vec<EntryHash>>
+    where F: Fn(EntryHash) -> TimeIndexResult<Vec<(DateTime<Utc>, EntryHash)>>,
+{
+    let children = read_children(context_hash)?;
+
+    // last hop outside the index tree links to the targeted nodes, so return those in range
+    if context_depth <= leaf_depth {
+        return Ok(children.into_iter()
+            .filter(|(time, _)| time >= from && time < until)
+            .map(|(_, hash)| hash)
+            .collect());
+    }
+
+    let mut found = vec![];
+    for (i, (start, child)) in children.iter().enumerate() {
+        if start >= until {
+            break;
+        }
+        if let Some((next_start, _)) = children.get(i + 1) {
+            if next_start <= from {
+                continue;
+            }
+        }
+        found.extend(collect_leaf_hashes_between(child.to_owned(), context_depth - 1, leaf_depth, from, until, read_children)?);
+    }
+
+    Ok(found)
+}
+
+/**
+ * Determine the time at which `entry_hash` is indexed in the `index_name` index,
+ * or `None` if it has not been indexed.
+ */
+pub fn get_entry_index_time<I>(index_name: &I, entry_hash: &EntryHash) -> TimeIndexResult<Option<DateTime<Utc>>>
+    where I: AsRef<str>,
+{
+    let links = get_links(
+        entry_hash.to_owned(),
+        LinkTypes::TimeIndex,
+        Some(link_prefix_for_index(index_name)),
+    )?;
+
+    match canonical_index_link(links) {
+        None => Ok(None),
+        Some(link) => {
+            let segment: IndexSegment = link.tag.to_owned().try_into()?;
+            Ok(Some(segment.into()))
+        },
+    }
+}
+
+/// Select the link from an entry to the index tree which determines its position in the index.
+///
+/// Entries indexed more than once by concurrent writes are positioned at the most recent time
+/// they were indexed, which is where they are first encountered when reading newest to oldest.
+///
+fn canonical_index_link(links: Vec<Link>) -> Option<Link> {
+    links.into_iter().max_by_key(|link| chronological_sort_key(&link.tag))
+}
+
 /// Recursively performs a depth-first traversal of the specified time index tree, returning the `EntryHash`es
 /// of all the leafmost nodes (i.e. indexed entries) present in the index.
 ///
@@ -61,6 +199,92 @@ fn collect_leaf_index_hashes<I>(index_name: &I, context_hash: EntryHash, context
         .collect())
 }
 
+/**
+ * Retrieve the oldest entry hashes stored in the `index_name` time-ordered index,
+ * up to a maximum of `limit`, in order from oldest to most recent.
+ *
+ * The index tree is traversed from its oldest end, and traversal stops as soon as
+ * `limit` entries have been found- so the cost of this method is proportional to
+ * `limit` rather than to the size of the index.
+ */
+pub fn read_oldest_entry_hashes<I>(index_name: &I, limit: usize) -> TimeIndexResult<Vec<EntryHash>>
+    where I: AsRef<str>,
+{
+    match get_root_hash(index_name)? {
+        None => Ok(vec![]),
+        Some(hash) => collect_oldest_leaf_hashes(
+            hash,
+            (*INDEX_DEPTH).len() as isize,
+            if *HAS_CHUNK_LEAVES { -1 } else { 0 },
+            limit,
+            &|node_hash| get_oldest_first_child_hashes_of_node(index_name, node_hash),
+        ),
+    }
+}
+
+/// Performs a depth-first traversal of a time index tree from its oldest end, returning the
+/// `EntryHash`es of up to `limit` leafmost nodes (i.e. indexed entries) in chronological order.
+///
+/// `read_children` loads the child node hashes of a node, ordered from oldest to newest.
+/// Nodes at `leaf_depth` link to indexed entries rather than further index nodes.
+///
+pub fn collect_oldest_leaf_hashes<F>(
+    context_hash: EntryHash,
+    context_depth: isize,
+    leaf_depth: isize,
+    limit: usize,
+    read_children: &F,
+) -> TimeIndexResult<Vec<EntryHash>>
+    where F: Fn(EntryHash) -> TimeIndexResult<Vec<EntryHash>>,
+{
+    let mut found = vec![];
+    collect_oldest_leaf_hashes_into(&mut found, context_hash, context_depth, leaf_depth, limit, read_children)?;
+    Ok(found)
+}
+
+/// Recursive step of `collect_oldest_leaf_hashes`, appending unique entries to `found`
+/// until it holds `limit` of them. Entries indexed more than once are only counted once.
+///
+fn collect_oldest_leaf_hashes_into<F>(
+    found: &mut Vec<EntryHash>,
+    context_hash: EntryHash,
+    context_depth: isize,
+    leaf_depth: isize,
+    limit: usize,
+    read_children: &F,
+) -> TimeIndexResult<()>
+    where F: Fn(EntryHash) -> TimeIndexResult<Vec<EntryHash>>,
+{
+    if found.len() >= limit {
+        return Ok(());
+    }
+
+    let children = read_children(context_hash)?;
+
+    // last hop outside the index tree links to the targeted nodes, so return them
+    if context_depth <= leaf_depth {
+        for child in children {
+            if found.len() >= limit {
+                break;
+            }
+            if !found.contains(&child) {
+                found.push(child);
+            }
+        }
+        return Ok(());
+    }
+
+    // still recursing downwards- only descend into as many children as needed to fill the page
+    for child in children {
+        collect_oldest_leaf_hashes_into(found, child, context_depth - 1, leaf_depth, limit, read_children)?;
+        if found.len() >= limit {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
 /**
  * Retrieve the most recent entry hashes stored in the `index_name` time-ordered index,
  * up to a maximum of `limit`.
@@ -75,19 +299,15 @@ pub fn get_latest_entry_hashes<I>(index_name: &I, limit: usize) -> TimeIndexResu
         None => Ok(vec![]),
         Some(latest) => {
             // load a page of links to entries immediately before the latest
-            let mut earlier_page = get_older_entry_hashes(index_name, latest.to_owned(), limit)?;
-
-            // remove the earliest linked entry
-            let num_earlier = earlier_page.len();
-            if num_earlier > 0 {
-                earlier_page.truncate(num_earlier - 1);
-            }
+            let earlier_page = get_older_entry_hashes(index_name, latest.to_owned(), limit)?;
 
-            // prepend the most recent one
-            Ok([
+            // prepend the most recent one, which may also have been indexed at an earlier time
+            let mut page = dedup_entry_hashes([
                 vec![latest.to_owned()],
                 earlier_page,
-            ].concat())
+            ].concat());
+            page.truncate(limit);
+            Ok(page)
         },
     }
 }
@@ -111,8 +331,10 @@ pub fn get_older_entry_hashes<I>(index_name: &I, before_entry: EntryHash, limit:
 /// Return a maximum of `limit` `Link`s, in order from most recent to oldest in `index_name`
 /// starting at the given (already indexed) `entry_hash`.
 ///
+/// Entries linked more than once, by concurrent writes or network partitions, are only returned
+/// at their most recent position within the page.
+///
 /// :TODO: account for the possibility that an entry might be validly linked multiple times in the same index
-/// :TODO: de-duplicate links to account for network partitions erroneously causing multiple writes of the same index
 ///
 fn get_ordered_links_before<I>(index_name: &I, entry_hash: EntryHash, limit: usize) -> TimeIndexResult<Vec<Link>>
     where I: AsRef<str>,
@@ -123,8 +345,8 @@ fn get_ordered_links_before<I>(index_name: &I, entry_hash: EntryHash, limit: usi
         LinkTypes::TimeIndex,
         Some(link_prefix_for_index(index_name)),
     )?;
-    let leaf_link = parents.first().ok_or(
-        TimeIndexingError::NotIndexed(index_name.as_ref().to_string(), entry_hash)
+    let leaf_link = canonical_index_link(parents).ok_or_else(||
+        TimeIndexingError::NotIndexed(index_name.as_ref().to_string(), entry_hash.to_owned())
     )?;
     let this_index: IndexSegment = leaf_link.tag.to_owned().try_into()?;
     let this_timestamp = this_index.into();
@@ -132,10 +354,8 @@ fn get_ordered_links_before<I>(index_name: &I, entry_hash: EntryHash, limit: usi
     let leaf_hash = leaf_link.target.to_owned().into_entry_hash().unwrap();
 
     // find all our older siblings
-    let mut older_siblings = get_ordered_child_links_of_node_older_than(index_name, leaf_hash, this_timestamp)?;
-
-    // truncate to requested limit
-    older_siblings.truncate(limit);
+    let mut older_siblings = vec![];
+    append_unique_links(&mut older_siblings, get_ordered_child_links_of_node_older_than(index_name, leaf_hash, this_timestamp)?, &entry_hash, limit);
 
     // continue fetching links from previous leaf nodes in the index until we have reached the limit
     let mut context_timestamp = this_timestamp;
@@ -155,8 +375,7 @@ fn get_ordered_links_before<I>(index_name: &I, entry_hash: EntryHash, limit: usi
             Some(prev) => {
                 // append any found link targets from the previous leaf
                 let more_older_siblings = get_ordered_child_links_of_node_older_than(index_name, prev.hash()?, this_timestamp)?;
-                older_siblings = [older_siblings, more_older_siblings].concat();
-                older_siblings.truncate(limit);
+                append_unique_links(&mut older_siblings, more_older_siblings, &entry_hash, limit);
 
                 // look further backward from previous leaf if we still need to find more
                 let prev_time: DateTime<Utc> = prev.into();
@@ -168,6 +387,21 @@ fn get_ordered_links_before<I>(index_name: &I, entry_hash: EntryHash, limit: usi
     Ok(older_siblings)
 }
 
+/// Append those of `more` whose targets are not already present in `links`, up to a total of `limit`.
+/// Links to `cursor` are skipped, since it was returned before any entries older than it.
+///
+fn append_unique_links(links: &mut Vec<Link>, more: Vec<Link>, cursor: &EntryHash, limit: usize) {
+    for link in more {
+        if links.len() >= limit {
+            break;
+        }
+        let is_cursor = link.target.to_owned().into_entry_hash().as_ref() == Some(cursor);
+        if !is_cursor && !links.iter().any(|l| l.target == link.target) {
+            links.push(link);
+        }
+    }
+}
+
 /// Locate all the child links of the node with hash `leaf_hash`, ordered from newest to oldest.
 ///
 fn get_ordered_child_links_of_node<I>(index_name: &I, leaf_hash: EntryHash) -> TimeIndexResult<Vec<Link>>
@@ -186,6 +420,64 @@ fn get_ordered_child_links_of_node<I>(index_name: &I, leaf_hash: EntryHash) -> T
     Ok(siblings)
 }
 
+/// Locate the targets of all child links of the node with hash `node_hash`, ordered from oldest to newest.
+///
+fn get_oldest_first_child_hashes_of_node<I>(index_name: &I, node_hash: EntryHash) -> TimeIndexResult<Vec<EntryHash>>
+    where I: AsRef<str>,
+{
+    let mut children = get_links(
+        node_hash,
+        LinkTypes::TimeIndex,
+        Some(link_prefix_for_index(index_name)),
+    )?;
+
+    children.sort_by_cached_key(|link| chronological_sort_key(&link.tag));
+
+    Ok(children.into_iter()
+        .filter_map(|link| link.target.into_entry_hash())
+        .collect())
+}
+
+/// Locate the targets of all child links of the node with hash `node_hash` along with
+/// the times encoded in their link tags, ordered from oldest to newest.
+///
+fn get_timed_child_hashes_of_node<I>(index_name: &I, node_hash: EntryHash) -> TimeIndexResult<Vec<(DateTime<Utc>, EntryHash)>>
+    where I: AsRef<str>,
+{
+    let links = get_links(
+        node_hash,
+        LinkTypes::TimeIndex,
+        Some(link_prefix_for_index(index_name)),
+    )?;
+
+    let mut children = vec![];
+    for link in links {
+        let segment: IndexSegment = link.tag.to_owned().try_into()?;
+        if let Some(target) = link.target.into_entry_hash() {
+            children.push((segment.into(), target));
+        }
+    }
+    children.sort_by(|a: &(DateTime<Utc>, EntryHash), b| a.0.cmp(&b.0));
+
+    Ok(children)
+}
+
+/// Determine a key for ordering sibling links in a time index chronologically.
+///
+/// Segment strings sort lexically, with the exception of the millisecond offsets
+/// appended to chunk segments, which must be compared numerically.
+///
+pub fn chronological_sort_key(tag: &LinkTag) -> (Vec<u8>, u64) {
+    let bytes = tag.as_ref();
+    match bytes.iter().position(|byte| *byte == b'|') {
+        None => (bytes.to_vec(), 0),
+        Some(pos) => (
+            bytes[..pos].to_vec(),
+            String::from_utf8_lossy(&bytes[pos + 1..]).parse().unwrap_or(0),
+        ),
+    }
+}
+
 /// Locate all the child links of the node with hash `leaf_hash` which are older than `this_timestamp`,
 /// ordered from newest to oldest.
 ///
@@ -12,6 +12,11 @@ use hdk_semantic_indexes_core::LinkTypes;
 ///
 /// The entry must already exist and have been written to the local DHT.
 ///
+/// A concurrent call may index the same entry between the existence check and the write. Where the
+/// competing write is visible once ours completes, only the write with the lowest `ActionHash` is
+/// kept, so that every writer agrees on which to remove; ours is then deleted and `AlreadyIndexed`
+/// returned. Duplicates which are not yet visible to either writer are skipped by readers.
+///
 pub fn index_entry<I>(index_name: &I, entry_hash: EntryHash, time: DateTime<Utc>) -> TimeIndexResult<()>
     where I: AsRef<str>,
 {
@@ -30,14 +35,109 @@ pub fn index_entry<I>(index_name: &I, entry_hash: EntryHash, time: DateTime<Utc>
     let encoded_link_tag = target_entry_segment.tag_for_index(&index_name);
 
     // link from the leaf index to the target entry
-    create_link(leafmost_hash.to_owned(), entry_hash.to_owned(), LinkTypes::TimeIndex, encoded_link_tag.to_owned())?;
+    let forward_link = create_link(leafmost_hash.to_owned(), entry_hash.to_owned(), LinkTypes::TimeIndex, encoded_link_tag.to_owned())?;
 
     // link reciprocally from the target entry back to the leaf index node
-    create_link(entry_hash, leafmost_hash, LinkTypes::TimeIndex, encoded_link_tag)?;
+    let reciprocal_link = create_link(entry_hash.to_owned(), leafmost_hash, LinkTypes::TimeIndex, encoded_link_tag)?;
+
+    // back out of any race with a concurrent write of the same entry
+    let indexed = get_links(entry_hash.to_owned(), LinkTypes::TimeIndex, Some(link_prefix_for_index(index_name)))?;
+    let competing: Vec<ActionHash> = indexed.into_iter().map(|link| link.create_link_hash).collect();
+    if superseded_by_concurrent_write(&reciprocal_link, competing.as_slice()) {
+        delete_link(forward_link)?;
+        delete_link(reciprocal_link)?;
+        return Err(TimeIndexingError::AlreadyIndexed(index_name.as_ref().to_owned(), entry_hash));
+    }
 
     Ok(())
 }
 
+/// Determine whether the reciprocal index link `own` should be removed in favour of one of the
+/// `indexed` links found for the same entry, which include `own` itself. The link with the lowest
+/// `ActionHash` is kept, so that concurrent writers observing the same links agree on the outcome.
+///
+pub fn superseded_by_concurrent_write(own: &ActionHash, indexed: &[ActionHash]) -> bool {
+    indexed.iter().any(|other| other.get_raw_39() < own.get_raw_39())
+}
+
+/// Remove an entry with hash `entry_hash` from the time-ordered index identified by `index_name`.
+///
+/// The links between the entry and its leafmost index node are deleted in both directions.
+/// Index tree nodes themselves are left in place. Returns `false` if the entry was not indexed.
+///
+pub fn remove_entry<I>(index_name: &I, entry_hash: EntryHash) -> TimeIndexResult<bool>
+    where I: AsRef<str>,
+{
+    let prefix = link_prefix_for_index(index_name);
+    let leaf_links = get_links(entry_hash.to_owned(), LinkTypes::TimeIndex, Some(prefix))?;
+    if leaf_links.len() == 0 {
+        return Ok(false);
+    }
+
+    for leaf_link in leaf_links {
+        // remove the reciprocal link from the leaf index node to the entry
+        if let Some(leaf_hash) = leaf_link.target.to_owned().into_entry_hash() {
+            for entry_link in get_links(leaf_hash, LinkTypes::TimeIndex, Some(leaf_link.tag.to_owned()))? {
+                if entry_link.target.to_owned().into_entry_hash() == Some(entry_hash.to_owned()) {
+                    delete_link(entry_link.create_link_hash)?;
+                }
+            }
+        }
+        delete_link(leaf_link.create_link_hash)?;
+    }
+
+    Ok(true)
+}
+
+/// Remove many entries from the time-ordered index identified by `index_name` at once.
+///
+/// Entries are grouped by the leafmost index node they are linked from, so that each
+/// leaf node is only visited once regardless of how many of its entries are removed.
+/// Entries which are not indexed are ignored. Remaining entries retain their ordering,
+/// since only the links for removed entries are deleted.
+///
+/// Returns the hashes of all entries which were removed.
+///
+pub fn remove_entries<I>(index_name: &I, entry_hashes: &[EntryHash]) -> TimeIndexResult<Vec<EntryHash>>
+    where I: AsRef<str>,
+{
+    let prefix = link_prefix_for_index(index_name);
+    let mut removed: Vec<EntryHash> = vec![];
+    // (leaf node hash, leaf link tag, entries to remove from the leaf)
+    let mut leaves: Vec<(EntryHash, LinkTag, Vec<EntryHash>)> = vec![];
+
+    for entry_hash in entry_hashes {
+        let leaf_links = get_links(entry_hash.to_owned(), LinkTypes::TimeIndex, Some(prefix.to_owned()))?;
+        if leaf_links.len() == 0 {
+            continue;
+        }
+
+        for leaf_link in leaf_links {
+            if let Some(leaf_hash) = leaf_link.target.to_owned().into_entry_hash() {
+                match leaves.iter_mut().find(|(hash, tag, _)| *hash == leaf_hash && *tag == leaf_link.tag) {
+                    Some((_, _, entries)) => entries.push(entry_hash.to_owned()),
+                    None => leaves.push((leaf_hash, leaf_link.tag.to_owned(), vec![entry_hash.to_owned()])),
+                }
+            }
+            delete_link(leaf_link.create_link_hash)?;
+        }
+        removed.push(entry_hash.to_owned());
+    }
+
+    // remove reciprocal links from each affected leaf index node in a single read
+    for (leaf_hash, tag, entries) in leaves {
+        for entry_link in get_links(leaf_hash, LinkTypes::TimeIndex, Some(tag))? {
+            if let Some(target) = entry_link.target.to_owned().into_entry_hash() {
+                if entries.contains(&target) {
+                    delete_link(entry_link.create_link_hash)?;
+                }
+            }
+        }
+    }
+
+    Ok(removed)
+}
+
 /// Returns the leaf-most `IndexSegment` in the time tree, so that target entries can be
 /// linked from it.
 ///
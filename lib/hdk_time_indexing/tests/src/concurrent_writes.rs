@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod concurrent_writes {
+    use std::collections::HashMap;
+    use ::fixt::prelude::*;
+    use hdk::prelude::{ActionHash, EntryHash};
+    use holo_hash::fixt::{ActionHashFixturator, EntryHashFixturator};
+
+    use hdk_time_indexing::reading::{collect_oldest_leaf_hashes, dedup_entry_hashes};
+    use hdk_time_indexing::writing::superseded_by_concurrent_write;
+
+    // this test demonstrates that two writers racing to index the same entry agree on
+    // which of their writes to keep, regardless of which of them performs the check
+    #[test]
+    fn racing_writers_keep_exactly_one_write() {
+        let writes: Vec<ActionHash> = (0..2).map(|_| fixt!(ActionHash)).collect();
+
+        let removed: Vec<bool> = writes.iter()
+            .map(|own| superseded_by_concurrent_write(own, writes.as_slice()))
+            .collect();
+        assert_eq!(removed.iter().filter(|r| **r).count(), 1, "exactly one of the racing writes is removed");
+
+        // a writer which cannot yet see the competing write keeps its own
+        for own in writes.iter() {
+            assert!(!superseded_by_concurrent_write(own, &[own.to_owned()]));
+            assert!(!superseded_by_concurrent_write(own, &[]));
+        }
+    }
+
+    // this test demonstrates that entries indexed twice by racing writes which were not
+    // backed out are only returned once, and do not count twice towards the page size
+    #[test]
+    fn duplicate_entries_skipped_when_reading() {
+        let [root, day_1, day_2] = [(); 3].map(|_| fixt!(EntryHash));
+        let entries: Vec<EntryHash> = (0..3).map(|_| fixt!(EntryHash)).collect();
+
+        let mut tree: HashMap<EntryHash, Vec<EntryHash>> = HashMap::new();
+        tree.insert(root.clone(), vec![day_1.clone(), day_2.clone()]);
+        tree.insert(day_1.clone(), vec![entries[0].clone(), entries[1].clone(), entries[0].clone()]);
+        tree.insert(day_2.clone(), vec![entries[1].clone(), entries[2].clone()]);
+        let read_children = |node: EntryHash| Ok(tree.get(&node).cloned().unwrap_or_default());
+
+        let result = collect_oldest_leaf_hashes(root.clone(), 1, 0, 3, &read_children).unwrap();
+        assert_eq!(result, entries, "page filled with distinct entries");
+
+        let result = collect_oldest_leaf_hashes(root, 1, 0, 2, &read_children).unwrap();
+        assert_eq!(result, entries[0..2].to_vec());
+
+        let hashes = vec![entries[1].clone(), entries[0].clone(), entries[1].clone()];
+        assert_eq!(dedup_entry_hashes(hashes), vec![entries[1].clone(), entries[0].clone()], "first occurrence kept");
+    }
+}
@@ -3,3 +3,6 @@ mod helper_macros;
 
 mod empty_indexes;
 mod data_structures;
+mod oldest_entries;
+mod range_entries;
+mod concurrent_writes;
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod oldest_entries {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use ::fixt::prelude::*;
+    use hdk::prelude::{EntryHash, LinkTag};
+    use holo_hash::fixt::EntryHashFixturator;
+
+    use hdk_time_indexing::reading::{collect_oldest_leaf_hashes, chronological_sort_key};
+
+    // this test demonstrates that reading from the oldest end of an index only
+    // visits as many nodes as are needed to fill the requested page
+    #[test]
+    fn read_oldest_entries_stops_at_limit() {
+        let [root, year_1, year_2, day_1, day_2, day_3] = [(); 6].map(|_| fixt!(EntryHash));
+        let entries: Vec<EntryHash> = (0..4).map(|_| fixt!(EntryHash)).collect();
+
+        // children of each node, oldest first
+        let mut tree: HashMap<EntryHash, Vec<EntryHash>> = HashMap::new();
+        tree.insert(root.clone(), vec![year_1.clone(), year_2.clone()]);
+        tree.insert(year_1.clone(), vec![day_1.clone(), day_2.clone()]);
+        tree.insert(year_2.clone(), vec![day_3.clone()]);
+        tree.insert(day_1.clone(), vec![entries[0].clone(), entries[1].clone()]);
+        tree.insert(day_2.clone(), vec![entries[2].clone()]);
+        tree.insert(day_3.clone(), vec![entries[3].clone()]);
+
+        let visited = RefCell::new(vec![]);
+        let read_children = |node: EntryHash| {
+            visited.borrow_mut().push(node.clone());
+            Ok(tree.get(&node).cloned().unwrap_or_default())
+        };
+
+        let result = collect_oldest_leaf_hashes(root.clone(), 2, 0, 3, &read_children).unwrap();
+        assert_eq!(result, entries[0..3].to_vec());
+        assert_eq!(*visited.borrow(), vec![root.clone(), year_1, day_1, day_2]);
+        assert!(!visited.borrow().contains(&year_2), "newer branches are not read");
+
+        visited.borrow_mut().clear();
+        let result = collect_oldest_leaf_hashes(root.clone(), 2, 0, 10, &read_children).unwrap();
+        assert_eq!(result, entries, "whole index returned in order when under limit");
+        assert_eq!(visited.borrow().len(), 6);
+
+        let result = collect_oldest_leaf_hashes(root, 2, 0, 0, &read_children).unwrap();
+        assert_eq!(result, vec![]);
+    }
+
+    #[test]
+    fn chunk_segments_sort_numerically() {
+        let tag = |s: &str| LinkTag::new([b"idx".as_ref(), &[0x0], s.as_bytes()].concat());
+        let mut tags = vec![
+            tag("2020-04-13T03:36|120000"),
+            tag("2020-04-13T03:36|30000"),
+            tag("2020-04-13T03:35|90000"),
+        ];
+        tags.sort_by_cached_key(chronological_sort_key);
+        assert_eq!(tags, vec![
+            tag("2020-04-13T03:35|90000"),
+            tag("2020-04-13T03:36|30000"),
+            tag("2020-04-13T03:36|120000"),
+        ]);
+    }
+}
@@ -0,0 +1,55 @@
+#[cfg(test)]
+mod range_entries {
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use chrono::{DateTime, TimeZone, Utc};
+    use ::fixt::prelude::*;
+    use hdk::prelude::EntryHash;
+    use holo_hash::fixt::EntryHashFixturator;
+
+    use hdk_time_indexing::reading::collect_leaf_hashes_between;
+
+    fn at(m: u32, d: u32, h: u32) -> DateTime<Utc> {
+        Utc.ymd(2024, m, d).and_hms(h, 0, 0)
+    }
+
+    fn day(m: u32, d: u32) -> DateTime<Utc> {
+        at(m, d, 0)
+    }
+
+    // this test demonstrates that reading a time range only visits the branches of the
+    // index overlapping it, and that entries either side of each boundary are excluded
+    #[test]
+    fn read_entries_between_prunes_out_of_range_branches() {
+        let [root, month_1, month_2, month_3, day_1, day_2, day_3, day_4] = [(); 8].map(|_| fixt!(EntryHash));
+        let entries: Vec<EntryHash> = (0..6).map(|_| fixt!(EntryHash)).collect();
+
+        // children of each node with the times they begin at, oldest first
+        let mut tree: HashMap<EntryHash, Vec<(DateTime<Utc>, EntryHash)>> = HashMap::new();
+        tree.insert(root.clone(), vec![(day(1, 1), month_1.clone()), (day(2, 1), month_2.clone()), (day(3, 1), month_3.clone())]);
+        tree.insert(month_1.clone(), vec![(day(1, 20), day_1.clone())]);
+        tree.insert(month_2.clone(), vec![(day(2, 1), day_2.clone()), (day(2, 28), day_3.clone())]);
+        tree.insert(month_3.clone(), vec![(day(3, 1), day_4.clone())]);
+        tree.insert(day_1.clone(), vec![(day(1, 20), entries[0].clone())]);
+        tree.insert(day_2.clone(), vec![(day(2, 1), entries[1].clone()), (at(2, 1, 12), entries[2].clone())]);
+        tree.insert(day_3.clone(), vec![(at(2, 28, 23), entries[3].clone())]);
+        tree.insert(day_4.clone(), vec![(day(3, 1), entries[4].clone()), (at(3, 1, 1), entries[5].clone())]);
+
+        let visited = RefCell::new(vec![]);
+        let read_children = |node: EntryHash| {
+            visited.borrow_mut().push(node.clone());
+            Ok(tree.get(&node).cloned().unwrap_or_default())
+        };
+
+        let result = collect_leaf_hashes_between(root.clone(), 2, 0, &at(2, 1, 6), &day(3, 1), &read_children).unwrap();
+        assert_eq!(result, entries[2..4].to_vec(), "start inclusive and end exclusive");
+        assert!(!visited.borrow().contains(&month_1), "earlier branches are not read");
+        assert!(!visited.borrow().contains(&month_3), "later branches are not read");
+
+        let result = collect_leaf_hashes_between(root.clone(), 2, 0, &day(1, 1), &day(4, 1), &read_children).unwrap();
+        assert_eq!(result, entries, "whole index returned in order when spanned by range");
+
+        let result = collect_leaf_hashes_between(root, 2, 0, &day(4, 1), &day(5, 1), &read_children).unwrap();
+        assert_eq!(result, vec![]);
+    }
+}
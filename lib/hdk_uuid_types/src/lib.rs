@@ -59,8 +59,12 @@ macro_rules! simple_alias {
 
 /// Supertrait to bind all dependent traits that implement unique identifier behaviours.
 ///
+/// Equality, ordering and hashing are keyed on both the `DnaHash` and the DNA-local identifier,
+/// such that identifiers from different DNAs never compare equal. Prefer using addresses directly
+/// as set & map keys over extracting the local identifier.
+///
 pub trait DnaAddressable<B>
-    where Self: Clone + Eq + std::hash::Hash
+    where Self: Clone + Eq + Ord + std::hash::Hash
             + Debug + std::fmt::Display + serde::Serialize
             + AsRef<DnaHash> + AsRef<B>,
         B: Clone,
@@ -80,7 +84,8 @@ pub trait DnaAddressable<B>
 macro_rules! addressable_identifier {
     ($r:ident => $base:ty) => {
         // externally facing type, with DnaHash of cell for universally-unique context
-        #[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq, Eq, Hash)]
+        // :NOTE: field order determines ordering, which is by DNA and then by local identifier
+        #[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
         pub struct $r(pub DnaHash, pub $base);
 
         // constructor
@@ -120,7 +125,7 @@ macro_rules! addressable_identifier {
 /// Supertrait for things which can be identified by some string label in a particular DNA
 ///
 pub trait DnaIdentifiable<B>
-    where Self: Clone + Debug + Eq + std::hash::Hash + AsRef<DnaHash> + AsRef<B>,
+    where Self: Clone + Debug + Eq + Ord + std::hash::Hash + AsRef<DnaHash> + AsRef<B>,
         B: Clone + AsRef<str> + std::fmt::Display,
 {
     fn new(dna: DnaHash, identifier: B) -> Self;
@@ -134,7 +139,7 @@ pub trait DnaIdentifiable<B>
 macro_rules! dna_scoped_string {
     ($r:ident) => {
         // externally facing type, with DnaHash of cell for context
-        #[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq, Eq, Hash)]
+        #[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
         pub struct $r(pub DnaHash, pub String);
 
         // constructor
@@ -190,4 +195,55 @@ mod tests {
 
         assert_eq!(base, extracted, "Original data matches wrapped, externalised, extracted roundtrip data");
     }
+
+    addressable_identifier!(TestAddress => EntryHash);
+    dna_scoped_string!(TestId);
+
+    fn dna(byte: u8) -> DnaHash {
+        DnaHash::from_raw_36(vec![byte; HOLO_HASH_UNTYPED_LEN])
+    }
+
+    fn entry(byte: u8) -> EntryHash {
+        EntryHash::from_raw_36(vec![byte; HOLO_HASH_UNTYPED_LEN])
+    }
+
+    #[test]
+    fn test_same_hash_in_distinct_dnas_is_distinct() {
+        for e in (0..=255u8).step_by(15) {
+            for (d1, d2) in (0..=254u8).step_by(17).map(|d| (d, d + 1)) {
+                let a = TestAddress(dna(d1), entry(e));
+                let b = TestAddress(dna(d2), entry(e));
+                assert_ne!(a, b);
+                assert_ne!(a.cmp(&b), std::cmp::Ordering::Equal);
+                assert_eq!(a.cmp(&b), b.cmp(&a).reverse(), "ordering is antisymmetric");
+                assert_eq!(vec![a.clone(), b.clone()].into_iter().collect::<std::collections::HashSet<_>>().len(), 2);
+                assert_eq!(vec![a.clone(), b.clone()].into_iter().collect::<std::collections::BTreeSet<_>>().len(), 2);
+                assert_eq!(a, TestAddress(dna(d1), entry(e)), "equal components compare equal");
+
+                let s1 = TestId(dna(d1), format!("id-{}", e));
+                let s2 = TestId(dna(d2), format!("id-{}", e));
+                assert_ne!(s1, s2);
+                assert_eq!(vec![s1, s2].into_iter().collect::<std::collections::BTreeSet<_>>().len(), 2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ordering_consistent_with_equality() {
+        let addresses: Vec<TestAddress> = (0..8u8)
+            .flat_map(|d| (0..8u8).map(move |e| TestAddress(dna(d * 31), entry(e * 29))))
+            .collect();
+        for a in addresses.iter() {
+            for b in addresses.iter() {
+                assert_eq!(a == b, a.cmp(b) == std::cmp::Ordering::Equal);
+                if a.0 != b.0 {
+                    assert_eq!(a.cmp(b), a.0.cmp(&b.0), "addresses are ordered by DNA first");
+                }
+            }
+        }
+        let mut sorted = addresses.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), addresses.len());
+    }
 }
@@ -7,6 +7,7 @@
 use serde::{Deserializer, Serializer, de::Error};
 
 use hdk::prelude::*;
+use hdk_records::{ RecordAPIResult, DataIntegrityError };
 use vf_attributes_hdk::{ ActionId, ProcessAddress, EconomicResourceAddress };
 
 pub mod builtins;
@@ -131,6 +132,46 @@ pub fn validate_flow_action(action_id: ActionId, input_process: Option<ProcessAd
     }
 }
 
+/// Alternative spellings accepted for builtin actions, mapped to their canonical IDs
+const ACTION_ALIASES: &[(&str, &str)] = &[
+    ("drop-off", "dropoff"),
+    ("pick-up", "pickup"),
+    ("deliver", "deliver-service"),
+    ("transfer-rights", "transfer-all-rights"),
+];
+
+/**
+ * Canonicalize a user-provided action identifier to its ValueFlows vocabulary ID.
+ *
+ * Matching ignores case and surrounding whitespace, and treats camelCase, underscores
+ * and spaces as equivalent to the hyphenated form (eg. `Transfer_All_Rights` and
+ * `transferAllRights` both resolve to `transfer-all-rights`). Unknown actions are rejected.
+ */
+pub fn normalize_action(input: &str) -> RecordAPIResult<ActionId> {
+    let mut key = String::with_capacity(input.len() + 4);
+    let mut prev_lower = false;
+    for c in input.trim().chars() {
+        if c.is_uppercase() && prev_lower {
+            key.push('-');
+        }
+        prev_lower = c.is_lowercase() || c.is_numeric();
+        match c {
+            '_' | ' ' => key.push('-'),
+            _ => key.extend(c.to_lowercase()),
+        }
+    }
+    let key = ACTION_ALIASES.iter()
+        .find(|(alias, _)| *alias == key)
+        .map(|(_, canonical)| canonical.to_string())
+        .unwrap_or(key);
+
+    match get_builtin_action(&key) {
+        // builtin lookups also accept underscored keys, so ensure the stored ID is the canonical one
+        Some(action) => Ok(ActionId::from(action.id)),
+        None => Err(DataIntegrityError::UnknownAction(input.to_string())),
+    }
+}
+
 pub fn validate_move_inventories(resouce_inventoried_as: Option<EconomicResourceAddress>, to_resource_inventoried_as: Option<EconomicResourceAddress>) -> Result<(), String> {
     match resouce_inventoried_as {
         Some(_) => match to_resource_inventoried_as {
@@ -143,3 +184,25 @@ pub fn validate_move_inventories(resouce_inventoried_as: Option<EconomicResource
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_action() {
+        assert_eq!(normalize_action("transfer-all-rights").unwrap(), ActionId::from("transfer-all-rights".to_string()));
+        assert_eq!(normalize_action(" Transfer_All_Rights ").unwrap(), ActionId::from("transfer-all-rights".to_string()));
+        assert_eq!(normalize_action("transferAllRights").unwrap(), ActionId::from("transfer-all-rights".to_string()));
+        assert_eq!(normalize_action("CONSUME").unwrap(), ActionId::from("consume".to_string()));
+        assert_eq!(normalize_action("Drop-Off").unwrap(), ActionId::from("dropoff".to_string()));
+        assert_eq!(normalize_action("deliver").unwrap(), ActionId::from("deliver-service".to_string()));
+    }
+
+    #[test]
+    fn test_normalize_unknown_action() {
+        assert!(matches!(normalize_action("teleport"), Err(DataIntegrityError::UnknownAction(a)) if a == "teleport"));
+        assert!(normalize_action("").is_err());
+        assert!(normalize_action("transfer--all-rights").is_err());
+    }
+}
@@ -1,17 +1,113 @@
 use hdk_uuid_types::*;
+use hdk_records::validation::EntryValue;
 
 // re-exports for convenience
 pub use chrono::{ FixedOffset, Utc, DateTime };
-pub use holo_hash::{ AgentPubKey, EntryHash, ActionHash };
+pub use holo_hash::{ AgentPubKey, EntryHash, ActionHash, DnaHash };
 pub use holochain_zome_types::timestamp::Timestamp;
 pub use hdk_uuid_types::{DnaAddressable, DnaIdentifiable};
-pub use hdk_semantic_indexes_zome_rpc::{ByAction, ByAddress, ByRevision};
-pub use hdk_records::{RecordMeta, RevisionMeta};
+pub use hdk_semantic_indexes_zome_rpc::{ByAction, ByAddress, ByAddresses, ByAddressResult, ByRevision, ReindexParams, IndexedAddresses, ErrorCode, ErrorEnvelope};
+pub use hdk_records::{ValidationHook, RecordMeta, RevisionMeta, PatchOp, PatchParams, DeleteParams, DeletionReport, FieldChange, RevisionDiffParams, RevisionHistoryParams, RevisionHistory, HistoricalRevision, RevisionEdit, RecordBundle, NameIndexParams, SlugParams, SlugResolution, IntegrityCheckParams, IntegrityReport, ReferenceStatus, RecordRole, RecordProjection};
+pub use hdk_records::snake_case_alias_tests;
 
 simple_alias!(ActionId => String);
 
 simple_alias!(ExternalURL => String);
 
+/// Upper bound on the length of any single `ExternalURL`
+pub const MAX_EXTERNAL_URL_LENGTH: usize = 2048;
+
+/// Default upper bound on the number of `ExternalURL`s held by list fields such as `images`
+pub const DEFAULT_MAX_EXTERNAL_URL_LIST_LENGTH: usize = 32;
+
+// DNA properties wrapper for reading the configured limit under the `max_external_url_list_length` key
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone)]
+struct ExternalURLListProperties {
+    #[serde(default)]
+    pub max_external_url_list_length: Option<usize>,
+}
+
+/// Read the maximum number of `ExternalURL`s held by list fields in the current DNA from its
+/// `max_external_url_list_length` property. If not configured, `DEFAULT_MAX_EXTERNAL_URL_LIST_LENGTH` is used.
+///
+pub fn read_max_external_url_list_length() -> usize {
+    let props: Option<ExternalURLListProperties> = dna_info().ok()
+        .and_then(|info| info.modifiers.properties.try_into().ok());
+    props.and_then(|p| p.max_external_url_list_length).unwrap_or(DEFAULT_MAX_EXTERNAL_URL_LIST_LENGTH)
+}
+
+impl ExternalURL {
+    /// Check that this value looks like an absolute URI (`scheme:rest`), is not overly long
+    /// and contains no whitespace or control characters.
+    ///
+    pub fn validate(&self) -> Result<(), String> {
+        let url = self.0.as_str();
+        if url.len() > MAX_EXTERNAL_URL_LENGTH {
+            return Err(format!("URL exceeds maximum length of {} characters", MAX_EXTERNAL_URL_LENGTH));
+        }
+        if url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(format!("URL {} contains invalid characters", url));
+        }
+        match url.split_once(':') {
+            Some((scheme, rest)) if !rest.is_empty()
+                && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme.chars().all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+                => Ok(()),
+            _ => Err(format!("{} is not a valid absolute URL", url)),
+        }
+    }
+}
+
+/// Validate a list of `ExternalURL`s for the named field, holding at most `max_length` entries.
+///
+pub fn validate_external_url_list(field_name: &str, urls: &Option<Vec<ExternalURL>>, max_length: usize) -> Result<(), String> {
+    match urls {
+        None => Ok(()),
+        Some(list) => {
+            if list.len() > max_length {
+                return Err(format!("{} may contain at most {} entries", field_name, max_length));
+            }
+            list.iter().try_for_each(|u| u.validate())
+        },
+    }
+}
+
+/// Map a list of classification `ExternalURL`s to the identifiers it is indexed under.
+///
+/// Shared by the classification indexes of all record types, so that the same URL is
+/// matched by each of them.
+///
+pub fn classification_index_paths(urls: &Option<Vec<ExternalURL>>) -> Vec<String> {
+    urls.iter().flatten().map(|url| url.as_ref().to_string()).collect()
+}
+
+/// Upper bound on the length of `note` fields
+pub const MAX_NOTE_LENGTH: usize = 10000;
+
+/// Validator for record types with a `note` field, enforcing `MAX_NOTE_LENGTH`
+pub const VALIDATE_NOTE_LENGTH: ValidationHook = ValidationHook { name: "note_length", check: validate_note_length };
+
+/// Validator for record types with `image` and / or `images` fields, @see `ExternalURL::validate`
+pub const VALIDATE_EXTERNAL_URLS: ValidationHook = ValidationHook { name: "external_urls", check: validate_external_url_fields };
+
+fn validate_note_length(entry: &EntryValue) -> Result<(), String> {
+    match entry.get("note").and_then(|n| n.as_str()) {
+        Some(note) if note.chars().count() > MAX_NOTE_LENGTH =>
+            Err(format!("note exceeds maximum length of {} characters", MAX_NOTE_LENGTH)),
+        _ => Ok(()),
+    }
+}
+
+fn validate_external_url_fields(entry: &EntryValue) -> Result<(), String> {
+    if let Some(image) = entry.get("image").and_then(|i| i.as_str()) {
+        ExternalURL(image.to_string()).validate()?;
+    }
+    let images: Option<Vec<ExternalURL>> = entry.get("images")
+        .and_then(|i| i.as_array())
+        .map(|list| list.iter().filter_map(|i| i.as_str()).map(|i| ExternalURL(i.to_string())).collect());
+    validate_external_url_list("images", &images, read_max_external_url_list_length())
+}
+
 addressable_identifier!(LocationAddress => EntryHash);
 
 dna_scoped_string!(UnitId);
@@ -39,4 +135,246 @@ addressable_identifier!(ProposedIntentAddress => EntryHash);
 addressable_identifier!(ProposalAddress => EntryHash);
 addressable_identifier!(ProposedToAddress => EntryHash);
 
-addressable_identifier!(EventOrCommitmentAddress => EntryHash);
+/// Generate `RecordRef` from the list of record types it may reference.
+///
+macro_rules! record_ref {
+    ($($variant:ident($address:ident)),+ $(,)?) => {
+        /// Reference to a record of one of several types, for polymorphic fields such as
+        /// `Satisfaction.satisfied_by`.
+        ///
+        /// Encoded as a pair of the record type name and its identifier, eg.
+        /// `["EconomicEvent", <EconomicEventAddress>]`. Type names match GraphQL `__typename`s.
+        ///
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub enum RecordRef {
+            $($variant($address)),+
+        }
+
+        impl RecordRef {
+            /// All record type names which a `RecordRef` may carry
+            pub const RECORD_TYPES: &'static [&'static str] = &[$(stringify!($variant)),+];
+
+            /// Name of the type of the referenced record
+            pub fn record_type(&self) -> &'static str {
+                match self {
+                    $(RecordRef::$variant(_) => stringify!($variant)),+
+                }
+            }
+        }
+
+        impl AsRef<DnaHash> for RecordRef {
+            fn as_ref(&self) -> &DnaHash {
+                match self {
+                    $(RecordRef::$variant(address) => address.as_ref()),+
+                }
+            }
+        }
+
+        impl AsRef<EntryHash> for RecordRef {
+            fn as_ref(&self) -> &EntryHash {
+                match self {
+                    $(RecordRef::$variant(address) => address.as_ref()),+
+                }
+            }
+        }
+
+        $(
+            impl From<$address> for RecordRef {
+                fn from(address: $address) -> RecordRef {
+                    RecordRef::$variant(address)
+                }
+            }
+        )+
+
+        impl serde::Serialize for RecordRef {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+                where S: serde::Serializer,
+            {
+                use serde::ser::SerializeTuple;
+                let mut pair = serializer.serialize_tuple(2)?;
+                pair.serialize_element(self.record_type())?;
+                match self {
+                    $(RecordRef::$variant(address) => pair.serialize_element(address)?),+
+                };
+                pair.end()
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for RecordRef {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+                where D: serde::Deserializer<'de>,
+            {
+                struct RecordRefVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for RecordRefVisitor {
+                    type Value = RecordRef;
+
+                    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        f.write_str("a pair of record type name and record identifier")
+                    }
+
+                    fn visit_seq<A>(self, mut seq: A) -> Result<RecordRef, A::Error>
+                        where A: serde::de::SeqAccess<'de>,
+                    {
+                        use serde::de::Error;
+                        let record_type: String = seq.next_element()?
+                            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+                        match record_type.as_str() {
+                            $(stringify!($variant) => Ok(RecordRef::$variant(
+                                seq.next_element()?.ok_or_else(|| A::Error::invalid_length(1, &self))?
+                            )),)+
+                            unknown => Err(A::Error::custom(format!(
+                                "unknown record type '{}' in record reference, expected one of: {}",
+                                unknown, RecordRef::RECORD_TYPES.join(", "),
+                            ))),
+                        }
+                    }
+                }
+
+                deserializer.deserialize_tuple(2, RecordRefVisitor)
+            }
+        }
+
+        impl std::fmt::Display for RecordRef {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    $(RecordRef::$variant(address) => write!(f, "{}:{}", stringify!($variant), address)),+
+                }
+            }
+        }
+    }
+}
+
+record_ref!(
+    Agent(AgentAddress),
+    EconomicEvent(EconomicEventAddress),
+    EconomicResource(EconomicResourceAddress),
+    Process(ProcessAddress),
+    Commitment(CommitmentAddress),
+    Fulfillment(FulfillmentAddress),
+    Intent(IntentAddress),
+    Satisfaction(SatisfactionAddress),
+    Plan(PlanAddress),
+    Agreement(AgreementAddress),
+    ResourceSpecification(ResourceSpecificationAddress),
+    ProcessSpecification(ProcessSpecificationAddress),
+    Proposal(ProposalAddress),
+);
+
+/// Upper bound on the number of attachments held by a single record
+pub const MAX_ATTACHMENTS_PER_RECORD: usize = 16;
+
+/// Reference to a binary attachment stored in the DNA's `attachment` zome.
+///
+/// `hash` addresses the attachment's content, so identical uploads share a single
+/// stored copy. `mime` and `filename` describe its use by the referencing record.
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct AttachmentRef {
+    pub hash: EntryHash,
+    pub mime: String,
+    pub filename: String,
+    pub size: u64,
+}
+
+/// Validate a list of `AttachmentRef`s for the named field, enforcing `MAX_ATTACHMENTS_PER_RECORD`.
+///
+pub fn validate_attachment_list(field_name: &str, attachments: &Option<Vec<AttachmentRef>>) -> Result<(), String> {
+    match attachments {
+        None => Ok(()),
+        Some(list) => {
+            if list.len() > MAX_ATTACHMENTS_PER_RECORD {
+                return Err(format!("{} may contain at most {} entries", field_name, MAX_ATTACHMENTS_PER_RECORD));
+            }
+            match list.iter().find(|a| a.mime.trim().is_empty() || a.filename.trim().is_empty()) {
+                Some(a) => Err(format!("{} entry {} must have a MIME type and filename", field_name, a.hash)),
+                None => Ok(()),
+            }
+        },
+    }
+}
+
+/// Request to move a record's `ResourceSpecification` reference from `from` to `to`,
+/// as issued when merging duplicate specifications. Records which no longer conform
+/// to `from` are left untouched.
+///
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReassignSpecificationParams<A> {
+    pub address: A,
+    pub from: ResourceSpecificationAddress,
+    pub to: ResourceSpecificationAddress,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_external_url_validation() {
+        assert!(ExternalURL("https://example.com/a.png".into()).validate().is_ok());
+        assert!(ExternalURL("ipfs:QmHash".into()).validate().is_ok());
+        assert!(ExternalURL("example.com/a.png".into()).validate().is_err());
+        assert!(ExternalURL("https:".into()).validate().is_err());
+        assert!(ExternalURL("https://exa mple.com".into()).validate().is_err());
+        assert!(ExternalURL(format!("https://{}", "a".repeat(MAX_EXTERNAL_URL_LENGTH))).validate().is_err());
+    }
+
+    #[test]
+    fn test_external_url_list_cap() {
+        let ok = Some(vec![ExternalURL("https://example.com/a.png".into())]);
+        assert!(validate_external_url_list("images", &ok, DEFAULT_MAX_EXTERNAL_URL_LIST_LENGTH).is_ok());
+        assert!(validate_external_url_list("images", &None, DEFAULT_MAX_EXTERNAL_URL_LIST_LENGTH).is_ok());
+        let too_many = Some((0..=DEFAULT_MAX_EXTERNAL_URL_LIST_LENGTH).map(|i| ExternalURL(format!("https://example.com/{}.png", i))).collect());
+        assert!(validate_external_url_list("images", &too_many, DEFAULT_MAX_EXTERNAL_URL_LIST_LENGTH).is_err());
+        assert!(validate_external_url_list("images", &too_many, DEFAULT_MAX_EXTERNAL_URL_LIST_LENGTH + 1).is_ok(), "configured limit applied");
+    }
+
+    #[test]
+    fn test_attachment_list_validation() {
+        let attachment = |filename: &str| AttachmentRef {
+            hash: EntryHash::from_raw_36(vec![0xab; 36]),
+            mime: "image/jpeg".to_string(),
+            filename: filename.to_string(),
+            size: 1024,
+        };
+        assert!(validate_attachment_list("attachments", &None).is_ok());
+        assert!(validate_attachment_list("attachments", &Some(vec![attachment("receipt.jpg")])).is_ok());
+        assert!(validate_attachment_list("attachments", &Some(vec![attachment(" ")])).is_err());
+        let too_many = Some((0..=MAX_ATTACHMENTS_PER_RECORD).map(|_| attachment("receipt.jpg")).collect());
+        assert!(validate_attachment_list("attachments", &too_many).is_err());
+    }
+
+    #[test]
+    fn test_classification_index_paths() {
+        let urls = Some(vec![ExternalURL("https://example.com/tax#fruit".into()), ExternalURL("https://example.com/tax#veg".into())]);
+        assert_eq!(classification_index_paths(&urls), vec!["https://example.com/tax#fruit", "https://example.com/tax#veg"]);
+        assert!(classification_index_paths(&None).is_empty());
+    }
+
+    #[test]
+    fn test_record_ref_encoding() {
+        let address = CommitmentAddress(DnaHash::from_raw_36(vec![0xdb; 36]), EntryHash::from_raw_36(vec![0xab; 36]));
+        let reference: RecordRef = address.clone().into();
+        assert_eq!(reference.record_type(), "Commitment");
+
+        let encoded = holochain_serialized_bytes::encode(&reference).unwrap();
+        assert_eq!(encoded, holochain_serialized_bytes::encode(&("Commitment", &address)).unwrap(), "encoded as type name and identifier");
+        let decoded: RecordRef = holochain_serialized_bytes::decode(&encoded).unwrap();
+        assert_eq!(decoded, reference);
+
+        let unknown = holochain_serialized_bytes::encode(&("Scenario", &address)).unwrap();
+        match holochain_serialized_bytes::decode::<_, RecordRef>(&unknown) {
+            Err(e) => assert!(e.to_string().contains("unknown record type 'Scenario'"), "unknown type named in error: {}", e),
+            Ok(r) => panic!("unknown record type accepted as {:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_note_length_hook() {
+        let ok: EntryValue = vec![("note", "a".repeat(MAX_NOTE_LENGTH))].into_iter().collect();
+        assert!(validate_note_length(&ok).is_ok());
+        let too_long: EntryValue = vec![("note", "a".repeat(MAX_NOTE_LENGTH + 1))].into_iter().collect();
+        assert!(validate_note_length(&too_long).is_err());
+    }
+}
@@ -9,6 +9,43 @@ use vf_attributes_hdk::UnitId;
 use hdk_records::{RecordAPIResult, DataIntegrityError};
 use hdk::prelude::*;
 
+/// Remote permission ID for reading `Unit` records from the DNA hosting them.
+pub const UNIT_READ_API_METHOD: &str = "read_unit";
+
+/// Payload for `UNIT_READ_API_METHOD`.
+#[derive(Serialize, Debug)]
+pub struct UnitReadRequest {
+    pub id: UnitId,
+}
+
+/// Response type for `UNIT_READ_API_METHOD`. Only the existence of the record is of interest.
+pub type UnitReadResponse = serde::de::IgnoredAny;
+
+/// Remote permission ID for reading `ResourceSpecification` records from the DNA hosting them.
+pub const RESOURCE_SPECIFICATION_READ_API_METHOD: &str = "read_resource_specification";
+
+/// Payload for `RESOURCE_SPECIFICATION_READ_API_METHOD`.
+#[derive(Serialize, Debug)]
+pub struct ResourceSpecificationReadRequest<A> {
+    pub address: A,
+}
+
+/// Response type for `RESOURCE_SPECIFICATION_READ_API_METHOD`. Only the default units of the record are of interest.
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceSpecificationUnitsResponse {
+    pub resource_specification: ResourceSpecificationUnits,
+}
+
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceSpecificationUnits {
+    #[serde(default)]
+    pub default_unit_of_resource: Option<UnitId>,
+    #[serde(default)]
+    pub default_unit_of_effort: Option<UnitId>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Unit {
     pub id: UnitId,
@@ -16,13 +53,53 @@ pub struct Unit {
     pub symbol: Option<String>,
 }
 
-#[derive(Clone, PartialEq, Serialize, Deserialize, SerializedBytes, Debug)]
+/// Upper bound on `decimal_places`, beyond which `f64` cannot represent the requested precision.
+pub const MAX_DECIMAL_PLACES: u8 = 15;
+
+#[derive(Clone, PartialEq, Deserialize, SerializedBytes, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct QuantityValue {
     // :TODO: https://users.rust-lang.org/t/currency-in-rust/890/9 ?
     has_numerical_value: f64,     // :NOTE: uses https://en.wikipedia.org/wiki/IEEE_754 for math
     #[serde(default)]
     has_unit: Option<UnitId>,
+    // fixed-point precision; when set, values are rounded to this many decimals
+    #[serde(default, deserialize_with = "deserialize_decimal_places")]
+    decimal_places: Option<u8>,
+}
+
+// reject precisions beyond `MAX_DECIMAL_PLACES`, which could not be honoured when rounding
+fn deserialize_decimal_places<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error>
+    where D: serde::Deserializer<'de>,
+{
+    match Option::<u8>::deserialize(deserializer)? {
+        Some(places) if places > MAX_DECIMAL_PLACES => Err(serde::de::Error::custom(format!(
+            "decimal_places of {} exceeds the maximum of {}", places, MAX_DECIMAL_PLACES,
+        ))),
+        places => Ok(places),
+    }
+}
+
+// output format of `QuantityValue`, emitting values at their fixed-point precision
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct QuantityValueOutput<'a> {
+    has_numerical_value: f64,
+    has_unit: &'a Option<UnitId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    decimal_places: Option<u8>,
+}
+
+impl Serialize for QuantityValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: serde::Serializer,
+    {
+        QuantityValueOutput {
+            has_numerical_value: self.get_numerical_value(),
+            has_unit: &self.has_unit,
+            decimal_places: self.decimal_places,
+        }.serialize(serializer)
+    }
 }
 
 impl<'a> QuantityValue {
@@ -30,38 +107,200 @@ impl<'a> QuantityValue {
         QuantityValue {
             has_numerical_value,
             has_unit,
+            decimal_places: None,
+        }
+    }
+
+    /// Create a fixed-point quantity, rounded to `decimal_places`.
+    pub fn with_precision(has_numerical_value: f64, has_unit: Option<UnitId>, decimal_places: u8) -> QuantityValue {
+        let decimal_places = decimal_places.min(MAX_DECIMAL_PLACES);
+        QuantityValue {
+            has_numerical_value: round_to_precision(has_numerical_value, decimal_places),
+            has_unit,
+            decimal_places: Some(decimal_places),
         }
     }
 
     pub fn get_numerical_value(&'a self) -> f64 {
-        self.has_numerical_value.to_owned()
+        match self.decimal_places {
+            Some(places) => round_to_precision(self.has_numerical_value, places),
+            None => self.has_numerical_value.to_owned(),
+        }
     }
 
     pub fn get_unit(&'a self) -> Option<UnitId> {
         self.has_unit.to_owned()
     }
+
+    pub fn get_decimal_places(&'a self) -> Option<u8> {
+        self.decimal_places.to_owned()
+    }
+
+    /// Copy of this quantity expressed in `unit`, retaining its value and precision.
+    pub fn with_unit(&'a self, has_unit: Option<UnitId>) -> QuantityValue {
+        QuantityValue {
+            has_unit,
+            ..self.to_owned()
+        }
+    }
+
+    /// Whether this quantity is greater than `other` by more than the tolerance of their precision.
+    pub fn exceeds(&'a self, other: &QuantityValue) -> bool {
+        let epsilon = precision_epsilon(combined_precision(self, other));
+        self.get_numerical_value() - other.get_numerical_value() > epsilon
+    }
+
+    /// Whether this quantity and `other` are equal within the tolerance of their precision.
+    pub fn approx_eq(&'a self, other: &QuantityValue) -> bool {
+        let epsilon = precision_epsilon(combined_precision(self, other));
+        (self.get_numerical_value() - other.get_numerical_value()).abs() <= epsilon
+    }
+}
+
+/// Round `value` to `decimal_places` using banker's rounding (ties to even).
+///
+/// Ties are detected with a small relative tolerance, so that values such as `0.125`
+/// which are not exactly representable in binary are still treated as halfway cases.
+///
+pub fn round_to_precision(value: f64, decimal_places: u8) -> f64 {
+    let factor = 10f64.powi(decimal_places.min(MAX_DECIMAL_PLACES) as i32);
+    let scaled = value * factor;
+    let floor = scaled.floor();
+    let fraction = scaled - floor;
+    let tie_tolerance = f64::EPSILON * scaled.abs().max(1.0) * 4.0;
+
+    let rounded = if (fraction - 0.5).abs() <= tie_tolerance {
+        if floor % 2.0 == 0.0 { floor } else { floor + 1.0 }
+    } else {
+        scaled.round()
+    };
+    rounded / factor
+}
+
+/// Comparison tolerance for quantities of the given precision: half of their smallest unit.
+/// Quantities without a fixed precision are compared exactly.
+///
+pub fn precision_epsilon(decimal_places: Option<u8>) -> f64 {
+    match decimal_places {
+        Some(places) => 0.5 / 10f64.powi(places.min(MAX_DECIMAL_PLACES) as i32),
+        None => 0.0,
+    }
+}
+
+// precision of the result of combining two quantities, keeping the finer of the two
+fn combined_precision(q1: &QuantityValue, q2: &QuantityValue) -> Option<u8> {
+    match (q1.decimal_places, q2.decimal_places) {
+        (Some(p1), Some(p2)) => Some(p1.max(p2)),
+        (Some(p), None) | (None, Some(p)) => Some(p),
+        (None, None) => None,
+    }
+}
+
+fn with_combined_precision(value: f64, q1: QuantityValue, q2: &QuantityValue) -> QuantityValue {
+    match combined_precision(&q1, q2) {
+        Some(places) => QuantityValue::with_precision(value, q1.has_unit, places),
+        None => QuantityValue::new(value, q1.has_unit),
+    }
 }
 
 pub fn add(q1: QuantityValue, q2: QuantityValue) -> RecordAPIResult<QuantityValue> {
     if q1.has_unit != q2.has_unit {
         return Err(DataIntegrityError::MismatchingUnits(q1.get_unit().map(|unit| unit.1), q2.get_unit().map(|unit| unit.1)));
     }
-    Ok(
-        QuantityValue {
-            has_numerical_value: q1.has_numerical_value + q2.has_numerical_value,
-            has_unit: q1.has_unit,
-        }
-    )
+    let value = q1.get_numerical_value() + q2.get_numerical_value();
+    Ok(with_combined_precision(value, q1, &q2))
 }
 
 pub fn subtract(q1: QuantityValue, q2: QuantityValue) -> RecordAPIResult<QuantityValue> {
     if q1.has_unit != q2.has_unit {
         return Err(DataIntegrityError::MismatchingUnits(q1.get_unit().map(|unit| unit.1), q2.get_unit().map(|unit| unit.1)));
     }
-    Ok(
-        QuantityValue {
-            has_numerical_value: q1.has_numerical_value - q2.has_numerical_value,
-            has_unit: q1.has_unit,
+    let value = q1.get_numerical_value() - q2.get_numerical_value();
+    Ok(with_combined_precision(value, q1, &q2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bankers_rounding() {
+        assert_eq!(round_to_precision(0.125, 2), 0.12);
+        assert_eq!(round_to_precision(0.135, 2), 0.14);
+        assert_eq!(round_to_precision(2.5, 0), 2.0);
+        assert_eq!(round_to_precision(3.5, 0), 4.0);
+        assert_eq!(round_to_precision(-2.5, 0), -2.0);
+        assert_eq!(round_to_precision(0.1 + 0.2, 2), 0.3);
+        assert_eq!(round_to_precision(2.9999999999, 4), 3.0);
+    }
+
+    #[test]
+    fn test_accumulation_is_stable() {
+        let step = QuantityValue::with_precision(0.1, None, 2);
+        let mut total = QuantityValue::with_precision(0.0, None, 2);
+        for _ in 0..10_000 {
+            total = add(total, step.clone()).unwrap();
+        }
+        assert_eq!(total.get_numerical_value(), 1000.0);
+
+        for _ in 0..10_000 {
+            total = subtract(total, step.clone()).unwrap();
+        }
+        assert_eq!(total.get_numerical_value(), 0.0);
+
+        // without a fixed precision, floating point noise accumulates
+        let mut unrounded = QuantityValue::new(0.0, None);
+        for _ in 0..10_000 {
+            unrounded = add(unrounded, QuantityValue::new(0.1, None)).unwrap();
         }
-    )
+        assert_ne!(unrounded.get_numerical_value(), 1000.0);
+    }
+
+    #[test]
+    fn test_precision_combines_to_finest() {
+        let total = add(QuantityValue::with_precision(1.5, None, 1), QuantityValue::with_precision(0.25, None, 2)).unwrap();
+        assert_eq!(total.get_decimal_places(), Some(2));
+        assert_eq!(total.get_numerical_value(), 1.75);
+
+        let total = add(QuantityValue::new(1.0, None), QuantityValue::with_precision(0.25, None, 2)).unwrap();
+        assert_eq!(total.get_decimal_places(), Some(2));
+        assert_eq!(total.get_numerical_value(), 1.25);
+    }
+
+    #[test]
+    fn test_serialization_roundtrip() {
+        let noisy = QuantityValue { has_numerical_value: 0.1 + 0.2, has_unit: None, decimal_places: Some(2) };
+        let bytes = SerializedBytes::try_from(noisy).unwrap();
+        let decoded = QuantityValue::try_from(bytes).unwrap();
+        assert_eq!(decoded.has_numerical_value, 0.3);
+        assert_eq!(decoded.get_decimal_places(), Some(2));
+
+        let plain = QuantityValue::new(0.1 + 0.2, None);
+        let decoded = QuantityValue::try_from(SerializedBytes::try_from(plain.clone()).unwrap()).unwrap();
+        assert_eq!(decoded, plain);
+    }
+
+    #[test]
+    fn test_excessive_precision_rejected() {
+        let finest = QuantityValue { has_numerical_value: 1.0, has_unit: None, decimal_places: Some(MAX_DECIMAL_PLACES) };
+        assert!(QuantityValue::try_from(SerializedBytes::try_from(finest).unwrap()).is_ok());
+
+        let excessive = QuantityValue { has_numerical_value: 1.0, has_unit: None, decimal_places: Some(MAX_DECIMAL_PLACES + 1) };
+        assert!(QuantityValue::try_from(SerializedBytes::try_from(excessive).unwrap()).is_err(), "decimal_places beyond MAX_DECIMAL_PLACES rejected");
+    }
+
+    #[test]
+    fn test_comparisons_use_precision() {
+        let required = QuantityValue::with_precision(3.0, None, 2);
+        let available = QuantityValue::new(2.9999999999, None);
+        assert!(!required.exceeds(&available));
+        assert!(required.approx_eq(&available));
+
+        let available = QuantityValue::new(2.99, None);
+        assert!(required.exceeds(&available));
+
+        let exact = QuantityValue::new(3.0, None);
+        assert!(!exact.exceeds(&QuantityValue::new(3.0, None)));
+        assert!(exact.exceeds(&QuantityValue::new(2.9999999999, None)));
+    }
 }
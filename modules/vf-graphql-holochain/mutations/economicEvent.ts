@@ -5,7 +5,7 @@
  * @since:   2019-05-27
  */
 
-import { ByRevision, DNAIdMappings } from '../types.js'
+import { ByRevision, DNAIdMappings, DeletionReport } from '../types.js'
 import { mapZomeFn } from '../connection.js'
 import { deleteHandler } from './'
 
@@ -31,7 +31,7 @@ export type updateHandler = (root: any, args: UpdateArgs) => Promise<EconomicEve
 export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const runCreate = mapZomeFn<CreateArgs, EconomicEventResponse>(dnaConfig, conductorUri, 'observation', 'economic_event', 'create_economic_event')
   const runUpdate = mapZomeFn<UpdateArgs, EconomicEventResponse>(dnaConfig, conductorUri, 'observation', 'economic_event', 'update_economic_event')
-  const runDelete = mapZomeFn<ByRevision, boolean>(dnaConfig, conductorUri, 'observation', 'economic_event', 'delete_economic_event')
+  const runDelete = mapZomeFn<ByRevision, DeletionReport>(dnaConfig, conductorUri, 'observation', 'economic_event', 'delete_economic_event')
 
   const createEconomicEvent: createHandler = async (root, args) => {
     return runCreate(args)
@@ -5,7 +5,7 @@
  * @since:   2019-08-31
  */
 
-import { ByRevision, DNAIdMappings } from '../types.js'
+import { ByRevision, DNAIdMappings, ReadParams } from '../types.js'
 import { mapZomeFn } from '../connection.js'
 import { deleteHandler } from './'
 
@@ -13,6 +13,8 @@ import {
   SatisfactionCreateParams,
   SatisfactionUpdateParams,
   SatisfactionResponse,
+  CommitmentResponse,
+  EconomicEventResponse,
 } from '@valueflows/vf-graphql'
 
 export interface CreateArgs {
@@ -30,12 +32,35 @@ export default (dnaConfig: DNAIdMappings, conductorUri: string) => {
   const runUpdate = mapZomeFn<UpdateArgs, SatisfactionResponse>(dnaConfig, conductorUri, 'planning', 'satisfaction', 'update_satisfaction')
   const runDelete = mapZomeFn<ByRevision, boolean>(dnaConfig, conductorUri, 'planning', 'satisfaction', 'delete_satisfaction')
 
+  const readCommitment = mapZomeFn<ReadParams, CommitmentResponse>(dnaConfig, conductorUri, 'planning', 'commitment', 'get_commitment')
+  const readEvent = mapZomeFn<ReadParams, EconomicEventResponse>(dnaConfig, conductorUri, 'observation', 'economic_event', 'get_economic_event')
+
+  // Satisfactions reference either a Commitment or an EconomicEvent. The zome API requires the type
+  // of the referenced record alongside its ID, which is determined by looking the record up.
+  const exists = (read: Promise<any>): Promise<boolean> => read.then(() => true, () => false)
+  const toRecordRef = async (satisfiedBy: string): Promise<[string, string]> => {
+    if (dnaConfig.planning && await exists(readCommitment({ address: satisfiedBy }))) {
+      return ['Commitment', satisfiedBy]
+    }
+    if (dnaConfig.observation && await exists(readEvent({ address: satisfiedBy }))) {
+      return ['EconomicEvent', satisfiedBy]
+    }
+    throw new Error(`Satisfaction references ${satisfiedBy}, which is not a known Commitment or EconomicEvent`)
+  }
+
   const createSatisfaction: createHandler = async (root, args) => {
-    return runCreate(args)
+    return runCreate({
+      satisfaction: { ...args.satisfaction, satisfiedBy: await toRecordRef(args.satisfaction.satisfiedBy) as any },
+    })
   }
 
   const updateSatisfaction: updateHandler = async (root, args) => {
-    return runUpdate(args)
+    if (!args.satisfaction.satisfiedBy) {
+      return runUpdate(args)
+    }
+    return runUpdate({
+      satisfaction: { ...args.satisfaction, satisfiedBy: await toRecordRef(args.satisfaction.satisfiedBy) as any },
+    })
   }
 
   const deleteSatisfaction: deleteHandler = async (root, args) => {
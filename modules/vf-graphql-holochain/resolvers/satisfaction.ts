@@ -41,21 +41,18 @@ export default (enabledVFModules: VfModule[] = DEFAULT_VF_MODULES, dnaConfig: DN
   return Object.assign(
     (hasObservation || hasCommitment ? {
       satisfiedBy: async (record: Satisfaction): Promise<EventOrCommitment> => {
-        const associatedId = remapCellId(record.id, record.satisfiedBy)
-        // :NOTE: this presumes a satisfaction will never be erroneously linked to 2 records
-        return (
-          await Promise.all((hasCommitment ? [
-            extractRecordsOrFail(readCommitments({ params: { satisfies: associatedId } }))
-              .then(addTypename('Commitment'))
-              .catch((e) => e),
-          ] : []).concat(hasObservation ? [
-            extractRecordsOrFail(readEvents({ params: { satisfies: associatedId } }))
-              .then(addTypename('EconomicEvent'))
-              .catch((e) => e),
-          ] : []))
-        )
-        .filter(r => !(r instanceof Error))
-        .pop()
+        // referenced records are encoded by the zome API as a pair of record type & ID
+        const [recordType, satisfiedById] = record.satisfiedBy as unknown as [string, string]
+        const associatedId = remapCellId(record.id, satisfiedById)
+        if (recordType === 'Commitment' && hasCommitment) {
+          return extractRecordsOrFail(readCommitments({ params: { satisfies: associatedId } }))
+            .then(addTypename('Commitment'))
+        }
+        if (recordType === 'EconomicEvent' && hasObservation) {
+          return extractRecordsOrFail(readEvents({ params: { satisfies: associatedId } }))
+            .then(addTypename('EconomicEvent'))
+        }
+        throw new Error(`Satisfaction references a ${recordType}, which is not enabled in this API`)
       }
     } : {}),
     (hasIntent ? {
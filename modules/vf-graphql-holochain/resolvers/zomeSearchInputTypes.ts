@@ -92,6 +92,7 @@ interface IntentQueryParams {
     inScopeOf?: AgentAddress,
     provider?: AgentAddress,
     receiver?: AgentAddress,
+    includeCancelled?: boolean,
 }
 
 interface PlanQueryParams {
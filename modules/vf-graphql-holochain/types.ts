@@ -101,6 +101,17 @@ export type ProcessSpecificationAddress = AddressableIdentifier
 export interface ByRevision {
   revisionId: string
 }
+
+// response from deletion handlers which check for dependent records
+export interface DependentRecordResult {
+  recordType: string,
+  address: AddressableIdentifier,
+  error?: string,
+}
+export interface DeletionReport {
+  deleted: boolean,
+  removedDependents: DependentRecordResult[],
+}
 // end of section
 
 // helpers for resolvers to inject __typename parameter for union type disambiguation
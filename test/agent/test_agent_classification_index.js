@@ -0,0 +1,53 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import { buildPlayer } from '../init.js'
+
+const WORKER = 'https://example.com/classifications/worker'
+const OWNER = 'https://example.com/classifications/owner'
+
+test('Agents are queryable by classification', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['agent'])
+  try {
+    const { cells: [agent] } = alice
+
+    let resp = await agent.call('agent', 'create_agent', {
+      agent: { name: 'test person', agentType: 'Person', classifiedAs: [WORKER] },
+    })
+    await pause(100)
+    const agentId = resp.agent.id
+    const revisionId = resp.agent.revisionId
+
+    resp = await agent.call('agent_index', 'query_agents', { params: { classifiedAs: WORKER } })
+    t.equal(resp.edges.length, 1, 'agent indexed by classification on creation')
+    t.deepEqual(resp.edges[0].node.id, agentId, 'correct agent returned')
+
+    resp = await agent.call('agent', 'update_agent', {
+      agent: { revisionId, classifiedAs: [OWNER] },
+    })
+    await pause(100)
+
+    resp = await agent.call('agent_index', 'query_agents', { params: { classifiedAs: WORKER } })
+    t.equal(resp.edges.length, 0, 'previous classification removed on update')
+    resp = await agent.call('agent_index', 'query_agents', { params: { classifiedAs: OWNER } })
+    t.equal(resp.edges.length, 1, 'new classification indexed on update')
+    t.deepEqual(resp.edges[0].node.id, agentId, 'correct agent returned after update')
+
+    resp = await agent.call('agent', 'query_agents_by_classified_as', { value: OWNER })
+    t.equal(resp.length, 1, 'derived index queryable via generated API')
+    t.deepEqual(resp[0].id, agentId, 'generated query API returns matching agent')
+    resp = await agent.call('agent', 'query_agents_by_classified_as', { value: WORKER })
+    t.equal(resp.length, 0, 'generated query API omits removed classifications')
+
+    resp = await agent.call('agent', 'resync_record_indexes', { address: agentId })
+    t.ok(resp, 'derived index resynced')
+    resp = await agent.call('agent', 'query_agents_by_classified_as', { value: OWNER })
+    t.equal(resp.length, 1, 'resync leaves current classification indexed')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,61 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier() },
+}
+
+test('Agent economic context aggregate', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'planning', 'agent'])
+  try {
+    const { cells: [observation, planning, agent] } = alice
+
+    let resp = await agent.call('agent', 'create_agent', { agent: { agentType: 'Person', name: 'Alice' } })
+    const aliceId = resp.agent.id
+    resp = await agent.call('agent', 'create_agent', { agent: { agentType: 'Person', name: 'Bob' } })
+    const bobId = resp.agent.id
+    await pause(100)
+
+    let context = await agent.call('agent', 'get_agent_context', { address: aliceId })
+    t.deepEqual(context.economicEventsAsProvider, [], 'agent with no activity has empty sections')
+    t.deepEqual(context.commitmentsAsReceiver, [], 'agent with no activity has empty sections')
+
+    context = await agent.call('agent', 'get_agent_context', { address: mockAddress() })
+    t.deepEqual(context.intentsAsProvider, [], 'unknown agent returns empty sections rather than an error')
+
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { provider: aliceId, receiver: bobId, ...testProps } })
+    const commitmentId = resp.commitment.id
+    resp = await planning.call('intent', 'create_intent', { intent: { provider: bobId, receiver: aliceId, ...testProps } })
+    const intentId = resp.intent.id
+    resp = await observation.call('economic_event', 'create_economic_event', {
+      event: { provider: aliceId, receiver: bobId, hasPointInTime: new Date(), ...testProps },
+    })
+    const eventId = resp.economicEvent.id
+    await pause(100)
+
+    context = await agent.call('agent', 'get_agent_context', { address: aliceId })
+    t.deepEqual(context.agent, aliceId, 'context identifies agent')
+    t.deepEqual(context.commitmentsAsProvider, [commitmentId], 'commitments as provider populated')
+    t.deepEqual(context.intentsAsReceiver, [intentId], 'intents as receiver populated')
+    t.deepEqual(context.economicEventsAsProvider, [eventId], 'events as provider populated')
+    t.deepEqual(context.economicEventsAsReceiver, [], 'unrelated sections remain empty')
+
+    context = await agent.call('agent', 'get_agent_context', { address: bobId })
+    t.deepEqual(context.commitmentsAsReceiver, [commitmentId], 'counterparty commitments populated')
+    t.deepEqual(context.economicEventsAsReceiver, [eventId], 'counterparty events populated')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,92 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier() },
+}
+
+test('Referenced agents cannot be deleted, but can be anonymised', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'agent'])
+  try {
+    const { cells: [observation, agent] } = alice
+
+    let resp = await agent.call('agent', 'create_agent', {
+      agent: { agentType: 'Person', name: 'Alice', image: 'https://example.com/alice.png', note: 'some note' },
+    })
+    const aliceAgent = resp.agent
+    resp = await agent.call('agent', 'create_agent', { agent: { agentType: 'Person', name: 'Bob' } })
+    const bobId = resp.agent.id
+    await pause(100)
+
+    resp = await observation.call('economic_event', 'create_economic_event', {
+      event: { provider: aliceAgent.id, receiver: bobId, hasPointInTime: new Date(), ...testEventProps },
+    })
+    const eventId = resp.economicEvent.id
+    await pause(100)
+
+    try {
+      await agent.call('agent', 'delete_agent', { revisionId: aliceAgent.revisionId })
+      t.fail('referenced agent should not be deleted')
+    } catch (err) {
+      t.ok(err.message.includes('1 economic events as provider'), 'deletion refused with reference counts')
+    }
+    resp = await agent.call('agent', 'get_agent', { address: aliceAgent.id })
+    t.equal(resp.agent.name, 'Alice', 'agent left intact after refused deletion')
+
+    resp = await agent.call('agent', 'delete_agent', { revisionId: aliceAgent.revisionId, anonymise: true })
+    t.equal(resp, false, 'nothing reported as deleted when anonymising')
+    await pause(100)
+
+    resp = await agent.call('agent', 'get_agent', { address: aliceAgent.id })
+    t.equal(resp.agent.name, 'Deleted agent', 'name replaced')
+    t.notOk(resp.agent.image, 'image cleared')
+    t.notOk(resp.agent.note, 'note cleared')
+    t.ok(resp.agent.retired, 'agent marked as retired')
+    t.deepEqual(resp.agent.economicEventsAsProvider, [eventId], 'references remain resolvable')
+
+    resp = await observation.call('economic_event', 'get_economic_event', { address: eventId })
+    t.deepEqual(resp.economicEvent.provider, aliceAgent.id, 'referencing event unaffected')
+
+    resp = await agent.call('agent_index', 'read_all_agents', {})
+    t.deepEqual(resp.edges.map(e => e.node.id), [bobId], 'retired agent excluded from agent listing')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('Unreferenced agents can be deleted', async (t) => {
+  const alice = await buildPlayer(['agent'])
+  try {
+    const { cells: [agent] } = alice
+
+    let resp = await agent.call('agent', 'create_agent', { agent: { agentType: 'Person', name: 'Alice' } })
+    const aliceAgent = resp.agent
+    await pause(100)
+
+    resp = await agent.call('agent', 'delete_agent', { revisionId: aliceAgent.revisionId })
+    t.ok(resp, 'agent deleted')
+    await pause(100)
+
+    try {
+      await agent.call('agent', 'get_agent', { address: aliceAgent.id })
+      t.fail('deleted agent should not be readable')
+    } catch (err) {
+      t.ok(err.message.includes('No entry at this address'), 'deleted agent no longer readable')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,43 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import { buildPlayer } from '../init.js'
+
+test('Agent type cannot be changed after creation', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['agent'])
+  try {
+    const { cells: [agent] } = alice
+
+    const createResp = await agent.call('agent', 'create_agent', {
+      agent: { name: 'test person', agentType: 'Person' },
+    })
+    await pause(100)
+    const revisionId = createResp.agent.revisionId
+
+    try {
+      await agent.call('agent', 'update_agent', {
+        agent: { revisionId, agentType: 'Organization' },
+      })
+      t.fail('agent type change accepted')
+    } catch (err) {
+      t.ok(err.toString().includes('agent_type'), 'changing agent type rejected')
+    }
+
+    let resp = await agent.call('agent', 'update_agent', {
+      agent: { revisionId, name: 'renamed person', agentType: 'Person' },
+    })
+    t.equal(resp.agent.agentType, 'Person', 'resending unchanged agent type accepted')
+
+    resp = await agent.call('agent', 'update_agent', {
+      agent: { revisionId: resp.agent.revisionId, note: 'no type given' },
+    })
+    t.equal(resp.agent.name, 'renamed person', 'updates omitting agent type accepted')
+    t.equal(resp.agent.agentType, 'Person', 'agent type retained')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,54 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import { buildPlayer } from '../init.js'
+
+const ids = (resp) => resp.map(r => r.agent.id)
+
+test('Organizations have member agents', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['agent'])
+  try {
+    const { cells: [agent] } = alice
+
+    const create = async (name, agentType) => (await agent.call('agent', 'create_agent', { agent: { name, agentType } })).agent.id
+    const org = await create('test co-op', 'Organization')
+    const otherOrg = await create('other co-op', 'Organization')
+    const person = await create('test person', 'Person')
+    await pause(100)
+
+    await agent.call('agent', 'add_membership', { organization: org, member: person })
+    await pause(100)
+
+    t.deepEqual(ids(await agent.call('agent', 'query_org_members', { address: org })), [person], 'member listed against organization')
+    t.deepEqual(ids(await agent.call('agent', 'query_agent_memberships', { address: person })), [org], 'organization listed against member')
+    let resp = await agent.call('agent', 'get_agent', { address: person })
+    t.deepEqual(resp.agent.memberOf, [org], 'membership included in agent record')
+    resp = await agent.call('agent_index', 'query_agents', { params: { memberOf: org } })
+    t.deepEqual(resp.edges.map(e => e.node.id), [person], 'members queryable via index')
+
+    await agent.call('agent', 'remove_membership', { organization: org, member: person })
+    await pause(100)
+
+    t.deepEqual(await agent.call('agent', 'query_org_members', { address: org }), [], 'member removed from organization')
+    t.deepEqual(await agent.call('agent', 'query_agent_memberships', { address: person }), [], 'organization removed from member')
+
+    try {
+      await agent.call('agent', 'add_membership', { organization: person, member: org })
+      t.fail('people should not have members')
+    } catch (err) {
+      t.ok(err.message.includes('cannot be a member of an agent of type'), 'membership of person refused')
+    }
+    try {
+      await agent.call('agent', 'add_membership', { organization: org, member: otherOrg })
+      t.fail('organizations should not be members by default')
+    } catch (err) {
+      t.ok(err.message.includes("of type 'Organization' cannot be a member"), 'organization as member refused')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,72 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+} from '../init.js'
+
+const testProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier() },
+}
+
+test('Agent-scoped "my records" queries', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'planning', 'agent'])
+  try {
+    const { cells: [observation, planning, agent] } = alice
+
+    let resp = await agent.call('agent', 'my_commitments', { role: 'provider' })
+    t.ok(resp.notAssociated, 'unassociated user is flagged')
+    t.equal(resp.edges.length, 0, 'unassociated user has no records')
+
+    resp = await agent.call('agent', 'create_agent', { agent: { agentType: 'Person', name: 'Alice' } })
+    const aliceId = resp.agent.id
+    resp = await agent.call('agent', 'create_agent', { agent: { agentType: 'Person', name: 'Bob' } })
+    const bobId = resp.agent.id
+    await agent.call('agent', 'associate_my_agent', { agentAddress: aliceId })
+    await pause(100)
+
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { provider: aliceId, receiver: bobId, ...testProps } })
+    const c1 = resp.commitment.id
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { provider: aliceId, receiver: bobId, ...testProps } })
+    const c2 = resp.commitment.id
+    resp = await planning.call('intent', 'create_intent', { intent: { provider: bobId, receiver: aliceId, ...testProps } })
+    const intentId = resp.intent.id
+    resp = await observation.call('economic_event', 'create_economic_event', {
+      event: { provider: aliceId, receiver: bobId, hasPointInTime: new Date(), ...testProps },
+    })
+    const eventId = resp.economicEvent.id
+    await pause(100)
+
+    resp = await agent.call('agent', 'my_commitments', { role: 'provider' })
+    t.notOk(resp.notAssociated, 'associated user is not flagged')
+    t.equal(resp.edges.length, 2, 'commitments as provider returned')
+    t.equal(resp.pageInfo.totalCount, 2, 'total count reported')
+    t.deepEqual(resp.edges.map(e => e.node.id).sort(), [c1, c2].sort(), 'correct commitments returned')
+
+    resp = await agent.call('agent', 'my_commitments', { role: 'receiver' })
+    t.equal(resp.edges.length, 0, 'no commitments as receiver')
+
+    const page1 = await agent.call('agent', 'my_commitments', { role: 'provider', limit: 1 })
+    t.equal(page1.edges.length, 1, 'limit respected')
+    t.ok(page1.pageInfo.hasNextPage, 'next page indicated')
+    const page2 = await agent.call('agent', 'my_commitments', { role: 'provider', limit: 1, cursor: page1.pageInfo.endCursor })
+    t.equal(page2.edges.length, 1, 'second page returned')
+    t.notOk(page2.pageInfo.hasNextPage, 'no further pages')
+    t.notDeepEqual(page2.edges[0].node.id, page1.edges[0].node.id, 'pages do not overlap')
+
+    resp = await agent.call('agent', 'my_intents', { role: 'receiver' })
+    t.deepEqual(resp.edges.map(e => e.node.id), [intentId], 'intents as receiver returned')
+
+    resp = await agent.call('agent', 'my_economic_events', { role: 'provider' })
+    t.deepEqual(resp.edges.map(e => e.node.id), [eventId], 'events as provider returned')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,40 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import { buildPlayer } from '../init.js'
+
+const examplePerson = {
+  agentType: 'Person',
+  name: 'test person',
+  image: 'https://image.png',
+  note: 'original note',
+}
+
+test('Agent revision diffs', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['agent'])
+  try {
+    const { cells: [agent] } = alice
+
+    const createResp = await agent.call('agent', 'create_agent', { agent: examplePerson })
+    const r1Id = createResp.agent.revisionId
+    await pause(100)
+
+    const updateResp = await agent.call('agent', 'update_agent', { agent: { revisionId: r1Id, note: 'updated note' } })
+    const r2Id = updateResp.agent.revisionId
+    await pause(100)
+
+    const changes = await agent.call('agent', 'diff_revisions', { fromRevisionId: r1Id, toRevisionId: r2Id })
+    t.deepEqual(changes, [
+      { field: 'note', oldValue: 'original note', newValue: 'updated note' },
+    ], 'only the changed field is reported')
+
+    const none = await agent.call('agent', 'diff_revisions', { fromRevisionId: r2Id, toRevisionId: r2Id })
+    t.deepEqual(none, [], 'identical revisions have no changes')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
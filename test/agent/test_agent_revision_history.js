@@ -0,0 +1,61 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import { buildPlayer } from '../init.js'
+
+test('Agent revision history windows', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['agent'])
+  try {
+    const { cells: [agent] } = alice
+
+    let resp = await agent.call('agent', 'create_agent', { agent: { agentType: 'Person', name: 'test person', note: 'note 1' } })
+    const agentId = resp.agent.id
+    const r1Id = resp.agent.revisionId
+    await pause(100)
+
+    resp = await agent.call('agent', 'update_agent', { agent: { revisionId: r1Id, note: 'note 2' } })
+    const r2Id = resp.agent.revisionId
+    await pause(100)
+
+    const windowStart = new Date()
+    await pause(100)
+
+    resp = await agent.call('agent', 'update_agent', { agent: { revisionId: r2Id, note: 'note 3' } })
+    const r3Id = resp.agent.revisionId
+    await pause(100)
+    resp = await agent.call('agent', 'update_agent', { agent: { revisionId: r3Id, note: 'note 4' } })
+    const r4Id = resp.agent.revisionId
+    await pause(100)
+
+    resp = await agent.call('agent', 'get_revision_history', { address: agentId })
+    t.deepEqual(resp.revisions.map(r => r.entry.agent.note), ['note 1', 'note 2', 'note 3', 'note 4'], 'full history returned oldest first')
+    t.notOk(resp.precedingRevision, 'no revision precedes an unbounded window')
+
+    resp = await agent.call('agent', 'get_revision_history', { address: agentId, since: windowStart })
+    t.deepEqual(resp.revisions.map(r => r.entry.agent.note), ['note 3', 'note 4'], 'revisions filtered by time')
+    t.deepEqual(resp.precedingRevision.id, r2Id, 'revision preceding the window referenced')
+    t.deepEqual(resp.revisions.map(r => r.version), [3, 4], 'revisions numbered from the creating revision')
+    t.equal(resp.precedingVersion, 2, 'version of preceding revision returned')
+
+    resp = await agent.call('agent', 'get_revision_history', { address: agentId, since: windowStart, limit: 1 })
+    t.deepEqual(resp.revisions.map(r => r.revision.id), [r3Id], 'revisions limited from start of window')
+    t.ok(resp.hasMore, 'truncated window flagged')
+
+    resp = await agent.call('agent', 'get_revision_history', { address: agentId, since: new Date() })
+    t.deepEqual(resp.revisions, [], 'empty window returns no revisions')
+    t.ok(resp.precedingRevision, 'empty window references preceding revision')
+
+    // reverting to earlier entry data must not be mistaken for the earlier revision
+    await agent.call('agent', 'update_agent', { agent: { revisionId: r4Id, note: 'note 3' } })
+    await pause(100)
+
+    resp = await agent.call('agent', 'get_revision_history', { address: agentId })
+    t.deepEqual(resp.revisions.map(r => r.entry.agent.note), ['note 1', 'note 2', 'note 3', 'note 4', 'note 3'], 'reverted revisions followed to the end of the history')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,61 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import { buildPlayer } from '../init.js'
+
+test('Agents can be resolved by unique slug', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['agent'])
+  try {
+    const { cells: [agent] } = alice
+
+    let resp = await agent.call('agent', 'create_agent', { agent: { name: 'Alice', agentType: 'Person', slug: 'alice' } })
+    const aliceAgent = resp.agent
+    t.equal(aliceAgent.slug, 'alice', 'slug stored on record')
+    resp = await agent.call('agent', 'create_agent', { agent: { name: 'Bob', agentType: 'Person', slug: 'bob' } })
+    const bobId = resp.agent.id
+    await pause(100)
+
+    resp = await agent.call('agent', 'resolve_by_slug', { slug: 'alice' })
+    t.deepEqual(resp.address, aliceAgent.id, 'slug resolves to the agent holding it')
+    t.notOk(resp.redirected, 'current slug is not a redirect')
+    resp = await agent.call('agent', 'resolve_by_slug', { slug: 'bob' })
+    t.deepEqual(resp.address, bobId, 'each slug resolves to the correct agent')
+    resp = await agent.call('agent', 'resolve_by_slug', { slug: 'nobody' })
+    t.equal(resp, null, 'unknown slugs do not resolve')
+
+    try {
+      await agent.call('agent', 'create_agent', { agent: { name: 'Other Alice', agentType: 'Person', slug: 'alice' } })
+      t.fail('duplicate slug should be rejected')
+    } catch (err) {
+      t.ok(err.message.includes("slug 'alice' is already in use"), 'duplicate slug rejected at creation')
+    }
+    try {
+      await agent.call('agent', 'create_agent', { agent: { name: 'Carol', agentType: 'Person', slug: 'Carol Smith' } })
+      t.fail('malformed slug should be rejected')
+    } catch (err) {
+      t.ok(err.message.includes('Invalid slug'), 'malformed slug rejected')
+    }
+
+    await agent.call('agent', 'update_agent', { agent: { revisionId: aliceAgent.revisionId, slug: 'alice-a' } })
+    await pause(100)
+
+    resp = await agent.call('agent', 'resolve_by_slug', { slug: 'alice-a' })
+    t.deepEqual(resp.address, aliceAgent.id, 'new slug resolves')
+    resp = await agent.call('agent', 'resolve_by_slug', { slug: 'alice' })
+    t.deepEqual(resp.address, aliceAgent.id, 'old slug redirects to the agent')
+    t.ok(resp.redirected, 'old slug flagged as a redirect')
+
+    try {
+      await agent.call('agent', 'create_agent', { agent: { name: 'Other Alice', agentType: 'Person', slug: 'alice' } })
+      t.fail('redirecting slug should not be claimable')
+    } catch (err) {
+      t.ok(err.message.includes('already in use'), 'redirecting slug cannot be claimed by another agent')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,69 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import { buildPlayer } from '../init.js'
+
+test('Open query access permits anyone to query', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['agent'])
+  try {
+    const { cells: [agent] } = alice
+
+    const { agent: { id } } = await agent.call('agent', 'create_agent', { agent: { name: 'bob', agentType: 'Person' } })
+    await pause(100)
+
+    const resp = await agent.call('agent_index', 'read_all_agents', {})
+    t.deepEqual(resp.edges.map(e => e.node.id), [id], 'agents listed without an associated profile')
+    const indexed = await agent.call('agent_index', 'read_indexed_addresses', {})
+    t.equal(indexed.addresses.length, 1, 'indexed addresses readable without an associated profile')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('Members-only query access requires an associated Agent profile', async (t) => {
+  const alice = await buildPlayer(['agent', 'observation'], undefined, {
+    agent: { query_access: 'members_only' },
+    observation: { query_access: 'members_only' },
+  })
+  try {
+    const { cells: [agent, observation] } = alice
+
+    try {
+      await agent.call('agent_index', 'read_all_agents', {})
+      t.fail('non-members should not be able to list agents')
+    } catch (err) {
+      t.ok(err.message.includes('UNAUTHORIZED'), 'listing refused to non-members')
+    }
+    try {
+      await agent.call('agent_index', 'read_indexed_addresses', {})
+      t.fail('non-members should not be able to read indexed addresses')
+    } catch (err) {
+      t.ok(err.message.includes('UNAUTHORIZED'), 'indexed addresses refused to non-members')
+    }
+
+    const { agent: { id } } = await agent.call('agent', 'create_agent', { agent: { name: 'alice', agentType: 'Person' } })
+    t.ok(id, 'non-members can create their profile')
+    await agent.call('agent', 'associate_my_agent', { agentAddress: id })
+    await pause(100)
+
+    const resp = await agent.call('agent_index', 'read_all_agents', {})
+    t.deepEqual(resp.edges.map(e => e.node.id), [id], 'agents listed once a profile is associated')
+    const indexed = await agent.call('agent_index', 'read_indexed_addresses', {})
+    t.equal(indexed.addresses.length, 1, 'indexed addresses readable once a profile is associated')
+
+    try {
+      await observation.call('economic_event_index', 'query_economic_events_between', { from: new Date(0), until: new Date() })
+      t.fail('membership cannot be determined without an agent zome')
+    } catch (err) {
+      t.ok(err.message.includes('MISCONFIGURED'), 'queries in DNAs without agent profiles refused as misconfigured')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
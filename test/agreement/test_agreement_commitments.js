@@ -0,0 +1,91 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+} from '../init.js'
+
+const testCommitmentProps = {
+  action: 'transfer',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1 },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  due: '2019-11-19T04:29:55.056Z',
+}
+
+test('Agreement creation with reciprocal commitments', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['agreement', 'planning'])
+  try {
+    const { cells: [agreement, planning] } = alice
+
+    const createResp = await agreement.call('agreement', 'create_agreement', {
+      agreement: {
+        name: 'reciprocal exchange',
+        planningDna: planning.cell_id[0],
+        commitments: [
+          { ...testCommitmentProps, note: 'outgoing' },
+          { ...testCommitmentProps, note: 'incoming' },
+        ],
+      },
+    })
+    const agreementId = createResp.agreement.id
+    t.ok(agreementId, 'agreement created')
+    t.equal(createResp.commitments.length, 2, 'all commitments created')
+    t.deepEqual(createResp.commitments.map(c => c.note), ['outgoing', 'incoming'], 'commitments returned in request order')
+    t.deepEqual(createResp.commitments[0].clauseOf, agreementId, 'first commitment is a clause of the agreement')
+    t.deepEqual(createResp.commitments[1].clauseOf, agreementId, 'second commitment is a clause of the agreement')
+    await pause(100)
+
+    const readResp = await agreement.call('agreement', 'get_agreement', { address: agreementId })
+    t.equal(readResp.agreement.commitments.length, 2, 'agreement references created commitments')
+
+    const commitmentResp = await planning.call('commitment', 'get_commitment', { address: createResp.commitments[0].id })
+    t.deepEqual(commitmentResp.commitment.clauseOf, agreementId, 'commitment stored in planning DNA')
+
+    // SCENARIO: the second commitment is invalid, so nothing should be kept
+    const { due, ...undatedCommitment } = testCommitmentProps
+    try {
+      await agreement.call('agreement', 'create_agreement', {
+        agreement: {
+          name: 'broken exchange',
+          planningDna: planning.cell_id[0],
+          commitments: [
+            { ...testCommitmentProps, note: 'valid' },
+            { ...undatedCommitment, note: 'invalid' },
+          ],
+        },
+      })
+      t.fail('agreement with an invalid commitment should be refused')
+    } catch (err) {
+      t.ok(err.message.includes('beginning, end, exact time or due date'), 'commitment failure reported')
+    }
+    await pause(100)
+
+    const agreements = await agreement.call('agreement_index', 'read_all_agreements', {})
+    t.equal(agreements.edges.length, 1, 'failed agreement not kept')
+    const commitments = await planning.call('commitment_index', 'read_all_commitments', {})
+    t.equal(commitments.edges.length, 2, 'commitments of failed agreement removed')
+    t.notOk(commitments.edges.find(e => e.node.note === 'valid'), 'valid commitment of failed agreement removed')
+
+    try {
+      await agreement.call('agreement', 'create_agreement', {
+        agreement: {
+          name: 'unplanned exchange',
+          commitments: [{ ...testCommitmentProps, note: 'nowhere' }],
+        },
+      })
+      t.fail('commitments without a planning DNA should be refused')
+    } catch (err) {
+      t.ok(err.message.includes('VALIDATION'), 'missing planning DNA classified as invalid')
+      t.ok(err.message.includes('planningDna'), 'missing planning DNA field reported')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
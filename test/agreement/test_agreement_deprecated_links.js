@@ -0,0 +1,57 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+} from '../init.js'
+
+const testCommitmentProps = {
+  action: 'transfer',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1 },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  due: '2019-11-19T04:29:55.056Z',
+}
+
+test('Deprecated agreement clauses are hidden from reads unless requested', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['agreement', 'planning'])
+  try {
+    const { cells: [agreement, planning] } = alice
+
+    let resp = await agreement.call('agreement', 'create_agreement', {
+      agreement: { name: 'renegotiated exchange' },
+    })
+    const agreementId = resp.agreement.id
+    await pause(100)
+
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { ...testCommitmentProps, clauseOf: agreementId, note: 'current' } })
+    const currentId = resp.commitment.id
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { ...testCommitmentProps, clauseOf: agreementId, note: 'superseded' } })
+    const supersededId = resp.commitment.id
+    await pause(100)
+
+    const deprecated = await agreement.call('agreement_index', '_internal_deprecate_agreement_commitments', { address: agreementId, target: supersededId })
+    t.equal(deprecated, 2, 'links in both directions deprecated')
+    await pause(100)
+
+    resp = await agreement.call('agreement_index', '_internal_read_agreement_commitments', { address: agreementId })
+    t.deepEqual(resp, [currentId], 'deprecated clause absent from default reads')
+
+    resp = await agreement.call('agreement_index', '_internal_read_agreement_commitments_with_deprecated', { address: agreementId })
+    t.deepEqual(resp, [currentId], 'deprecated clause absent unless requested')
+
+    resp = await agreement.call('agreement_index', '_internal_read_agreement_commitments_with_deprecated', { address: agreementId, includeDeprecated: true })
+    t.deepEqual(resp, [currentId, supersededId], 'deprecated clause returned after current clauses when requested')
+
+    resp = await agreement.call('agreement', 'get_agreement', { address: agreementId })
+    t.deepEqual(resp.agreement.commitments, [currentId], 'record reads unaffected by deprecated links')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
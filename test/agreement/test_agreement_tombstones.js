@@ -0,0 +1,67 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+} from '../init.js'
+
+const testCommitmentProps = {
+  action: 'transfer',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1 },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  due: '2019-11-19T04:29:55.056Z',
+}
+
+test('Agreement clauses are retained as tombstones when commitments are deleted', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['agreement', 'planning'])
+  try {
+    const { cells: [agreement, planning] } = alice
+
+    let resp = await agreement.call('agreement', 'create_agreement', {
+      agreement: { name: 'audited exchange' },
+    })
+    const agreementId = resp.agreement.id
+    await pause(100)
+
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { ...testCommitmentProps, clauseOf: agreementId, note: 'kept' } })
+    const keptId = resp.commitment.id
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { ...testCommitmentProps, clauseOf: agreementId, note: 'withdrawn' } })
+    const withdrawnId = resp.commitment.id
+    await pause(100)
+
+    await planning.call('commitment', 'delete_commitment', { revisionId: resp.commitment.revisionId })
+    await pause(100)
+
+    resp = await agreement.call('agreement', 'get_agreement', { address: agreementId })
+    t.deepEqual(resp.agreement.commitments, [keptId], 'deleted commitment no longer a live clause')
+
+    resp = await agreement.call('agreement_index', 'query_agreements', { params: { commitments: withdrawnId } })
+    t.equal(resp.edges.length, 0, 'tombstones skipped by normal queries')
+
+    resp = await agreement.call('agreement_index', 'query_agreements', { params: { commitments: withdrawnId }, include_tombstones: true })
+    t.equal(resp.edges.length, 1, 'tombstones returned when requested')
+    t.deepEqual(resp.edges[0].node.id, agreementId, 'former agreement found via tombstone')
+
+    const history = await agreement.call('agreement_index', '_internal_read_agreement_commitments_history', { address: agreementId })
+    t.equal(history.length, 2, 'history includes live and removed clauses')
+    const live = history.find(h => !h.removedAt)
+    const removed = history.find(h => h.removedAt)
+    t.deepEqual(live.address, keptId, 'live clause has no removal time')
+    t.deepEqual(removed.address, withdrawnId, 'removed clause retained')
+    t.ok(new Date(removed.removedAt) > new Date('2020-01-01'), 'removal time recorded')
+
+    const migrated = await agreement.call('agreement_index', 'migrate_index_links', { addresses: [agreementId] })
+    t.equal(migrated, 0, 'tombstones not treated as legacy links')
+    resp = await agreement.call('agreement', 'get_agreement', { address: agreementId })
+    t.deepEqual(resp.agreement.commitments, [keptId], 'link repair does not resurrect tombstoned clauses')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
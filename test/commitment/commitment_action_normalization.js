@@ -0,0 +1,55 @@
+import test from 'tape'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testProps = {
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier() },
+  provider: mockAddress(),
+  receiver: mockAddress(),
+  due: new Date(),
+}
+
+test('Commitment actions are stored in canonical form', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning'])
+  try {
+    const { cells: [planning] } = alice
+
+    let resp = await planning.call('commitment', 'create_commitment', { commitment: { ...testProps, action: 'Transfer_All_Rights' } })
+    t.equal(resp.commitment.action, 'transfer-all-rights', 'mis-cased action normalized')
+
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { ...testProps, action: 'transferCustody' } })
+    t.equal(resp.commitment.action, 'transfer-custody', 'camelCased action normalized')
+
+    resp = await planning.call('commitment', 'update_commitment', { commitment: { revisionId: resp.commitment.revisionId, action: 'Drop-Off' } })
+    t.equal(resp.commitment.action, 'dropoff', 'updated action normalized')
+
+    try {
+      await planning.call('commitment', 'update_commitment', { commitment: { revisionId: resp.commitment.revisionId, action: 'teleport' } })
+      t.fail('unknown action should be rejected on update')
+    } catch (err) {
+      t.ok(err.message.includes("Unknown action 'teleport'"), 'unknown action rejected on update')
+    }
+
+    resp = await planning.call('intent', 'create_intent', { intent: { ...testProps, action: 'consume' } })
+    resp = await planning.call('intent', 'update_intent', { intent: { revisionId: resp.intent.revisionId, action: 'CONSUME' } })
+    t.equal(resp.intent.action, 'consume', 'updated intent action normalized')
+
+    try {
+      await planning.call('commitment', 'create_commitment', { commitment: { ...testProps, action: 'teleport' } })
+      t.fail('unknown action should be rejected')
+    } catch (err) {
+      t.ok(err.message.includes("Unknown action 'teleport'"), 'unknown action rejected')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,66 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const APPLES = 'https://example.com/classification/apples'
+const PEARS = 'https://example.com/classification/pears'
+
+const testProps = {
+  action: 'raise',
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier() },
+  provider: mockAddress(),
+  receiver: mockAddress(),
+  due: new Date(),
+}
+
+test('Commitments, intents and events are queryable by resource classification', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'planning'])
+  try {
+    const { cells: [observation, planning] } = alice
+
+    const queryCommitments = async (classifiedAs) => {
+      const resp = await planning.call('commitment_index', 'query_commitments', { params: { classifiedAs } })
+      return resp.edges.map(e => e.node.id)
+    }
+
+    let resp = await planning.call('commitment', 'create_commitment', { commitment: { ...testProps, resourceClassifiedAs: [APPLES, PEARS] } })
+    const mixedId = resp.commitment.id
+    const revisionId = resp.commitment.revisionId
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { ...testProps, resourceClassifiedAs: [PEARS] } })
+    const pearsId = resp.commitment.id
+    await pause(100)
+
+    t.deepEqual(await queryCommitments(APPLES), [mixedId], 'commitment indexed by each classification')
+    t.equal((await queryCommitments(PEARS)).length, 2, 'commitments sharing a classification returned together')
+
+    await planning.call('commitment', 'update_commitment', { commitment: { revisionId, resourceClassifiedAs: [APPLES] } })
+    await pause(100)
+    t.deepEqual(await queryCommitments(PEARS), [pearsId], 'removed classification no longer matches')
+    t.deepEqual(await queryCommitments(APPLES), [mixedId], 'retained classification still matches')
+
+    resp = await planning.call('intent', 'create_intent', { intent: { ...testProps, resourceClassifiedAs: [APPLES] } })
+    const intentId = resp.intent.id
+    await pause(100)
+    resp = await planning.call('intent_index', 'query_intents', { params: { classifiedAs: APPLES } })
+    t.deepEqual(resp.edges.map(e => e.node.id), [intentId], 'intents queryable by classification')
+
+    resp = await observation.call('economic_event', 'create_economic_event', {
+      event: { ...testProps, hasPointInTime: new Date(), resourceClassifiedAs: [PEARS] },
+    })
+    const eventId = resp.economicEvent.id
+    await pause(100)
+    resp = await observation.call('economic_event_index', 'query_economic_events', { params: { classifiedAs: PEARS } })
+    t.deepEqual(resp.edges.map(e => e.node.id), [eventId], 'events queryable by classification')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
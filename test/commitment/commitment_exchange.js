@@ -0,0 +1,101 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const seller = mockAddress()
+const buyer = mockAddress()
+
+const goodsProps = {
+  action: 'transfer',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier() },
+  provider: seller,
+  receiver: buyer,
+}
+const paymentProps = {
+  action: 'transfer',
+  resourceClassifiedAs: ['currency'],
+  resourceQuantity: { hasNumericalValue: 10, hasUnit: mockIdentifier() },
+  provider: buyer,
+  receiver: seller,
+}
+
+test('Exchange commitments are created in reciprocal pairs', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning', 'agreement'])
+  try {
+    const { cells: [planning, agreementCell] } = alice
+    const { agreement: { id: agreement } } = await agreementCell.call('agreement', 'create_agreement', { agreement: { name: 'sale' } })
+    await pause(100)
+
+    try {
+      await planning.call('commitment', 'create_exchange_commitments', {
+        agreement: mockAddress(),
+        primary: goodsProps,
+        reciprocal: paymentProps,
+      })
+      t.fail('exchange against a missing agreement should be refused')
+    } catch (err) {
+      t.ok(err, 'exchange against a missing agreement refused')
+    }
+
+    const resp = await planning.call('commitment', 'create_exchange_commitments', {
+      agreement,
+      primary: goodsProps,
+      reciprocal: paymentProps,
+    })
+    const { primary, reciprocal } = resp
+    t.deepEqual(primary.clauseOf, agreement, 'primary commitment is clause of agreement')
+    t.deepEqual(reciprocal.clauseOf, agreement, 'reciprocal commitment is clause of agreement')
+    t.deepEqual(primary.reciprocalOf, reciprocal.id, 'primary references its counterpart')
+    t.deepEqual(reciprocal.reciprocalOf, primary.id, 'reciprocal references its counterpart')
+    await pause(100)
+
+    let read = await planning.call('commitment', 'get_commitment', { address: primary.id })
+    t.deepEqual(read.commitment.reciprocalOf, reciprocal.id, 'counterpart readable from stored record')
+
+    let query = await planning.call('commitment_index', 'query_commitments', { params: { reciprocalOf: primary.id } })
+    t.deepEqual(query.edges.map(e => e.node.id), [reciprocal.id], 'counterpart queryable via reciprocal_of index')
+
+    try {
+      await planning.call('commitment', 'create_exchange_commitments', {
+        agreement,
+        primary: goodsProps,
+        reciprocal: { ...paymentProps, provider: seller },
+      })
+      t.fail('non-mirrored commitments should be refused')
+    } catch (err) {
+      t.ok(err.message.includes('must swap provider and receiver'), 'non-mirrored commitments refused')
+    }
+
+    try {
+      await planning.call('commitment', 'create_exchange_commitments', {
+        agreement,
+        primary: goodsProps,
+        reciprocal: { ...paymentProps, action: 'not-an-action' },
+      })
+      t.fail('invalid reciprocal commitment should be refused')
+    } catch (err) {
+      t.ok(err.message.includes("Unknown action 'not-an-action'"), 'invalid reciprocal commitment refused')
+    }
+    await pause(100)
+
+    query = await planning.call('commitment_index', 'read_all_commitments', {})
+    t.equal(query.edges.length, 2, 'no commitment left behind by failed exchange')
+
+    await planning.call('commitment', 'delete_commitment', { revisionId: reciprocal.revisionId })
+    await pause(100)
+    read = await planning.call('commitment', 'get_commitment', { address: primary.id })
+    t.notOk(read.commitment.reciprocalOf, 'reciprocal link removed on deletion of counterpart')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
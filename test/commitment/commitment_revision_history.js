@@ -0,0 +1,48 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testProps = {
+  action: 'consume',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier() },
+  provider: mockAddress(),
+  receiver: mockAddress(),
+  due: new Date(),
+}
+
+test('Commitment revision history windows', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning'])
+  try {
+    const { cells: [planning] } = alice
+
+    let resp = await planning.call('commitment', 'create_commitment', { commitment: { ...testProps, note: 'note 1' } })
+    const commitmentId = resp.commitment.id
+    await pause(100)
+
+    const windowStart = new Date()
+    await pause(100)
+
+    resp = await planning.call('commitment', 'update_commitment', { commitment: { revisionId: resp.commitment.revisionId, note: 'note 2' } })
+    await pause(100)
+    await planning.call('commitment', 'update_commitment', { commitment: { revisionId: resp.commitment.revisionId, note: 'note 3' } })
+    await pause(100)
+
+    resp = await planning.call('commitment', 'get_revision_history', { address: commitmentId, since: windowStart, limit: 1 })
+    t.deepEqual(resp.revisions.map(r => r.entry.commitment.note), ['note 2'], 'revisions returned as commitment responses')
+    t.equal(resp.revisions[0].entry.commitment.id.toString(), commitmentId.toString(), 'revisions identify the commitment')
+    t.equal(resp.precedingVersion, 1, 'revision preceding the window referenced')
+    t.ok(resp.hasMore, 'truncated window flagged')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,67 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const PENDING = 'commitment.pending'
+const COMPLETED = 'commitment.completed'
+
+const testProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier() },
+  provider: mockAddress(),
+  receiver: mockAddress(),
+  due: new Date(),
+}
+
+test('Commitments move between pending and completed indexes as they are finished', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning'])
+  try {
+    const { cells: [planning] } = alice
+
+    const queryState = async (finishedState) => {
+      const resp = await planning.call('commitment_index', 'query_commitments', { params: { finishedState } })
+      return resp.edges.map(e => e.node.id)
+    }
+
+    let resp = await planning.call('commitment', 'create_commitment', { commitment: testProps })
+    const commitmentId = resp.commitment.id
+    const revisionId = resp.commitment.revisionId
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { ...testProps, finished: true } })
+    const finishedId = resp.commitment.id
+    await pause(100)
+
+    t.deepEqual(await queryState(PENDING), [commitmentId], 'new commitment indexed as pending')
+    t.deepEqual(await queryState(COMPLETED), [finishedId], 'commitment created as finished indexed as completed')
+
+    resp = await planning.call('commitment', 'update_commitment', { commitment: { revisionId, finished: true } })
+    const finishedRevisionId = resp.commitment.revisionId
+    await pause(100)
+
+    t.deepEqual(await queryState(PENDING), [], 'finished commitment removed from pending index')
+    const completed = await queryState(COMPLETED)
+    t.equal(completed.length, 2, 'finished commitment added to completed index')
+    t.ok(completed.some(id => JSON.stringify(id) === JSON.stringify(commitmentId)), 'correct commitment moved')
+
+    resp = await planning.call('commitment', 'update_commitment', { commitment: { revisionId: finishedRevisionId, note: 'unrelated edit' } })
+    await pause(100)
+    t.deepEqual(await queryState(PENDING), [], 'unrelated edits leave state indexes untouched')
+    t.equal((await queryState(COMPLETED)).length, 2, 'unrelated edits do not duplicate completed index entries')
+
+    await planning.call('commitment', 'update_commitment', { commitment: { revisionId: resp.commitment.revisionId, finished: false } })
+    await pause(100)
+    t.deepEqual(await queryState(PENDING), [commitmentId], 'reopened commitment returns to pending index')
+    t.deepEqual(await queryState(COMPLETED), [finishedId], 'reopened commitment removed from completed index')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,58 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  due: '2019-11-19T04:29:55.056Z',
+}
+
+test('zome errors are returned with a stable classification code', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning', 'agent'])
+  try {
+    const { cells: [planning, agent] } = alice
+
+    const commitmentResponse = await planning.call('commitment', 'create_commitment', {
+      commitment: { note: 'a commitment', ...testEventProps },
+    })
+    await pause(100)
+    await planning.call('commitment', 'delete_commitment', { revisionId: commitmentResponse.commitment.revisionId })
+    await pause(100)
+
+    try {
+      await planning.call('commitment', 'get_commitment', { address: commitmentResponse.commitment.id })
+      t.fail('deleted record retrieved')
+    } catch (err) {
+      t.ok(err.message.includes('NOT_FOUND'), 'missing records reported as NOT_FOUND')
+      t.ok(err.message.includes('No entry at this address'), 'error message retained')
+    }
+
+    const agentResponse = await agent.call('agent', 'create_agent', {
+      agent: { name: 'test person', agentType: 'Person' },
+    })
+    try {
+      await agent.call('agent', 'update_agent', {
+        agent: { revisionId: agentResponse.agent.revisionId, agentType: 'Organization' },
+      })
+      t.fail('immutable field updated')
+    } catch (err) {
+      t.ok(err.message.includes('VALIDATION'), 'rejected updates reported as VALIDATION')
+      t.ok(err.message.includes('agent_type'), 'offending field reported')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
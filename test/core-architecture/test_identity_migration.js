@@ -0,0 +1,53 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+}
+
+test('record identities can only be migrated by network admins', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'], undefined, {
+    observation: { identity_schemes: { current: 'V2', fallbacks: ['V1'] } },
+  })
+  try {
+    const { cells: [observation] } = alice
+
+    const resp = await observation.call('economic_event', 'create_economic_event', { event: testEventProps })
+    const eventId = resp.economicEvent.id
+    await pause(100)
+
+    try {
+      await observation.call('economic_event_index', 'migrate_identity', { address: eventId })
+      t.fail('migration should be refused before network admins are set')
+    } catch (err) {
+      t.ok(err.message.includes('UNAUTHORIZED'), 'non-admins cannot migrate identities')
+    }
+
+    await observation.call('network_settings', 'create_network_settings', { networkSettings: {} })
+    await pause(100)
+
+    const identity = await observation.call('economic_event_index', 'migrate_identity', { address: eventId })
+    t.ok(identity, 'admins can migrate identities')
+    await pause(100)
+
+    const read = await observation.call('economic_event', 'get_economic_event', { address: eventId })
+    t.equal(read.economicEvent.id, eventId, 'record readable after migration')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,99 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+}
+
+test('index links can be individually removed and restored by network admins', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+
+    const resp = await observation.call('economic_event', 'create_economic_event', {
+      event: testEventProps,
+      newInventoriedResource: { name: 'bicycle' },
+    })
+    const eventId = resp.economicEvent.id
+    const resourceId = resp.economicResource.id
+    await pause(100)
+
+    const indexes = await observation.call('economic_event_index', 'list_managed_indexes', null)
+    const affects = indexes.find(i => i.name === 'affects')
+    const params = {
+      sourceType: affects.recordType,
+      sourceAddress: eventId,
+      destType: affects.relatedRecordType,
+      destAddress: resourceId,
+      linkTag: affects.linkTag,
+      linkTagReciprocal: affects.reciprocalLinkTag,
+    }
+
+    try {
+      await observation.call('economic_event_index', 'unlink_index', params)
+      t.fail('unlinking should be refused before network admins are set')
+    } catch (err) {
+      t.ok(err.message.includes('UNAUTHORIZED'), 'non-admins cannot unlink')
+    }
+
+    await observation.call('network_settings', 'create_network_settings', { networkSettings: {} })
+    await pause(100)
+
+    let report = await observation.call('economic_event_index', 'unlink_index', params)
+    t.deepEqual(report, { forward: true, reciprocal: true }, 'both directions of the link removed')
+    await pause(100)
+
+    let linked = await observation.call('economic_event_index', '_internal_read_economic_event_affects', { address: eventId })
+    t.deepEqual(linked, [], 'link absent from index reads')
+    const record = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.ok(record.economicResource, 'linked record unaffected')
+
+    report = await observation.call('economic_event_index', 'unlink_index', params)
+    t.equal(report.forward || report.reciprocal, false, 'nothing removed when already unlinked')
+    t.ok(report.warning, 'warning returned when nothing removed')
+
+    // SCENARIO: link restored in the opposite orientation
+    report = await observation.call('economic_event_index', 'link_index', {
+      sourceType: params.destType,
+      sourceAddress: resourceId,
+      destType: params.sourceType,
+      destAddress: eventId,
+      linkTag: params.linkTagReciprocal,
+      linkTagReciprocal: params.linkTag,
+    })
+    t.deepEqual(report, { forward: true, reciprocal: true }, 'both directions of the link restored')
+    await pause(100)
+
+    linked = await observation.call('economic_event_index', '_internal_read_economic_event_affects', { address: eventId })
+    t.deepEqual(linked, [resourceId], 'link present in index reads')
+
+    try {
+      await observation.call('economic_event_index', 'unlink_index', { ...params, linkTag: 'economic_event_nonexistent' })
+      t.fail('unknown relationships should be refused')
+    } catch (err) {
+      t.ok(err.message.includes('VALIDATION'), 'unknown relationship rejected')
+    }
+
+    const log = await observation.call('economic_event_index', 'read_index_admin_log', null)
+    t.deepEqual(log.map(e => e.action.operation), ['Unlink', 'Unlink', 'Link'], 'all operations logged in order')
+    t.deepEqual(log.map(e => e.action.forwardChanged), [true, false, true], 'changes made by each operation logged')
+    t.deepEqual(log[0].author, observation.cell_id[1], 'acting agent logged')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
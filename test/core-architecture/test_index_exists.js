@@ -0,0 +1,46 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  due: '2019-11-19T04:29:55.056Z',
+}
+
+test('index reads distinguish missing index bases from empty indexes', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning'])
+  try {
+    const { cells: [planning] } = alice
+
+    const resp = await planning.call('commitment', 'create_commitment', {
+      commitment: { note: 'a commitment without fulfillments', ...testEventProps },
+    })
+    await pause(100)
+    const commitmentId = resp.commitment.id
+
+    let result = await planning.call('commitment_index', '_internal_read_commitment_fulfilled_by_if_exists', { address: commitmentId })
+    t.deepEqual(result, [], 'existing record with no index entries returns an empty list')
+
+    const neverCreated = [commitmentId[0], mockAddress(false)[1]]
+    result = await planning.call('commitment_index', '_internal_read_commitment_fulfilled_by_if_exists', { address: neverCreated })
+    t.equal(result, null, 'record which was never indexed returns null')
+
+    result = await planning.call('commitment_index', '_internal_read_commitment_fulfilled_by', { address: neverCreated })
+    t.deepEqual(result, [], 'standard index reads unchanged')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
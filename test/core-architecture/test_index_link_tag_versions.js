@@ -0,0 +1,49 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('index links are written with versioned tags and remain readable after migration', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+
+    const pResp = await observation.call('process', 'create_process', { process: { name: 'versioned index context' } })
+    await pause(100)
+    const processId = pResp.process.id
+
+    const ieResp = await observation.call('economic_event', 'create_economic_event', {
+      event: { note: 'test input event', action: 'consume', inputOf: processId, ...testEventProps },
+    })
+    await pause(100)
+    const iEventId = ieResp.economicEvent.id
+
+    // links written by the current version need no migration
+    const migrated = await observation.call('process_index', 'migrate_index_links', { addresses: [processId] })
+    t.equal(migrated, 0, 'newly written links are already versioned')
+
+    let readResponse = await observation.call('economic_event_index', 'query_economic_events', { params: { inputOf: processId } })
+    t.deepLooseEqual(readResponse.edges.map(e => e.node.id), [iEventId], 'forward index readable')
+
+    readResponse = await observation.call('process_index', 'query_processes', { params: { observedInputs: iEventId } })
+    t.deepLooseEqual(readResponse.edges.map(e => e.node.id), [processId], 'reciprocal index readable')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
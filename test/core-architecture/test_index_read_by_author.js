@@ -0,0 +1,51 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockAgentId,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+}
+
+test('index links can be filtered by the agent who created them', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+
+    const resp = await observation.call('economic_event', 'create_economic_event', {
+      event: testEventProps,
+      newInventoriedResource: { name: 'bicycle' },
+    })
+    const eventId = resp.economicEvent.id
+    const resourceId = resp.economicResource.id
+    await pause(100)
+
+    let linked = await observation.call('economic_event_index', '_internal_read_economic_event_affects_by_author', {
+      address: eventId,
+      author: observation.cell_id[1],
+    })
+    t.deepEqual(linked, [resourceId], 'links created by the author returned')
+
+    linked = await observation.call('economic_event_index', '_internal_read_economic_event_affects_by_author', {
+      address: eventId,
+      author: mockAgentId(false)[1],
+    })
+    t.deepEqual(linked, [], 'links created by other agents excluded')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
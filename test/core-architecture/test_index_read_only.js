@@ -0,0 +1,53 @@
+import test from 'tape'
+import { buildPlayer, getDNAProperties, mockAddress } from '../init.js'
+
+function readOnly (dna, zomes) {
+  const properties = getDNAProperties(dna)
+  return Object.fromEntries(zomes.map(zome => [zome, { ...properties[zome], read_only: true }]))
+}
+
+async function assertReadOnly (t, call, description) {
+  try {
+    await call()
+    t.fail(`${description} should be refused`)
+  } catch (err) {
+    t.ok(err.message.includes('READ_ONLY'), `${description} refused by read-only follower`)
+  }
+}
+
+test('Custom index zome externs refuse writes to read-only followers', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'agent'], undefined, {
+    observation: readOnly('observation', ['process_index', 'economic_resource_index']),
+    agent: readOnly('agent', ['agent_index']),
+  })
+  try {
+    const { cells: [observation, agent] } = alice
+    const timestamp = new Date().toISOString()
+
+    await assertReadOnly(t, () => observation.call('process_index', 'record_process_state_change', {
+      address: mockAddress(false), state: 'inProgress', timestamp,
+    }), 'process state changes')
+    await assertReadOnly(t, () => observation.call('process_index', 'remove_process_state_history', {
+      address: mockAddress(false),
+    }), 'process state history removal')
+    await assertReadOnly(t, () => observation.call('economic_resource_index', 'record_economic_resource_expiry', {
+      address: mockAddress(false), timestamp,
+    }), 'resource expiry')
+    await assertReadOnly(t, () => observation.call('economic_resource_index', 'remove_economic_resource_expiry', {
+      address: mockAddress(false),
+    }), 'resource expiry removal')
+    await assertReadOnly(t, () => agent.call('agent_index', 'retire_agent', {
+      address: mockAddress(false),
+    }), 'agent retirement')
+
+    const history = await observation.call('process_index', 'read_process_state_history', { address: mockAddress(false) })
+    t.deepEqual(history, [], 'reads unaffected')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,197 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAgentId,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  due: '2019-11-19T04:29:55.056Z',
+}
+
+test('network settings CRUD & admin restrictions', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning'])
+  try {
+    const { cells: [planning] } = alice
+
+    let readResp = await planning.call('network_settings', 'get_network_settings', null)
+    t.equal(readResp.networkSettings, null, 'no settings before creation')
+
+    const createResp = await planning.call('network_settings', 'create_network_settings', {
+      networkSettings: { defaultTimezoneOffsetMinutes: 600 },
+    })
+    t.ok(createResp.networkSettings.revisionId, 'settings created')
+    t.equal(createResp.networkSettings.enforceSpecUnits, false, 'enforcement flag defaults to off')
+    t.equal(createResp.networkSettings.enforceRegisteredUnits, false, 'unit registration flag defaults to off')
+    t.deepEqual(createResp.networkSettings.adminAgents, [planning.cell_id[1]], 'creator granted admin')
+    await pause(100)
+
+    try {
+      await planning.call('network_settings', 'create_network_settings', { networkSettings: {} })
+      t.fail('second settings record should be refused')
+    } catch (err) {
+      t.ok(err.message.includes('Network settings have already been created'), 'settings are a singleton')
+    }
+
+    const updateResp = await planning.call('network_settings', 'update_network_settings', {
+      networkSettings: { revisionId: createResp.networkSettings.revisionId, defaultTimezoneOffsetMinutes: -300 },
+    })
+    t.equal(updateResp.networkSettings.defaultTimezoneOffsetMinutes, -300, 'admin can update settings')
+    await pause(100)
+
+    readResp = await planning.call('network_settings', 'get_network_settings', null)
+    t.equal(readResp.networkSettings.defaultTimezoneOffsetMinutes, -300, 'latest revision read')
+    t.deepEqual(readResp.networkSettings.meta.previousRevision.id, createResp.networkSettings.revisionId, 'update history available')
+
+    const prevResp = await planning.call('network_settings', 'get_revision', { revisionId: createResp.networkSettings.revisionId })
+    t.equal(prevResp.networkSettings.defaultTimezoneOffsetMinutes, 600, 'previous revisions readable')
+
+    // SCENARIO: grant admin rights to another agent, revoking our own
+    const otherAdmin = mockAgentId(false)[1]
+    const grantResp = await planning.call('network_settings', 'update_network_settings', {
+      networkSettings: { revisionId: updateResp.networkSettings.revisionId, adminAgents: [otherAdmin] },
+    })
+    t.deepEqual(grantResp.networkSettings.adminAgents, [otherAdmin], 'admin rights reassigned')
+    await pause(100)
+
+    try {
+      await planning.call('network_settings', 'update_network_settings', {
+        networkSettings: { revisionId: grantResp.networkSettings.revisionId, enforceSpecUnits: true },
+      })
+      t.fail('update by non-admin should be refused')
+    } catch (err) {
+      t.ok(err.message.includes('The current agent is not a network admin'), 'non-admins cannot update settings')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('network settings enforce units on commitment quantities', async (t) => {
+  const alice = await buildPlayer(['planning'])
+  try {
+    const { cells: [planning] } = alice
+
+    let resp = await planning.call('commitment', 'create_commitment', {
+      commitment: { ...testEventProps, resourceQuantity: { hasNumericalValue: 1 } },
+    })
+    t.ok(resp.commitment.id, 'unitless quantities accepted when no settings exist')
+
+    await planning.call('network_settings', 'create_network_settings', {
+      networkSettings: { enforceSpecUnits: true },
+    })
+    await pause(100)
+
+    try {
+      await planning.call('commitment', 'create_commitment', {
+        commitment: { ...testEventProps, resourceQuantity: { hasNumericalValue: 1 } },
+      })
+      t.fail('unitless quantity should be refused')
+    } catch (err) {
+      t.ok(err.message.includes('resourceQuantity has no unit'), 'unitless quantities refused when enforced')
+    }
+
+    resp = await planning.call('commitment', 'create_commitment', {
+      commitment: { ...testEventProps, resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) } },
+    })
+    t.ok(resp.commitment.id, 'quantities with units accepted when enforced')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('network settings enforce registered units on commitment quantities', async (t) => {
+  const alice = await buildPlayer(['planning', 'specification'])
+  try {
+    const { cells: [planning, specification] } = alice
+
+    const unitResp = await specification.call('unit', 'create_unit', {
+      unit: { label: 'kilograms', symbol: 'kg' },
+    })
+    const unitId = unitResp.unit.id
+    await pause(100)
+
+    await planning.call('network_settings', 'create_network_settings', {
+      networkSettings: { enforceRegisteredUnits: true },
+    })
+    await pause(100)
+
+    const resp = await planning.call('commitment', 'create_commitment', {
+      commitment: { ...testEventProps, resourceQuantity: { hasNumericalValue: 1, hasUnit: unitId } },
+    })
+    t.ok(resp.commitment.id, 'quantities referencing registered units accepted')
+
+    try {
+      await planning.call('commitment', 'create_commitment', {
+        commitment: { ...testEventProps, effortQuantity: { hasNumericalValue: 1, hasUnit: [unitId[0], 'unregistered'] } },
+      })
+      t.fail('unregistered unit should be refused')
+    } catch (err) {
+      t.ok(err.message.includes('the unit unregistered of effortQuantity was not found'), 'unregistered units refused when enforced')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('network settings enforce specification units on commitment quantities', async (t) => {
+  const alice = await buildPlayer(['planning', 'specification'])
+  try {
+    const { cells: [planning, specification] } = alice
+
+    const kg = (await specification.call('unit', 'create_unit', { unit: { label: 'kilograms', symbol: 'kg' } })).unit.id
+    const lb = (await specification.call('unit', 'create_unit', { unit: { label: 'pounds', symbol: 'lb' } })).unit.id
+    const specResp = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: { name: 'flour', defaultUnitOfResource: kg },
+    })
+    const specId = specResp.resourceSpecification.id
+    await pause(100)
+
+    await planning.call('network_settings', 'create_network_settings', {
+      networkSettings: { enforceSpecUnits: true },
+    })
+    await pause(100)
+
+    const resp = await planning.call('commitment', 'create_commitment', {
+      commitment: { ...testEventProps, resourceConformsTo: specId, resourceQuantity: { hasNumericalValue: 1, hasUnit: kg } },
+    })
+    t.ok(resp.commitment.id, 'quantities in the specification unit accepted')
+
+    try {
+      await planning.call('commitment', 'create_commitment', {
+        commitment: { ...testEventProps, resourceConformsTo: specId, resourceQuantity: { hasNumericalValue: 1, hasUnit: lb } },
+      })
+      t.fail('quantity in another unit should be refused')
+    } catch (err) {
+      t.ok(err.message.includes('resourceQuantity is measured in'), 'quantities in other units refused when enforced')
+    }
+
+    try {
+      await planning.call('commitment', 'update_commitment', {
+        commitment: { revisionId: resp.commitment.revisionId, resourceQuantity: { hasNumericalValue: 2, hasUnit: lb } },
+      })
+      t.fail('update to another unit should be refused')
+    } catch (err) {
+      t.ok(err.message.includes('resourceQuantity is measured in'), 'updates checked against the specification unit')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
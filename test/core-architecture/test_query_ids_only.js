@@ -0,0 +1,48 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import { buildPlayer } from '../init.js'
+
+test('queries can return record identifiers in place of full records', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+
+    let resp = await observation.call('process', 'create_process', { process: { name: 'parent process' } })
+    const parentId = resp.process.id
+    await pause(100)
+
+    const children = []
+    for (const name of ['first child', 'second child']) {
+      resp = await observation.call('process', 'create_process', { process: { name, composedOfParent: parentId } })
+      children.push(resp.process)
+      await pause(100)
+    }
+    resp = await observation.call('process', 'update_process', { process: { revisionId: children[0].revisionId, note: 'updated' } })
+    const updatedRevision = resp.process.revisionId
+    await pause(100)
+
+    // ASSERT: filtered queries
+    const full = await observation.call('process_index', 'query_processes', { params: { composedOfParent: parentId } })
+    const ids = await observation.call('process_index', 'query_processes', { params: { composedOfParent: parentId }, responseMode: 'ids_only' })
+    t.deepEqual(ids.edges.map(e => e.node.id), full.edges.map(e => e.node.id), 'same records returned in the same order')
+    t.deepEqual(ids.edges.map(e => e.cursor), full.edges.map(e => e.cursor), 'same cursors returned')
+    t.deepEqual(Object.keys(ids.edges[0].node).sort(), ['id', 'revisionId'], 'only identifiers returned')
+    const updated = ids.edges.find(e => e.node.id.toString() === children[0].id.toString())
+    t.deepEqual(updated.node.revisionId, updatedRevision, 'latest revision returned')
+
+    // ASSERT: list queries
+    const list = await observation.call('process_index', 'read_all_processes', { last: 10, responseMode: 'ids_only' })
+    t.equal(list.edges.length, 3, 'all records listed')
+    t.ok(list.edges.every(e => e.node.revisionId && !e.node.name), 'list returns identifiers only')
+
+    // ASSERT: full responses remain the default
+    t.ok(full.edges[0].node.name, 'full records returned by default')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
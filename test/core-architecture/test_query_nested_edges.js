@@ -0,0 +1,69 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'consume',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('query results can be returned with their own edges resolved', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning', 'observation'])
+  try {
+    const { cells: [planning, observation] } = alice
+
+    const pResp = await observation.call('process', 'create_process', { process: { name: 'test process' } })
+    const processId = pResp.process.id
+    const commitmentResp = await planning.call('commitment', 'create_commitment', {
+      commitment: { note: 'a commitment', ...testEventProps },
+    })
+    const eventResp = await observation.call('economic_event', 'create_economic_event', {
+      event: { note: 'an event', inputOf: processId, ...testEventProps },
+    })
+    await pause(100)
+    const eventId = eventResp.economicEvent.id
+
+    await planning.call('fulfillment', 'create_fulfillment', {
+      fulfillment: { fulfills: commitmentResp.commitment.id, fulfilledBy: eventId },
+    })
+    await pause(100)
+
+    // ASSERT: depth-0 queries behave as before
+    let resp = await observation.call('economic_event_index', 'query_economic_events', { params: { inputOf: processId } })
+    t.equal(resp.edges.length, 1, 'event returned')
+    t.notOk(resp.edges[0].edges, 'no nested edges resolved by default')
+
+    resp = await observation.call('economic_event_index', 'query_economic_events', { params: { inputOf: processId }, depth: 0 })
+    t.notOk(resp.edges[0].edges, 'no nested edges resolved at depth 0')
+
+    // ASSERT: depth-1 queries include immediate edges of each result
+    resp = await observation.call('economic_event_index', 'query_economic_events', { params: { inputOf: processId }, depth: 1 })
+    t.deepEqual(resp.edges[0].node.id, eventId, 'event returned')
+    const { edges } = resp.edges[0]
+    t.ok(edges, 'nested edges resolved')
+    t.equal(edges.inputOf.length, 1, 'inputOf edge populated')
+    t.deepEqual(edges.inputOf[0], processId, 'inputOf edge references process')
+    t.equal(edges.fulfills.length, 1, 'fulfills edge populated')
+    t.deepEqual(edges.fulfills[0][1], resp.edges[0].node.fulfills[0][1], 'fulfills edge references fulfillment')
+    t.deepEqual(edges.outputOf, [], 'unrelated edges empty')
+
+    // ASSERT: requested depth is capped
+    const capped = await observation.call('economic_event_index', 'query_economic_events', { params: { inputOf: processId }, depth: 10 })
+    t.deepEqual(capped.edges[0].edges, edges, 'depth capped at one level')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
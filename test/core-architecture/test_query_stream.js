@@ -0,0 +1,80 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import { buildPlayer } from '../init.js'
+
+const NUM_RECORDS = 25
+const CHUNK_SIZE = 10
+
+test('large queries can be streamed to the client as signals', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation], player } = alice
+
+    let resp = await observation.call('process', 'create_process', { process: { name: 'parent process' } })
+    const parentId = resp.process.id
+    await pause(100)
+
+    for (let i = 0; i < NUM_RECORDS; i++) {
+      await observation.call('process', 'create_process', { process: { name: `child ${i}`, composedOfParent: parentId } })
+    }
+    await pause(100)
+
+    const chunks = []
+    player.appAgentWs.on('signal', (signal) => {
+      if (signal.payload && signal.payload.streamId === 'stream-test') {
+        chunks.push(signal.payload)
+      }
+    })
+
+    const summary = await observation.call('process_index', 'query_processes_stream', {
+      params: { composedOfParent: parentId },
+      streamId: 'stream-test',
+      chunkSize: CHUNK_SIZE,
+    })
+    t.equal(summary.chunks, Math.ceil(NUM_RECORDS / CHUNK_SIZE), 'query split into expected number of chunks')
+    t.deepEqual(summary.chunksFailed, [], 'all chunks emitted')
+    t.equal(summary.totalResults, NUM_RECORDS, 'all results counted')
+    t.equal(summary.totalErrors, 0, 'no read errors')
+
+    // signals may be delivered after the zome call returns
+    for (let i = 0; i < 20 && chunks.length < summary.chunks; i++) {
+      await pause(100)
+    }
+    t.equal(chunks.length, summary.chunks, 'expected number of chunk signals received')
+    chunks.sort((a, b) => a.sequence - b.sequence)
+    t.deepEqual(chunks.map(c => c.sequence), [0, 1, 2], 'chunks numbered in sequence')
+    t.deepEqual(chunks.map(c => c.results.length), [10, 10, 5], 'chunks limited to requested size')
+
+    // ASSERT: streamed results match the buffered query
+    const full = await observation.call('process_index', 'query_processes', { params: { composedOfParent: parentId } })
+    const streamed = chunks.flatMap(c => c.results)
+    t.deepEqual(streamed.map(e => e.cursor), full.edges.map(e => e.cursor), 'streamed results cover all query results, in order')
+    t.ok(streamed.every(e => e.node.name), 'full records streamed')
+    t.equal(summary.nextCursor, null, 'no continuation cursor once all results are streamed')
+
+    // ASSERT: results beyond the call's limit are streamed by continuation calls
+    const partial = await observation.call('process_index', 'query_processes_stream', {
+      params: { composedOfParent: parentId },
+      streamId: 'stream-test-1',
+      chunkSize: CHUNK_SIZE,
+      limit: 20,
+    })
+    t.equal(partial.totalResults, 20, 'call streams at most its limit')
+    t.ok(partial.nextCursor, 'continuation cursor returned while results remain')
+    const rest = await observation.call('process_index', 'query_processes_stream', {
+      params: { composedOfParent: parentId },
+      streamId: 'stream-test-2',
+      chunkSize: CHUNK_SIZE,
+      after: partial.nextCursor,
+    })
+    t.equal(rest.totalResults, NUM_RECORDS - 20, 'continuation streams the remaining results')
+    t.equal(rest.nextCursor, null, 'no cursor after the final call')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
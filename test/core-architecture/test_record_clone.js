@@ -0,0 +1,72 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  due: '2019-11-19T04:29:55.056Z',
+}
+
+test('records can be cloned with field overrides', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning'])
+  try {
+    const { cells: [planning] } = alice
+
+    const source = await planning.call('commitment', 'create_commitment', {
+      commitment: { note: 'weekly delivery', finished: true, ...testEventProps },
+    })
+    await pause(100)
+
+    const cloneResp = await planning.call('commitment', 'clone_record', {
+      address: source.commitment.id,
+      overrides: { revisionId: source.commitment.revisionId, due: '2019-11-26T04:29:55.056Z' },
+    })
+    await pause(100)
+    t.deepEqual(cloneResp.clonedFrom, source.commitment.id, 'response references source record')
+    t.notDeepEqual(cloneResp.commitment.id, source.commitment.id, 'clone is a new record')
+    t.equal(cloneResp.commitment.note, 'weekly delivery', 'unreferenced fields copied')
+    t.equal(cloneResp.commitment.due, '2019-11-26T04:29:55.056Z', 'overridden fields applied')
+    t.equal(cloneResp.commitment.finished, false, 'finished flag reset on the copy')
+
+    const queryResp = await planning.call('commitment_index', 'query_commitments', { params: { provider: testEventProps.provider } })
+    t.equal(queryResp.edges.length, 2, 'clone indexed via the standard create path')
+
+    const intent = await planning.call('intent', 'create_intent', {
+      intent: { note: 'weekly need', ...testEventProps },
+    })
+    await pause(100)
+
+    const intentClone = await planning.call('intent', 'clone_record', {
+      address: intent.intent.id,
+      overrides: { revisionId: intent.intent.revisionId, note: 'next week' },
+    })
+    t.deepEqual(intentClone.clonedFrom, intent.intent.id, 'intent clone references source record')
+    t.equal(intentClone.intent.note, 'next week', 'intent overrides applied')
+    t.deepEqual(intentClone.intent.resourceClassifiedAs, ['some-resource-type'], 'intent fields copied')
+
+    try {
+      await planning.call('commitment', 'clone_record', {
+        address: source.commitment.id,
+        overrides: { revisionId: cloneResp.commitment.revisionId },
+      })
+      t.fail('clone with mismatched revision accepted')
+    } catch (err) {
+      t.ok(err.toString().includes('Conflicting revisions'), 'stale or mismatched source revision rejected')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
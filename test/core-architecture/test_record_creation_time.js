@@ -0,0 +1,50 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  due: '2019-11-19T04:29:55.056Z',
+}
+
+test('record creation time is read from the initial create action', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning'])
+  try {
+    const { cells: [planning] } = alice
+
+    const createResponse = await planning.call('commitment', 'create_commitment', {
+      commitment: { note: 'test commitment', ...testEventProps },
+    })
+    t.ok(createResponse.commitment && createResponse.commitment.id, 'record created successfully')
+    const createdTime = createResponse.commitment.meta.retrievedRevision.time
+    await pause(100)
+
+    let readResponse = await planning.call('commitment', 'get_commitment', { address: createResponse.commitment.id })
+    t.equal(readResponse.commitment.createdAt, createdTime, 'creation time matches create action')
+
+    const updateResponse = await planning.call('commitment', 'update_commitment', {
+      commitment: { revisionId: createResponse.commitment.revisionId, note: 'updated commitment' },
+    })
+    t.notEqual(updateResponse.commitment.meta.retrievedRevision.time, createdTime, 'update written at a later time')
+    await pause(100)
+
+    readResponse = await planning.call('commitment', 'get_commitment', { address: createResponse.commitment.id })
+    t.equal(readResponse.commitment.note, 'updated commitment', 'latest revision read')
+    t.equal(readResponse.commitment.createdAt, createdTime, 'creation time unchanged after update')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
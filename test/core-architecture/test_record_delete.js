@@ -75,7 +75,7 @@ test('Cannot delete records of a different type via zome API deletion handlers',
 
     const satisfaction = {
       satisfies: commitmentId, // erroneous but doesn't matter for now
-      satisfiedBy: commitmentId,
+      satisfiedBy: ['Commitment', commitmentId],
       note: 'satisfaction indicating the relationship',
     }
     const satisfactionResp = await planning.call('satisfaction', 'create_satisfaction', { satisfaction })
@@ -0,0 +1,41 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+} from '../init.js'
+
+test('record reads resolve the latest revision through the full update chain', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['specification'])
+  try {
+    const { cells: [specification] } = alice
+
+    let resp = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: { name: 'apples', note: 'initial' },
+    })
+    const recordId = resp.resourceSpecification.id
+    const createdRevisionId = resp.resourceSpecification.revisionId
+    await pause(100)
+
+    resp = await specification.call('resource_specification', 'update_resource_specification', {
+      resourceSpecification: { revisionId: createdRevisionId, note: 'revised' },
+    })
+    await pause(100)
+    // restore the initial content, so that the latest revision shares its entry with the first
+    resp = await specification.call('resource_specification', 'update_resource_specification', {
+      resourceSpecification: { revisionId: resp.resourceSpecification.revisionId, note: 'initial' },
+    })
+    const latestRevisionId = resp.resourceSpecification.revisionId
+    await pause(100)
+
+    resp = await specification.call('resource_specification', 'get_resource_specification', { address: recordId })
+    t.deepEqual(resp.resourceSpecification.revisionId, latestRevisionId, 'latest revision returned after two updates')
+    t.notDeepEqual(resp.resourceSpecification.revisionId, createdRevisionId, 'initial create not returned')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
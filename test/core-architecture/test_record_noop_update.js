@@ -0,0 +1,40 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import { buildPlayer } from '../init.js'
+
+test('updates which change nothing do not write a revision', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['agent'])
+  try {
+    const { cells: [agent] } = alice
+
+    let resp = await agent.call('agent', 'create_agent', { agent: { agentType: 'Person', name: 'test person', note: 'note 1' } })
+    const agentId = resp.agent.id
+    await pause(100)
+
+    resp = await agent.call('agent', 'update_agent', { agent: { revisionId: resp.agent.revisionId, note: 'note 2' } })
+    const revisionId = resp.agent.revisionId
+    t.notOk('changed' in resp.agent.meta, 'changed updates not flagged')
+    await pause(100)
+
+    // SCENARIO: a form resubmitted as-is, twice
+    for (let i = 0; i < 2; i++) {
+      resp = await agent.call('agent', 'update_agent', { agent: { revisionId, name: 'test person', note: 'note 2' } })
+      t.deepEqual(resp.agent.revisionId, revisionId, 'existing revision returned')
+      t.equal(resp.agent.meta.changed, false, 'response flagged as unchanged')
+      await pause(100)
+    }
+
+    resp = await agent.call('agent', 'get_revision_history', { address: agentId })
+    t.deepEqual(resp.revisions.map(r => r.entry.agent.note), ['note 1', 'note 2'], 'no revisions written for identical updates')
+
+    resp = await agent.call('agent', 'get_agent', { address: agentId })
+    t.deepEqual(resp.agent.revisionId, revisionId, 'latest revision unchanged')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,96 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  due: '2019-11-19T04:29:55.056Z',
+}
+
+test('patch operations set and clear individual fields', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning'])
+  try {
+    const { cells: [planning] } = alice
+
+    const commitment = {
+      note: 'test commitment',
+      finished: false,
+      ...testEventProps,
+    }
+
+    const createResponse = await planning.call('commitment', 'create_commitment', { commitment })
+    t.ok(createResponse.commitment && createResponse.commitment.id, 'record created successfully')
+    await pause(100)
+
+    const patchResponse = await planning.call('commitment', 'patch_record', {
+      address: createResponse.commitment.id,
+      ops: [
+        { op: 'set', field: 'finished', value: true },
+        { op: 'clear', field: 'note' },
+      ],
+    })
+    t.notEqual(patchResponse.commitment.revisionId, createResponse.commitment.revisionId, 'patch creates a new revision')
+    await pause(100)
+
+    const readResponse = await planning.call('commitment', 'get_commitment', { address: createResponse.commitment.id })
+    t.equal(readResponse.commitment.finished, true, 'field assigned by set operation')
+    t.equal(readResponse.commitment.note, undefined, 'field removed by clear operation')
+    t.deepEqual(readResponse.commitment.resourceClassifiedAs, ['some-resource-type'], 'unreferenced fields remain intact')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('patches with invalid operations are rejected without applying any changes', async (t) => {
+  const alice = await buildPlayer(['planning'])
+  try {
+    const { cells: [planning] } = alice
+
+    const commitment = {
+      note: 'test commitment 2',
+      ...testEventProps,
+    }
+
+    const createResponse = await planning.call('commitment', 'create_commitment', { commitment })
+    t.ok(createResponse.commitment && createResponse.commitment.id, 'record created successfully')
+    await pause(100)
+
+    try {
+      await planning.call('commitment', 'patch_record', {
+        address: createResponse.commitment.id,
+        ops: [
+          { op: 'set', field: 'note', value: 'updated note' },
+          { op: 'set', field: 'notAField', value: 'oops' },
+          { op: 'set', field: 'finished', value: 'not a boolean' },
+        ],
+      })
+      t.fail('invalid patch should be rejected')
+    } catch (err) {
+      t.ok(err.toString().match(/Patch rejected/), 'patch rejected with error')
+      t.ok(err.toString().match(/op 1 \(notAField\): unknown field/), 'unknown field reported')
+      t.ok(err.toString().match(/op 2 \(finished\): type mismatch/), 'type mismatch reported')
+    }
+    await pause(100)
+
+    const readResponse = await planning.call('commitment', 'get_commitment', { address: createResponse.commitment.id })
+    t.equal(readResponse.commitment.note, 'test commitment 2', 'valid operations not applied when others fail')
+    t.equal(readResponse.commitment.revisionId, createResponse.commitment.revisionId, 'no new revision created')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,84 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+} from '../init.js'
+
+test('Reindexing all records of a type proceeds in batches and skips failures', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'plan'])
+  try {
+    const { cells: [observation, plan] } = alice
+
+    let resp = await plan.call('plan', 'create_plan', { plan: { name: 'reindexed plan' } })
+    const planId = resp.plan.id
+
+    const processes = []
+    for (let i = 0; i < 3; i++) {
+      resp = await observation.call('process', 'create_process', { process: { name: `process ${i}`, plannedWithin: planId } })
+      processes.push(resp.process)
+    }
+    await pause(100)
+
+    // deleted records remain in the creation time index but can no longer be read
+    await observation.call('process', 'delete_process', { revisionId: processes[1].revisionId })
+    await pause(100)
+
+    let report = await observation.call('process_index', 'reindex_all', { limit: 2 })
+    t.equal(report.reindexed + report.failures.length, 2, 'first batch limited to requested size')
+    t.ok(report.nextCursor, 'cursor returned while records remain')
+
+    let reindexed = report.reindexed
+    let failures = report.failures
+    report = await observation.call('process_index', 'reindex_all', { after: report.nextCursor, limit: 2 })
+    reindexed += report.reindexed
+    failures = failures.concat(report.failures)
+    t.equal(report.nextCursor, null, 'no cursor returned once all records are visited')
+
+    t.equal(reindexed, 2, 'readable records reindexed')
+    t.equal(failures.length, 1, 'failed record reported without aborting the run')
+    t.ok(failures[0].error, 'failure includes error description')
+    await pause(100)
+
+    resp = await plan.call('plan', 'get_plan', { address: planId })
+    t.equal(resp.plan.processes.length, 2, 'plan index links intact after reindexing')
+
+    resp = await observation.call('process_index', 'query_processes_in_state', { state: 'planned' })
+    t.equal(resp.results.edges.length, 2, 'state index intact after reindexing')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('Reindexing is supported by every record type', async (t) => {
+  const alice = await buildPlayer(['planning', 'agent'])
+  try {
+    const { cells: [planning, agent] } = alice
+
+    const { agent: { id: agentId } } = await agent.call('agent', 'create_agent', { agent: { name: 'alice', agentType: 'Person' } })
+    await planning.call('commitment', 'create_commitment', {
+      commitment: { action: 'raise', provider: agentId, receiver: agentId, resourceClassifiedAs: ['some-resource-type'], note: 'reindexed commitment' },
+    })
+    await planning.call('intent', 'create_intent', {
+      intent: { action: 'raise', provider: agentId, resourceClassifiedAs: ['some-resource-type'] },
+    })
+    await pause(100)
+
+    for (const [cell, zome] of [[planning, 'commitment_index'], [planning, 'intent_index'], [agent, 'agent_index']]) {
+      const report = await cell.call(zome, 'reindex_all', {})
+      t.equal(report.failures.length, 0, `${zome} records resynced without failures`)
+      t.equal(report.reindexed, 1, `${zome} records reindexed`)
+    }
+
+    const resp = await agent.call('agent', 'get_agent', { address: agentId })
+    t.equal(resp.agent.commitmentsAsProvider.length, 1, 'agent index links intact after reindexing')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
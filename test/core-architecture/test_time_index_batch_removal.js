@@ -0,0 +1,55 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+} from '../init.js'
+
+const NUM_RECORDS = 100
+
+test('Bulk removal from time indexes preserves ordering of remaining entries', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['specification'])
+  try {
+    const { cells: [specification] } = alice
+
+    for (let i = 0; i < NUM_RECORDS; i++) {
+      await specification.call('resource_specification', 'create_resource_specification', {
+        resourceSpecification: { name: `spec ${i}` },
+      })
+    }
+    await pause(100)
+
+    let resp = await specification.call('resource_specification_index', 'read_all_resource_specifications', { last: NUM_RECORDS })
+    t.equal(resp.edges.length, NUM_RECORDS, 'all records indexed')
+    const initialOrder = resp.edges.map(e => e.node.id)
+
+    const toRemove = initialOrder.filter((_id, i) => i % 2 === 0)
+    const expectedOrder = initialOrder.filter((_id, i) => i % 2 === 1)
+
+    try {
+      await specification.call('resource_specification_index', 'remove_from_time_index_batch', { addresses: toRemove })
+      t.fail('removal should be refused before network admins are set')
+    } catch (err) {
+      t.ok(err.message.includes('UNAUTHORIZED'), 'non-admins cannot remove entries from time indexes')
+    }
+
+    await specification.call('network_settings', 'create_network_settings', { networkSettings: {} })
+    await pause(100)
+
+    const removed = await specification.call('resource_specification_index', 'remove_from_time_index_batch', { addresses: toRemove })
+    t.equal(removed, NUM_RECORDS / 2, 'requested entries removed')
+    await pause(100)
+
+    resp = await specification.call('resource_specification_index', 'read_all_resource_specifications', { last: NUM_RECORDS })
+    t.deepEqual(resp.edges.map(e => e.node.id), expectedOrder, 'remaining entries retain their ordering')
+
+    const removedAgain = await specification.call('resource_specification_index', 'remove_from_time_index_batch', { addresses: toRemove })
+    t.equal(removedAgain, 0, 'entries not present in the index are skipped')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,88 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  mockAddress, mockIdentifier,
+  buildPlayer,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  provider: mockAddress(),
+  receiver: mockAddress(),
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier() },
+  hasPointInTime: '2024-02-14T10:00:00.000Z',
+}
+
+test('EconomicEvents reference attachments stored in the attachment zome', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+
+    const content = Buffer.from('delivery receipt #1234')
+    const uploaded = await observation.call('attachment', 'upload_attachment', {
+      chunks: [content.subarray(0, 8), content.subarray(8)],
+    })
+    t.equal(uploaded.size, content.length, 'attachment size recorded')
+
+    const reuploaded = await observation.call('attachment', 'upload_attachment', { chunks: [content] })
+    t.deepEqual(reuploaded.hash, uploaded.hash, 'identical content deduplicated regardless of chunking')
+
+    const read = await observation.call('attachment', 'get_attachment', { hash: uploaded.hash })
+    t.equal(Buffer.from(read.data).toString(), content.toString(), 'attachment content readable')
+
+    const attachment = { hash: uploaded.hash, mime: 'text/plain', filename: 'receipt.txt', size: uploaded.size }
+    const created = await observation.call('economic_event', 'create_economic_event', {
+      event: { ...testEventProps, attachments: [attachment] },
+    })
+    t.deepEqual(created.economicEvent.attachments, [attachment], 'attachment stored with event')
+    await pause(100)
+
+    try {
+      await observation.call('attachment', 'list_unreferenced_attachments', null)
+      t.fail('listing unreferenced attachments should require network admin')
+    } catch (err) {
+      t.ok(err.message.includes('UNAUTHORIZED'), 'non-admins cannot list unreferenced attachments')
+    }
+    await observation.call('network_settings', 'create_network_settings', { networkSettings: {} })
+    await pause(100)
+
+    let resp = await observation.call('attachment', 'list_unreferenced_attachments', null)
+    t.equal(resp.attachments.length, 0, 'referenced attachment not listed for garbage collection')
+
+    try {
+      await observation.call('economic_event', 'create_economic_event', {
+        event: { ...testEventProps, attachments: [{ ...attachment, hash: mockAddress(false)[1] }] },
+      })
+      t.fail('event referencing missing attachment should be rejected')
+    } catch (err) {
+      t.ok(err.message.includes('No attachment found'), 'missing attachment rejected')
+    }
+
+    try {
+      await observation.call('economic_event', 'create_economic_event', {
+        event: { ...testEventProps, attachments: [{ ...attachment, size: attachment.size + 1 }] },
+      })
+      t.fail('event misreporting attachment size should be rejected')
+    } catch (err) {
+      t.ok(err.message.includes('stored attachment holds'), 'attachment size checked against stored manifest')
+    }
+
+    await observation.call('economic_event', 'delete_economic_event', { revisionId: created.economicEvent.revisionId })
+    await pause(100)
+
+    resp = await observation.call('attachment', 'get_attachment', { hash: uploaded.hash })
+    t.equal(resp.size, content.length, 'attachment retained after event deletion')
+
+    resp = await observation.call('attachment', 'list_unreferenced_attachments', null)
+    t.equal(resp.attachments.length, 1, 'attachment listed for garbage collection once unreferenced')
+    t.deepEqual(resp.attachments[0].hash, uploaded.hash, 'unreferenced attachment identified')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,63 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  mockAddress, mockIdentifier,
+  buildPlayer,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  provider: mockAddress(),
+  receiver: mockAddress(),
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier() },
+  hasPointInTime: '2024-02-14T10:00:00.000Z',
+}
+
+test('EconomicEvents are deduplicated by external reference', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+
+    const created = await observation.call('economic_event', 'create_economic_event', {
+      event: { ...testEventProps, externalReference: 'scale-7:reading:89123' },
+    })
+    t.equal(created.economicEvent.externalReference, 'scale-7:reading:89123', 'external reference stored')
+    t.equal(created.alreadyExisted, false, 'new event not flagged as existing')
+    await pause(100)
+
+    const reimported = await observation.call('economic_event', 'create_economic_event', {
+      event: { ...testEventProps, externalReference: ' Scale-7:Reading:89123 ' },
+    })
+    t.ok(reimported.alreadyExisted, 're-import flagged as existing')
+    t.deepEqual(reimported.economicEvent.id, created.economicEvent.id, 'existing event returned for normalised reference')
+
+    let resp = await observation.call('economic_event_index', 'query_economic_events', { params: { period: 'period.2024-W07' } })
+    t.equal(resp.edges.length, 1, 'no duplicate event created')
+
+    resp = await observation.call('economic_event', 'get_economic_event_by_external_reference', { reference: 'scale-7:reading:89123' })
+    t.deepEqual(resp.economicEvent.id, created.economicEvent.id, 'event readable by external reference')
+
+    try {
+      await observation.call('economic_event', 'get_economic_event_by_external_reference', { reference: 'scale-7:reading:1' })
+      t.fail('unknown reference should not resolve')
+    } catch (err) {
+      t.ok(err.message.includes('No entry at this address'), 'unknown reference not found')
+    }
+
+    await observation.call('economic_event', 'delete_economic_event', { revisionId: created.economicEvent.revisionId })
+    await pause(100)
+
+    resp = await observation.call('economic_event', 'create_economic_event', {
+      event: { ...testEventProps, externalReference: 'scale-7:reading:89123' },
+    })
+    t.equal(resp.alreadyExisted, false, 'reference released when event deleted')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
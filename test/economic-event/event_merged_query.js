@@ -0,0 +1,100 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  mockAddress,
+  mockIdentifier,
+  buildPlayer,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('events affecting several resources can be queried as one time-ordered stream', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'specification'])
+  try {
+    const { cells: [observation, specification] } = alice
+
+    const resourceUnitId = mockIdentifier(false)
+    const rsResp = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: { name: 'test resource specification', defaultUnitOfEffort: resourceUnitId },
+    })
+    await pause(100)
+    const resourceSpecificationId = rsResp.resourceSpecification.id
+
+    const raise = (note) => ({
+      note,
+      action: 'raise',
+      resourceQuantity: { hasNumericalValue: 8, hasUnit: resourceUnitId },
+      resourceConformsTo: resourceSpecificationId,
+      ...testEventProps,
+    })
+    const adjust = (note, resourceInventoriedAs) => ({
+      note,
+      action: 'raise',
+      resourceInventoriedAs,
+      resourceQuantity: { hasNumericalValue: 1, hasUnit: resourceUnitId },
+      ...testEventProps,
+    })
+
+    // SCENARIO: interleave events between two resources
+    let resp = await observation.call('economic_event', 'create_economic_event', { event: raise('A created'), newInventoriedResource: { note: 'resource A' } })
+    await pause(100)
+    const resourceA = resp.economicResource.id
+    const event1 = resp.economicEvent.id
+
+    resp = await observation.call('economic_event', 'create_economic_event', { event: raise('B created'), newInventoriedResource: { note: 'resource B' } })
+    await pause(100)
+    const resourceB = resp.economicResource.id
+    const event2 = resp.economicEvent.id
+
+    resp = await observation.call('economic_event', 'create_economic_event', { event: adjust('A adjusted', resourceA) })
+    await pause(100)
+    const event3 = resp.economicEvent.id
+
+    resp = await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'move',
+        resourceInventoriedAs: resourceB,
+        toResourceInventoriedAs: resourceA,
+        resourceQuantity: { hasNumericalValue: 1, hasUnit: resourceUnitId },
+        ...testEventProps,
+      },
+    })
+    await pause(100)
+    const event4 = resp.economicEvent.id
+
+    // ASSERT: merged results are ordered by time without duplicates
+    resp = await observation.call('economic_event_index', 'query_economic_events_merged', {
+      index: 'affects',
+      baseAddresses: [resourceA, resourceB],
+    })
+    t.equal(resp.edges.length, 4, 'events linked from both resources returned once')
+    t.deepLooseEqual(resp.edges.map(e => e.node.id), [event4, event3, event2, event1], 'events interleaved by time, most recent first')
+
+    // ASSERT: pagination spans all resources
+    resp = await observation.call('economic_event_index', 'query_economic_events_merged', {
+      index: 'affects',
+      baseAddresses: [resourceA, resourceB],
+      last: 2,
+      before: event3[1],
+    })
+    t.deepLooseEqual(resp.edges.map(e => e.node.id), [event2, event1], 'page continues from cursor across resources')
+
+    // ASSERT: counts span all resources without duplicates
+    resp = await observation.call('economic_event_index', 'count_distinct_targets', {
+      index: 'affects',
+      baseAddresses: [resourceA, resourceB],
+    })
+    t.equal(resp, 4, 'event linked from both resources counted once')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
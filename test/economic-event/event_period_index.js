@@ -0,0 +1,94 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  mockAddress, mockIdentifier,
+  buildPlayer,
+} from '../init.js'
+
+const eventAt = (time) => ({
+  action: 'raise',
+  provider: mockAddress(),
+  receiver: mockAddress(),
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier() },
+  hasPointInTime: time,
+})
+
+test('EconomicEvents can be queried by reporting period', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+
+    const e1 = await observation.call('economic_event', 'create_economic_event', { event: eventAt('2024-02-14T10:00:00.000Z') })
+    const e2 = await observation.call('economic_event', 'create_economic_event', { event: eventAt('2024-02-29T10:00:00.000Z') })
+    await observation.call('economic_event', 'create_economic_event', { event: eventAt('2024-03-04T10:00:00.000Z') })
+    await pause(100)
+
+    let resp = await observation.call('economic_event_index', 'query_economic_events', { params: { period: 'period.2024-W07' } })
+    t.equal(resp.edges.length, 1, 'events in ISO week returned')
+    t.deepEqual(resp.edges[0].node.id, e1.economicEvent.id, 'correct event in week')
+
+    resp = await observation.call('economic_event_index', 'query_economic_events', { params: { period: 'period.2024-02' } })
+    t.equal(resp.edges.length, 2, 'events in calendar month returned')
+
+    resp = await observation.call('economic_event_index', 'query_economic_events', { params: { period: 'period.2023-02' } })
+    t.equal(resp.edges.length, 0, 'empty periods return no events')
+
+    await observation.call('economic_event', 'delete_economic_event', { revisionId: e2.economicEvent.revisionId })
+    await pause(100)
+
+    resp = await observation.call('economic_event_index', 'query_economic_events', { params: { period: 'period.2024-02' } })
+    t.equal(resp.edges.length, 1, 'deleted events removed from period index')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('EconomicEvents retain their offset and can be bucketed in a report timezone', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+
+    // 2024-03-01T01:30:00Z
+    const late = await observation.call('economic_event', 'create_economic_event', { event: eventAt('2024-02-29T23:30:00-02:00') })
+    const local = await observation.call('economic_event', 'create_economic_event', { event: eventAt('2024-03-01T09:00:00+05:30') })
+    await pause(100)
+
+    let resp = await observation.call('economic_event', 'get_economic_event', { address: local.economicEvent.id })
+    t.equal(resp.economicEvent.hasPointInTime, '2024-03-01T09:00:00+05:30', 'original offset returned verbatim')
+    resp = await observation.call('economic_event', 'get_economic_event', { address: late.economicEvent.id })
+    t.equal(resp.economicEvent.hasPointInTime, '2024-02-29T23:30:00-02:00', 'negative offset returned verbatim')
+
+    resp = await observation.call('economic_event_index', 'query_economic_events', { params: { period: 'period.2024-02' } })
+    t.equal(resp.edges.length, 1, 'period index buckets events in their own offset')
+
+    resp = await observation.call('economic_event_index', 'query_economic_events_in_period', { period: 'period.2024-02', reportTimezone: 'Z' })
+    t.equal(resp.edges.length, 0, 'no events fall in February as observed from UTC')
+
+    resp = await observation.call('economic_event_index', 'query_economic_events_in_period', { period: 'period.2024-03', reportTimezone: 'Z' })
+    t.equal(resp.edges.length, 2, 'both events fall in March as observed from UTC')
+
+    resp = await observation.call('economic_event_index', 'query_economic_events_in_period', { period: 'period.2024-02', reportTimezone: '-03:30' })
+    t.equal(resp.edges.length, 1, 'events re-bucketed in half-hour report offset')
+    t.deepEqual(resp.edges[0].node.id, late.economicEvent.id, 'correct event in report offset period')
+
+    try {
+      await observation.call('economic_event_index', 'query_economic_events_in_period', { period: 'period.2024-02', reportTimezone: 'AEST' })
+      t.fail('invalid report timezone should be rejected')
+    } catch (e) {
+      t.ok(e.message.match(/not a valid UTC offset/), 'invalid report timezone rejected')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
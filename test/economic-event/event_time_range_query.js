@@ -0,0 +1,78 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  mockAddress,
+  mockIdentifier,
+  buildPlayer,
+} from '../init.js'
+
+const worker = mockAddress(false)
+const otherAgent = mockAddress(false)
+
+const workEvent = (note, provider, hasPointInTime) => ({
+  note,
+  action: 'work',
+  provider,
+  receiver: otherAgent,
+  effortQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  resourceConformsTo: mockAddress(false),
+  hasPointInTime,
+})
+
+test('events can be queried by occurrence time range and field in one traversal', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+    const create = async (event) => (await observation.call('economic_event', 'create_economic_event', { event })).economicEvent.id
+
+    const from = '2019-11-01T00:00:00.000Z'
+    const until = '2019-12-01T00:00:00.000Z'
+
+    // recorded out of order, to ensure results are ordered by when events occurred
+    const second = await create(workEvent('second in range', worker, '2019-11-10T09:00:00.000Z'))
+    await create(workEvent('after range', worker, '2019-12-01T00:00:00.000Z'))
+    const first = await create(workEvent('first in range', worker, '2019-11-01T00:00:00.000Z'))
+    await create(workEvent('other agent in range', otherAgent, '2019-11-12T09:00:00.000Z'))
+    await create(workEvent('before range', worker, '2019-10-31T23:59:59.000Z'))
+    const third = await create({ ...workEvent('third in range', worker), hasBeginning: '2019-11-20T09:00:00.000Z', hasEnd: '2019-11-20T17:00:00.000Z' })
+    await pause(100)
+
+    let resp = await observation.call('economic_event_index', 'query_economic_events_between', { from, until, agent: worker })
+    t.deepEqual(resp.results.edges.map(e => e.node.id), [third, second, first], 'only matching events occurring within range returned, most recent first')
+    t.notOk(resp.nextCursor, 'single page covers range')
+
+    resp = await observation.call('economic_event_index', 'query_economic_events_between', { from, until, agent: worker, limit: 2 })
+    t.deepEqual(resp.results.edges.map(e => e.node.id), [third, second], 'page limited to matching events')
+    t.ok(resp.nextCursor, 'further pages indicated')
+
+    resp = await observation.call('economic_event_index', 'query_economic_events_between', { from, until, agent: worker, limit: 2, after: resp.nextCursor })
+    t.deepEqual(resp.results.edges.map(e => e.node.id), [first], 'next page continues over filtered set')
+    t.notOk(resp.nextCursor, 'range exhausted')
+
+    resp = await observation.call('economic_event_index', 'query_economic_events_between', { from, until })
+    t.equal(resp.results.edges.length, 4, 'all events in range returned without filters')
+
+    resp = await observation.call('economic_event_index', 'query_economic_events_between', { from, until, action: 'consume' })
+    t.equal(resp.results.edges.length, 0, 'events filtered by action')
+
+    resp = await observation.call('economic_event', 'get_economic_event', { address: second })
+    await observation.call('economic_event', 'update_economic_event', { event: { revisionId: resp.economicEvent.revisionId, hasPointInTime: '2019-11-25T09:00:00.000Z' } })
+    await pause(100)
+    resp = await observation.call('economic_event_index', 'query_economic_events_between', { from, until, agent: worker })
+    t.deepEqual(resp.results.edges.map(e => e.node.id), [second, third, first], 'events reordered when their occurrence time changes')
+
+    try {
+      await observation.call('economic_event_index', 'query_economic_events_between', { from, until, after: mockAddress(false)[1] })
+      t.fail('unknown cursor should be rejected')
+    } catch (err) {
+      t.ok(err.message.includes('VALIDATION'), 'unknown cursor reported as a validation error')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,46 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  mockAddress, mockIdentifier,
+  buildPlayer,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  provider: mockAddress(),
+  receiver: mockAddress(),
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier() },
+  hasPointInTime: '2024-02-14T10:00:00.000Z',
+}
+
+test('EconomicEvent indexes can be warmed prior to querying', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+
+    for (let i = 0; i < 3; i++) {
+      await observation.call('economic_event', 'create_economic_event', { event: testEventProps })
+    }
+    await pause(100)
+
+    let resp = await observation.call('economic_event_index', 'warm_indexes', { types: ['economic_event', 'process'], depth: 2 })
+    t.deepEqual(resp.touched.map(i => i.recordType), ['economic_event', 'process'], 'requested types warmed in order')
+    t.ok(resp.touched[0].linksTouched >= 3, 'root and recent event index links touched')
+    t.equal(resp.touched[1].linksTouched, 0, 'empty indexes touch no links')
+    t.deepEqual(resp.skipped, [], 'no types deferred')
+
+    const again = await observation.call('economic_event_index', 'warm_indexes', { types: ['economic_event', 'process'], depth: 2 })
+    t.deepEqual(again, resp, 'repeated warming is stable')
+
+    resp = await observation.call('economic_event_index', 'query_economic_events', { params: { period: 'period.2024-W07' } })
+    t.equal(resp.edges.length, 3, 'indexes still readable after warming')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
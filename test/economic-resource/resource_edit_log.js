@@ -0,0 +1,60 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  provider: mockAddress(),
+  receiver: mockAddress(),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+  resourceClassifiedAs: ['test-classification'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier() },
+}
+
+test('EconomicResource edit log attributes changed fields to each revision', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+
+    let resp = await observation.call('economic_event', 'create_economic_event', {
+      event: testEventProps,
+      newInventoriedResource: { name: 'shared bicycle', note: 'good condition' },
+    })
+    const resourceId = resp.economicResource.id
+    await pause(100)
+
+    resp = await observation.call('economic_resource', 'update_economic_resource', {
+      resource: { revisionId: resp.economicResource.revisionId, note: 'scratched frame' },
+    })
+    await pause(100)
+    await observation.call('economic_resource', 'update_economic_resource', {
+      resource: { revisionId: resp.economicResource.revisionId, image: 'https://example.com/bicycle.jpg' },
+    })
+    await pause(100)
+
+    const log = await observation.call('economic_resource', 'get_resource_edit_log', { address: resourceId })
+    t.equal(log.length, 3, 'one entry per revision')
+    t.ok(log[0].changes.find(c => c.field === 'note' && c.newValue === 'good condition'), 'initial revision reports fields set on creation')
+    t.ok(log[0].changes.find(c => c.field === 'classifiedAs'), 'field names reported in camelCase')
+    t.notOk(log[0].changes.find(c => c.field === 'accountingQuantity' || c.field === 'onhandQuantity'), 'derived quantities not reported')
+
+    t.deepEqual(log[1].changes.map(c => c.field), ['note'], 'only changed fields reported')
+    t.equal(log[1].changes[0].oldValue, 'good condition', 'previous value reported')
+    t.equal(log[1].changes[0].newValue, 'scratched frame', 'new value reported')
+    t.ok(log[1].revision.agentPubKey, 'author of change reported')
+    t.ok(new Date(log[1].revision.time) > new Date('2020-01-01'), 'time of change reported')
+
+    t.deepEqual(log[2].changes.map(c => c.field), ['image'], 'subsequent changes diffed against preceding revision')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,85 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  mockAddress, mockIdentifier,
+  buildPlayer,
+} from '../init.js'
+
+const testEventProps = {
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('EconomicResources & EconomicEvents can be queried by location', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'specification'])
+  try {
+    const { cells: [observation, specification] } = alice
+
+    const unit = mockIdentifier(false)
+    const warehouseA = mockAddress(false)
+    const warehouseB = mockAddress(false)
+
+    const rsResp = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: { name: 'pallets' },
+    })
+    const rsId = rsResp.resourceSpecification.id
+
+    const raise = (hasNumericalValue) => ({
+      action: 'raise',
+      resourceConformsTo: rsId,
+      resourceQuantity: { hasNumericalValue, hasUnit: unit },
+      ...testEventProps,
+    })
+    const sourceResp = await observation.call('economic_event', 'create_economic_event', {
+      event: raise(10),
+      newInventoriedResource: { note: 'stock at A', currentLocation: warehouseA },
+    })
+    const source = sourceResp.economicResource
+    const destResp = await observation.call('economic_event', 'create_economic_event', {
+      event: raise(0),
+      newInventoriedResource: { note: 'unlocated stock' },
+    })
+    const dest = destResp.economicResource
+    await pause(100)
+
+    let resp = await observation.call('economic_resource_index', 'query_economic_resources', { params: { atLocation: warehouseA } })
+    t.equal(resp.edges.length, 1, 'resources queryable by initial location')
+    t.deepEqual(resp.edges[0].node.id, source.id, 'correct resource located')
+
+    let summary = await observation.call('economic_resource_index', 'read_inventory_summary', { conformsTo: [rsId], atLocation: warehouseA })
+    t.equal(summary[0].onhandQuantities[0].hasNumericalValue, 10, 'inventory summary restricted to location')
+
+    // SCENARIO: move resource to another location
+    const moveResp = await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'move',
+        resourceInventoriedAs: source.id,
+        toResourceInventoriedAs: dest.id,
+        atLocation: warehouseB,
+        resourceQuantity: { hasNumericalValue: 1, hasUnit: unit },
+        ...testEventProps,
+      },
+    })
+    await pause(100)
+
+    resp = await observation.call('economic_resource_index', 'query_economic_resources', { params: { atLocation: warehouseA } })
+    t.equal(resp.edges.length, 0, 'moved resource unlinked from previous location')
+    resp = await observation.call('economic_resource_index', 'query_economic_resources', { params: { atLocation: warehouseB } })
+    t.deepEqual(resp.edges.map(e => e.node.id).sort(), [source.id, dest.id].sort(), 'moved resources linked at new location')
+
+    summary = await observation.call('economic_resource_index', 'read_inventory_summary', { conformsTo: [rsId], atLocation: warehouseA })
+    t.equal(summary[0].onhandQuantities.length, 0, 'no inventory left at previous location')
+
+    resp = await observation.call('economic_event_index', 'query_economic_events', { params: { atLocation: warehouseB } })
+    t.equal(resp.edges.length, 1, 'events queryable by location')
+    t.deepEqual(resp.edges[0].node.id, moveResp.economicEvent.id, 'correct event located')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
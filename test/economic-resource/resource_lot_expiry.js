@@ -0,0 +1,71 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  mockAddress, mockIdentifier,
+  buildPlayer,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  provider: mockAddress(),
+  receiver: mockAddress(),
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier() },
+  hasPointInTime: '2019-11-19T04:29:55.000Z',
+}
+
+test('EconomicResource lots can be queried by expiry', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+
+    const lotResp = await observation.call('economic_event', 'create_economic_event', {
+      event: testEventProps,
+      newInventoriedResource: {
+        note: 'expiring lot',
+        lotDetails: { batchNumber: 'B-001', expiryDate: '2020-01-01T00:00:00.000Z' },
+      },
+    })
+    const lotResource = lotResp.economicResource
+    t.equal(lotResource.lotDetails.batchNumber, 'B-001', 'lot details stored on resource')
+
+    const untrackedResp = await observation.call('economic_event', 'create_economic_event', {
+      event: testEventProps,
+      newInventoriedResource: { note: 'untracked resource' },
+    })
+    t.ok(untrackedResp.economicResource.id, 'resource without lot tracking created')
+    await pause(100)
+
+    let resp = await observation.call('economic_resource_index', 'query_economic_resources_expiring_before', {
+      expiringBefore: '2020-06-01T00:00:00.000Z',
+    })
+    t.equal(resp.edges.length, 1, 'only lot-tracked resources returned')
+    t.deepEqual(resp.edges[0].node.id, lotResource.id, 'resource expiring before cutoff returned')
+
+    resp = await observation.call('economic_resource_index', 'query_economic_resources_expiring_before', {
+      expiringBefore: '2019-06-01T00:00:00.000Z',
+    })
+    t.equal(resp.edges.length, 0, 'resources expiring after cutoff excluded')
+
+    // SCENARIO: expiry date corrected
+    await observation.call('economic_resource', 'update_economic_resource', {
+      resource: {
+        revisionId: lotResource.revisionId,
+        lotDetails: { batchNumber: 'B-001', expiryDate: '2021-01-01T00:00:00.000Z' },
+      },
+    })
+    await pause(100)
+
+    resp = await observation.call('economic_resource_index', 'query_economic_resources_expiring_before', {
+      expiringBefore: '2020-06-01T00:00:00.000Z',
+    })
+    t.equal(resp.edges.length, 0, 'expiry index updated when lot expiry changes')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,92 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const unit = mockIdentifier()
+const testEventProps = {
+  provider: mockAddress(),
+  receiver: mockAddress(),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('EconomicResource on-hand balance is counted incrementally and reconciled against events', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+    const createEvent = (event, extra = {}) => observation.call('economic_event', 'create_economic_event', { event: { ...testEventProps, ...event }, ...extra })
+
+    let resp = await createEvent(
+      { action: 'raise', resourceClassifiedAs: ['test-classification'], resourceQuantity: { hasNumericalValue: 10, hasUnit: unit } },
+      { newInventoriedResource: { name: 'grain store' } },
+    )
+    const resourceId = resp.economicResource.id
+    await pause(100)
+
+    const consume = (qty) => createEvent({ action: 'lower', resourceInventoriedAs: resourceId, resourceQuantity: { hasNumericalValue: qty, hasUnit: unit } })
+    resp = await consume(1)
+    const firstConsumed = resp.economicEvent.revisionId
+    await Promise.all([consume(2), consume(3)])
+    await pause(100)
+
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.equal(resp.economicResource.onhandQuantity.hasNumericalValue, 4, 'concurrent events all counted')
+
+    await observation.call('economic_event', 'delete_economic_event', { revisionId: firstConsumed })
+    await pause(100)
+
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.equal(resp.economicResource.onhandQuantity.hasNumericalValue, 5, 'deleted event reversed from balance')
+
+    resp = await observation.call('economic_resource', 'reconcile_onhand_balance', { address: resourceId })
+    t.equal(resp.recomputed.hasNumericalValue, 5, 'full recompute reflects all remaining events')
+    t.equal(resp.counted.hasNumericalValue, resp.recomputed.hasNumericalValue, 'incremental counter matches full recompute')
+    await pause(100)
+
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.equal(resp.economicResource.onhandQuantity.hasNumericalValue, 5, 'balance unchanged after reconciliation')
+
+    resp = await consume(1)
+    const reversedTwice = resp.economicEvent.revisionId
+    await pause(100)
+    resp = await observation.call('economic_resource', 'reconcile_onhand_balance', { address: resourceId })
+    t.equal(resp.counted.hasNumericalValue, 4, 'event recorded after reconciliation counted once')
+    t.equal(resp.recomputed.hasNumericalValue, 4, 'event recorded after reconciliation recomputed once')
+    await pause(100)
+
+    await observation.call('economic_resource', '_internal_reverse_inventory', { revisionId: reversedTwice })
+    await observation.call('economic_resource', '_internal_reverse_inventory', { revisionId: reversedTwice })
+    await pause(100)
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.equal(resp.economicResource.onhandQuantity.hasNumericalValue, 5, 'repeated reversal of an event applied once')
+    resp = await observation.call('economic_resource', 'reconcile_onhand_balance', { address: resourceId })
+    t.equal(resp.recomputed.hasNumericalValue, 5, 'reversed event excluded from recompute')
+    t.equal(resp.counted.hasNumericalValue, 5, 'reversal counted once')
+    await pause(100)
+
+    // enough deltas to compact the counter into a new checkpoint
+    for (let i = 0; i < 34; i++) {
+      await createEvent({ action: 'raise', resourceInventoriedAs: resourceId, resourceQuantity: { hasNumericalValue: 1, hasUnit: unit } })
+    }
+    await pause(100)
+    resp = await observation.call('economic_resource', 'reconcile_onhand_balance', { address: resourceId })
+    t.equal(resp.counted.hasNumericalValue, 39, 'compacted counter matches events')
+    t.equal(resp.recomputed.hasNumericalValue, 39, 'compacted counter matches full recompute')
+    await pause(100)
+
+    resp = await observation.call('economic_resource_index', 'read_all_economic_resources', {})
+    t.equal(resp.edges[0].node.onhandQuantity.hasNumericalValue, 39, 'counter used in list responses')
+    resp = await observation.call('economic_resource', 'update_economic_resource', { resource: { revisionId: resp.edges[0].node.revisionId, note: 'updated' } })
+    t.equal(resp.economicResource.onhandQuantity.hasNumericalValue, 39, 'counter used in update responses')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
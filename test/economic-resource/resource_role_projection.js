@@ -0,0 +1,85 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  getDNAProperties,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  provider: mockAddress(),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+  resourceClassifiedAs: ['test-classification'],
+  resourceQuantity: { hasNumericalValue: 3, hasUnit: mockIdentifier() },
+}
+
+const restrictedProperties = {
+  economic_resource: { ...getDNAProperties('observation').economic_resource, restrict_fields_by_role: true },
+}
+
+test('EconomicResource fields visible to agents represented by its primary accountable', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'agent'], undefined, { observation: restrictedProperties })
+  try {
+    const { cells: [observation, agent] } = alice
+
+    const { agent: { id: agentId } } = await agent.call('agent', 'create_agent', { agent: { name: 'alice', agentType: 'Person' } })
+    await agent.call('agent', 'associate_my_agent', { agentAddress: agentId })
+    await pause(100)
+
+    let resp = await observation.call('economic_event', 'create_economic_event', {
+      event: { ...testEventProps, receiver: agentId },
+      newInventoriedResource: { name: 'bicycle parts', note: 'spares' },
+    })
+    const resourceId = resp.economicResource.id
+    await pause(100)
+
+    resp = await observation.call('economic_resource', 'get_economic_resource_for_caller', { address: resourceId })
+    t.deepEqual(resp.id, resourceId, 'record identified')
+    t.equal(resp.role, 'owner', 'agent represented by the primary accountable reads as its owner')
+    t.equal(resp.fields.name, 'bicycle parts', 'public fields returned')
+    t.equal(resp.fields.onhandQuantity.hasNumericalValue, 3, 'owner-only fields returned')
+
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.equal(resp.economicResource.onhandQuantity.hasNumericalValue, 3, 'owner-only fields returned by standard reads')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('EconomicResource owner-only fields hidden from the public', async (t) => {
+  const alice = await buildPlayer(['observation'], undefined, { observation: restrictedProperties })
+  try {
+    const { cells: [observation] } = alice
+
+    let resp = await observation.call('economic_event', 'create_economic_event', {
+      event: { ...testEventProps, receiver: mockAddress() },
+      newInventoriedResource: { name: 'bicycle parts', note: 'spares' },
+    })
+    const resourceId = resp.economicResource.id
+    await pause(100)
+
+    resp = await observation.call('economic_resource', 'get_economic_resource_for_caller', { address: resourceId })
+    t.equal(resp.role, 'public', 'author of the resource is not its owner unless represented by its primary accountable')
+    t.equal(resp.fields.name, 'bicycle parts', 'public fields returned')
+    t.equal(resp.fields.note, 'spares', 'public fields returned')
+    t.ok(resp.fields.primaryAccountable, 'public fields named as in the record response')
+    t.equal(resp.fields.onhandQuantity, undefined, 'on-hand quantity hidden')
+    t.equal(resp.fields.accountingQuantity, undefined, 'accounting quantity hidden')
+    t.equal(resp.fields._nonce, undefined, 'entry internals never returned')
+
+    resp = await observation.call('economic_resource', 'get_economic_resource', { address: resourceId })
+    t.equal(resp.economicResource.name, 'bicycle parts', 'public fields returned by standard reads')
+    t.equal(resp.economicResource.onhandQuantity, undefined, 'owner-only fields hidden from standard reads')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
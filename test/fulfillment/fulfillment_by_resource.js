@@ -0,0 +1,69 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('fulfillments can be queried via the events affecting a resource', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning', 'observation'])
+  try {
+    const { cells: [planning, observation] } = alice
+
+    let resp = await observation.call('economic_event', 'create_economic_event', {
+      event: { note: 'inventory created', ...testEventProps },
+      newInventoriedResource: { name: 'stock' },
+    })
+    const resourceId = resp.economicResource.id
+    const event1 = resp.economicEvent.id
+    resp = await observation.call('economic_event', 'create_economic_event', {
+      event: { note: 'inventory topped up', resourceInventoriedAs: resourceId, ...testEventProps },
+    })
+    const event2 = resp.economicEvent.id
+    resp = await observation.call('economic_event', 'create_economic_event', {
+      event: { note: 'inventory recount, fulfilling nothing', resourceInventoriedAs: resourceId, ...testEventProps },
+    })
+    await pause(100)
+
+    resp = await observation.call('fulfillment_index', 'query_resource_fulfillments', { resource: resourceId })
+    t.equal(resp.edges.length, 0, 'resource with no fulfilled events has no fulfillments')
+
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { note: 'first commitment', ...testEventProps } })
+    const c1 = resp.commitment.id
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { note: 'second commitment', ...testEventProps } })
+    const c2 = resp.commitment.id
+    await pause(100)
+
+    const fulfillmentIds = []
+    for (const [fulfills, fulfilledBy] of [[c1, event1], [c2, event1], [c2, event2]]) {
+      resp = await planning.call('fulfillment', 'create_fulfillment', { fulfillment: { fulfills, fulfilledBy } })
+      fulfillmentIds.push([fulfilledBy[0], resp.fulfillment.id[1]])
+    }
+    await pause(100)
+
+    resp = await observation.call('fulfillment_index', 'query_resource_fulfillments', { resource: resourceId })
+    const returned = resp.edges.map(e => e.node.id)
+    t.equal(returned.length, 3, 'fulfillments of every affecting event returned')
+    t.equal(new Set(returned.map(id => id[1].toString())).size, 3, 'no fulfillment returned twice')
+    for (const id of fulfillmentIds) {
+      t.ok(returned.some(r => r[1].toString() === id[1].toString()), 'fulfillment present in results')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,181 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+async function createFulfilledCommitment (t, planning, observation) {
+  const commitmentResponse = await planning.call('commitment', 'create_commitment', {
+    commitment: { note: 'a commitment to provide something', ...testEventProps },
+  })
+  t.ok(commitmentResponse.commitment && commitmentResponse.commitment.id, 'commitment created successfully')
+  await pause(100)
+
+  const eventResp = await observation.call('economic_event', 'create_economic_event', {
+    event: { note: 'test event which is fulfilling a commitment', ...testEventProps, action: 'produce' },
+  })
+  t.ok(eventResp.economicEvent && eventResp.economicEvent.id, 'event created successfully')
+  await pause(100)
+
+  const fulfillmentResp = await planning.call('fulfillment', 'create_fulfillment', {
+    fulfillment: {
+      fulfills: commitmentResponse.commitment.id,
+      fulfilledBy: eventResp.economicEvent.id,
+      note: 'fulfillment indicating the relationship',
+    },
+  })
+  t.ok(fulfillmentResp.fulfillment && fulfillmentResp.fulfillment.id, 'fulfillment created successfully')
+  await pause(100)
+
+  return {
+    commitment: commitmentResponse.commitment,
+    event: eventResp.economicEvent,
+    fulfillment: fulfillmentResp.fulfillment,
+  }
+}
+
+test('records with dependents cannot be deleted without cascade', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning', 'observation'])
+  try {
+    const { cells: [planning, observation] } = alice
+
+    const { commitment, event, fulfillment } = await createFulfilledCommitment(t, planning, observation)
+
+    try {
+      await planning.call('commitment', 'delete_commitment', { revisionId: commitment.revisionId })
+      t.fail('deletion should be refused')
+    } catch (err) {
+      t.ok(err.message.includes('Record has dependents and cannot be deleted without cascade'), 'commitment deletion refused')
+      t.ok(err.message.includes('1 fulfillments'), 'dependent counts reported')
+    }
+
+    try {
+      await observation.call('economic_event', 'delete_economic_event', { revisionId: event.revisionId })
+      t.fail('deletion should be refused')
+    } catch (err) {
+      t.ok(err.message.includes('Record has dependents and cannot be deleted without cascade'), 'event deletion refused')
+    }
+    await pause(100)
+
+    const readResponse = await planning.call('commitment', 'get_commitment', { address: commitment.id })
+    t.deepLooseEqual(readResponse.commitment.fulfilledBy, [fulfillment.id], 'commitment and dependents remain intact')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('cascading deletes remove dependents and their reciprocal indexes', async (t) => {
+  const alice = await buildPlayer(['planning', 'observation'])
+  try {
+    const { cells: [planning, observation] } = alice
+
+    const { commitment, event, fulfillment } = await createFulfilledCommitment(t, planning, observation)
+
+    const delResp = await planning.call('commitment', 'delete_commitment', { revisionId: commitment.revisionId, cascade: true })
+    t.equal(delResp.deleted, true, 'commitment deleted')
+    t.equal(delResp.removedDependents.length, 1, 'removed dependents reported')
+    t.equal(delResp.removedDependents[0].recordType, 'fulfillments', 'removed dependent type reported')
+    t.deepLooseEqual(delResp.removedDependents[0].address, fulfillment.id[1], 'removed dependent address reported')
+    await pause(100)
+
+    try {
+      await planning.call('fulfillment', 'get_fulfillment', { address: fulfillment.id })
+      t.fail('fulfillment should be removed')
+    } catch (err) {
+      t.ok(err.message.includes('No entry at this address'), 'dependent fulfillment removed')
+    }
+
+    const readResponse = await observation.call('economic_event', 'get_economic_event', { address: event.id })
+    t.equal(readResponse.economicEvent.fulfills, undefined, 'reciprocal index in observation DNA removed')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('cascading event deletes remove the originating records in the planning DNA', async (t) => {
+  const alice = await buildPlayer(['planning', 'observation'])
+  try {
+    const { cells: [planning, observation] } = alice
+
+    const { commitment, event, fulfillment } = await createFulfilledCommitment(t, planning, observation)
+
+    const delResp = await observation.call('economic_event', 'delete_economic_event', { revisionId: event.revisionId, cascade: true })
+    t.equal(delResp.deleted, true, 'event deleted')
+    t.equal(delResp.removedDependents.length, 1, 'removed dependents reported')
+    await pause(100)
+
+    try {
+      await planning.call('fulfillment', 'get_fulfillment', { address: fulfillment.id })
+      t.fail('originating fulfillment should be removed')
+    } catch (err) {
+      t.ok(err.message.includes('No entry at this address'), 'originating fulfillment removed')
+    }
+
+    const readResponse = await planning.call('commitment', 'get_commitment', { address: commitment.id })
+    t.equal(readResponse.commitment.fulfilledBy, undefined, 'commitment index in planning DNA removed')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('cascading deletes report partial failures without deleting the record', async (t) => {
+  const alice = await buildPlayer(['planning', 'observation'])
+  try {
+    const { cells: [planning, observation] } = alice
+
+    const { commitment, fulfillment } = await createFulfilledCommitment(t, planning, observation)
+
+    // SCENARIO: inject a dangling reference to a nonexistent fulfillment
+    const missingFulfillmentId = mockAddress(false)
+    await planning.call('commitment_index', '_internal_index_commitment_fulfilled_by', {
+      remote_entry: missingFulfillmentId,
+      target_entries: [commitment.id],
+      removed_entries: [],
+    })
+    await pause(100)
+
+    try {
+      await planning.call('commitment', 'delete_commitment', { revisionId: commitment.revisionId, cascade: true })
+      t.fail('cascading delete should fail')
+    } catch (err) {
+      t.ok(err.message.includes('Cascading delete failed, record not deleted'), 'partial failure reported')
+      t.ok(err.message.match(/Removed: \[fulfillments [^\]]+\]/), 'successfully removed dependents reported')
+      t.ok(err.message.match(/Failed: \[fulfillments [^\]]+\(.+\)\]/), 'failed dependents reported with reasons')
+    }
+    await pause(100)
+
+    const readResponse = await planning.call('commitment', 'get_commitment', { address: commitment.id })
+    t.ok(readResponse.commitment, 'record remains after failed cascade')
+
+    try {
+      await planning.call('fulfillment', 'get_fulfillment', { address: fulfillment.id })
+      t.fail('valid dependent should have been removed')
+    } catch (err) {
+      t.ok(err.message.includes('No entry at this address'), 'valid dependents removed before failure')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
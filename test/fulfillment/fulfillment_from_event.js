@@ -0,0 +1,77 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('Fulfillments can be created from the observation side', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning', 'observation'])
+  try {
+    const { cells: [planning, observation] } = alice
+
+    // SCENARIO: event recorded before the commitment it fulfills is known
+    const eventResp = await observation.call('economic_event', 'create_economic_event', {
+      event: { note: 'event recorded in the field', ...testEventProps },
+    })
+    const eventId = eventResp.economicEvent.id
+    await pause(100)
+
+    const commitmentResp = await planning.call('commitment', 'create_commitment', {
+      commitment: { note: 'commitment linked later', ...testEventProps },
+    })
+    const commitmentId = commitmentResp.commitment.id
+    await pause(100)
+
+    const fulfillmentResp = await observation.call('fulfillment', 'create_fulfillment_from_event', {
+      fulfillment: {
+        fulfilledBy: eventId,
+        fulfills: commitmentId,
+        resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+        note: 'linked from observation app',
+      },
+    })
+    const fulfillment = fulfillmentResp.fulfillment
+    t.ok(fulfillment.id, 'fulfillment created from observation side')
+    await pause(100)
+
+    let readResp = await observation.call('economic_event', 'get_economic_event', { address: eventId })
+    t.equal(readResp.economicEvent.fulfills.length, 1, 'fulfillment indexed against event')
+    t.deepLooseEqual(readResp.economicEvent.fulfills[0], fulfillment.id, 'EconomicEvent.fulfills reference OK in observation DNA')
+
+    readResp = await planning.call('commitment', 'get_commitment', { address: commitmentId })
+    t.equal(readResp.commitment.fulfilledBy.length, 1, 'fulfillment replicated & indexed against commitment in planning DNA')
+    const fulfillmentIdPlanning = readResp.commitment.fulfilledBy[0]
+    t.deepLooseEqual(fulfillmentIdPlanning[1], fulfillment.id[1], 'planning & observation records share an identity')
+
+    readResp = await planning.call('fulfillment', 'get_fulfillment', { address: fulfillmentIdPlanning })
+    t.equal(readResp.fulfillment.note, 'linked from observation app', 'fulfillment fields saved in planning DNA')
+
+    // SCENARIO: repeat request for the same event & commitment
+    const repeatResp = await observation.call('fulfillment', 'create_fulfillment_from_event', {
+      fulfillment: { fulfilledBy: eventId, fulfills: commitmentId },
+    })
+    t.deepEqual(repeatResp.fulfillment.id, fulfillment.id, 'existing fulfillment returned instead of duplicating')
+    await pause(100)
+
+    readResp = await planning.call('commitment', 'get_commitment', { address: commitmentId })
+    t.equal(readResp.commitment.fulfilledBy.length, 1, 'no duplicate fulfillment created in planning DNA')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,82 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('fulfillment index zomes describe their managed indexes', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'planning'])
+  try {
+    const { cells: [observation, planning] } = alice
+
+    const planningIndexes = await planning.call('fulfillment_index', 'list_managed_indexes', null)
+    t.deepEqual(planningIndexes, [{
+      name: 'fulfills',
+      recordType: 'fulfillment',
+      relatedRecordType: 'commitment',
+      linkTag: 'fulfillment_fulfills',
+      reciprocalLinkTag: 'commitment_fulfilled_by',
+      timeIndex: 'fulfillment_fulfills.indexed',
+      updateMethod: '_internal_index_fulfillment_fulfills',
+    }], 'planning DNA fulfillment indexes described')
+
+    const observationIndexes = await observation.call('fulfillment_index', 'list_managed_indexes', null)
+    t.deepEqual(observationIndexes, [{
+      name: 'fulfilled_by',
+      recordType: 'fulfillment',
+      relatedRecordType: 'economic_event',
+      linkTag: 'fulfillment_fulfilled_by',
+      reciprocalLinkTag: 'economic_event_fulfills',
+      timeIndex: 'fulfillment_fulfilled_by.indexed',
+      updateMethod: '_internal_index_fulfillment_fulfilled_by',
+    }], 'observation DNA fulfillment indexes described')
+
+    // SCENARIO: described indexes are the ones populated when records are linked
+    const eventResp = await observation.call('economic_event', 'create_economic_event', {
+      event: { ...testEventProps, action: 'produce' },
+    })
+    const eventId = eventResp.economicEvent.id
+    const commitmentResp = await planning.call('commitment', 'create_commitment', {
+      commitment: testEventProps,
+    })
+    const commitmentId = commitmentResp.commitment.id
+    await pause(100)
+
+    const fulfillmentResp = await observation.call('fulfillment', 'create_fulfillment_from_event', {
+      fulfillment: { fulfilledBy: eventId, fulfills: commitmentId },
+    })
+    const fulfillmentId = fulfillmentResp.fulfillment.id
+    await pause(100)
+
+    const readResp = await planning.call('commitment', 'get_commitment', { address: commitmentId })
+    const planningFulfillmentId = readResp.commitment.fulfilledBy[0]
+
+    for (const [cell, address, descriptors, expected] of [
+      [planning, planningFulfillmentId, planningIndexes, commitmentId],
+      [observation, fulfillmentId, observationIndexes, eventId],
+    ]) {
+      for (const { recordType, name } of descriptors) {
+        const related = await cell.call('fulfillment_index', `_internal_read_${recordType}_${name}`, { address })
+        t.deepEqual(related, [expected], `${recordType}.${name} index populated`)
+      }
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,85 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('Fulfillment replicas can be checked and repaired across DNAs', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning', 'observation'])
+  try {
+    const { cells: [planning, observation] } = alice
+
+    let resp = await observation.call('economic_event', 'create_economic_event', { event: testEventProps })
+    const eventId = resp.economicEvent.id
+    resp = await planning.call('commitment', 'create_commitment', { commitment: testEventProps })
+    const commitmentId = resp.commitment.id
+    await pause(100)
+
+    const fulfillmentIds = []
+    for (const note of ['first', 'second']) {
+      resp = await planning.call('fulfillment', 'create_fulfillment', {
+        fulfillment: { fulfilledBy: eventId, fulfills: commitmentId, note },
+      })
+      fulfillmentIds.push(resp.fulfillment.id)
+    }
+    await pause(100)
+
+    // ASSERT: consistent replicas report no discrepancies
+    resp = await planning.call('fulfillment', 'check_cross_dna_integrity', { recordType: 'fulfillment' })
+    t.equal(resp.checked, 2, 'all records checked')
+    t.deepEqual([resp.missingRemoteRecord, resp.missingRemoteIndex, resp.remoteHasExtra], [[], [], []], 'no discrepancies found')
+    t.deepEqual(resp.failures, [], 'every record checked')
+    t.notOk(resp.nextCursor, 'single batch covers all records')
+
+    // ASSERT: batches resume from cursor
+    resp = await planning.call('fulfillment', 'check_cross_dna_integrity', { recordType: 'fulfillment', batchSize: 1 })
+    t.equal(resp.checked, 1, 'batch size respected')
+    resp = await planning.call('fulfillment', 'check_cross_dna_integrity', { recordType: 'fulfillment', batchSize: 1, cursor: resp.nextCursor })
+    t.equal(resp.checked, 1, 'next batch continues from cursor')
+    t.notOk(resp.nextCursor, 'final batch has no cursor')
+
+    // SCENARIO: observation copy lost
+    resp = await observation.call('economic_event', 'get_economic_event', { address: eventId })
+    const lostReplica = resp.economicEvent.fulfills.find(id => id[1].toString() === fulfillmentIds[0][1].toString())
+    resp = await observation.call('fulfillment', 'get_fulfillment', { address: lostReplica })
+    await observation.call('fulfillment', 'fulfillment_deleted', { revisionId: resp.fulfillment.revisionId })
+    await pause(100)
+
+    resp = await planning.call('fulfillment', 'check_cross_dna_integrity', { recordType: 'fulfillment' })
+    t.deepLooseEqual(resp.missingRemoteRecord, [fulfillmentIds[0]], 'missing replica reported')
+
+    // ASSERT: report output repairs the replica
+    resp = await planning.call('fulfillment', 'replicate_records', { addresses: resp.missingRemoteRecord })
+    t.equal(resp, 1, 'missing replica re-sent')
+    await pause(100)
+
+    resp = await planning.call('fulfillment', 'check_cross_dna_integrity', { recordType: 'fulfillment' })
+    t.deepEqual(resp.missingRemoteRecord, [], 'replica restored')
+    t.deepEqual(resp.missingRemoteIndex, [], 'replica indexed against event')
+
+    try {
+      await planning.call('fulfillment', 'check_cross_dna_integrity', { recordType: 'satisfaction' })
+      t.fail('unsupported record type accepted')
+    } catch (e) {
+      t.ok(e.toString().match('not supported'), 'unsupported record type rejected')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,134 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  getDNAProperties,
+  serializeHash,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('commitments fulfilled in observation DNAs cannot be deleted', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning', 'observation'])
+  try {
+    const { cells: [planning, observation] } = alice
+
+    let resp = await observation.call('process', 'create_process', { process: { name: 'batch' } })
+    const processId = resp.process.id
+    resp = await observation.call('economic_event', 'create_economic_event', { event: testEventProps })
+    const eventId = resp.economicEvent.id
+    await pause(100)
+
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { ...testEventProps, inputOf: processId } })
+    const fulfilled = resp.commitment
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { ...testEventProps, inputOf: processId } })
+    const unreferenced = resp.commitment
+    await pause(100)
+
+    // SCENARIO: fulfillment only recorded in the observation DNA, as remains when replicating a delete fails
+    await observation.call('fulfillment', 'fulfillment_created', {
+      fulfillment: { fulfills: fulfilled.id, fulfilledBy: eventId, note: 'unreplicated fulfillment' },
+    })
+    await pause(100)
+
+    try {
+      await planning.call('commitment', 'delete_commitment', { revisionId: fulfilled.revisionId })
+      t.fail('deletion should be refused')
+    } catch (err) {
+      t.ok(err.message.includes('Record has dependents and cannot be deleted without cascade'), 'fulfilled commitment deletion refused')
+      t.ok(err.message.includes('1 observation fulfillments'), 'observation DNA dependents reported')
+    }
+
+    try {
+      await planning.call('commitment', 'delete_commitment', { revisionId: fulfilled.revisionId, cascade: true })
+      t.fail('cascading deletion should be refused')
+    } catch (err) {
+      t.ok(err.message.includes('1 observation fulfillments'), 'observation DNA dependents not cascaded unless configured')
+    }
+
+    resp = await planning.call('commitment', 'get_commitment', { address: fulfilled.id })
+    t.ok(resp.commitment, 'fulfilled commitment remains')
+
+    resp = await planning.call('commitment', 'delete_commitment', { revisionId: unreferenced.revisionId })
+    t.equal(resp.deleted, true, 'unreferenced commitment deleted')
+
+    // SCENARIO: commitment referencing a DNA which cannot be reached
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { ...testEventProps, inputOf: mockAddress(false) } })
+    const unverifiable = resp.commitment
+    await pause(100)
+
+    try {
+      await planning.call('commitment', 'delete_commitment', { revisionId: unverifiable.revisionId })
+      t.fail('deletion should be refused')
+    } catch (err) {
+      t.ok(err.message.includes('could not be checked, record not deleted'), 'deletion refused when dependents cannot be checked')
+    }
+
+    try {
+      await planning.call('commitment', 'force_delete_commitment', { revisionId: unverifiable.revisionId })
+      t.fail('forced deletion should be refused to non-admins')
+    } catch (err) {
+      t.ok(err.message.includes('UNAUTHORIZED'), 'forced deletion restricted to admins')
+    }
+
+    await planning.call('network_settings', 'create_network_settings', { networkSettings: {} })
+    await pause(100)
+
+    resp = await planning.call('commitment', 'force_delete_commitment', { revisionId: unverifiable.revisionId })
+    t.equal(resp.deleted, true, 'admins can delete commitments referencing unreachable DNAs')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('configured observation DNAs are checked for fulfillments of commitments without references', async (t) => {
+  // determine the observation DNA hash, which does not depend on the planning DNA's properties
+  const probe = await buildPlayer(['observation'])
+  const observationDna = serializeHash(probe.cells[0].cell_id[0])
+  await probe.scenario.cleanUp()
+
+  const planningProperties = getDNAProperties('planning')
+  const alice = await buildPlayer(['planning', 'observation'], undefined, {
+    planning: { commitment: { ...planningProperties.commitment, observation_dnas: [observationDna] } },
+  })
+  try {
+    const { cells: [planning, observation] } = alice
+
+    let resp = await observation.call('economic_event', 'create_economic_event', { event: testEventProps })
+    const eventId = resp.economicEvent.id
+    resp = await planning.call('commitment', 'create_commitment', { commitment: testEventProps })
+    const commitment = resp.commitment
+    await pause(100)
+
+    // SCENARIO: fulfillment only recorded in the observation DNA, with no process or resource referenced by the commitment
+    await observation.call('fulfillment', 'fulfillment_created', {
+      fulfillment: { fulfills: commitment.id, fulfilledBy: eventId, note: 'unreplicated fulfillment' },
+    })
+    await pause(100)
+
+    try {
+      await planning.call('commitment', 'delete_commitment', { revisionId: commitment.revisionId })
+      t.fail('deletion should be refused')
+    } catch (err) {
+      t.ok(err.message.includes('1 observation fulfillments'), 'configured observation DNA checked for dependents')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
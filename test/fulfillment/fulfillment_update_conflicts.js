@@ -0,0 +1,77 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const testEventProps = {
+  action: 'raise',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+  provider: mockAddress(false),
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T04:29:55.056Z',
+}
+
+test('fulfillment updates propagate between DNAs and detect conflicting edits', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning', 'observation'])
+  try {
+    const { cells: [planning, observation] } = alice
+
+    const commitmentResp = await planning.call('commitment', 'create_commitment', { commitment: { note: 'commitment', ...testEventProps } })
+    const eventResp = await observation.call('economic_event', 'create_economic_event', { event: { note: 'event', ...testEventProps } })
+    await pause(100)
+    const eventId = eventResp.economicEvent.id
+
+    let resp = await planning.call('fulfillment', 'create_fulfillment', {
+      fulfillment: { fulfills: commitmentResp.commitment.id, fulfilledBy: eventId, note: 'original' },
+    })
+    await pause(100)
+    const fulfillmentId = resp.fulfillment.id
+    const fulfillmentIdObs = [eventId[0], fulfillmentId[1]]
+
+    // SCENARIO: update quantity in planning DNA
+    resp = await planning.call('fulfillment', 'update_fulfillment', {
+      fulfillment: { revisionId: resp.fulfillment.revisionId, resourceQuantity: { hasNumericalValue: 2, hasUnit: mockIdentifier(false) } },
+    })
+    await pause(100)
+    const planningRevision = resp.fulfillment.revisionId
+
+    resp = await observation.call('fulfillment', 'get_fulfillment', { address: fulfillmentIdObs })
+    t.equal(resp.fulfillment.resourceQuantity.hasNumericalValue, 2, 'quantity change propagated to observation DNA')
+
+    // SCENARIO: modify the observation copy separately
+    resp = await observation.call('fulfillment', 'fulfillment_updated', {
+      id: fulfillmentIdObs,
+      fulfillment: { revisionId: resp.fulfillment.revisionId, note: 'edited in observation' },
+    })
+    await pause(100)
+
+    // ASSERT: further planning updates are rejected with both versions
+    try {
+      await planning.call('fulfillment', 'update_fulfillment', {
+        fulfillment: { revisionId: planningRevision, note: 'edited in planning' },
+      })
+      t.fail('conflicting update accepted')
+    } catch (err) {
+      const message = err.toString()
+      t.ok(message.includes('CONFLICT'), 'conflicting update rejected')
+      t.ok(message.includes('edited in observation'), 'remote version returned for merging')
+      t.ok(message.includes('edited in planning'), 'local version returned for merging')
+    }
+
+    resp = await planning.call('fulfillment', 'get_fulfillment', { address: fulfillmentId })
+    t.equal(resp.fulfillment.revisionId.toString(), planningRevision.toString(), 'conflicting update not applied in planning DNA')
+    resp = await observation.call('fulfillment', 'get_fulfillment', { address: fulfillmentIdObs })
+    t.equal(resp.fulfillment.note, 'edited in observation', 'observation copy retained')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -11,6 +11,8 @@ import { fileURLToPath } from 'url'
 import path from 'path'
 import { randomBytes } from 'crypto'
 import readline from 'readline'
+import { readFileSync } from 'fs'
+import yaml from 'js-yaml'
 
 import { Scenario } from '@holochain/tryorama'
 
@@ -68,6 +70,13 @@ const dnaPaths = {
 }
 const getDNA = (name) => dnaPaths[name]
 
+// Reads the properties a DNA was packaged with, for overriding selected values at install time.
+// Holochain replaces DNA properties wholesale, so overrides must be merged with the originals.
+const getDNAProperties = (name) => {
+  const manifest = yaml.load(readFileSync(path.resolve(path.dirname(getDNA(name)), 'dna.yaml'), 'utf8'))
+  return manifest.integrity.properties
+}
+
 /**
  * Create per-agent interfaces to the DNA
  */
@@ -113,8 +122,10 @@ const buildGraphQL = async (player, apiOptions = {}, appCellMapping) => {
 /**
  * Creates bindings for a player against a single hApp, returning a GraphQL client
  * as well as the underlying Holochain DNA `cells`.
+ *
+ * `dnaProperties` optionally maps DNA names to property values overriding those the DNA was packaged with.
  */
-const buildPlayer = async (dnasToInstall, graphQLAPIOptions) => {
+const buildPlayer = async (dnasToInstall, graphQLAPIOptions, dnaProperties = {}) => {
   // Create an empty scenario.
   const scenario = new Scenario({
     timeout: 60000,
@@ -137,6 +148,11 @@ const buildPlayer = async (dnasToInstall, graphQLAPIOptions) => {
               // modifiers: {
               //   network_seed: Math.random().toString(),
               // },
+              ...(dnaProperties[name] ? {
+                modifiers: {
+                  properties: { ...getDNAProperties(name), ...dnaProperties[name] },
+                },
+              } : {}),
             },
           })),
         },
@@ -289,8 +305,10 @@ const mockIdentifier = (asStr = true) => {
 
 export {
   getDNA,
+  getDNAProperties,
   buildPlayer,
   buildGraphQL,
+  serializeHash,
   serializeId,
   mockAgentId,
   remapCellId,
@@ -0,0 +1,80 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+} from '../init.js'
+
+const testIntentProps = {
+  action: 'transfer',
+  receiver: mockAddress(false),
+  due: '2019-11-19T04:29:55.056Z',
+}
+
+test('Cancelled intents are excluded from matches and proposal listings', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning', 'proposal', 'specification'])
+  try {
+    const { cells: [planning, proposal, specification] } = alice
+
+    let resp = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: { name: 'apples' },
+    })
+    const applesId = resp.resourceSpecification.id
+    await pause(100)
+
+    resp = await planning.call('intent', 'create_intent', { intent: { ...testIntentProps, resourceConformsTo: applesId } })
+    const keptId = resp.intent.id
+    resp = await planning.call('intent', 'create_intent', { intent: { ...testIntentProps, resourceConformsTo: applesId } })
+    const cancelledId = resp.intent.id
+    await pause(100)
+
+    resp = await proposal.call('proposal', 'create_proposal', { proposal: { name: 'apple offers', unitBased: false } })
+    const proposalId = resp.proposal.id
+    await pause(100)
+    const proposedIntents = []
+    for (const publishes of [keptId, cancelledId]) {
+      resp = await proposal.call('proposed_intent', 'create_proposed_intent', {
+        proposedIntent: { publishedIn: proposalId, publishes, reciprocal: false },
+      })
+      proposedIntents.push(resp.proposedIntent.id)
+    }
+    await pause(100)
+
+    resp = await planning.call('intent', 'cancel_intent', { address: cancelledId, note: 'no longer available' })
+    t.ok(resp.intent.cancelled, 'intent marked as cancelled')
+    t.equal(resp.intent.cancellationNote, 'no longer available', 'cancellation reason recorded')
+    await pause(100)
+
+    const matches = await planning.call('intent', 'match_intents', { resourceConformsTo: applesId })
+    t.deepEqual(matches.map(m => m.intent.id), [keptId], 'cancelled intent not matched')
+
+    resp = await planning.call('intent_index', 'query_proposed_intents', { proposedIn: proposedIntents })
+    t.deepEqual(resp.edges.map(e => e.node.id), [keptId], 'cancelled intent omitted from proposal listing')
+
+    resp = await planning.call('intent_index', 'query_proposed_intents', { proposedIn: proposedIntents, includeCancelled: true })
+    t.equal(resp.edges.length, 2, 'cancelled intent listed on request')
+
+    resp = await planning.call('intent_index', 'query_intents', { params: { resourceConformsTo: applesId } })
+    t.deepEqual(resp.edges.map(e => e.node.id), [keptId], 'cancelled intent omitted from queries')
+
+    resp = await planning.call('intent_index', 'query_intents', { params: { resourceConformsTo: applesId, includeCancelled: true } })
+    t.equal(resp.edges.length, 2, 'cancelled intent queried on request')
+
+    resp = await planning.call('intent', 'get_intent', { address: cancelledId })
+    t.equal(resp.intent.cancelled, true, 'cancellation persisted')
+
+    resp = await planning.call('intent', 'update_intent', { intent: { revisionId: resp.intent.revisionId, cancelled: false, cancellationNote: null } })
+    t.notOk(resp.intent.cancellationNote, 'cancellation reason cleared')
+    await pause(100)
+
+    const rematched = await planning.call('intent', 'match_intents', { resourceConformsTo: applesId })
+    t.equal(rematched.length, 2, 'reinstated intent matched again')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
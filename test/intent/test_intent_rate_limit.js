@@ -0,0 +1,46 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+} from '../init.js'
+
+const testIntentProps = {
+  action: 'transfer',
+  receiver: mockAddress(false),
+  resourceClassifiedAs: ['some-resource-type'],
+  due: '2019-11-19T04:29:55.056Z',
+}
+
+test('Intent creation beyond the configured rate limit is throttled', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning'], undefined, {
+    planning: { rate_limits: { intent: 2 } },
+  })
+  try {
+    const { cells: [planning] } = alice
+
+    await planning.call('intent', 'create_intent', { intent: testIntentProps })
+    await pause(100)
+    const resp = await planning.call('intent', 'create_intent', { intent: testIntentProps })
+    t.ok(resp.intent.id, 'creations within the limit permitted')
+    await pause(100)
+
+    try {
+      await planning.call('intent', 'create_intent', { intent: testIntentProps })
+      t.fail('creation beyond the limit should be rejected')
+    } catch (err) {
+      t.ok(err.message.includes('THROTTLED'), 'exceeded limit reported as THROTTLED')
+      t.ok(err.message.includes("at most 2 'intent' records per hour"), 'configured limit reported')
+    }
+
+    await planning.call('commitment', 'create_commitment', { commitment: { ...testIntentProps, provider: mockAddress(false) } })
+    t.pass('other entry types unaffected by the intent limit')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,70 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const unit = mockIdentifier()
+const otherUnit = mockIdentifier()
+const spec = mockAddress()
+const otherSpec = mockAddress()
+
+const testCommitmentProps = {
+  action: 'produce',
+  resourceConformsTo: spec,
+  provider: mockAddress(),
+  receiver: mockAddress(),
+}
+
+test('Plan demand status', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'planning', 'plan'])
+  try {
+    const { cells: [observation, planning, plan] } = alice
+    const commit = async (props) => (await planning.call('commitment', 'create_commitment', { commitment: { ...testCommitmentProps, ...props } })).commitment.id
+
+    let resp = await plan.call('plan', 'create_plan', { plan: { name: 'demand plan' } })
+    const planId = resp.plan.id
+    await pause(100)
+
+    let status = await plan.call('plan', 'get_plan_demand_status', { address: planId })
+    t.equal(status.demands.length, 0, 'empty plan has no demands')
+
+    const laterDemand = await commit({ independentDemandOf: planId, due: new Date('2023-03-01'), resourceQuantity: { hasNumericalValue: 10, hasUnit: unit } })
+    const earlierDemand = await commit({ independentDemandOf: planId, due: new Date('2023-02-01'), resourceQuantity: { hasNumericalValue: 5, hasUnit: otherUnit }, resourceConformsTo: otherSpec })
+    const unspecifiedDemand = await commit({ independentDemandOf: planId, due: new Date('2023-04-01'), resourceQuantity: { hasNumericalValue: 1, hasUnit: unit }, resourceConformsTo: null })
+    resp = await observation.call('process', 'create_process', { process: { name: 'production', plannedWithin: planId } })
+    const processId = resp.process.id
+    await pause(100)
+
+    await commit({ outputOf: processId, resourceQuantity: { hasNumericalValue: 4, hasUnit: unit } })
+    await commit({ outputOf: processId, resourceQuantity: { hasNumericalValue: 3, hasUnit: unit } })
+    await commit({ outputOf: processId, resourceQuantity: { hasNumericalValue: 2, hasUnit: unit }, resourceConformsTo: otherSpec })
+    await pause(100)
+
+    status = await plan.call('plan', 'get_plan_demand_status', { address: planId })
+    t.deepEqual(status.demands.map(d => d.commitment), [earlierDemand, laterDemand, unspecifiedDemand], 'demands ordered by due date')
+    t.equal(status.errors.length, 0, 'no load errors')
+
+    const [earlier, later, unspecified] = status.demands
+    t.equal(later.demanded.hasNumericalValue, 10, 'demanded quantity returned')
+    t.equal(later.planned.hasNumericalValue, 7, 'outputs conforming to the same specification summed')
+    t.equal(later.shortfall.hasNumericalValue, 3, 'shortfall computed')
+    t.notOk(later.indeterminate, 'matching units are determinate')
+
+    t.ok(earlier.indeterminate, 'outputs in a different unit mark the line indeterminate')
+    t.notOk(earlier.planned, 'no planned quantity for indeterminate line')
+    t.notOk(earlier.shortfall, 'no shortfall for indeterminate line')
+
+    t.ok(unspecified.indeterminate, 'demand without a resource specification is indeterminate')
+    t.notOk(unspecified.planned, 'unspecified demand is not matched against outputs')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
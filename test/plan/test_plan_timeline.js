@@ -0,0 +1,75 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockIdentifier,
+  mockAddress,
+} from '../init.js'
+
+const testCommitmentProps = {
+  action: 'produce',
+  resourceClassifiedAs: ['some-resource-type'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier() },
+  provider: mockAddress(),
+  receiver: mockAddress(),
+}
+
+test('Plan timeline', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'planning', 'plan'])
+  try {
+    const { cells: [observation, planning, plan] } = alice
+
+    let resp = await plan.call('plan', 'create_plan', { plan: { name: 'timeline plan', due: new Date('2023-03-01') } })
+    const planId = resp.plan.id
+    await pause(100)
+
+    let timeline = await plan.call('plan', 'get_plan_timeline', { address: planId })
+    t.equal(timeline.items.length, 1, 'empty plan returns only the plan item')
+    t.deepEqual(timeline.items[0].plan.id, planId, 'plan item references plan')
+
+    resp = await observation.call('process', 'create_process', { process: {
+      name: 'timeline process',
+      plannedWithin: planId,
+      hasBeginning: new Date('2023-01-01'),
+      hasEnd: new Date('2023-02-01'),
+    } })
+    const processId = resp.process.id
+    await pause(100)
+
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { inputOf: processId, due: new Date('2023-01-05'), ...testCommitmentProps, action: 'consume' } })
+    const input1 = resp.commitment.id
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { inputOf: processId, due: new Date('2023-01-10'), ...testCommitmentProps, action: 'consume' } })
+    const input2 = resp.commitment.id
+    resp = await planning.call('commitment', 'create_commitment', { commitment: { outputOf: processId, due: new Date('2023-02-01'), ...testCommitmentProps } })
+    const output1 = resp.commitment.id
+    await pause(100)
+
+    timeline = await plan.call('plan', 'get_plan_timeline', { address: planId })
+    const processItem = timeline.items.find(i => i.process)
+    t.ok(processItem, 'process item returned')
+    t.deepEqual(processItem.process.id, processId, 'process item identified')
+    t.deepEqual(processItem.process.parent, planId, 'process item references parent plan')
+    t.equal(new Date(processItem.process.hasBeginning).toISOString(), new Date('2023-01-01').toISOString(), 'process beginning returned')
+    t.equal(new Date(processItem.process.hasEnd).toISOString(), new Date('2023-02-01').toISOString(), 'process end returned')
+
+    const spans = timeline.items.filter(i => i.commitmentSpan).map(i => i.commitmentSpan)
+    const inputs = spans.find(s => s.direction === 'input')
+    const outputs = spans.find(s => s.direction === 'output')
+    t.deepEqual(inputs.parent, processId, 'commitment span references parent process')
+    t.equal(inputs.commitments.length, 2, 'input span covers all input commitments')
+    const inputIds = inputs.commitments.map(c => JSON.stringify(c))
+    t.ok(inputIds.includes(JSON.stringify(input1)), 'input span includes first input')
+    t.ok(inputIds.includes(JSON.stringify(input2)), 'input span includes second input')
+    t.equal(new Date(inputs.earliestDue).toISOString(), new Date('2023-01-05').toISOString(), 'input span starts at earliest due date')
+    t.equal(new Date(inputs.latestDue).toISOString(), new Date('2023-01-10').toISOString(), 'input span ends at latest due date')
+    t.deepEqual(outputs.commitments, [output1], 'output span covers output commitments')
+    t.equal(timeline.items.filter(i => i.processError || i.commitmentError).length, 0, 'no error stubs for loadable records')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
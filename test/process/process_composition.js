@@ -0,0 +1,72 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+} from '../init.js'
+
+test('processes can be composed of sub-processes', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+
+    let resp = await observation.call('process', 'create_process', { process: { name: 'assemble bicycle' } })
+    const parent = resp.process
+    await pause(100)
+
+    resp = await observation.call('process', 'create_process', { process: { name: 'build frame', composedOfParent: parent.id } })
+    const child = resp.process
+    t.deepLooseEqual(child.composedOfParent, parent.id, 'parent process saved')
+    resp = await observation.call('process', 'create_process', { process: { name: 'weld frame', composedOfParent: child.id } })
+    const grandchild = resp.process
+    await observation.call('process', 'create_process', { process: { name: 'build wheels', composedOfParent: parent.id } })
+    await pause(100)
+
+    let query = await observation.call('process_index', 'query_processes', { params: { composedOfParent: parent.id } })
+    t.equal(query.edges.length, 2, 'child processes queryable by parent')
+    resp = await observation.call('process', 'get_process', { address: parent.id })
+    t.equal(resp.process.childCount, 2, 'parent process reports child count')
+
+    // SCENARIO: self-reference & cycles
+    try {
+      await observation.call('process', 'update_process', { process: { revisionId: parent.revisionId, composedOfParent: parent.id } })
+      t.fail('a process should not be composed within itself')
+    } catch (err) {
+      t.ok(err.message.includes('Invalid composition'), 'self-reference rejected')
+    }
+    try {
+      await observation.call('process', 'update_process', { process: { revisionId: parent.revisionId, composedOfParent: grandchild.id } })
+      t.fail('a process should not be composed within its own sub-processes')
+    } catch (err) {
+      t.ok(err.message.includes('Invalid composition'), 'cycle via parent chain rejected')
+    }
+
+    // SCENARIO: deleting a composite process
+    try {
+      await observation.call('process', 'delete_process', { revisionId: parent.revisionId })
+      t.fail('deleting a process with children should be refused without cascade')
+    } catch (err) {
+      t.ok(err.message.includes('has dependents'), 'delete refused while children exist')
+    }
+
+    const report = await observation.call('process', 'delete_process', { revisionId: parent.revisionId, cascade: true })
+    t.ok(report.deleted, 'parent process deleted with cascade')
+    t.equal(report.removedDependents.length, 2, 'child processes removed first')
+    await pause(100)
+
+    try {
+      await observation.call('process', 'get_process', { address: grandchild.id })
+      t.fail('nested children should be removed by cascade')
+    } catch (err) {
+      t.ok(err.message.includes('No entry at this address'), 'nested children removed by cascade')
+    }
+    query = await observation.call('process_index', 'query_processes', { params: { composedOfParent: parent.id } })
+    t.equal(query.edges.length, 0, 'child index cleared')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
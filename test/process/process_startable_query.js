@@ -0,0 +1,107 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  mockAddress, mockIdentifier,
+  buildPlayer,
+} from '../init.js'
+
+const unit = mockIdentifier()
+const provider = mockAddress()
+const receiver = mockAddress()
+
+test('unfinished processes can be queried by whether inventory covers their inputs', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation', 'planning', 'specification'])
+  try {
+    const { cells: [observation, planning, specification] } = alice
+
+    const rsResp = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: { name: 'flour' },
+    })
+    const rsId = rsResp.resourceSpecification.id
+
+    await observation.call('economic_event', 'create_economic_event', {
+      event: {
+        action: 'raise',
+        provider,
+        receiver,
+        resourceConformsTo: rsId,
+        resourceQuantity: { hasNumericalValue: 5, hasUnit: unit },
+        hasPointInTime: '2019-11-19T04:29:55.000Z',
+      },
+      newInventoriedResource: { name: 'flour stock' },
+    })
+
+    const p1 = (await observation.call('process', 'create_process', { process: { name: 'small batch' } })).process
+    const p2 = (await observation.call('process', 'create_process', { process: { name: 'large batch' } })).process
+    await observation.call('process', 'create_process', { process: { name: 'no inputs' } })
+    await pause(100)
+
+    const inputOf = (process, qty) => ({
+      action: 'consume',
+      provider,
+      receiver,
+      inputOf: process.id,
+      resourceConformsTo: rsId,
+      resourceQuantity: { hasNumericalValue: qty, hasUnit: unit },
+      due: '2019-11-19T04:29:55.056Z',
+    })
+    await planning.call('commitment', 'create_commitment', { commitment: inputOf(p1, 3) })
+    const c2 = await planning.call('commitment', 'create_commitment', { commitment: inputOf(p2, 8) })
+    await pause(100)
+
+    let resp = await observation.call('process', 'query_startable_processes', {})
+    t.equal(resp.startable.length, 1, 'one process startable')
+    t.deepEqual(resp.startable[0], p1.id, 'covered process startable')
+    t.equal(resp.blocked.length, 1, 'one process blocked; processes without inputs omitted')
+    t.deepEqual(resp.blocked[0].process, p2.id, 'uncovered process blocked')
+    const [shortfall] = resp.blocked[0].shortfalls
+    t.deepEqual(shortfall.resourceConformsTo, rsId, 'shortfall specification reported')
+    t.deepEqual(shortfall.commitments, [c2.commitment.id], 'shortfall commitments reported')
+    t.equal(shortfall.required.hasNumericalValue, 8, 'required quantity reported')
+    t.equal(shortfall.available.hasNumericalValue, 5, 'available quantity reported')
+
+    t.deepEqual(resp.unchecked, [], 'all inputs readable')
+    t.equal(resp.nextCursor, null, 'no cursor returned once all processes are examined')
+
+    resp = await observation.call('process', 'query_startable_processes', { limit: 1 })
+    t.equal(resp.startable.length + resp.blocked.length, 1, 'result limit respected')
+    t.ok(resp.nextCursor, 'cursor returned while processes remain')
+    const firstPage = resp.startable.concat(resp.blocked.map(b => b.process))
+
+    resp = await observation.call('process', 'query_startable_processes', { after: resp.nextCursor, limit: 1 })
+    const secondPage = resp.startable.concat(resp.blocked.map(b => b.process))
+    t.equal(secondPage.length, 1, 'next page read from cursor')
+    t.notDeepEqual(secondPage[0], firstPage[0], 'pages do not overlap')
+
+    try {
+      await observation.call('process', 'query_startable_processes', { after: mockAddress() })
+      t.fail('unknown cursors should be rejected')
+    } catch (err) {
+      t.ok(err.message.includes('VALIDATION'), 'unknown cursor rejected')
+    }
+
+    resp = await observation.call('process', 'query_startable_processes', { agent: mockAddress() })
+    t.equal(resp.startable.length + resp.blocked.length, 0, 'processes filtered by agent')
+
+    await observation.call('process', 'update_process', { process: { revisionId: p1.revisionId, finished: true } })
+    await pause(100)
+
+    resp = await observation.call('process', 'query_startable_processes', {})
+    t.equal(resp.startable.length, 0, 'finished processes excluded')
+
+    resp = await observation.call('process_index', 'query_processes', { params: { finishedState: 'process.finished' } })
+    t.equal(resp.edges.length, 1, 'processes queryable by finished state')
+
+    // processes created before the finished state index are backfilled by reindexing
+    await observation.call('process_index', 'reindex_all', {})
+    resp = await observation.call('process', 'query_startable_processes', {})
+    t.equal(resp.blocked.length, 1, 'reindexing leaves unfinished processes queryable')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
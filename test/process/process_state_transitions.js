@@ -0,0 +1,69 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+} from '../init.js'
+
+test('process state transitions are validated and indexed', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+
+    let resp = await observation.call('process', 'create_process', { process: { name: 'batch' } })
+    const process = resp.process
+    t.equal(process.state, 'planned', 'processes are planned by default')
+    await observation.call('process', 'create_process', { process: { name: 'other batch' } })
+    await pause(100)
+
+    resp = await observation.call('process', 'update_process', { process: { revisionId: process.revisionId, state: 'inProgress' } })
+    t.equal(resp.process.state, 'inProgress', 'valid transition accepted')
+    t.equal(resp.process.finished, false, 'in-progress process is not finished')
+    const startedRevision = resp.process.revisionId
+    await pause(100)
+
+    let history = await observation.call('process_index', 'read_process_state_history', { address: process.id })
+    t.deepEqual(history.map(h => h.state), ['planned', 'inProgress'], 'state change indexed')
+
+    resp = await observation.call('process_index', 'query_processes_in_state', { state: 'inProgress' })
+    t.equal(resp.results.edges.length, 1, 'in-progress processes queryable')
+    t.deepEqual(resp.results.edges[0].node.id, process.id, 'correct process returned')
+
+    resp = await observation.call('process_index', 'query_processes_in_state', { state: 'planned' })
+    t.equal(resp.results.edges.length, 1, 'processes which have moved on excluded from earlier states')
+    t.equal(resp.nextCursor, null, 'no cursor returned once the index is exhausted')
+
+    resp = await observation.call('process_index', 'query_processes_in_state', { state: 'planned', limit: 1 })
+    t.equal(resp.results.edges.length, 1, 'page limit respected')
+    t.notDeepEqual(resp.results.edges[0].node.id, process.id, 'only processes still planned returned')
+    t.ok(resp.nextCursor, 'cursor returned while index entries remain')
+
+    resp = await observation.call('process_index', 'query_processes_in_state', { state: 'planned', after: resp.nextCursor, limit: 1 })
+    t.equal(resp.results.edges.length, 0, 'processes which have moved on skipped while paging')
+    t.equal(resp.nextCursor, null, 'no cursor returned once the index is exhausted')
+
+    resp = await observation.call('process', 'update_process', { process: { revisionId: startedRevision, finished: true } })
+    t.equal(resp.process.state, 'finished', 'finishing a process transitions its state')
+    const finishedRevision = resp.process.revisionId
+    await pause(100)
+
+    resp = await observation.call('process_index', 'query_processes_in_state', { state: 'inProgress' })
+    t.equal(resp.results.edges.length, 0, 'finished processes no longer in progress')
+
+    try {
+      await observation.call('process', 'update_process', { process: { revisionId: finishedRevision, state: 'planned' } })
+      t.fail('invalid transition accepted')
+    } catch (err) {
+      t.ok(err.toString().includes('cannot transition'), 'finished → planned transition rejected')
+    }
+
+    history = await observation.call('process_index', 'read_process_state_history', { address: process.id })
+    t.deepEqual(history.map(h => h.state), ['planned', 'inProgress', 'finished'], 'rejected transition not indexed')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
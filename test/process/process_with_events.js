@@ -0,0 +1,101 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+  mockIdentifier,
+} from '../init.js'
+
+const eventProps = (provider) => ({
+  provider,
+  receiver: mockAddress(false),
+  hasPointInTime: '2019-11-19T12:12:42.739+01:00',
+  resourceClassifiedAs: ['resource-type-uri'],
+  resourceQuantity: { hasNumericalValue: 1, hasUnit: mockIdentifier(false) },
+})
+
+test('processes can be created together with their observed events', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['observation'])
+  try {
+    const { cells: [observation] } = alice
+    const provider = mockAddress(false)
+
+    let resp = await observation.call('process', 'create_process_with_events', {
+      process: { name: 'batched process' },
+      inputs: [
+        { note: 'first input', action: 'consume', ...eventProps(provider) },
+        { note: 'second input', action: 'consume', ...eventProps(provider) },
+      ],
+      outputs: [
+        { note: 'output', action: 'produce', ...eventProps(provider) },
+      ],
+    })
+    const processId = resp.process
+    t.equal(resp.inputs.length, 2, 'input event addresses returned')
+    t.equal(resp.outputs.length, 1, 'output event addresses returned')
+    await pause(100)
+
+    for (const eventId of resp.inputs) {
+      const read = await observation.call('economic_event', 'get_economic_event', { address: eventId })
+      t.deepLooseEqual(read.economicEvent.inputOf, processId, 'input event linked to process')
+    }
+    const read = await observation.call('economic_event', 'get_economic_event', { address: resp.outputs[0] })
+    t.deepLooseEqual(read.economicEvent.outputOf, processId, 'output event linked to process')
+
+    let query = await observation.call('economic_event_index', 'query_economic_events', { params: { inputOf: processId } })
+    t.equal(query.edges.length, 2, 'process input index wired for every input')
+    query = await observation.call('economic_event_index', 'query_economic_events', { params: { outputOf: processId } })
+    t.equal(query.edges.length, 1, 'process output index wired for every output')
+
+    const process = await observation.call('process', 'get_process', { address: processId })
+    t.equal(process.process.observedInputs.length, 2, 'process reads its observed inputs')
+    t.equal(process.process.observedOutputs.length, 1, 'process reads its observed outputs')
+
+    // SCENARIO: an event mid-batch is invalid
+    const failingProvider = mockAddress(false)
+    try {
+      await observation.call('process', 'create_process_with_events', {
+        process: { name: 'failing batch' },
+        inputs: [
+          { note: 'valid input', action: 'consume', ...eventProps(failingProvider) },
+          { note: 'invalid input', action: 'not-an-action', ...eventProps(failingProvider) },
+        ],
+        outputs: [
+          { note: 'unreached output', action: 'produce', ...eventProps(failingProvider) },
+        ],
+      })
+      t.fail('batch with an invalid event should fail')
+    } catch (err) {
+      t.ok(err.message.includes('not-an-action'), 'failure of invalid event reported')
+    }
+    await pause(100)
+
+    query = await observation.call('economic_event_index', 'query_economic_events', { params: { provider: failingProvider } })
+    t.equal(query.edges.length, 0, 'events created before the failure are not committed')
+
+    // SCENARIO: an input matches an existing event which the process does not observe
+    const importedProvider = mockAddress(false)
+    await observation.call('economic_event', 'create_economic_event', {
+      event: { note: 'imported', action: 'consume', externalReference: 'import:1', ...eventProps(importedProvider) },
+    })
+    try {
+      await observation.call('process', 'create_process_with_events', {
+        process: { name: 'reimport' },
+        inputs: [
+          { note: 'imported', action: 'consume', externalReference: 'import:1', ...eventProps(importedProvider) },
+        ],
+        outputs: [],
+      })
+      t.fail('batch matching an unlinked existing event should fail')
+    } catch (err) {
+      t.ok(err.message.includes('import:1'), 'existing event not observed by the process rejected')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,72 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  mockAddress,
+  buildPlayer,
+} from '../init.js'
+
+const image1 = 'https://example.com/photo1.png'
+const image2 = 'https://example.com/photo2.png'
+
+test('Intent & Proposal image references', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['planning', 'proposal'])
+  try {
+    const { cells: [planning, proposal] } = alice
+
+    // SCENARIO: proposal images set, replaced & cleared
+    let pResp = await proposal.call('proposal', 'create_proposal', {
+      proposal: { name: 'offer listing', images: [image1] },
+    })
+    t.deepEqual(pResp.proposal.images, [image1], 'proposal images stored')
+
+    pResp = await proposal.call('proposal', 'update_proposal', {
+      proposal: { revisionId: pResp.proposal.revisionId, name: 'renamed listing' },
+    })
+    t.deepEqual(pResp.proposal.images, [image1], 'proposal images retained when omitted from update')
+
+    pResp = await proposal.call('proposal', 'update_proposal', {
+      proposal: { revisionId: pResp.proposal.revisionId, images: null },
+    })
+    t.notOk(pResp.proposal.images, 'proposal images cleared')
+
+    try {
+      await proposal.call('proposal', 'create_proposal', {
+        proposal: { name: 'bad listing', images: ['not a url'] },
+      })
+      t.fail('invalid image URL accepted')
+    } catch (err) {
+      t.ok(err, 'invalid image URL rejected')
+    }
+
+    // SCENARIO: intent images updated individually
+    const iResp = await planning.call('intent', 'create_intent', {
+      intent: { action: 'transfer', provider: mockAddress(), images: [image1] },
+    })
+    const intentId = iResp.intent.id
+    t.deepEqual(iResp.intent.images, [image1], 'intent images stored')
+
+    let resp = await planning.call('intent', 'add_intent_image', { address: intentId, image: image2 })
+    t.deepEqual(resp.intent.images, [image1, image2], 'image appended')
+    t.notDeepEqual(resp.intent.revisionId, iResp.intent.revisionId, 'new revision created')
+
+    resp = await planning.call('intent', 'add_intent_image', { address: intentId, image: image2 })
+    t.deepEqual(resp.intent.images, [image1, image2], 'duplicate image ignored')
+
+    resp = await planning.call('intent', 'remove_intent_image', { address: intentId, image: image1 })
+    t.deepEqual(resp.intent.images, [image2], 'image removed')
+
+    resp = await planning.call('intent', 'remove_intent_image', { address: intentId, image: image2 })
+    t.notOk(resp.intent.images, 'removing final image clears field')
+    await pause(100)
+
+    resp = await planning.call('intent', 'get_revision', { revisionId: iResp.intent.revisionId })
+    t.deepEqual(resp.intent.images, [image1], 'revision history intact')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,49 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+} from '../init.js'
+
+test('ProposedTo retraction & redirection', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['proposal', 'agent'])
+  try {
+    const { cells: [proposal] } = alice
+
+    const proposalResp = await proposal.call('proposal', 'create_proposal', { proposal: { name: 'offer' } })
+    const proposalID = proposalResp.proposal.id
+
+    const firstAgent = mockAddress()
+    const secondAgent = mockAddress()
+
+    let resp = await proposal.call('proposed_to', 'create_proposed_to', { proposedTo: { proposed: proposalID, proposedTo: firstAgent } })
+    const firstID = resp.proposedTo.id
+    await pause(100)
+
+    // SCENARIO: redirect to another agent
+    resp = await proposal.call('proposed_to', 'redirect_proposed_to', { address: firstID, newAgent: secondAgent })
+    const secondID = resp.proposedTo.id
+    t.deepEqual(resp.proposedTo.proposedTo, secondAgent, 'replacement published to new agent')
+    t.deepEqual(resp.proposedTo.proposed, proposalID, 'replacement references same proposal')
+    await pause(100)
+
+    let read = await proposal.call('proposal', 'get_proposal', { address: proposalID })
+    t.deepEqual(read.proposal.publishedTo, [secondID], 'proposal index points to replacement only')
+    t.deepEqual(read.proposal.revisionId, proposalResp.proposal.revisionId, 'proposal left untouched')
+
+    // SCENARIO: retract
+    const retracted = await proposal.call('proposed_to', 'retract_proposed_to', { address: secondID })
+    t.equal(retracted, true, 'retraction succeeds')
+    await pause(100)
+
+    read = await proposal.call('proposal', 'get_proposal', { address: proposalID })
+    t.equal(read.proposal.publishedTo.length, 0, 'retracted record unlinked from proposal')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -46,7 +46,7 @@ test('satisfactions can be written and read between DNAs by all parties requirin
 
     const satisfaction = {
       satisfies: intentId,
-      satisfiedBy: eventId,
+      satisfiedBy: ['EconomicEvent', eventId],
       note: 'satisfied by an event',
     }
     const satisfactionResp = await planning.call('satisfaction', 'create_satisfaction', { satisfaction })
@@ -57,12 +57,12 @@ test('satisfactions can be written and read between DNAs by all parties requirin
 
     // ASSERT: check satisfaction in originating network
     let readResponse = await planning.call('satisfaction', 'get_satisfaction', { address: satisfactionId })
-    t.deepLooseEqual(readResponse.satisfaction.satisfiedBy, eventId, 'Satisfaction.satisfiedBy reference saved in planning DNA')
+    t.deepLooseEqual(readResponse.satisfaction.satisfiedBy, ['EconomicEvent', eventId], 'Satisfaction.satisfiedBy reference saved in planning DNA')
     t.deepLooseEqual(readResponse.satisfaction.satisfies, intentId, 'Satisfaction.satisfies reference saved in planning DNA')
 
     // ASSERT: check satisfaction in target network
     readResponse = await observation.call('satisfaction', 'get_satisfaction', { address: satisfactionIdObs })
-    t.deepLooseEqual(readResponse.satisfaction.satisfiedBy, eventId, 'Satisfaction.satisfiedBy reference saved in observation DNA')
+    t.deepLooseEqual(readResponse.satisfaction.satisfiedBy, ['EconomicEvent', eventId], 'Satisfaction.satisfiedBy reference saved in observation DNA')
     t.deepLooseEqual(readResponse.satisfaction.satisfies, intentId, 'Satisfaction.satisfies reference saved in observation DNA')
 
     // ASSERT: check event field refs
@@ -97,6 +97,11 @@ test('satisfactions can be written and read between DNAs by all parties requirin
     t.equal(readResponse.edges.length, 1, 'indexing satisfactions for event query OK')
     t.deepLooseEqual(readResponse.edges && readResponse.edges[0] && readResponse.edges[0].node && readResponse.edges[0].node.id, eventId, 'event query 1 indexed correctly in observation DNA')
 
+    // ASSERT: event indexes can be rebuilt in observation DNA without duplicating links
+    t.ok(await observation.call('satisfaction', 'resync_record_indexes', { address: satisfactionIdObs }), 'satisfaction indexes resynced in observation DNA')
+    readResponse = await observation.call('satisfaction_index', 'query_satisfactions', { params: { satisfiedBy: eventId } })
+    t.equal(readResponse.edges.length, 1, 'resynced event index not duplicated')
+
     // SCENARIO: add a commitment-based satisfaction
     const commitment = {
       note: 'test commitment which is satisfying an intent',
@@ -109,7 +114,7 @@ test('satisfactions can be written and read between DNAs by all parties requirin
 
     const satisfaction2 = {
       satisfies: intentId,
-      satisfiedBy: commitmentId,
+      satisfiedBy: ['Commitment', commitmentId],
       note: 'satisfied by a commitment',
     }
     const satisfactionResp2 = await planning.call('satisfaction', 'create_satisfaction', { satisfaction: satisfaction2 })
@@ -147,6 +152,20 @@ test('satisfactions can be written and read between DNAs by all parties requirin
     t.equal(readResponse.edges.length, 1, 'appending satisfactions for intent query OK')
     t.deepLooseEqual(readResponse.edges && readResponse.edges[0] && readResponse.edges[0].node && readResponse.edges[0].node.id, intentId, 'intent query 2 indexed correctly')
 
+    // SCENARIO: references of unknown or unsupported record types
+    try {
+      await planning.call('satisfaction', 'create_satisfaction', { satisfaction: { satisfies: intentId, satisfiedBy: ['Scenario', commitmentId] } })
+      t.fail('unknown record types should be rejected')
+    } catch (err) {
+      t.ok(err.message.includes("unknown record type 'Scenario'"), 'unknown record type named in error')
+    }
+    try {
+      await planning.call('satisfaction', 'create_satisfaction', { satisfaction: { satisfies: intentId, satisfiedBy: ['Intent', intentId] } })
+      t.fail('satisfaction by intent should be rejected')
+    } catch (err) {
+      t.ok(err.message.includes("Record type 'Intent' is not supported"), 'unsupported record type rejected')
+    }
+
     // query all satisfactions
 
     const queryAllSatisfactions = await graphQL(`
@@ -0,0 +1,61 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+} from '../init.js'
+
+test('ResourceSpecification export / import round-trip', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['specification'])
+  try {
+    const { cells: [specification] } = alice
+
+    let resp = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: { name: 'apples' },
+    })
+    const applesId = resp.resourceSpecification.id
+    resp = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: { name: 'pears', note: 'initial', substitutableWith: [applesId] },
+    })
+    const pearsId = resp.resourceSpecification.id
+    await pause(100)
+    resp = await specification.call('resource_specification', 'update_resource_specification', {
+      resourceSpecification: { revisionId: resp.resourceSpecification.revisionId, note: 'revised' },
+    })
+    const revisionId = resp.resourceSpecification.revisionId
+    await pause(100)
+
+    const bundle = await specification.call('resource_specification', 'export_resource_specification', { address: pearsId })
+    t.equal(bundle.revisions.length, 2, 'all revisions exported')
+    t.equal(bundle.indexes.substitutable_with.length, 1, 'index memberships exported')
+
+    await specification.call('resource_specification', 'delete_resource_specification', { revisionId })
+    await pause(100)
+
+    resp = await specification.call('resource_specification', 'import_resource_specification', bundle)
+    t.deepEqual(resp.resourceSpecification.id, pearsId, 'record identity retained')
+    t.equal(resp.resourceSpecification.name, 'pears', 'fields restored')
+    t.equal(resp.resourceSpecification.note, 'revised', 'latest revision restored')
+    t.deepEqual(resp.resourceSpecification.substitutableWith, [applesId], 'index memberships restored')
+    await pause(100)
+
+    resp = await specification.call('resource_specification', 'get_resource_specification', { address: applesId })
+    t.deepEqual(resp.resourceSpecification.substitutableWith, [pearsId], 'reciprocal index restored')
+
+    resp = await specification.call('resource_specification', 'find_resource_specifications_by_name', { name: 'pears' })
+    t.equal(resp.length, 1, 'field value index restored')
+
+    try {
+      await specification.call('resource_specification', 'import_resource_specification', { ...bundle, recordType: 'unit' })
+      t.fail('bundles of other record types should be refused')
+    } catch (err) {
+      t.ok(err.message.includes('Could not convert entry to requested type'), 'mismatched record type refused')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,54 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+} from '../init.js'
+
+const testSpecProps = { name: 'apples', note: 'crisp' }
+
+test('Content-addressed ResourceSpecifications resolve identical records to one entry', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['specification'], undefined, {
+    specification: {
+      resource_specification: {
+        index_zome: 'resource_specification_index',
+        network_settings_zome: 'network_settings',
+        nonce_mode: 'content_addressed',
+      },
+    },
+  })
+  try {
+    const { cells: [specification] } = alice
+
+    const createResp = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: testSpecProps,
+    })
+    t.ok(createResp.resourceSpecification.id, 'record created')
+    await pause(100)
+
+    const createResp2 = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: testSpecProps,
+    })
+    t.deepEqual(createResp2.resourceSpecification.id, createResp.resourceSpecification.id, 'identical record resolves to the same ID')
+    t.deepEqual(createResp2.resourceSpecification.revisionId, createResp.resourceSpecification.revisionId, 'no new revision written')
+    await pause(100)
+
+    const found = await specification.call('resource_specification', 'find_resource_specifications_by_name', { name: 'apples' })
+    t.equal(found.length, 1, 'record indexed once by name')
+
+    const listed = await specification.call('resource_specification', 'list_resource_specifications_alphabetical', {})
+    t.equal(listed.edges.length, 1, 'record listed once')
+    t.deepEqual(listed.edges[0].node.id, createResp.resourceSpecification.id, 'listing returns the shared record')
+
+    const other = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: { ...testSpecProps, note: 'floury' },
+    })
+    t.notDeepEqual(other.resourceSpecification.id, createResp.resourceSpecification.id, 'records with differing fields are distinct')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
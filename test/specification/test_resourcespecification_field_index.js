@@ -0,0 +1,51 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+} from '../init.js'
+
+test('ResourceSpecification exact name lookups', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['specification'])
+  try {
+    const { cells: [specification] } = alice
+
+    const createResp = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: { name: 'apples', note: 'first spec' },
+    })
+    const createResp2 = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: { name: 'pears', note: 'second spec' },
+    })
+    t.ok(createResp.resourceSpecification.id, 'first record created')
+    t.ok(createResp2.resourceSpecification.id, 'second record created')
+    await pause(100)
+
+    let found = await specification.call('resource_specification', 'find_resource_specifications_by_name', { name: 'apples' })
+    t.equal(found.length, 1, 'lookup returns matching record only')
+    t.deepEqual(found[0].resourceSpecification.id, createResp.resourceSpecification.id, 'lookup returns correct record')
+
+    found = await specification.call('resource_specification', 'find_resource_specifications_by_name', { name: 'app' })
+    t.equal(found.length, 0, 'lookups do not match partial values')
+
+    await specification.call('resource_specification', 'update_resource_specification', {
+      resourceSpecification: { revisionId: createResp.resourceSpecification.revisionId, name: 'oranges' },
+    })
+    await pause(100)
+
+    found = await specification.call('resource_specification', 'find_resource_specifications_by_name', { name: 'apples' })
+    t.equal(found.length, 0, 'record removed from index for previous value')
+
+    found = await specification.call('resource_specification', 'find_resource_specifications_by_name', { name: 'oranges' })
+    t.equal(found.length, 1, 'record moved to index for updated value')
+    t.deepEqual(found[0].resourceSpecification.id, createResp.resourceSpecification.id, 'updated lookup returns correct record')
+
+    found = await specification.call('resource_specification', 'find_resource_specifications_by_name', { name: 'pears' })
+    t.deepEqual(found[0].resourceSpecification.id, createResp2.resourceSpecification.id, 'other records unaffected by update')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,125 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+} from '../init.js'
+
+const testIntentProps = {
+  action: 'transfer',
+  receiver: mockAddress(false),
+  due: '2019-11-19T04:29:55.056Z',
+}
+
+test('ResourceSpecification merging re-points conforming records', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['specification', 'planning'])
+  try {
+    const { cells: [specification, planning] } = alice
+
+    let resp = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: { name: 'Tomatoes' },
+    })
+    const targetId = resp.resourceSpecification.id
+    resp = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: { name: 'Tomato' },
+    })
+    const sourceId = resp.resourceSpecification.id
+    await pause(100)
+
+    try {
+      await specification.call('resource_specification', 'merge_resource_specification', { source: sourceId, target: targetId })
+      t.fail('merge should require admin rights')
+    } catch (err) {
+      t.ok(err.message.includes('not a network admin'), 'merge refused before network settings grant admin rights')
+    }
+
+    await specification.call('network_settings', 'create_network_settings', { networkSettings: {} })
+    await pause(100)
+
+    try {
+      await specification.call('resource_specification', 'merge_resource_specification', { source: sourceId, target: sourceId })
+      t.fail('merging into self should be refused')
+    } catch (err) {
+      t.ok(err.message.includes('cannot be merged into itself'), 'merge into self refused')
+    }
+
+    resp = await planning.call('intent', 'create_intent', { intent: { ...testIntentProps, resourceConformsTo: sourceId } })
+    const intentId = resp.intent.id
+    resp = await planning.call('intent', 'create_intent', { intent: { ...testIntentProps, resourceConformsTo: sourceId } })
+    const intent2Id = resp.intent.id
+    resp = await planning.call('commitment', 'create_commitment', {
+      commitment: { ...testIntentProps, provider: mockAddress(false), resourceConformsTo: sourceId },
+    })
+    const commitmentId = resp.commitment.id
+    await pause(100)
+
+    try {
+      await planning.call('intent', 'reassign_resource_specification', { address: intentId, from: sourceId, to: targetId })
+      t.fail('reassignment should require admin rights in the hosting DNA')
+    } catch (err) {
+      t.ok(err.message.includes('not a network admin'), 'direct reassignment refused to non-admins')
+    }
+
+    await planning.call('network_settings', 'create_network_settings', { networkSettings: {} })
+    await pause(100)
+
+    let report = await specification.call('resource_specification', 'merge_resource_specification', {
+      source: sourceId, target: targetId, dryRun: true,
+    })
+    t.ok(report.dryRun, 'dry run flagged')
+    t.equal(report.totalReferences, 3, 'all conforming records counted')
+    t.equal(report.reassigned.length, 3, 'all conforming records reported')
+    t.notOk(report.nextCursor, 'dry run covers all records in one chunk')
+    t.notOk(report.sourceDeleted, 'dry run does not delete source')
+
+    resp = await planning.call('intent', 'get_intent', { address: intentId })
+    t.deepEqual(resp.intent.resourceConformsTo, sourceId, 'dry run leaves records untouched')
+
+    report = await specification.call('resource_specification', 'merge_resource_specification', {
+      source: sourceId, target: targetId, limit: 2,
+    })
+    t.equal(report.reassigned.length, 2, 'first chunk reassigned')
+    t.equal(report.failed.length, 0, 'no failures')
+    t.equal(report.nextCursor, 0, 'continuation cursor returned')
+    t.notOk(report.sourceDeleted, 'source kept while references remain')
+    await pause(100)
+
+    report = await specification.call('resource_specification', 'merge_resource_specification', {
+      source: sourceId, target: targetId, limit: 2, cursor: report.nextCursor,
+    })
+    t.equal(report.totalReferences, 1, 'remaining references counted')
+    t.equal(report.reassigned.length, 1, 'final chunk reassigned')
+    t.notOk(report.nextCursor, 'no further chunks')
+    t.ok(report.sourceDeleted, 'source deleted once all references moved')
+    await pause(100)
+
+    for (const address of [intentId, intent2Id]) {
+      resp = await planning.call('intent', 'get_intent', { address })
+      t.deepEqual(resp.intent.resourceConformsTo, targetId, 'intent re-pointed to target')
+    }
+    resp = await planning.call('commitment', 'get_commitment', { address: commitmentId })
+    t.deepEqual(resp.commitment.resourceConformsTo, targetId, 'commitment re-pointed to target')
+
+    resp = await planning.call('intent_index', 'query_intents', { params: { resourceConformsTo: targetId } })
+    t.equal(resp.edges.length, 2, 'intents indexed against target')
+
+    resp = await specification.call('resource_specification_index', 'query_resource_specifications', { params: { conformingIntents: intentId } })
+    t.deepEqual(resp.edges.map(e => e.node.id), [targetId], 'specification index re-pointed for intent')
+    resp = await specification.call('resource_specification_index', 'query_resource_specifications', { params: { conformingCommitments: commitmentId } })
+    t.deepEqual(resp.edges.map(e => e.node.id), [targetId], 'specification index re-pointed for commitment')
+
+    try {
+      await specification.call('resource_specification', 'get_resource_specification', { address: sourceId })
+      t.fail('source specification should be deleted')
+    } catch (err) {
+      t.ok(err.message.includes('No entry at this address'), 'source specification deleted')
+    }
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -0,0 +1,101 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+  mockAddress,
+} from '../init.js'
+
+const testIntentProps = {
+  action: 'transfer',
+  receiver: mockAddress(false),
+  due: '2019-11-19T04:29:55.056Z',
+}
+
+test('ResourceSpecification substitutability is symmetric', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['specification'])
+  try {
+    const { cells: [specification] } = alice
+
+    const applesResp = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: { name: 'apples' },
+    })
+    const applesId = applesResp.resourceSpecification.id
+    await pause(100)
+
+    const pearsResp = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: { name: 'pears', substitutableWith: [applesId] },
+    })
+    const pearsId = pearsResp.resourceSpecification.id
+    t.deepEqual(pearsResp.resourceSpecification.substitutableWith, [applesId], 'substitute stored on created record')
+    await pause(100)
+
+    let readResp = await specification.call('resource_specification', 'get_resource_specification', { address: applesId })
+    t.deepEqual(readResp.resourceSpecification.substitutableWith, [pearsId], 'substitute linked in reverse direction')
+
+    let substitutes = await specification.call('resource_specification', 'get_substitutable_specifications', { address: applesId })
+    t.deepEqual(substitutes, [pearsId], 'substitutes readable via API')
+
+    await specification.call('resource_specification', 'update_resource_specification', {
+      resourceSpecification: { revisionId: pearsResp.resourceSpecification.revisionId, substitutableWith: [] },
+    })
+    await pause(100)
+
+    readResp = await specification.call('resource_specification', 'get_resource_specification', { address: applesId })
+    t.notOk(readResp.resourceSpecification.substitutableWith, 'substitute removed in reverse direction on update')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('Intent matching includes substitutable specifications', async (t) => {
+  const alice = await buildPlayer(['planning', 'specification'])
+  try {
+    const { cells: [planning, specification] } = alice
+
+    const applesResp = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: { name: 'apples' },
+    })
+    const applesId = applesResp.resourceSpecification.id
+    await pause(100)
+    const pearsResp = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: { name: 'pears', substitutableWith: [applesId] },
+    })
+    const pearsId = pearsResp.resourceSpecification.id
+    await pause(100)
+    const quincesResp = await specification.call('resource_specification', 'create_resource_specification', {
+      resourceSpecification: { name: 'quinces', substitutableWith: [pearsId] },
+    })
+    const quincesId = quincesResp.resourceSpecification.id
+    await pause(100)
+
+    const appleIntent = await planning.call('intent', 'create_intent', {
+      intent: { ...testIntentProps, resourceConformsTo: applesId },
+    })
+    const pearIntent = await planning.call('intent', 'create_intent', {
+      intent: { ...testIntentProps, resourceConformsTo: pearsId },
+    })
+    await planning.call('intent', 'create_intent', {
+      intent: { ...testIntentProps, resourceConformsTo: quincesId },
+    })
+    await planning.call('intent', 'create_intent', {
+      intent: { ...testIntentProps, resourceConformsTo: applesId, finished: true },
+    })
+    await pause(100)
+
+    const matches = await planning.call('intent', 'match_intents', { resourceConformsTo: applesId })
+    t.equal(matches.length, 2, 'unfinished direct & one-hop substitute matches returned')
+    t.deepEqual(matches[0].intent.id, appleIntent.intent.id, 'direct match returned first')
+    t.equal(matches[0].substitute, false, 'direct match not flagged as substitute')
+    t.deepEqual(matches[1].intent.id, pearIntent.intent.id, 'substitute match returned')
+    t.equal(matches[1].substitute, true, 'substitute match flagged')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
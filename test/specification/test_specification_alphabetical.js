@@ -0,0 +1,75 @@
+import test from 'tape'
+import { pause } from '@holochain/tryorama'
+import {
+  buildPlayer,
+} from '../init.js'
+
+const names = (resp) => resp.edges.map(e => e.node.name)
+
+test('ResourceSpecifications are listed alphabetically', async (t) => {
+  // display the filename for context in the terminal and use .warn
+  // to override the tap testing log filters
+  console.warn(`\n\n${import.meta.url}`)
+  const alice = await buildPlayer(['specification'])
+  try {
+    const { cells: [specification] } = alice
+
+    const ids = {}
+    for (const name of ['pears', 'Apples', 'Émincé', 'apricots', '3-ply box', 'bananas']) {
+      const resp = await specification.call('resource_specification', 'create_resource_specification', {
+        resourceSpecification: { name },
+      })
+      ids[name] = resp.resourceSpecification
+    }
+    await pause(100)
+
+    let resp = await specification.call('resource_specification', 'list_resource_specifications_alphabetical', {})
+    t.deepEqual(names(resp), ['3-ply box', 'Apples', 'apricots', 'bananas', 'pears', 'Émincé'], 'listed in name order, non-ASCII last')
+    t.notOk(resp.pageInfo.hasNextPage, 'single page')
+
+    resp = await specification.call('resource_specification', 'list_resource_specifications_alphabetical', { startingWith: 'a', limit: 2 })
+    t.deepEqual(names(resp), ['Apples', 'apricots'], 'listing starts at requested letter')
+    t.ok(resp.pageInfo.hasNextPage, 'further pages indicated')
+
+    resp = await specification.call('resource_specification', 'list_resource_specifications_alphabetical', { cursor: resp.pageInfo.endCursor, limit: 2 })
+    t.deepEqual(names(resp), ['bananas', 'pears'], 'next page follows cursor across buckets')
+    t.ok(resp.pageInfo.hasPreviousPage, 'previous page indicated')
+
+    await specification.call('resource_specification', 'update_resource_specification', {
+      resourceSpecification: { revisionId: ids.pears.revisionId, name: 'avocados' },
+    })
+    await specification.call('resource_specification', 'delete_resource_specification', { revisionId: ids.bananas.revisionId })
+    await pause(100)
+
+    resp = await specification.call('resource_specification', 'list_resource_specifications_alphabetical', { startingWith: 'a' })
+    t.deepEqual(names(resp), ['Apples', 'apricots', 'avocados', 'Émincé'], 'renamed record moved between buckets, deleted record removed')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
+
+test('ProcessSpecifications are listed alphabetically', async (t) => {
+  const alice = await buildPlayer(['specification'])
+  try {
+    const { cells: [specification] } = alice
+
+    for (const name of ['weaving', 'dyeing', 'carding', 'Spinning']) {
+      await specification.call('process_specification', 'create_process_specification', {
+        processSpecification: { name },
+      })
+    }
+    await pause(100)
+
+    let resp = await specification.call('process_specification', 'list_process_specifications_alphabetical', {})
+    t.deepEqual(names(resp), ['carding', 'dyeing', 'Spinning', 'weaving'], 'listed in name order')
+
+    resp = await specification.call('process_specification', 'list_process_specifications_alphabetical', { startingWith: 'S' })
+    t.deepEqual(names(resp), ['Spinning', 'weaving'], 'starting letter is case-insensitive')
+  } catch (e) {
+    await alice.scenario.cleanUp()
+    throw e
+  }
+  await alice.scenario.cleanUp()
+})
@@ -7,6 +7,7 @@
  * @since:   2019-12-23
  */
 use hdk::prelude::*;
+use hdk_rpc_errors::{ErrorCode, ErrorEnvelope};
 
 use vf_attributes_hdk::{
     ActionId,
@@ -26,7 +27,9 @@ struct ById {
 fn get_action(ById { id }: ById) -> ExternResult<Action> {
     match get_builtin_action(id.as_ref()) {
         Some(action) => Ok(action),
-        None => Err(wasm_error!(WasmErrorInner::Guest(format!("No action with ID '{}' available", id.as_ref())))),
+        None => Err(ErrorEnvelope::new(ErrorCode::NotFound, format!("No action with ID '{}' available", id.as_ref()))
+            .with_field("id")
+            .into_wasm_error()),
     }
 }
 
@@ -8,7 +8,9 @@
  * @package hREA
  */
 use hdi::prelude::*;
-pub use hc_zome_rea_agent_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_rate_limits::validate_creation_rate;
+use hdk_records::slugs::{ validate_slug_claim_link, validate_slug_claim_unlink };
+pub use hc_zome_rea_agent_storage::{EntryTypes, EntryTypesUnit, LinkTypes, AGENT_ENTRY_TYPE};
 
 #[hdk_extern]
 pub fn entry_defs(_: ()) -> ExternResult<EntryDefsCallbackResult> {
@@ -27,4 +29,30 @@ pub fn __num_entry_types() -> u8 {
 #[no_mangle]
 pub fn __num_link_types() -> u8 {
     LinkTypes::len()
-}
\ No newline at end of file
+}
+
+#[hdk_extern]
+fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+    match op {
+        Op::StoreRecord { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::StoreEntry { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterCreateLink(RegisterCreateLink { create_link }) => {
+            let link = &create_link.hashed.content;
+            match LinkTypes::from_type(link.zome_index, link.link_type)? {
+                Some(LinkTypes::SlugIndex) => validate_slug_claim_link(&link.author, &link.target_address, &link.tag),
+                _ => Ok(ValidateCallbackResult::Valid),
+            }
+        },
+        Op::RegisterDeleteLink(RegisterDeleteLink { delete_link, create_link }) => {
+            match LinkTypes::from_type(create_link.zome_index, create_link.link_type)? {
+                Some(LinkTypes::SlugIndex) => validate_slug_claim_unlink(&delete_link.hashed.content.author, &create_link.author),
+                _ => Ok(ValidateCallbackResult::Valid),
+            }
+        },
+        Op::RegisterUpdate { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterDelete { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterAgentActivity(RegisterAgentActivity { action, .. }) => validate_creation_rate(
+            &action, &ScopedEntryDefIndex::try_from(&EntryTypesUnit::Agent)?, AGENT_ENTRY_TYPE,
+        ),
+    }
+}
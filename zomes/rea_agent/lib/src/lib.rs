@@ -9,6 +9,7 @@
 use paste::paste;
 use hdk::prelude::*;
 use hdk_records::{
+    generate_patch_builder, log_warn,
     RecordAPIResult,
     records::{
         create_record,
@@ -17,30 +18,78 @@ use hdk_records::{
         update_record,
         delete_record,
     },
-    metadata::read_revision_metadata_abbreviated,
+    metadata::{read_revision_metadata_abbreviated, read_record_author},
+    diffs::diff_record_revisions,
+    history::read_revision_window,
+    rate_limits::throw_if_creation_rate_exceeded,
+    rpc::{call_zome_method, call_local_zome_method},
+    dependencies::{
+        throw_if_dependents,
+        summarize_dependents,
+    },
+    slugs::{
+        sync_slug_index,
+        throw_if_slug_taken,
+        resolve_by_slug,
+    },
+    FieldChange,
     SignedActionHashed,
     DataIntegrityError,
     DnaAddressable,
+    MaybeUndefined,
 };
 use hdk_semantic_indexes_client_lib::*;
 
 use hc_zome_rea_agent_storage::*;
 use hc_zome_rea_agent_rpc::*;
+use hc_zome_rea_commitment_storage_consts::COMMITMENT_READ_BATCH_API_METHOD;
+use hc_zome_rea_commitment_rpc::{
+    Response as CommitmentResponse,
+    ResponseData as CommitmentResponseData,
+};
+use hc_zome_rea_intent_storage_consts::INTENT_READ_BATCH_API_METHOD;
+use hc_zome_rea_intent_rpc::{
+    Response as IntentResponse,
+    ResponseData as IntentResponseData,
+};
+use hc_zome_rea_economic_event_storage_consts::EVENT_READ_BATCH_API_METHOD;
+use hc_zome_rea_economic_event_rpc::{
+    Response as EconomicEventResponse,
+    ResponseData as EconomicEventResponseData,
+};
+use hc_zome_rea_network_settings_lib::NetworkSettingsReader;
+
+pub use hc_zome_rea_agent_storage::{AGENT_ENTRY_TYPE, EntryData};
 
-pub use hc_zome_rea_agent_storage::AGENT_ENTRY_TYPE;
+/// Name of the slug index of agents, @see hdk_records::slugs
+const SLUG_INDEX: &str = "agent.slug";
 
 /// properties accessor for zome config
 fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
     Some(conf.agent.index_zome)
 }
 
+/// properties accessor for zome config
+fn read_network_settings_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.agent.network_settings_zome
+}
+
+derived_index!(agent {
+    field: classified_as,
+    path: classification_index_paths
+}<AgentClassificationId>);
+
 pub fn handle_create_agent<S>(entry_def_id: S, agent: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display
 {
-    let agent_type = agent.agent_type.clone();
+    throw_if_creation_rate_exceeded(&EntryTypesUnit::Agent, entry_def_id.as_ref())?;
+    if let MaybeUndefined::Some(slug) = &agent.slug {
+        throw_if_slug_taken::<AgentAddress, _,_,_>(LinkTypes::SlugIndex, SLUG_INDEX, slug, None)?;
+    }
     let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, agent)?;
-    let e = update_string_index!(agent(&base_address).agent_type(vec![agent_type])<AgentTypeId>);
-    hdk::prelude::debug!("handle_create_agent::agent_type index {:?}", e);
+    log_index_results("handle_create_agent", create_agent_indexes(&base_address, &entry_resp)?);
+    let e = create_agent_classified_as_index(&base_address, &entry_resp);
+    hdk::prelude::debug!("handle_create_agent::classified_as index {:?}", e);
     construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
 }
 
@@ -75,6 +124,34 @@ pub fn handle_get_my_agent() -> RecordAPIResult<ResponseData>
     handle_whois_query(my_pub_key)
 }
 
+/// Whether the given agent has associated an `Agent` profile, without reading the profile itself.
+/// Used by index zomes to check the caller's access to members-only queries.
+///
+pub fn handle_has_associated_agent(agent_pubkey: AgentPubKey) -> RecordAPIResult<bool>
+{
+    Ok(!get_links(agent_pubkey, LinkTypes::MyAgent, None)?.is_empty())
+}
+
+/// Whether `agent_pub_key` acts on behalf of `agent`- because `agent` is the profile associated
+/// with the key, or an organization which the associated profile is a member of.
+/// Used by other DNAs to determine the role of a caller with respect to records `agent` is accountable for.
+///
+pub fn handle_is_represented_by(AgentRepresentationParams { agent, agent_pub_key }: AgentRepresentationParams) -> RecordAPIResult<bool>
+{
+    let associated = match get_links(agent_pub_key, LinkTypes::MyAgent, None)?.pop() {
+        Some(link) => match link.target.into_entry_hash() {
+            Some(hash) => AgentAddress::new(dna_info()?.hash, hash),
+            None => return Ok(false),
+        },
+        None => return Ok(false),
+    };
+    if associated == agent {
+        return Ok(true);
+    }
+    let organizations: Vec<AgentAddress> = read_index!(agent(&associated).member_of)?;
+    Ok(organizations.contains(&agent))
+}
+
 pub fn handle_whois_query(agent_pubkey: AgentPubKey) -> RecordAPIResult<ResponseData>
 {
     let mut links = get_links(agent_pubkey, LinkTypes::MyAgent, None)?;
@@ -95,24 +172,379 @@ pub fn handle_get_agent(address: AgentAddress) -> RecordAPIResult<ResponseData>
     construct_response(&base_address, &revision, &entry, get_link_fields(&base_address)?)
 }
 
+/// Index writes made for an `Agent` when it is created, and re-run to resync its indexes.
+///
+fn create_agent_indexes(base_address: &AgentAddress, entry: &EntryData) -> RecordAPIResult<RecordIndexResults>
+{
+    sync_slug_index(LinkTypes::SlugIndex, SLUG_INDEX, base_address, Some(entry), None, extract_slug, false)?;
+    Ok(vec![
+        ("agent_type", update_string_index!(agent(base_address).agent_type(vec![entry.agent_type.to_owned()])<AgentTypeId>)),
+    ])
+}
+
+/// Rebuild the indexes of an `Agent` from its latest revision.
+///
+pub fn handle_resync_agent_indexes(address: AgentAddress) -> RecordAPIResult<bool>
+{
+    let (_meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+
+    let mut results = create_agent_indexes(&base_address, &entry)?;
+    results.push(("classified_as", resync_agent_classified_as_index(&base_address, &entry)));
+    throw_if_resync_failed(results)
+}
+
+/// Read every index held against an agent in one call.
+/// The agent need not have a profile record; unindexed sections are returned empty.
+///
+pub fn handle_get_agent_context(address: AgentAddress) -> RecordAPIResult<AgentContext>
+{
+    Ok(AgentContext {
+        commitments_as_provider: read_index_if_exists!(agent(&address).commitments_as_provider)?.unwrap_or_default(),
+        commitments_as_receiver: read_index_if_exists!(agent(&address).commitments_as_receiver)?.unwrap_or_default(),
+        intents_as_provider: read_index_if_exists!(agent(&address).intents_as_provider)?.unwrap_or_default(),
+        intents_as_receiver: read_index_if_exists!(agent(&address).intents_as_receiver)?.unwrap_or_default(),
+        economic_events_as_provider: read_index_if_exists!(agent(&address).economic_events_as_provider)?.unwrap_or_default(),
+        economic_events_as_receiver: read_index_if_exists!(agent(&address).economic_events_as_receiver)?.unwrap_or_default(),
+        inventoried_economic_resources: read_index_if_exists!(agent(&address).inventoried_economic_resources)?.unwrap_or_default(),
+        agent: address,
+    })
+}
+
+/// Make `member` a member of the agent `organization`, updating both sides of the membership.
+///
+/// Only agents of the types listed in the zome's `organization_agent_types` config may have members,
+/// and only those listed in `member_agent_types` may be members.
+///
+pub fn handle_add_membership(MembershipParams { organization, member }: MembershipParams) -> RecordAPIResult<bool>
+{
+    let (_, _, org_entry) = read_record_entry::<EntryData, EntryStorage, AgentAddress>(organization.as_ref())?;
+    let (_, _, member_entry) = read_record_entry::<EntryData, EntryStorage, AgentAddress>(member.as_ref())?;
+    read_zome_config()?.validate_membership(&org_entry.agent_type, &member_entry.agent_type)?;
+
+    throw_if_index_update_failed(
+        create_index!(agent.member_of(&organization), agent.members(&member))
+    )?;
+    Ok(true)
+}
+
+/// Remove the membership of `member` in `organization`, from both sides of the membership.
+///
+pub fn handle_remove_membership(MembershipParams { organization, member }: MembershipParams) -> RecordAPIResult<bool>
+{
+    throw_if_index_update_failed(
+        update_index!(agent.member_of.not(&vec![organization]), agent.members(&member))
+    )?;
+    Ok(true)
+}
+
+/// Read the member agents of an organization
+///
+pub fn handle_query_org_members(organization: AgentAddress) -> RecordAPIResult<Vec<ResponseData>>
+{
+    let members: Vec<AgentAddress> = read_index!(agent(&organization).members)?;
+    members.into_iter().map(handle_get_agent).collect()
+}
+
+/// Read the organizations an agent is a member of
+///
+pub fn handle_query_agent_memberships(address: AgentAddress) -> RecordAPIResult<Vec<ResponseData>>
+{
+    let organizations: Vec<AgentAddress> = read_index!(agent(&address).member_of)?;
+    organizations.into_iter().map(handle_get_agent).collect()
+}
+
+/// Find the agent holding `slug`, or which held it prior to changing slug
+///
+pub fn handle_resolve_by_slug(slug: String) -> RecordAPIResult<Option<SlugResolution<AgentAddress>>>
+{
+    resolve_by_slug(LinkTypes::SlugIndex, SLUG_INDEX, slug)
+}
+
+fn extract_slug(entry: &EntryData) -> Option<String> {
+    entry.slug.to_owned()
+}
+
+fn read_zome_config() -> RecordAPIResult<AgentZomeConfig> {
+    let conf: DnaConfigSlice = dna_info()?.modifiers.properties.try_into()?;
+    Ok(conf.agent)
+}
+
+/// Default page size for "my records" queries
+const MY_RECORDS_PAGE_SIZE: usize = 30;
+
+/// Read the current user's `Commitment`s, as provider or receiver.
+///
+pub fn handle_get_my_commitments(params: MyRecordsParams) -> RecordAPIResult<MyRecordsConnection<CommitmentResponse>>
+{
+    let agent = match my_agent_address()? {
+        None => return Ok(MyRecordsConnection::not_associated()),
+        Some(agent) => agent,
+    };
+    let addresses = match params.role {
+        AgentRole::Provider => read_index!(agent(&agent).commitments_as_provider)?,
+        AgentRole::Receiver => read_index!(agent(&agent).commitments_as_receiver)?,
+    };
+    page_my_records(addresses, &params, |batch| {
+        let records: Vec<(_, CommitmentResponseData)> = read_records_batch(batch, &COMMITMENT_READ_BATCH_API_METHOD)?;
+        Ok(records.into_iter().map(|(id, r)| (id, r.commitment)).collect())
+    })
+}
+
+/// Read the current user's `Intent`s, as provider or receiver.
+///
+pub fn handle_get_my_intents(params: MyRecordsParams) -> RecordAPIResult<MyRecordsConnection<IntentResponse>>
+{
+    let agent = match my_agent_address()? {
+        None => return Ok(MyRecordsConnection::not_associated()),
+        Some(agent) => agent,
+    };
+    let addresses = match params.role {
+        AgentRole::Provider => read_index!(agent(&agent).intents_as_provider)?,
+        AgentRole::Receiver => read_index!(agent(&agent).intents_as_receiver)?,
+    };
+    page_my_records(addresses, &params, |batch| {
+        let records: Vec<(_, IntentResponseData)> = read_records_batch(batch, &INTENT_READ_BATCH_API_METHOD)?;
+        Ok(records.into_iter().map(|(id, r)| (id, r.intent)).collect())
+    })
+}
+
+/// Read the current user's `EconomicEvent`s, as provider or receiver.
+///
+pub fn handle_get_my_economic_events(params: MyRecordsParams) -> RecordAPIResult<MyRecordsConnection<EconomicEventResponse>>
+{
+    let agent = match my_agent_address()? {
+        None => return Ok(MyRecordsConnection::not_associated()),
+        Some(agent) => agent,
+    };
+    let addresses = match params.role {
+        AgentRole::Provider => read_index!(agent(&agent).economic_events_as_provider)?,
+        AgentRole::Receiver => read_index!(agent(&agent).economic_events_as_receiver)?,
+    };
+    page_my_records(addresses, &params, |batch| {
+        let records: Vec<(_, EconomicEventResponseData)> = read_records_batch(batch, &EVENT_READ_BATCH_API_METHOD)?;
+        Ok(records.into_iter().map(|(id, r)| (id, r.economic_event)).collect())
+    })
+}
+
+/// Address of the `Agent` associated with the current user, if any
+///
+fn my_agent_address() -> RecordAPIResult<Option<AgentAddress>>
+{
+    match handle_get_my_agent() {
+        Ok(agent) => Ok(Some(agent.agent.id)),
+        Err(DataIntegrityError::AgentNotLinked) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Select one page of `addresses` following the cursor in `params` and load its records.
+/// Records which can no longer be read are omitted from the page.
+///
+fn page_my_records<A, T, F>(addresses: Vec<A>, params: &MyRecordsParams, read_batch: F) -> RecordAPIResult<MyRecordsConnection<T>>
+    where A: Clone + PartialEq + std::fmt::Display,
+        F: FnOnce(Vec<A>) -> RecordAPIResult<Vec<(A, T)>>,
+{
+    let total = addresses.len();
+    let limit = params.limit.unwrap_or(MY_RECORDS_PAGE_SIZE);
+    let start = match &params.cursor {
+        None => 0,
+        Some(cursor) => match addresses.iter().position(|a| &a.to_string() == cursor) {
+            Some(pos) => pos + 1,
+            // unknown cursor: nothing follows it
+            None => total,
+        },
+    };
+    let end = std::cmp::min(start + limit, total);
+    let page: Vec<A> = addresses[start..end].to_vec();
+
+    let mut records = match page.is_empty() {
+        true => vec![],
+        false => read_batch(page.to_owned())?,
+    };
+    let edges: Vec<MyRecordEdge<T>> = page.iter()
+        .filter_map(|address| {
+            let idx = records.iter().position(|(id, _)| id == address)?;
+            let (_, node) = records.swap_remove(idx);
+            Some(MyRecordEdge { node, cursor: address.to_string() })
+        })
+        .collect();
+
+    Ok(MyRecordsConnection {
+        page_info: PageInfo {
+            start_cursor: edges.first().map(|e| e.cursor.to_owned()).unwrap_or_else(|| "0".to_string()),
+            end_cursor: edges.last().map(|e| e.cursor.to_owned()).unwrap_or_else(|| "0".to_string()),
+            has_previous_page: start > 0,
+            has_next_page: end < total,
+            total_count: Some(total),
+            page_limit: Some(limit),
+        },
+        edges,
+        not_associated: false,
+    })
+}
+
+/// Read a set of records held in other DNAs, making one request per DNA.
+/// Records which cannot be read are omitted from the output.
+///
+fn read_records_batch<A, R, S>(addresses: Vec<A>, permission_id: &S) -> RecordAPIResult<Vec<(A, R)>>
+    where A: AsRef<DnaHash> + Clone + Serialize + serde::de::DeserializeOwned + std::fmt::Debug + std::fmt::Display,
+        R: serde::de::DeserializeOwned + std::fmt::Debug,
+        S: AsRef<str>,
+{
+    let mut by_dna: Vec<(DnaHash, Vec<A>)> = vec![];
+    for address in addresses {
+        let dna: &DnaHash = address.as_ref();
+        match by_dna.iter_mut().find(|(d, _)| d == dna) {
+            Some((_, batch)) => batch.push(address),
+            None => by_dna.push((dna.to_owned(), vec![address])),
+        }
+    }
+
+    let mut records = vec![];
+    for (_dna, batch) in by_dna {
+        let results: Vec<ByAddressResult<A, R>> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+            &batch[0],
+            permission_id,
+            ByAddresses { addresses: batch.to_owned() },
+            LinkTypes::AvailableCapability,
+        )?;
+        for result in results {
+            let address = result.address.to_owned();
+            match result.into_result() {
+                Ok(record) => records.push((address, record)),
+                Err(e) => log_warn!("read_records_batch", address = address; "omitting unreadable record: {}", e),
+            }
+        }
+    }
+    Ok(records)
+}
+
 pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
 {
     let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
     construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
 }
 
+/// List the fields which changed between two revisions of the same `Agent`.
+///
+pub fn handle_diff_agent_revisions(from_revision_id: ActionHash, to_revision_id: ActionHash) -> RecordAPIResult<Vec<FieldChange>>
+{
+    diff_record_revisions::<EntryData, EntryStorage, AgentAddress>(&from_revision_id, &to_revision_id)
+}
+
+/// Read the revisions of an agent written within the window described by `params`
+///
+pub fn handle_get_agent_revision_history(params: RevisionHistoryParams<AgentAddress>) -> RecordAPIResult<RevisionHistory<ResponseData>>
+{
+    let link_fields = get_link_fields(&params.address)?;
+    read_revision_window::<EntryData, EntryStorage, AgentAddress, _, _>(
+        &params.address, params.since, params.until, params.limit,
+        |meta, entry| construct_response(&params.address, meta, entry, link_fields.to_owned()),
+    )
+}
+
 pub fn handle_update_agent(agent: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
     let revision_hash = agent.get_revision_id().clone();
-    let (meta, identity_address, entry, _prev_entry): (_,_, EntryData, EntryData) = update_record(&revision_hash, agent)?;
+    if let MaybeUndefined::Some(true) = agent.retired {
+        let (_meta, identity_address, entry): (_, AgentAddress, EntryData) = read_record_entry_by_action(&revision_hash)?;
+        if !entry.retired {
+            throw_if_cannot_retire(&identity_address)?;
+        }
+    }
+    let (meta, identity_address, entry, prev_entry): (_,_, EntryData, EntryData) = update_record(&revision_hash, agent)?;
+
+    hdk_records::return_if_unchanged!(meta, revision_hash, construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?), agent);
+
+    sync_slug_index(LinkTypes::SlugIndex, SLUG_INDEX, &identity_address, Some(&entry), Some(&prev_entry), extract_slug, read_zome_config()?.keep_slug_redirects)?;
+    let e = update_agent_classified_as_index(&identity_address, &entry, &prev_entry);
+    hdk::prelude::debug!("handle_update_agent::classified_as index {:?}", e);
+    if entry.retired && !prev_entry.retired {
+        let _retired: bool = call_local_zome_method(read_index_zome, "retire_agent", ByAddress { address: identity_address.to_owned() })?;
+    }
     construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?)
 }
 
-pub fn handle_delete_agent(revision_id: ActionHash) -> RecordAPIResult<bool> {
+generate_patch_builder!(UpdateRequest {
+    name,
+    agent_type,
+    slug,
+    image,
+    classified_as,
+    note,
+    retired
+});
+
+/// Apply a set of `PatchOp`s to the latest revision of a `Agent`.
+/// The patch is converted to an `UpdateRequest` and applied via `handle_update_agent`.
+///
+pub fn handle_patch_agent(address: AgentAddress, ops: Vec<PatchOp>) -> RecordAPIResult<ResponseData>
+{
+    let revision_id = handle_get_agent(address)?.agent.revision_id;
+    handle_update_agent(build_patch_update(revision_id, ops)?)
+}
+
+/// Ensure the calling agent may retire the agent identified by `address`. Only the agent which
+/// created the record, or a network admin, may retire it.
+///
+fn throw_if_cannot_retire(address: &AgentAddress) -> RecordAPIResult<()> {
+    if read_record_author(address.as_ref())? == agent_info()?.agent_latest_pubkey {
+        return Ok(());
+    }
+    NetworkSettingsReader::new(read_network_settings_zome).throw_if_not_admin()
+}
+
+/// Name given to agents whose profile has been cleared by `handle_delete_agent`
+pub const ANONYMISED_AGENT_NAME: &str = "Deleted agent";
+
+/// Delete an `Agent`, refusing to do so whilst any records reference it as provider, receiver
+/// or primary accountable.
+///
+/// If `anonymise` is set, the agent's profile is instead cleared and the record retired, such that
+/// all references to it remain resolvable. Since nothing is deleted, `false` is returned.
+///
+pub fn handle_delete_agent(revision_id: ActionHash, anonymise: bool) -> RecordAPIResult<bool> {
+    if anonymise {
+        handle_update_agent(UpdateRequest {
+            revision_id,
+            name: MaybeUndefined::Some(ANONYMISED_AGENT_NAME.to_string()),
+            agent_type: MaybeUndefined::Undefined,
+            slug: MaybeUndefined::None,
+            image: MaybeUndefined::None,
+            classified_as: MaybeUndefined::Undefined,
+            note: MaybeUndefined::None,
+            retired: MaybeUndefined::Some(true),
+        })?;
+        return Ok(false);
+    }
 
     // load the record to ensure it is of the correct type
-    let (_revision, _base_address, _entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
-    // This is where indexes would be updated if necessary
+    let (_revision, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+
+    // check for inbound references
+    let context = handle_get_agent_context(base_address.to_owned())?;
+    throw_if_dependents(vec![
+        summarize_dependents("commitments as provider", &context.commitments_as_provider),
+        summarize_dependents("commitments as receiver", &context.commitments_as_receiver),
+        summarize_dependents("intents as provider", &context.intents_as_provider),
+        summarize_dependents("intents as receiver", &context.intents_as_receiver),
+        summarize_dependents("economic events as provider", &context.economic_events_as_provider),
+        summarize_dependents("economic events as receiver", &context.economic_events_as_receiver),
+        summarize_dependents("inventoried economic resources", &context.inventoried_economic_resources),
+    ])?;
+    let e = delete_agent_classified_as_index(&base_address, &entry);
+    hdk::prelude::debug!("handle_delete_agent::classified_as index {:?}", e);
+    sync_slug_index(LinkTypes::SlugIndex, SLUG_INDEX, &base_address, None, Some(&entry), extract_slug, false)?;
+    let members: Vec<AgentAddress> = read_index!(agent(&base_address).members)?;
+    if !members.is_empty() {
+        let e = update_index!(agent.members.not(&members), agent.member_of(&base_address));
+        hdk::prelude::debug!("handle_delete_agent::members index {:?}", e);
+    }
+    let organizations: Vec<AgentAddress> = read_index!(agent(&base_address).member_of)?;
+    if !organizations.is_empty() {
+        let e = update_index!(agent.member_of.not(&organizations), agent.members(&base_address));
+        hdk::prelude::debug!("handle_delete_agent::member_of index {:?}", e);
+    }
 
     delete_record::<EntryStorage>(&revision_id)
 }
@@ -134,6 +566,8 @@ fn construct_response<'a>(
         economic_events_as_provider,
         economic_events_as_receiver,
         inventoried_economic_resources,
+        members,
+        member_of,
     ): (
         // Vec<CommitmentAddress>,
         // Vec<EconomicEventAddress>,
@@ -149,6 +583,8 @@ fn construct_response<'a>(
         Vec<EconomicEventAddress>,
         Vec<EconomicEventAddress>,
         Vec<EconomicResourceAddress>,
+        Vec<AgentAddress>,
+        Vec<AgentAddress>,
     ),
 ) -> RecordAPIResult<ResponseData> {
     Ok(ResponseData {
@@ -158,6 +594,7 @@ fn construct_response<'a>(
             meta: read_revision_metadata_abbreviated(meta)?,
             name: e.name.to_owned(),
             agent_type: e.agent_type.to_owned(),
+            slug: e.slug.to_owned(),
             image: e.image.to_owned(),
             classified_as: e.classified_as.to_owned(),
             note: e.note.to_owned(),
@@ -175,6 +612,9 @@ fn construct_response<'a>(
             economic_events_as_provider: economic_events_as_provider.to_owned(),
             economic_events_as_receiver: economic_events_as_receiver.to_owned(),
             inventoried_economic_resources: inventoried_economic_resources.to_owned(),
+            members: members.to_owned(),
+            member_of: member_of.to_owned(),
+            retired: e.retired,
         }
     })
 }
@@ -202,6 +642,8 @@ fn get_link_fields(base_address: &AgentAddress) -> RecordAPIResult<(
     Vec<EconomicEventAddress>,
     Vec<EconomicEventAddress>,
     Vec<EconomicResourceAddress>,
+    Vec<AgentAddress>,
+    Vec<AgentAddress>,
 )> {
     Ok((
         // read_index!(agent(base_address).commitments)?,
@@ -218,5 +660,7 @@ fn get_link_fields(base_address: &AgentAddress) -> RecordAPIResult<(
         read_index!(agent(base_address).economic_events_as_provider)?,
         read_index!(agent(base_address).economic_events_as_receiver)?,
         read_index!(agent(base_address).inventoried_economic_resources)?,
+        read_index!(agent(base_address).members)?,
+        read_index!(agent(base_address).member_of)?,
     ))
 }
@@ -10,21 +10,27 @@ use serde_maybe_undefined::MaybeUndefined;
 use hdk_uuid_types::{ DnaHash, addressable_identifier };
 pub use hdk::prelude::*;
 pub use vf_attributes_hdk::{
+    ErrorCode, ErrorEnvelope,
+    PatchOp, PatchParams,
     AgentAddress,
     ProcessAddress,
     EconomicEventAddress,
     CommitmentAddress,
     IntentAddress,
     ActionHash,
-    ExternalURL,
+    ExternalURL, classification_index_paths,
     EconomicResourceAddress,
     PlanAddress,
     ProposalAddress,
-    ByRevision, RecordMeta, RevisionMeta, ByAddress,
+    ByRevision, RecordMeta, RevisionMeta, ByAddress, ByAddresses, ByAddressResult,
+    FieldChange, RevisionDiffParams, RevisionHistoryParams, RevisionHistory,
+    SlugParams, SlugResolution,
 };
+pub use hdk_relay_pagination::PageInfo;
 
 // internal type for indexing against agent_type string
 addressable_identifier!(AgentTypeId => EntryHash);
+addressable_identifier!(AgentClassificationId => EntryHash);
 
 //---------------- EXTERNAL RECORD STRUCTURE ----------------
 
@@ -34,37 +40,59 @@ addressable_identifier!(AgentTypeId => EntryHash);
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     pub id: AgentAddress,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     pub meta: RecordMeta,
     pub name: String,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "agent_type"))]
     pub agent_type: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub image: Option<ExternalURL>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "classified_as"))]
     pub classified_as: Option<Vec<ExternalURL>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub note: Option<String>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "commitments_as_provider"))]
     pub commitments_as_provider: Vec<CommitmentAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "commitments_as_receiver"))]
     pub commitments_as_receiver: Vec<CommitmentAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "intents_as_provider"))]
     pub intents_as_provider: Vec<IntentAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "intents_as_receiver"))]
     pub intents_as_receiver: Vec<IntentAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "economic_events_as_provider"))]
     pub economic_events_as_provider: Vec<EconomicEventAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "economic_events_as_receiver"))]
     pub economic_events_as_receiver: Vec<EconomicEventAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "inventoried_economic_resources"))]
     pub inventoried_economic_resources: Vec<EconomicResourceAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub members: Vec<AgentAddress>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "member_of"))]
+    pub member_of: Vec<AgentAddress>,
+    // set once the agent has been anonymised in place of deletion
+    #[serde(default)]
+    pub retired: bool,
 }
 
 /// I/O struct to describe what is returned outside the gateway.
@@ -77,6 +105,111 @@ pub struct ResponseData {
     pub agent: Response,
 }
 
+/// Aggregate of all economic activity indexed against an agent, for
+/// reading in a single call. Sections with no activity are empty.
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentContext {
+    pub agent: AgentAddress,
+    pub commitments_as_provider: Vec<CommitmentAddress>,
+    pub commitments_as_receiver: Vec<CommitmentAddress>,
+    pub intents_as_provider: Vec<IntentAddress>,
+    pub intents_as_receiver: Vec<IntentAddress>,
+    pub economic_events_as_provider: Vec<EconomicEventAddress>,
+    pub economic_events_as_receiver: Vec<EconomicEventAddress>,
+    pub inventoried_economic_resources: Vec<EconomicResourceAddress>,
+}
+
+/// Side of an exchange in which the current agent participates
+///
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum AgentRole {
+    Provider,
+    Receiver,
+}
+
+/// Parameters for reading the current agent's records, in index order.
+/// `cursor` is the ID of the last record of the previous page, as returned in its edge.
+///
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MyRecordsParams {
+    pub role: AgentRole,
+    #[serde(default)]
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Page of the current agent's records, in the same shape as index zome query results.
+/// `not_associated` is set where the current user has no associated `Agent`, in which
+/// case there are no results.
+///
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MyRecordsConnection<T> {
+    pub page_info: PageInfo,
+    pub edges: Vec<MyRecordEdge<T>>,
+    pub not_associated: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MyRecordEdge<T> {
+    pub node: T,
+    pub cursor: String,
+}
+
+impl<T> MyRecordsConnection<T> {
+    pub fn not_associated() -> Self {
+        MyRecordsConnection {
+            page_info: PageInfo {
+                start_cursor: "0".to_string(),
+                end_cursor: "0".to_string(),
+                has_previous_page: false,
+                has_next_page: false,
+                total_count: Some(0),
+                page_limit: None,
+            },
+            edges: vec![],
+            not_associated: true,
+        }
+    }
+}
+
+/// Parameters for adding or removing the membership of `member` in `organization`
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MembershipParams {
+    pub organization: AgentAddress,
+    pub member: AgentAddress,
+}
+
+/// Parameters for checking whether `agent_pub_key` acts on behalf of `agent`, either as
+/// the agent's associated profile or as a member of it.
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentRepresentationParams {
+    pub agent: AgentAddress,
+    pub agent_pub_key: AgentPubKey,
+}
+
+/// Parameters for deleting an `Agent`. Deletion is refused while other records reference the agent,
+/// unless `anonymise` is set- in which case the agent's profile is cleared and the record retired instead,
+/// and `false` is returned as nothing was deleted.
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AgentDeleteParams {
+    pub revision_id: ActionHash,
+    #[serde(default)]
+    pub anonymise: bool,
+}
+
 //---------------- CREATE REQUEST ----------------
 
 /// I/O struct to describe the complete input record, including all managed links
@@ -87,12 +220,18 @@ pub struct CreateRequest {
     #[serde(default)]
     pub name: String,
     #[serde()]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "agent_type"))]
     pub agent_type: String,
+    // unique handle for use in shareable URLs, @see hdk_records::slugs
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub slug: MaybeUndefined<String>,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
     pub image: MaybeUndefined<ExternalURL>,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "classified_as"))]
     pub classified_as: MaybeUndefined<Vec<ExternalURL>>,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
@@ -110,19 +249,33 @@ impl<'a> CreateRequest {
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateRequest {
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
     pub name: MaybeUndefined<String>,
+    // immutable, accepted only so that attempts to change it can be rejected
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "agent_type"))]
+    pub agent_type: MaybeUndefined<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub slug: MaybeUndefined<String>,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
     pub image: MaybeUndefined<ExternalURL>,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "classified_as"))]
     pub classified_as: MaybeUndefined<Vec<ExternalURL>>,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
     pub note: MaybeUndefined<String>,
+    // retired agents are excluded from agent listings; once set, this cannot be unset
+    #[serde(default)]
+    #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    pub retired: MaybeUndefined<bool>,
 }
 
 impl<'a> UpdateRequest {
@@ -156,4 +309,17 @@ pub struct QueryParams {
     // for internal use in order to query for people or organizations specifically
     pub agent_type: Option<String>,
     pub agent_type_internal: Option<AgentTypeId>,
+
+    pub classified_as: Option<String>,
+    pub classified_as_internal: Option<AgentClassificationId>,
+
+    pub members: Option<AgentAddress>,
+    pub member_of: Option<AgentAddress>,
+}
+
+#[cfg(all(test, feature = "snake_case_aliases"))]
+mod tests {
+    use super::*;
+
+    vf_attributes_hdk::snake_case_alias_tests!(Response, CreateRequest, UpdateRequest);
 }
@@ -11,6 +11,7 @@ use hdk::prelude::*;
 use hdk_records::{
     RecordAPIResult, DataIntegrityError,
     generate_record_entry,
+    immutable_fields,
     record_interface::{Updateable},
 };
 
@@ -38,6 +39,40 @@ pub struct DnaConfigSlice {
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
 pub struct AgentZomeConfig {
     pub index_zome: String,
+    // agent types which may have members, and which may be members; empty lists allow any type
+    #[serde(default = "default_organization_agent_types")]
+    pub organization_agent_types: Vec<String>,
+    #[serde(default = "default_member_agent_types")]
+    pub member_agent_types: Vec<String>,
+    // whether changed slugs continue to resolve to the agent
+    #[serde(default = "default_keep_slug_redirects")]
+    pub keep_slug_redirects: bool,
+    // zome ID of the network settings zome, used to authorise administrative operations
+    pub network_settings_zome: Option<String>,
+}
+
+fn default_organization_agent_types() -> Vec<String> {
+    vec!["Organization".to_string()]
+}
+
+fn default_member_agent_types() -> Vec<String> {
+    vec!["Person".to_string()]
+}
+
+fn default_keep_slug_redirects() -> bool {
+    true
+}
+
+impl AgentZomeConfig {
+    /// Ensure an agent of `member_type` may be made a member of an agent of `organization_type`
+    ///
+    pub fn validate_membership(&self, organization_type: &str, member_type: &str) -> RecordAPIResult<()> {
+        let allowed = |types: &Vec<String>, t: &str| types.is_empty() || types.iter().any(|a| a == t);
+        if !allowed(&self.organization_agent_types, organization_type) || !allowed(&self.member_agent_types, member_type) {
+            return Err(DataIntegrityError::InvalidMembership(organization_type.to_string(), member_type.to_string()));
+        }
+        Ok(())
+    }
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -46,9 +81,13 @@ pub struct AgentZomeConfig {
 pub struct EntryData {
     pub name: String,
     pub agent_type: String,
+    #[serde(default)]
+    pub slug: Option<String>,
     pub image: Option<ExternalURL>,
     pub classified_as: Option<Vec<ExternalURL>>,
     pub note: Option<String>,
+    #[serde(default)]
+    pub retired: bool,
     pub _nonce: Bytes,
 }
 
@@ -84,6 +123,9 @@ impl TryFrom<AvailableCapability> for EntryTypes {
 #[hdk_link_types(skip_no_mangle = true)]
 pub enum LinkTypes {
     MyAgent,
+    // unique lookup of agents by slug
+    // @see hdk_records::slugs
+    SlugIndex,
     // relates to dna-auth-resolver mixin
     // and remote authorizations
     AvailableCapability
@@ -99,9 +141,11 @@ impl TryFrom<CreateRequest> for EntryData {
         Ok(EntryData {
             name: e.name.into(),
             agent_type: e.agent_type.into(),
+            slug: e.slug.into(),
             image: e.image.into(),
             classified_as: e.classified_as.into(),
             note: e.note.into(),
+            retired: false,
             _nonce: random_bytes(32)?,
         })
     }
@@ -111,14 +155,51 @@ impl TryFrom<CreateRequest> for EntryData {
 
 /// Handles update operations by merging any newly provided fields
 impl Updateable<UpdateRequest> for EntryData {
+    immutable_fields!(UpdateRequest { agent_type });
+
     fn update_with(&self, e: UpdateRequest) -> RecordAPIResult<EntryData> {
         Ok(EntryData {
             name: if !e.name.is_some() { self.name.to_owned() } else { e.name.to_owned().unwrap() },
-            agent_type: self.agent_type.to_owned(), //assume cannot update agent_type
-            image: if !e.image.is_some() { self.image.to_owned() } else { e.image.to_owned().into() },
-            classified_as: if !e.classified_as.is_some() { self.classified_as.to_owned() } else { e.classified_as.to_owned().into() },
-            note: if !e.note.is_some() { self.note.to_owned() } else { e.note.to_owned().into() },
+            agent_type: self.agent_type.to_owned(),
+            slug: if e.slug.is_undefined() { self.slug.to_owned() } else { e.slug.to_owned().into() },
+            // `null` clears optional profile fields, as when anonymising
+            image: if e.image.is_undefined() { self.image.to_owned() } else { e.image.to_owned().into() },
+            classified_as: if e.classified_as.is_undefined() { self.classified_as.to_owned() } else { e.classified_as.to_owned().into() },
+            note: if e.note.is_undefined() { self.note.to_owned() } else { e.note.to_owned().into() },
+            retired: self.retired || e.retired.to_owned().to_option() == Some(true),
             _nonce: self._nonce.to_owned(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(path: &str) -> ExternalURL {
+        ExternalURL(format!("http://example.com/{}", path))
+    }
+
+    fn entry() -> EntryData {
+        EntryData {
+            name: "test person".to_string(),
+            agent_type: "Person".to_string(),
+            slug: Some("test-person".to_string()),
+            image: Some(url("avatar.png")),
+            classified_as: Some(vec![url("classifications/member")]),
+            note: Some("a note".to_string()),
+            retired: false,
+            _nonce: Bytes::from(vec![0; 32]),
+        }
+    }
+
+    hdk_records::update_semantics_tests!(EntryData, UpdateRequest, entry(), {
+        "name" => "renamed person",
+        "agentType" => "Organization",
+        "slug" => "renamed-person",
+        "image" => url("updated.png"),
+        "classifiedAs" => vec![url("classifications/founder")],
+        "note" => "updated note",
+        "retired" => true,
+    });
+}
@@ -6,3 +6,5 @@
  * @package hREA
  */
 pub const AGENT_ENTRY_TYPE: &str = "agent";
+
+pub const AGENT_REPRESENTATION_API_METHOD: &str = "read_agent_representation";
@@ -11,6 +11,9 @@ use hdk::prelude::*;
 
 use hc_zome_rea_agent_rpc::*;
 use hc_zome_rea_agent_lib::*;
+use hc_zome_rea_commitment_rpc::Response as CommitmentResponse;
+use hc_zome_rea_intent_rpc::Response as IntentResponse;
+use hc_zome_rea_economic_event_rpc::Response as EconomicEventResponse;
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -42,11 +45,83 @@ fn get_my_agent(_: ()) -> ExternResult<ResponseData> {
     Ok(handle_get_my_agent()?)
 }
 
+// used by index zomes configured with `query_access: members_only`
+#[hdk_extern]
+fn has_associated_agent(agent_pubkey: AgentPubKey) -> ExternResult<bool> {
+    Ok(handle_has_associated_agent(agent_pubkey)?)
+}
+
+// used by other DNAs to determine the role of a caller, @see hdk_records::projections
+#[hdk_extern]
+fn is_represented_by(params: AgentRepresentationParams) -> ExternResult<bool> {
+    Ok(handle_is_represented_by(params)?)
+}
+
 #[hdk_extern]
 fn get_agent(ByAddress { address }: ByAddress<AgentAddress>) -> ExternResult<ResponseData> {
     Ok(handle_get_agent(address)?)
 }
 
+#[hdk_extern]
+fn resync_record_indexes(ByAddress { address }: ByAddress<AgentAddress>) -> ExternResult<bool> {
+    Ok(handle_resync_agent_indexes(address)?)
+}
+
+#[hdk_extern]
+fn diff_revisions(RevisionDiffParams { from_revision_id, to_revision_id }: RevisionDiffParams) -> ExternResult<Vec<FieldChange>> {
+    Ok(handle_diff_agent_revisions(from_revision_id, to_revision_id)?)
+}
+
+#[hdk_extern]
+fn get_revision_history(params: RevisionHistoryParams<AgentAddress>) -> ExternResult<RevisionHistory<ResponseData>> {
+    Ok(handle_get_agent_revision_history(params)?)
+}
+
+#[hdk_extern]
+fn get_agent_context(ByAddress { address }: ByAddress<AgentAddress>) -> ExternResult<AgentContext> {
+    Ok(handle_get_agent_context(address)?)
+}
+
+#[hdk_extern]
+fn my_commitments(params: MyRecordsParams) -> ExternResult<MyRecordsConnection<CommitmentResponse>> {
+    Ok(handle_get_my_commitments(params)?)
+}
+
+#[hdk_extern]
+fn my_intents(params: MyRecordsParams) -> ExternResult<MyRecordsConnection<IntentResponse>> {
+    Ok(handle_get_my_intents(params)?)
+}
+
+#[hdk_extern]
+fn my_economic_events(params: MyRecordsParams) -> ExternResult<MyRecordsConnection<EconomicEventResponse>> {
+    Ok(handle_get_my_economic_events(params)?)
+}
+
+#[hdk_extern]
+fn add_membership(params: MembershipParams) -> ExternResult<bool> {
+    Ok(handle_add_membership(params)?)
+}
+
+#[hdk_extern]
+fn remove_membership(params: MembershipParams) -> ExternResult<bool> {
+    Ok(handle_remove_membership(params)?)
+}
+
+#[hdk_extern]
+fn query_org_members(ByAddress { address }: ByAddress<AgentAddress>) -> ExternResult<Vec<ResponseData>> {
+    Ok(handle_query_org_members(address)?)
+}
+
+#[hdk_extern]
+fn query_agent_memberships(ByAddress { address }: ByAddress<AgentAddress>) -> ExternResult<Vec<ResponseData>> {
+    Ok(handle_query_agent_memberships(address)?)
+}
+
+#[hdk_extern]
+fn resolve_by_slug(SlugParams { slug }: SlugParams) -> ExternResult<Option<SlugResolution<AgentAddress>>> {
+    Ok(handle_resolve_by_slug(slug)?)
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
@@ -75,6 +150,11 @@ fn update_agent(UpdateParams { agent }: UpdateParams) -> ExternResult<ResponseDa
 }
 
 #[hdk_extern]
-fn delete_agent(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
-    Ok(handle_delete_agent(revision_id)?)
+fn patch_record(PatchParams { address, ops }: PatchParams<AgentAddress>) -> ExternResult<ResponseData> {
+    Ok(handle_patch_agent(address, ops)?)
+}
+
+#[hdk_extern]
+fn delete_agent(AgentDeleteParams { revision_id, anonymise }: AgentDeleteParams) -> ExternResult<bool> {
+    Ok(handle_delete_agent(revision_id, anonymise)?)
 }
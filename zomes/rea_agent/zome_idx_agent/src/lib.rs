@@ -30,4 +30,27 @@ struct Agent {
     // :SHONK: redundant loopback index, required for internals of bidirectional index link management.
     // Aside from better support for such edge-cases, the other benefit to obviating this workaround is DHT bloat.
     agent_type_internal: Local<agent, agent_type>,
+
+    // query agents by classification (derived from `classified_as`)
+    classified_as: Local<agent, classified_as_internal>::String,
+    classified_as_internal: Local<agent, classified_as>,
+
+    // organizational membership
+    members: Local<agent, member_of>,
+    member_of: Local<agent, members>,
+}
+
+//---------------- RETIREMENT ----------------
+
+/// Remove a retired agent from the listing of all agents.
+/// The agent remains readable, and queryable via its other indexes.
+///
+/// Only the agent which created the record, or a network admin, may retire it.
+///
+#[hdk_extern]
+fn retire_agent(ByAddress { address }: ByAddress<AgentAddress>) -> ExternResult<bool> {
+    register_time_indexes(&TIME_INDEXES);
+    throw_if_index_read_only(read_index_read_only)?;
+    throw_if_not_record_author_or_network_admin(&address, read_network_settings_zome)?;
+    Ok(remove_from_time_index(&local_time_index_id(), &address)?)
 }
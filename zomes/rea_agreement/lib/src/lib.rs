@@ -7,21 +7,31 @@
  * @package hREA
  */
 use paste::paste;
+use hdk::prelude::{Serialize, EntryHash};
 use hdk_records::{
-    RecordAPIResult, SignedActionHashed,
+    generate_patch_builder,
+    RecordAPIResult, DataIntegrityError, SignedActionHashed, MaybeUndefined, OtherCellResult,
+    DeleteParams, DeletionReport,
     records::{
         create_record,
         read_record_entry,
         read_record_entry_by_action,
         update_record,
+        revision_changed,
         delete_record,
     },
     metadata::read_revision_metadata_abbreviated,
+    rpc::call_zome_method,
 };
 use hdk_semantic_indexes_client_lib::*;
 
 use hc_zome_rea_agreement_storage::*;
 use hc_zome_rea_agreement_rpc::*;
+use hc_zome_rea_commitment_rpc::ResponseData as CommitmentResponseData;
+use hc_zome_rea_commitment_storage_consts::{
+    COMMITMENT_CREATE_API_METHOD,
+    COMMITMENT_DELETE_API_METHOD,
+};
 
 
 pub use hc_zome_rea_agreement_storage::AGREEMENT_ENTRY_TYPE;
@@ -34,8 +44,78 @@ fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
 pub fn handle_create_agreement<S>(entry_def_id: S, agreement: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
+    let clauses = agreement.commitments.to_owned().unwrap_or_default();
+    let planning_dna = match (&agreement.planning_dna, clauses.is_empty()) {
+        (Some(dna), _) => Some(dna.to_owned()),
+        (None, true) => None,
+        (None, false) => return Err(DataIntegrityError::FieldRequired("planningDna".to_string())),
+    };
+
     let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, agreement)?;
-    construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
+
+    // an error discards the agreement, so only records written in the planning DNA need removing
+    let commitments = match planning_dna {
+        Some(planning_dna) => create_clause_commitments(&planning_dna, &base_address, clauses)?,
+        None => vec![],
+    };
+
+    let mut response = construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)?;
+    response.commitments = commitments;
+    Ok(response)
+}
+
+/// Create each of `clauses` as a `Commitment` in the DNA `planning_dna`, as a clause of `agreement`.
+///
+/// If any commitment fails, those already created are deleted, which also removes their
+/// `Agreement.commitments` index entries. The original error is returned, along with any
+/// commitments which could not be removed.
+///
+fn create_clause_commitments(
+    planning_dna: &DnaHash,
+    agreement: &AgreementAddress,
+    clauses: Vec<CommitmentCreateRequest>,
+) -> RecordAPIResult<Vec<CommitmentResponse>> {
+    let mut created: Vec<CommitmentResponse> = vec![];
+    for mut commitment in clauses {
+        commitment.clause_of = MaybeUndefined::Some(agreement.to_owned());
+
+        let resp: OtherCellResult<CommitmentResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+            planning_dna,
+            &COMMITMENT_CREATE_API_METHOD,
+            CommitmentCreateParams { commitment },
+            LinkTypes::AvailableCapability,
+        );
+        match resp {
+            Ok(CommitmentResponseData { commitment }) => created.push(commitment),
+            Err(e) => {
+                let orphaned: Vec<String> = created.into_iter()
+                    .filter_map(|commitment| {
+                        let deleted: OtherCellResult<DeletionReport> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+                            planning_dna,
+                            &COMMITMENT_DELETE_API_METHOD,
+                            DeleteParams { revision_id: commitment.revision_id, cascade: false },
+                            LinkTypes::AvailableCapability,
+                        );
+                        match deleted {
+                            Ok(_) => None,
+                            Err(_) => Some(AsRef::<EntryHash>::as_ref(&commitment.id).to_string()),
+                        }
+                    })
+                    .collect();
+                return Err(match orphaned.is_empty() {
+                    true => e.into(),
+                    false => DataIntegrityError::RollbackIncomplete(Box::new(e.into()), orphaned),
+                });
+            },
+        }
+    }
+    Ok(created)
+}
+
+/// Payload for `COMMITMENT_CREATE_API_METHOD`
+#[derive(Debug, Serialize)]
+struct CommitmentCreateParams {
+    commitment: CommitmentCreateRequest,
 }
 
 pub fn handle_get_agreement(address: AgreementAddress) -> RecordAPIResult<ResponseData>
@@ -44,6 +124,15 @@ pub fn handle_get_agreement(address: AgreementAddress) -> RecordAPIResult<Respon
     construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
 }
 
+/// Resync the indexes of an `Agreement`. Agreements hold no field indexes of their own- their relationships
+/// are indexed by the records referencing them- so this only confirms the record is readable.
+///
+pub fn handle_resync_agreement_indexes(address: AgreementAddress) -> RecordAPIResult<bool>
+{
+    read_record_entry::<EntryData, EntryStorage, AgreementAddress>(address.as_ref())?;
+    Ok(true)
+}
+
 pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
 {
     let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
@@ -54,7 +143,24 @@ pub fn handle_update_agreement(agreement: UpdateRequest) -> RecordAPIResult<Resp
 {
     let revision_hash = agreement.get_revision_id().clone();
     let (meta, identity_address, entry, _prev_entry): (_,_, EntryData, EntryData) = update_record(&revision_hash, agreement)?;
-    construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?)
+    let mut response = construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?)?;
+    response.agreement.meta.changed = revision_changed(&meta, &revision_hash);
+    Ok(response)
+}
+
+generate_patch_builder!(UpdateRequest {
+    name,
+    created,
+    note
+});
+
+/// Apply a set of `PatchOp`s to the latest revision of a `Agreement`.
+/// The patch is converted to an `UpdateRequest` and applied via `handle_update_agreement`.
+///
+pub fn handle_patch_agreement(address: AgreementAddress, ops: Vec<PatchOp>) -> RecordAPIResult<ResponseData>
+{
+    let revision_id = handle_get_agreement(address)?.agreement.revision_id;
+    handle_update_agreement(build_patch_update(revision_id, ops)?)
 }
 
 pub fn handle_delete_agreement(address: ActionHash) -> RecordAPIResult<bool> {
@@ -74,6 +180,7 @@ fn construct_response<'a>(
     ),
 ) -> RecordAPIResult<ResponseData> {
     Ok(ResponseData {
+        commitments: vec![],
         agreement: Response {
             id: address.to_owned(),
             revision_id: meta.as_hash().to_owned(),
@@ -10,6 +10,8 @@ use holochain_serialized_bytes::prelude::*;
 
 use serde_maybe_undefined::MaybeUndefined;
 pub use vf_attributes_hdk::{
+    ErrorCode, ErrorEnvelope,
+    PatchOp, PatchParams,
     AgreementAddress,
     CommitmentAddress,
     EconomicEventAddress,
@@ -18,6 +20,11 @@ pub use vf_attributes_hdk::{
     ByAction, ActionHash,
     ByRevision, RecordMeta, RevisionMeta,
     AgentAddress,
+    DnaHash,
+};
+pub use hc_zome_rea_commitment_rpc::{
+    CreateRequest as CommitmentCreateRequest,
+    Response as CommitmentResponse,
 };
 
 //---------------- EXTERNAL RECORD STRUCTURE ----------------
@@ -28,6 +35,7 @@ pub use vf_attributes_hdk::{
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     pub id: AgreementAddress,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     pub meta: RecordMeta,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -41,6 +49,7 @@ pub struct Response {
     pub commitments: Vec<CommitmentAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "economic_events"))]
     pub economic_events: Vec<EconomicEventAddress>,
     // #[serde(default)]
     // #[serde(skip_serializing_if = "Vec::is_empty")]
@@ -55,6 +64,10 @@ pub struct Response {
 #[serde(rename_all = "camelCase")]
 pub struct ResponseData {
     pub agreement: Response,
+    /// Commitments created alongside the agreement, if any were requested.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub commitments: Vec<CommitmentResponse>,
 }
 
 //---------------- CREATE REQUEST ----------------
@@ -73,6 +86,16 @@ pub struct CreateRequest {
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
     pub note: MaybeUndefined<String>,
+    /// Commitments to create as clauses of the new agreement. Either all of them
+    /// are created or the agreement is removed again.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commitments: Option<Vec<CommitmentCreateRequest>>,
+    /// DNA hosting the commitment zome. Required when `commitments` are given.
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "planning_dna"))]
+    pub planning_dna: Option<DnaHash>,
 }
 
 impl<'a> CreateRequest {
@@ -86,6 +109,7 @@ impl<'a> CreateRequest {
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateRequest {
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
@@ -115,3 +139,10 @@ pub struct QueryParams {
     pub commitments: Option<CommitmentAddress>,
     pub involved_agents: Option<AgentAddress>,
 }
+
+#[cfg(all(test, feature = "snake_case_aliases"))]
+mod tests {
+    use super::*;
+
+    vf_attributes_hdk::snake_case_alias_tests!(Response, CreateRequest, UpdateRequest);
+}
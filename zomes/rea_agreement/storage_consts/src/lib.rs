@@ -6,3 +6,5 @@
  * @package hREA
  */
 pub const AGREEMENT_ENTRY_TYPE: &str = "agreement";
+
+pub const AGREEMENT_READ_API_METHOD: &str = "read_agreement";
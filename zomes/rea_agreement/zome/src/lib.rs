@@ -33,6 +33,11 @@ fn get_agreement(ReadParams { address }: ReadParams) -> ExternResult<ResponseDat
     Ok(handle_get_agreement(address)?)
 }
 
+#[hdk_extern]
+fn resync_record_indexes(ReadParams { address }: ReadParams) -> ExternResult<bool> {
+    Ok(handle_resync_agreement_indexes(address)?)
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
@@ -49,6 +54,11 @@ fn update_agreement(UpdateParams { agreement }: UpdateParams) -> ExternResult<Re
     Ok(handle_update_agreement(agreement)?)
 }
 
+#[hdk_extern]
+fn patch_record(PatchParams { address, ops }: PatchParams<AgreementAddress>) -> ExternResult<ResponseData> {
+    Ok(handle_patch_agreement(address, ops)?)
+}
+
 #[hdk_extern]
 fn delete_agreement(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
     Ok(handle_delete_agreement(revision_id)?)
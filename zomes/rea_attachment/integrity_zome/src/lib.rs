@@ -0,0 +1,127 @@
+/**
+ * hREA attachment integrity zome for API definition
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ */
+use hdi::prelude::*;
+pub use hc_zome_rea_attachment_storage::{AttachmentChunk, AttachmentManifest, EntryTypes, EntryTypesUnit, LinkTypes, DnaConfigSlice};
+use hc_zome_rea_attachment_storage_consts::ATTACHMENTS_ANCHOR;
+
+#[hdk_extern]
+pub fn entry_defs(_: ()) -> ExternResult<EntryDefsCallbackResult> {
+    let defs: Vec<EntryDef> = EntryTypes::ENTRY_DEFS
+        .iter()
+        .map(|a| EntryDef::from(a.clone()))
+        .collect();
+    Ok(EntryDefsCallbackResult::from(defs))
+}
+
+#[no_mangle]
+pub fn __num_entry_types() -> u8 {
+    EntryTypesUnit::len()
+}
+
+#[no_mangle]
+pub fn __num_link_types() -> u8 {
+    LinkTypes::len()
+}
+
+#[hdk_extern]
+fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+    match op {
+        Op::StoreRecord { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::StoreEntry(StoreEntry { entry, .. }) => validate_entry(entry),
+        Op::RegisterCreateLink(RegisterCreateLink { create_link }) => {
+            let link = &create_link.hashed.content;
+            match LinkTypes::from_type(link.zome_index, link.link_type)? {
+                Some(LinkTypes::StoredAttachment) => validate_stored_attachment_link(&link.base_address, &link.target_address),
+                Some(LinkTypes::AttachmentReferrer) => validate_referrer_link(&link.base_address, &link.target_address),
+                None => Ok(ValidateCallbackResult::Valid),
+            }
+        },
+        Op::RegisterDeleteLink { .. } => Ok(ValidateCallbackResult::Valid),
+        // attachment content is immutable
+        Op::RegisterUpdate { .. } => Ok(ValidateCallbackResult::Invalid("Attachments cannot be updated".to_string())),
+        Op::RegisterDelete { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterAgentActivity { .. } => Ok(ValidateCallbackResult::Valid),
+    }
+}
+
+fn validate_entry(entry: Entry) -> ExternResult<ValidateCallbackResult> {
+    if let Ok(manifest) = AttachmentManifest::try_from(&entry) {
+        return validate_manifest(manifest);
+    }
+    match AttachmentChunk::try_from(&entry) {
+        Ok(chunk) => Ok(match chunk.validate() {
+            Ok(()) => ValidateCallbackResult::Valid,
+            Err(e) => ValidateCallbackResult::Invalid(e),
+        }),
+        _ => Ok(ValidateCallbackResult::Valid),
+    }
+}
+
+/// Manifests must reference existing chunks, whose sizes sum to the size of the attachment,
+/// and may not exceed the DNA's `attachment.max_total_size`
+///
+fn validate_manifest(manifest: AttachmentManifest) -> ExternResult<ValidateCallbackResult> {
+    if let Err(e) = manifest.validate() {
+        return Ok(ValidateCallbackResult::Invalid(e));
+    }
+    let conf: DnaConfigSlice = dna_info()?.modifiers.properties.try_into()
+        .map_err(|e: SerializedBytesError| wasm_error!(WasmErrorInner::Guest(e.to_string())))?;
+    let max_total_size = conf.attachment.max_total_size();
+    if manifest.size > max_total_size {
+        return Ok(ValidateCallbackResult::Invalid(format!("Attachment of {} bytes exceeds the maximum attachment size of {} bytes", manifest.size, max_total_size)));
+    }
+
+    let mut size: u64 = 0;
+    for chunk_hash in manifest.chunks.iter() {
+        let chunk = must_get_entry(chunk_hash.to_owned())?;
+        match AttachmentChunk::try_from(chunk.as_content()) {
+            Ok(chunk) => size += chunk.data.len() as u64,
+            Err(_) => return Ok(ValidateCallbackResult::Invalid(format!("Attachment manifest references {}, which is not an attachment chunk", chunk_hash))),
+        }
+    }
+    if size != manifest.size {
+        return Ok(ValidateCallbackResult::Invalid(format!("Attachment manifest records a size of {} bytes but its chunks hold {} bytes", manifest.size, size)));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Stored attachments may only be linked from the attachments anchor, and must point to a manifest
+///
+fn validate_stored_attachment_link(base: &AnyLinkableHash, target: &AnyLinkableHash) -> ExternResult<ValidateCallbackResult> {
+    let anchor = Path::from(ATTACHMENTS_ANCHOR).path_entry_hash()?;
+    if *base != AnyLinkableHash::from(anchor) {
+        return Ok(ValidateCallbackResult::Invalid("Stored attachments may only be linked from the attachments anchor".to_string()));
+    }
+    if !is_manifest(target)? {
+        return Ok(ValidateCallbackResult::Invalid("Stored attachment links must point to an attachment manifest".to_string()));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Referrer links must be based from a manifest and point to the identity address of a record
+///
+fn validate_referrer_link(base: &AnyLinkableHash, target: &AnyLinkableHash) -> ExternResult<ValidateCallbackResult> {
+    if !is_manifest(base)? {
+        return Ok(ValidateCallbackResult::Invalid("Attachment referrers may only be linked from an attachment manifest".to_string()));
+    }
+    if target.to_owned().into_entry_hash().is_none() {
+        return Ok(ValidateCallbackResult::Invalid("Attachment referrer links must point to the identity address of a record".to_string()));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+fn is_manifest(address: &AnyLinkableHash) -> ExternResult<bool> {
+    let hash = match address.to_owned().into_entry_hash() {
+        Some(hash) => hash,
+        None => return Ok(false),
+    };
+    let entry = must_get_entry(hash)?;
+    Ok(AttachmentManifest::try_from(entry.as_content()).is_ok())
+}
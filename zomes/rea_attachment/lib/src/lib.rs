@@ -0,0 +1,168 @@
+/**
+ * hREA attachment zome library API
+ *
+ * Contains helper methods that can be used to store and read binary attachments
+ * in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * Attachments are addressed by the hash of their `AttachmentManifest`, which is
+ * determined by their content alone. Uploading content which is already stored
+ * writes nothing and returns the existing address.
+ *
+ * Attachments are never deleted along with the records referencing them, as they
+ * may be shared. Record zomes report the attachments each record references via
+ * `handle_record_attachment_references`, so that unreferenced attachments can be listed.
+ *
+ * @package hREA
+ * @since   2026-10-16
+ */
+use std::collections::HashSet;
+use hdk::prelude::*;
+use hdk_records::{
+    RecordAPIResult, DataIntegrityError,
+    entries::{
+        create_entry,
+        get_entry_by_address,
+    },
+};
+use hdk_semantic_indexes_zome_lib::throw_if_not_network_admin;
+
+pub use hc_zome_rea_attachment_storage_consts::*;
+use hc_zome_rea_attachment_storage::*;
+use hc_zome_rea_attachment_rpc::*;
+
+fn read_max_total_size() -> RecordAPIResult<u64> {
+    let conf: DnaConfigSlice = dna_info()?.modifiers.properties.try_into()?;
+    Ok(conf.attachment.max_total_size())
+}
+
+/// Properties accessor for zome config.
+fn read_network_settings_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.attachment.network_settings_zome
+}
+
+//---------------- CREATE ----------------
+
+pub fn handle_upload_attachment(chunks: Vec<ByteBuf>) -> RecordAPIResult<AttachmentSummary>
+{
+    let size = check_attachment_size(chunks.as_slice(), read_max_total_size()?)?;
+    let stored_chunks = rechunk(chunks.as_slice());
+    let manifest = AttachmentManifest {
+        chunks: stored_chunks.iter()
+            .map(|chunk| hash_entry(chunk.to_owned()))
+            .collect::<ExternResult<Vec<EntryHash>>>()?,
+        size,
+    };
+    let hash = hash_entry(manifest.to_owned())?;
+
+    // identical content has already been stored
+    if get(hash.to_owned(), GetOptions::default())?.is_some() {
+        return Ok(AttachmentSummary { hash, size });
+    }
+
+    for chunk in stored_chunks {
+        create_entry::<EntryTypes, _, _>(chunk)?;
+    }
+    create_entry::<EntryTypes, _, _>(manifest)?;
+    create_link(attachments_anchor_hash()?, hash.to_owned(), LinkTypes::StoredAttachment, ())?;
+
+    Ok(AttachmentSummary { hash, size })
+}
+
+//---------------- READ ----------------
+
+pub fn handle_get_attachment(hash: EntryHash) -> RecordAPIResult<AttachmentResponse>
+{
+    let manifest = read_manifest(&hash)?;
+
+    let mut data = Vec::with_capacity(manifest.size as usize);
+    for chunk_hash in manifest.chunks.iter() {
+        let (_meta, chunk): (_, AttachmentChunk) = get_entry_by_address(chunk_hash)?;
+        data.extend(chunk.data);
+    }
+
+    Ok(AttachmentResponse {
+        hash,
+        size: manifest.size,
+        data: ByteBuf::from(data),
+    })
+}
+
+//---------------- REFERENCES ----------------
+
+/// Record changes to the attachments referenced by a record. Every `added` attachment must exist.
+///
+/// Only record zomes of the agent's own cell may report references.
+///
+pub fn handle_record_attachment_references(request: AttachmentReferencesRequest) -> RecordAPIResult<bool>
+{
+    if call_info()?.provenance != agent_info()?.agent_latest_pubkey {
+        return Err(DataIntegrityError::NotLocalCaller);
+    }
+    let AttachmentReferencesRequest { referrer, added, removed } = request;
+    let referrer_target = AnyLinkableHash::from(referrer);
+
+    // check all additions before writing anything
+    for hash in added.iter() {
+        read_manifest(hash)?;
+    }
+
+    for hash in added.iter() {
+        if read_referrer_links(hash)?.iter().all(|l| l.target != referrer_target) {
+            create_link(hash.to_owned(), referrer_target.to_owned(), LinkTypes::AttachmentReferrer, ())?;
+        }
+    }
+    for hash in removed.iter().filter(|h| !added.contains(h)) {
+        for link in read_referrer_links(hash)?.into_iter().filter(|l| l.target == referrer_target) {
+            delete_link(link.create_link_hash)?;
+        }
+    }
+
+    Ok(true)
+}
+
+/// List every stored attachment which is not referenced by any record, for garbage collection.
+/// Only network admins may list unreferenced attachments.
+///
+pub fn handle_list_unreferenced_attachments() -> RecordAPIResult<UnreferencedAttachments>
+{
+    throw_if_not_network_admin(read_network_settings_zome)?;
+
+    let mut seen = HashSet::new();
+    let mut attachments = vec![];
+
+    for link in get_links(attachments_anchor_hash()?, LinkTypes::StoredAttachment, None)? {
+        let hash = match link.target.into_entry_hash() {
+            Some(hash) => hash,
+            None => continue,
+        };
+        // concurrent uploads of the same content may link it more than once
+        if !seen.insert(hash.to_owned()) {
+            continue;
+        }
+        if read_referrer_links(&hash)?.is_empty() {
+            let manifest = read_manifest(&hash)?;
+            attachments.push(AttachmentSummary { hash, size: manifest.size });
+        }
+    }
+
+    Ok(UnreferencedAttachments { attachments })
+}
+
+//---------------- INTERNALS ----------------
+
+fn attachments_anchor_hash() -> RecordAPIResult<EntryHash> {
+    Ok(Path::from(ATTACHMENTS_ANCHOR).path_entry_hash()?)
+}
+
+fn read_referrer_links(hash: &EntryHash) -> RecordAPIResult<Vec<Link>> {
+    Ok(get_links(hash.to_owned(), LinkTypes::AttachmentReferrer, None)?)
+}
+
+fn read_manifest(hash: &EntryHash) -> RecordAPIResult<AttachmentManifest> {
+    match get_entry_by_address(hash) {
+        Ok((_meta, manifest)) => Ok(manifest),
+        Err(DataIntegrityError::EntryNotFound) | Err(DataIntegrityError::EntryWrongType) =>
+            Err(DataIntegrityError::AttachmentNotFound(hash.to_owned())),
+        Err(e) => Err(e),
+    }
+}
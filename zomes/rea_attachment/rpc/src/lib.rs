@@ -0,0 +1,74 @@
+/**
+ * hREA attachment zome I/O data structures
+ *
+ * Required by packages wishing to interact with the zome via its standard RPC interface,
+ * and by the zome API handlers accepting these parameters.
+ *
+ * @package hREA
+ * @since   2026-10-16
+ */
+use holochain_serialized_bytes::prelude::*;
+
+pub use serde_bytes::ByteBuf;
+pub use vf_attributes_hdk::{
+    ErrorCode, ErrorEnvelope,
+    EntryHash,
+    AttachmentRef,
+};
+
+//---------------- EXTERNAL RECORD STRUCTURE ----------------
+
+/// Address and total size of the content of a stored attachment
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentSummary {
+    pub hash: EntryHash,
+    pub size: u64,
+}
+
+/// Content of a stored attachment, reassembled from its chunks
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentResponse {
+    pub hash: EntryHash,
+    pub size: u64,
+    pub data: ByteBuf,
+}
+
+/// Attachments which are no longer referenced by any record
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UnreferencedAttachments {
+    pub attachments: Vec<AttachmentSummary>,
+}
+
+//---------------- CREATE REQUEST ----------------
+
+/// Content of an attachment to store, as chunks to be concatenated in order.
+///
+/// Large files should be split by the client so that no chunk exceeds the zome's maximum chunk size.
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UploadRequest {
+    pub chunks: Vec<ByteBuf>,
+}
+
+//---------------- REFERENCES ----------------
+
+/// Changes to the attachments referenced by a record, made by the zome storing the record.
+///
+/// `referrer` is the identity address of the record. `added` attachments must exist.
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AttachmentReferencesRequest {
+    pub referrer: EntryHash,
+    #[serde(default)]
+    pub added: Vec<EntryHash>,
+    #[serde(default)]
+    pub removed: Vec<EntryHash>,
+}
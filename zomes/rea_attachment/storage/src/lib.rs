@@ -0,0 +1,178 @@
+/**
+ * hREA attachment zome internal data structures
+ *
+ * Required by the zome itself, and for any DNA-local zomes interacting with its
+ * storage API directly.
+ *
+ * Attachment content is stored as `AttachmentChunk` entries, assembled by an
+ * `AttachmentManifest` listing the chunks in order. Entries are content-addressed,
+ * so the hash of the manifest identifies the attachment's content and identical
+ * uploads share the same entries.
+ *
+ * @package hREA
+ * @since   2026-10-16
+ */
+use hdk::prelude::*;
+
+use hdk_records::{RecordAPIResult, DataIntegrityError};
+
+//--------------- ZOME CONFIGURATION ATTRIBUTES ----------------
+
+/// Upper bound on the size of each stored chunk, well within Holochain's maximum entry size
+pub const MAX_ATTACHMENT_CHUNK_BYTES: usize = 1024 * 1024;
+
+/// Maximum total size of an attachment, where not set via `AttachmentZomeConfig.max_total_size`
+pub const DEFAULT_MAX_ATTACHMENT_BYTES: u64 = 10 * 1024 * 1024;
+
+// :TODO: remove this, replace with reference to appropriate namespacing of zome config
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
+pub struct DnaConfigSlice {
+    pub attachment: AttachmentZomeConfig,
+}
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
+pub struct AttachmentZomeConfig {
+    // maximum total size of a single attachment in bytes
+    #[serde(default)]
+    pub max_total_size: Option<u64>,
+    // zome ID of the network settings zome, whose admins may list unreferenced attachments
+    #[serde(default)]
+    pub network_settings_zome: Option<String>,
+}
+
+impl AttachmentZomeConfig {
+    pub fn max_total_size(&self) -> u64 {
+        self.max_total_size.unwrap_or(DEFAULT_MAX_ATTACHMENT_BYTES)
+    }
+}
+
+//---------------- RECORD INTERNALS & VALIDATION ----------------
+
+/// A contiguous section of an attachment's content
+///
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct AttachmentChunk {
+    #[serde(with = "serde_bytes")]
+    pub data: Vec<u8>,
+}
+
+impl AttachmentChunk {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.data.is_empty() {
+            return Err("Attachment chunks must not be empty".to_string());
+        }
+        if self.data.len() > MAX_ATTACHMENT_CHUNK_BYTES {
+            return Err(format!("Attachment chunk of {} bytes exceeds the maximum chunk size of {} bytes", self.data.len(), MAX_ATTACHMENT_CHUNK_BYTES));
+        }
+        Ok(())
+    }
+}
+
+/// The chunks of an attachment's content, in order, and their total size
+///
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct AttachmentManifest {
+    pub chunks: Vec<EntryHash>,
+    pub size: u64,
+}
+
+impl AttachmentManifest {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.chunks.is_empty() || self.size == 0 {
+            return Err("Attachments must contain at least one chunk of data".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Determine the total size of an attachment's content, rejecting empty content and content
+/// larger than `max_total_size`.
+///
+pub fn check_attachment_size<C: AsRef<[u8]>>(chunks: &[C], max_total_size: u64) -> RecordAPIResult<u64> {
+    let size: u64 = chunks.iter().map(|c| c.as_ref().len() as u64).sum();
+    if size == 0 {
+        return Err(DataIntegrityError::EmptyAttachment);
+    }
+    if size > max_total_size {
+        return Err(DataIntegrityError::AttachmentTooLarge(size, max_total_size));
+    }
+    Ok(size)
+}
+
+/// Split uploaded content into chunks of `MAX_ATTACHMENT_CHUNK_BYTES`. Content is rejoined
+/// before splitting, so that the stored chunks do not depend on how the client divided it.
+///
+pub fn rechunk<C: AsRef<[u8]>>(chunks: &[C]) -> Vec<AttachmentChunk> {
+    let content: Vec<u8> = chunks.iter().flat_map(|c| c.as_ref().iter().cloned()).collect();
+    content.chunks(MAX_ATTACHMENT_CHUNK_BYTES)
+        .map(|c| AttachmentChunk { data: c.to_vec() })
+        .collect()
+}
+
+//---------------- Holochain App Entry And Link Types Setup ----------------
+
+#[hdk_entry_defs(skip_hdk_extern = true)]
+#[unit_enum(EntryTypesUnit)]
+pub enum EntryTypes {
+    AttachmentChunk(AttachmentChunk),
+    AttachmentManifest(AttachmentManifest),
+}
+
+impl From<AttachmentChunk> for EntryTypes
+{
+    fn from(e: AttachmentChunk) -> EntryTypes
+    {
+        EntryTypes::AttachmentChunk(e)
+    }
+}
+
+impl From<AttachmentManifest> for EntryTypes
+{
+    fn from(e: AttachmentManifest) -> EntryTypes
+    {
+        EntryTypes::AttachmentManifest(e)
+    }
+}
+
+#[hdk_link_types(skip_no_mangle = true)]
+pub enum LinkTypes {
+    // links from the attachments anchor to the manifest of every stored attachment
+    StoredAttachment,
+    // links from an attachment's manifest to the identities of records referencing it
+    AttachmentReferrer,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attachment_size_limits() {
+        assert_eq!(check_attachment_size(&[vec![1u8; 10], vec![2u8; 5]], 15).unwrap(), 15);
+        match check_attachment_size(&[vec![1u8; 10], vec![2u8; 6]], 15) {
+            Err(DataIntegrityError::AttachmentTooLarge(16, 15)) => (),
+            r => panic!("oversized attachment accepted: {:?}", r),
+        }
+        match check_attachment_size::<Vec<u8>>(&[vec![]], 15) {
+            Err(DataIntegrityError::EmptyAttachment) => (),
+            r => panic!("empty attachment accepted: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn test_rechunk_caps_chunk_size() {
+        let chunks = rechunk(&[vec![1u8; MAX_ATTACHMENT_CHUNK_BYTES * 2 + 3], vec![], vec![2u8; 4]]);
+        assert_eq!(chunks.iter().map(|c| c.data.len()).collect::<Vec<usize>>(), vec![MAX_ATTACHMENT_CHUNK_BYTES, MAX_ATTACHMENT_CHUNK_BYTES, 7]);
+        assert!(chunks.iter().all(|c| c.validate().is_ok()));
+    }
+
+    #[test]
+    fn test_identical_content_shares_chunks() {
+        let content: Vec<u8> = (0..MAX_ATTACHMENT_CHUNK_BYTES + 100).map(|i| (i % 251) as u8).collect();
+        let whole = rechunk(&[content.to_owned()]);
+        let split = rechunk(&[content[..10].to_vec(), content[10..].to_vec()]);
+        assert_eq!(whole, split, "stored chunks are independent of upload chunking");
+    }
+}
@@ -0,0 +1,15 @@
+/**
+ * Storage constants for zome entry & link type identifiers
+ *
+ * Used by modules interfacing with the underlying Holochain storage system directly.
+ *
+ * @package hREA
+ */
+pub const ATTACHMENT_ENTRY_TYPE: &str = "attachment";
+
+// anchor `Path` from which every stored attachment is linked, for garbage collection
+pub const ATTACHMENTS_ANCHOR: &str = "attachments";
+
+// zome API method called by record zomes to verify the attachments referenced by a record
+// and record the reference
+pub const ATTACHMENT_REFERENCE_API_METHOD: &str = "record_attachment_references";
@@ -0,0 +1,41 @@
+/**
+ * hREA attachment zome API definition
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ * @since   2026-10-16
+ */
+use hdk::prelude::*;
+
+use hc_zome_rea_attachment_rpc::*;
+use hc_zome_rea_attachment_lib::*;
+
+#[hdk_extern]
+fn upload_attachment(UploadRequest { chunks }: UploadRequest) -> ExternResult<AttachmentSummary> {
+    Ok(handle_upload_attachment(chunks)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ByHash {
+    pub hash: EntryHash,
+}
+
+#[hdk_extern]
+fn get_attachment(ByHash { hash }: ByHash) -> ExternResult<AttachmentResponse> {
+    Ok(handle_get_attachment(hash)?)
+}
+
+// :NOTE: called by record zomes in the same DNA, should not be exposed to remote DNAs
+#[hdk_extern]
+fn record_attachment_references(request: AttachmentReferencesRequest) -> ExternResult<bool> {
+    Ok(handle_record_attachment_references(request)?)
+}
+
+// :NOTE: administrative operation, should not be exposed to remote DNAs
+#[hdk_extern]
+fn list_unreferenced_attachments(_: ()) -> ExternResult<UnreferencedAttachments> {
+    Ok(handle_list_unreferenced_attachments()?)
+}
@@ -6,60 +6,77 @@
  *
  * @package hREA
  */
+use std::convert::TryInto;
 use paste::paste;
+use hdk::prelude::{DnaHash, EntryHash};
 use hdk_records::{
-    RecordAPIResult, MaybeUndefined, SignedActionHashed,
+    generate_patch_builder,
+    RecordAPIResult, DataIntegrityError, MaybeUndefined, SignedActionHashed,
+    DnaAddressable,
     records::{
         create_record,
         read_record_entry,
+        read_record_entry_with_creation_time,
         read_record_entry_by_action,
         update_record,
         delete_record,
     },
     metadata::read_revision_metadata_abbreviated,
+    history::read_revision_window,
+    record_interface::{Updateable, Identifiable},
+    validation::run_validation_hooks,
+    rpc::{call_local_zome_method, call_zome_method},
+    dependencies::{
+        summarize_dependents,
+        throw_if_dependents,
+        cascade_delete_dependents,
+        throw_if_cascade_failed,
+    },
 };
 use hdk_semantic_indexes_client_lib::*;
 
 use hc_zome_rea_commitment_storage::*;
 use hc_zome_rea_commitment_rpc::*;
+use hc_zome_rea_fulfillment_rpc::{
+    ByAddress,
+    Response as FulfillmentRecord,
+    ResponseData as FulfillmentResponse,
+};
+use hc_zome_rea_fulfillment_storage_consts::{ READ_COMMITMENT_FULFILLMENTS_API_METHOD, REPLICATE_DELETE_API_METHOD };
+use hc_zome_rea_satisfaction_rpc::{
+    ResponseData as SatisfactionResponse,
+};
+use hc_zome_rea_agreement_storage_consts::AGREEMENT_READ_API_METHOD;
+use hc_zome_rea_agreement_rpc::ResponseData as AgreementResponseData;
+use hc_zome_rea_network_settings_lib::{
+    NetworkSettingsReader, QuantityValue, UNIT_READ_API_METHOD, UnitReadRequest, UnitReadResponse,
+    RESOURCE_SPECIFICATION_READ_API_METHOD, ResourceSpecificationReadRequest, ResourceSpecificationUnitsResponse,
+};
 
 /// properties accessor for zome config
 fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
     Some(conf.commitment.index_zome)
 }
 
+derived_index!(commitment {
+    field: resource_classified_as,
+    index: classified_as,
+    path: classification_index_paths
+}<CommitmentClassificationId>);
+
 pub fn handle_create_commitment<S>(entry_def_id: S, commitment: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
+    throw_if_unitless_quantities(&commitment.resource_quantity, &commitment.effort_quantity)?;
+
     let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, commitment.to_owned())?;
+    throw_if_spec_units_mismatched(&entry_resp)?;
 
     // handle link fields
     // :TODO: improve error handling
-    let e1 = create_index!(commitment.provider(&commitment.provider), agent.commitments_as_provider(&base_address));
-    hdk::prelude::debug!("handle_create_commitment::provider index {:?}", e1);
-    let e2 = create_index!(commitment.receiver(&commitment.receiver), agent.commitments_as_receiver(&base_address));
-    hdk::prelude::debug!("handle_create_commitment::receiver index {:?}", e2);
-
-    if let CreateRequest { input_of: MaybeUndefined::Some(input_of), .. } = &commitment {
-        let e = create_index!(commitment.input_of(input_of), process.committed_inputs(&base_address));
-        hdk::prelude::debug!("handle_create_commitment::input_of index {:?}", e);
-    };
-    if let CreateRequest { output_of: MaybeUndefined::Some(output_of), .. } = &commitment {
-        let e = create_index!(commitment.output_of(output_of), process.committed_outputs(&base_address));
-        hdk::prelude::debug!("handle_create_commitment::output_of index {:?}", e);
-    };
-    if let CreateRequest { clause_of: MaybeUndefined::Some(clause_of), .. } = &commitment {
-        let e = create_index!(commitment.clause_of(clause_of), agreement.commitments(&base_address));
-        hdk::prelude::debug!("handle_create_commitment::clause_of index {:?}", e);
-    };
-    if let CreateRequest { independent_demand_of: MaybeUndefined::Some(independent_demand_of), .. } = &commitment {
-        let e = create_index!(commitment.independent_demand_of(independent_demand_of), plan.independent_demands(&base_address));
-        hdk::prelude::debug!("handle_create_commitment::independent_demand_of index {:?}", e);
-    };
-    if let CreateRequest { planned_within: MaybeUndefined::Some(planned_within), .. } = &commitment {
-        let e = create_index!(commitment.planned_within(planned_within), plan.non_process_commitments(&base_address));
-        hdk::prelude::debug!("handle_create_commitment::planned_within index {:?}", e);
-    };
+    log_index_results("handle_create_commitment", create_commitment_indexes(&base_address, &entry_resp)?);
+    let e = create_commitment_classified_as_index(&base_address, &entry_resp);
+    hdk::prelude::debug!("handle_create_commitment::classified_as index {:?}", e);
     // // TODO: because commitment.in_scope_of is a vec of ids rather than one id, make sure this is still handled properly
     // if let CreateRequest { in_scope_of: MaybeUndefined::Some(in_scope_of), .. } = &commitment {
     //     let e = create_index!(commitment.in_scope_of(in_scope_of), agent.commitments(&base_address));
@@ -70,10 +87,118 @@ pub fn handle_create_commitment<S>(entry_def_id: S, commitment: CreateRequest) -
     construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
 }
 
+/// Index writes made for a `Commitment` when it is created, and re-run to resync its indexes.
+///
+fn create_commitment_indexes(base_address: &CommitmentAddress, entry: &EntryData) -> RecordAPIResult<RecordIndexResults>
+{
+    let mut results = vec![
+        ("provider", create_index!(commitment.provider(&entry.provider), agent.commitments_as_provider(base_address))),
+        ("receiver", create_index!(commitment.receiver(&entry.receiver), agent.commitments_as_receiver(base_address))),
+    ];
+    if let Some(input_of) = &entry.input_of {
+        results.push(("input_of", create_index!(commitment.input_of(input_of), process.committed_inputs(base_address))));
+    }
+    if let Some(output_of) = &entry.output_of {
+        results.push(("output_of", create_index!(commitment.output_of(output_of), process.committed_outputs(base_address))));
+    }
+    if let Some(clause_of) = &entry.clause_of {
+        results.push(("clause_of", create_index!(commitment.clause_of(clause_of), agreement.commitments(base_address))));
+    }
+    if let Some(independent_demand_of) = &entry.independent_demand_of {
+        results.push(("independent_demand_of", create_index!(commitment.independent_demand_of(independent_demand_of), plan.independent_demands(base_address))));
+    }
+    if let Some(planned_within) = &entry.planned_within {
+        results.push(("planned_within", create_index!(commitment.planned_within(planned_within), plan.non_process_commitments(base_address))));
+    }
+    if let Some(at_location) = &entry.at_location {
+        results.push(("at_location", create_index!(commitment(base_address).at_location(at_location))));
+    }
+    if let Some(resource_conforms_to) = &entry.resource_conforms_to {
+        results.push(("resource_conforms_to", create_index!(commitment.resource_conforms_to(resource_conforms_to), resource_specification.conforming_commitments(base_address))));
+    }
+    throw_if_index_update_failed(
+        update_string_index!(commitment(base_address).finished_state(entry.finished_state())<CommitmentStateId>)
+    )?;
+    Ok(results)
+}
+
+/// Rebuild the indexes of a `Commitment` from its latest revision.
+///
+pub fn handle_resync_commitment_indexes(address: CommitmentAddress) -> RecordAPIResult<bool>
+{
+    let (_meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+
+    let mut results = create_commitment_indexes(&base_address, &entry)?;
+    results.push(("classified_as", resync_commitment_classified_as_index(&base_address, &entry)));
+    throw_if_resync_failed(results)
+}
+
+/// Create the two mirrored `Commitment`s of an exchange (eg. goods one way, payment the other)
+/// as clauses of `agreement`, and link them to each other via their `reciprocal_of` indexes.
+///
+/// The `agreement` must exist, and both requests are validated before either record is written.
+///
+pub fn handle_create_exchange_commitments<S>(entry_def_id: S, agreement: AgreementAddress, primary: CreateRequest, reciprocal: CreateRequest) -> RecordAPIResult<ExchangeResponseData>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    if primary.provider != reciprocal.receiver || primary.receiver != reciprocal.provider {
+        return Err(DataIntegrityError::ExchangeNotReciprocal);
+    }
+    throw_if_agreement_missing(&agreement)?;
+
+    let primary = CreateRequest { clause_of: MaybeUndefined::Some(agreement.to_owned()), ..primary };
+    let reciprocal = CreateRequest { clause_of: MaybeUndefined::Some(agreement), ..reciprocal };
+    validate_create_request(&primary)?;
+    validate_create_request(&reciprocal)?;
+
+    let first = handle_create_commitment(&entry_def_id, primary)?.commitment;
+    let second = handle_create_commitment(&entry_def_id, reciprocal)?.commitment;
+    throw_if_index_update_failed(
+        create_index!(commitment.reciprocal_of(&second.id), commitment.reciprocal_of(&first.id))
+    )?;
+
+    Ok(ExchangeResponseData {
+        primary: Response { reciprocal_of: Some(second.id.to_owned()), ..first },
+        reciprocal: Response { reciprocal_of: Some(first.id), ..second },
+    })
+}
+
+/// Create a new `Commitment` as a copy of the latest revision of an existing one, with
+/// `overrides` merged over the copied fields. The copy is not marked as finished
+/// unless the overrides say so.
+///
+pub fn handle_clone_commitment<S>(entry_def_id: S, address: CommitmentAddress, overrides: UpdateRequest) -> RecordAPIResult<CloneResponseData>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    let (meta, source_address, mut entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    if meta.as_hash() != &overrides.revision_id {
+        return Err(DataIntegrityError::UpdateConflict(vec![meta.as_hash().to_owned()]));
+    }
+    entry.finished = false;
+
+    let created = handle_create_commitment(entry_def_id, entry.update_with(overrides)?.into())?;
+    Ok(CloneResponseData {
+        cloned_from: source_address,
+        commitment: created.commitment,
+    })
+}
+
 pub fn handle_get_commitment(address: CommitmentAddress) -> RecordAPIResult<ResponseData>
 {
-    let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
-    construct_response(&base_address, &meta, &entry, get_link_fields(&address)?)
+    let (meta, base_address, entry, created_at) = read_record_entry_with_creation_time::<EntryData, EntryStorage, _>(address.as_ref())?;
+    let mut response = construct_response(&base_address, &meta, &entry, get_link_fields(&address)?)?;
+    response.commitment.created_at = Some(created_at);
+    Ok(response)
+}
+
+/// Read many `Commitment`s in a single call. Each address is returned alongside its
+/// record or the reason it could not be read, so that one failure does not fail the batch.
+///
+pub fn handle_get_commitments(addresses: Vec<CommitmentAddress>) -> RecordAPIResult<Vec<ByAddressResult<CommitmentAddress, ResponseData>>>
+{
+    Ok(addresses.into_iter()
+        .map(|address| ByAddressResult::new(address.to_owned(), handle_get_commitment(address)))
+        .collect())
 }
 
 pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
@@ -82,10 +207,26 @@ pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseD
     construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
 }
 
+/// Read the revisions of a commitment written within the window described by `params`
+///
+pub fn handle_get_commitment_revision_history(params: RevisionHistoryParams<CommitmentAddress>) -> RecordAPIResult<RevisionHistory<ResponseData>>
+{
+    let link_fields = get_link_fields(&params.address)?;
+    read_revision_window::<EntryData, EntryStorage, CommitmentAddress, _, _>(
+        &params.address, params.since, params.until, params.limit,
+        |meta, entry| construct_response(&params.address, meta, entry, link_fields.to_owned()),
+    )
+}
+
 pub fn handle_update_commitment(commitment: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
+    throw_if_unitless_quantities(&commitment.resource_quantity, &commitment.effort_quantity)?;
+
     let address = commitment.get_revision_id().to_owned();
     let (meta, base_address, new_entry, prev_entry): (_, CommitmentAddress, EntryData, EntryData) = update_record(&address, commitment.to_owned())?;
+    throw_if_spec_units_mismatched(&new_entry)?;
+
+    hdk_records::return_if_unchanged!(meta, address, construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?), commitment);
 
     if new_entry.input_of != prev_entry.input_of {
         let new_value = match &new_entry.input_of { Some(val) => vec![val.to_owned()], None => vec![] };
@@ -177,16 +318,157 @@ pub fn handle_update_commitment(commitment: UpdateRequest) -> RecordAPIResult<Re
         );
         hdk::prelude::debug!("handle_update_commitment::receiver index {:?}", e);
     }
+    if new_entry.at_location != prev_entry.at_location {
+        let new_value = match &new_entry.at_location { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.at_location { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(commitment(&base_address).at_location(new_value.as_slice()).not(prev_value.as_slice()));
+        hdk::prelude::debug!("handle_update_commitment::at_location index {:?}", e);
+    }
+    if new_entry.resource_conforms_to != prev_entry.resource_conforms_to {
+        let new_value = match &new_entry.resource_conforms_to { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.resource_conforms_to { Some(val) => vec![val.to_owned()], None => vec![] };
+        update_index!(
+            commitment
+                .resource_conforms_to(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            resource_specification.conforming_commitments(&base_address)
+        )?;
+    }
+    let e = update_commitment_classified_as_index(&base_address, &new_entry, &prev_entry);
+    hdk::prelude::debug!("handle_update_commitment::classified_as index {:?}", e);
+    // move between pending & completed indexes in one operation, failing the update if this cannot be done
+    if new_entry.finished != prev_entry.finished {
+        throw_if_index_update_failed(
+            update_string_index!(commitment(&base_address).finished_state(new_entry.finished_state()).not(prev_entry.finished_state())<CommitmentStateId>)
+        )?;
+    }
 
     construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?)
 }
 
-pub fn handle_delete_commitment(revision_id: ActionHash) -> RecordAPIResult<bool>
+generate_patch_builder!(UpdateRequest {
+    action,
+    note,
+    input_of,
+    output_of,
+    provider,
+    receiver,
+    resource_inventoried_as,
+    resource_classified_as,
+    resource_conforms_to,
+    resource_quantity,
+    effort_quantity,
+    has_beginning,
+    has_end,
+    has_point_in_time,
+    due,
+    at_location,
+    agreed_in,
+    clause_of,
+    independent_demand_of,
+    planned_within,
+    finished,
+    in_scope_of
+});
+
+/// Apply a set of `PatchOp`s to the latest revision of a `Commitment`.
+/// The patch is converted to an `UpdateRequest` and applied via `handle_update_commitment`.
+///
+pub fn handle_patch_commitment(address: CommitmentAddress, ops: Vec<PatchOp>) -> RecordAPIResult<ResponseData>
+{
+    let revision_id = handle_get_commitment(address)?.commitment.revision_id;
+    handle_update_commitment(build_patch_update(revision_id, ops)?)
+}
+
+/// Point a `Commitment` conforming to `from` at the `ResourceSpecification` `to` instead, moving it
+/// between the specifications' indexes. Returns `false` if the commitment does not conform to `from`.
+/// Only network admins may reassign records, as this rewrites records authored by other agents.
+///
+pub fn handle_reassign_resource_specification(params: ReassignSpecificationParams<CommitmentAddress>) -> RecordAPIResult<bool>
+{
+    NetworkSettingsReader::new(read_network_settings_zome).throw_if_not_admin()?;
+
+    let (meta, _base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(params.address.as_ref())?;
+    if entry.resource_conforms_to.as_ref() != Some(&params.from) {
+        return Ok(false);
+    }
+    let (_meta, base_address, _new_entry, _prev_entry): (_, CommitmentAddress, EntryData, EntryData) = update_record(meta.as_hash(), params.to_owned())?;
+
+    update_index!(
+        commitment
+            .resource_conforms_to(&vec![params.to])
+            .not(&vec![params.from]),
+        resource_specification.conforming_commitments(&base_address)
+    )?;
+    Ok(true)
+}
+
+/// Delete a `Commitment`, refusing to do so if any `Fulfillment` or `Satisfaction`
+/// records reference it. If `cascade` is set, dependent records are removed first.
+///
+/// `Fulfillment`s held by observation DNAs but missing from this DNA are also dependents.
+/// These are only removed by a cascading delete where the `cascade_remote_dependents` zome
+/// config is set. If an observation DNA cannot be checked, the commitment is not deleted.
+///
+pub fn handle_delete_commitment(revision_id: ActionHash, cascade: bool) -> RecordAPIResult<DeletionReport>
+{
+    delete_commitment_record(revision_id, cascade, false)
+}
+
+/// Delete a `Commitment` as `handle_delete_commitment` does, skipping observation DNAs which cannot
+/// be reached rather than refusing the delete. Dependents in reachable DNAs are still checked.
+///
+/// Only network admins may do so, as a way to remove commitments referencing uninstalled DNAs.
+///
+pub fn handle_force_delete_commitment(revision_id: ActionHash, cascade: bool) -> RecordAPIResult<DeletionReport>
+{
+    NetworkSettingsReader::new(read_network_settings_zome).throw_if_not_admin()?;
+    delete_commitment_record(revision_id, cascade, true)
+}
+
+fn delete_commitment_record(revision_id: ActionHash, cascade: bool, skip_unreachable: bool) -> RecordAPIResult<DeletionReport>
 {
     // load the record to ensure it is of the correct type
     let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
 
+    // check for dependent records
+    let fulfillments: Vec<FulfillmentAddress> = read_index!(commitment(&base_address).fulfilled_by)?;
+    let satisfactions: Vec<SatisfactionAddress> = read_index!(commitment(&base_address).satisfies)?;
+    if !cascade {
+        throw_if_dependents(vec![
+            summarize_dependents("fulfillments", &fulfillments),
+            summarize_dependents("satisfactions", &satisfactions),
+        ])?;
+    }
+
+    let remote_fulfillments = read_unreplicated_fulfillments(&base_address, &entry, &fulfillments, skip_unreachable)?;
+    let remote_addresses: Vec<FulfillmentAddress> = remote_fulfillments.iter().map(|f| f.id.to_owned()).collect();
+    if !(cascade && read_cascade_remote_dependents()?) {
+        throw_if_dependents(vec![
+            summarize_dependents("observation fulfillments", &remote_addresses),
+        ])?;
+    }
+
+    let removed_dependents = if cascade {
+        let (mut removed, mut failed) = cascade_delete_dependents("fulfillments", fulfillments, delete_fulfillment);
+        let (removed_s, failed_s) = cascade_delete_dependents("satisfactions", satisfactions, delete_satisfaction);
+        let (removed_r, failed_r) = cascade_delete_dependents("observation fulfillments", remote_addresses, |address| {
+            delete_remote_fulfillment(address, &remote_fulfillments)
+        });
+        removed.extend(removed_s);
+        removed.extend(removed_r);
+        failed.extend(failed_s);
+        failed.extend(failed_r);
+        throw_if_cascade_failed(removed, failed)?
+    } else {
+        vec![]
+    };
+
     // handle link fields
+    let e = update_string_index!(commitment(&base_address).finished_state.not(entry.finished_state())<CommitmentStateId>);
+    hdk::prelude::debug!("handle_delete_commitment::finished_state index {:?}", e);
+    let e = delete_commitment_classified_as_index(&base_address, &entry);
+    hdk::prelude::debug!("handle_delete_commitment::classified_as index {:?}", e);
     if let Some(process_address) = entry.input_of {
         let e = update_index!(commitment.input_of.not(&vec![process_address]), process.committed_inputs(&base_address));
         hdk::prelude::debug!("handle_delete_commitment::input_of index {:?}", e);
@@ -211,9 +493,213 @@ pub fn handle_delete_commitment(revision_id: ActionHash) -> RecordAPIResult<bool
     hdk::prelude::debug!("handle_delete_commitment::provider index {:?}", e1);
     let e2 = update_index!(commitment.receiver.not(&vec![entry.receiver]), agent.commitments_as_receiver(&base_address));
     hdk::prelude::debug!("handle_delete_commitment::receiver index {:?}", e2);
+    if let Some(location_address) = entry.at_location {
+        let e = update_index!(commitment(&base_address).at_location.not(&vec![location_address]));
+        hdk::prelude::debug!("handle_delete_commitment::at_location index {:?}", e);
+    }
+    if let Some(spec_address) = entry.resource_conforms_to {
+        let e = update_index!(commitment.resource_conforms_to.not(&vec![spec_address]), resource_specification.conforming_commitments(&base_address));
+        hdk::prelude::debug!("handle_delete_commitment::resource_conforms_to index {:?}", e);
+    }
+    let reciprocals: Vec<CommitmentAddress> = read_index!(commitment(&base_address).reciprocal_of)?;
+    if !reciprocals.is_empty() {
+        let e = update_index!(commitment.reciprocal_of.not(&reciprocals), commitment.reciprocal_of(&base_address));
+        hdk::prelude::debug!("handle_delete_commitment::reciprocal_of index {:?}", e);
+    }
 
     // delete entry last, as it must be present in order for links to be removed
-    delete_record::<EntryStorage>(&revision_id)
+    Ok(DeletionReport {
+        deleted: delete_record::<EntryStorage>(&revision_id)?,
+        removed_dependents,
+    })
+}
+
+/// Reject quantities lacking units, or referencing units which have not been
+/// registered, if the network settings require them.
+///
+fn throw_if_unitless_quantities(
+    resource_quantity: &MaybeUndefined<QuantityValue>,
+    effort_quantity: &MaybeUndefined<QuantityValue>,
+) -> RecordAPIResult<()> {
+    let settings = NetworkSettingsReader::new(read_network_settings_zome);
+    let quantities = [
+        ("resourceQuantity", resource_quantity),
+        ("effortQuantity", effort_quantity),
+    ];
+    settings.throw_if_unitless_quantities(&quantities)?;
+    settings.throw_if_unregistered_units(&quantities, |unit| {
+        call_zome_method::<EntryTypes, _, UnitReadResponse, _, _, _, _, _>(
+            unit,
+            &UNIT_READ_API_METHOD,
+            UnitReadRequest { id: unit.to_owned() },
+            LinkTypes::AvailableCapability,
+        )
+    })
+}
+
+/// Reject quantities measured in units other than those of the `ResourceSpecification`
+/// the commitment conforms to, if the network settings require them.
+///
+/// Checked against the stored entry so that updates leaving either field unchanged are covered.
+///
+fn throw_if_spec_units_mismatched(entry: &EntryData) -> RecordAPIResult<()> {
+    let settings = NetworkSettingsReader::new(read_network_settings_zome);
+    settings.throw_if_spec_units_mismatched(
+        &entry.resource_quantity.to_owned().into(),
+        &entry.effort_quantity.to_owned().into(),
+        entry.resource_conforms_to.as_ref(),
+        |spec: &ResourceSpecificationAddress| call_zome_method::<EntryTypes, _, ResourceSpecificationUnitsResponse, _, _, _, _, _>(
+            spec,
+            &RESOURCE_SPECIFICATION_READ_API_METHOD,
+            ResourceSpecificationReadRequest { address: spec.to_owned() },
+            LinkTypes::AvailableCapability,
+        ),
+    )
+}
+
+/// Run the checks applied to a new `Commitment` which do not depend upon it having been written,
+/// so that invalid requests can be refused before any related record is created.
+///
+fn validate_create_request(commitment: &CreateRequest) -> RecordAPIResult<()> {
+    throw_if_unitless_quantities(&commitment.resource_quantity, &commitment.effort_quantity)?;
+    let entry: EntryData = commitment.to_owned().try_into()?;
+    run_validation_hooks(&entry, &entry.validation_hooks())?;
+    entry.validate_or_fields()
+        .and_then(|()| entry.validate_action())
+        .map_err(|e| DataIntegrityError::Wasm(hdk::prelude::wasm_error!(hdk::prelude::WasmErrorInner::Guest(e))))?;
+    throw_if_spec_units_mismatched(&entry)
+}
+
+/// Ensure `agreement` can be read from the DNA which holds it.
+///
+fn throw_if_agreement_missing(agreement: &AgreementAddress) -> RecordAPIResult<()> {
+    let _agreement: AgreementResponseData = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        agreement,
+        &AGREEMENT_READ_API_METHOD,
+        ByAddress { address: agreement.to_owned() },
+        LinkTypes::AvailableCapability,
+    )?;
+    Ok(())
+}
+
+/// Properties accessor for zome config.
+fn read_network_settings_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.commitment.network_settings_zome
+}
+
+/// Properties accessor for zome config.
+fn read_fulfillment_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.commitment.fulfillment_zome
+}
+
+/// Properties accessor for zome config.
+fn read_satisfaction_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.commitment.satisfaction_zome
+}
+
+/// Remove a dependent `Fulfillment` via its storage zome, which also clears
+/// reciprocal indexes in the observation DNA.
+///
+fn delete_fulfillment(address: &FulfillmentAddress) -> RecordAPIResult<bool> {
+    let record: FulfillmentResponse = call_local_zome_method(read_fulfillment_zome, "get_fulfillment", ByAddress { address: address.to_owned() })?;
+    Ok(call_local_zome_method(read_fulfillment_zome, "delete_fulfillment", ByRevision { revision_id: record.fulfillment.revision_id })?)
+}
+
+/// Remove a dependent `Satisfaction` via its storage zome, which also clears
+/// reciprocal indexes in the observation DNA.
+///
+fn delete_satisfaction(address: &SatisfactionAddress) -> RecordAPIResult<bool> {
+    let record: SatisfactionResponse = call_local_zome_method(read_satisfaction_zome, "get_satisfaction", ByAddress { address: address.to_owned() })?;
+    Ok(call_local_zome_method(read_satisfaction_zome, "delete_satisfaction", ByRevision { revision_id: record.satisfaction.revision_id })?)
+}
+
+/// Whether cascading deletes may remove `Fulfillment`s found only in observation DNAs
+fn read_cascade_remote_dependents() -> RecordAPIResult<bool> {
+    let conf: DnaConfigSlice = hdk::prelude::dna_info()?.modifiers.properties.try_into()?;
+    Ok(conf.commitment.cascade_remote_dependents)
+}
+
+/// Observation DNAs configured to be checked for the dependents of every `Commitment`
+fn read_observation_dnas() -> RecordAPIResult<Vec<DnaHash>> {
+    let conf: DnaConfigSlice = hdk::prelude::dna_info()?.modifiers.properties.try_into()?;
+    Ok(conf.commitment.observation_dnas.into_iter().map(DnaHash::from).collect())
+}
+
+/// Observation DNA to be queried for the dependents of a `Commitment`
+struct ObservationDna(DnaHash);
+
+impl AsRef<DnaHash> for ObservationDna {
+    fn as_ref(&self) -> &DnaHash {
+        &self.0
+    }
+}
+
+/// Read the `Fulfillment`s of a `Commitment` held by observation DNAs which have no copy in this DNA,
+/// as can remain where replicating a delete failed.
+///
+/// Observation DNAs are those listed in the `observation_dnas` zome config, those of the events
+/// fulfilling the commitment, and those of the processes and resource it references. Errors if any
+/// of these cannot be queried, unless `skip_unreachable` is set.
+///
+fn read_unreplicated_fulfillments(
+    address: &CommitmentAddress,
+    entry: &EntryData,
+    local_fulfillments: &Vec<FulfillmentAddress>,
+    skip_unreachable: bool,
+) -> RecordAPIResult<Vec<FulfillmentRecord>> {
+    let mut dnas: Vec<DnaHash> = vec![];
+    let mut add_dna = |dna: &DnaHash| if !dnas.contains(dna) { dnas.push(dna.to_owned()) };
+    for dna in read_observation_dnas()? {
+        add_dna(&dna);
+    }
+    for fulfillment in local_fulfillments {
+        // unreadable local fulfillments are reported when deleting or summarising dependents
+        let record: Option<FulfillmentResponse> = call_local_zome_method(read_fulfillment_zome, "get_fulfillment", ByAddress { address: fulfillment.to_owned() }).ok();
+        if let Some(FulfillmentResponse { fulfillment }) = record {
+            add_dna(fulfillment.fulfilled_by.as_ref());
+        }
+    }
+    for process in entry.input_of.iter().chain(entry.output_of.iter()) {
+        add_dna(process.as_ref());
+    }
+    if let Some(resource) = &entry.resource_inventoried_as {
+        add_dna(resource.as_ref());
+    }
+
+    let mut unreplicated = vec![];
+    for dna in dnas {
+        let remote: Vec<FulfillmentRecord> = match call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+            &ObservationDna(dna.to_owned()),
+            &READ_COMMITMENT_FULFILLMENTS_API_METHOD,
+            ByAddress { address: address.to_owned() },
+            LinkTypes::AvailableCapability,
+        ) {
+            Ok(remote) => remote,
+            Err(e) if skip_unreachable => {
+                hdk_records::log_warn!("delete_commitment", dna = dna; "skipping unreachable observation DNA: {}", e);
+                continue;
+            },
+            Err(e) => return Err(DataIntegrityError::DependentsUnverifiable(dna, e.to_string())),
+        };
+
+        // copies of local fulfillments share their identity, and are removed along with them
+        unreplicated.extend(remote.into_iter().filter(|r| {
+            !local_fulfillments.iter().any(|l| AsRef::<EntryHash>::as_ref(l) == AsRef::<EntryHash>::as_ref(&r.id))
+        }));
+    }
+    Ok(unreplicated)
+}
+
+/// Remove a `Fulfillment` held only by an observation DNA, which also clears its event indexes there.
+///
+fn delete_remote_fulfillment(address: &FulfillmentAddress, records: &Vec<FulfillmentRecord>) -> RecordAPIResult<bool> {
+    let record = records.iter().find(|r| r.id == *address).ok_or(DataIntegrityError::EntryNotFound)?;
+    Ok(call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        address,
+        &REPLICATE_DELETE_API_METHOD,
+        ByRevision { revision_id: record.revision_id.to_owned() },
+        LinkTypes::AvailableCapability,
+    )?)
 }
 
 /// Create response from input DHT primitives
@@ -222,10 +708,12 @@ fn construct_response<'a>(
         fulfillments,
         satisfactions,
         involved_agents,
+        reciprocals,
     ): (
         Vec<FulfillmentAddress>,
         Vec<SatisfactionAddress>,
         Vec<AgentAddress>,
+        Vec<CommitmentAddress>,
     )
 ) -> RecordAPIResult<ResponseData> {
     Ok(ResponseData {
@@ -233,6 +721,7 @@ fn construct_response<'a>(
             id: address.to_owned(),
             revision_id: meta.as_hash().to_owned(),
             meta: read_revision_metadata_abbreviated(meta)?,
+            created_at: None,
             action: e.action.to_owned(),
             note: e.note.to_owned(),
             input_of: e.input_of.to_owned(),
@@ -258,6 +747,7 @@ fn construct_response<'a>(
             fulfilled_by: fulfillments.to_owned(),
             satisfies: satisfactions.to_owned(),
             involved_agents: involved_agents.to_owned(),
+            reciprocal_of: reciprocals.first().cloned(),
             // TODO: does this need to be updated to reflect current vf-graphql? For example, shouldn't the field `in_scope_of` not be part of `EntryData` and instead passed in?
         }
     })
@@ -290,15 +780,22 @@ fn read_plan_index_zome(conf: DnaConfigSlice) -> Option<String> {
     conf.commitment.plan_index_zome
 }
 
+/// Properties accessor for zome config
+fn read_resource_specification_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.commitment.resource_specification_index_zome
+}
+
 // @see construct_response
 fn get_link_fields(commitment: &CommitmentAddress) -> RecordAPIResult<(
     Vec<FulfillmentAddress>,
     Vec<SatisfactionAddress>,
     Vec<AgentAddress>, // is this for `involved_agents` or `in_scope_of` or both?
+    Vec<CommitmentAddress>,
 )> {
     Ok((
         read_index!(commitment(commitment).fulfilled_by)?,
         read_index!(commitment(commitment).satisfies)?,
         vec![],   // :TODO:
+        read_index!(commitment(commitment).reciprocal_of)?,
     ))
 }
@@ -7,14 +7,19 @@
  * @package hREA
  */
 use holochain_serialized_bytes::prelude::*;
+use hdk_uuid_types::{ DnaHash, addressable_identifier };
 
 use serde_maybe_undefined::{MaybeUndefined, default_false};
 use vf_measurement::QuantityValue;
 pub use vf_attributes_hdk::{
-    ActionHash, ByAction, ByRevision, RecordMeta, RevisionMeta,
+    ErrorCode, ErrorEnvelope,
+    PatchOp, PatchParams,
+    DeleteParams, DeletionReport,
+    ActionHash, ByAction, ByAddressResult, ByRevision, RecordMeta, RevisionMeta,
+    RevisionHistoryParams, RevisionHistory,
     ActionId,
-    DateTime, FixedOffset,
-    ExternalURL,
+    DateTime, FixedOffset, Utc,
+    ExternalURL, classification_index_paths,
     LocationAddress,
     AgentAddress,
     EconomicResourceAddress,
@@ -24,8 +29,16 @@ pub use vf_attributes_hdk::{
     AgreementAddress,
     FulfillmentAddress,
     SatisfactionAddress,
+    EntryHash,
+    ReassignSpecificationParams,
 };
 
+// internal type for indexing against pending / completed state
+addressable_identifier!(CommitmentStateId => EntryHash);
+
+// internal type for indexing against resource classification URLs
+addressable_identifier!(CommitmentClassificationId => EntryHash);
+
 //---------------- EXTERNAL RECORD STRUCTURE ----------------
 
 // Export external type interface to allow consuming zomes to easily import & define zome API
@@ -37,46 +50,67 @@ pub use vf_attributes_hdk::{ CommitmentAddress };
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     pub id: CommitmentAddress,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     pub meta: RecordMeta,
+    /// time of the initial create action, only populated when reading records by ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "created_at"))]
+    pub created_at: Option<DateTime<Utc>>,
     pub action: ActionId,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub note: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "input_of"))]
     pub input_of: Option<ProcessAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "output_of"))]
     pub output_of: Option<ProcessAddress>,
     pub provider: AgentAddress,
     pub receiver: AgentAddress,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_inventoried_as"))]
     pub resource_inventoried_as: Option<EconomicResourceAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_classified_as"))]
     pub resource_classified_as: Option<Vec<ExternalURL>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_conforms_to"))]
     pub resource_conforms_to: Option<ResourceSpecificationAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_quantity"))]
     pub resource_quantity: Option<QuantityValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "effort_quantity"))]
     pub effort_quantity: Option<QuantityValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_beginning"))]
     pub has_beginning: Option<DateTime<FixedOffset>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_end"))]
     pub has_end: Option<DateTime<FixedOffset>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_point_in_time"))]
     pub has_point_in_time: Option<DateTime<FixedOffset>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub due: Option<DateTime<FixedOffset>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "at_location"))]
     pub at_location: Option<LocationAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "in_scope_of"))]
     pub in_scope_of: Option<Vec<String>>, // Why is this not considered a link field like those below? Or is this just out of date?
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "agreed_in"))]
     pub agreed_in: Option<ExternalURL>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "clause_of"))]
     pub clause_of: Option<AgreementAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "independent_demand_of"))]
     pub independent_demand_of: Option<PlanAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "planned_within"))]
     pub planned_within: Option<PlanAddress>,
 
     pub finished: bool,
@@ -84,13 +118,20 @@ pub struct Response {
     // LINK FIELDS
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "fulfilled_by"))]
     pub fulfilled_by: Vec<FulfillmentAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub satisfies: Vec<SatisfactionAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "involved_agents"))]
     pub involved_agents: Vec<AgentAddress>,
+    /// the mirrored commitment of an exchange this commitment was created as part of
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "reciprocal_of"))]
+    pub reciprocal_of: Option<CommitmentAddress>,
 }
 
 /// I/O struct to describe what is returned outside the gateway.
@@ -114,42 +155,58 @@ pub struct CreateRequest {
     #[serde(default)]
     pub note: MaybeUndefined<String>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "input_of"))]
     pub input_of: MaybeUndefined<ProcessAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "output_of"))]
     pub output_of: MaybeUndefined<ProcessAddress>,
     pub provider: AgentAddress,
     pub receiver: AgentAddress,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_inventoried_as"))]
     pub resource_inventoried_as: MaybeUndefined<EconomicResourceAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_classified_as"))]
     pub resource_classified_as: MaybeUndefined<Vec<ExternalURL>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_conforms_to"))]
     pub resource_conforms_to: MaybeUndefined<ResourceSpecificationAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_quantity"))]
     pub resource_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "effort_quantity"))]
     pub effort_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_beginning"))]
     pub has_beginning: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_end"))]
     pub has_end: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_point_in_time"))]
     pub has_point_in_time: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
     pub due: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "at_location"))]
     pub at_location: MaybeUndefined<LocationAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "agreed_in"))]
     pub agreed_in: MaybeUndefined<ExternalURL>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "clause_of"))]
     pub clause_of: MaybeUndefined<AgreementAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "planned_within"))]
     pub planned_within: MaybeUndefined<PlanAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "independent_demand_of"))]
     pub independent_demand_of: MaybeUndefined<PlanAddress>,
     #[serde(default = "default_false")]
     pub finished: MaybeUndefined<bool>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "in_scope_of"))]
     pub in_scope_of: MaybeUndefined<Vec<String>>,
 }
 
@@ -164,50 +221,67 @@ impl<'a> CreateRequest {
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateRequest {
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     #[serde(default)]
     pub action: MaybeUndefined<ActionId>,
     #[serde(default)]
     pub note: MaybeUndefined<String>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "input_of"))]
     pub input_of: MaybeUndefined<ProcessAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "output_of"))]
     pub output_of: MaybeUndefined<ProcessAddress>,
     #[serde(default)]
     pub provider: MaybeUndefined<AgentAddress>,
     #[serde(default)]
     pub receiver: MaybeUndefined<AgentAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_inventoried_as"))]
     pub resource_inventoried_as: MaybeUndefined<EconomicResourceAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_classified_as"))]
     pub resource_classified_as: MaybeUndefined<Vec<ExternalURL>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_conforms_to"))]
     pub resource_conforms_to: MaybeUndefined<ResourceSpecificationAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_quantity"))]
     pub resource_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "effort_quantity"))]
     pub effort_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_beginning"))]
     pub has_beginning: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_end"))]
     pub has_end: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_point_in_time"))]
     pub has_point_in_time: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
     pub due: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "at_location"))]
     pub at_location: MaybeUndefined<LocationAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "agreed_in"))]
     pub agreed_in: MaybeUndefined<ExternalURL>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "clause_of"))]
     pub clause_of: MaybeUndefined<AgreementAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "independent_demand_of"))]
     pub independent_demand_of: MaybeUndefined<PlanAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "planned_within"))]
     pub planned_within: MaybeUndefined<PlanAddress>,
     #[serde(default)]
     pub finished: MaybeUndefined<bool>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "in_scope_of"))]
     pub in_scope_of: MaybeUndefined<Vec<String>>,
 }
 
@@ -219,6 +293,46 @@ impl<'a> UpdateRequest {
     // :TODO: accessors for other field data
 }
 
+//---------------- CLONE REQUEST ----------------
+
+/// Parameters for creating a new `Commitment` as a copy of an existing one.
+/// `overrides` are merged over the copied fields as for an update, and its
+/// `revision_id` must reference the latest revision of the source record.
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CloneParams {
+    pub address: CommitmentAddress,
+    pub overrides: UpdateRequest,
+}
+
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CloneResponseData {
+    pub cloned_from: CommitmentAddress,
+    pub commitment: Response,
+}
+
+//---------------- EXCHANGE REQUEST ----------------
+
+/// Parameters for creating the two mirrored `Commitment`s of an exchange as clauses of `agreement`.
+/// The `reciprocal` commitment must swap the `provider` and `receiver` of the `primary` one.
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeParams {
+    pub agreement: AgreementAddress,
+    pub primary: CreateRequest,
+    pub reciprocal: CreateRequest,
+}
+
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeResponseData {
+    pub primary: Response,
+    pub reciprocal: Response,
+}
+
 //---------------- QUERY FILTER REQUEST ----------------
 
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
@@ -233,4 +347,22 @@ pub struct QueryParams {
     pub receiver: Option<AgentAddress>,
     pub independent_demand_of: Option<PlanAddress>,
     pub planned_within: Option<PlanAddress>,
+    pub at_location: Option<LocationAddress>,
+    pub resource_conforms_to: Option<ResourceSpecificationAddress>,
+    pub reciprocal_of: Option<CommitmentAddress>,
+
+    // `commitment.pending` or `commitment.completed`
+    pub finished_state: Option<String>,
+    pub finished_state_internal: Option<CommitmentStateId>,
+
+    // resource classification URL, @see vf_attributes_hdk::classification_index_paths
+    pub classified_as: Option<String>,
+    pub classified_as_internal: Option<CommitmentClassificationId>,
+}
+
+#[cfg(all(test, feature = "snake_case_aliases"))]
+mod tests {
+    use super::*;
+
+    vf_attributes_hdk::snake_case_alias_tests!(Response, CreateRequest, UpdateRequest);
 }
@@ -31,9 +31,9 @@ pub use vf_attributes_hdk::{
     PlanAddress,
 };
 
-use vf_actions::{ validate_flow_action };
+use vf_actions::{ validate_flow_action, normalize_action };
 
-use hc_zome_rea_commitment_rpc::{ CreateRequest, UpdateRequest };
+use hc_zome_rea_commitment_rpc::{ CreateRequest, UpdateRequest, ReassignSpecificationParams };
 
 // :SHONK: needed as re-export in zome logic to allow validation logic to parse entries
 pub use hdk_records::record_interface::Identified;
@@ -53,6 +53,20 @@ pub struct CommitmentZomeConfig {
     pub agreement_index_zome: Option<String>,
     pub agent_index_zome: Option<String>,
     pub plan_index_zome: Option<String>,
+    pub resource_specification_index_zome: Option<String>,
+    // zome IDs of dependent record zomes, used for cascading deletes
+    pub fulfillment_zome: Option<String>,
+    pub satisfaction_zome: Option<String>,
+    // zome ID of the network settings zome, used to read enforcement flags
+    pub network_settings_zome: Option<String>,
+    // when set, cascading deletes also remove `Fulfillment`s found only in observation DNAs,
+    // rather than refusing to delete the commitment
+    #[serde(default)]
+    pub cascade_remote_dependents: bool,
+    // observation DNAs always checked for `Fulfillment`s when deleting a commitment, in addition
+    // to the DNAs of the records it references
+    #[serde(default)]
+    pub observation_dnas: Vec<holo_hash::DnaHashB64>,
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -84,7 +98,17 @@ pub struct EntryData {
     pub _nonce: Bytes,
 }
 
+/// Index paths for querying commitments by whether they are pending or completed
+pub const COMMITMENT_STATE_PENDING: &str = "commitment.pending";
+pub const COMMITMENT_STATE_COMPLETED: &str = "commitment.completed";
+
 impl EntryData {
+    /// State path this commitment should be indexed under.
+    ///
+    pub fn finished_state(&self) -> Vec<String> {
+        vec![(if self.finished { COMMITMENT_STATE_COMPLETED } else { COMMITMENT_STATE_PENDING }).to_string()]
+    }
+
     pub fn validate_action(&self) -> Result<(), String> {
         validate_flow_action(self.action.to_owned(), self.input_of.to_owned(), self.output_of.to_owned())
     }
@@ -147,7 +171,7 @@ impl TryFrom<CreateRequest> for EntryData {
 
     fn try_from(e: CreateRequest) -> RecordAPIResult<EntryData> {
         Ok(EntryData {
-            action: e.action.to_owned(),
+            action: normalize_action(e.action.as_ref())?,
             note: e.note.into(),
             provider: e.provider.into(),
             receiver: e.receiver.into(),
@@ -174,13 +198,45 @@ impl TryFrom<CreateRequest> for EntryData {
     }
 }
 
+//---------------- CLONE ----------------
+
+/// Convert stored entry data back into a creation request, for duplicating records
+impl From<EntryData> for CreateRequest {
+    fn from(e: EntryData) -> CreateRequest {
+        CreateRequest {
+            action: e.action,
+            note: e.note.into(),
+            provider: e.provider,
+            receiver: e.receiver,
+            input_of: e.input_of.into(),
+            output_of: e.output_of.into(),
+            resource_inventoried_as: e.resource_inventoried_as.into(),
+            resource_classified_as: e.resource_classified_as.into(),
+            resource_conforms_to: e.resource_conforms_to.into(),
+            resource_quantity: e.resource_quantity.into(),
+            effort_quantity: e.effort_quantity.into(),
+            has_beginning: e.has_beginning.into(),
+            has_end: e.has_end.into(),
+            has_point_in_time: e.has_point_in_time.into(),
+            due: e.due.into(),
+            at_location: e.at_location.into(),
+            agreed_in: e.agreed_in.into(),
+            clause_of: e.clause_of.into(),
+            planned_within: e.planned_within.into(),
+            independent_demand_of: e.independent_demand_of.into(),
+            finished: MaybeUndefined::Some(e.finished),
+            in_scope_of: e.in_scope_of.into(),
+        }
+    }
+}
+
 //---------------- UPDATE ----------------
 
 /// Handles update operations by merging any newly provided fields
 impl Updateable<UpdateRequest> for EntryData {
     fn update_with(&self, e: UpdateRequest) -> RecordAPIResult<EntryData> {
         Ok(EntryData {
-            action: if !e.action.is_some() { self.action.to_owned() } else { e.action.to_owned().unwrap() },
+            action: if !e.action.is_some() { self.action.to_owned() } else { normalize_action(e.action.to_owned().unwrap().as_ref())? },
             provider: if !e.provider.is_some() { self.provider.to_owned() } else { e.provider.to_owned().unwrap() },
             receiver: if !e.receiver.is_some() { self.receiver.to_owned() } else { e.receiver.to_owned().unwrap() },
             input_of: if e.input_of == MaybeUndefined::Undefined { self.input_of.to_owned() } else { e.input_of.to_owned().into() },
@@ -199,10 +255,94 @@ impl Updateable<UpdateRequest> for EntryData {
             agreed_in: if e.agreed_in == MaybeUndefined::Undefined { self.agreed_in.clone() } else { e.agreed_in.clone().into() },
             clause_of: if e.clause_of == MaybeUndefined::Undefined { self.clause_of.clone() } else { e.clause_of.clone().into() },
             independent_demand_of: if e.independent_demand_of == MaybeUndefined::Undefined { self.independent_demand_of.clone() } else { e.independent_demand_of.clone().into() },
-            finished: if e.finished == MaybeUndefined::Undefined { self.finished.clone() } else { e.finished.clone().to_option().unwrap() },
+            finished: if !e.finished.is_some() { self.finished.clone() } else { e.finished.clone().to_option().unwrap() },
             in_scope_of: if e.in_scope_of== MaybeUndefined::Undefined { self.in_scope_of.clone() } else { e.in_scope_of.clone().into() },
             note: if e.note== MaybeUndefined::Undefined { self.note.clone() } else { e.note.clone().into() },
             _nonce: self._nonce.to_owned(),
         })
     }
 }
+
+/// Re-points the commitment at a different `ResourceSpecification` when specifications are merged.
+impl Updateable<ReassignSpecificationParams<CommitmentAddress>> for EntryData {
+    fn update_with(&self, e: ReassignSpecificationParams<CommitmentAddress>) -> RecordAPIResult<EntryData> {
+        Ok(EntryData {
+            resource_conforms_to: Some(e.to),
+            ..self.to_owned()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dna() -> DnaHash {
+        DnaHash::from_raw_36(vec![0xdb; 36])
+    }
+
+    fn hash(n: u8) -> EntryHash {
+        EntryHash::from_raw_36(vec![n; 36])
+    }
+
+    fn url(path: &str) -> ExternalURL {
+        ExternalURL(format!("http://example.com/{}", path))
+    }
+
+    fn time(time: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(time).unwrap()
+    }
+
+    fn entry() -> EntryData {
+        EntryData {
+            action: ActionId("produce".to_string()),
+            provider: AgentAddress(dna(), hash(1)),
+            receiver: AgentAddress(dna(), hash(2)),
+            input_of: Some(ProcessAddress(dna(), hash(3))),
+            output_of: Some(ProcessAddress(dna(), hash(4))),
+            resource_inventoried_as: Some(EconomicResourceAddress(dna(), hash(5))),
+            resource_classified_as: Some(vec![url("classifications/bicycle")]),
+            resource_conforms_to: Some(ResourceSpecificationAddress(dna(), hash(6))),
+            resource_quantity: Some(QuantityValue::new(1.0, None)),
+            effort_quantity: Some(QuantityValue::new(2.0, None)),
+            has_beginning: Some(time("2024-02-14T10:00:00Z")),
+            has_end: Some(time("2024-02-15T10:00:00Z")),
+            has_point_in_time: Some(time("2024-02-14T12:00:00Z")),
+            due: Some(time("2024-02-16T10:00:00Z")),
+            at_location: Some(LocationAddress(dna(), hash(7))),
+            agreed_in: Some(url("agreements/1")),
+            clause_of: Some(AgreementAddress(dna(), hash(8))),
+            independent_demand_of: Some(PlanAddress(dna(), hash(9))),
+            planned_within: Some(PlanAddress(dna(), hash(10))),
+            finished: false,
+            in_scope_of: Some(vec!["scope".to_string()]),
+            note: Some("a note".to_string()),
+            _nonce: Bytes::from(vec![0; 32]),
+        }
+    }
+
+    hdk_records::update_semantics_tests!(EntryData, UpdateRequest, entry(), {
+        "action" => ActionId("use".to_string()),
+        "note" => "updated note",
+        "inputOf" => ProcessAddress(dna(), hash(13)),
+        "outputOf" => ProcessAddress(dna(), hash(14)),
+        "provider" => AgentAddress(dna(), hash(11)),
+        "receiver" => AgentAddress(dna(), hash(12)),
+        "resourceInventoriedAs" => EconomicResourceAddress(dna(), hash(15)),
+        "resourceClassifiedAs" => vec![url("classifications/wheel")],
+        "resourceConformsTo" => ResourceSpecificationAddress(dna(), hash(16)),
+        "resourceQuantity" => QuantityValue::new(3.0, None),
+        "effortQuantity" => QuantityValue::new(4.0, None),
+        "hasBeginning" => time("2024-03-14T10:00:00Z"),
+        "hasEnd" => time("2024-03-15T10:00:00Z"),
+        "hasPointInTime" => time("2024-03-14T12:00:00Z"),
+        "due" => time("2024-03-16T10:00:00Z"),
+        "atLocation" => LocationAddress(dna(), hash(17)),
+        "agreedIn" => url("agreements/2"),
+        "clauseOf" => AgreementAddress(dna(), hash(18)),
+        "independentDemandOf" => PlanAddress(dna(), hash(19)),
+        "plannedWithin" => PlanAddress(dna(), hash(20)),
+        "finished" => true,
+        "inScopeOf" => vec!["updated scope".to_string()],
+    });
+}
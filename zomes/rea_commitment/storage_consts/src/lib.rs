@@ -6,3 +6,10 @@
  * @package hREA
  */
 pub const COMMITMENT_ENTRY_TYPE: &str = "commitment";
+
+// remote_auth permission ID for batch reads of commitments from other DNAs
+pub const COMMITMENT_READ_BATCH_API_METHOD: &str = "read_commitments";
+
+// remote_auth permission IDs for managing commitments from other DNAs
+pub const COMMITMENT_CREATE_API_METHOD: &str = "create_commitment";
+pub const COMMITMENT_DELETE_API_METHOD: &str = "delete_commitment";
@@ -28,6 +28,11 @@ fn create_commitment(CreateParams { commitment }: CreateParams) -> ExternResult<
     Ok(handle_create_commitment(COMMITMENT_ENTRY_TYPE, commitment)?)
 }
 
+#[hdk_extern]
+fn create_exchange_commitments(ExchangeParams { agreement, primary, reciprocal }: ExchangeParams) -> ExternResult<ExchangeResponseData> {
+    Ok(handle_create_exchange_commitments(COMMITMENT_ENTRY_TYPE, agreement, primary, reciprocal)?)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct ByAddress {
     pub address: CommitmentAddress,
@@ -38,11 +43,31 @@ fn get_commitment(ByAddress { address }: ByAddress) -> ExternResult<ResponseData
     Ok(handle_get_commitment(address)?)
 }
 
+#[hdk_extern]
+fn resync_record_indexes(ByAddress { address }: ByAddress) -> ExternResult<bool> {
+    Ok(handle_resync_commitment_indexes(address)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ByAddresses {
+    pub addresses: Vec<CommitmentAddress>,
+}
+
+#[hdk_extern]
+fn get_commitments(ByAddresses { addresses }: ByAddresses) -> ExternResult<Vec<ByAddressResult<CommitmentAddress, ResponseData>>> {
+    Ok(handle_get_commitments(addresses)?)
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
 }
 
+#[hdk_extern]
+fn get_revision_history(params: RevisionHistoryParams<CommitmentAddress>) -> ExternResult<RevisionHistory<ResponseData>> {
+    Ok(handle_get_commitment_revision_history(params)?)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct UpdateParams {
@@ -55,6 +80,28 @@ fn update_commitment(UpdateParams { commitment }: UpdateParams) -> ExternResult<
 }
 
 #[hdk_extern]
-fn delete_commitment(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
-    Ok(handle_delete_commitment(revision_id)?)
+fn patch_record(PatchParams { address, ops }: PatchParams<CommitmentAddress>) -> ExternResult<ResponseData> {
+    Ok(handle_patch_commitment(address, ops)?)
+}
+
+#[hdk_extern]
+fn clone_record(CloneParams { address, overrides }: CloneParams) -> ExternResult<CloneResponseData> {
+    Ok(handle_clone_commitment(COMMITMENT_ENTRY_TYPE, address, overrides)?)
+}
+
+// used by the specification DNA when merging `ResourceSpecification`s
+#[hdk_extern]
+fn reassign_resource_specification(params: ReassignSpecificationParams<CommitmentAddress>) -> ExternResult<bool> {
+    Ok(handle_reassign_resource_specification(params)?)
+}
+
+#[hdk_extern]
+fn delete_commitment(DeleteParams { revision_id, cascade }: DeleteParams) -> ExternResult<DeletionReport> {
+    Ok(handle_delete_commitment(revision_id, cascade)?)
+}
+
+// :NOTE: administrative operation, for commitments referencing DNAs which are no longer installed
+#[hdk_extern]
+fn force_delete_commitment(DeleteParams { revision_id, cascade }: DeleteParams) -> ExternResult<DeletionReport> {
+    Ok(handle_force_delete_commitment(revision_id, cascade)?)
 }
@@ -20,5 +20,19 @@ struct Commitment {
     receiver: Local<agent, commitments_as_receiver>,
     independent_demand_of: Local<plan, independent_demands>,
     planned_within: Local<plan, non_process_commitments>,
+    at_location: Local<location, commitments_at>,
+    resource_conforms_to: Local<resource_specification, conforming_commitments>,
+    // mirrored commitments of an exchange, @see handle_create_exchange_commitments
+    reciprocal_of: Local<commitment, reciprocal_of>,
     // in_scope_of: Local<agent, commitments>,
+
+    // pending / completed state index, maintained as commitments are finished
+    finished_state: Local<commitment, finished_state_internal>::String,
+    // :SHONK: redundant loopback index, @see zome_idx_agent
+    finished_state_internal: Local<commitment, finished_state>,
+
+    // query commitments by resource classification URL
+    classified_as: Local<commitment, classified_as_internal>::String,
+    // :SHONK: redundant loopback index, @see zome_idx_agent
+    classified_as_internal: Local<commitment, classified_as>,
 }
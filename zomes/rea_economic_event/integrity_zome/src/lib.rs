@@ -9,6 +9,7 @@
  */
 use hdi::prelude::*;
 pub use hc_zome_rea_economic_event_storage::{Identified, EntryStorage, EntryTypes, EntryTypesUnit, LinkTypes};
+use hc_zome_rea_attachment_storage::AttachmentManifest;
 
 #[hdk_extern]
 pub fn entry_defs(_: ()) -> ExternResult<EntryDefsCallbackResult> {
@@ -59,10 +60,27 @@ macro_rules! declare_economic_event_zome_validation_defaults {
             match EntryStorage::try_from(&entry) {
                 Ok(event_storage) => {
                     let record = event_storage.entry();
-                    record.validate_or_fields()
+                    if let Err(e) = record.validate_or_fields()
                         .and_then(|()| { record.validate_action() })
-                        .and_then(|()| { Ok(ValidateCallbackResult::Valid) })
-                        .or_else(|e| { Ok(ValidateCallbackResult::Invalid(e)) })
+                        .and_then(|()| { record.validate_attachments() })
+                    {
+                        return Ok(ValidateCallbackResult::Invalid(e));
+                    }
+                    // attachments must have been stored before they can be referenced
+                    for attachment in record.attachments.iter().flatten() {
+                        let stored = must_get_entry(attachment.hash.to_owned())?;
+                        match AttachmentManifest::try_from(stored.as_content()) {
+                            Ok(manifest) if manifest.size == attachment.size => {},
+                            Ok(manifest) => return Ok(ValidateCallbackResult::Invalid(format!(
+                                "EconomicEvent attachment {} records a size of {} bytes but the stored attachment holds {} bytes",
+                                attachment.hash, attachment.size, manifest.size,
+                            ))),
+                            Err(_) => return Ok(ValidateCallbackResult::Invalid(format!(
+                                "EconomicEvent attachment {} is not a stored attachment", attachment.hash,
+                            ))),
+                        }
+                    }
+                    Ok(ValidateCallbackResult::Valid)
                 },
                 _ => Ok(ValidateCallbackResult::Valid),
             }
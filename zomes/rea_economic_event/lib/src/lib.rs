@@ -6,11 +6,21 @@
  *
  * @package hREA
  */
+use std::convert::TryInto;
 use paste::paste;
 use hdk_records::{
-    RecordAPIResult, OtherCellResult, MaybeUndefined, SignedActionHashed,
+    generate_patch_builder,
+    RecordAPIResult, DataIntegrityError, OtherCellResult, MaybeUndefined, SignedActionHashed,
+    DnaAddressable,
     rpc::{
         call_local_zome_method,
+        call_zome_method,
+    },
+    dependencies::{
+        summarize_dependents,
+        throw_if_dependents,
+        cascade_delete_dependents,
+        throw_if_cascade_failed,
     },
     records::{
         create_record,
@@ -20,8 +30,15 @@ use hdk_records::{
         delete_record,
     },
     metadata::read_revision_metadata_abbreviated,
+    history::read_revision_window,
+    field_indexes::{
+        sync_field_value_index,
+        find_by_field_value,
+    },
 };
 use hdk_semantic_indexes_client_lib::*;
+use hdk_semantic_indexes_zome_rpc::AppendAddress;
+use vf_actions::normalize_action;
 
 pub use hc_zome_rea_economic_event_storage_consts::*;
 
@@ -30,8 +47,18 @@ use hc_zome_rea_economic_event_storage::*;
 use hc_zome_rea_economic_event_rpc::{
     CreateRequest as EconomicEventCreateRequest,
     UpdateRequest as EconomicEventUpdateRequest,
+    EventPeriodId,
+    EventClassificationId,
+    AttachmentRef,
+    classification_index_paths,
 };
-use hc_zome_rea_economic_resource_rpc::{ CreationPayload as ResourceCreationPayload };
+use hc_zome_rea_economic_resource_rpc::{ CreationPayload as ResourceCreationPayload, InventoryDeltasPayload };
+use hc_zome_rea_network_settings_lib::{
+    NetworkSettingsReader, UNIT_READ_API_METHOD, UnitReadRequest, UnitReadResponse,
+    RESOURCE_SPECIFICATION_READ_API_METHOD, ResourceSpecificationReadRequest, ResourceSpecificationUnitsResponse,
+};
+use hc_zome_rea_attachment_storage_consts::ATTACHMENT_REFERENCE_API_METHOD;
+use hc_zome_rea_attachment_rpc::AttachmentReferencesRequest;
 
 use hc_zome_rea_economic_resource_storage::{
     EntryData as EconomicResourceData,
@@ -71,7 +98,48 @@ impl API for EconomicEventZomePermissableDefault {
         entry_def_id: Self::S,
         event: EconomicEventCreateRequest, new_inventoried_resource: Option<ResourceCreateRequest>
     ) -> RecordAPIResult<ResponseData> {
-        let mut resources_affected: Vec<(SignedActionHashed, EconomicResourceAddress, EconomicResourceData, EconomicResourceData)> = vec![];
+        // canonicalize the action before it is used to determine resource effects
+        let event = EconomicEventCreateRequest { action: normalize_action(event.action.as_ref())?, ..event };
+        throw_if_missing_attachments(&event.attachments)?;
+
+        // events imported from external systems are only recorded once
+        if let Some(reference) = event.get_external_reference() {
+            if let Some(existing) = find_by_external_reference(&reference)? {
+                if read_reject_duplicate_external_references()? {
+                    return Err(DataIntegrityError::ExternalReferenceExists(reference));
+                }
+                return Ok(ResponseData {
+                    already_existed: true,
+                    ..Self::get_economic_event(existing)?
+                });
+            }
+        }
+
+        let settings = NetworkSettingsReader::new(read_network_settings_zome);
+        let quantities = [
+            ("resourceQuantity", &event.resource_quantity),
+            ("effortQuantity", &event.effort_quantity),
+        ];
+        settings.throw_if_unitless_quantities(&quantities)?;
+        settings.throw_if_unregistered_units(&quantities, |unit| {
+            call_zome_method::<EntryTypes, _, UnitReadResponse, _, _, _, _, _>(
+                unit,
+                &UNIT_READ_API_METHOD,
+                UnitReadRequest { id: unit.to_owned() },
+                LinkTypes::AvailableCapability,
+            )
+        })?;
+        settings.throw_if_spec_units_mismatched(
+            &event.resource_quantity, &event.effort_quantity,
+            event.resource_conforms_to.to_owned().to_option().as_ref(),
+            |spec: &ResourceSpecificationAddress| call_zome_method::<EntryTypes, _, ResourceSpecificationUnitsResponse, _, _, _, _, _>(
+                spec,
+                &RESOURCE_SPECIFICATION_READ_API_METHOD,
+                ResourceSpecificationReadRequest { address: spec.to_owned() },
+                LinkTypes::AvailableCapability,
+            ),
+        )?;
+
         let mut resource_created: Option<(SignedActionHashed, EconomicResourceAddress, EconomicResourceData)> = None;
 
         // if the event observes a new resource, create that resource & return it in the response
@@ -79,12 +147,12 @@ impl API for EconomicEventZomePermissableDefault {
             let new_resource = handle_create_inventory_from_event(
                 &economic_resource, &event,
             )?;
-            resource_created = Some(new_resource.clone());
-            resources_affected.push((new_resource.0, new_resource.1, new_resource.2.clone(), new_resource.2));
+            resource_created = Some(new_resource);
         }
 
         // update any linked resources affected by the event
-        resources_affected.append(&mut handle_update_resource_inventory(&event)?);
+        let resources_updated = handle_update_resource_inventory(&event)?;
+        let updated_addresses: Vec<EconomicResourceAddress> = resources_updated.iter().map(|r| r.1.to_owned()).collect();
 
         // Now that the resource updates have succeeded, write the event.
         // Note we ignore the revision ID because events can't be edited (only underwritten by subsequent events)
@@ -98,10 +166,9 @@ impl API for EconomicEventZomePermissableDefault {
             },
         )?;
 
-        // Link any affected resources to this event so that we can pull all the events which affect any resource
-        for resource_data in resources_affected.iter() {
-            let e = create_index!(economic_event.affects(resource_data.1), economic_resource.affected_by(&event_address));
-            hdk::prelude::debug!("create_economic_event::affects index {:?}", e);
+        // record the balance changes against the event, now that it has an identity
+        if !updated_addresses.is_empty() {
+            handle_record_inventory_deltas(&event_address, updated_addresses)?;
         }
 
         match resource_created {
@@ -123,24 +190,107 @@ impl API for EconomicEventZomePermissableDefault {
         construct_response(&base_address, &meta, &entry, get_link_fields(&address)?)
     }
 
+    /// Rebuild the indexes of an event from its latest revision.
+    ///
+    fn resync_economic_event_indexes(address: EconomicEventAddress) -> RecordAPIResult<bool> {
+        let (_meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+
+        let mut results = create_economic_event_indexes(&base_address, &entry)?;
+        results.push(("classified_as", resync_economic_event_classified_as_index(&base_address, &entry)));
+        throw_if_resync_failed(results)
+    }
+
+    fn get_economic_event_by_external_reference(reference: String) -> RecordAPIResult<ResponseData> {
+        let address = find_by_external_reference(&reference)?.ok_or(DataIntegrityError::EntryNotFound)?;
+        Self::get_economic_event(address)
+    }
+
+    /// Each address is returned alongside its record or the reason it could not be read.
+    fn get_economic_events(addresses: Vec<EconomicEventAddress>) -> RecordAPIResult<Vec<ByAddressResult<EconomicEventAddress, ResponseData>>> {
+        Ok(addresses.into_iter()
+            .map(|address| ByAddressResult::new(address.to_owned(), Self::get_economic_event(address)))
+            .collect())
+    }
+
     fn get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
     {
         let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
         construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
     }
 
+    fn get_economic_event_revision_history(params: RevisionHistoryParams<EconomicEventAddress>) -> RecordAPIResult<RevisionHistory<ResponseData>>
+    {
+        let link_fields = get_link_fields(&params.address)?;
+        read_revision_window::<EntryData, EntryStorage, EconomicEventAddress, _, _>(
+            &params.address, params.since, params.until, params.limit,
+            |meta, entry| construct_response(&params.address, meta, entry, link_fields.to_owned()),
+        )
+    }
+
     fn update_economic_event(event: EconomicEventUpdateRequest) -> RecordAPIResult<ResponseData> {
         let address = event.get_revision_id().to_owned();
-        let (meta, identity_address, new_entry, _prev_entry): (_, EconomicEventAddress, EntryData, EntryData) = update_record(&address, event)?;
+        throw_if_missing_attachments(&event.attachments)?;
+        let (meta, identity_address, new_entry, prev_entry): (_, EconomicEventAddress, EntryData, EntryData) = update_record(&address, event)?;
+
+        hdk_records::return_if_unchanged!(meta, address, construct_response(&identity_address, &meta, &new_entry, get_link_fields(&identity_address)?), economic_event);
+
+        let new_periods = new_entry.reporting_periods();
+        let prev_periods = prev_entry.reporting_periods();
+        if new_periods != prev_periods {
+            let e = update_string_index!(economic_event(&identity_address).period(new_periods).not(prev_periods)<EventPeriodId>);
+            hdk::prelude::debug!("update_economic_event::period index {:?}", e);
+        }
+        let e = update_economic_event_classified_as_index(&identity_address, &new_entry, &prev_entry);
+        hdk::prelude::debug!("update_economic_event::classified_as index {:?}", e);
+        if new_entry.realization_of != prev_entry.realization_of {
+            let new_value = match &new_entry.realization_of { Some(val) => vec![val.to_owned()], None => vec![] };
+            let prev_value = match &prev_entry.realization_of { Some(val) => vec![val.to_owned()], None => vec![] };
+            let e = update_index!(
+                economic_event
+                    .realization_of(new_value.as_slice())
+                    .not(prev_value.as_slice()),
+                agreement.economic_events(&identity_address)
+            );
+            hdk::prelude::debug!("update_economic_event::realization_of index {:?}", e);
+        }
+        sync_occurrence_index(&identity_address, Some(&new_entry), Some(&prev_entry))?;
+        sync_attachment_references(&identity_address, Some(&new_entry), Some(&prev_entry))?;
 
         // :TODO: optimise this- should pass results from `replace_direct_index` instead of retrieving from `get_link_fields` where updates
         construct_response(&identity_address, &meta, &new_entry, get_link_fields(&identity_address)?)
     }
 
-    fn delete_economic_event(revision_id: ActionHash) -> RecordAPIResult<bool> {
+    fn patch_economic_event(address: EconomicEventAddress, ops: Vec<PatchOp>) -> RecordAPIResult<ResponseData> {
+        let revision_id = Self::get_economic_event(address)?.economic_event.revision_id;
+        Self::update_economic_event(build_patch_update(revision_id, ops)?)
+    }
+
+    fn delete_economic_event(revision_id: ActionHash, cascade: bool) -> RecordAPIResult<DeletionReport> {
         // read any referencing indexes
         let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
 
+        // check for dependent records
+        // :NOTE: appreciations are not yet modelled by hREA, so cannot depend on events
+        let fulfillments: Vec<FulfillmentAddress> = read_index!(economic_event(&base_address).fulfills)?;
+        let satisfactions: Vec<SatisfactionAddress> = read_index!(economic_event(&base_address).satisfies)?;
+
+        let removed_dependents = if cascade {
+            let (mut removed, mut failed) = cascade_delete_dependents("fulfillments", fulfillments, delete_fulfillment);
+            let (removed_s, failed_s) = cascade_delete_dependents("satisfactions", satisfactions, delete_satisfaction);
+            removed.extend(removed_s);
+            failed.extend(failed_s);
+            throw_if_cascade_failed(removed, failed)?
+        } else {
+            throw_if_dependents(vec![
+                summarize_dependents("fulfillments", &fulfillments),
+                summarize_dependents("satisfactions", &satisfactions),
+            ])?;
+            vec![]
+        };
+
+        // attachments are left in place, as they may be referenced by other records
+        sync_attachment_references(&base_address, None, Some(&entry))?;
+
         // handle link fields
         if let Some(process_address) = entry.input_of {
             let e = update_index!(economic_event.input_of.not(&vec![process_address.to_owned()]), process.observed_inputs(&base_address));
@@ -158,13 +308,87 @@ impl API for EconomicEventZomePermissableDefault {
         hdk::prelude::debug!("delete_economic_event::provider index {:?}", e);
         let e = update_index!(economic_event.receiver.not(&vec![entry.receiver]), agent.economic_events_as_receiver(&base_address));
         hdk::prelude::debug!("delete_economic_event::receiver index {:?}", e);
-
-        // :TODO: handle cleanup of foreign key fields? (fulfillment, satisfaction)
-        // May not be needed due to cross-record deletion validation logic.
+        let e = update_string_index!(economic_event(&base_address).period.not(entry.reporting_periods())<EventPeriodId>);
+        hdk::prelude::debug!("delete_economic_event::period index {:?}", e);
+        let e = delete_economic_event_classified_as_index(&base_address, &entry);
+        hdk::prelude::debug!("delete_economic_event::classified_as index {:?}", e);
+        if let Some(location_address) = entry.at_location {
+            let e = update_index!(economic_event(&base_address).at_location.not(&vec![location_address]));
+            hdk::prelude::debug!("delete_economic_event::at_location index {:?}", e);
+        }
+        if let Some(spec_address) = entry.resource_conforms_to.to_owned() {
+            let e = update_index!(economic_event.resource_conforms_to.not(&vec![spec_address]), resource_specification.conforming_economic_events(&base_address));
+            hdk::prelude::debug!("delete_economic_event::resource_conforms_to index {:?}", e);
+        }
+        sync_field_value_index(LinkTypes::FieldValueIndex, EXTERNAL_REFERENCE_INDEX, &base_address, None, Some(&entry), extract_external_reference)?;
+        sync_occurrence_index(&base_address, None, Some(&entry))?;
+        if entry.resource_inventoried_as.is_some() || entry.to_resource_inventoried_as.is_some() {
+            let e = handle_reverse_resource_inventory(&revision_id)?;
+            hdk::prelude::debug!("delete_economic_event::reversed resource balances {:?}", e);
+        }
 
         // delete entry last as it must be present in order for links to be removed
-        delete_record::<EntryStorage>(&revision_id)
+        Ok(DeletionReport {
+            deleted: delete_record::<EntryStorage>(&revision_id)?,
+            removed_dependents,
+        })
     }
+
+    fn reassign_resource_specification(params: ReassignSpecificationParams<EconomicEventAddress>) -> RecordAPIResult<bool> {
+        NetworkSettingsReader::new(read_network_settings_zome).throw_if_not_admin()?;
+
+        let (meta, _base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(params.address.as_ref())?;
+        if entry.resource_conforms_to.as_ref() != Some(&params.from) {
+            return Ok(false);
+        }
+        let (_meta, identity_address, _new_entry, _prev_entry): (_, EconomicEventAddress, EntryData, EntryData) = update_record(meta.as_hash(), params.to_owned())?;
+
+        update_index!(
+            economic_event
+                .resource_conforms_to(&vec![params.to])
+                .not(&vec![params.from]),
+            resource_specification.conforming_economic_events(&identity_address)
+        )?;
+        Ok(true)
+    }
+}
+
+generate_patch_builder!(EconomicEventUpdateRequest {
+    note,
+    agreed_in,
+    realization_of,
+    triggered_by,
+    in_scope_of,
+    attachments
+});
+
+/// Properties accessor for zome config.
+fn read_network_settings_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.economic_event.network_settings_zome
+}
+
+/// Properties accessor for zome config.
+fn read_fulfillment_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.economic_event.fulfillment_zome
+}
+
+/// Properties accessor for zome config.
+fn read_satisfaction_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.economic_event.satisfaction_zome
+}
+
+/// Remove a dependent `Fulfillment`, along with its originating record in the planning DNA
+/// of the fulfilled commitment. Fails without removing anything if that DNA cannot be reached.
+///
+fn delete_fulfillment(address: &FulfillmentAddress) -> RecordAPIResult<bool> {
+    Ok(call_local_zome_method(read_fulfillment_zome, "delete_fulfillment_from_event", ByAddress { address: address.to_owned() })?)
+}
+
+/// Remove a dependent `Satisfaction`, along with its originating record in the planning DNA
+/// of the satisfied intent. Fails without removing anything if that DNA cannot be reached.
+///
+fn delete_satisfaction(address: &SatisfactionAddress) -> RecordAPIResult<bool> {
+    Ok(call_local_zome_method(read_satisfaction_zome, "delete_satisfaction_from_event", ByAddress { address: address.to_owned() })?)
 }
 
 // API logic handlers
@@ -185,6 +409,94 @@ fn read_agreement_index_zome(conf: DnaConfigSlice) -> Option<String> {
     conf.economic_event.agreement_index_zome
 }
 
+/// Properties accessor for zome config.
+fn read_resource_specification_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.economic_event.resource_specification_index_zome
+}
+
+derived_index!(economic_event {
+    field: resource_classified_as,
+    index: classified_as,
+    path: classification_index_paths
+}<EventClassificationId>);
+
+/// field value index for uniqueness of `external_reference`
+const EXTERNAL_REFERENCE_INDEX: &str = "economic_event.external_reference";
+
+/// field extractor for `EXTERNAL_REFERENCE_INDEX`
+fn extract_external_reference(e: &EntryData) -> Option<String> {
+    e.external_reference.as_ref().map(|r| normalize_external_reference(r))
+}
+
+/// Locate the event previously recorded with the given external reference, if any.
+fn find_by_external_reference(reference: &str) -> RecordAPIResult<Option<EconomicEventAddress>> {
+    let addresses: Vec<EconomicEventAddress> = find_by_field_value(LinkTypes::FieldValueIndex, EXTERNAL_REFERENCE_INDEX, normalize_external_reference(reference))?;
+    Ok(addresses.into_iter().next())
+}
+
+/// Properties accessor for zome config.
+fn read_attachment_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.economic_event.attachment_zome
+}
+
+/// Attachments must be uploaded to the attachment zome before an event can reference them.
+fn throw_if_missing_attachments(attachments: &MaybeUndefined<Vec<AttachmentRef>>) -> RecordAPIResult<()> {
+    for attachment in attachments.to_owned().to_option().unwrap_or_default() {
+        if hdk::prelude::get(attachment.hash.to_owned(), hdk::prelude::GetOptions::default())?.is_none() {
+            return Err(DataIntegrityError::AttachmentNotFound(attachment.hash));
+        }
+    }
+    Ok(())
+}
+
+/// Report the attachments added to & removed from an event to the attachment zome,
+/// which tracks references for garbage collection.
+fn sync_attachment_references(address: &EconomicEventAddress, new_entry: Option<&EntryData>, prev_entry: Option<&EntryData>) -> RecordAPIResult<()> {
+    let new_hashes = new_entry.map(|e| e.attachment_hashes()).unwrap_or_default();
+    let prev_hashes = prev_entry.map(|e| e.attachment_hashes()).unwrap_or_default();
+    let added: Vec<EntryHash> = new_hashes.iter().filter(|h| !prev_hashes.contains(h)).cloned().collect();
+    let removed: Vec<EntryHash> = prev_hashes.into_iter().filter(|h| !new_hashes.contains(h)).collect();
+    if added.is_empty() && removed.is_empty() {
+        return Ok(());
+    }
+
+    let _: bool = call_local_zome_method(read_attachment_zome, ATTACHMENT_REFERENCE_API_METHOD, AttachmentReferencesRequest {
+        referrer: address.as_ref().to_owned(),
+        added,
+        removed,
+    })?;
+    Ok(())
+}
+
+/// Keep the occurrence-ordered time index of events in sync with record data, so that
+/// events can be queried by when they happened rather than when they were recorded.
+/// Events without `has_point_in_time` or `has_beginning` are not indexed.
+///
+fn sync_occurrence_index(address: &EconomicEventAddress, new_entry: Option<&EntryData>, prev_entry: Option<&EntryData>) -> RecordAPIResult<()> {
+    let new_time = new_entry.and_then(EntryData::occurred_at);
+    let prev_time = prev_entry.and_then(EntryData::occurred_at);
+    if new_time == prev_time {
+        return Ok(());
+    }
+
+    if prev_time.is_some() {
+        let _removed: bool = call_local_zome_method(read_index_zome, "remove_economic_event_occurrence", ByAddress { address: address.to_owned() })?;
+    }
+    if let Some(occurred_at) = new_time {
+        let _indexed: bool = call_local_zome_method(read_index_zome, "record_economic_event_occurrence", AppendAddress {
+            address: address.to_owned(),
+            timestamp: occurred_at.into(),
+        })?;
+    }
+    Ok(())
+}
+
+/// Whether duplicate external references should be refused rather than resolved to the existing event
+fn read_reject_duplicate_external_references() -> RecordAPIResult<bool> {
+    let conf: DnaConfigSlice = hdk::prelude::dna_info()?.modifiers.properties.try_into()?;
+    Ok(conf.economic_event.reject_duplicate_external_references)
+}
+
 fn handle_create_economic_event_record<S>(entry_def_id: S, event: &EconomicEventCreateRequest, resource_address: Option<EconomicResourceAddress>,
 ) -> RecordAPIResult<(SignedActionHashed, EconomicEventAddress, EntryData)>
     where S: AsRef<str> + std::fmt::Display,
@@ -200,27 +512,49 @@ fn handle_create_economic_event_record<S>(entry_def_id: S, event: &EconomicEvent
 
     // handle link fields
     // :TODO: handle errors better https://github.com/h-REA/hREA/issues/264
-    let e1 = create_index!(economic_event.provider(event.provider), agent.economic_events_as_provider(&base_address))?;
-    let e2 = create_index!(economic_event.receiver(event.receiver), agent.economic_events_as_receiver(&base_address))?;
-    hdk::prelude::debug!("handle_create_economic_event::provider index {:?}", e1);
-    hdk::prelude::debug!("handle_create_economic_event::receiver index {:?}", e2);
-
-    if let EconomicEventCreateRequest { input_of: MaybeUndefined::Some(input_of), .. } = event {
-        let e = create_index!(economic_event.input_of(input_of), process.observed_inputs(&base_address));
-        hdk::prelude::debug!("handle_create_economic_event_record::input_of index {:?}", e);
-      };
-      if let EconomicEventCreateRequest { output_of: MaybeUndefined::Some(output_of), .. } = event {
-        let e = create_index!(economic_event.output_of(output_of), process.observed_outputs(&base_address));
-        hdk::prelude::debug!("handle_create_economic_event_record::output_of index {:?}", e);
-      };
-      if let EconomicEventCreateRequest { realization_of: MaybeUndefined::Some(realization_of), .. } = event {
-        let e = create_index!(economic_event.realization_of(realization_of), agreement.economic_events(&base_address));
-        hdk::prelude::debug!("handle_create_economic_event_record::realization_of index {:?}", e);
-    };
+    log_index_results("handle_create_economic_event_record", create_economic_event_indexes(&base_address, &entry_resp)?);
+    let e = create_economic_event_classified_as_index(&base_address, &entry_resp);
+    hdk::prelude::debug!("handle_create_economic_event_record::classified_as index {:?}", e);
+    sync_attachment_references(&base_address, Some(&entry_resp), None)?;
 
     Ok((meta, base_address, entry_resp))
 }
 
+/// Index writes made for an `EconomicEvent` when it is created, and re-run to resync its indexes.
+///
+fn create_economic_event_indexes(base_address: &EconomicEventAddress, entry: &EntryData) -> RecordAPIResult<RecordIndexResults>
+{
+    let mut results = vec![
+        ("provider", Ok(create_index!(economic_event.provider(&entry.provider), agent.economic_events_as_provider(base_address))?)),
+        ("receiver", Ok(create_index!(economic_event.receiver(&entry.receiver), agent.economic_events_as_receiver(base_address))?)),
+    ];
+
+    // processes must record the events they observe
+    if let Some(input_of) = &entry.input_of {
+        throw_if_index_update_failed(create_index!(economic_event.input_of(input_of), process.observed_inputs(base_address)))?;
+    }
+    if let Some(output_of) = &entry.output_of {
+        throw_if_index_update_failed(create_index!(economic_event.output_of(output_of), process.observed_outputs(base_address)))?;
+    }
+    if let Some(realization_of) = &entry.realization_of {
+        results.push(("realization_of", create_index!(economic_event.realization_of(realization_of), agreement.economic_events(base_address))));
+    }
+    // link any affected resources to this event so that we can pull all the events which affect any resource
+    for resource in entry.resource_inventoried_as.iter().chain(entry.to_resource_inventoried_as.iter()) {
+        results.push(("affects", create_index!(economic_event.affects(resource), economic_resource.affected_by(base_address))));
+    }
+    results.push(("period", update_string_index!(economic_event(base_address).period(entry.reporting_periods())<EventPeriodId>)));
+    if let Some(at_location) = &entry.at_location {
+        results.push(("at_location", create_index!(economic_event(base_address).at_location(at_location))));
+    }
+    if let Some(resource_conforms_to) = &entry.resource_conforms_to {
+        results.push(("resource_conforms_to", create_index!(economic_event.resource_conforms_to(resource_conforms_to), resource_specification.conforming_economic_events(base_address))));
+    }
+    sync_field_value_index(LinkTypes::FieldValueIndex, EXTERNAL_REFERENCE_INDEX, base_address, Some(entry), None, extract_external_reference)?;
+    sync_occurrence_index(base_address, Some(entry), None)?;
+    Ok(results)
+}
+
 /// Properties accessor for zome config.
 ///
 /// :TODO: should this be configurable as an array, to allow multiple inventories to be driven by the same event log?
@@ -262,6 +596,33 @@ fn handle_update_resource_inventory(
     )?)
 }
 
+/// Record the changes made by a newly written event in the on-hand balance counters of updated resources
+///
+fn handle_record_inventory_deltas(
+    event_address: &EconomicEventAddress,
+    resources: Vec<EconomicResourceAddress>,
+) -> RecordAPIResult<Vec<EconomicResourceAddress>>
+{
+    Ok(call_local_zome_method(
+        read_resource_zome,
+        INVENTORY_DELTAS_API_METHOD.to_string(),
+        InventoryDeltasPayload { event: event_address.to_owned(), resources },
+    )?)
+}
+
+/// Reverse the effect of an event on the on-hand balances of affected resources, prior to its deletion
+///
+fn handle_reverse_resource_inventory(
+    revision_id: &ActionHash,
+) -> RecordAPIResult<Vec<EconomicResourceAddress>>
+{
+    Ok(call_local_zome_method(
+        read_resource_zome,
+        INVENTORY_REVERSAL_API_METHOD.to_string(),
+        ByRevision { revision_id: revision_id.to_owned() },
+    )?)
+}
+
 /**
  * Create response from input DHT primitives
  *
@@ -316,6 +677,8 @@ pub fn construct_response_with_resource<'a>(
             triggered_by: event.triggered_by.to_owned(),
             realization_of: event.realization_of.to_owned(),
             in_scope_of: event.in_scope_of.to_owned(),
+            external_reference: event.external_reference.to_owned(),
+            attachments: event.attachments.to_owned(),
             fulfills: fulfillments.to_owned(),
             satisfies: satisfactions.to_owned(),
         },
@@ -323,6 +686,7 @@ pub fn construct_response_with_resource<'a>(
             Some(addr) => Some(construct_resource_response(&addr, &resource_meta, &resource, (contained_in, stage, state, contains))?),
             None => None,
         },
+        already_existed: false,
     })
 }
 
@@ -361,10 +725,13 @@ pub fn construct_response<'a>(
             triggered_by: e.triggered_by.to_owned(),
             realization_of: e.realization_of.to_owned(),
             in_scope_of: e.in_scope_of.to_owned(),
+            external_reference: e.external_reference.to_owned(),
+            attachments: e.attachments.to_owned(),
             fulfills: fulfillments.to_owned(),
             satisfies: satisfactions.to_owned(),
         },
         economic_resource: None,
+        already_existed: false,
     })
 }
 
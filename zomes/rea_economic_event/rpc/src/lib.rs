@@ -11,12 +11,17 @@ use holochain_serialized_bytes::prelude::*;
 use serde_maybe_undefined::MaybeUndefined;
 use vf_measurement::QuantityValue;
 use hdk_relay_pagination::PageInfo;
+use hdk_uuid_types::{ DnaHash, addressable_identifier };
 pub use vf_attributes_hdk::{
-    ActionHash, ByAddress, ByAction, ByRevision, RecordMeta, RevisionMeta,
+    ErrorCode, ErrorEnvelope,
+    PatchOp, PatchParams,
+    DeleteParams, DeletionReport,
+    ActionHash, ByAddress, ByAddresses, ByAddressResult, ByAction, ByRevision, RecordMeta, RevisionMeta,
+    RevisionHistoryParams, RevisionHistory,
     EconomicEventAddress,
     EconomicResourceAddress,
     ActionId,
-    ExternalURL,
+    ExternalURL, classification_index_paths,
     LocationAddress,
     AgentAddress,
     ProcessAddress,
@@ -30,54 +35,102 @@ pub use vf_attributes_hdk::{
     ProductBatchAddress,
     UnitId,
     DateTime, FixedOffset,
+    EntryHash,
+    AttachmentRef,
+    ReassignSpecificationParams,
 };
 
+// internal type for indexing against reporting period strings
+addressable_identifier!(EventPeriodId => EntryHash);
+
+// internal type for indexing against resource classification URLs
+addressable_identifier!(EventClassificationId => EntryHash);
+
 //---------------- EXTERNAL RECORD STRUCTURE ----------------
 
+/// Batch or lot metadata for resources tracked in lots.
+/// Resources with an `expiry_date` are indexed for querying by expiry.
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LotDetails {
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch_number: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub production_date: Option<DateTime<FixedOffset>>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiry_date: Option<DateTime<FixedOffset>>,
+}
+
 /// I/O struct to describe EconomicEvents, including all managed link fields
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     pub id: EconomicEventAddress,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     pub meta: RecordMeta,
     pub action: ActionId,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub note: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "input_of"))]
     pub input_of: Option<ProcessAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "output_of"))]
     pub output_of: Option<ProcessAddress>,
     pub provider: AgentAddress,
     pub receiver: AgentAddress,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_inventoried_as"))]
     pub resource_inventoried_as: Option<EconomicResourceAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "to_resource_inventoried_as"))]
     pub to_resource_inventoried_as: Option<EconomicResourceAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_classified_as"))]
     pub resource_classified_as: Option<Vec<ExternalURL>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_conforms_to"))]
     pub resource_conforms_to: Option<ResourceSpecificationAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_quantity"))]
     pub resource_quantity: Option<QuantityValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "effort_quantity"))]
     pub effort_quantity: Option<QuantityValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_beginning"))]
     pub has_beginning: Option<DateTime<FixedOffset>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_end"))]
     pub has_end: Option<DateTime<FixedOffset>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_point_in_time"))]
     pub has_point_in_time: Option<DateTime<FixedOffset>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "at_location"))]
     pub at_location: Option<LocationAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "agreed_in"))]
     pub agreed_in: Option<ExternalURL>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "realization_of"))]
     pub realization_of: Option<AgreementAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "triggered_by"))]
     pub triggered_by: Option<EconomicEventAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "in_scope_of"))]
     pub in_scope_of: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "external_reference"))]
+    pub external_reference: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attachments: Option<Vec<AttachmentRef>>,
 
     // LINK FIELDS
     #[serde(default)]
@@ -95,37 +148,50 @@ pub struct Response {
 #[serde(rename_all = "camelCase")]
 pub struct ResourceResponse {
     pub id: EconomicResourceAddress,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     pub meta: RecordMeta,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "conforms_to"))]
     pub conforms_to: Option<ResourceSpecificationAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "classified_as"))]
     pub classified_as: Option<Vec<ExternalURL>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "tracking_identifier"))]
     pub tracking_identifier: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub lot: Option<ProductBatchAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "lot_details"))]
+    pub lot_details: Option<LotDetails>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub image: Option<ExternalURL>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "accounting_quantity"))]
     pub accounting_quantity: Option<QuantityValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "onhand_quantity"))]
     pub onhand_quantity: Option<QuantityValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "unit_of_effort"))]
     pub unit_of_effort: Option<UnitId>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "contained_in"))]
     pub contained_in: Option<EconomicResourceAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub stage: Option<ProcessSpecificationAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub state: Option<ActionId>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "current_location"))]
     pub current_location: Option<LocationAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub note: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "primary_accountable"))]
     pub primary_accountable: Option<AgentAddress>,
 
     // query edges
@@ -145,6 +211,10 @@ pub struct ResponseData {
     pub economic_event: Response,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub economic_resource: Option<ResourceResponse>,
+    /// Set when a create request matched the `external_reference` of an existing event,
+    /// which is returned in place of creating a duplicate.
+    #[serde(default)]
+    pub already_existed: bool,
 }
 
 /// I/O struct to describe what is returned outside the gateway
@@ -204,42 +274,67 @@ pub struct CreateRequest {
     #[serde(default)]
     pub note: MaybeUndefined<String>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "input_of"))]
     pub input_of: MaybeUndefined<ProcessAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "output_of"))]
     pub output_of: MaybeUndefined<ProcessAddress>,
     pub provider: AgentAddress,
     pub receiver: AgentAddress,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_inventoried_as"))]
     pub resource_inventoried_as: MaybeUndefined<EconomicResourceAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "to_resource_inventoried_as"))]
     pub to_resource_inventoried_as: MaybeUndefined<EconomicResourceAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_classified_as"))]
     pub resource_classified_as: MaybeUndefined<Vec<ExternalURL>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_conforms_to"))]
     pub resource_conforms_to: MaybeUndefined<ResourceSpecificationAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_quantity"))]
     pub resource_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "effort_quantity"))]
     pub effort_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_beginning"))]
     pub has_beginning: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_end"))]
     pub has_end: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_point_in_time"))]
     pub has_point_in_time: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "at_location"))]
     pub at_location: MaybeUndefined<LocationAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "agreed_in"))]
     pub agreed_in: MaybeUndefined<ExternalURL>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "realization_of"))]
     pub realization_of: MaybeUndefined<AgreementAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "triggered_by"))]
     pub triggered_by: MaybeUndefined<EconomicEventAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "in_scope_of"))]
     pub in_scope_of: MaybeUndefined<Vec<String>>,
+    /// Stable identifier of the event in an external source system (eg. `scale-7:reading:89123`),
+    /// used to detect duplicate imports. Cannot be changed once set.
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "external_reference"))]
+    pub external_reference: MaybeUndefined<String>,
+    /// Files attached to the event, previously stored via the attachment zome's `upload_attachment`
+    #[serde(default)]
+    pub attachments: MaybeUndefined<Vec<AttachmentRef>>,
 
     // :SHONK: internal field used in updating linked resource quantities
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "target_inventory_type"))]
     pub target_inventory_type: Option<ResourceInventoryType>,
 }
 
@@ -271,6 +366,10 @@ impl<'a> CreateRequest {
     pub fn get_realization_of(&'a self) -> MaybeUndefined<AgreementAddress> {
         self.realization_of.to_owned()
     }
+
+    pub fn get_external_reference(&'a self) -> Option<String> {
+        self.external_reference.to_owned().to_option()
+    }
 }
 
 // used in EconomicResource API
@@ -280,16 +379,23 @@ pub struct ResourceCreateRequest {
     #[serde(default)]
     pub name: MaybeUndefined<String>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "conforms_to"))]
     pub conforms_to: MaybeUndefined<ResourceSpecificationAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "tracking_identifier"))]
     pub tracking_identifier: MaybeUndefined<String>,
     #[serde(default)]
     pub lot: MaybeUndefined<ProductBatchAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "lot_details"))]
+    pub lot_details: MaybeUndefined<LotDetails>,
+    #[serde(default)]
     pub image: MaybeUndefined<ExternalURL>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "contained_in"))]
     pub contained_in: MaybeUndefined<EconomicResourceAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "current_location"))]
     pub current_location: MaybeUndefined<LocationAddress>,
     #[serde(default)]
     pub note: MaybeUndefined<String>,
@@ -314,17 +420,24 @@ pub struct CreateParams {
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateRequest {
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     #[serde(default)]
     pub note: MaybeUndefined<String>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "agreed_in"))]
     pub agreed_in: MaybeUndefined<ExternalURL>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "realization_of"))]
     pub realization_of: MaybeUndefined<AgreementAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "triggered_by"))]
     pub triggered_by: MaybeUndefined<EconomicEventAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "in_scope_of"))]
     pub in_scope_of: MaybeUndefined<Vec<String>>,
+    #[serde(default)]
+    pub attachments: MaybeUndefined<Vec<AttachmentRef>>,
 }
 
 impl<'a> UpdateRequest {
@@ -354,4 +467,45 @@ pub struct QueryParams {
     pub affects: Option<EconomicResourceAddress>,
     pub provider: Option<AgentAddress>,
     pub receiver: Option<AgentAddress>,
+    pub at_location: Option<LocationAddress>,
+    pub resource_conforms_to: Option<ResourceSpecificationAddress>,
+
+    // reporting period path, eg. `period.2024-W07` or `period.2024-03`
+    pub period: Option<String>,
+    pub period_internal: Option<EventPeriodId>,
+
+    // resource classification URL, @see vf_attributes_hdk::classification_index_paths
+    pub classified_as: Option<String>,
+    pub classified_as_internal: Option<EventClassificationId>,
+}
+
+/// Parameters for querying events created within a period of time, filtered by their fields.
+/// `agent` matches either the provider or receiver. `after` is the `nextCursor` of a previous page.
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeRangeQueryParams {
+    pub from: DateTime<FixedOffset>,
+    pub until: DateTime<FixedOffset>,
+    #[serde(default)]
+    pub agent: Option<AgentAddress>,
+    #[serde(default)]
+    pub action: Option<ActionId>,
+    #[serde(default)]
+    pub after: Option<EntryHash>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ByExternalReference {
+    pub reference: String,
+}
+
+#[cfg(all(test, feature = "snake_case_aliases"))]
+mod tests {
+    use super::*;
+
+    vf_attributes_hdk::snake_case_alias_tests!(Response, ResourceResponse, CreateRequest, ResourceCreateRequest, UpdateRequest);
 }
@@ -7,6 +7,7 @@
  * @package hREA
  */
 use hdk::prelude::*;
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
 
 use hc_zome_dna_auth_resolver_core::AvailableCapability;
 use hdk_records::{
@@ -28,6 +29,8 @@ use vf_attributes_hdk::{
     ResourceSpecificationAddress,
     AgreementAddress,
     DateTime, FixedOffset,
+    AttachmentRef,
+    validate_attachment_list,
 };
 use vf_actions::{ validate_flow_action, validate_move_inventories };
 use hc_zome_rea_economic_event_rpc::*;
@@ -52,6 +55,18 @@ pub struct EconomicEventZomeConfig {
     pub process_index_zome: Option<String>,
     pub agreement_index_zome: Option<String>,
     pub agent_index_zome: Option<String>,
+    pub resource_specification_index_zome: Option<String>,
+    // zome IDs of dependent record zomes, used for cascading deletes
+    pub fulfillment_zome: Option<String>,
+    pub satisfaction_zome: Option<String>,
+    // zome ID of the network settings zome, used to read enforcement flags
+    pub network_settings_zome: Option<String>,
+    // zome ID of the attachment zome, which tracks the attachments referenced by each event
+    pub attachment_zome: Option<String>,
+    // when set, creating an event with an already-recorded `external_reference` is an error
+    // rather than returning the existing event
+    #[serde(default)]
+    pub reject_duplicate_external_references: bool,
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -78,6 +93,10 @@ pub struct EntryData {
     pub triggered_by: Option<EconomicEventAddress>,
     pub in_scope_of: Option<Vec<String>>,
     pub note: Option<String>,
+    #[serde(default)]
+    pub external_reference: Option<String>,
+    #[serde(default)]
+    pub attachments: Option<Vec<AttachmentRef>>,
     pub _nonce: Bytes,
 }
 
@@ -102,6 +121,119 @@ impl EntryData {
         }
         Ok(())
     }
+
+    pub fn validate_attachments(&self) -> Result<(), String> {
+        validate_attachment_list("EconomicEvent attachments", &self.attachments)
+    }
+
+    /// Content addresses of all attachments referenced by this event
+    ///
+    pub fn attachment_hashes(&self) -> Vec<EntryHash> {
+        self.attachments.iter().flatten().map(|a| a.hash.to_owned()).collect()
+    }
+
+    /// Time at which this event occurred: `has_point_in_time` or else `has_beginning`.
+    ///
+    pub fn occurred_at(&self) -> Option<DateTime<FixedOffset>> {
+        self.has_point_in_time.or(self.has_beginning)
+    }
+
+    /// Reporting period paths this event should be indexed under, derived from
+    /// `occurred_at`. @see `period_paths`
+    ///
+    pub fn reporting_periods(&self) -> Vec<String> {
+        match self.occurred_at() {
+            Some(time) => period_paths(&time),
+            None => vec![],
+        }
+    }
+}
+
+/// Canonical form of an external reference for uniqueness checks- surrounding whitespace
+/// and letter case are not significant.
+///
+pub fn normalize_external_reference(reference: &str) -> String {
+    reference.trim().to_lowercase()
+}
+
+/// Root component of reporting period index paths
+pub const PERIOD_PATH_ROOT: &str = "period";
+
+/// Determine the ISO week (eg. `period.2024-W07`) and calendar month (eg. `period.2024-03`)
+/// reporting period paths for a point in time. Periods are bucketed in the time's own offset.
+///
+pub fn period_paths(time: &DateTime<FixedOffset>) -> Vec<String> {
+    vec![week_period_path(time), month_period_path(time)]
+}
+
+/// Determine the reporting period paths for a point in time as observed from `report_offset`,
+/// rather than from the offset the time was recorded with.
+///
+pub fn period_paths_in(time: &DateTime<FixedOffset>, report_offset: &FixedOffset) -> Vec<String> {
+    period_paths(&time.with_timezone(report_offset))
+}
+
+/// Determine the reporting periods immediately before and after `period`, of the same kind.
+///
+/// Offsets never differ by more than a day, so events bucketed in their own offset which fall
+/// within `period` in some other offset are always indexed under one of these or `period` itself.
+///
+pub fn adjacent_periods(period: &str) -> Result<Vec<String>, String> {
+    let invalid = || format!("{} is not a valid reporting period", period);
+    let (year, spec) = period.strip_prefix(PERIOD_PATH_ROOT)
+        .and_then(|p| p.strip_prefix('.'))
+        .and_then(|p| p.split_once('-'))
+        .ok_or_else(invalid)?;
+    let year: i32 = year.parse().map_err(|_| invalid())?;
+
+    match spec.strip_prefix('W') {
+        Some(week) => {
+            let start = NaiveDate::from_isoywd_opt(year, week.parse().map_err(|_| invalid())?, Weekday::Mon)
+                .ok_or_else(invalid)?;
+            Ok(vec![
+                week_period_path(&(start - Duration::days(7))),
+                week_period_path(&(start + Duration::days(7))),
+            ])
+        },
+        None => {
+            let start = NaiveDate::from_ymd_opt(year, spec.parse().map_err(|_| invalid())?, 1)
+                .ok_or_else(invalid)?;
+            Ok(vec![
+                month_period_path(&(start - Duration::days(1))),
+                month_period_path(&(start + Duration::days(31)).with_day(1).ok_or_else(invalid)?),
+            ])
+        },
+    }
+}
+
+/// Parse a UTC offset for reporting purposes, in the form `+05:30`, `-08`, or `Z`.
+///
+pub fn parse_utc_offset(offset: &str) -> Result<FixedOffset, String> {
+    let invalid = || format!("{} is not a valid UTC offset", offset);
+    if offset == "Z" {
+        return FixedOffset::east_opt(0).ok_or_else(invalid);
+    }
+    let (sign, digits) = match offset.chars().next() {
+        Some('+') => (1, &offset[1..]),
+        Some('-') => (-1, &offset[1..]),
+        _ => return Err(invalid()),
+    };
+    let (hours, minutes) = digits.split_once(':').unwrap_or((digits, "00"));
+    let hours: i32 = hours.parse().map_err(|_| invalid())?;
+    let minutes: i32 = minutes.parse().map_err(|_| invalid())?;
+    if minutes >= 60 {
+        return Err(invalid());
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).ok_or_else(invalid)
+}
+
+fn week_period_path<T: Datelike>(time: &T) -> String {
+    let week = time.iso_week();
+    format!("{}.{}-W{:02}", PERIOD_PATH_ROOT, week.year(), week.week())
+}
+
+fn month_period_path<T: Datelike>(time: &T) -> String {
+    format!("{}.{}-{:02}", PERIOD_PATH_ROOT, time.year(), time.month())
 }
 
 generate_record_entry!(EntryData, EconomicEventAddress, EntryStorage);
@@ -138,7 +270,10 @@ impl TryFrom<AvailableCapability> for EntryTypes {
 pub enum LinkTypes {
     // relates to dna-auth-resolver mixin
     // and remote authorizations
-    AvailableCapability
+    AvailableCapability,
+    // exact-match lookup of records by field value
+    // @see hdk_records::field_indexes
+    FieldValueIndex,
 }
 
 //---------------- CREATE ----------------
@@ -171,6 +306,10 @@ impl TryFrom<CreateRequest> for EntryData {
             triggered_by: e.triggered_by.into(),
             at_location: e.at_location.into(),
             in_scope_of: e.in_scope_of.into(),
+            external_reference: e.external_reference.to_option()
+                .map(|r| r.trim().to_string())
+                .filter(|r| !r.is_empty()),
+            attachments: e.attachments.into(),
             _nonce: random_bytes(32)?,
         })
     }
@@ -196,13 +335,158 @@ impl Updateable<UpdateRequest> for EntryData {
             has_beginning: self.has_beginning.to_owned(),
             has_end: self.has_end.to_owned(),
             has_point_in_time: self.has_point_in_time.to_owned(),
-            agreed_in: self.agreed_in.to_owned(),
-            triggered_by: self.triggered_by.to_owned(),
-            realization_of: self.realization_of.to_owned(),
+            agreed_in: if e.agreed_in == MaybeUndefined::Undefined { self.agreed_in.to_owned() } else { e.agreed_in.to_owned().into() },
+            triggered_by: if e.triggered_by == MaybeUndefined::Undefined { self.triggered_by.to_owned() } else { e.triggered_by.to_owned().into() },
+            realization_of: if e.realization_of == MaybeUndefined::Undefined { self.realization_of.to_owned() } else { e.realization_of.to_owned().into() },
             at_location: self.at_location.to_owned(),
             in_scope_of: if e.in_scope_of== MaybeUndefined::Undefined { self.in_scope_of.to_owned() } else { e.in_scope_of.to_owned().into() },
             note: if e.note== MaybeUndefined::Undefined { self.note.to_owned() } else { e.note.to_owned().into() },
+            external_reference: self.external_reference.to_owned(),
+            attachments: if e.attachments == MaybeUndefined::Undefined { self.attachments.to_owned() } else { e.attachments.to_owned().into() },
             _nonce: self._nonce.to_owned(),
         })
     }
 }
+
+/// Re-points the event at a different `ResourceSpecification` when specifications are merged.
+/// This is the only means of changing `resource_conforms_to` after creation.
+impl Updateable<ReassignSpecificationParams<EconomicEventAddress>> for EntryData {
+    fn update_with(&self, e: ReassignSpecificationParams<EconomicEventAddress>) -> RecordAPIResult<EntryData> {
+        Ok(EntryData {
+            resource_conforms_to: Some(e.to),
+            ..self.to_owned()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(time: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(time).unwrap()
+    }
+
+    #[test]
+    fn test_period_paths() {
+        assert_eq!(period_paths(&parse("2024-02-14T10:00:00Z")), vec!["period.2024-W07", "period.2024-02"]);
+        // ISO week-numbering year differs from calendar year around new year
+        assert_eq!(period_paths(&parse("2021-01-01T00:00:00Z")), vec!["period.2020-W53", "period.2021-01"]);
+        // periods are bucketed in the event's own offset
+        assert_eq!(period_paths(&parse("2024-03-31T23:30:00-02:00")), vec!["period.2024-W13", "period.2024-03"]);
+    }
+
+    #[test]
+    fn test_period_paths_in_report_offset() {
+        let time = parse("2024-03-31T23:30:00-02:00");
+        assert_eq!(period_paths_in(&time, &FixedOffset::east_opt(0).unwrap()), vec!["period.2024-W14", "period.2024-04"]);
+        assert_eq!(period_paths_in(&time, &parse_utc_offset("+05:30").unwrap()), vec!["period.2024-W14", "period.2024-04"]);
+        assert_eq!(period_paths_in(&time, &parse_utc_offset("-02:00").unwrap()), period_paths(&time));
+    }
+
+    #[test]
+    fn test_adjacent_periods() {
+        assert_eq!(adjacent_periods("period.2024-W07").unwrap(), vec!["period.2024-W06", "period.2024-W08"]);
+        assert_eq!(adjacent_periods("period.2021-W01").unwrap(), vec!["period.2020-W53", "period.2021-W02"]);
+        assert_eq!(adjacent_periods("period.2024-01").unwrap(), vec!["period.2023-12", "period.2024-02"]);
+        assert_eq!(adjacent_periods("period.2024-12").unwrap(), vec!["period.2024-11", "period.2025-01"]);
+        assert!(adjacent_periods("period.2024-13").is_err());
+        assert!(adjacent_periods("2024-W07").is_err());
+    }
+
+    #[test]
+    fn test_parse_utc_offset() {
+        assert_eq!(parse_utc_offset("Z").unwrap().local_minus_utc(), 0);
+        assert_eq!(parse_utc_offset("+05:30").unwrap().local_minus_utc(), 19800);
+        assert_eq!(parse_utc_offset("-03:30").unwrap().local_minus_utc(), -12600);
+        assert_eq!(parse_utc_offset("+05:45").unwrap().local_minus_utc(), 20700);
+        assert_eq!(parse_utc_offset("-08").unwrap().local_minus_utc(), -28800);
+        assert!(parse_utc_offset("05:30").is_err());
+        assert!(parse_utc_offset("+05:60").is_err());
+        assert!(parse_utc_offset("+25:00").is_err());
+    }
+
+    #[derive(Serialize, Deserialize, SerializedBytes, Debug, PartialEq)]
+    struct TimedEntry {
+        time: DateTime<FixedOffset>,
+    }
+
+    #[test]
+    fn test_normalize_external_reference() {
+        assert_eq!(normalize_external_reference(" Scale-7:Reading:89123\n"), "scale-7:reading:89123");
+        assert_eq!(normalize_external_reference("scale-7:reading:89123"), normalize_external_reference("SCALE-7:READING:89123"));
+    }
+
+    #[test]
+    fn test_offsets_preserved_through_storage() {
+        for time in [
+            "2024-03-01T09:00:00+00:00",
+            "2024-03-01T09:00:00+05:30",
+            "2024-03-01T09:00:00-03:30",
+            "2024-03-01T09:00:00+05:45",
+            "2024-03-01T09:00:00+14:00",
+            "2024-03-01T09:00:00-11:00",
+        ] {
+            let entry = TimedEntry { time: parse(time) };
+            let stored = SerializedBytes::try_from(&entry).unwrap();
+            let read = TimedEntry::try_from(stored).unwrap();
+
+            assert_eq!(read, entry);
+            assert_eq!(read.time.offset(), entry.time.offset(), "original offset should be retained");
+            assert_eq!(read.time.to_rfc3339(), time, "local time should be returned verbatim");
+        }
+    }
+
+    fn dna() -> DnaHash {
+        DnaHash::from_raw_36(vec![0xdb; 36])
+    }
+
+    fn hash(n: u8) -> EntryHash {
+        EntryHash::from_raw_36(vec![n; 36])
+    }
+
+    fn url(path: &str) -> ExternalURL {
+        ExternalURL(format!("http://example.com/{}", path))
+    }
+
+    fn attachment(n: u8, filename: &str) -> AttachmentRef {
+        AttachmentRef { hash: hash(n), mime: "image/jpeg".to_string(), filename: filename.to_string(), size: 1024 }
+    }
+
+    fn entry() -> EntryData {
+        EntryData {
+            action: ActionId("produce".to_string()),
+            provider: AgentAddress(dna(), hash(1)),
+            receiver: AgentAddress(dna(), hash(2)),
+            input_of: None,
+            output_of: Some(ProcessAddress(dna(), hash(3))),
+            resource_inventoried_as: Some(EconomicResourceAddress(dna(), hash(4))),
+            to_resource_inventoried_as: None,
+            resource_classified_as: Some(vec![url("classifications/bicycle")]),
+            resource_conforms_to: Some(ResourceSpecificationAddress(dna(), hash(5))),
+            resource_quantity: Some(QuantityValue::new(1.0, None)),
+            effort_quantity: None,
+            has_beginning: None,
+            has_end: None,
+            has_point_in_time: Some(parse("2024-02-14T10:00:00Z")),
+            at_location: Some(LocationAddress(dna(), hash(6))),
+            agreed_in: Some(url("agreements/1")),
+            realization_of: Some(AgreementAddress(dna(), hash(7))),
+            triggered_by: Some(EconomicEventAddress(dna(), hash(8))),
+            in_scope_of: Some(vec!["scope".to_string()]),
+            note: Some("a note".to_string()),
+            external_reference: Some("ref-1".to_string()),
+            attachments: Some(vec![attachment(9, "receipt.jpg")]),
+            _nonce: Bytes::from(vec![0; 32]),
+        }
+    }
+
+    hdk_records::update_semantics_tests!(EntryData, UpdateRequest, entry(), {
+        "note" => "updated note",
+        "agreedIn" => url("agreements/2"),
+        "realizationOf" => AgreementAddress(dna(), hash(17)),
+        "triggeredBy" => EconomicEventAddress(dna(), hash(18)),
+        "inScopeOf" => vec!["updated scope".to_string()],
+        "attachments" => vec![attachment(19, "invoice.pdf")],
+    });
+}
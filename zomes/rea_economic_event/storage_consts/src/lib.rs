@@ -7,5 +7,10 @@
  */
 pub const EVENT_ENTRY_TYPE: &str = "economic_event";
 
+// remote_auth permission ID for batch reads of events from other DNAs
+pub const EVENT_READ_BATCH_API_METHOD: &str = "read_economic_events";
+
 pub const INVENTORY_CREATION_API_METHOD: &str = "_internal_create_inventory";
 pub const INVENTORY_UPDATE_API_METHOD: &str = "_internal_update_inventory";
+pub const INVENTORY_DELTAS_API_METHOD: &str = "_internal_record_inventory_deltas";
+pub const INVENTORY_REVERSAL_API_METHOD: &str = "_internal_reverse_inventory";
@@ -9,9 +9,15 @@ pub trait API {
         event: CreateRequest, new_inventoried_resource: Option<ResourceCreateRequest>
     ) -> RecordAPIResult<ResponseData>;
     fn get_economic_event(address: EconomicEventAddress) -> RecordAPIResult<ResponseData>;
+    fn get_economic_events(addresses: Vec<EconomicEventAddress>) -> RecordAPIResult<Vec<ByAddressResult<EconomicEventAddress, ResponseData>>>;
+    fn get_economic_event_by_external_reference(reference: String) -> RecordAPIResult<ResponseData>;
+    fn resync_economic_event_indexes(address: EconomicEventAddress) -> RecordAPIResult<bool>;
     fn get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>;
+    fn get_economic_event_revision_history(params: RevisionHistoryParams<EconomicEventAddress>) -> RecordAPIResult<RevisionHistory<ResponseData>>;
     fn update_economic_event(event: UpdateRequest) -> RecordAPIResult<ResponseData>;
-    fn delete_economic_event(revision_id: ActionHash) -> RecordAPIResult<bool>;
+    fn patch_economic_event(address: EconomicEventAddress, ops: Vec<PatchOp>) -> RecordAPIResult<ResponseData>;
+    fn delete_economic_event(revision_id: ActionHash, cascade: bool) -> RecordAPIResult<DeletionReport>;
+    fn reassign_resource_specification(params: ReassignSpecificationParams<EconomicEventAddress>) -> RecordAPIResult<bool>;
 }
 
 /// Macro to programatically and predictably bind an `API` implementation to a
@@ -36,19 +42,51 @@ macro_rules! declare_economic_event_zome_api {
             Ok(<$zome_api>::get_economic_event(address)?)
         }
 
+        #[hdk_extern]
+        fn get_economic_events(ByAddresses { addresses }: ByAddresses<EconomicEventAddress>) -> ExternResult<Vec<ByAddressResult<EconomicEventAddress, ResponseData>>> {
+            Ok(<$zome_api>::get_economic_events(addresses)?)
+        }
+
+        #[hdk_extern]
+        fn get_economic_event_by_external_reference(ByExternalReference { reference }: ByExternalReference) -> ExternResult<ResponseData> {
+            Ok(<$zome_api>::get_economic_event_by_external_reference(reference)?)
+        }
+
+        // rebuilds the indexes of an event, used by `reindex_all` in the index zome
+        #[hdk_extern]
+        fn resync_record_indexes(ByAddress { address }: ByAddress<EconomicEventAddress>) -> ExternResult<bool> {
+            Ok(<$zome_api>::resync_economic_event_indexes(address)?)
+        }
+
         #[hdk_extern]
         fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
             Ok(<$zome_api>::get_revision(revision_id)?)
         }
 
+        #[hdk_extern]
+        fn get_revision_history(params: RevisionHistoryParams<EconomicEventAddress>) -> ExternResult<RevisionHistory<ResponseData>> {
+            Ok(<$zome_api>::get_economic_event_revision_history(params)?)
+        }
+
         #[hdk_extern]
         fn update_economic_event(UpdateParams { event }: UpdateParams) -> ExternResult<ResponseData> {
             Ok(<$zome_api>::update_economic_event(event)?)
         }
 
         #[hdk_extern]
-        fn delete_economic_event(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
-            Ok(<$zome_api>::delete_economic_event(revision_id)?)
+        fn patch_record(PatchParams { address, ops }: PatchParams<EconomicEventAddress>) -> ExternResult<ResponseData> {
+            Ok(<$zome_api>::patch_economic_event(address, ops)?)
+        }
+
+        #[hdk_extern]
+        fn delete_economic_event(DeleteParams { revision_id, cascade }: DeleteParams) -> ExternResult<DeletionReport> {
+            Ok(<$zome_api>::delete_economic_event(revision_id, cascade)?)
+        }
+
+        // used by the specification DNA when merging `ResourceSpecification`s
+        #[hdk_extern]
+        fn reassign_resource_specification(params: ReassignSpecificationParams<EconomicEventAddress>) -> ExternResult<bool> {
+            Ok(<$zome_api>::reassign_resource_specification(params)?)
         }
     };
 }
@@ -6,8 +6,9 @@
  */
 use hdk_semantic_indexes_zome_derive::index_zome;
 use hc_zome_rea_economic_event_rpc::*;
+use hc_zome_rea_economic_event_storage::{period_paths_in, adjacent_periods, parse_utc_offset};
 
-#[index_zome]
+#[index_zome(time_indexes="occurred")]
 struct EconomicEvent {
     input_of: Local<process, observed_inputs>,
     output_of: Local<process, observed_outputs>,
@@ -19,4 +20,129 @@ struct EconomicEvent {
     affects: Local<economic_resource, affected_by>,
     provider: Local<agent, economic_events_as_provider>,
     receiver: Local<agent, economic_events_as_receiver>,
+    at_location: Local<location, economic_events_at>,
+    resource_conforms_to: Local<resource_specification, conforming_economic_events>,
+
+    // query events by reporting period
+    period: Local<economic_event, period_internal>::String,
+    // :SHONK: redundant loopback index, @see zome_idx_agent
+    period_internal: Local<economic_event, period>,
+
+    // query events by resource classification URL
+    classified_as: Local<economic_event, classified_as_internal>::String,
+    // :SHONK: redundant loopback index, @see zome_idx_agent
+    classified_as_internal: Local<economic_event, classified_as>,
+}
+
+//---------------- REPORTING PERIODS ----------------
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PeriodQueryParams {
+    // reporting period path, eg. `period.2024-W07` or `period.2024-03`
+    pub period: String,
+    // UTC offset to bucket events in, eg. `+05:30` or `Z`
+    pub report_timezone: String,
+}
+
+/// Query events falling within a reporting period as observed from `report_timezone`.
+///
+/// The `period` filter of `query_economic_events` buckets events in the offset they were
+/// recorded with. Here events indexed under the requested period and its neighbours are
+/// re-bucketed in the report's offset instead.
+///
+#[hdk_extern]
+fn query_economic_events_in_period(PeriodQueryParams { period, report_timezone }: PeriodQueryParams) -> ExternResult<QueryResults> {
+    register_time_indexes(&TIME_INDEXES);
+    throw_if_query_not_permitted(read_query_access, read_agent_zome, read_network_settings_zome)?;
+
+    let report_offset = parse_utc_offset(&report_timezone).map_err(|e| wasm_error!(WasmErrorInner::Guest(e)))?;
+    let mut candidate_periods = vec![period.to_owned()];
+    candidate_periods.append(&mut adjacent_periods(&period).map_err(|e| wasm_error!(WasmErrorInner::Guest(e)))?);
+
+    let mut entries: Vec<RecordAPIResult<ResponseData>> = vec![];
+    for candidate in candidate_periods.iter() {
+        let index_anchor_path = Path::from(candidate);
+        let index_anchor_id = EventPeriodId::new(dna_info()?.hash, index_anchor_path.path_entry_hash()?);
+        entries.append(&mut query_index::<ResponseData, EconomicEventAddress, _,_,_,_,_,_>(
+            &index_anchor_id,
+            &"economic_event_period_internal",
+            &local_time_index_id(),
+            &read_index_target_zome,
+            &QUERY_FN_NAME,
+            false,
+        )?);
+    }
+
+    let in_period: Vec<RecordAPIResult<ResponseData>> = entries.into_iter()
+        .filter(|r| match r {
+            Ok(record) => match record.economic_event.has_point_in_time.or(record.economic_event.has_beginning) {
+                Some(time) => period_paths_in(&time, &report_offset).contains(&period),
+                None => false,
+            },
+            Err(_) => true,
+        })
+        .collect();
+
+    Ok(handle_list_output(in_period.as_slice(), 0)?)
+}
+
+//---------------- FILTERED TIME RANGES ----------------
+
+const OCCURRED_TIME_INDEX_NAME: &str = "occurred";
+
+fn occurred_time_index_id() -> String {
+    time_index_name(TIME_INDEX_RECORD_TYPE, OCCURRED_TIME_INDEX_NAME)
+}
+
+#[hdk_extern]
+fn record_economic_event_occurrence(AppendAddress { address, timestamp }: AppendAddress<EconomicEventAddress>) -> ExternResult<bool> {
+    register_time_indexes(&TIME_INDEXES);
+    throw_if_index_read_only(read_index_read_only)?;
+    Ok(append_to_time_index(&occurred_time_index_id(), &address, timestamp)?)
+}
+
+#[hdk_extern]
+fn remove_economic_event_occurrence(ByAddress { address }: ByAddress<EconomicEventAddress>) -> ExternResult<bool> {
+    register_time_indexes(&TIME_INDEXES);
+    throw_if_index_read_only(read_index_read_only)?;
+    Ok(remove_from_time_index(&occurred_time_index_id(), &address)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TimeRangeQueryResults {
+    pub results: QueryResults,
+    pub next_cursor: Option<EntryHash>,
+}
+
+/// Query events which occurred within a period of time and involve the given agent and/or action,
+/// most recent first. Events are ordered by `hasPointInTime`, or else `hasBeginning`; events with
+/// neither are never returned. Every event occurring within the period is read in order to filter it.
+///
+#[hdk_extern]
+fn query_economic_events_between(params: TimeRangeQueryParams) -> ExternResult<TimeRangeQueryResults> {
+    register_time_indexes(&TIME_INDEXES);
+    throw_if_query_not_permitted(read_query_access, read_agent_zome, read_network_settings_zome)?;
+
+    let TimeRangeQueryParams { from, until, agent, action, after, limit } = params;
+    let matches = query_time_index_where::<ResponseData, EconomicEventAddress, _,_,_,_>(
+        &read_index_target_zome,
+        &QUERY_FN_NAME,
+        &occurred_time_index_id(),
+        from.into(),
+        until.into(),
+        |record: &ResponseData| {
+            let event = &record.economic_event;
+            agent.as_ref().map_or(true, |a| &event.provider == a || &event.receiver == a)
+                && action.as_ref().map_or(true, |a| &event.action == a)
+        },
+        after,
+        limit.unwrap_or(PAGE_SIZE),
+    )?;
+
+    Ok(TimeRangeQueryResults {
+        results: handle_list_output(matches.records.as_slice(), 0)?,
+        next_cursor: matches.next_cursor,
+    })
 }
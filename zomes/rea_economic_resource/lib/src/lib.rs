@@ -6,8 +6,10 @@
  *
  * @package hREA
  */
+use std::convert::TryInto;
 use paste::paste;
 use hdk_records::{
+    generate_patch_builder,
     DataIntegrityError, RecordAPIResult, MaybeUndefined,
     records::{
         get_latest_action_hash,
@@ -17,8 +19,20 @@ use hdk_records::{
         update_record,
     },
     metadata::read_revision_metadata_abbreviated,
-    EntryHash, SignedActionHashed,
+    history::{read_edit_log, read_revision_window},
+    projections::{project_record, redact_for_role},
+    entries::get_entry_by_address,
+    rpc::{call_local_zome_method, call_zome_method},
+    EntryHash, SignedActionHashed, OtherCellResult,
+    RecordRole,
+    log_warn,
 };
+use hdk::prelude::{
+    call_info, create_entry, create_link, delete_link, dna_info, get_links, hash_entry, sys_time,
+    LinkTag, SerializedBytes, UnsafeBytes,
+};
+use vf_measurement::{QuantityValue, subtract};
+use hdk_semantic_indexes_zome_rpc::AppendAddress;
 use hdk_semantic_indexes_client_lib::*;
 
 use vf_attributes_hdk::{
@@ -32,6 +46,8 @@ pub use hc_zome_rea_economic_resource_storage_consts::*;
 pub use hc_zome_rea_economic_event_storage_consts::{EVENT_ENTRY_TYPE};
 pub use hc_zome_rea_process_storage_consts::{PROCESS_ENTRY_TYPE};
 pub use hc_zome_rea_resource_specification_storage_consts::{ECONOMIC_RESOURCE_SPECIFICATION_ENTRY_TYPE};
+use hc_zome_rea_agent_storage_consts::AGENT_REPRESENTATION_API_METHOD;
+use hc_zome_rea_agent_rpc::AgentRepresentationParams;
 
 use hc_zome_rea_economic_resource_zome_api::*;
 use hc_zome_rea_economic_resource_storage::*;
@@ -47,6 +63,7 @@ use hc_zome_rea_economic_event_rpc::{
     ResourceInventoryType,
     CreateRequest as EventCreateRequest,
 };
+use hc_zome_rea_network_settings_lib::NetworkSettingsReader;
 
 
 
@@ -73,8 +90,6 @@ impl API for EconomicResourceZomePermissableDefault {
     fn create_inventory_from_event(resource_entry_def_id: Self::S, params: CreationPayload) -> RecordAPIResult<(SignedActionHashed, EconomicResourceAddress, EntryData)>
     {
         let event_params = params.get_event_params().clone();
-        let resource_params = params.get_resource_params().clone();
-        let resource_spec = params.get_resource_specification_id();
         // :TODO: move this assertion to validation callback
         if let MaybeUndefined::Some(_sent_inventory_id) = event_params.resource_inventoried_as {
             return Err(DataIntegrityError::RemoteRequestError("cannot create a new EconomicResource and specify an inventoried resource ID in the same event".to_string()));
@@ -86,20 +101,8 @@ impl API for EconomicResourceZomePermissableDefault {
             params.with_inventory_type(ResourceInventoryType::ProvidingInventory),  // inventories can only be inited by their owners initially
         )?;
 
-        // :NOTE: this will always run- resource without a specification ID would fail entry validation (implicit in the above)
-        if let Some(conforms_to) = resource_spec {
-            let e = create_index!(economic_resource.conforms_to(conforms_to), resource_specification.conforming_resources(&base_address));
-            hdk::prelude::debug!("create_inventory_from_event::conforms_to index {:?}", e);
-          }
-          if let Some(contained_in) = resource_params.get_contained_in() {
-            let e = create_index!(economic_resource(&base_address).contained_in(&contained_in));
-            hdk::prelude::debug!("create_inventory_from_event::contained_in index {:?}", e);
-        };
-
-        if entry_resp.primary_accountable.is_some() {
-            let e = create_index!(economic_resource.primary_accountable(&event_params.receiver), agent.inventoried_economic_resources(&base_address));
-            hdk::prelude::debug!("create_inventory_from_event::new_inventoried_resource::primary_accountable index {:?}", e);
-        }
+        log_index_results("create_inventory_from_event", create_economic_resource_indexes(&base_address, &entry_resp)?);
+        write_onhand_checkpoint(&base_address, entry_resp.onhand_quantity.to_owned(), OnhandBalanceInclusions::default())?;
 
         Ok((meta, base_address, entry_resp))
     }
@@ -107,13 +110,47 @@ impl API for EconomicResourceZomePermissableDefault {
     fn get_economic_resource(address: EconomicResourceAddress) -> RecordAPIResult<ResponseData>
     {
         let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
-        construct_response(&base_address, &meta, &entry, get_link_fields(&address)?)
+        let mut resp = construct_response(&base_address, &meta, &entry, get_link_fields(&address)?)?;
+        resp.economic_resource.onhand_quantity = compute_onhand_quantity(&base_address)?;
+        restrict_to_caller_role(&entry, resp)
     }
 
     fn get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
     {
         let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
-        construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
+        restrict_to_caller_role(&entry, construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)?)
+    }
+
+    fn get_economic_resource_revision_history(params: RevisionHistoryParams<EconomicResourceAddress>) -> RecordAPIResult<RevisionHistory<ResponseData>>
+    {
+        let link_fields = get_link_fields(&params.address)?;
+        read_revision_window::<EntryData, EntryStorage, EconomicResourceAddress, _, _>(
+            &params.address, params.since, params.until, params.limit,
+            |meta, entry| restrict_to_caller_role(entry, construct_response(&params.address, meta, entry, link_fields.to_owned())?),
+        )
+    }
+
+    /// Rebuild the indexes of a resource from its latest revision.
+    ///
+    fn resync_economic_resource_indexes(address: EconomicResourceAddress) -> RecordAPIResult<bool>
+    {
+        let (_meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+        throw_if_resync_failed(create_economic_resource_indexes(&base_address, &entry)?)
+    }
+
+    /// Quantities are derived from the resource's events rather than edited, so are omitted.
+    ///
+    fn get_resource_edit_log(address: EconomicResourceAddress) -> RecordAPIResult<Vec<RevisionEdit>>
+    {
+        read_edit_log::<EntryData, EntryStorage, EconomicResourceAddress>(&address, &["accounting_quantity", "onhand_quantity"])
+    }
+
+    fn get_economic_resource_for_caller(address: EconomicResourceAddress) -> RecordAPIResult<RecordProjection<EconomicResourceAddress>>
+    {
+        let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+        let mut resp = construct_response_record(&base_address, &meta, &entry, get_link_fields(&base_address)?)?;
+        resp.onhand_quantity = compute_onhand_quantity(&base_address)?;
+        project_record(base_address, meta.as_hash().to_owned(), &entry, &resp, read_caller_role(&entry)?)
     }
 
     /// Handle update of resources by iterative reduction of event records over time.
@@ -133,6 +170,7 @@ impl API for EconomicResourceZomePermissableDefault {
                 event.with_inventory_type(ResourceInventoryType::ReceivingInventory),
             )?;
             resources_affected.push((meta, resource_address.clone(), new_resource.clone(), prev_resource.clone()));
+            sync_location_index(&resource_address, &new_resource, Some(&prev_resource))?;
             if new_resource.primary_accountable != prev_resource.primary_accountable {
                 let new_value = if let Some(val) = &new_resource.primary_accountable { vec![val.to_owned()] } else { vec![] };
                 let prev_value = if let Some(val) = &prev_resource.primary_accountable { vec![val.to_owned()] } else { vec![] };
@@ -147,20 +185,81 @@ impl API for EconomicResourceZomePermissableDefault {
         // after receiver, run provider. This entry data will be returned in the response.
         if let MaybeUndefined::Some(provider_inventory) = &event.resource_inventoried_as {
             let inv_entry_hash: &EntryHash = provider_inventory.as_ref();
-            resources_affected.push(handle_update_inventory_resource(
+            let (meta, resource_address, new_resource, prev_resource) = handle_update_inventory_resource(
                 &get_latest_action_hash(inv_entry_hash.clone())?,   // :TODO: temporal reduction here! Should error on mismatch and return latest valid ID
                 event.with_inventory_type(ResourceInventoryType::ProvidingInventory),
-            )?);
+            )?;
+            sync_location_index(&resource_address, &new_resource, Some(&prev_resource))?;
+            resources_affected.push((meta, resource_address, new_resource, prev_resource));
         }
 
         Ok(resources_affected)
     }
 
+    /// Reverse the changes an event made to the on-hand balances of the resources it affected,
+    /// prior to the event being deleted.
+    ///
+    /// :WARNING: Should only ever be wired up as the dependency of an EconomicEvent zome.
+    ///
+    fn reverse_inventory_from_event(event_revision_id: ActionHash) -> RecordAPIResult<Vec<EconomicResourceAddress>>
+    {
+        let (_meta, event_address, event) = read_record_entry_by_action::<EventData, EventStorage, _>(&event_revision_id)?;
+
+        let mut resources: Vec<EconomicResourceAddress> = vec![];
+        for resource in event.to_resource_inventoried_as.iter().chain(event.resource_inventoried_as.iter()) {
+            if !resources.contains(resource) {
+                resources.push(resource.to_owned());
+            }
+        }
+
+        let event_hash: &EntryHash = event_address.as_ref();
+        let mut reversed = vec![];
+        for resource in resources {
+            let (_meta, _base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(resource.as_ref())?;
+            let unit = match &entry.onhand_quantity {
+                Some(onhand) => onhand.get_unit(),
+                None => continue,
+            };
+            // reversing an event twice would double its reversal
+            let already_reversed = read_onhand_delta_links(&resource)?.iter()
+                .any(|d| d.delta.reversal && d.event.as_ref() == Some(event_hash));
+            if already_reversed {
+                continue;
+            }
+            let deltas = event_onhand_deltas(&resource, &event)?;
+            if deltas.is_empty() {
+                continue;
+            }
+            for delta in deltas {
+                let reversal = subtract(QuantityValue::new(0.0, unit.to_owned()), delta.with_unit(unit.to_owned()))?;
+                write_onhand_delta(&resource, &event_address, reversal, true)?;
+            }
+            reversed.push(resource);
+        }
+        Ok(reversed)
+    }
+
+    fn record_inventory_deltas(event: EconomicEventAddress, resources: Vec<EconomicResourceAddress>) -> RecordAPIResult<Vec<EconomicResourceAddress>>
+    {
+        record_inventory_deltas(&event, resources)
+    }
+
+    fn reconcile_onhand_balance(address: EconomicResourceAddress) -> RecordAPIResult<OnhandBalanceReconciliation>
+    {
+        reconcile_onhand_balance(&address)
+    }
+
     fn update_economic_resource(resource: UpdateRequest) -> RecordAPIResult<ResponseData>
     {
         let address = resource.get_revision_id().clone();
         let (meta, identity_address, entry, prev_entry): (_,_, EntryData, EntryData) = update_record(&address, resource)?;
 
+        hdk_records::return_if_unchanged!(meta, address, construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?)
+            .and_then(|mut response| {
+                response.economic_resource.onhand_quantity = compute_onhand_quantity(&identity_address)?;
+                Ok(response)
+            }), economic_resource);
+
         // :TODO: issue #192
         // https://github.com/h-REA/hREA/issues/192
         if entry.contained_in != prev_entry.contained_in {
@@ -169,11 +268,351 @@ impl API for EconomicResourceZomePermissableDefault {
             let e = update_index!(economic_resource(&identity_address).contained_in(now_contained.as_slice()).not(prev_contained.as_slice()));
             hdk::prelude::debug!("update_economic_resource::contained_in index {:?}", e);
         }
+        sync_expiry_index(&identity_address, Some(&entry), Some(&prev_entry))?;
 
 
         // :TODO: optimise this- should pass results from `replace_direct_index` instead of retrieving from `get_link_fields` where updates
-        construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?)
+        let mut response = construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?)?;
+        response.economic_resource.onhand_quantity = compute_onhand_quantity(&identity_address)?;
+        Ok(response)
+    }
+
+    fn patch_economic_resource(address: EconomicResourceAddress, ops: Vec<PatchOp>) -> RecordAPIResult<ResponseData>
+    {
+        let revision_id = Self::get_economic_resource(address)?.economic_resource.revision_id;
+        Self::update_economic_resource(build_patch_update(revision_id, ops)?)
+    }
+
+    fn reassign_resource_specification(params: ReassignSpecificationParams<EconomicResourceAddress>) -> RecordAPIResult<bool>
+    {
+        NetworkSettingsReader::new(read_network_settings_zome).throw_if_not_admin()?;
+
+        let (meta, _base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(params.address.as_ref())?;
+        if entry.conforms_to.as_ref() != Some(&params.from) {
+            return Ok(false);
+        }
+        let (_meta, identity_address, _new_entry, _prev_entry): (_, EconomicResourceAddress, EntryData, EntryData) = update_record(meta.as_hash(), params.to_owned())?;
+
+        update_index!(
+            economic_resource
+                .conforms_to(&vec![params.to])
+                .not(&vec![params.from]),
+            resource_specification.conforming_resources(&identity_address)
+        )?;
+        Ok(true)
+    }
+}
+
+generate_patch_builder!(UpdateRequest {
+    classified_as,
+    image,
+    contained_in,
+    unit_of_effort,
+    note,
+    lot_details
+});
+
+/// Keep the expiry-ordered time index of lot-tracked resources in sync with record data.
+/// Resources without a lot expiry date are not indexed.
+///
+fn sync_expiry_index(
+    address: &EconomicResourceAddress,
+    new_entry: Option<&EntryData>,
+    prev_entry: Option<&EntryData>,
+) -> RecordAPIResult<()> {
+    let new_expiry = new_entry.and_then(read_expiry_date);
+    let prev_expiry = prev_entry.and_then(read_expiry_date);
+    if new_expiry == prev_expiry {
+        return Ok(());
+    }
+
+    if prev_expiry.is_some() {
+        let _removed: bool = call_local_zome_method(read_index_zome, "remove_economic_resource_expiry", ByAddress { address: address.to_owned() })?;
+    }
+    if let Some(expiry_date) = new_expiry {
+        let _indexed: bool = call_local_zome_method(read_index_zome, "record_economic_resource_expiry", AppendAddress {
+            address: address.to_owned(),
+            timestamp: expiry_date.into(),
+        })?;
+    }
+    Ok(())
+}
+
+/// Move the location index link of a resource whose `current_location` has changed
+/// (eg. via a `move` event), as part of the same operation which updated the resource.
+///
+/// Index writes made for an `EconomicResource` when it is created, and re-run to resync its indexes.
+///
+fn create_economic_resource_indexes(base_address: &EconomicResourceAddress, entry: &EntryData) -> RecordAPIResult<RecordIndexResults>
+{
+    let mut results = vec![];
+    // :NOTE: this will always run- resource without a specification ID would fail entry validation
+    if let Some(conforms_to) = &entry.conforms_to {
+        results.push(("conforms_to", create_index!(economic_resource.conforms_to(conforms_to), resource_specification.conforming_resources(base_address))));
+    }
+    if let Some(contained_in) = &entry.contained_in {
+        results.push(("contained_in", create_index!(economic_resource(base_address).contained_in(contained_in))));
+    }
+    if let Some(primary_accountable) = &entry.primary_accountable {
+        results.push(("primary_accountable", create_index!(economic_resource.primary_accountable(primary_accountable), agent.inventoried_economic_resources(base_address))));
+    }
+    if let Some(current_location) = &entry.current_location {
+        results.push(("at_location", create_index!(economic_resource(base_address).at_location(current_location))));
+    }
+    sync_expiry_index(base_address, Some(entry), None)?;
+    Ok(results)
+}
+
+fn sync_location_index(
+    address: &EconomicResourceAddress,
+    new_entry: &EntryData,
+    prev_entry: Option<&EntryData>,
+) -> RecordAPIResult<()> {
+    let prev_location = prev_entry.and_then(|e| e.current_location.to_owned());
+    if new_entry.current_location == prev_location {
+        return Ok(());
+    }
+
+    let new_value = match &new_entry.current_location { Some(val) => vec![val.to_owned()], None => vec![] };
+    let prev_value = match &prev_location { Some(val) => vec![val.to_owned()], None => vec![] };
+    let e = update_index!(economic_resource(address).at_location(new_value.as_slice()).not(prev_value.as_slice()))?;
+    hdk::prelude::debug!("sync_location_index::at_location index {:?}", e);
+    Ok(())
+}
+
+fn read_expiry_date(e: &EntryData) -> Option<DateTime<FixedOffset>> {
+    e.lot_details.as_ref().and_then(|lot| lot.expiry_date)
+}
+
+/// Number of balance deltas recorded against a resource after which they are compacted into a new
+/// checkpoint, bounding the number of links read to determine the balance.
+const ONHAND_BALANCE_COMPACT_INTERVAL: usize = 32;
+
+/// Current on-hand quantity of a resource, read from its incrementally-maintained balance counter.
+/// Resources without a counter (eg. those created before it was introduced) are recomputed in full.
+///
+pub fn compute_onhand_quantity(resource: &EconomicResourceAddress) -> RecordAPIResult<Option<QuantityValue>>
+{
+    match read_onhand_counter(resource)? {
+        (Some(checkpoint), pending) => sum_onhand_balance(checkpoint.onhand_quantity, delta_values(&pending).as_slice()),
+        (None, _) => Ok(recompute_onhand_checkpoint(resource)?.0),
+    }
+}
+
+/// Recompute the on-hand balance of a resource from all events affecting it and checkpoint
+/// the result, so that subsequent reads only need to add the deltas not accounted for by it.
+///
+fn reconcile_onhand_balance(resource: &EconomicResourceAddress) -> RecordAPIResult<OnhandBalanceReconciliation>
+{
+    let counted = match read_onhand_counter(resource)? {
+        (Some(checkpoint), pending) => sum_onhand_balance(checkpoint.onhand_quantity, delta_values(&pending).as_slice())?,
+        (None, _) => None,
+    };
+    let (recomputed, included) = recompute_onhand_checkpoint(resource)?;
+    write_onhand_checkpoint(resource, recomputed.to_owned(), included)?;
+
+    Ok(OnhandBalanceReconciliation { counted, recomputed })
+}
+
+/// Fold the deltas not yet accounted for by a resource's latest checkpoint into a new checkpoint.
+///
+fn compact_onhand_counter(
+    resource: &EconomicResourceAddress,
+    checkpoint: OnhandBalanceCheckpoint,
+    pending: Vec<OnhandDeltaLink>,
+) -> RecordAPIResult<()>
+{
+    let onhand_quantity = sum_onhand_balance(checkpoint.onhand_quantity, delta_values(&pending).as_slice())?;
+    let mut included = checkpoint.included.unwrap_or_default();
+    included.deltas.extend(pending.into_iter().map(|d| d.id));
+    write_onhand_checkpoint(resource, onhand_quantity, included)
+}
+
+/// Checkpoint the on-hand balance of a resource, replacing any previous checkpoints. Newly created
+/// resources start their balance counter with a checkpoint of their initial on-hand quantity.
+///
+fn write_onhand_checkpoint(
+    resource: &EconomicResourceAddress,
+    onhand_quantity: Option<QuantityValue>,
+    included: OnhandBalanceInclusions,
+) -> RecordAPIResult<()>
+{
+    let base: &EntryHash = resource.as_ref();
+    let superseded = get_links(base.to_owned(), LinkTypes::OnhandBalanceCheckpoint, None)?;
+
+    let checkpoint = OnhandBalanceCheckpoint {
+        resource: resource.to_owned(),
+        onhand_quantity,
+        as_of: sys_time()?,
+        included: Some(included),
+    };
+    create_entry(EntryTypes::OnhandBalanceCheckpoint(checkpoint.to_owned()))?;
+    create_link(base.to_owned(), hash_entry(checkpoint)?, LinkTypes::OnhandBalanceCheckpoint, ())?;
+
+    for link in superseded {
+        delete_link(link.create_link_hash)?;
     }
+    Ok(())
+}
+
+/// Record the changes made by a newly written event in the balance counters of the resources it updated.
+/// Counters are reconciled beforehand if missing, or compacted once enough deltas have accumulated.
+///
+fn record_inventory_deltas(event_address: &EconomicEventAddress, resources: Vec<EconomicResourceAddress>) -> RecordAPIResult<Vec<EconomicResourceAddress>>
+{
+    let (_meta, _base_address, event) = read_record_entry::<EventData, EventStorage, _>(event_address.as_ref())?;
+
+    let mut recorded = vec![];
+    for resource in resources {
+        let (_meta, _base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(resource.as_ref())?;
+        let unit = match &entry.onhand_quantity {
+            Some(onhand) => onhand.get_unit(),
+            None => continue,
+        };
+        let deltas = event_onhand_deltas(&resource, &event)?;
+        if deltas.is_empty() {
+            continue;
+        }
+        match read_onhand_counter(&resource)? {
+            (Some(_), pending) if pending.len() < ONHAND_BALANCE_COMPACT_INTERVAL => {},
+            (Some(checkpoint), pending) => compact_onhand_counter(&resource, checkpoint, pending)?,
+            // if the event is counted by the recomputed checkpoint, its deltas are excluded from reads
+            (None, _) => { reconcile_onhand_balance(&resource)?; },
+        };
+        for delta in deltas {
+            write_onhand_delta(&resource, event_address, delta.with_unit(unit.to_owned()), false)?;
+        }
+        recorded.push(resource);
+    }
+    Ok(recorded)
+}
+
+fn write_onhand_delta(resource: &EconomicResourceAddress, event: &EconomicEventAddress, delta: QuantityValue, reversal: bool) -> RecordAPIResult<()>
+{
+    let tag: SerializedBytes = OnhandBalanceDelta { delta, reversal }.try_into()?;
+    let base: &EntryHash = resource.as_ref();
+    let target: &EntryHash = event.as_ref();
+    create_link(base.to_owned(), target.to_owned(), LinkTypes::OnhandBalanceDelta, LinkTag::new(tag.bytes().to_owned()))?;
+    Ok(())
+}
+
+/// A balance delta link, identified by the action which created it.
+///
+struct OnhandDeltaLink {
+    id: ActionHash,
+    // the event making the change; deltas recorded before events were attributed target resource revisions
+    event: Option<EntryHash>,
+    delta: OnhandBalanceDelta,
+}
+
+fn read_onhand_delta_links(resource: &EconomicResourceAddress) -> RecordAPIResult<Vec<OnhandDeltaLink>>
+{
+    let base: &EntryHash = resource.as_ref();
+    get_links(base.to_owned(), LinkTypes::OnhandBalanceDelta, None)?
+        .into_iter()
+        .map(|link| {
+            let delta: OnhandBalanceDelta = SerializedBytes::from(UnsafeBytes::from(link.tag.into_inner())).try_into()?;
+            Ok(OnhandDeltaLink { id: link.create_link_hash, event: link.target.into_entry_hash(), delta })
+        })
+        .collect()
+}
+
+fn delta_values(deltas: &[OnhandDeltaLink]) -> Vec<QuantityValue>
+{
+    deltas.iter().map(|d| d.delta.delta.to_owned()).collect()
+}
+
+/// Read the latest balance checkpoint of a resource, and the balance deltas not accounted for by it.
+/// Checkpoints which do not record the deltas they account for are disregarded.
+///
+fn read_onhand_counter(resource: &EconomicResourceAddress) -> RecordAPIResult<(Option<OnhandBalanceCheckpoint>, Vec<OnhandDeltaLink>)>
+{
+    let base: &EntryHash = resource.as_ref();
+    let latest_checkpoint = get_links(base.to_owned(), LinkTypes::OnhandBalanceCheckpoint, None)?
+        .into_iter()
+        .filter_map(|link| link.target.into_entry_hash())
+        .filter_map(|hash| get_entry_by_address::<OnhandBalanceCheckpoint>(&hash).ok())
+        .map(|(_meta, checkpoint)| checkpoint)
+        .filter(|checkpoint| checkpoint.included.is_some())
+        .max_by_key(|checkpoint| checkpoint.as_of);
+
+    let pending = read_onhand_delta_links(resource)?
+        .into_iter()
+        .filter(|d| match latest_checkpoint.as_ref().and_then(|c| c.included.as_ref()) {
+            Some(included) => !is_accounted_for(included, d),
+            None => true,
+        })
+        .collect();
+
+    Ok((latest_checkpoint, pending))
+}
+
+fn is_accounted_for(included: &OnhandBalanceInclusions, d: &OnhandDeltaLink) -> bool
+{
+    included.deltas.contains(&d.id)
+        || (!d.delta.reversal && d.event.as_ref().map_or(false, |event| included.events.contains(event)))
+}
+
+/// Recompute the on-hand balance of a resource from every event affecting it, or `None` if
+/// the resource does not track an on-hand quantity, along with the events and deltas accounted for.
+///
+/// Events which have been reversed are not counted. Their deltas are accounted for only once both
+/// the forward and reversing deltas are visible, so that the remaining one is counted when it arrives.
+///
+fn recompute_onhand_checkpoint(resource: &EconomicResourceAddress) -> RecordAPIResult<(Option<QuantityValue>, OnhandBalanceInclusions)>
+{
+    let (_meta, _base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(resource.as_ref())?;
+    let unit = match &entry.onhand_quantity {
+        Some(onhand) => onhand.get_unit(),
+        None => return Ok((None, OnhandBalanceInclusions::default())),
+    };
+
+    let delta_links = read_onhand_delta_links(resource)?;
+    let has_delta = |event: &EntryHash, reversal: bool| delta_links.iter()
+        .any(|d| d.delta.reversal == reversal && d.event.as_ref() == Some(event));
+
+    let mut deltas = vec![];
+    let mut included = OnhandBalanceInclusions::default();
+    for event_address in get_affecting_events(resource)? {
+        let event_hash: &EntryHash = event_address.as_ref();
+        if has_delta(event_hash, true) {
+            continue;
+        }
+        match read_record_entry::<EventData, EventStorage, _>(event_hash) {
+            Ok((_, _, event)) => {
+                deltas.append(&mut event_onhand_deltas(resource, &event)?);
+                included.events.push(event_hash.to_owned());
+            },
+            // deleted events no longer count toward the balance
+            Err(DataIntegrityError::EntryNotFound) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    included.deltas = delta_links.iter()
+        .filter(|d| match &d.event {
+            Some(event) => !included.events.contains(event) && has_delta(event, false) && has_delta(event, true),
+            // deltas recorded against revisions predate attribution, and were always covered by recomputation
+            None => true,
+        })
+        .map(|d| d.id.to_owned())
+        .collect();
+
+    Ok((Some(recompute_onhand_balance(unit, deltas)?), included))
+}
+
+/// Changes made by an event to the on-hand quantity of a resource, for each side of the event
+/// which the resource is on.
+///
+fn event_onhand_deltas(resource: &EconomicResourceAddress, event: &EventData) -> RecordAPIResult<Vec<QuantityValue>>
+{
+    let mut deltas = vec![];
+    if event.to_resource_inventoried_as.as_ref() == Some(resource) {
+        deltas.extend(onhand_quantity_delta(&event.action, event.resource_quantity.to_owned(), ResourceInventoryType::ReceivingInventory)?);
+    }
+    if event.resource_inventoried_as.as_ref() == Some(resource) {
+        deltas.extend(onhand_quantity_delta(&event.action, event.resource_quantity.to_owned(), ResourceInventoryType::ProvidingInventory)?);
+    }
+    Ok(deltas)
 }
 
 /// Properties accessor for zome config
@@ -186,6 +625,51 @@ fn read_resource_specification_index_zome(conf: DnaConfigSlice) -> Option<String
     conf.economic_resource.resource_specification_index_zome
 }
 
+/// Properties accessor for zome config
+fn read_network_settings_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.economic_resource.network_settings_zome
+}
+
+fn read_zome_config() -> RecordAPIResult<EconomicResourceZomeConfig> {
+    let conf: DnaConfigSlice = dna_info()?.modifiers.properties.try_into()?;
+    Ok(conf.economic_resource)
+}
+
+/// Determine the role of the calling agent with respect to a resource. Owners are the agents
+/// represented by its `primary_accountable`, as determined by the agent's own DNA.
+/// Callers are public where no agent is accountable or that DNA cannot be reached.
+///
+fn read_caller_role(entry: &EntryData) -> RecordAPIResult<RecordRole> {
+    let agent = match &entry.primary_accountable {
+        Some(agent) => agent.to_owned(),
+        None => return Ok(RecordRole::Public),
+    };
+    let represented: OtherCellResult<bool> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        &agent,
+        &AGENT_REPRESENTATION_API_METHOD.to_string(),
+        AgentRepresentationParams { agent: agent.to_owned(), agent_pub_key: call_info()?.provenance },
+        LinkTypes::AvailableCapability,
+    );
+    Ok(match represented {
+        Ok(true) => RecordRole::Owner,
+        Ok(false) => RecordRole::Public,
+        Err(e) => {
+            log_warn!("read_caller_role"; "could not determine the agents represented by {:?}, reading as public: {:?}", agent, e);
+            RecordRole::Public
+        },
+    })
+}
+
+/// Hide the fields of a resource not visible to the calling agent, where the zome is
+/// configured with `restrict_fields_by_role`.
+///
+fn restrict_to_caller_role(entry: &EntryData, mut resp: ResponseData) -> RecordAPIResult<ResponseData> {
+    if read_zome_config()?.restrict_fields_by_role {
+        resp.economic_resource = redact_for_role(entry, resp.economic_resource, read_caller_role(entry)?)?;
+    }
+    Ok(resp)
+}
+
 fn handle_update_inventory_resource(
     resource_addr: &ActionHash,
     event: EventCreateRequest,
@@ -237,6 +721,7 @@ pub fn construct_response_record<'a>(
         classified_as: e.classified_as.to_owned(),
         tracking_identifier: e.tracking_identifier.to_owned(),
         lot: e.lot.to_owned(),
+        lot_details: e.lot_details.to_owned(),
         image: e.image.to_owned(),
         accounting_quantity: e.accounting_quantity.to_owned(),
         onhand_quantity: e.onhand_quantity.to_owned(),
@@ -9,8 +9,13 @@
 use holochain_serialized_bytes::prelude::*;
 
 use serde_maybe_undefined::MaybeUndefined;
+pub use vf_measurement::QuantityValue;
 pub use vf_attributes_hdk::{
-    ActionHash, ByAddress, ByRevision, RecordMeta, RevisionMeta,
+    ErrorCode, ErrorEnvelope,
+    PatchOp, PatchParams,
+    ActionHash, ByAddress, ByRevision, RecordMeta, RevisionMeta, RevisionEdit, FieldChange,
+    RevisionHistoryParams, RevisionHistory,
+    RecordRole, RecordProjection,
     EconomicResourceAddress,
     EconomicEventAddress,
     ExternalURL,
@@ -19,8 +24,11 @@ pub use vf_attributes_hdk::{
     UnitId,
     ProductBatchAddress,
     AgentAddress,
+    DateTime, FixedOffset,
+    ReassignSpecificationParams,
 };
 
+pub use hc_zome_rea_economic_event_rpc::LotDetails;
 use hc_zome_rea_economic_event_rpc::{
     CreateRequest as EventCreateRequest,
     ResourceCreateRequest as CreateRequest,
@@ -37,6 +45,14 @@ pub struct CreationPayload {
     pub resource: CreateRequest,
 }
 
+/// Resources whose on-hand balance counters should record the changes made by a newly written event.
+/// Resources created by the event are excluded, as their initial balance already includes it.
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+pub struct InventoryDeltasPayload {
+    pub event: EconomicEventAddress,
+    pub resources: Vec<EconomicResourceAddress>,
+}
+
 impl<'a> CreationPayload {
     pub fn get_event_params(&'a self) -> &EventCreateRequest {
         &self.event
@@ -64,17 +80,24 @@ impl<'a> CreationPayload {
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateRequest {
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "classified_as"))]
     pub classified_as: MaybeUndefined<Vec<ExternalURL>>,
     #[serde(default)]
     pub image: MaybeUndefined<ExternalURL>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "contained_in"))]
     pub contained_in: MaybeUndefined<EconomicResourceAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "unit_of_effort"))]
     pub unit_of_effort: MaybeUndefined<UnitId>,
     #[serde(default)]
     pub note: MaybeUndefined<String>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "lot_details"))]
+    pub lot_details: MaybeUndefined<LotDetails>,
 }
 
 impl<'a> UpdateRequest {
@@ -103,4 +126,58 @@ pub struct QueryParams {
     pub conforms_to: Option<ResourceSpecificationAddress>,
     pub affected_by: Option<EconomicEventAddress>,
     pub primary_accountable: Option<AgentAddress>,
+    pub at_location: Option<LocationAddress>,
+}
+
+/// Parameters for querying lot-tracked resources by expiry
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ExpiryQueryParams {
+    pub expiring_before: DateTime<FixedOffset>,
+}
+
+//---------------- INVENTORY SUMMARY ----------------
+
+/// Parameters for summarising on-hand inventory by `ResourceSpecification`
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InventorySummaryParams {
+    pub conforms_to: Vec<ResourceSpecificationAddress>,
+    // only count resources currently at this location
+    #[serde(default)]
+    pub at_location: Option<LocationAddress>,
+}
+
+/// Total on-hand quantity of all resources conforming to a `ResourceSpecification`.
+/// Quantities measured in differing units are totalled separately.
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InventorySummary {
+    pub conforms_to: ResourceSpecificationAddress,
+    pub onhand_quantities: Vec<QuantityValue>,
+}
+
+//---------------- ON-HAND BALANCE ----------------
+
+/// Outcome of recomputing a resource's on-hand balance from all events affecting it.
+/// `counted` is the balance held by the incremental counter beforehand, if there was one;
+/// any difference from `recomputed` was drift which the reconciliation has corrected.
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct OnhandBalanceReconciliation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub counted: Option<QuantityValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recomputed: Option<QuantityValue>,
+}
+
+#[cfg(all(test, feature = "snake_case_aliases"))]
+mod tests {
+    use super::*;
+
+    vf_attributes_hdk::snake_case_alias_tests!(UpdateRequest);
 }
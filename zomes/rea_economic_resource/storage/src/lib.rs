@@ -12,8 +12,8 @@ use hdk::prelude::*;
 use hdk_records::{
     RecordAPIResult, DataIntegrityError,
     MaybeUndefined, OtherCellResult,
-    generate_record_entry,
-    record_interface::Updateable,
+    generate_record_entry, role_visible_fields,
+    record_interface::{Updateable, RoleVisibility},
     rpc::call_zome_method,
 };
 
@@ -54,6 +54,11 @@ pub struct EconomicResourceZomeConfig {
     pub index_zome: String,
     pub resource_specification_index_zome: Option<String>,
     pub agent_index_zome: Option<String>,
+    // zome ID of the network settings zome, used to authorise administrative operations
+    pub network_settings_zome: Option<String>,
+    // whether reads hide the fields not visible to the caller's `RecordRole`
+    #[serde(default)]
+    pub restrict_fields_by_role: bool,
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -65,6 +70,8 @@ pub struct EntryData {
     pub classified_as: Option<Vec<ExternalURL>>,
     pub tracking_identifier: Option<String>,
     pub lot: Option<ProductBatchAddress>,
+    #[serde(default)]
+    pub lot_details: Option<LotDetails>,
     pub image: Option<ExternalURL>,
     pub accounting_quantity: Option<QuantityValue>,
     pub onhand_quantity: Option<QuantityValue>,
@@ -87,6 +94,59 @@ impl EntryData {
 
 generate_record_entry!(EntryData, EconomicResourceAddress, EntryStorage);
 
+/// Stock levels, lots & whereabouts of a resource are only visible to its owners- agents
+/// represented by its `primary_accountable`.
+impl RoleVisibility for EntryData {
+    role_visible_fields!(Public {
+        name,
+        conforms_to,
+        classified_as,
+        image,
+        unit_of_effort,
+        note,
+        primary_accountable,
+    });
+}
+
+/// Reconciled on-hand balance of an `EconomicResource`. Balance deltas not accounted for by the
+/// checkpoint are added to this value when reading.
+///
+/// `included` is `None` for checkpoints written before deltas were accounted for by identity,
+/// which are disregarded.
+///
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct OnhandBalanceCheckpoint {
+    pub resource: EconomicResourceAddress,
+    pub onhand_quantity: Option<QuantityValue>,
+    pub as_of: Timestamp,
+    #[serde(default)]
+    pub included: Option<OnhandBalanceInclusions>,
+}
+
+/// Balance deltas accounted for by an `OnhandBalanceCheckpoint`. Deltas are accounted for by
+/// identity rather than by time, so that deltas gossiped late or authored while the checkpoint was
+/// being computed are neither lost nor counted twice: forward deltas of the events in `events` were
+/// counted via those events, and the deltas in `deltas` (by `CreateLink` action hash) were counted directly.
+///
+#[derive(Clone, Serialize, Deserialize, Default, PartialEq, Debug)]
+pub struct OnhandBalanceInclusions {
+    pub events: Vec<EntryHash>,
+    pub deltas: Vec<ActionHash>,
+}
+
+/// Change to the on-hand balance of an `EconomicResource` made by a single event, stored
+/// in the tag of a link from the resource to the event. Links authored concurrently never conflict,
+/// so no changes are lost when events affecting the same resource are created in parallel.
+/// `reversal` marks the deltas undoing the changes of a deleted event.
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
+pub struct OnhandBalanceDelta {
+    pub delta: QuantityValue,
+    #[serde(default)]
+    pub reversal: bool,
+}
+
 //---------------- Holochain App Entry And Link Types Setup ----------------
 
 #[hdk_entry_defs(skip_hdk_extern = true)]
@@ -94,7 +154,8 @@ generate_record_entry!(EntryData, EconomicResourceAddress, EntryStorage);
 pub enum EntryTypes {
     EconomicResource(EntryStorage),
     #[entry_def(visibility = "private")]
-    AvailableCapability(AvailableCapability)
+    AvailableCapability(AvailableCapability),
+    OnhandBalanceCheckpoint(OnhandBalanceCheckpoint),
 }
 
 impl From<EntryStorage> for EntryTypes
@@ -117,7 +178,9 @@ impl TryFrom<AvailableCapability> for EntryTypes {
 pub enum LinkTypes {
     // relates to dna-auth-resolver mixin
     // and remote authorizations
-    AvailableCapability
+    AvailableCapability,
+    OnhandBalanceDelta,
+    OnhandBalanceCheckpoint,
 }
 
 //---------------- CREATE ----------------
@@ -146,7 +209,7 @@ impl TryFrom<CreationPayload> for EntryData {
         )?;
         let quantity_value = match e.resource_quantity.to_owned() {
             MaybeUndefined::Some(resource_quantity) => {
-                Some(QuantityValue::new(resource_quantity.get_numerical_value(), unit_of_resource))
+                Some(resource_quantity.with_unit(unit_of_resource))
             },
             _ => None,
         };
@@ -156,6 +219,7 @@ impl TryFrom<CreationPayload> for EntryData {
             classified_as: if e.resource_classified_as == MaybeUndefined::Undefined { None } else { e.resource_classified_as.to_owned().to_option() },
             tracking_identifier: if r.tracking_identifier == MaybeUndefined::Undefined { None } else { r.tracking_identifier.to_owned().to_option() },
             lot: if r.lot == MaybeUndefined::Undefined { None } else { r.lot.to_owned().to_option() },
+            lot_details: if r.lot_details == MaybeUndefined::Undefined { None } else { r.lot_details.to_owned().to_option() },
             image: if r.image == MaybeUndefined::Undefined { None } else { r.image.to_owned().to_option() },
             accounting_quantity: match quantity_value.clone() {
                 Some(resource_quantity) => update_quantity(
@@ -267,6 +331,7 @@ impl Updateable<UpdateRequest> for EntryData {
             classified_as: if e.classified_as == MaybeUndefined::Undefined { self.classified_as.to_owned() } else { e.classified_as.to_owned().to_option() },
             tracking_identifier: self.tracking_identifier.to_owned(),
             lot: self.lot.to_owned(),
+            lot_details: if e.lot_details == MaybeUndefined::Undefined { self.lot_details.to_owned() } else { e.lot_details.to_owned().to_option() },
             image: if e.image == MaybeUndefined::Undefined { self.image.to_owned() } else { e.image.to_owned().to_option() },
             accounting_quantity: self.accounting_quantity.to_owned(),
             onhand_quantity: self.onhand_quantity.to_owned(),
@@ -280,6 +345,17 @@ impl Updateable<UpdateRequest> for EntryData {
     }
 }
 
+/// Re-points the resource at a different `ResourceSpecification` when specifications are merged.
+/// This is the only means of changing `conforms_to` after creation.
+impl Updateable<ReassignSpecificationParams<EconomicResourceAddress>> for EntryData {
+    fn update_with(&self, e: ReassignSpecificationParams<EconomicResourceAddress>) -> RecordAPIResult<EntryData> {
+        Ok(EntryData {
+            conforms_to: Some(e.to),
+            ..self.to_owned()
+        })
+    }
+}
+
 /// Handle update operations by observed events
 ///
 /// :WARNING: we presume the event has already been determined to relate to the
@@ -315,6 +391,7 @@ impl Updateable<EventCreateRequest> for EntryData {
             },
             tracking_identifier: self.tracking_identifier.to_owned(),
             lot: self.lot.to_owned(),
+            lot_details: self.lot_details.to_owned(),
             image: self.image.to_owned(),
             accounting_quantity: update_quantity(
                 self.accounting_quantity.to_owned(), e.resource_quantity.to_owned(),
@@ -422,3 +499,171 @@ fn get_event_action(
         }
     }
 }
+
+//---------------- ON-HAND BALANCE ----------------
+
+/// Change made by an event to the on-hand quantity of the resource on the given side of it.
+/// Returns `None` where the event has no quantity or does not affect on-hand quantity.
+///
+pub fn onhand_quantity_delta(
+    action: &ActionId,
+    quantity: Option<QuantityValue>,
+    inventory_type: ResourceInventoryType,
+) -> RecordAPIResult<Option<QuantityValue>> {
+    let quantity = match quantity {
+        Some(q) => q,
+        None => return Ok(None),
+    };
+    match get_event_action(action, ResourceValueType::OnhandValue, inventory_type) {
+        ActionInventoryEffect::NoEffect => Ok(None),
+        ActionInventoryEffect::Increment => Ok(Some(quantity)),
+        ActionInventoryEffect::Decrement => Ok(Some(subtract(QuantityValue::new(0.0, quantity.get_unit()), quantity)?)),
+    }
+}
+
+/// Difference between two successive on-hand quantities of a resource, as recorded in its
+/// balance counter. A resource without a previous on-hand quantity counts up from zero.
+///
+pub fn onhand_quantity_change(
+    prev: Option<&QuantityValue>,
+    next: Option<&QuantityValue>,
+) -> RecordAPIResult<Option<QuantityValue>> {
+    match (prev, next) {
+        (_, None) => Ok(None),
+        (None, Some(next)) => Ok(Some(next.to_owned())),
+        (Some(prev), Some(next)) if prev == next => Ok(None),
+        (Some(prev), Some(next)) => Ok(Some(subtract(next.to_owned(), prev.to_owned())?)),
+    }
+}
+
+/// Add balance deltas to a balance. Deltas may be given in any order, so concurrently
+/// recorded changes sum to the same result regardless of which is read first.
+///
+pub fn sum_onhand_balance(
+    balance: Option<QuantityValue>,
+    deltas: &[QuantityValue],
+) -> RecordAPIResult<Option<QuantityValue>> {
+    let mut total = balance;
+    for delta in deltas {
+        total = Some(match total {
+            Some(t) => add(t, delta.to_owned())?,
+            None => delta.to_owned(),
+        });
+    }
+    Ok(total)
+}
+
+/// Recompute an on-hand balance in full from the changes made by every event affecting a
+/// resource. Changes are expressed in the resource's `unit`, since the quantity of the event
+/// which created the resource may have been relabelled with its specification's default unit.
+///
+pub fn recompute_onhand_balance(
+    unit: Option<UnitId>,
+    event_deltas: Vec<QuantityValue>,
+) -> RecordAPIResult<QuantityValue> {
+    let mut total = QuantityValue::new(0.0, unit.to_owned());
+    for delta in event_deltas {
+        total = add(total, delta.with_unit(unit.to_owned()))?;
+    }
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kg() -> Option<UnitId> {
+        Some(UnitId(DnaHash::from_raw_36(vec![0xdb; 36]), "kg".to_string()))
+    }
+
+    #[test]
+    fn test_incremental_balance_matches_recompute() {
+        let events = vec![
+            ("raise", 10.0, ResourceInventoryType::ProvidingInventory),
+            ("consume", 3.0, ResourceInventoryType::ProvidingInventory),
+            ("transfer", 2.0, ResourceInventoryType::ProvidingInventory),
+            ("produce", 4.5, ResourceInventoryType::ProvidingInventory),
+            ("transfer", 1.5, ResourceInventoryType::ReceivingInventory),
+            ("work", 8.0, ResourceInventoryType::ProvidingInventory),
+            ("lower", 0.5, ResourceInventoryType::ProvidingInventory),
+        ];
+
+        // apply each event to the stored quantity, recording the change made as a balance delta
+        let mut onhand: Option<QuantityValue> = None;
+        let mut deltas = vec![];
+        for (action, qty, side) in events.iter() {
+            let next = update_quantity(
+                Some(onhand.to_owned().unwrap_or(QuantityValue::new(0.0, kg()))),
+                MaybeUndefined::Some(QuantityValue::new(*qty, kg())),
+                &ActionId::from(action.to_string()), ResourceValueType::OnhandValue, side.to_owned(),
+            ).unwrap();
+            if let Some(delta) = onhand_quantity_change(onhand.as_ref(), next.as_ref()).unwrap() {
+                deltas.push(delta);
+            }
+            onhand = next;
+        }
+
+        // checkpoint part-way through, then read remaining deltas in a different order
+        let checkpoint = sum_onhand_balance(None, &deltas[..3]).unwrap();
+        let mut remaining = deltas[3..].to_vec();
+        remaining.reverse();
+        let counted = sum_onhand_balance(checkpoint, remaining.as_slice()).unwrap().unwrap();
+
+        let recomputed = recompute_onhand_balance(kg(), events.iter()
+            .filter_map(|(action, qty, side)| onhand_quantity_delta(
+                &ActionId::from(action.to_string()), Some(QuantityValue::new(*qty, kg())), side.to_owned(),
+            ).unwrap())
+            .collect()
+        ).unwrap();
+
+        assert!(counted.approx_eq(&recomputed));
+        assert!(counted.approx_eq(&QuantityValue::new(10.5, kg())));
+        assert_eq!(counted.get_unit(), recomputed.get_unit());
+    }
+
+    #[test]
+    fn test_unchanged_quantity_records_no_delta() {
+        let qty = QuantityValue::new(2.0, kg());
+        assert_eq!(onhand_quantity_change(Some(&qty), Some(&qty)).unwrap(), None);
+        assert_eq!(onhand_quantity_change(None, Some(&qty)).unwrap(), Some(qty.to_owned()));
+        assert_eq!(onhand_quantity_change(Some(&qty), None).unwrap(), None);
+    }
+
+    fn hash(n: u8) -> EntryHash {
+        EntryHash::from_raw_36(vec![n; 36])
+    }
+
+    fn url(path: &str) -> ExternalURL {
+        ExternalURL(format!("http://example.com/{}", path))
+    }
+
+    fn entry() -> EntryData {
+        let dna = DnaHash::from_raw_36(vec![0xdb; 36]);
+        EntryData {
+            name: Some("bicycle".to_string()),
+            conforms_to: Some(ResourceSpecificationAddress(dna.to_owned(), hash(1))),
+            classified_as: Some(vec![url("classifications/bicycle")]),
+            tracking_identifier: Some("bike-001".to_string()),
+            lot: None,
+            lot_details: Some(LotDetails { batch_number: Some("batch-1".to_string()), production_date: None, expiry_date: None }),
+            image: Some(url("bicycle.png")),
+            accounting_quantity: Some(QuantityValue::new(1.0, kg())),
+            onhand_quantity: Some(QuantityValue::new(1.0, kg())),
+            unit_of_effort: kg(),
+            current_location: None,
+            contained_in: Some(EconomicResourceAddress(dna.to_owned(), hash(2))),
+            note: Some("a note".to_string()),
+            primary_accountable: Some(AgentAddress(dna, hash(3))),
+            _nonce: Bytes::from(vec![0; 32]),
+        }
+    }
+
+    hdk_records::update_semantics_tests!(EntryData, UpdateRequest, entry(), {
+        "classifiedAs" => vec![url("classifications/wheel")],
+        "image" => url("wheel.png"),
+        "containedIn" => EconomicResourceAddress(DnaHash::from_raw_36(vec![0xdb; 36]), hash(12)),
+        "unitOfEffort" => UnitId(DnaHash::from_raw_36(vec![0xdb; 36]), "hour".to_string()),
+        "note" => "updated note",
+        "lotDetails" => LotDetails { batch_number: Some("batch-2".to_string()), production_date: None, expiry_date: None },
+    });
+}
@@ -15,9 +15,18 @@ pub trait API {
     fn update_inventory_from_event(
         event: EventCreateRequest,
     ) -> RecordAPIResult<Vec<(SignedActionHashed, EconomicResourceAddress, EntryData, EntryData)>>;
+    fn record_inventory_deltas(event: EconomicEventAddress, resources: Vec<EconomicResourceAddress>) -> RecordAPIResult<Vec<EconomicResourceAddress>>;
+    fn reverse_inventory_from_event(event_revision_id: ActionHash) -> RecordAPIResult<Vec<EconomicResourceAddress>>;
+    fn reconcile_onhand_balance(address: EconomicResourceAddress) -> RecordAPIResult<OnhandBalanceReconciliation>;
     fn get_economic_resource(address: EconomicResourceAddress) -> RecordAPIResult<ResponseData>;
     fn get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>;
+    fn get_economic_resource_revision_history(params: RevisionHistoryParams<EconomicResourceAddress>) -> RecordAPIResult<RevisionHistory<ResponseData>>;
+    fn resync_economic_resource_indexes(address: EconomicResourceAddress) -> RecordAPIResult<bool>;
+    fn get_resource_edit_log(address: EconomicResourceAddress) -> RecordAPIResult<Vec<RevisionEdit>>;
+    fn get_economic_resource_for_caller(address: EconomicResourceAddress) -> RecordAPIResult<RecordProjection<EconomicResourceAddress>>;
     fn update_economic_resource(resource: UpdateRequest) -> RecordAPIResult<ResponseData>;
+    fn patch_economic_resource(address: EconomicResourceAddress, ops: Vec<PatchOp>) -> RecordAPIResult<ResponseData>;
+    fn reassign_resource_specification(params: ReassignSpecificationParams<EconomicResourceAddress>) -> RecordAPIResult<bool>;
 }
 
 /// Macro to programatically and predictably bind an `API` implementation to a
@@ -46,19 +55,71 @@ macro_rules! declare_economic_resource_zome_api {
             Ok(<$zome_api>::update_inventory_from_event(event)?)
         }
 
+        #[hdk_extern]
+        fn _internal_record_inventory_deltas(InventoryDeltasPayload { event, resources }: InventoryDeltasPayload) -> ExternResult<Vec<EconomicResourceAddress>>
+        {
+            Ok(<$zome_api>::record_inventory_deltas(event, resources)?)
+        }
+
+        #[hdk_extern]
+        fn _internal_reverse_inventory(ByRevision { revision_id }: ByRevision) -> ExternResult<Vec<EconomicResourceAddress>>
+        {
+            Ok(<$zome_api>::reverse_inventory_from_event(revision_id)?)
+        }
+
+        // recompute on-hand balance from events, correcting any drift in the incremental counter
+        #[hdk_extern]
+        fn reconcile_onhand_balance(ByAddress { address }: ByAddress<EconomicResourceAddress>) -> ExternResult<OnhandBalanceReconciliation> {
+            Ok(<$zome_api>::reconcile_onhand_balance(address)?)
+        }
+
         #[hdk_extern]
         fn get_economic_resource(ByAddress { address }: ByAddress<EconomicResourceAddress>) -> ExternResult<$crate::ResponseData> {
             Ok(<$zome_api>::get_economic_resource(address)?)
         }
 
+        // rebuilds the indexes of a resource, used by `reindex_all` in the index zome
+        #[hdk_extern]
+        fn resync_record_indexes(ByAddress { address }: ByAddress<EconomicResourceAddress>) -> ExternResult<bool> {
+            Ok(<$zome_api>::resync_economic_resource_indexes(address)?)
+        }
+
         #[hdk_extern]
         fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<$crate::ResponseData> {
             Ok(<$zome_api>::get_revision(revision_id)?)
         }
 
+        #[hdk_extern]
+        fn get_revision_history(params: RevisionHistoryParams<EconomicResourceAddress>) -> ExternResult<RevisionHistory<$crate::ResponseData>> {
+            Ok(<$zome_api>::get_economic_resource_revision_history(params)?)
+        }
+
+        // changes made by each revision, for attributing edits between custodians
+        #[hdk_extern]
+        fn get_resource_edit_log(ByAddress { address }: ByAddress<EconomicResourceAddress>) -> ExternResult<Vec<RevisionEdit>> {
+            Ok(<$zome_api>::get_resource_edit_log(address)?)
+        }
+
+        // fields of the resource visible to the calling agent, @see hdk_records::projections
+        #[hdk_extern]
+        fn get_economic_resource_for_caller(ByAddress { address }: ByAddress<EconomicResourceAddress>) -> ExternResult<RecordProjection<EconomicResourceAddress>> {
+            Ok(<$zome_api>::get_economic_resource_for_caller(address)?)
+        }
+
         #[hdk_extern]
         fn update_economic_resource(UpdateParams { resource }: UpdateParams) -> ExternResult<$crate::ResponseData> {
             Ok(<$zome_api>::update_economic_resource(resource)?)
         }
+
+        #[hdk_extern]
+        fn patch_record(PatchParams { address, ops }: PatchParams<EconomicResourceAddress>) -> ExternResult<$crate::ResponseData> {
+            Ok(<$zome_api>::patch_economic_resource(address, ops)?)
+        }
+
+        // used by the specification DNA when merging `ResourceSpecification`s
+        #[hdk_extern]
+        fn reassign_resource_specification(params: ReassignSpecificationParams<EconomicResourceAddress>) -> ExternResult<bool> {
+            Ok(<$zome_api>::reassign_resource_specification(params)?)
+        }
     };
 }
@@ -4,14 +4,16 @@
  * @package hREA
  * @since   2021-08-29
  */
+use std::collections::HashSet;
 use hdk_semantic_indexes_zome_derive::index_zome;
+use vf_measurement::add;
 use hc_zome_rea_economic_resource_rpc::*;
 use hc_zome_rea_economic_event_rpc::{
     ResourceResponse as Response,
     ResourceResponseData as ResponseData,
 };
 
-#[index_zome]
+#[index_zome(time_indexes="expiry")]
 struct EconomicResource {
     contains: Local<economic_resource, contained_in>,
     contained_in: Local<economic_resource, contains>,
@@ -20,4 +22,95 @@ struct EconomicResource {
     // internal indexes (not part of REA spec)
     affected_by: Local<economic_event, affects>,
     primary_accountable: Local<agent, inventoried_economic_events>,
+    // indexes `current_location`
+    at_location: Local<location, economic_resources_at>,
+}
+
+//---------------- LOT EXPIRY INDEX ----------------
+
+const EXPIRY_TIME_INDEX_NAME: &str = "expiry";
+
+fn expiry_time_index_id() -> String {
+    time_index_name(TIME_INDEX_RECORD_TYPE, EXPIRY_TIME_INDEX_NAME)
+}
+
+#[hdk_extern]
+fn record_economic_resource_expiry(AppendAddress { address, timestamp }: AppendAddress<EconomicResourceAddress>) -> ExternResult<bool> {
+    register_time_indexes(&TIME_INDEXES);
+    throw_if_index_read_only(read_index_read_only)?;
+    Ok(append_to_time_index(&expiry_time_index_id(), &address, timestamp)?)
+}
+
+#[hdk_extern]
+fn remove_economic_resource_expiry(ByAddress { address }: ByAddress<EconomicResourceAddress>) -> ExternResult<bool> {
+    register_time_indexes(&TIME_INDEXES);
+    throw_if_index_read_only(read_index_read_only)?;
+    Ok(remove_from_time_index(&expiry_time_index_id(), &address)?)
+}
+
+/// Query lot-tracked resources expiring before the given time, soonest-expiring last.
+/// Resources without a lot expiry date are never returned.
+///
+#[hdk_extern]
+fn query_economic_resources_expiring_before(ExpiryQueryParams { expiring_before }: ExpiryQueryParams) -> ExternResult<QueryResults> {
+    register_time_indexes(&TIME_INDEXES);
+    throw_if_query_not_permitted(read_query_access, read_agent_zome, read_network_settings_zome)?;
+
+    let entries_result = query_time_index_before::<ResponseData, EconomicResourceAddress, _,_,_>(
+        &read_index_target_zome,
+        &QUERY_FN_NAME,
+        &expiry_time_index_id(),
+        expiring_before.into(),
+    );
+
+    Ok(handle_list_output(entries_result?.as_slice(), 0)?)
+}
+
+//---------------- INVENTORY SUMMARY ----------------
+
+/// Total the on-hand quantities of resources conforming to each of the given `ResourceSpecification`s.
+/// If `at_location` is given, only resources currently at that location are counted.
+///
+#[hdk_extern]
+fn read_inventory_summary(InventorySummaryParams { conforms_to, at_location }: InventorySummaryParams) -> ExternResult<Vec<InventorySummary>> {
+    register_time_indexes(&TIME_INDEXES);
+    throw_if_index_read_not_permitted(read_query_access, read_agent_zome, read_network_settings_zome)?;
+
+    let located: Option<HashSet<EconomicResourceAddress>> = match &at_location {
+        Some(location) => Some(read_index::<EconomicResourceAddress, _,_,_>(location, &"location_economic_resources_at", &local_time_index_id())?
+            .into_iter().collect()),
+        None => None,
+    };
+
+    Ok(conforms_to.into_iter()
+        .map(|spec| summarize_inventory(spec, located.as_ref()))
+        .collect::<RecordAPIResult<Vec<InventorySummary>>>()?)
+}
+
+fn summarize_inventory(conforms_to: ResourceSpecificationAddress, located: Option<&HashSet<EconomicResourceAddress>>) -> RecordAPIResult<InventorySummary> {
+    let resources = query_index::<ResponseData, EconomicResourceAddress, _,_,_,_,_,_>(
+        &conforms_to,
+        &"resource_specification_conforming_resources",
+        &local_time_index_id(),
+        &read_index_target_zome,
+        &QUERY_FN_NAME,
+        false,
+    )?;
+
+    let mut onhand_quantities: Vec<QuantityValue> = vec![];
+    let quantities = resources.into_iter()
+        .filter_map(Result::ok)
+        .filter(|r| match located {
+            Some(located) => located.contains(&r.economic_resource.id),
+            None => true,
+        })
+        .filter_map(|r| r.economic_resource.onhand_quantity);
+    for quantity in quantities {
+        match onhand_quantities.iter().position(|q| q.get_unit() == quantity.get_unit()) {
+            Some(i) => onhand_quantities[i] = add(onhand_quantities[i].to_owned(), quantity)?,
+            None => onhand_quantities.push(quantity),
+        }
+    }
+
+    Ok(InventorySummary { conforms_to, onhand_quantities })
 }
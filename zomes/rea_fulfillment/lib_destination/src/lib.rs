@@ -10,8 +10,10 @@
  * @package hREA
  */
 use paste::paste;
+use hdk::prelude::{dna_info, random_bytes, EntryHash, DnaHash};
 use hdk_records::{
-    RecordAPIResult,
+    RecordAPIResult, DataIntegrityError, DnaAddressable, MaybeUndefined,
+    rpc::call_zome_method,
     records::{
         create_record,
         read_record_entry,
@@ -19,9 +21,14 @@ use hdk_records::{
         update_record,
         delete_record,
     },
+    field_indexes::{
+        sync_field_value_index,
+        find_by_field_value,
+    },
 };
 use hdk_semantic_indexes_client_lib::*;
 
+use hc_zome_rea_fulfillment_storage_consts::*;
 use hc_zome_rea_fulfillment_storage::*;
 use hc_zome_rea_fulfillment_rpc::*;
 
@@ -38,14 +45,48 @@ pub fn handle_create_fulfillment<S>(entry_def_id: S, fulfillment: CreateRequest)
     let (meta, fulfillment_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, fulfillment.to_owned())?;
 
     // link entries in the local DNA
-    let e = create_index!(fulfillment.fulfilled_by(fulfillment.get_fulfilled_by()), economic_event.fulfills(&fulfillment_address));
-    hdk::prelude::debug!("handle_create_fulfillment::fulfilled_by index (destination) {:?}", e);
+    log_index_results("handle_create_fulfillment (destination)", create_fulfillment_indexes(&fulfillment_address, &entry_resp)?);
 
     // :TODO: figure out if necessary/desirable to do bidirectional bridging between observation and other planning DNAs
 
     construct_response(&fulfillment_address, &meta, &entry_resp)
 }
 
+/// Create a `Fulfillment` from the observation side, for an event recorded before its commitment was known.
+///
+/// The record is first created in the planning DNA of the commitment being fulfilled, then replicated locally
+/// with the same nonce so that both copies share an identity. Where the planning DNA already holds a `Fulfillment`
+/// for the same event & commitment, that record is returned rather than creating a duplicate.
+///
+pub fn handle_create_fulfillment_from_event<S>(entry_def_id: S, fulfillment: CreateRequest) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    let request = CreateRequest {
+        nonce: MaybeUndefined::Some(random_bytes(32)?),
+        ..fulfillment
+    };
+
+    let origin: OriginCreateResponse = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        request.get_fulfills(),
+        &REPLICATE_CREATE_FROM_EVENT_API_METHOD,
+        CreateParams { fulfillment: request.to_owned() },
+        LinkTypes::AvailableCapability
+    )?;
+
+    if !origin.created {
+        // existing records are replicated here when created in the planning DNA
+        let origin_hash: &EntryHash = origin.fulfillment.id.as_ref();
+        let local_address = FulfillmentAddress::new(dna_info()?.hash, origin_hash.to_owned());
+        return Ok(match handle_get_fulfillment(local_address) {
+            Ok(local) => local,
+            // :TODO: repair missing replicas, which requires the origin nonce
+            Err(_) => ResponseData { fulfillment: origin.fulfillment },
+        });
+    }
+
+    handle_create_fulfillment(entry_def_id, request)
+}
+
 pub fn handle_get_fulfillment(address: FulfillmentAddress) -> RecordAPIResult<ResponseData>
 {
     let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
@@ -62,6 +103,9 @@ pub fn handle_update_fulfillment(fulfillment: UpdateRequest) -> RecordAPIResult<
 {
     let (meta, base_address, new_entry, prev_entry): (_, FulfillmentAddress, EntryData, EntryData) = update_record(&fulfillment.get_revision_id(), fulfillment.to_owned())?;
 
+    hdk_records::return_if_unchanged!(meta, *fulfillment.get_revision_id(), construct_response(&base_address, &meta, &new_entry), fulfillment);
+
+    sync_field_value_index(LinkTypes::FieldValueIndex, FULFILLS_INDEX, &base_address, Some(&new_entry), Some(&prev_entry), extract_fulfills)?;
     if new_entry.fulfilled_by != prev_entry.fulfilled_by {
         let e = update_index!(
             fulfillment
@@ -75,18 +119,135 @@ pub fn handle_update_fulfillment(fulfillment: UpdateRequest) -> RecordAPIResult<
     construct_response(&base_address, &meta, &new_entry)
 }
 
+/// Apply an update replicated from the planning DNA to the latest revision of the local copy.
+///
+/// Where the local copy has been modified since the revision last seen by the planning DNA,
+/// the update is rejected and the current local version returned with the error.
+///
+pub fn handle_replicate_update_fulfillment(params: ReplicateUpdateParams) -> RecordAPIResult<ResponseData>
+{
+    let identity_hash: &EntryHash = params.id.as_ref();
+    let local_address = FulfillmentAddress::new(dna_info()?.hash, identity_hash.to_owned());
+    let current = handle_get_fulfillment(local_address)?.fulfillment;
+
+    if let Some(last_seen) = params.last_seen_revision {
+        if last_seen != current.revision_id {
+            return Err(DataIntegrityError::stale_revision(current.revision_id.to_owned(), &current));
+        }
+    }
+
+    handle_update_fulfillment(UpdateRequest {
+        revision_id: current.revision_id,
+        ..params.fulfillment
+    })
+}
+
 pub fn handle_delete_fulfillment(revision_id: ActionHash) -> RecordAPIResult<bool>
 {
     // read any referencing indexes
     let (_meta, base_address, fulfillment) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
 
     // handle link fields
+    sync_field_value_index(LinkTypes::FieldValueIndex, FULFILLS_INDEX, &base_address, None, Some(&fulfillment), extract_fulfills)?;
     let e = update_index!(fulfillment.fulfilled_by.not(&vec![fulfillment.fulfilled_by]), economic_event.fulfills(&base_address));
     hdk::prelude::debug!("handle_delete_fulfillment::fulfilled_by index (destination) {:?}", e);
 
     delete_record::<EntryStorage>(&revision_id)
 }
 
+/// Delete a `Fulfillment` from the observation side, when the event fulfilling it is removed.
+///
+/// The originating record is removed from the planning DNA of the commitment being fulfilled first,
+/// so that it is not left orphaned. If that DNA cannot be reached the local copy is left in place.
+///
+pub fn handle_delete_fulfillment_from_event(address: FulfillmentAddress) -> RecordAPIResult<bool>
+{
+    let ResponseData { fulfillment } = handle_get_fulfillment(address.to_owned())?;
+
+    let origin_deleted: bool = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        &fulfillment.fulfills,
+        &REPLICATE_DELETE_FROM_EVENT_API_METHOD,
+        ByAddress { address },
+        LinkTypes::AvailableCapability
+    )?;
+    hdk::prelude::debug!("handle_delete_fulfillment_from_event::call_zome_method::{:?} {:?}", REPLICATE_DELETE_FROM_EVENT_API_METHOD, origin_deleted);
+
+    handle_delete_fulfillment(fulfillment.revision_id)
+}
+
+/// Report on the local copies of `Fulfillment`s held by a planning DNA, for checking the
+/// consistency of the two DNAs. Statuses are addressed in this DNA.
+///
+/// Besides each record and its event index, other records linked from the same event which
+/// fulfill commitments in the sender's DNA are returned as `related`.
+///
+pub fn handle_verify_references(references: Vec<FulfillmentReference>) -> RecordAPIResult<Vec<ReferenceStatus<FulfillmentAddress>>>
+{
+    let local_dna = dna_info()?.hash;
+    references.into_iter().map(|reference| {
+        let identity_hash: &EntryHash = reference.id.as_ref();
+        let address = FulfillmentAddress::new(local_dna.to_owned(), identity_hash.to_owned());
+        let origin_dna: &DnaHash = reference.fulfills.as_ref();
+
+        let linked: Vec<FulfillmentAddress> = read_index!(economic_event(&reference.fulfilled_by).fulfills)?;
+        let related = linked.iter()
+            .filter(|l| **l != address)
+            .filter(|l| match handle_get_fulfillment((*l).to_owned()) {
+                Ok(ResponseData { fulfillment }) => AsRef::<DnaHash>::as_ref(&fulfillment.fulfills) == origin_dna,
+                Err(_) => false,
+            })
+            .cloned()
+            .collect();
+
+        Ok(ReferenceStatus {
+            record_exists: handle_get_fulfillment(address.to_owned()).is_ok(),
+            index_exists: linked.contains(&address),
+            address,
+            related,
+        })
+    }).collect()
+}
+
+/// Index writes made for a `Fulfillment` when it is created, and re-run to resync its indexes.
+///
+fn create_fulfillment_indexes(base_address: &FulfillmentAddress, entry: &EntryData) -> RecordAPIResult<RecordIndexResults>
+{
+    sync_field_value_index(LinkTypes::FieldValueIndex, FULFILLS_INDEX, base_address, Some(entry), None, extract_fulfills)?;
+    Ok(vec![
+        ("fulfilled_by", create_index!(fulfillment.fulfilled_by(&entry.fulfilled_by), economic_event.fulfills(base_address))),
+    ])
+}
+
+/// Rebuild the indexes of a `Fulfillment` from its latest revision.
+///
+pub fn handle_resync_fulfillment_indexes(address: FulfillmentAddress) -> RecordAPIResult<bool>
+{
+    let (_meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, FulfillmentAddress>(address.as_ref())?;
+    throw_if_resync_failed(create_fulfillment_indexes(&base_address, &entry)?)
+}
+
+/// Read the local copies of all `Fulfillment`s of a `Commitment` in a planning DNA, for checking
+/// for dependents before the commitment is deleted.
+///
+pub fn handle_read_commitment_fulfillments(commitment: CommitmentAddress) -> RecordAPIResult<Vec<Response>>
+{
+    let addresses: Vec<FulfillmentAddress> = find_by_field_value(LinkTypes::FieldValueIndex, FULFILLS_INDEX, commitment.to_string())?;
+    addresses.into_iter()
+        .map(handle_get_fulfillment)
+        // records deleted since being indexed are not dependents
+        .filter(|r| !matches!(r, Err(DataIntegrityError::EntryNotFound)))
+        .map(|r| r.map(|ResponseData { fulfillment }| fulfillment))
+        .collect()
+}
+
+/// field value index of the `Commitment` each `Fulfillment` fulfills
+const FULFILLS_INDEX: &str = "fulfillment.fulfills";
+
+/// field extractor for `FULFILLS_INDEX`
+fn extract_fulfills(e: &EntryData) -> Option<String> {
+    Some(e.fulfills.to_string())
+}
+
 /// Properties accessor for zome config.
 fn read_economic_event_index_zome(conf: DnaConfigSliceObservation) -> Option<String> {
     Some(conf.fulfillment.economic_event_index_zome)
@@ -10,8 +10,10 @@
  * @package hREA
  */
 use paste::paste;
+use hdk::prelude::{query, create_entry, dna_info, ChainQueryFilter, EntryHash, DnaHash};
 use hdk_records::{
-    RecordAPIResult, OtherCellResult,
+    generate_patch_builder,
+    RecordAPIResult, OtherCellResult, DataIntegrityError, DnaAddressable,
     MaybeUndefined,
     records::{
         create_record,
@@ -20,7 +22,7 @@ use hdk_records::{
         update_record,
         delete_record,
     },
-    rpc::call_zome_method,
+    rpc::{call_zome_method, call_local_zome_method},
 };
 use hdk_semantic_indexes_client_lib::*;
 
@@ -43,29 +45,66 @@ pub fn handle_create_fulfillment<S>(entry_def_id: S, fulfillment: CreateRequest)
     let (meta, fulfillment_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, fulfillment.to_owned())?;
 
     // link entries in the local DNA
-    let e = create_index!(fulfillment.fulfills(fulfillment.get_fulfills()), commitment.fulfilled_by(&fulfillment_address));
-    hdk::prelude::debug!("handle_create_fulfillment::fulfills index (origin) {:?}", e);
+    log_index_results("handle_create_fulfillment (origin)", create_fulfillment_indexes(&fulfillment_address, &entry_resp));
 
     // :TODO: report any error
     // update in the associated foreign DNA as well
-    let pingback: OtherCellResult<ResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
-        fulfillment.get_fulfilled_by(),
-        &REPLICATE_CREATE_API_METHOD,
-        CreateParams { fulfillment: CreateRequest {
-            fulfilled_by: entry_resp.fulfilled_by.to_owned(),
-            fulfills: entry_resp.fulfills.to_owned(),
-            resource_quantity: entry_resp.resource_quantity.to_owned().into(),
-            effort_quantity: entry_resp.effort_quantity.to_owned().into(),
-            note: entry_resp.note.to_owned().into(),
-            nonce: MaybeUndefined::Some(entry_resp._nonce.to_owned()),
-        } },
-        LinkTypes::AvailableCapability
-    );
+    let pingback = replicate_fulfillment(&entry_resp);
     hdk::prelude::debug!("handle_create_fulfillment::call_zome_method::{:?} {:?}", REPLICATE_CREATE_API_METHOD, pingback);
+    if let Ok(ResponseData { fulfillment: remote }) = pingback {
+        write_remote_revision(fulfillment_address.as_ref(), remote.revision_id)?;
+    }
 
     construct_response(&fulfillment_address, &meta, &entry_resp)
 }
 
+/// Create a `Fulfillment` requested from the observation DNA, for an event which was recorded prior to
+/// being linked to its commitment. Unlike `handle_create_fulfillment`, the observation DNA is not
+/// pinged back since it manages its own copy of the record.
+///
+/// If a `Fulfillment` for the same event & commitment already exists, it is returned instead.
+///
+pub fn handle_create_fulfillment_from_event<S>(entry_def_id: S, fulfillment: CreateRequest) -> RecordAPIResult<OriginCreateResponse>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    let existing: Vec<FulfillmentAddress> = read_index!(commitment(fulfillment.get_fulfills()).fulfilled_by)?;
+    for address in existing {
+        if let Ok(ResponseData { fulfillment: record }) = handle_get_fulfillment(address) {
+            if record.fulfilled_by == *fulfillment.get_fulfilled_by() {
+                return Ok(OriginCreateResponse { fulfillment: record, created: false });
+            }
+        }
+    }
+
+    let (meta, fulfillment_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, fulfillment.to_owned())?;
+
+    // link entries in the local DNA
+    log_index_results("handle_create_fulfillment_from_event (origin)", create_fulfillment_indexes(&fulfillment_address, &entry_resp));
+
+    Ok(OriginCreateResponse {
+        fulfillment: construct_response(&fulfillment_address, &meta, &entry_resp)?.fulfillment,
+        created: true,
+    })
+}
+
+/// Index writes made for a `Fulfillment` when it is created, and re-run to resync its indexes.
+///
+fn create_fulfillment_indexes(base_address: &FulfillmentAddress, entry: &EntryData) -> RecordIndexResults
+{
+    vec![
+        ("fulfills", create_index!(fulfillment.fulfills(&entry.fulfills), commitment.fulfilled_by(base_address))),
+    ]
+}
+
+/// Rebuild the local indexes of a `Fulfillment` from its latest revision. The copy in the
+/// observation DNA resyncs its own indexes.
+///
+pub fn handle_resync_fulfillment_indexes(address: FulfillmentAddress) -> RecordAPIResult<bool>
+{
+    let (_meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, FulfillmentAddress>(address.as_ref())?;
+    throw_if_resync_failed(create_fulfillment_indexes(&base_address, &entry))
+}
+
 pub fn handle_get_fulfillment(address: FulfillmentAddress) -> RecordAPIResult<ResponseData>
 {
     let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
@@ -82,6 +121,8 @@ pub fn handle_update_fulfillment(fulfillment: UpdateRequest) -> RecordAPIResult<
 {
     let (meta, base_address, new_entry, prev_entry): (_, FulfillmentAddress, EntryData, EntryData) = update_record(&fulfillment.get_revision_id(), fulfillment.to_owned())?;
 
+    hdk_records::return_if_unchanged!(meta, *fulfillment.get_revision_id(), construct_response(&base_address, &meta, &new_entry), fulfillment);
+
     // update commitment indexes in local DNA
     if new_entry.fulfills != prev_entry.fulfills {
         let e = update_index!(
@@ -94,21 +135,50 @@ pub fn handle_update_fulfillment(fulfillment: UpdateRequest) -> RecordAPIResult<
     }
 
     // update fulfillment records in remote DNA (and by proxy, event indexes in remote DNA)
-    if new_entry.fulfilled_by != prev_entry.fulfilled_by {
+    if replicated_fields_changed(&new_entry, &prev_entry) {
+        let identity_hash: &EntryHash = base_address.as_ref();
         let pingback: OtherCellResult<ResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
             // :TODO: update to intelligently call remote DNAs if new & old target record are not in same network
             &prev_entry.fulfilled_by,
             &REPLICATE_UPDATE_API_METHOD,
-            UpdateParams { fulfillment: fulfillment.to_owned() },
+            ReplicateUpdateParams {
+                id: base_address.to_owned(),
+                last_seen_revision: read_remote_revision(identity_hash)?,
+                fulfillment: fulfillment.to_owned(),
+            },
             LinkTypes::AvailableCapability
         );
-        // :TODO: report any error
         hdk::prelude::debug!("handle_update_fulfillment::call_zome_method::{:?} {:?}", REPLICATE_UPDATE_API_METHOD, pingback);
+        match pingback {
+            Ok(ResponseData { fulfillment: remote }) => write_remote_revision(identity_hash, remote.revision_id)?,
+            // abort the update so that the caller may merge the two versions, or retry once the remote DNA is reachable
+            Err(e) => {
+                let local = construct_response(&base_address, &meta, &new_entry)?.fulfillment;
+                return Err(DataIntegrityError::replicated_update_failed(e, &local));
+            },
+        }
     }
 
     construct_response(&base_address, &meta, &new_entry)
 }
 
+generate_patch_builder!(UpdateRequest {
+    fulfilled_by,
+    fulfills,
+    resource_quantity,
+    effort_quantity,
+    note
+});
+
+/// Apply a set of `PatchOp`s to the latest revision of a `Fulfillment`.
+/// The patch is converted to an `UpdateRequest` and applied via `handle_update_fulfillment`.
+///
+pub fn handle_patch_fulfillment(address: FulfillmentAddress, ops: Vec<PatchOp>) -> RecordAPIResult<ResponseData>
+{
+    let revision_id = handle_get_fulfillment(address)?.fulfillment.revision_id;
+    handle_update_fulfillment(build_patch_update(revision_id, ops)?)
+}
+
 pub fn handle_delete_fulfillment(revision_id: ActionHash) -> RecordAPIResult<bool>
 {
     let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
@@ -130,6 +200,156 @@ pub fn handle_delete_fulfillment(revision_id: ActionHash) -> RecordAPIResult<boo
     delete_record::<EntryStorage>(&revision_id)
 }
 
+/// Delete a `Fulfillment` requested from the observation DNA, when the event fulfilling it is removed.
+/// Unlike `handle_delete_fulfillment`, the observation DNA is not pinged back since it removes its own copy.
+///
+/// Returns `false` if this DNA holds no record with the identity of `address`.
+///
+pub fn handle_delete_fulfillment_from_event(address: FulfillmentAddress) -> RecordAPIResult<bool>
+{
+    let identity_hash: &EntryHash = address.as_ref();
+    let local_address = FulfillmentAddress::new(dna_info()?.hash, identity_hash.to_owned());
+    let revision_id = match handle_get_fulfillment(local_address) {
+        Ok(ResponseData { fulfillment }) => fulfillment.revision_id,
+        Err(DataIntegrityError::EntryNotFound) => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+
+    // update commitment indexes in local DNA
+    let e = update_index!(fulfillment.fulfills.not(&vec![entry.fulfills]), commitment.fulfilled_by(&base_address));
+    hdk::prelude::debug!("handle_delete_fulfillment_from_event::fulfills index (origin) {:?}", e);
+
+    delete_record::<EntryStorage>(&revision_id)
+}
+
+/// Check a batch of `Fulfillment`s in this DNA against their copies in the observation DNAs
+/// holding the events which fulfill them.
+///
+/// Records are visited in creation order, continuing from `cursor`. The resulting report's
+/// `missing_remote_record` may be passed to `handle_replicate_fulfillments` to repair them.
+///
+pub fn handle_check_cross_dna_integrity(params: IntegrityCheckParams) -> RecordAPIResult<IntegrityReport<FulfillmentAddress>>
+{
+    if params.record_type != FULFILLMENT_ENTRY_TYPE {
+        return Err(DataIntegrityError::UnsupportedRecordType(params.record_type));
+    }
+
+    let batch: IndexedAddresses<FulfillmentAddress> = call_local_zome_method(
+        read_fulfillment_index_zome,
+        "read_indexed_addresses",
+        ReindexParams { after: params.cursor, limit: params.batch_size },
+    )?;
+
+    let local_dna = dna_info()?.hash;
+    let mut report = IntegrityReport::new(FULFILLMENT_ENTRY_TYPE, batch.next_cursor);
+
+    // group records by the DNA holding their other copy
+    let mut references: Vec<(DnaHash, Vec<FulfillmentReference>)> = vec![];
+    for address in batch.addresses {
+        let fulfillment = match handle_get_fulfillment(address.to_owned()) {
+            Ok(ResponseData { fulfillment }) => fulfillment,
+            Err(e) => {
+                report.record_failure(address, e);
+                continue;
+            },
+        };
+        let remote_dna: &DnaHash = fulfillment.fulfilled_by.as_ref();
+        let reference = FulfillmentReference {
+            id: fulfillment.id,
+            fulfilled_by: fulfillment.fulfilled_by.to_owned(),
+            fulfills: fulfillment.fulfills,
+        };
+        match references.iter_mut().find(|(dna, _)| dna == remote_dna) {
+            Some((_, group)) => group.push(reference),
+            None => references.push((remote_dna.to_owned(), vec![reference])),
+        }
+    }
+
+    for (_, group) in references {
+        let statuses: OtherCellResult<Vec<ReferenceStatus<FulfillmentAddress>>> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+            &group[0].fulfilled_by,
+            &VERIFY_REFERENCES_API_METHOD,
+            VerifyReferencesParams { references: group.to_owned() },
+            LinkTypes::AvailableCapability
+        );
+        match statuses {
+            Ok(statuses) => report.record_statuses(statuses, &local_dna, |address| handle_get_fulfillment(address.to_owned()).is_ok()),
+            // records held by other DNAs can still be checked if one is unreachable
+            Err(e) => for reference in group {
+                report.record_failure(reference.id, &e);
+            },
+        }
+    }
+
+    Ok(report)
+}
+
+/// Re-send `Fulfillment`s to the observation DNAs holding the events which fulfill them, for
+/// repairing records found to be missing there. Returns the number of records replicated.
+///
+pub fn handle_replicate_fulfillments(addresses: Vec<FulfillmentAddress>) -> RecordAPIResult<u32>
+{
+    let mut replicated = 0;
+    for address in addresses {
+        let (_meta, _base_address, entry) = read_record_entry::<EntryData, EntryStorage, FulfillmentAddress>(address.as_ref())?;
+        let ResponseData { fulfillment: remote } = replicate_fulfillment(&entry)?;
+        write_remote_revision(address.as_ref(), remote.revision_id)?;
+        replicated += 1;
+    }
+    Ok(replicated)
+}
+
+/// Create the copy of a `Fulfillment` held by the observation DNA of the event which fulfills it.
+/// The nonce of the local entry is sent so that both copies share an identity.
+///
+fn replicate_fulfillment(entry: &EntryData) -> OtherCellResult<ResponseData> {
+    call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        &entry.fulfilled_by,
+        &REPLICATE_CREATE_API_METHOD,
+        CreateParams { fulfillment: CreateRequest {
+            fulfilled_by: entry.fulfilled_by.to_owned(),
+            fulfills: entry.fulfills.to_owned(),
+            resource_quantity: entry.resource_quantity.to_owned().into(),
+            effort_quantity: entry.effort_quantity.to_owned().into(),
+            note: entry.note.to_owned().into(),
+            nonce: MaybeUndefined::Some(entry._nonce.to_owned()),
+        } },
+        LinkTypes::AvailableCapability
+    )
+}
+
+/// Determine whether an update modified any fields held in the other DNA's copy of the record.
+fn replicated_fields_changed(new_entry: &EntryData, prev_entry: &EntryData) -> bool {
+    new_entry.fulfilled_by != prev_entry.fulfilled_by
+        || new_entry.fulfills != prev_entry.fulfills
+        || new_entry.resource_quantity != prev_entry.resource_quantity
+        || new_entry.effort_quantity != prev_entry.effort_quantity
+        || new_entry.note != prev_entry.note
+}
+
+/// Read the revision of the remote copy of a `Fulfillment` last seen by the current agent, if any.
+///
+fn read_remote_revision(fulfillment: &EntryHash) -> RecordAPIResult<Option<ActionHash>> {
+    let filter = ChainQueryFilter::new()
+        .entry_type(EntryTypesUnit::RemoteRevision.try_into()?)
+        .include_entries(true);
+    Ok(query(filter)?.iter().rev()
+        .filter_map(|record| record.entry().to_app_option::<RemoteRevision>().ok().flatten())
+        .find(|seen| seen.fulfillment == *fulfillment)
+        .map(|seen| seen.revision_id))
+}
+
+/// Record the revision of the remote copy of a `Fulfillment` after it has been synchronised.
+///
+fn write_remote_revision(fulfillment: &EntryHash, revision_id: ActionHash) -> RecordAPIResult<()> {
+    create_entry(EntryTypes::RemoteRevision(RemoteRevision {
+        fulfillment: fulfillment.to_owned(),
+        revision_id,
+    }))?;
+    Ok(())
+}
+
 /// Properties accessor for zome config.
 fn read_commitment_index_zome(conf: DnaConfigSlicePlanning) -> Option<String> {
     Some(conf.fulfillment.commitment_index_zome)
@@ -11,9 +11,14 @@ use holochain_serialized_bytes::prelude::*;
 use serde_bytes::ByteBuf;
 use serde_maybe_undefined::{MaybeUndefined};
 use vf_measurement::QuantityValue;
+use vf_attributes_hdk::EntryHash;
 pub use vf_attributes_hdk::{
-    ActionHash, ByAction, ByAddress, ByRevision, RecordMeta, RevisionMeta,
+    ErrorCode, ErrorEnvelope,
+    PatchOp, PatchParams,
+    ActionHash, ByAction, ByAddress, ByAddresses, ByRevision, RecordMeta, RevisionMeta,
+    ReindexParams, IndexedAddresses, IntegrityCheckParams, IntegrityReport, ReferenceStatus,
     EconomicEventAddress,
+    EconomicResourceAddress,
     CommitmentAddress,
 };
 
@@ -31,6 +36,40 @@ pub struct UpdateParams {
     pub fulfillment: UpdateRequest,
 }
 
+/// Update sent to the DNA holding the other copy of a `Fulfillment`.
+///
+/// `last_seen_revision` is the revision of the receiving copy last seen by the sender. Where it is
+/// provided and the receiving copy has since been modified, the update is rejected as a conflict.
+/// The `revision_id` of the nested `fulfillment` is ignored.
+///
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplicateUpdateParams {
+    pub id: FulfillmentAddress,
+    #[serde(default)]
+    pub last_seen_revision: Option<ActionHash>,
+    pub fulfillment: UpdateRequest,
+}
+
+/// Request sent to the DNA holding the other copy of a set of `Fulfillment`s, to report on the
+/// status of its copies as `ReferenceStatus`es.
+///
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyReferencesParams {
+    pub references: Vec<FulfillmentReference>,
+}
+
+/// A `Fulfillment` as known to the sender, along with the records it links.
+///
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FulfillmentReference {
+    pub id: FulfillmentAddress,
+    pub fulfilled_by: EconomicEventAddress,
+    pub fulfills: CommitmentAddress,
+}
+
 //---------------- EXTERNAL RECORD STRUCTURE ----------------
 
 // Export external type interface to allow consuming zomes to easily import & define zome API
@@ -42,13 +81,17 @@ pub use vf_attributes_hdk::{ FulfillmentAddress };
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     pub id: FulfillmentAddress,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     pub meta: RecordMeta,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "fulfilled_by"))]
     pub fulfilled_by: EconomicEventAddress,
     pub fulfills: CommitmentAddress,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_quantity"))]
     pub resource_quantity: Option<QuantityValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "effort_quantity"))]
     pub effort_quantity: Option<QuantityValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub note: Option<String>,
@@ -64,6 +107,16 @@ pub struct ResponseData {
     pub fulfillment: Response,
 }
 
+/// Response from the planning DNA when a `Fulfillment` is created from the observation side.
+/// `created` is `false` where an existing `Fulfillment` for the same event & commitment was returned instead.
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct OriginCreateResponse {
+    pub fulfillment: Response,
+    pub created: bool,
+}
+
 //---------------- CREATE REQUEST ----------------
 
 /// I/O struct to describe the complete input record, including all managed links
@@ -71,13 +124,16 @@ pub struct ResponseData {
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateRequest {
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "fulfilled_by"))]
     pub fulfilled_by: EconomicEventAddress,
     pub fulfills: CommitmentAddress,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_quantity"))]
     pub resource_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "effort_quantity"))]
     pub effort_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
@@ -110,18 +166,22 @@ impl<'a> CreateRequest {
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateRequest {
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "fulfilled_by"))]
     pub fulfilled_by: MaybeUndefined<EconomicEventAddress>, // note this setup allows None to be passed but `update_with` ignores it
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
     pub fulfills: MaybeUndefined<CommitmentAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_quantity"))]
     pub resource_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "effort_quantity"))]
     pub effort_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
@@ -144,3 +204,23 @@ pub struct QueryParams {
     pub fulfills: Option<CommitmentAddress>,
     pub fulfilled_by: Option<EconomicEventAddress>,
 }
+
+/// Query for the `Fulfillment`s of every `EconomicEvent` affecting an `EconomicResource`,
+/// paginated as for other list queries.
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceFulfillmentsParams {
+    pub resource: EconomicResourceAddress,
+    #[serde(default)]
+    pub last: Option<usize>,
+    #[serde(default)]
+    pub before: Option<EntryHash>,
+}
+
+#[cfg(all(test, feature = "snake_case_aliases"))]
+mod tests {
+    use super::*;
+
+    vf_attributes_hdk::snake_case_alias_tests!(Response, OriginCreateResponse, CreateRequest, UpdateRequest);
+}
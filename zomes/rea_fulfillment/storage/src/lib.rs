@@ -65,6 +65,16 @@ pub struct EntryData {
 
 generate_record_entry!(EntryData, FulfillmentAddress, EntryStorage);
 
+/// Revision of the copy of a `Fulfillment` held in the other DNA, as last seen by the authoring agent.
+/// Kept private to the agent's source chain, since it only serves to detect conflicting edits.
+///
+#[hdk_entry_helper]
+#[derive(Clone)]
+pub struct RemoteRevision {
+    pub fulfillment: EntryHash,
+    pub revision_id: ActionHash,
+}
+
 //---------------- Holochain App Entry And Link Types Setup ----------------
 
 
@@ -73,7 +83,9 @@ generate_record_entry!(EntryData, FulfillmentAddress, EntryStorage);
 pub enum EntryTypes {
     Fulfillment(EntryStorage),
     #[entry_def(visibility = "private")]
-    AvailableCapability(AvailableCapability)
+    AvailableCapability(AvailableCapability),
+    #[entry_def(visibility = "private")]
+    RemoteRevision(RemoteRevision),
 }
 
 impl From<EntryStorage> for EntryTypes
@@ -96,7 +108,10 @@ impl TryFrom<AvailableCapability> for EntryTypes {
 pub enum LinkTypes {
     // relates to dna-auth-resolver mixin
     // and remote authorizations
-    AvailableCapability
+    AvailableCapability,
+    // exact-match lookup of records by field value
+    // @see hdk_records::field_indexes
+    FieldValueIndex,
 }
 
 
@@ -10,3 +10,7 @@ pub const FULFILLMENT_ENTRY_TYPE: &str = "fulfillment";
 pub const REPLICATE_CREATE_API_METHOD: &str = "create_fulfillment";
 pub const REPLICATE_UPDATE_API_METHOD: &str = "update_fulfillment";
 pub const REPLICATE_DELETE_API_METHOD: &str = "delete_fulfillment";
+pub const REPLICATE_CREATE_FROM_EVENT_API_METHOD: &str = "create_fulfillment_from_event";
+pub const REPLICATE_DELETE_FROM_EVENT_API_METHOD: &str = "delete_fulfillment_from_event";
+pub const VERIFY_REFERENCES_API_METHOD: &str = "verify_fulfillment_references";
+pub const READ_COMMITMENT_FULFILLMENTS_API_METHOD: &str = "read_commitment_fulfillments";
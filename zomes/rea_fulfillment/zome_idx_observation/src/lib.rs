@@ -12,3 +12,25 @@ use hdk_semantic_indexes_zome_lib::ByAddress; // disambiguate from RPC query str
 struct Fulfillment {
     fulfilled_by: Local<economic_event, fulfills>,
 }
+
+/// Query the `Fulfillment`s of all `EconomicEvent`s affecting an `EconomicResource`, most recent first.
+/// Fulfillments of several of the resource's events are returned once.
+///
+#[hdk_extern]
+fn query_resource_fulfillments(ResourceFulfillmentsParams { resource, last, before }: ResourceFulfillmentsParams) -> ExternResult<QueryResults> {
+    register_time_indexes(&TIME_INDEXES);
+    throw_if_query_not_permitted(read_query_access, read_agent_zome, read_network_settings_zome)?;
+
+    let events = read_index::<EconomicEventAddress, _,_,_>(&resource, &"economic_resource_affected_by", &local_time_index_id())?;
+    let entries_result = query_indexes_merged::<ResponseData, FulfillmentAddress, _,_,_,_,_,_>(
+        events.as_slice(),
+        &"economic_event_fulfills",
+        &local_time_index_id(),
+        &read_index_target_zome,
+        &QUERY_FN_NAME,
+        before,
+        last.unwrap_or(PAGE_SIZE),
+    );
+
+    Ok(handle_list_output(entries_result?.as_slice(), 0)?)
+}
@@ -20,6 +20,11 @@ fn fulfillment_created(CreateParams { fulfillment }: CreateParams) -> ExternResu
     Ok(handle_create_fulfillment(FULFILLMENT_ENTRY_TYPE, fulfillment)?)
 }
 
+#[hdk_extern]
+fn create_fulfillment_from_event(CreateParams { fulfillment }: CreateParams) -> ExternResult<ResponseData> {
+    Ok(handle_create_fulfillment_from_event(FULFILLMENT_ENTRY_TYPE, fulfillment)?)
+}
+
 #[hdk_extern]
 fn get_fulfillment(ByAddress { address }: ByAddress<FulfillmentAddress>) -> ExternResult<ResponseData> {
     Ok(handle_get_fulfillment(address)?)
@@ -31,11 +36,31 @@ fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<Response
 }
 
 #[hdk_extern]
-fn fulfillment_updated(UpdateParams { fulfillment }: UpdateParams) -> ExternResult<ResponseData> {
-    Ok(handle_update_fulfillment(fulfillment)?)
+fn fulfillment_updated(params: ReplicateUpdateParams) -> ExternResult<ResponseData> {
+    Ok(handle_replicate_update_fulfillment(params)?)
 }
 
 #[hdk_extern]
 fn fulfillment_deleted(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
     Ok(handle_delete_fulfillment(revision_id)?)
 }
+
+#[hdk_extern]
+fn delete_fulfillment_from_event(ByAddress { address }: ByAddress<FulfillmentAddress>) -> ExternResult<bool> {
+    Ok(handle_delete_fulfillment_from_event(address)?)
+}
+
+#[hdk_extern]
+fn verify_references(VerifyReferencesParams { references }: VerifyReferencesParams) -> ExternResult<Vec<ReferenceStatus<FulfillmentAddress>>> {
+    Ok(handle_verify_references(references)?)
+}
+
+#[hdk_extern]
+fn read_commitment_fulfillments(ByAddress { address }: ByAddress<CommitmentAddress>) -> ExternResult<Vec<Response>> {
+    Ok(handle_read_commitment_fulfillments(address)?)
+}
+
+#[hdk_extern]
+fn resync_record_indexes(ByAddress { address }: ByAddress<FulfillmentAddress>) -> ExternResult<bool> {
+    Ok(handle_resync_fulfillment_indexes(address)?)
+}
@@ -18,11 +18,21 @@ fn create_fulfillment(CreateParams { fulfillment }: CreateParams) -> ExternResul
     Ok(handle_create_fulfillment(FULFILLMENT_ENTRY_TYPE, fulfillment)?)
 }
 
+#[hdk_extern]
+fn fulfillment_from_event_created(CreateParams { fulfillment }: CreateParams) -> ExternResult<OriginCreateResponse> {
+    Ok(handle_create_fulfillment_from_event(FULFILLMENT_ENTRY_TYPE, fulfillment)?)
+}
+
 #[hdk_extern]
 fn get_fulfillment(ByAddress { address }: ByAddress<FulfillmentAddress>) -> ExternResult<ResponseData> {
     Ok(handle_get_fulfillment(address)?)
 }
 
+#[hdk_extern]
+fn resync_record_indexes(ByAddress { address }: ByAddress<FulfillmentAddress>) -> ExternResult<bool> {
+    Ok(handle_resync_fulfillment_indexes(address)?)
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
@@ -33,7 +43,29 @@ fn update_fulfillment(UpdateParams { fulfillment }: UpdateParams) -> ExternResul
     Ok(handle_update_fulfillment(fulfillment)?)
 }
 
+#[hdk_extern]
+fn patch_record(PatchParams { address, ops }: PatchParams<FulfillmentAddress>) -> ExternResult<ResponseData> {
+    Ok(handle_patch_fulfillment(address, ops)?)
+}
+
 #[hdk_extern]
 fn delete_fulfillment(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
     Ok(handle_delete_fulfillment(revision_id)?)
 }
+
+#[hdk_extern]
+fn fulfillment_from_event_deleted(ByAddress { address }: ByAddress<FulfillmentAddress>) -> ExternResult<bool> {
+    Ok(handle_delete_fulfillment_from_event(address)?)
+}
+
+// :NOTE: administrative operation, should not be exposed to remote DNAs
+#[hdk_extern]
+fn check_cross_dna_integrity(params: IntegrityCheckParams) -> ExternResult<IntegrityReport<FulfillmentAddress>> {
+    Ok(handle_check_cross_dna_integrity(params)?)
+}
+
+// :NOTE: administrative operation, should not be exposed to remote DNAs
+#[hdk_extern]
+fn replicate_records(ByAddresses { addresses }: ByAddresses<FulfillmentAddress>) -> ExternResult<u32> {
+    Ok(handle_replicate_fulfillments(addresses)?)
+}
@@ -8,8 +8,11 @@
  * @package hREA
  */
 use hdi::prelude::*;
+use hdk_rate_limits::validate_creation_rate;
+use hc_zome_rea_intent_storage_consts::INTENT_ENTRY_TYPE;
 use hc_zome_rea_intent_storage::{
-    EntryStorage, EntryTypes, EntryTypesUnit, Identified, LinkTypes,
+    EntryStorage, EntryTypes, EntryTypesUnit, Identified, Identifiable, LinkTypes,
+    check_validation_hooks,
 };
 
 #[hdk_extern]
@@ -40,7 +43,9 @@ fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
         Op::RegisterDeleteLink { .. } => Ok(ValidateCallbackResult::Valid),
         Op::RegisterUpdate { .. } => Ok(ValidateCallbackResult::Valid),
         Op::RegisterDelete { .. } => Ok(ValidateCallbackResult::Valid),
-        Op::RegisterAgentActivity { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterAgentActivity(RegisterAgentActivity { action, .. }) => validate_creation_rate(
+            &action, &ScopedEntryDefIndex::try_from(&EntryTypesUnit::Intent)?, INTENT_ENTRY_TYPE,
+        ),
     }
 }
 
@@ -51,6 +56,7 @@ fn validate_entry(entry: Entry) -> ExternResult<ValidateCallbackResult> {
             record
                 .validate_or_fields()
                 .and_then(|()| record.validate_action())
+                .and_then(|()| check_validation_hooks(&record, &record.validation_hooks()))
                 .and_then(|()| Ok(ValidateCallbackResult::Valid))
                 .or_else(|e| Ok(ValidateCallbackResult::Invalid(e)))
         }
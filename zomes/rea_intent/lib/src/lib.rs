@@ -8,7 +8,9 @@
  */
 use paste::paste;
 use hdk_records::{
-    RecordAPIResult, MaybeUndefined, SignedActionHashed,
+    generate_patch_builder,
+    RecordAPIResult, DataIntegrityError, MaybeUndefined, SignedActionHashed, OtherCellResult,
+    DnaAddressable,
     records::{
         create_record,
         read_record_entry,
@@ -17,61 +19,173 @@ use hdk_records::{
         delete_record,
     },
     metadata::read_revision_metadata_abbreviated,
+    history::read_revision_window,
+    record_interface::Updateable,
+    rate_limits::throw_if_creation_rate_exceeded,
+    rpc::{call_local_zome_method, call_zome_method},
+    field_indexes::{
+        sync_field_value_index,
+        find_by_field_value,
+    },
+    dependencies::{
+        summarize_dependents,
+        throw_if_dependents,
+        cascade_delete_dependents,
+        throw_if_cascade_failed,
+    },
 };
 use hdk_semantic_indexes_client_lib::*;
 
 use hc_zome_rea_intent_storage::*;
 use hc_zome_rea_intent_rpc::*;
+use hc_zome_rea_satisfaction_rpc::{
+    ByAddress,
+    ResponseData as SatisfactionResponse,
+};
+use hc_zome_rea_network_settings_lib::NetworkSettingsReader;
+use vf_measurement::QuantityValue;
 
 /// properties accessor for zome config
 fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
     Some(conf.intent.index_zome)
 }
 
+/// properties accessor for zome config
+fn read_network_settings_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.intent.network_settings_zome
+}
+
+/// field value index for exact-match lookups of records by `resource_conforms_to`
+const RESOURCE_CONFORMS_TO_INDEX: &str = "intent.resource_conforms_to";
+
+/// field extractor for `RESOURCE_CONFORMS_TO_INDEX`. Cancelled intents are not indexed, and so cannot be matched.
+fn extract_resource_conforms_to(e: &EntryData) -> Option<String> {
+    if e.cancelled { return None; }
+    e.resource_conforms_to.as_ref().map(|spec| spec.to_string())
+}
+
+derived_index!(intent {
+    field: resource_classified_as,
+    index: classified_as,
+    path: classification_index_paths
+}<IntentClassificationId>);
+
 pub fn handle_create_intent<S>(entry_def_id: S, intent: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
+    throw_if_creation_rate_exceeded(&EntryTypesUnit::Intent, entry_def_id.as_ref())?;
     let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, intent.to_owned())?;
 
     // handle link fields
     // :TODO: improve error handling
-
-    if let CreateRequest { provider: MaybeUndefined::Some(provider), .. } = &intent {
-        create_index!(intent.provider(provider), agent.intents_as_provider(&base_address))?;
-    };
-    if let CreateRequest { receiver: MaybeUndefined::Some(receiver), .. } = &intent {
-        create_index!(intent.receiver(receiver), agent.intents_as_receiver(&base_address))?;
-    };
-    if let CreateRequest { input_of: MaybeUndefined::Some(input_of), .. } = &intent {
-        let e = create_index!(intent.input_of(input_of), process.intended_inputs(&base_address));
-        hdk::prelude::debug!("handle_create_intent::input_of index {:?}", e);
-    };
-    if let CreateRequest { output_of: MaybeUndefined::Some(output_of), .. } = &intent {
-        let e = create_index!(intent.output_of(output_of), process.intended_outputs(&base_address));
-        hdk::prelude::debug!("handle_create_intent::output_of index {:?}", e);
-    };
+    log_index_results("handle_create_intent", create_intent_indexes(&base_address, &entry_resp)?);
+    let e = create_intent_classified_as_index(&base_address, &entry_resp);
+    hdk::prelude::debug!("handle_create_intent::classified_as index {:?}", e);
 
     // return entire record structure
     construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
 }
 
+/// Index writes made for an `Intent` when it is created, and re-run to resync its indexes.
+///
+fn create_intent_indexes(base_address: &IntentAddress, entry: &EntryData) -> RecordAPIResult<RecordIndexResults>
+{
+    sync_field_value_index(LinkTypes::FieldValueIndex, RESOURCE_CONFORMS_TO_INDEX, base_address, Some(entry), None, extract_resource_conforms_to)?;
+
+    let mut results = vec![];
+    if let Some(provider) = &entry.provider {
+        results.push(("provider", Ok(create_index!(intent.provider(provider), agent.intents_as_provider(base_address))?)));
+    }
+    if let Some(receiver) = &entry.receiver {
+        results.push(("receiver", Ok(create_index!(intent.receiver(receiver), agent.intents_as_receiver(base_address))?)));
+    }
+    if let Some(input_of) = &entry.input_of {
+        results.push(("input_of", create_index!(intent.input_of(input_of), process.intended_inputs(base_address))));
+    }
+    if let Some(output_of) = &entry.output_of {
+        results.push(("output_of", create_index!(intent.output_of(output_of), process.intended_outputs(base_address))));
+    }
+    if let Some(resource_conforms_to) = &entry.resource_conforms_to {
+        results.push(("resource_conforms_to", create_index!(intent.resource_conforms_to(resource_conforms_to), resource_specification.conforming_intents(base_address))));
+    }
+    Ok(results)
+}
+
+/// Rebuild the indexes of an `Intent` from its latest revision.
+///
+pub fn handle_resync_intent_indexes(address: IntentAddress) -> RecordAPIResult<bool>
+{
+    let (_meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+
+    let mut results = create_intent_indexes(&base_address, &entry)?;
+    results.push(("classified_as", resync_intent_classified_as_index(&base_address, &entry)));
+    throw_if_resync_failed(results)
+}
+
+/// Create a new `Intent` as a copy of the latest revision of an existing one, with
+/// `overrides` merged over the copied fields. The copy is not marked as finished
+/// or cancelled unless the overrides say so.
+///
+pub fn handle_clone_intent<S>(entry_def_id: S, address: IntentAddress, overrides: UpdateRequest) -> RecordAPIResult<CloneResponseData>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    let (meta, source_address, mut entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    if meta.as_hash() != &overrides.revision_id {
+        return Err(DataIntegrityError::UpdateConflict(vec![meta.as_hash().to_owned()]));
+    }
+    entry.finished = false;
+    entry.cancelled = false;
+    entry.cancellation_note = None;
+
+    let created = handle_create_intent(entry_def_id, entry.update_with(overrides)?.into())?;
+    Ok(CloneResponseData {
+        cloned_from: source_address,
+        intent: created.intent,
+    })
+}
+
 pub fn handle_get_intent(address: IntentAddress) -> RecordAPIResult<ResponseData>
 {
     let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
     construct_response(&base_address, &meta, &entry, get_link_fields(&address)?)
 }
 
+/// Read many `Intent`s in a single call. Each address is returned alongside its
+/// record or the reason it could not be read, so that one failure does not fail the batch.
+///
+pub fn handle_get_intents(addresses: Vec<IntentAddress>) -> RecordAPIResult<Vec<ByAddressResult<IntentAddress, ResponseData>>>
+{
+    Ok(addresses.into_iter()
+        .map(|address| ByAddressResult::new(address.to_owned(), handle_get_intent(address)))
+        .collect())
+}
+
 pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
 {
     let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
     construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
 }
 
+/// Read the revisions of an intent written within the window described by `params`
+///
+pub fn handle_get_intent_revision_history(params: RevisionHistoryParams<IntentAddress>) -> RecordAPIResult<RevisionHistory<ResponseData>>
+{
+    let link_fields = get_link_fields(&params.address)?;
+    read_revision_window::<EntryData, EntryStorage, IntentAddress, _, _>(
+        &params.address, params.since, params.until, params.limit,
+        |meta, entry| construct_response(&params.address, meta, entry, link_fields.to_owned()),
+    )
+}
+
 pub fn handle_update_intent(intent: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
     let address = intent.get_revision_id().to_owned();
     let (meta, base_address, new_entry, prev_entry): (_, IntentAddress, EntryData, EntryData) = update_record(&address, intent.to_owned())?;
 
+    hdk_records::return_if_unchanged!(meta, address, construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?), intent);
+
+    sync_field_value_index(LinkTypes::FieldValueIndex, RESOURCE_CONFORMS_TO_INDEX, &base_address, Some(&new_entry), Some(&prev_entry), extract_resource_conforms_to)?;
+
     // handle link fields
     if new_entry.provider != prev_entry.provider {
         let new_value = match &new_entry.provider { Some(val) => vec![val.to_owned()], None => vec![] };
@@ -115,15 +229,211 @@ pub fn handle_update_intent(intent: UpdateRequest) -> RecordAPIResult<ResponseDa
         );
         hdk::prelude::debug!("handle_update_intent::output_of index {:?}", e);
     }
+    if new_entry.resource_conforms_to != prev_entry.resource_conforms_to {
+        let new_value = match &new_entry.resource_conforms_to { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.resource_conforms_to { Some(val) => vec![val.to_owned()], None => vec![] };
+        update_index!(
+            intent
+                .resource_conforms_to(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            resource_specification.conforming_intents(&base_address)
+        )?;
+    }
+    let e = update_intent_classified_as_index(&base_address, &new_entry, &prev_entry);
+    hdk::prelude::debug!("handle_update_intent::classified_as index {:?}", e);
 
     construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?)
 }
 
-pub fn handle_delete_intent(revision_id: ActionHash) -> RecordAPIResult<bool>
+generate_patch_builder!(UpdateRequest {
+    action,
+    note,
+    image,
+    images,
+    input_of,
+    output_of,
+    provider,
+    receiver,
+    resource_inventoried_as,
+    resource_classified_as,
+    resource_conforms_to,
+    resource_quantity,
+    effort_quantity,
+    available_quantity,
+    has_beginning,
+    has_end,
+    has_point_in_time,
+    due,
+    at_location,
+    agreed_in,
+    finished,
+    cancelled,
+    cancellation_note,
+    in_scope_of
+});
+
+/// Withdraw an `Intent`, recording an optional reason. The intent no longer appears in
+/// matches or proposal listings by default, but its satisfactions and proposal links are retained.
+///
+/// Cancelling an intent which has already been fully satisfied is permitted, but logged as a warning.
+///
+pub fn handle_cancel_intent(address: IntentAddress, note: Option<String>) -> RecordAPIResult<ResponseData>
+{
+    let current = handle_get_intent(address)?.intent;
+    let fully_satisfied = !current.satisfied_by.is_empty()
+        && current.available_quantity.as_ref().map_or(true, |q| !q.exceeds(&QuantityValue::new(0.0, q.get_unit())));
+    if fully_satisfied {
+        hdk_records::log_warn!("cancel_intent", address = current.id; "cancelling intent which has already been satisfied");
+    }
+
+    let mut update = build_patch_update(current.revision_id, vec![])?;
+    update.cancelled = MaybeUndefined::Some(true);
+    update.cancellation_note = note.into();
+    handle_update_intent(update)
+}
+
+/// Append an image to the `images` list of the latest revision of an `Intent`.
+/// Images already present are left in place; the record is updated via `handle_update_intent`.
+///
+pub fn handle_add_intent_image(address: IntentAddress, image: ExternalURL) -> RecordAPIResult<ResponseData>
+{
+    let current = handle_get_intent(address)?.intent;
+    let mut images = current.images.unwrap_or_default();
+    if images.contains(&image) {
+        return handle_get_revision(current.revision_id);
+    }
+    images.push(image);
+
+    let mut update = build_patch_update(current.revision_id, vec![])?;
+    update.images = MaybeUndefined::Some(images);
+    handle_update_intent(update)
+}
+
+/// Remove an image from the `images` list of the latest revision of an `Intent`.
+/// Removing the final image clears the field. The record is updated via `handle_update_intent`.
+///
+pub fn handle_remove_intent_image(address: IntentAddress, image: ExternalURL) -> RecordAPIResult<ResponseData>
+{
+    let current = handle_get_intent(address)?.intent;
+    let images = current.images.unwrap_or_default();
+    if !images.contains(&image) {
+        return handle_get_revision(current.revision_id);
+    }
+    let remaining: Vec<ExternalURL> = images.into_iter().filter(|i| *i != image).collect();
+
+    let mut update = build_patch_update(current.revision_id, vec![])?;
+    update.images = if remaining.is_empty() { MaybeUndefined::None } else { MaybeUndefined::Some(remaining) };
+    handle_update_intent(update)
+}
+
+/// Find unfinished, uncancelled `Intent`s which could be satisfied by resources conforming to the given `ResourceSpecification`.
+///
+/// Intents conforming directly to the specification are returned first. These are followed by
+/// intents conforming to any specification marked as substitutable with it, which are flagged
+/// as `substitute` matches. Substitutability is not transitive- only direct substitutes are considered.
+///
+pub fn handle_match_intents(resource_conforms_to: ResourceSpecificationAddress) -> RecordAPIResult<Vec<IntentMatch>>
+{
+    let mut seen: Vec<IntentAddress> = vec![];
+    let mut matches = read_unfinished_conforming_intents(&resource_conforms_to, false, &mut seen)?;
+
+    for substitute_spec in read_substitutable_specifications(&resource_conforms_to)? {
+        if substitute_spec == resource_conforms_to { continue; }
+        matches.append(&mut read_unfinished_conforming_intents(&substitute_spec, true, &mut seen)?);
+    }
+
+    Ok(matches)
+}
+
+fn read_unfinished_conforming_intents(
+    resource_conforms_to: &ResourceSpecificationAddress,
+    substitute: bool,
+    seen: &mut Vec<IntentAddress>,
+) -> RecordAPIResult<Vec<IntentMatch>> {
+    let addresses: Vec<IntentAddress> = find_by_field_value(LinkTypes::FieldValueIndex, RESOURCE_CONFORMS_TO_INDEX, resource_conforms_to.to_string())?;
+
+    let mut matches = vec![];
+    for address in addresses {
+        if seen.contains(&address) { continue; }
+        seen.push(address.to_owned());
+
+        let intent = handle_get_intent(address)?.intent;
+        if intent.finished || intent.cancelled { continue; }
+        matches.push(IntentMatch { intent, substitute });
+    }
+    Ok(matches)
+}
+
+/// Read the direct substitutes of a `ResourceSpecification` from the DNA it is stored in
+///
+fn read_substitutable_specifications(specification_id: &ResourceSpecificationAddress) -> RecordAPIResult<Vec<ResourceSpecificationAddress>> {
+    let resp: OtherCellResult<Vec<ResourceSpecificationAddress>> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        specification_id,
+        &String::from("read_substitutable_specifications"),
+        GetSpecificationRequest { address: specification_id.to_owned() },
+        LinkTypes::AvailableCapability
+    );
+    Ok(resp?)
+}
+
+/// Apply a set of `PatchOp`s to the latest revision of a `Intent`.
+/// The patch is converted to an `UpdateRequest` and applied via `handle_update_intent`.
+///
+pub fn handle_patch_intent(address: IntentAddress, ops: Vec<PatchOp>) -> RecordAPIResult<ResponseData>
+{
+    let revision_id = handle_get_intent(address)?.intent.revision_id;
+    handle_update_intent(build_patch_update(revision_id, ops)?)
+}
+
+/// Point an `Intent` conforming to `from` at the `ResourceSpecification` `to` instead, moving it
+/// between the specifications' indexes. Returns `false` if the intent does not conform to `from`.
+/// Only network admins may reassign records, as this rewrites records authored by other agents.
+///
+pub fn handle_reassign_resource_specification(params: ReassignSpecificationParams<IntentAddress>) -> RecordAPIResult<bool>
+{
+    NetworkSettingsReader::new(read_network_settings_zome).throw_if_not_admin()?;
+
+    let (meta, _base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(params.address.as_ref())?;
+    if entry.resource_conforms_to.as_ref() != Some(&params.from) {
+        return Ok(false);
+    }
+    let (_meta, base_address, new_entry, prev_entry): (_, IntentAddress, EntryData, EntryData) = update_record(meta.as_hash(), params.to_owned())?;
+
+    sync_field_value_index(LinkTypes::FieldValueIndex, RESOURCE_CONFORMS_TO_INDEX, &base_address, Some(&new_entry), Some(&prev_entry), extract_resource_conforms_to)?;
+    update_index!(
+        intent
+            .resource_conforms_to(&vec![params.to])
+            .not(&vec![params.from]),
+        resource_specification.conforming_intents(&base_address)
+    )?;
+    Ok(true)
+}
+
+/// Delete an `Intent`, refusing to do so if any `Satisfaction` records reference it.
+/// If `cascade` is set, dependent records are removed first.
+///
+pub fn handle_delete_intent(revision_id: ActionHash, cascade: bool) -> RecordAPIResult<DeletionReport>
 {
     // load the record to ensure it is of the correct type
     let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
 
+    // check for dependent records
+    let satisfactions: Vec<SatisfactionAddress> = read_index!(intent(&base_address).satisfied_by)?;
+
+    let removed_dependents = if cascade {
+        let (removed, failed) = cascade_delete_dependents("satisfactions", satisfactions, delete_satisfaction);
+        throw_if_cascade_failed(removed, failed)?
+    } else {
+        throw_if_dependents(vec![
+            summarize_dependents("satisfactions", &satisfactions),
+        ])?;
+        vec![]
+    };
+
+    sync_field_value_index(LinkTypes::FieldValueIndex, RESOURCE_CONFORMS_TO_INDEX, &base_address, None, Some(&entry), extract_resource_conforms_to)?;
+    let e = delete_intent_classified_as_index(&base_address, &entry);
+    hdk::prelude::debug!("handle_delete_intent::classified_as index {:?}", e);
+
     // handle link fields
     if let Some(process_address) = entry.input_of {
         let e = update_index!(intent.input_of.not(&vec![process_address]), process.intended_inputs(&base_address));
@@ -141,9 +451,29 @@ pub fn handle_delete_intent(revision_id: ActionHash) -> RecordAPIResult<bool>
         let e = update_index!(intent.receiver.not(&vec![agent_address]), process.intents_as_receiver(&base_address));
         hdk::prelude::debug!("handle_delete_intent::receiver index {:?}", e);
     }
+    if let Some(spec_address) = entry.resource_conforms_to {
+        let e = update_index!(intent.resource_conforms_to.not(&vec![spec_address]), resource_specification.conforming_intents(&base_address));
+        hdk::prelude::debug!("handle_delete_intent::resource_conforms_to index {:?}", e);
+    }
 
     // delete entry last, as it must be present in order for links to be removed
-    delete_record::<EntryStorage>(&revision_id)
+    Ok(DeletionReport {
+        deleted: delete_record::<EntryStorage>(&revision_id)?,
+        removed_dependents,
+    })
+}
+
+/// Properties accessor for zome config.
+fn read_satisfaction_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.intent.satisfaction_zome
+}
+
+/// Remove a dependent `Satisfaction` via its storage zome, which also clears
+/// reciprocal indexes in the observation DNA.
+///
+fn delete_satisfaction(address: &SatisfactionAddress) -> RecordAPIResult<bool> {
+    let record: SatisfactionResponse = call_local_zome_method(read_satisfaction_zome, "get_satisfaction", ByAddress { address: address.to_owned() })?;
+    Ok(call_local_zome_method(read_satisfaction_zome, "delete_satisfaction", ByRevision { revision_id: record.satisfaction.revision_id })?)
 }
 
 /// Create response from input DHT primitives
@@ -164,6 +494,7 @@ pub fn construct_response<'a>(
             action: e.action.to_owned(),
             note: e.note.to_owned(),
             image: e.image.to_owned(),
+            images: e.images.to_owned(),
             input_of: e.input_of.to_owned(),
             output_of: e.output_of.to_owned(),
             provider: e.provider.to_owned(),
@@ -181,6 +512,8 @@ pub fn construct_response<'a>(
             at_location: e.at_location.to_owned(),
             agreed_in: e.agreed_in.to_owned(),
             finished: e.finished.to_owned(),
+            cancelled: e.cancelled.to_owned(),
+            cancellation_note: e.cancellation_note.to_owned(),
             in_scope_of: e.in_scope_of.to_owned(),
             satisfied_by: satisfactions.to_owned(),
             // published_in: published_in.to_owned(),
@@ -205,6 +538,11 @@ fn read_agent_index_zome(conf: DnaConfigSlice) -> Option<String> {
     conf.intent.agent_index_zome
 }
 
+/// Properties accessor for zome config
+fn read_resource_specification_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.intent.resource_specification_index_zome
+}
+
 // @see construct_response
 pub fn get_link_fields(intent: &IntentAddress) -> RecordAPIResult<(
     Vec<SatisfactionAddress>,
@@ -7,12 +7,17 @@
  * @package hREA
  */
 use holochain_serialized_bytes::prelude::*;
+use hdk_uuid_types::{ DnaHash, addressable_identifier };
 
 use serde_maybe_undefined::{MaybeUndefined, default_false};
 use vf_measurement::QuantityValue;
 pub use vf_attributes_hdk::{
+    ErrorCode, ErrorEnvelope,
+    PatchOp, PatchParams,
+    DeleteParams, DeletionReport,
+    RevisionHistoryParams, RevisionHistory,
     ActionId,
-    ExternalURL,
+    ExternalURL, classification_index_paths,
     DateTime, FixedOffset,
     ProcessAddress,
     AgentAddress,
@@ -21,9 +26,14 @@ pub use vf_attributes_hdk::{
     SatisfactionAddress,
     LocationAddress,
     ProposedIntentAddress,
-    ActionHash, ByAction, ByRevision, RecordMeta, RevisionMeta,
+    EntryHash,
+    ActionHash, ByAction, ByAddressResult, ByRevision, RecordMeta, RevisionMeta,
+    ReassignSpecificationParams,
 };
 
+// internal type for indexing against resource classification URLs
+addressable_identifier!(IntentClassificationId => EntryHash);
+
 //---------------- EXTERNAL RECORD STRUCTURE ----------------
 
 // Export external type interface to allow consuming zomes to easily import & define zome API
@@ -35,6 +45,7 @@ pub use vf_attributes_hdk::{ IntentAddress };
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     pub id: IntentAddress,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     pub meta: RecordMeta,
     pub action: ActionId,
@@ -43,42 +54,64 @@ pub struct Response {
     #[serde(default)]
     pub image: Option<ExternalURL>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub images: Option<Vec<ExternalURL>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "input_of"))]
     pub input_of: Option<ProcessAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "output_of"))]
     pub output_of: Option<ProcessAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub provider: Option<AgentAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub receiver: Option<AgentAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_inventoried_as"))]
     pub resource_inventoried_as: Option<EconomicResourceAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_classified_as"))]
     pub resource_classified_as: Option<Vec<ExternalURL>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_conforms_to"))]
     pub resource_conforms_to: Option<ResourceSpecificationAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_quantity"))]
     pub resource_quantity: Option<QuantityValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "effort_quantity"))]
     pub effort_quantity: Option<QuantityValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "available_quantity"))]
     pub available_quantity: Option<QuantityValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_beginning"))]
     pub has_beginning: Option<DateTime<FixedOffset>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_end"))]
     pub has_end: Option<DateTime<FixedOffset>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_point_in_time"))]
     pub has_point_in_time: Option<DateTime<FixedOffset>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub due: Option<DateTime<FixedOffset>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "at_location"))]
     pub at_location: Option<LocationAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "agreed_in"))]
     pub agreed_in: Option<ExternalURL>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "in_scope_of"))]
     pub in_scope_of: Option<Vec<String>>,
     pub finished: bool,
     #[serde(default)]
+    pub cancelled: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "cancellation_note"))]
+    pub cancellation_note: Option<String>,
+    #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "satisfied_by"))]
     pub satisfied_by: Vec<SatisfactionAddress>,
     // #[serde(skip_serializing_if = "Option::is_none")]
     // pub published_in: Option<Vec<ProposedIntentAddress>>,
@@ -107,40 +140,61 @@ pub struct CreateRequest {
     #[serde(default)]
     pub image: MaybeUndefined<ExternalURL>,
     #[serde(default)]
+    pub images: MaybeUndefined<Vec<ExternalURL>>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "input_of"))]
     pub input_of: MaybeUndefined<ProcessAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "output_of"))]
     pub output_of: MaybeUndefined<ProcessAddress>,
     #[serde(default)]
     pub provider: MaybeUndefined<AgentAddress>,
     #[serde(default)]
     pub receiver: MaybeUndefined<AgentAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_inventoried_as"))]
     pub resource_inventoried_as: MaybeUndefined<EconomicResourceAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_classified_as"))]
     pub resource_classified_as: MaybeUndefined<Vec<ExternalURL>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_conforms_to"))]
     pub resource_conforms_to: MaybeUndefined<ResourceSpecificationAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_quantity"))]
     pub resource_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "effort_quantity"))]
     pub effort_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "available_quantity"))]
     pub available_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_beginning"))]
     pub has_beginning: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_end"))]
     pub has_end: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_point_in_time"))]
     pub has_point_in_time: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
     pub due: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "at_location"))]
     pub at_location: MaybeUndefined<LocationAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "agreed_in"))]
     pub agreed_in: MaybeUndefined<ExternalURL>,
     #[serde(default = "default_false")]
     pub finished: MaybeUndefined<bool>,
     #[serde(default)]
+    pub cancelled: MaybeUndefined<bool>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "cancellation_note"))]
+    pub cancellation_note: MaybeUndefined<String>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "in_scope_of"))]
     pub in_scope_of: MaybeUndefined<Vec<String>>,
 }
 
@@ -155,6 +209,7 @@ impl<'a> CreateRequest {
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateRequest {
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     #[serde(default)]
     pub action: MaybeUndefined<ActionId>,
@@ -163,40 +218,61 @@ pub struct UpdateRequest {
     #[serde(default)]
     pub image: MaybeUndefined<ExternalURL>,
     #[serde(default)]
+    pub images: MaybeUndefined<Vec<ExternalURL>>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "input_of"))]
     pub input_of: MaybeUndefined<ProcessAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "output_of"))]
     pub output_of: MaybeUndefined<ProcessAddress>,
     #[serde(default)]
     pub provider: MaybeUndefined<AgentAddress>,
     #[serde(default)]
     pub receiver: MaybeUndefined<AgentAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_inventoried_as"))]
     pub resource_inventoried_as: MaybeUndefined<EconomicResourceAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_classified_as"))]
     pub resource_classified_as: MaybeUndefined<Vec<ExternalURL>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_conforms_to"))]
     pub resource_conforms_to: MaybeUndefined<ResourceSpecificationAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_quantity"))]
     pub resource_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "effort_quantity"))]
     pub effort_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "available_quantity"))]
     pub available_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_beginning"))]
     pub has_beginning: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_end"))]
     pub has_end: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_point_in_time"))]
     pub has_point_in_time: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
     pub due: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "at_location"))]
     pub at_location: MaybeUndefined<LocationAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "agreed_in"))]
     pub agreed_in: MaybeUndefined<ExternalURL>,
     #[serde(default)]
     pub finished: MaybeUndefined<bool>,
     #[serde(default)]
+    pub cancelled: MaybeUndefined<bool>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "cancellation_note"))]
+    pub cancellation_note: MaybeUndefined<String>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "in_scope_of"))]
     pub in_scope_of: MaybeUndefined<Vec<String>>,
 }
 
@@ -208,6 +284,26 @@ impl<'a> UpdateRequest {
     // :TODO: accessors for other field data
 }
 
+//---------------- CLONE REQUEST ----------------
+
+/// Parameters for creating a new `Intent` as a copy of an existing one.
+/// `overrides` are merged over the copied fields as for an update, and its
+/// `revision_id` must reference the latest revision of the source record.
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CloneParams {
+    pub address: IntentAddress,
+    pub overrides: UpdateRequest,
+}
+
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CloneResponseData {
+    pub cloned_from: IntentAddress,
+    pub intent: Response,
+}
+
 //---------------- QUERY FILTER REQUEST ----------------
 
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
@@ -219,4 +315,81 @@ pub struct QueryParams {
     pub proposed_in: Option<ProposedIntentAddress>,
     pub provider: Option<AgentAddress>,
     pub receiver: Option<AgentAddress>,
+    pub resource_conforms_to: Option<ResourceSpecificationAddress>,
+
+    // resource classification URL, @see vf_attributes_hdk::classification_index_paths
+    pub classified_as: Option<String>,
+    pub classified_as_internal: Option<IntentClassificationId>,
+
+    // cancelled intents are omitted from results unless set
+    #[serde(default)]
+    pub include_cancelled: bool,
+}
+
+/// Parameters for `query_proposed_intents`
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProposedIntentQueryParams {
+    pub proposed_in: Vec<ProposedIntentAddress>,
+    #[serde(default)]
+    pub include_cancelled: bool,
+}
+
+//---------------- INTENT MATCHING ----------------
+
+/// Parameters for `match_intents`
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchParams {
+    pub resource_conforms_to: ResourceSpecificationAddress,
+}
+
+/// An `Intent` matched against a `ResourceSpecification`.
+/// `substitute` is set where the intent conforms to a substitutable specification rather than the one requested.
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IntentMatch {
+    pub intent: Response,
+    pub substitute: bool,
+}
+
+/// Request payload for reading substitutes from the specification DNA
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSpecificationRequest {
+    pub address: ResourceSpecificationAddress,
+}
+
+//---------------- CANCELLATION ----------------
+
+/// Parameters for `cancel_intent`
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelIntentParams {
+    pub address: IntentAddress,
+    #[serde(default)]
+    pub note: Option<String>,
+}
+
+//---------------- IMAGE UPDATES ----------------
+
+/// Parameters for `add_intent_image` & `remove_intent_image`
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IntentImageParams {
+    pub address: IntentAddress,
+    pub image: ExternalURL,
+}
+
+#[cfg(all(test, feature = "snake_case_aliases"))]
+mod tests {
+    use super::*;
+
+    vf_attributes_hdk::snake_case_alias_tests!(Response, CreateRequest, UpdateRequest);
 }
@@ -28,13 +28,15 @@ pub use vf_attributes_hdk::{
     ProcessAddress,
     ResourceSpecificationAddress,
 };
+use vf_attributes_hdk::{ VALIDATE_NOTE_LENGTH, VALIDATE_EXTERNAL_URLS };
 
-use vf_actions::{ validate_flow_action };
+use vf_actions::{ validate_flow_action, normalize_action };
 
-use hc_zome_rea_intent_rpc::{ CreateRequest, UpdateRequest };
+use hc_zome_rea_intent_rpc::{ CreateRequest, UpdateRequest, ReassignSpecificationParams };
 
 // :SHONK: needed as re-export in zome logic to allow validation logic to parse entries
-pub use hdk_records::record_interface::Identified;
+pub use hdk_records::record_interface::{Identified, Identifiable};
+pub use hdk_records::validation::check_validation_hooks;
 
 //--------------- ZOME CONFIGURATION ATTRIBUTES ----------------
 
@@ -49,6 +51,11 @@ pub struct IntentZomeConfig {
     pub index_zome: String,
     pub process_index_zome: Option<String>,
     pub agent_index_zome: Option<String>,
+    pub resource_specification_index_zome: Option<String>,
+    // zome ID of dependent record zome, used for cascading deletes
+    pub satisfaction_zome: Option<String>,
+    // zome ID of the network settings zome, used to authorise administrative operations
+    pub network_settings_zome: Option<String>,
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -73,8 +80,15 @@ pub struct EntryData {
     pub at_location: Option<LocationAddress>,
     pub agreed_in: Option<ExternalURL>,
     pub finished: bool,
+    // withdrawn by its author, as distinct from deletion
+    #[serde(default)]
+    pub cancelled: bool,
+    #[serde(default)]
+    pub cancellation_note: Option<String>,
     pub in_scope_of: Option<Vec<String>>,
     pub image: Option<ExternalURL>,
+    #[serde(default)]
+    pub images: Option<Vec<ExternalURL>>,
     pub note: Option<String>,
     pub _nonce: Bytes,
 }
@@ -92,7 +106,7 @@ impl EntryData {
     }
 }
 
-generate_record_entry!(EntryData, IntentAddress, EntryStorage);
+generate_record_entry!(EntryData, IntentAddress, EntryStorage, validated_by [VALIDATE_NOTE_LENGTH, VALIDATE_EXTERNAL_URLS]);
 
 //---------------- Holochain App Entry And Link Types Setup ----------------
 
@@ -124,7 +138,10 @@ impl TryFrom<AvailableCapability> for EntryTypes {
 pub enum LinkTypes {
     // relates to dna-auth-resolver mixin
     // and remote authorizations
-    AvailableCapability
+    AvailableCapability,
+    // exact-match lookup of records by field value
+    // @see hdk_records::field_indexes
+    FieldValueIndex,
 }
 
 
@@ -136,9 +153,10 @@ impl TryFrom<CreateRequest> for EntryData {
 
     fn try_from(e: CreateRequest) -> RecordAPIResult<EntryData> {
         Ok(EntryData {
-            action: e.action.to_owned(),
+            action: normalize_action(e.action.as_ref())?,
             note: e.note.to_owned().into(),
             image: e.image.to_owned().into(),
+            images: e.images.to_owned().into(),
             provider: e.provider.to_owned().into(),
             receiver: e.receiver.to_owned().into(),
             input_of: e.input_of.into(),
@@ -156,19 +174,55 @@ impl TryFrom<CreateRequest> for EntryData {
             at_location: e.at_location.to_owned().into(),
             agreed_in: e.agreed_in.to_owned().into(),
             finished: e.finished.to_option().unwrap(),  // :NOTE: unsafe, would crash if not for "default_false" binding via Serde
+            cancelled: e.cancelled.to_option().unwrap_or(false),
+            cancellation_note: e.cancellation_note.into(),
             in_scope_of: e.in_scope_of.to_owned().into(),
             _nonce: random_bytes(32)?,
         })
     }
 }
 
+//---------------- CLONE ----------------
+
+/// Convert stored entry data back into a creation request, for duplicating records
+impl From<EntryData> for CreateRequest {
+    fn from(e: EntryData) -> CreateRequest {
+        CreateRequest {
+            action: e.action,
+            note: e.note.into(),
+            image: e.image.into(),
+            images: e.images.into(),
+            provider: e.provider.into(),
+            receiver: e.receiver.into(),
+            input_of: e.input_of.into(),
+            output_of: e.output_of.into(),
+            resource_inventoried_as: e.resource_inventoried_as.into(),
+            resource_classified_as: e.resource_classified_as.into(),
+            resource_conforms_to: e.resource_conforms_to.into(),
+            resource_quantity: e.resource_quantity.into(),
+            effort_quantity: e.effort_quantity.into(),
+            available_quantity: e.available_quantity.into(),
+            has_beginning: e.has_beginning.into(),
+            has_end: e.has_end.into(),
+            has_point_in_time: e.has_point_in_time.into(),
+            due: e.due.into(),
+            at_location: e.at_location.into(),
+            agreed_in: e.agreed_in.into(),
+            finished: MaybeUndefined::Some(e.finished),
+            cancelled: MaybeUndefined::Some(e.cancelled),
+            cancellation_note: e.cancellation_note.into(),
+            in_scope_of: e.in_scope_of.into(),
+        }
+    }
+}
+
 //---------------- UPDATE ----------------
 
 /// Handles update operations by merging any newly provided fields
 impl Updateable<UpdateRequest> for EntryData {
     fn update_with(&self, e: UpdateRequest) -> RecordAPIResult<EntryData> {
         Ok(EntryData {
-            action: if !e.action.is_some() { self.action.to_owned() } else { e.action.to_owned().unwrap() },
+            action: if !e.action.is_some() { self.action.to_owned() } else { normalize_action(e.action.to_owned().unwrap().as_ref())? },
             provider: if e.provider == MaybeUndefined::Undefined { self.provider.to_owned() } else { e.provider.to_owned().into() },
             receiver: if e.receiver == MaybeUndefined::Undefined { self.receiver.to_owned() } else { e.receiver.to_owned().into() },
             input_of: if e.input_of == MaybeUndefined::Undefined { self.input_of.to_owned() } else { e.input_of.to_owned().into() },
@@ -185,11 +239,102 @@ impl Updateable<UpdateRequest> for EntryData {
             due: if e.due == MaybeUndefined::Undefined { self.due.to_owned() } else { e.due.to_owned().into() },
             at_location: if e.at_location == MaybeUndefined::Undefined { self.at_location.to_owned() } else { e.at_location.to_owned().into() },
             agreed_in: if e.agreed_in == MaybeUndefined::Undefined { self.agreed_in.to_owned() } else { e.agreed_in.to_owned().into() },
-            finished: if e.finished == MaybeUndefined::Undefined { self.finished.to_owned() } else { e.finished.to_owned().to_option().unwrap() },
+            finished: if !e.finished.is_some() { self.finished.to_owned() } else { e.finished.to_owned().to_option().unwrap() },
+            cancelled: if !e.cancelled.is_some() { self.cancelled.to_owned() } else { e.cancelled.to_owned().unwrap() },
+            cancellation_note: if e.cancellation_note == MaybeUndefined::Undefined { self.cancellation_note.to_owned() } else { e.cancellation_note.to_owned().into() },
             in_scope_of: if e.in_scope_of== MaybeUndefined::Undefined { self.in_scope_of.to_owned() } else { e.in_scope_of.to_owned().into() },
             image: if e.image== MaybeUndefined::Undefined { self.image.to_owned() } else { e.image.to_owned().into() },
+            images: if e.images == MaybeUndefined::Undefined { self.images.to_owned() } else { e.images.to_owned().into() },
             note: if e.note== MaybeUndefined::Undefined { self.note.to_owned() } else { e.note.to_owned().into() },
             _nonce: self._nonce.to_owned(),
         })
     }
 }
+
+/// Re-points the intent at a different `ResourceSpecification` when specifications are merged.
+impl Updateable<ReassignSpecificationParams<IntentAddress>> for EntryData {
+    fn update_with(&self, e: ReassignSpecificationParams<IntentAddress>) -> RecordAPIResult<EntryData> {
+        Ok(EntryData {
+            resource_conforms_to: Some(e.to),
+            ..self.to_owned()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dna() -> DnaHash {
+        DnaHash::from_raw_36(vec![0xdb; 36])
+    }
+
+    fn hash(n: u8) -> EntryHash {
+        EntryHash::from_raw_36(vec![n; 36])
+    }
+
+    fn url(path: &str) -> ExternalURL {
+        ExternalURL(format!("http://example.com/{}", path))
+    }
+
+    fn time(time: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(time).unwrap()
+    }
+
+    fn entry() -> EntryData {
+        EntryData {
+            action: ActionId("produce".to_string()),
+            provider: Some(AgentAddress(dna(), hash(1))),
+            receiver: Some(AgentAddress(dna(), hash(2))),
+            input_of: Some(ProcessAddress(dna(), hash(3))),
+            output_of: Some(ProcessAddress(dna(), hash(4))),
+            resource_inventoried_as: Some(EconomicResourceAddress(dna(), hash(5))),
+            resource_classified_as: Some(vec![url("classifications/bicycle")]),
+            resource_conforms_to: Some(ResourceSpecificationAddress(dna(), hash(6))),
+            resource_quantity: Some(QuantityValue::new(1.0, None)),
+            effort_quantity: Some(QuantityValue::new(2.0, None)),
+            available_quantity: Some(QuantityValue::new(5.0, None)),
+            has_beginning: Some(time("2024-02-14T10:00:00Z")),
+            has_end: Some(time("2024-02-15T10:00:00Z")),
+            has_point_in_time: Some(time("2024-02-14T12:00:00Z")),
+            due: Some(time("2024-02-16T10:00:00Z")),
+            at_location: Some(LocationAddress(dna(), hash(7))),
+            agreed_in: Some(url("agreements/1")),
+            finished: false,
+            cancelled: false,
+            cancellation_note: Some("a cancellation note".to_string()),
+            in_scope_of: Some(vec!["scope".to_string()]),
+            image: Some(url("bicycle.png")),
+            images: Some(vec![url("bicycle-side.png")]),
+            note: Some("a note".to_string()),
+            _nonce: Bytes::from(vec![0; 32]),
+        }
+    }
+
+    hdk_records::update_semantics_tests!(EntryData, UpdateRequest, entry(), {
+        "action" => ActionId("use".to_string()),
+        "note" => "updated note",
+        "image" => url("wheel.png"),
+        "images" => vec![url("wheel-side.png")],
+        "inputOf" => ProcessAddress(dna(), hash(13)),
+        "outputOf" => ProcessAddress(dna(), hash(14)),
+        "provider" => AgentAddress(dna(), hash(11)),
+        "receiver" => AgentAddress(dna(), hash(12)),
+        "resourceInventoriedAs" => EconomicResourceAddress(dna(), hash(15)),
+        "resourceClassifiedAs" => vec![url("classifications/wheel")],
+        "resourceConformsTo" => ResourceSpecificationAddress(dna(), hash(16)),
+        "resourceQuantity" => QuantityValue::new(3.0, None),
+        "effortQuantity" => QuantityValue::new(4.0, None),
+        "availableQuantity" => QuantityValue::new(6.0, None),
+        "hasBeginning" => time("2024-03-14T10:00:00Z"),
+        "hasEnd" => time("2024-03-15T10:00:00Z"),
+        "hasPointInTime" => time("2024-03-14T12:00:00Z"),
+        "due" => time("2024-03-16T10:00:00Z"),
+        "atLocation" => LocationAddress(dna(), hash(17)),
+        "agreedIn" => url("agreements/2"),
+        "finished" => true,
+        "cancelled" => true,
+        "cancellationNote" => "updated cancellation note",
+        "inScopeOf" => vec!["updated scope".to_string()],
+    });
+}
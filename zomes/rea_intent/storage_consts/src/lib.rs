@@ -6,3 +6,6 @@
  * @package hREA
  */
 pub const INTENT_ENTRY_TYPE: &str = "intent";
+
+// remote_auth permission ID for batch reads of intents from other DNAs
+pub const INTENT_READ_BATCH_API_METHOD: &str = "read_intents";
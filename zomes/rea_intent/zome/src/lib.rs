@@ -37,11 +37,31 @@ fn get_intent(ByAddress { address }: ByAddress) -> ExternResult<ResponseData> {
     Ok(handle_get_intent(address)?)
 }
 
+#[hdk_extern]
+fn resync_record_indexes(ByAddress { address }: ByAddress) -> ExternResult<bool> {
+    Ok(handle_resync_intent_indexes(address)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ByAddresses {
+    pub addresses: Vec<IntentAddress>,
+}
+
+#[hdk_extern]
+fn get_intents(ByAddresses { addresses }: ByAddresses) -> ExternResult<Vec<ByAddressResult<IntentAddress, ResponseData>>> {
+    Ok(handle_get_intents(addresses)?)
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
 }
 
+#[hdk_extern]
+fn get_revision_history(params: RevisionHistoryParams<IntentAddress>) -> ExternResult<RevisionHistory<ResponseData>> {
+    Ok(handle_get_intent_revision_history(params)?)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct UpdateParams {
@@ -54,6 +74,42 @@ fn update_intent(UpdateParams { intent }: UpdateParams) -> ExternResult<Response
 }
 
 #[hdk_extern]
-fn delete_intent(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
-    Ok(handle_delete_intent(revision_id)?)
+fn patch_record(PatchParams { address, ops }: PatchParams<IntentAddress>) -> ExternResult<ResponseData> {
+    Ok(handle_patch_intent(address, ops)?)
+}
+
+#[hdk_extern]
+fn clone_record(CloneParams { address, overrides }: CloneParams) -> ExternResult<CloneResponseData> {
+    Ok(handle_clone_intent(INTENT_ENTRY_TYPE, address, overrides)?)
+}
+
+#[hdk_extern]
+fn cancel_intent(CancelIntentParams { address, note }: CancelIntentParams) -> ExternResult<ResponseData> {
+    Ok(handle_cancel_intent(address, note)?)
+}
+
+#[hdk_extern]
+fn add_intent_image(IntentImageParams { address, image }: IntentImageParams) -> ExternResult<ResponseData> {
+    Ok(handle_add_intent_image(address, image)?)
+}
+
+#[hdk_extern]
+fn remove_intent_image(IntentImageParams { address, image }: IntentImageParams) -> ExternResult<ResponseData> {
+    Ok(handle_remove_intent_image(address, image)?)
+}
+
+// used by the specification DNA when merging `ResourceSpecification`s
+#[hdk_extern]
+fn reassign_resource_specification(params: ReassignSpecificationParams<IntentAddress>) -> ExternResult<bool> {
+    Ok(handle_reassign_resource_specification(params)?)
+}
+
+#[hdk_extern]
+fn delete_intent(DeleteParams { revision_id, cascade }: DeleteParams) -> ExternResult<DeletionReport> {
+    Ok(handle_delete_intent(revision_id, cascade)?)
+}
+
+#[hdk_extern]
+fn match_intents(MatchParams { resource_conforms_to }: MatchParams) -> ExternResult<Vec<IntentMatch>> {
+    Ok(handle_match_intents(resource_conforms_to)?)
 }
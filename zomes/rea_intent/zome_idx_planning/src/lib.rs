@@ -7,7 +7,7 @@
 use hdk_semantic_indexes_zome_derive::index_zome;
 use hc_zome_rea_intent_rpc::*;
 
-#[index_zome]
+#[index_zome(result_filter = "include_in_results")]
 struct Intent {
     satisfied_by: Local<satisfaction, satisfies>,
     input_of: Local<process, intended_inputs>,
@@ -17,4 +17,44 @@ struct Intent {
     // internal indexes (not part of VF spec)
     provider: Local<agent, intents_as_provider>,
     receiver: Local<agent, intents_as_receiver>,
+    resource_conforms_to: Local<resource_specification, conforming_intents>,
+
+    // query intents by resource classification URL
+    classified_as: Local<intent, classified_as_internal>::String,
+    // :SHONK: redundant loopback index, @see zome_idx_agent
+    classified_as_internal: Local<intent, classified_as>,
+}
+
+/// Cancelled intents are omitted from `query_intents` unless `include_cancelled` is set.
+///
+fn include_in_results(params: &QueryParams, record: &ResponseData) -> bool {
+    params.include_cancelled || !record.intent.cancelled
+}
+
+//---------------- PROPOSAL LISTINGS ----------------
+
+/// Query the intents published via any of the given `ProposedIntent`s, most recent first.
+/// Cancelled intents are omitted unless `include_cancelled` is set.
+///
+#[hdk_extern]
+fn query_proposed_intents(ProposedIntentQueryParams { proposed_in, include_cancelled }: ProposedIntentQueryParams) -> ExternResult<QueryResults> {
+    register_time_indexes(&TIME_INDEXES);
+    throw_if_query_not_permitted(read_query_access, read_agent_zome, read_network_settings_zome)?;
+
+    let mut entries = vec![];
+    for proposed_intent in proposed_in {
+        entries.append(&mut query_index::<ResponseData, IntentAddress, _,_,_,_,_,_>(
+            &proposed_intent,
+            &"proposed_intent_publishes",
+            &local_time_index_id(),
+            &read_index_target_zome,
+            &QUERY_FN_NAME,
+            false,
+        )?);
+    }
+    let entries: Vec<RecordAPIResult<ResponseData>> = entries.into_iter()
+        .filter(|r| include_cancelled || !matches!(r, Ok(ResponseData { intent: Response { cancelled: true, .. } })))
+        .collect();
+
+    Ok(handle_list_output(entries.as_slice(), 0)?)
 }
@@ -0,0 +1,149 @@
+/**
+ * hREA network settings integrity zome for API definition
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ */
+use hdi::prelude::*;
+pub use hc_zome_rea_network_settings_storage::{EntryData, EntryTypes, EntryTypesUnit, LinkTypes, DnaConfigSlice};
+use hc_zome_rea_network_settings_storage_consts::NETWORK_SETTINGS_ANCHOR;
+
+#[hdk_extern]
+pub fn entry_defs(_: ()) -> ExternResult<EntryDefsCallbackResult> {
+    let defs: Vec<EntryDef> = EntryTypes::ENTRY_DEFS
+        .iter()
+        .map(|a| EntryDef::from(a.clone()))
+        .collect();
+    Ok(EntryDefsCallbackResult::from(defs))
+}
+
+#[no_mangle]
+pub fn __num_entry_types() -> u8 {
+    EntryTypesUnit::len()
+}
+
+#[no_mangle]
+pub fn __num_link_types() -> u8 {
+    LinkTypes::len()
+}
+
+#[hdk_extern]
+fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+    match op {
+        Op::StoreRecord { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::StoreEntry(StoreEntry { action, entry }) => validate_entry(&action.hashed.content, entry),
+        Op::RegisterCreateLink(RegisterCreateLink { create_link }) => validate_revision_link(
+            &create_link.hashed.content.author,
+            &create_link.hashed.content.base_address,
+            &create_link.hashed.content.target_address,
+        ),
+        Op::RegisterDeleteLink(RegisterDeleteLink { delete_link, create_link }) => validate_revision_unlink(
+            &delete_link.hashed.content.author,
+            &create_link.target_address,
+        ),
+        Op::RegisterUpdate(RegisterUpdate { update, .. }) => validate_admin_author(
+            &update.hashed.content.author,
+            &update.hashed.content.original_entry_address,
+        ),
+        Op::RegisterDelete(RegisterDelete { delete }) => validate_admin_author(
+            &delete.hashed.content.author,
+            &delete.hashed.content.deletes_entry_address,
+        ),
+        Op::RegisterAgentActivity { .. } => Ok(ValidateCallbackResult::Valid),
+    }
+}
+
+fn validate_entry(action: &EntryCreationAction, entry: Entry) -> ExternResult<ValidateCallbackResult> {
+    match EntryData::try_from(&entry) {
+        Ok(record) => {
+            if let Err(e) = record.validate() {
+                return Ok(ValidateCallbackResult::Invalid(e));
+            }
+            match action {
+                EntryCreationAction::Create(create) => validate_creator(&create.author),
+                // authors of updates are checked against the revision being updated
+                EntryCreationAction::Update(_) => Ok(ValidateCallbackResult::Valid),
+            }
+        }
+        _ => Ok(ValidateCallbackResult::Valid),
+    }
+}
+
+/// Where the network has a progenitor, only the progenitor may create the settings record.
+///
+/// Otherwise settings created by any agent are valid, and readers resolve concurrent
+/// settings records to the one created first.
+///
+fn validate_creator(author: &AgentPubKey) -> ExternResult<ValidateCallbackResult> {
+    let conf: DnaConfigSlice = dna_info()?.modifiers.properties.try_into()
+        .map_err(|e: SerializedBytesError| wasm_error!(WasmErrorInner::Guest(e.to_string())))?;
+    match conf.network_settings.progenitor {
+        Some(progenitor) if progenitor != *author =>
+            Ok(ValidateCallbackResult::Invalid("Only the network progenitor may create network settings".to_string())),
+        _ => Ok(ValidateCallbackResult::Valid),
+    }
+}
+
+/// Only agents listed as admins in the revision being updated or deleted may modify it
+///
+fn validate_admin_author(author: &AgentPubKey, original_entry_address: &EntryHash) -> ExternResult<ValidateCallbackResult> {
+    let original = must_get_entry(original_entry_address.to_owned())?;
+    match EntryData::try_from(original.as_content()) {
+        Ok(record) => {
+            if record.is_admin(author) {
+                Ok(ValidateCallbackResult::Valid)
+            } else {
+                Ok(ValidateCallbackResult::Invalid("Only network admins may modify network settings".to_string()))
+            }
+        }
+        _ => Ok(ValidateCallbackResult::Valid),
+    }
+}
+
+/// Revision links must be based from the settings anchor and point at a settings revision
+/// written by the agent creating the link.
+///
+fn validate_revision_link(author: &AgentPubKey, base: &AnyLinkableHash, target: &AnyLinkableHash) -> ExternResult<ValidateCallbackResult> {
+    let anchor = Path::from(NETWORK_SETTINGS_ANCHOR).path_entry_hash()?;
+    if *base != AnyLinkableHash::from(anchor) {
+        return Ok(ValidateCallbackResult::Invalid("Network settings revisions may only be linked from the settings anchor".to_string()));
+    }
+    let revision = match read_linked_revision(target)? {
+        Some(revision) => revision,
+        None => return Ok(ValidateCallbackResult::Invalid("Network settings anchor links must point to a network settings revision".to_string())),
+    };
+    if revision.action().author() != author {
+        return Ok(ValidateCallbackResult::Invalid("Only the author of a network settings revision may link it".to_string()));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+
+/// Revision links may only be removed by admins of the revision they point to
+///
+fn validate_revision_unlink(author: &AgentPubKey, target: &AnyLinkableHash) -> ExternResult<ValidateCallbackResult> {
+    let revision = match read_linked_revision(target)? {
+        Some(revision) => revision,
+        None => return Ok(ValidateCallbackResult::Valid),
+    };
+    match revision.entry().as_option().map(EntryData::try_from) {
+        Some(Ok(record)) if record.is_admin(author) => Ok(ValidateCallbackResult::Valid),
+        _ => Ok(ValidateCallbackResult::Invalid("Only network admins may unlink network settings revisions".to_string())),
+    }
+}
+
+/// Read the settings revision targeted by an anchor link, or `None` if the target is not one.
+///
+fn read_linked_revision(target: &AnyLinkableHash) -> ExternResult<Option<Record>> {
+    let revision_id = match target.to_owned().into_action_hash() {
+        Some(revision_id) => revision_id,
+        None => return Ok(None),
+    };
+    let revision = must_get_valid_record(revision_id)?;
+    match revision.entry().as_option().map(EntryData::try_from) {
+        Some(Ok(_)) => Ok(Some(revision)),
+        _ => Ok(None),
+    }
+}
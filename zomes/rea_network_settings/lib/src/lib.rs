@@ -0,0 +1,375 @@
+/**
+ * hREA network settings zome library API
+ *
+ * Contains helper methods that can be used to manipulate the network `Settings`
+ * record in either the local Holochain zome, or a separate DNA-local zome.
+ *
+ * Settings are a singleton record. Its current revision is linked directly from an
+ * anchor `Path`, so that reads only require a link query and a single `get`.
+ * Only agents listed in the record's `admin_agents` may modify it; the agent which
+ * first creates the record becomes its initial admin.
+ *
+ * @package hREA
+ * @since   2026-10-16
+ */
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use hdk::prelude::*;
+use hdk_records::{
+    RecordAPIResult, DataIntegrityError, MaybeUndefined,
+    OtherCellResult, CrossCellError, ErrorCode, ErrorCoded,
+    record_interface::Updateable,
+    entries::{
+        create_entry,
+        update_entry,
+        delete_entry,
+        get_entry_by_action,
+    },
+    rpc::call_local_zome_method,
+    metadata::read_revision_metadata_abbreviated,
+    logging::override_log_level,
+};
+pub use vf_measurement::{
+    QuantityValue, UNIT_READ_API_METHOD, UnitReadRequest, UnitReadResponse,
+    RESOURCE_SPECIFICATION_READ_API_METHOD, ResourceSpecificationReadRequest, ResourceSpecificationUnitsResponse, ResourceSpecificationUnits,
+};
+use vf_attributes_hdk::UnitId;
+
+pub use hc_zome_rea_network_settings_storage_consts::*;
+use hc_zome_rea_network_settings_storage::*;
+use hc_zome_rea_network_settings_rpc::*;
+
+//---------------- READ ----------------
+
+pub fn handle_get_network_settings() -> RecordAPIResult<MaybeResponseData>
+{
+    Ok(MaybeResponseData {
+        network_settings: match read_current_revision()? {
+            None => None,
+            Some((meta, entry)) => Some(construct_response(&meta, &entry)?.network_settings),
+        },
+    })
+}
+
+pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
+{
+    let (meta, entry): (_, EntryData) = get_entry_by_action(&revision_id)?;
+    construct_response(&meta, &entry)
+}
+
+//---------------- CREATE ----------------
+
+pub fn handle_create_network_settings(settings: CreateRequest) -> RecordAPIResult<ResponseData>
+{
+    if let Some(_) = read_current_revision()? {
+        return Err(DataIntegrityError::NetworkSettingsExist);
+    }
+
+    let entry = EntryData::from_request(settings, agent_info()?.agent_latest_pubkey)?;
+    let (meta, _entry_hash) = create_entry::<EntryTypes, _, _>(entry.to_owned())?;
+    link_current_revision(meta.as_hash())?;
+
+    construct_response(&meta, &entry)
+}
+
+//---------------- UPDATE ----------------
+
+pub fn handle_update_network_settings(settings: UpdateRequest) -> RecordAPIResult<ResponseData>
+{
+    let revision_id = settings.get_revision_id().clone();
+    let (current_meta, prev_entry) = read_current_revision()?.ok_or(DataIntegrityError::EntryNotFound)?;
+
+    // settings are a singleton, so updates must always be made against the latest revision
+    if *current_meta.as_hash() != revision_id {
+        return Err(DataIntegrityError::UpdateConflict(vec![current_meta.as_hash().to_owned()]));
+    }
+    throw_if_not_admin(&prev_entry)?;
+
+    let new_entry = prev_entry.update_with(settings)?;
+    let (meta, _entry_hash) = update_entry(&revision_id, new_entry.to_owned())?;
+    unlink_revisions()?;
+    link_current_revision(meta.as_hash())?;
+
+    construct_response(&meta, &new_entry)
+}
+
+/// Change the log level of zomes reading network settings, without having to reinstall the DNA.
+/// Pass `None` to revert to the levels configured in DNA properties.
+///
+pub fn handle_set_log_level(level: Option<LogLevel>) -> RecordAPIResult<ResponseData>
+{
+    let (current_meta, _entry) = read_current_revision()?.ok_or(DataIntegrityError::EntryNotFound)?;
+    handle_update_network_settings(UpdateRequest {
+        revision_id: current_meta.as_hash().to_owned(),
+        default_currency_unit: MaybeUndefined::Undefined,
+        default_timezone_offset_minutes: MaybeUndefined::Undefined,
+        enforce_spec_units: MaybeUndefined::Undefined,
+        enforce_registered_units: MaybeUndefined::Undefined,
+        admin_agents: MaybeUndefined::Undefined,
+        log_level: level.into(),
+    })
+}
+
+//---------------- DELETE ----------------
+
+pub fn handle_delete_network_settings(revision_id: ActionHash) -> RecordAPIResult<bool>
+{
+    let (_meta, entry): (_, EntryData) = get_entry_by_action(&revision_id)?;
+    throw_if_not_admin(&entry)?;
+
+    unlink_revisions()?;
+    delete_entry::<EntryData>(&revision_id)
+}
+
+//---------------- INTERNALS ----------------
+
+fn settings_anchor_hash() -> RecordAPIResult<EntryHash> {
+    Ok(Path::from(NETWORK_SETTINGS_ANCHOR).path_entry_hash()?)
+}
+
+fn read_revision_links() -> RecordAPIResult<Vec<Link>> {
+    Ok(get_links(settings_anchor_hash()?, LinkTypes::NetworkSettingsRevision, None)?)
+}
+
+/// Read the current revision of the settings record, if one has been created.
+///
+/// If multiple admins have concurrently updated the record, the most recently
+/// linked revision wins. If multiple agents have concurrently created settings
+/// records, only revisions of the first created record are read.
+///
+fn read_current_revision() -> RecordAPIResult<Option<(SignedActionHashed, EntryData)>> {
+    let mut revisions = vec![];
+    for link in read_revision_links()? {
+        let revision_id = match link.target.into_action_hash() {
+            Some(revision_id) => revision_id,
+            None => continue,
+        };
+        let (meta, entry): (SignedActionHashed, EntryData) = get_entry_by_action(&revision_id)?;
+        revisions.push((read_settings_root(&meta)?, link.timestamp, meta, entry));
+    }
+
+    let first_root = revisions.iter().map(|(root, _, _, _)| root.to_owned()).min();
+    Ok(revisions.into_iter()
+        .filter(|(root, _, _, _)| Some(root) == first_root.as_ref())
+        .max_by_key(|(_, linked_at, _, _)| *linked_at)
+        .map(|(_, _, meta, entry)| (meta, entry)))
+}
+
+/// Identify the settings record a revision belongs to by the time and hash of its initial `Create` action.
+///
+fn read_settings_root(meta: &SignedActionHashed) -> RecordAPIResult<(Timestamp, Vec<u8>)> {
+    let mut current = meta.to_owned();
+    loop {
+        let original_id = match current.action() {
+            Action::Update(update) => update.original_action_address.to_owned(),
+            _ => break,
+        };
+        let (original, _entry): (SignedActionHashed, EntryData) = get_entry_by_action(&original_id)?;
+        current = original;
+    }
+    Ok((current.action().timestamp(), current.as_hash().get_raw_39().to_vec()))
+}
+
+fn link_current_revision(revision_id: &ActionHash) -> RecordAPIResult<()> {
+    create_link(settings_anchor_hash()?, revision_id.to_owned(), LinkTypes::NetworkSettingsRevision, ())?;
+    Ok(())
+}
+
+fn unlink_revisions() -> RecordAPIResult<()> {
+    for link in read_revision_links()? {
+        delete_link(link.create_link_hash)?;
+    }
+    Ok(())
+}
+
+fn throw_if_not_admin(entry: &EntryData) -> RecordAPIResult<()> {
+    if !entry.is_admin(&agent_info()?.agent_latest_pubkey) {
+        return Err(DataIntegrityError::NotNetworkAdmin);
+    }
+    Ok(())
+}
+
+fn construct_response<'a>(
+    meta: &SignedActionHashed, e: &EntryData
+) -> RecordAPIResult<ResponseData> {
+    Ok(ResponseData {
+        network_settings: Response {
+            revision_id: meta.as_hash().to_owned(),
+            meta: read_revision_metadata_abbreviated(meta)?,
+            default_currency_unit: e.default_currency_unit.to_owned(),
+            default_timezone_offset_minutes: e.default_timezone_offset_minutes.to_owned(),
+            enforce_spec_units: e.enforce_spec_units,
+            enforce_registered_units: e.enforce_registered_units,
+            admin_agents: e.admin_agents.to_owned(),
+            log_level: e.log_level,
+        }
+    })
+}
+
+//---------------- CLIENT API ----------------
+
+/// Typed, memoised accessor for network settings, for use by other zomes in the same DNA.
+///
+/// Construct one per zome call and pass it to any logic needing settings values- the
+/// settings zome is only queried on first access. Where no settings zome is configured
+/// for the calling zome, or no settings have been created, defaults are returned.
+///
+pub struct NetworkSettingsReader<C, F>
+    where F: Fn(C) -> Option<String> + Copy,
+{
+    zome_name_from_config: F,
+    memo: RefCell<Option<Option<Response>>>,
+    _config: PhantomData<C>,
+}
+
+impl<C, F> NetworkSettingsReader<C, F>
+    where F: Fn(C) -> Option<String> + Copy,
+        C: std::fmt::Debug,
+        SerializedBytes: TryInto<C, Error = SerializedBytesError>,
+{
+    pub fn new(zome_name_from_config: F) -> Self {
+        Self {
+            zome_name_from_config,
+            memo: RefCell::new(None),
+            _config: PhantomData,
+        }
+    }
+
+    /// Read the full settings record, or `None` if settings are not in use for the network.
+    /// Any log level set for the network is applied to the calling zome upon first read.
+    ///
+    pub fn settings(&self) -> RecordAPIResult<Option<Response>> {
+        if let Some(memoised) = self.memo.borrow().as_ref() {
+            return Ok(memoised.to_owned());
+        }
+
+        let resp: OtherCellResult<MaybeResponseData> = call_local_zome_method(
+            self.zome_name_from_config,
+            String::from("get_network_settings"),
+            (),
+        );
+        let settings = match resp {
+            Ok(MaybeResponseData { network_settings }) => network_settings,
+            Err(CrossCellError::NotConfigured(_, _)) => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        override_log_level(settings.as_ref().and_then(|s| s.log_level));
+        *self.memo.borrow_mut() = Some(settings.to_owned());
+        Ok(settings)
+    }
+
+    pub fn default_currency_unit(&self) -> RecordAPIResult<Option<UnitId>> {
+        Ok(self.settings()?.and_then(|s| s.default_currency_unit))
+    }
+
+    pub fn default_timezone_offset_minutes(&self) -> RecordAPIResult<Option<i32>> {
+        Ok(self.settings()?.and_then(|s| s.default_timezone_offset_minutes))
+    }
+
+    pub fn enforce_spec_units(&self) -> RecordAPIResult<bool> {
+        Ok(self.settings()?.map(|s| s.enforce_spec_units).unwrap_or(false))
+    }
+
+    pub fn enforce_registered_units(&self) -> RecordAPIResult<bool> {
+        Ok(self.settings()?.map(|s| s.enforce_registered_units).unwrap_or(false))
+    }
+
+    /// Ensure the calling agent is a network admin. Operations gated on this check are
+    /// unavailable to everyone where no settings have been created for the network.
+    ///
+    pub fn throw_if_not_admin(&self) -> RecordAPIResult<()> {
+        let caller = agent_info()?.agent_latest_pubkey;
+        match self.settings()? {
+            Some(s) if s.admin_agents.contains(&caller) => Ok(()),
+            _ => Err(DataIntegrityError::NotNetworkAdmin),
+        }
+    }
+
+    /// When the network enforces units, ensure all provided quantities specify one.
+    /// `quantities` are pairs of field names and values, the field name being used for error reporting.
+    ///
+    pub fn throw_if_unitless_quantities(&self, quantities: &[(&str, &MaybeUndefined<QuantityValue>)]) -> RecordAPIResult<()> {
+        if !self.enforce_spec_units()? {
+            return Ok(());
+        }
+        for (field_name, quantity) in quantities {
+            if let MaybeUndefined::Some(q) = quantity {
+                if q.get_unit().is_none() {
+                    return Err(DataIntegrityError::QuantityUnitRequired(field_name.to_string()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// When the network enforces spec units, ensure the quantities of a record conforming to a
+    /// `ResourceSpecification` are measured in the default units declared by the specification.
+    /// `read_spec_units` reads those units from the DNA hosting `conforms_to`, and is only
+    /// called where the record conforms to a specification and enforcement is enabled.
+    ///
+    pub fn throw_if_spec_units_mismatched<A, F>(
+        &self,
+        resource_quantity: &MaybeUndefined<QuantityValue>,
+        effort_quantity: &MaybeUndefined<QuantityValue>,
+        conforms_to: Option<&A>,
+        read_spec_units: F,
+    ) -> RecordAPIResult<()>
+        where F: FnOnce(&A) -> OtherCellResult<ResourceSpecificationUnitsResponse>,
+    {
+        let spec = match conforms_to {
+            Some(spec) => spec,
+            None => return Ok(()),
+        };
+        if !self.enforce_spec_units()? {
+            return Ok(());
+        }
+        let units = read_spec_units(spec)?.resource_specification;
+        let expected = [
+            ("resourceQuantity", resource_quantity, units.default_unit_of_resource),
+            ("effortQuantity", effort_quantity, units.default_unit_of_effort),
+        ];
+        for (field_name, quantity, spec_unit) in expected.iter() {
+            if let (MaybeUndefined::Some(q), Some(spec_unit)) = (quantity, spec_unit) {
+                match q.get_unit() {
+                    Some(unit) if unit != *spec_unit => {
+                        return Err(DataIntegrityError::QuantityUnitMismatch(field_name.to_string(), unit.1, spec_unit.1.to_owned()));
+                    },
+                    _ => (),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// When the network requires registered units, ensure every unit referenced by the
+    /// provided quantities resolves to an existing `Unit` record.
+    /// `read_unit` performs the lookup against the DNA hosting the unit; records it
+    /// reports as not found are rejected, any other failure is propagated as-is.
+    ///
+    pub fn throw_if_unregistered_units<F, R>(
+        &self,
+        quantities: &[(&str, &MaybeUndefined<QuantityValue>)],
+        read_unit: F,
+    ) -> RecordAPIResult<()>
+        where F: Fn(&UnitId) -> OtherCellResult<R>,
+    {
+        if !self.enforce_registered_units()? {
+            return Ok(());
+        }
+        for (field_name, quantity) in quantities {
+            if let MaybeUndefined::Some(q) = quantity {
+                if let Some(unit) = q.get_unit() {
+                    match read_unit(&unit) {
+                        Ok(_) => (),
+                        Err(e) if e.error_envelope().code == ErrorCode::NotFound => {
+                            return Err(DataIntegrityError::QuantityUnitNotRegistered(field_name.to_string(), unit.1));
+                        },
+                        Err(e) => return Err(e.into()),
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+/**
+ * hREA network settings zome I/O data structures
+ *
+ * Required by packages wishing to interact with the zome via its standard RPC interface,
+ * and by the zome API handlers accepting these parameters.
+ *
+ * @package hREA
+ * @since   2026-10-16
+ */
+use holochain_serialized_bytes::prelude::*;
+
+use hdk_records::MaybeUndefined;
+pub use hdk_records::LogLevel;
+
+//---------------- EXTERNAL RECORD STRUCTURE ----------------
+
+// Export external type interface to allow consuming zomes to easily import & define zome API
+pub use vf_attributes_hdk::{
+    ErrorCode, ErrorEnvelope,
+    ActionHash, AgentPubKey,
+    UnitId,
+    ByRevision, RecordMeta, RevisionMeta,
+};
+
+/// I/O struct to describe the complete record
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
+    pub revision_id: ActionHash,
+    pub meta: RecordMeta,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "default_currency_unit"))]
+    pub default_currency_unit: Option<UnitId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "default_timezone_offset_minutes"))]
+    pub default_timezone_offset_minutes: Option<i32>,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "enforce_spec_units"))]
+    pub enforce_spec_units: bool,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "enforce_registered_units"))]
+    pub enforce_registered_units: bool,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "admin_agents"))]
+    pub admin_agents: Vec<AgentPubKey>,
+    /// log level applied to zomes which read network settings, overriding their DNA configuration
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "log_level"))]
+    pub log_level: Option<LogLevel>,
+}
+
+/// I/O struct to describe what is returned outside the gateway.
+/// Responses are usually returned as named attributes in order to leave space
+/// for future additional return values.
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResponseData {
+    pub network_settings: Response,
+}
+
+/// Response for reads of the settings record, which may not yet have been configured
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MaybeResponseData {
+    pub network_settings: Option<Response>,
+}
+
+//---------------- CREATE REQUEST ----------------
+
+/// I/O struct to describe the complete input record
+///
+/// The creating agent is always granted admin rights, in addition to any `admin_agents` provided.
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateRequest {
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "default_currency_unit"))]
+    pub default_currency_unit: MaybeUndefined<UnitId>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "default_timezone_offset_minutes"))]
+    pub default_timezone_offset_minutes: MaybeUndefined<i32>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "enforce_spec_units"))]
+    pub enforce_spec_units: MaybeUndefined<bool>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "enforce_registered_units"))]
+    pub enforce_registered_units: MaybeUndefined<bool>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "admin_agents"))]
+    pub admin_agents: MaybeUndefined<Vec<AgentPubKey>>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "log_level"))]
+    pub log_level: MaybeUndefined<LogLevel>,
+}
+
+//---------------- UPDATE REQUEST ----------------
+
+/// I/O struct to describe the complete input record
+///
+/// Providing `admin_agents` replaces the full set of admins, and is how admin rights are
+/// granted to & revoked from other agents.
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateRequest {
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
+    pub revision_id: ActionHash,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "default_currency_unit"))]
+    pub default_currency_unit: MaybeUndefined<UnitId>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "default_timezone_offset_minutes"))]
+    pub default_timezone_offset_minutes: MaybeUndefined<i32>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "enforce_spec_units"))]
+    pub enforce_spec_units: MaybeUndefined<bool>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "enforce_registered_units"))]
+    pub enforce_registered_units: MaybeUndefined<bool>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "admin_agents"))]
+    pub admin_agents: MaybeUndefined<Vec<AgentPubKey>>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "log_level"))]
+    pub log_level: MaybeUndefined<LogLevel>,
+}
+
+impl<'a> UpdateRequest {
+    pub fn get_revision_id(&'a self) -> &ActionHash {
+        &self.revision_id
+    }
+}
+
+#[cfg(all(test, feature = "snake_case_aliases"))]
+mod tests {
+    use super::*;
+
+    vf_attributes_hdk::snake_case_alias_tests!(Response, CreateRequest, UpdateRequest);
+}
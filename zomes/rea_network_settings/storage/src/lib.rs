@@ -0,0 +1,141 @@
+/**
+ * hREA network settings zome internal data structures
+ *
+ * Required by the zome itself, and for any DNA-local zomes interacting with its
+ * storage API directly.
+ *
+ * @package hREA
+ * @since   2026-10-16
+ */
+use hdk::prelude::*;
+
+use hdk_records::{
+    RecordAPIResult, DataIntegrityError, MaybeUndefined,
+    record_interface::{ Updateable },
+};
+
+use hc_zome_rea_network_settings_rpc::{ CreateRequest, UpdateRequest, LogLevel };
+
+pub use vf_attributes_hdk::{ UnitId };
+
+//--------------- ZOME CONFIGURATION ATTRIBUTES ----------------
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
+pub struct DnaConfigSlice {
+    #[serde(default)]
+    pub network_settings: NetworkSettingsZomeConfig,
+}
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug, Default)]
+pub struct NetworkSettingsZomeConfig {
+    // If set, only this agent may create the network settings record.
+    #[serde(default)]
+    pub progenitor: Option<AgentPubKey>,
+}
+
+//---------------- RECORD INTERNALS & VALIDATION ----------------
+
+// bounds of real-world UTC offsets, from UTC-12:00 to UTC+14:00
+const MIN_TIMEZONE_OFFSET_MINUTES: i32 = -12 * 60;
+const MAX_TIMEZONE_OFFSET_MINUTES: i32 = 14 * 60;
+
+/// Settings are stored as a single record per network. Unlike other hREA records
+/// there is no separate identity entry- the anchor `Path` serves that purpose.
+///
+#[hdk_entry_helper]
+#[derive(Clone, Default)]
+pub struct EntryData {
+    pub default_currency_unit: Option<UnitId>,
+    pub default_timezone_offset_minutes: Option<i32>,
+    pub enforce_spec_units: bool,
+    // added after initial release; absent from older revisions
+    #[serde(default)]
+    pub enforce_registered_units: bool,
+    pub admin_agents: Vec<AgentPubKey>,
+    #[serde(default)]
+    pub log_level: Option<LogLevel>,
+}
+
+impl<'a> EntryData {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.admin_agents.len() == 0 {
+            return Err("Network settings must retain at least one admin agent".to_string());
+        }
+        if let Some(offset) = self.default_timezone_offset_minutes {
+            if offset < MIN_TIMEZONE_OFFSET_MINUTES || offset > MAX_TIMEZONE_OFFSET_MINUTES {
+                return Err(format!("Timezone offset of {} minutes is out of range", offset));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_admin(&'a self, agent: &AgentPubKey) -> bool {
+        self.admin_agents.contains(agent)
+    }
+}
+
+//---------------- Holochain App Entry And Link Types Setup ----------------
+
+#[hdk_entry_defs(skip_hdk_extern = true)]
+#[unit_enum(EntryTypesUnit)]
+pub enum EntryTypes {
+    NetworkSettingsEntry(EntryData),
+}
+
+impl From<EntryData> for EntryTypes
+{
+    fn from(e: EntryData) -> EntryTypes
+    {
+        EntryTypes::NetworkSettingsEntry(e)
+    }
+}
+
+#[hdk_link_types(skip_no_mangle = true)]
+pub enum LinkTypes {
+    // links from the settings anchor to the current revision of the settings record
+    NetworkSettingsRevision,
+}
+
+//---------------- CREATE ----------------
+
+/// Pick relevant fields out of I/O record into underlying DHT entry
+///
+/// `creator` is always included in the initial set of admins.
+///
+impl EntryData {
+    pub fn from_request(e: CreateRequest, creator: AgentPubKey) -> RecordAPIResult<EntryData> {
+        let mut admin_agents: Vec<AgentPubKey> = e.admin_agents.to_option().unwrap_or(vec![]);
+        if !admin_agents.contains(&creator) {
+            admin_agents.insert(0, creator);
+        }
+
+        let entry = EntryData {
+            default_currency_unit: e.default_currency_unit.into(),
+            default_timezone_offset_minutes: e.default_timezone_offset_minutes.into(),
+            enforce_spec_units: e.enforce_spec_units.to_option().unwrap_or(false),
+            enforce_registered_units: e.enforce_registered_units.to_option().unwrap_or(false),
+            admin_agents,
+            log_level: e.log_level.into(),
+        };
+        entry.validate().map_err(|e| DataIntegrityError::Wasm(wasm_error!(WasmErrorInner::Guest(e))))?;
+        Ok(entry)
+    }
+}
+
+//---------------- UPDATE ----------------
+
+/// Handles update operations by merging any newly provided fields
+impl Updateable<UpdateRequest> for EntryData {
+    fn update_with(&self, e: UpdateRequest) -> RecordAPIResult<EntryData> {
+        let entry = EntryData {
+            default_currency_unit: if e.default_currency_unit == MaybeUndefined::Undefined { self.default_currency_unit.to_owned() } else { e.default_currency_unit.to_owned().into() },
+            default_timezone_offset_minutes: if e.default_timezone_offset_minutes == MaybeUndefined::Undefined { self.default_timezone_offset_minutes.to_owned() } else { e.default_timezone_offset_minutes.to_owned().into() },
+            enforce_spec_units: if !e.enforce_spec_units.is_some() { self.enforce_spec_units } else { e.enforce_spec_units.to_owned().unwrap() },
+            enforce_registered_units: if !e.enforce_registered_units.is_some() { self.enforce_registered_units } else { e.enforce_registered_units.to_owned().unwrap() },
+            admin_agents: if !e.admin_agents.is_some() { self.admin_agents.to_owned() } else { e.admin_agents.to_owned().unwrap() },
+            log_level: if e.log_level == MaybeUndefined::Undefined { self.log_level } else { e.log_level.to_owned().into() },
+        };
+        entry.validate().map_err(|e| DataIntegrityError::Wasm(wasm_error!(WasmErrorInner::Guest(e))))?;
+        Ok(entry)
+    }
+}
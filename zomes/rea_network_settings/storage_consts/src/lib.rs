@@ -0,0 +1,11 @@
+/**
+ * Storage constants for zome entry & link type identifiers
+ *
+ * Used by modules interfacing with the underlying Holochain storage system directly.
+ *
+ * @package hREA
+ */
+pub const NETWORK_SETTINGS_ENTRY_TYPE: &str = "network_settings";
+
+// anchor `Path` from which the current revision of the settings record is linked
+pub const NETWORK_SETTINGS_ANCHOR: &str = "network_settings";
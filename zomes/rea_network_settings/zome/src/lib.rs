@@ -0,0 +1,62 @@
+/**
+ * hREA network settings zome API definition
+ *
+ * Defines the top-level zome configuration needed by Holochain's build system
+ * to bundle the app. This basically involves wiring up the helper methods from the
+ * related `_lib` module into a packaged zome WASM binary.
+ *
+ * @package hREA
+ * @since   2026-10-16
+ */
+use hdk::prelude::*;
+
+use hc_zome_rea_network_settings_rpc::*;
+use hc_zome_rea_network_settings_lib::*;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateParams {
+    pub network_settings: CreateRequest,
+}
+
+#[hdk_extern]
+fn create_network_settings(CreateParams { network_settings }: CreateParams) -> ExternResult<ResponseData> {
+    Ok(handle_create_network_settings(network_settings)?)
+}
+
+#[hdk_extern]
+fn get_network_settings(_: ()) -> ExternResult<MaybeResponseData> {
+    Ok(handle_get_network_settings()?)
+}
+
+#[hdk_extern]
+fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
+    Ok(handle_get_revision(revision_id)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct UpdateParams {
+    pub network_settings: UpdateRequest,
+}
+
+#[hdk_extern]
+fn update_network_settings(UpdateParams { network_settings }: UpdateParams) -> ExternResult<ResponseData> {
+    Ok(handle_update_network_settings(network_settings)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LogLevelParams {
+    pub level: Option<LogLevel>,
+}
+
+// :NOTE: administrative operation, restricted to network admins
+#[hdk_extern]
+fn set_log_level(LogLevelParams { level }: LogLevelParams) -> ExternResult<ResponseData> {
+    Ok(handle_set_log_level(level)?)
+}
+
+#[hdk_extern]
+fn delete_network_settings(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
+    Ok(handle_delete_network_settings(revision_id)?)
+}
@@ -7,21 +7,36 @@
  * @package hREA
  */
 use paste::paste;
+use hdk::prelude::{DnaHash, serde};
 use hdk_records::{
-    RecordAPIResult, SignedActionHashed,
+    generate_patch_builder,
+    RecordAPIResult, SignedActionHashed, OtherCellResult,
     records::{
         create_record,
         read_record_entry,
         read_record_entry_by_action,
         update_record,
+        revision_changed,
         delete_record,
     },
     metadata::read_revision_metadata_abbreviated,
+    rpc::call_zome_method,
 };
 use hdk_semantic_indexes_client_lib::*;
+use vf_measurement::{QuantityValue, add, subtract};
 
 use hc_zome_rea_plan_storage::*;
 use hc_zome_rea_plan_rpc::*;
+use hc_zome_rea_process_storage_consts::PROCESS_READ_BATCH_API_METHOD;
+use hc_zome_rea_process_rpc::{
+    Response as ProcessResponse,
+    ResponseData as ProcessResponseData,
+};
+use hc_zome_rea_commitment_storage_consts::COMMITMENT_READ_BATCH_API_METHOD;
+use hc_zome_rea_commitment_rpc::{
+    Response as CommitmentResponse,
+    ResponseData as CommitmentResponseData,
+};
 
 
 pub use hc_zome_rea_plan_storage::PLAN_ENTRY_TYPE;
@@ -44,6 +59,235 @@ pub fn handle_get_plan(address: PlanAddress) -> RecordAPIResult<ResponseData>
     construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
 }
 
+/// Assemble scheduling data for a `Plan`, its `Process`es and the due dates of
+/// their input & output `Commitment`s. Records are read with one batch request
+/// per remote DNA; any which fail to load are returned as error stubs.
+///
+pub fn handle_get_plan_timeline(address: PlanAddress) -> RecordAPIResult<PlanTimeline>
+{
+    let plan = handle_get_plan(address)?.plan;
+    let processes = read_remote_batch(
+        plan.processes.to_owned(), PROCESS_READ_BATCH_API_METHOD,
+        |r: ProcessResponseData| r.process,
+    );
+    let commitment_ids: Vec<CommitmentAddress> = processes.iter()
+        .filter_map(|(_, p)| p.as_ref().ok())
+        .flat_map(|p| p.committed_inputs.iter().chain(p.committed_outputs.iter()).cloned())
+        .collect();
+    let commitments = read_remote_batch(
+        commitment_ids, COMMITMENT_READ_BATCH_API_METHOD,
+        |r: CommitmentResponseData| r.commitment,
+    );
+
+    let mut items = vec![TimelineItem::Plan(PlanTimelineEntry {
+        id: plan.id.to_owned(),
+        name: plan.name.to_owned(),
+        due: plan.due.to_owned(),
+    })];
+    for (process_id, process) in processes {
+        let process = match process {
+            Ok(p) => p,
+            Err(message) => {
+                items.push(TimelineItem::ProcessError(TimelineErrorStub { id: process_id, parent: plan.id.to_owned(), message }));
+                continue;
+            },
+        };
+        items.push(TimelineItem::Process(ProcessTimelineEntry {
+            id: process.id.to_owned(),
+            parent: plan.id.to_owned(),
+            name: process.name.to_owned(),
+            has_beginning: process.has_beginning.to_owned(),
+            has_end: process.has_end.to_owned(),
+        }));
+        for (direction, ids) in [
+            (CommitmentDirection::Input, &process.committed_inputs),
+            (CommitmentDirection::Output, &process.committed_outputs),
+        ] {
+            if ids.is_empty() {
+                continue;
+            }
+            let mut loaded: Vec<&CommitmentResponse> = vec![];
+            for id in ids {
+                match commitments.iter().find(|(c, _)| c == id) {
+                    Some((_, Ok(c))) => loaded.push(c),
+                    Some((_, Err(message))) => items.push(TimelineItem::CommitmentError(TimelineErrorStub {
+                        id: id.to_owned(), parent: process.id.to_owned(), message: message.to_owned(),
+                    })),
+                    None => {},
+                }
+            }
+            if loaded.is_empty() {
+                continue;
+            }
+            items.push(TimelineItem::CommitmentSpan(CommitmentSpanTimelineEntry {
+                parent: process.id.to_owned(),
+                direction,
+                earliest_due: loaded.iter().filter_map(|c| c.due).min(),
+                latest_due: loaded.iter().filter_map(|c| c.due).max(),
+                commitments: loaded.iter().map(|c| c.id.to_owned()).collect(),
+            }));
+        }
+    }
+
+    Ok(PlanTimeline { items })
+}
+
+/// Read records from the DNAs they belong to, making a single request to each DNA.
+/// Every requested address is returned alongside its record or a description of
+/// why it could not be loaded.
+///
+fn read_remote_batch<A, D, R>(
+    addresses: Vec<A>,
+    remote_permission_id: &str,
+    unwrap: fn(D) -> R,
+) -> Vec<(A, Result<R, String>)>
+    where A: AsRef<DnaHash> + Clone + PartialEq + serde::Serialize + serde::de::DeserializeOwned + std::fmt::Debug,
+        D: serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    let mut by_dna: Vec<Vec<A>> = vec![];
+    for address in addresses {
+        match by_dna.iter_mut().find(|batch| batch[0].as_ref() == address.as_ref()) {
+            Some(batch) => batch.push(address),
+            None => by_dna.push(vec![address]),
+        }
+    }
+
+    let mut results = vec![];
+    for batch in by_dna {
+        let resp: OtherCellResult<Vec<ByAddressResult<A, D>>> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+            &batch[0],
+            &remote_permission_id,
+            ByAddresses { addresses: batch.to_owned() },
+            LinkTypes::AvailableCapability,
+        );
+        match resp {
+            Ok(mut records) => {
+                for address in batch {
+                    match records.iter().position(|r| r.address == address) {
+                        Some(i) => results.push((address, records.swap_remove(i).into_result().map(unwrap))),
+                        None => results.push((address, Err("record not found".to_string()))),
+                    }
+                }
+            },
+            Err(e) => results.extend(batch.into_iter().map(|address| (address, Err(e.to_string())))),
+        }
+    }
+    results
+}
+
+/// Report how much of each independent demand of a `Plan` is covered by the output
+/// `Commitment`s of `Process`es planned within it. Outputs are matched to a demand
+/// by `resource_conforms_to`; demands are ordered by `due`, with undated demands last.
+///
+/// Records which fail to load are returned as errors. If any process or output commitment
+/// cannot be loaded the planned supply is unknown, so every demand is marked indeterminate.
+///
+pub fn handle_get_plan_demand_status(address: PlanAddress) -> RecordAPIResult<PlanDemandStatus>
+{
+    let plan = handle_get_plan(address)?.plan;
+    let processes = read_remote_batch(
+        plan.processes.to_owned(), PROCESS_READ_BATCH_API_METHOD,
+        |r: ProcessResponseData| r.process,
+    );
+
+    let mut errors = vec![];
+    let mut output_ids: Vec<CommitmentAddress> = vec![];
+    for (id, process) in processes.iter() {
+        match process {
+            Ok(p) => output_ids.extend(p.committed_outputs.iter().cloned()),
+            Err(message) => errors.push(DemandStatusError::ProcessError(TimelineErrorStub {
+                id: id.to_owned(), parent: plan.id.to_owned(), message: message.to_owned(),
+            })),
+        }
+    }
+
+    let mut commitment_ids = plan.independent_demands.to_owned();
+    for id in output_ids.iter() {
+        if !commitment_ids.contains(id) {
+            commitment_ids.push(id.to_owned());
+        }
+    }
+    let commitments = read_remote_batch(
+        commitment_ids, COMMITMENT_READ_BATCH_API_METHOD,
+        |r: CommitmentResponseData| r.commitment,
+    );
+
+    let mut supply_incomplete = !errors.is_empty();
+    let mut demands: Vec<&CommitmentResponse> = vec![];
+    let mut outputs: Vec<&CommitmentResponse> = vec![];
+    for (id, commitment) in commitments.iter() {
+        match commitment {
+            Ok(c) => {
+                if plan.independent_demands.contains(id) {
+                    demands.push(c);
+                }
+                if output_ids.contains(id) {
+                    outputs.push(c);
+                }
+            },
+            Err(message) => {
+                supply_incomplete = supply_incomplete || output_ids.contains(id);
+                errors.push(DemandStatusError::CommitmentError(TimelineErrorStub {
+                    id: id.to_owned(), parent: plan.id.to_owned(), message: message.to_owned(),
+                }));
+            },
+        }
+    }
+    demands.sort_by_key(|c| (c.due.is_none(), c.due));
+
+    let demands = demands.into_iter()
+        .map(|demand| {
+            let coverage = match (&demand.resource_conforms_to, &demand.resource_quantity, supply_incomplete) {
+                (Some(spec), Some(demanded), false) => {
+                    let supplied: Vec<QuantityValue> = outputs.iter()
+                        .filter(|o| o.id != demand.id && o.resource_conforms_to.as_ref() == Some(spec))
+                        .filter_map(|o| o.resource_quantity.to_owned())
+                        .collect();
+                    demand_coverage(demanded, supplied.as_slice())
+                },
+                _ => None,
+            };
+            DemandStatus {
+                commitment: demand.id.to_owned(),
+                due: demand.due.to_owned(),
+                resource_conforms_to: demand.resource_conforms_to.to_owned(),
+                demanded: demand.resource_quantity.to_owned(),
+                indeterminate: coverage.is_none(),
+                planned: coverage.as_ref().map(|(planned, _)| planned.to_owned()),
+                shortfall: coverage.map(|(_, shortfall)| shortfall),
+            }
+        })
+        .collect();
+
+    Ok(PlanDemandStatus { demands, errors })
+}
+
+/// Total the `supplied` quantities and determine the shortfall against `demanded`,
+/// which is zero where supply meets or exceeds the demand. Returns `None` if any
+/// supplied quantity is measured in a different unit to the demand.
+///
+fn demand_coverage(demanded: &QuantityValue, supplied: &[QuantityValue]) -> Option<(QuantityValue, QuantityValue)>
+{
+    let mut planned = QuantityValue::new(0.0, demanded.get_unit());
+    for qty in supplied {
+        planned = add(planned, qty.to_owned()).ok()?;
+    }
+    let shortfall = match demanded.exceeds(&planned) {
+        true => subtract(demanded.to_owned(), planned.to_owned()).ok()?,
+        false => QuantityValue::new(0.0, demanded.get_unit()),
+    };
+    Some((planned, shortfall))
+}
+
+/// Resync the indexes of a `Plan`. Plans hold no field indexes of their own- their relationships
+/// are indexed by the records referencing them- so this only confirms the record is readable.
+///
+pub fn handle_resync_plan_indexes(address: PlanAddress) -> RecordAPIResult<bool>
+{
+    read_record_entry::<EntryData, EntryStorage, PlanAddress>(address.as_ref())?;
+    Ok(true)
+}
+
 pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
 {
     let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
@@ -54,7 +298,26 @@ pub fn handle_update_plan(plan: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
     let revision_hash = plan.get_revision_id().clone();
     let (meta, identity_address, entry, _prev_entry): (_,_, EntryData, EntryData) = update_record(&revision_hash, plan)?;
-    construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?)
+    let mut response = construct_response(&identity_address, &meta, &entry, get_link_fields(&identity_address)?)?;
+    response.plan.meta.changed = revision_changed(&meta, &revision_hash);
+    Ok(response)
+}
+
+generate_patch_builder!(UpdateRequest {
+    name,
+    created,
+    due,
+    note,
+    deletable
+});
+
+/// Apply a set of `PatchOp`s to the latest revision of a `Plan`.
+/// The patch is converted to an `UpdateRequest` and applied via `handle_update_plan`.
+///
+pub fn handle_patch_plan(address: PlanAddress, ops: Vec<PatchOp>) -> RecordAPIResult<ResponseData>
+{
+    let revision_id = handle_get_plan(address)?.plan.revision_id;
+    handle_update_plan(build_patch_update(revision_id, ops)?)
 }
 
 pub fn handle_delete_plan(address: ActionHash) -> RecordAPIResult<bool> {
@@ -104,3 +367,41 @@ fn get_link_fields(base_address: &PlanAddress) -> RecordAPIResult<(
         read_index!(plan(base_address).independent_demands)?,
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit(name: &str) -> Option<UnitId> {
+        Some(UnitId(DnaHash::from_raw_36(vec![0xdb; 36]), name.to_string()))
+    }
+
+    #[test]
+    fn test_demand_coverage() {
+        let demanded = QuantityValue::new(10.0, unit("kg"));
+
+        let (planned, shortfall) = demand_coverage(&demanded, &[]).unwrap();
+        assert_eq!(planned, QuantityValue::new(0.0, unit("kg")));
+        assert_eq!(shortfall, demanded);
+
+        let (planned, shortfall) = demand_coverage(&demanded, &[
+            QuantityValue::new(3.0, unit("kg")),
+            QuantityValue::new(4.0, unit("kg")),
+        ]).unwrap();
+        assert_eq!(planned, QuantityValue::new(7.0, unit("kg")));
+        assert_eq!(shortfall, QuantityValue::new(3.0, unit("kg")));
+
+        let (planned, shortfall) = demand_coverage(&demanded, &[QuantityValue::new(12.0, unit("kg"))]).unwrap();
+        assert_eq!(planned, QuantityValue::new(12.0, unit("kg")));
+        assert_eq!(shortfall, QuantityValue::new(0.0, unit("kg")));
+    }
+
+    #[test]
+    fn test_demand_coverage_mismatching_units() {
+        let demanded = QuantityValue::new(10.0, unit("kg"));
+        assert!(demand_coverage(&demanded, &[
+            QuantityValue::new(3.0, unit("kg")),
+            QuantityValue::new(4.0, unit("lb")),
+        ]).is_none());
+    }
+}
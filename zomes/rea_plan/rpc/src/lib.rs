@@ -9,14 +9,19 @@
 use holochain_serialized_bytes::prelude::*;
 
 use serde_maybe_undefined::MaybeUndefined;
+use vf_measurement::QuantityValue;
 pub use vf_attributes_hdk::{
+    ErrorCode, ErrorEnvelope,
+    PatchOp, PatchParams,
     PlanAddress,
     CommitmentAddress,
     ProcessAddress,
     EconomicEventAddress,
+    ResourceSpecificationAddress,
+    UnitId,
     DateTime,
     FixedOffset,
-    ByAction, ActionHash, ByRevision, RecordMeta, RevisionMeta,
+    ByAction, ByAddress, ByAddresses, ByAddressResult, ActionHash, ByRevision, RecordMeta, RevisionMeta,
 };
 
 //---------------- EXTERNAL RECORD STRUCTURE ----------------
@@ -27,6 +32,7 @@ pub use vf_attributes_hdk::{
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     pub id: PlanAddress,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     pub meta: RecordMeta,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -44,6 +50,7 @@ pub struct Response {
     pub processes: Vec<ProcessAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "independent_demands"))]
     pub independent_demands: Vec<CommitmentAddress>,
 }
 
@@ -96,6 +103,7 @@ impl<'a> CreateRequest {
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateRequest {
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
@@ -135,3 +143,131 @@ pub struct QueryParams {
     pub non_process_commitments: Option<CommitmentAddress>,
     pub independent_demands: Option<CommitmentAddress>,
 }
+
+//---------------- TIMELINE ----------------
+
+/// Scheduling data for a `Plan` and everything planned within it, suitable for
+/// rendering as a Gantt chart. Items are returned as a flat list; nesting is
+/// expressed by the `parent` reference of each item.
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanTimeline {
+    pub items: Vec<TimelineItem>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum TimelineItem {
+    Plan(PlanTimelineEntry),
+    Process(ProcessTimelineEntry),
+    CommitmentSpan(CommitmentSpanTimelineEntry),
+    ProcessError(TimelineErrorStub<ProcessAddress, PlanAddress>),
+    CommitmentError(TimelineErrorStub<CommitmentAddress, ProcessAddress>),
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanTimelineEntry {
+    pub id: PlanAddress,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due: Option<DateTime<FixedOffset>>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessTimelineEntry {
+    pub id: ProcessAddress,
+    pub parent: PlanAddress,
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_beginning: Option<DateTime<FixedOffset>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_end: Option<DateTime<FixedOffset>>,
+}
+
+/// Which side of a `Process` a group of `Commitment`s is attached to
+///
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CommitmentDirection {
+    Input,
+    Output,
+}
+
+/// Earliest and latest `due` dates of a process's input or output commitments.
+/// Commitments without a `due` date are listed but do not affect the span.
+///
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CommitmentSpanTimelineEntry {
+    pub parent: ProcessAddress,
+    pub direction: CommitmentDirection,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub earliest_due: Option<DateTime<FixedOffset>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_due: Option<DateTime<FixedOffset>>,
+    pub commitments: Vec<CommitmentAddress>,
+}
+
+/// Placeholder for a record which could not be loaded
+///
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct TimelineErrorStub<A, P> {
+    pub id: A,
+    pub parent: P,
+    pub message: String,
+}
+
+//---------------- DEMAND STATUS ----------------
+
+/// Coverage of a `Plan`'s independent demands by the outputs of processes planned
+/// within it. Demands are ordered by due date, with undated demands last.
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanDemandStatus {
+    pub demands: Vec<DemandStatus>,
+    pub errors: Vec<DemandStatusError>,
+}
+
+/// A `Process` or `Commitment` of a `Plan` which could not be loaded when determining its demand status
+///
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum DemandStatusError {
+    ProcessError(TimelineErrorStub<ProcessAddress, PlanAddress>),
+    CommitmentError(TimelineErrorStub<CommitmentAddress, PlanAddress>),
+}
+
+/// Planned coverage of a single independent demand. Quantities are omitted when
+/// the line is `indeterminate`, which occurs when the demand has no resource quantity
+/// or specification, when matching process outputs are measured in a different unit,
+/// or when some process outputs of the plan could not be loaded.
+///
+#[derive(Clone, Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DemandStatus {
+    pub commitment: CommitmentAddress,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub due: Option<DateTime<FixedOffset>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_conforms_to: Option<ResourceSpecificationAddress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub demanded: Option<QuantityValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub planned: Option<QuantityValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shortfall: Option<QuantityValue>,
+    pub indeterminate: bool,
+}
+
+#[cfg(all(test, feature = "snake_case_aliases"))]
+mod tests {
+    use super::*;
+
+    vf_attributes_hdk::snake_case_alias_tests!(Response, CreateRequest, UpdateRequest);
+}
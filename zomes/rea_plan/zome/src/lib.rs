@@ -33,6 +33,21 @@ fn get_plan(ReadParams { address }: ReadParams) -> ExternResult<ResponseData> {
     Ok(handle_get_plan(address)?)
 }
 
+#[hdk_extern]
+fn resync_record_indexes(ReadParams { address }: ReadParams) -> ExternResult<bool> {
+    Ok(handle_resync_plan_indexes(address)?)
+}
+
+#[hdk_extern]
+fn get_plan_timeline(ByAddress { address }: ByAddress<PlanAddress>) -> ExternResult<PlanTimeline> {
+    Ok(handle_get_plan_timeline(address)?)
+}
+
+#[hdk_extern]
+fn get_plan_demand_status(ByAddress { address }: ByAddress<PlanAddress>) -> ExternResult<PlanDemandStatus> {
+    Ok(handle_get_plan_demand_status(address)?)
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
@@ -49,6 +64,11 @@ fn update_plan(UpdateParams { plan }: UpdateParams) -> ExternResult<ResponseData
     Ok(handle_update_plan(plan)?)
 }
 
+#[hdk_extern]
+fn patch_record(PatchParams { address, ops }: PatchParams<PlanAddress>) -> ExternResult<ResponseData> {
+    Ok(handle_patch_plan(address, ops)?)
+}
+
 #[hdk_extern]
 fn delete_plan(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
     Ok(handle_delete_plan(revision_id)?)
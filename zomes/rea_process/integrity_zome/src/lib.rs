@@ -9,6 +9,7 @@
  */
 use hdi::prelude::*;
 pub use hc_zome_rea_process_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hc_zome_rea_process_storage::{EntryStorage, Identified};
 
 #[hdk_extern]
 pub fn entry_defs(_: ()) -> ExternResult<EntryDefsCallbackResult> {
@@ -27,4 +28,48 @@ pub fn __num_entry_types() -> u8 {
 #[no_mangle]
 pub fn __num_link_types() -> u8 {
     LinkTypes::len()
-}
\ No newline at end of file
+}
+#[hdk_extern]
+fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+    match op {
+        Op::StoreRecord { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::StoreEntry(StoreEntry { entry, .. }) => validate_entry(entry),
+        Op::RegisterCreateLink { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterDeleteLink { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterUpdate(RegisterUpdate { update, .. }) => validate_state_transition(
+            &update.hashed.content.original_entry_address,
+            &update.hashed.content.entry_hash,
+        ),
+        Op::RegisterDelete { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterAgentActivity { .. } => Ok(ValidateCallbackResult::Valid),
+    }
+}
+
+fn validate_entry(entry: Entry) -> ExternResult<ValidateCallbackResult> {
+    match EntryStorage::try_from(&entry) {
+        Ok(process_storage) => {
+            let record = process_storage.entry();
+            record
+                .validate_state()
+                .and_then(|()| Ok(ValidateCallbackResult::Valid))
+                .or_else(|e| Ok(ValidateCallbackResult::Invalid(e)))
+        }
+        _ => Ok(ValidateCallbackResult::Valid),
+    }
+}
+
+/// Processes may not be moved backwards through their lifecycle states by an update
+///
+fn validate_state_transition(original_entry_address: &EntryHash, new_entry_address: &EntryHash) -> ExternResult<ValidateCallbackResult> {
+    let original = must_get_entry(original_entry_address.to_owned())?;
+    let updated = must_get_entry(new_entry_address.to_owned())?;
+    match (EntryStorage::try_from(original.as_content()), EntryStorage::try_from(updated.as_content())) {
+        (Ok(prev), Ok(next)) => {
+            prev.entry()
+                .validate_state_transition(&next.entry())
+                .and_then(|()| Ok(ValidateCallbackResult::Valid))
+                .or_else(|e| Ok(ValidateCallbackResult::Invalid(e)))
+        }
+        _ => Ok(ValidateCallbackResult::Valid),
+    }
+}
@@ -7,8 +7,10 @@
  * @package hREA
  */
 use paste::paste;
+use hdk::prelude::DnaHash;
 use hdk_records::{
-    RecordAPIResult,
+    generate_patch_builder,
+    RecordAPIResult, DataIntegrityError,
     records::{
         create_record,
         read_record_entry,
@@ -17,12 +19,37 @@ use hdk_records::{
         delete_record,
     },
     metadata::read_revision_metadata_abbreviated,
+    history::read_revision_window,
+    rpc::{call_zome_method, call_local_zome_method},
+    dependencies::{
+        summarize_dependents,
+        throw_if_dependents,
+        cascade_delete_dependents,
+        throw_if_cascade_failed,
+    },
+    RevisionMeta,
     MaybeUndefined, SignedActionHashed,
+    DeletionReport, OtherCellResult,
 };
 use hdk_semantic_indexes_client_lib::*;
+use vf_measurement::add;
 
 use hc_zome_rea_process_storage::*;
 use hc_zome_rea_process_rpc::*;
+use hc_zome_rea_commitment_storage_consts::COMMITMENT_READ_BATCH_API_METHOD;
+use hc_zome_rea_commitment_rpc::{
+    Response as CommitmentResponse,
+    ResponseData as CommitmentResponseData,
+};
+use hc_zome_rea_economic_resource_rpc::{
+    InventorySummary, InventorySummaryParams,
+};
+use hc_zome_rea_economic_event_rpc::{
+    CreateParams as EventCreateParams,
+    CreateRequest as EventCreateRequest,
+    Response as EventResponse,
+    ResponseData as EventResponseData,
+};
 
 
 /// properties accessor for zome config
@@ -33,36 +60,136 @@ fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
 pub fn handle_create_process<S>(entry_def_id: S, process: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
+    if let MaybeUndefined::Some(parent) = &process.composed_of_parent {
+        validate_composed_of_parent(None, parent)?;
+    }
     let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, process.to_owned())?;
 
     // handle link fields
     // :TODO: propogate errors
-    if let CreateRequest { planned_within: MaybeUndefined::Some(planned_within), .. } = &process {
-        let e = create_index!(process.planned_within(planned_within), plan.processes(&base_address));
-        hdk::prelude::debug!("handle_create_process::planned_within index {:?}", e);
-    };
+    log_index_results("create_process", create_process_indexes(&base_address, &entry_resp)?);
+
+    record_state_change(&base_address, &meta, entry_resp.current_state())?;
 
     // :TODO: pass results from link creation rather than re-reading
     construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
 }
 
+/// Create a `Process` together with its observed input & output `EconomicEvent`s, as a single unit.
+///
+/// Events are written via the configured `economic_event_zome`, which indexes them against the process.
+/// Events matching the external reference of an existing event must already be observed by the
+/// process in the same way. If any event cannot be created the error is returned, and the zome call
+/// fails without committing any of its writes.
+///
+pub fn handle_create_process_with_events<S>(entry_def_id: S, params: CreateWithEventsParams) -> RecordAPIResult<CreateWithEventsResponse>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    let process = handle_create_process(entry_def_id, params.process)?.process;
+
+    let mut inputs = vec![];
+    for mut event in params.inputs {
+        event.input_of = MaybeUndefined::Some(process.id.to_owned());
+        inputs.push(create_process_event(event)?);
+    }
+    let mut outputs = vec![];
+    for mut event in params.outputs {
+        event.output_of = MaybeUndefined::Some(process.id.to_owned());
+        outputs.push(create_process_event(event)?);
+    }
+
+    Ok(CreateWithEventsResponse { process: process.id, inputs, outputs })
+}
+
+fn create_process_event(event: EventCreateRequest) -> RecordAPIResult<EconomicEventAddress>
+{
+    let reference = event.get_external_reference();
+    let input_of = event.input_of.to_owned().to_option();
+    let output_of = event.output_of.to_owned().to_option();
+
+    let resp: EventResponseData = call_local_zome_method(
+        read_economic_event_zome, "create_economic_event",
+        EventCreateParams { event, new_inventoried_resource: None },
+    )?;
+    let EventResponse { id, input_of: existing_input_of, output_of: existing_output_of, .. } = resp.economic_event;
+    if resp.already_existed && (existing_input_of != input_of || existing_output_of != output_of) {
+        return Err(DataIntegrityError::ExternalReferenceExists(reference.unwrap_or_default()));
+    }
+    Ok(id)
+}
+
+/// Properties accessor for zome config
+fn read_economic_event_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.process.economic_event_zome
+}
+
 pub fn handle_get_process(address: ProcessAddress) -> RecordAPIResult<ResponseData>
 {
     let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
     construct_response(&base_address, &meta, &entry, get_link_fields(&address)?)
 }
 
+/// Index writes made for a `Process` when it is created, and re-run to resync its indexes.
+///
+fn create_process_indexes(base_address: &ProcessAddress, entry: &EntryData) -> RecordAPIResult<RecordIndexResults>
+{
+    let mut results = vec![];
+    if let Some(planned_within) = &entry.planned_within {
+        results.push(("planned_within", create_index!(process.planned_within(planned_within), plan.processes(base_address))));
+    }
+    if let Some(composed_of_parent) = &entry.composed_of_parent {
+        results.push(("composed_of_parent", create_index!(process.composed_of_parent(composed_of_parent), process.composed_of_children(base_address))));
+    }
+    results.push(("finished_state", update_string_index!(process(base_address).finished_state(entry.finished_state())<ProcessStateId>)));
+    Ok(results)
+}
+
+/// Rebuild the indexes of a `Process` from its latest revision.
+///
+pub fn handle_resync_process_indexes(address: ProcessAddress) -> RecordAPIResult<bool>
+{
+    let (_meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    throw_if_resync_failed(create_process_indexes(&base_address, &entry)?)
+}
+
+/// Read many `Process`es in a single call. Each address is returned alongside its
+/// record or the reason it could not be read, so that one failure does not fail the batch.
+///
+pub fn handle_get_processes(addresses: Vec<ProcessAddress>) -> RecordAPIResult<Vec<ByAddressResult<ProcessAddress, ResponseData>>>
+{
+    Ok(addresses.into_iter()
+        .map(|address| ByAddressResult::new(address.to_owned(), handle_get_process(address)))
+        .collect())
+}
+
 pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
 {
     let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
     construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
 }
 
+/// Read the revisions of a process written within the window described by `params`
+///
+pub fn handle_get_process_revision_history(params: RevisionHistoryParams<ProcessAddress>) -> RecordAPIResult<RevisionHistory<ResponseData>>
+{
+    let link_fields = get_link_fields(&params.address)?;
+    read_revision_window::<EntryData, EntryStorage, ProcessAddress, _, _>(
+        &params.address, params.since, params.until, params.limit,
+        |meta, entry| construct_response(&params.address, meta, entry, link_fields.to_owned()),
+    )
+}
+
 pub fn handle_update_process(process: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
     let address = process.get_revision_id().clone();
+    if let MaybeUndefined::Some(parent) = &process.composed_of_parent {
+        let (_meta, base_address, _entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&address)?;
+        validate_composed_of_parent(Some(&base_address), parent)?;
+    }
     let (meta, base_address, new_entry, prev_entry): (_,_, EntryData, EntryData) = update_record(&address, process)?;
 
+    hdk_records::return_if_unchanged!(meta, address, construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?), process);
+
     // handle link fields
     if new_entry.planned_within != prev_entry.planned_within {
         let new_value = match &new_entry.planned_within { Some(val) => vec![val.to_owned()], None => vec![] };
@@ -73,23 +200,153 @@ pub fn handle_update_process(process: UpdateRequest) -> RecordAPIResult<Response
                 .not(prev_value.as_slice()),
             plan.processes(&base_address)
         );
-        hdk::prelude::debug!("handle_update_process::planned_within index {:?}", e);
+        hdk_records::log_debug!("update_process", index = "planned_within"; "{:?}", e);
+    }
+    if new_entry.composed_of_parent != prev_entry.composed_of_parent {
+        let new_value = match &new_entry.composed_of_parent { Some(val) => vec![val.to_owned()], None => vec![] };
+        let prev_value = match &prev_entry.composed_of_parent { Some(val) => vec![val.to_owned()], None => vec![] };
+        let e = update_index!(
+            process
+                .composed_of_parent(new_value.as_slice())
+                .not(prev_value.as_slice()),
+            process.composed_of_children(&base_address)
+        );
+        hdk_records::log_debug!("update_process", index = "composed_of_parent"; "{:?}", e);
+    }
+    if new_entry.finished != prev_entry.finished {
+        let e = update_string_index!(process(&base_address).finished_state(new_entry.finished_state()).not(prev_entry.finished_state())<ProcessStateId>);
+        hdk_records::log_debug!("update_process", index = "finished_state"; "{:?}", e);
+    }
+    if new_entry.current_state() != prev_entry.current_state() {
+        record_state_change(&base_address, &meta, new_entry.current_state())?;
     }
     construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?)
 }
 
-pub fn handle_delete_process(revision_id: ActionHash) -> RecordAPIResult<bool>
+generate_patch_builder!(UpdateRequest {
+    name,
+    has_beginning,
+    has_end,
+    before,
+    after,
+    classified_as,
+    based_on,
+    planned_within,
+    composed_of_parent,
+    finished,
+    state,
+    in_scope_of,
+    note
+});
+
+/// Apply a set of `PatchOp`s to the latest revision of a `Process`.
+/// The patch is converted to an `UpdateRequest` and applied via `handle_update_process`.
+///
+pub fn handle_patch_process(address: ProcessAddress, ops: Vec<PatchOp>) -> RecordAPIResult<ResponseData>
+{
+    let revision_id = handle_get_process(address)?.process.revision_id;
+    handle_update_process(build_patch_update(revision_id, ops)?)
+}
+
+/// Delete a `Process`, refusing to do so if any other processes are composed within it.
+/// If `cascade` is set, child processes (and their own children) are removed first.
+///
+pub fn handle_delete_process(revision_id: ActionHash, cascade: bool) -> RecordAPIResult<DeletionReport>
+{
+    delete_process_within(revision_id, cascade, &[])
+}
+
+/// Delete a `Process` reached by cascading from the processes in `ancestors`, outermost first.
+/// A process which is its own ancestor is never revisited, so that composition cycles written by
+/// concurrent updates cannot recurse indefinitely.
+///
+fn delete_process_within(revision_id: ActionHash, cascade: bool, ancestors: &[ProcessAddress]) -> RecordAPIResult<DeletionReport>
 {
     // load the record to ensure it is of the correct type
     let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    if ancestors.contains(&base_address) {
+        return Err(DataIntegrityError::InvalidComposition("processes composed within their own sub-processes cannot be deleted by cascade".to_string()));
+    }
+    if ancestors.len() >= MAX_PROCESS_COMPOSITION_DEPTH {
+        return Err(DataIntegrityError::InvalidComposition(format!("processes nested more than {} levels deep cannot be deleted by cascade", MAX_PROCESS_COMPOSITION_DEPTH)));
+    }
+
+    // check for dependent records
+    let children: Vec<ProcessAddress> = read_index!(process(&base_address).composed_of_children)?;
+
+    let removed_dependents = if cascade {
+        let path: Vec<ProcessAddress> = ancestors.iter().cloned().chain(std::iter::once(base_address.to_owned())).collect();
+        let (removed, failed) = cascade_delete_dependents("processes", children, |child| delete_child_process(child, &path));
+        throw_if_cascade_failed(removed, failed)?
+    } else {
+        throw_if_dependents(vec![
+            summarize_dependents("processes", &children),
+        ])?;
+        vec![]
+    };
 
     // handle link fields
+    let e = update_string_index!(process(&base_address).finished_state.not(entry.finished_state())<ProcessStateId>);
+    hdk_records::log_debug!("delete_process", index = "finished_state"; "{:?}", e);
     if let Some(plan_address) = entry.planned_within {
         let e = update_index!(process.planned_within.not(&vec![plan_address]), plan.processes(&base_address));
-        hdk::prelude::debug!("handle_delete_process::planned_within index {:?}", e);
+        hdk_records::log_debug!("delete_process", index = "planned_within"; "{:?}", e);
+    }
+    if let Some(parent_address) = entry.composed_of_parent {
+        let e = update_index!(process.composed_of_parent.not(&vec![parent_address]), process.composed_of_children(&base_address));
+        hdk_records::log_debug!("delete_process", index = "composed_of_parent"; "{:?}", e);
+    }
+    let _removed: bool = call_local_zome_method(read_index_zome, "remove_process_state_history", ByAddress { address: base_address.to_owned() })?;
+
+    // delete entry last, as it must be present in order for links to be removed
+    Ok(DeletionReport {
+        deleted: delete_record::<EntryStorage>(&revision_id)?,
+        removed_dependents,
+    })
+}
+
+/// Delete the latest revision of a child process as part of a cascading delete.
+///
+fn delete_child_process(address: &ProcessAddress, ancestors: &[ProcessAddress]) -> RecordAPIResult<bool>
+{
+    let revision_id = handle_get_process(address.to_owned())?.process.revision_id;
+    Ok(delete_process_within(revision_id, true, ancestors)?.deleted)
+}
+
+/// Upper bound on the number of ancestors walked when validating process composition
+pub const MAX_PROCESS_COMPOSITION_DEPTH: usize = 32;
+
+/// Ensure that setting `parent` as the parent of the process at `address` (if already created)
+/// would not make the process a parent of itself, by walking up the chain of parent processes.
+///
+fn validate_composed_of_parent(address: Option<&ProcessAddress>, parent: &ProcessAddress) -> RecordAPIResult<()>
+{
+    let mut ancestor = Some(parent.to_owned());
+    let mut depth = 0;
+    while let Some(current) = ancestor {
+        if Some(&current) == address {
+            return Err(DataIntegrityError::InvalidComposition("a process cannot be composed within itself or its own sub-processes".to_string()));
+        }
+        depth += 1;
+        if depth > MAX_PROCESS_COMPOSITION_DEPTH {
+            return Err(DataIntegrityError::InvalidComposition(format!("processes cannot be nested more than {} levels deep", MAX_PROCESS_COMPOSITION_DEPTH)));
+        }
+        let (_meta, _base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(current.as_ref())?;
+        ancestor = entry.composed_of_parent;
     }
+    Ok(())
+}
 
-    delete_record::<EntryStorage>(&revision_id)
+/// Index the process as having entered `state` at the time of the revision `meta`
+///
+fn record_state_change(address: &ProcessAddress, meta: &SignedActionHashed, state: ProcessState) -> RecordAPIResult<()>
+{
+    let _indexed: bool = call_local_zome_method(read_index_zome, "record_process_state_change", StateChangeParams {
+        address: address.to_owned(),
+        state,
+        timestamp: RevisionMeta::from(meta).time,
+    })?;
+    Ok(())
 }
 
 /// Create response from input DHT primitives
@@ -102,6 +359,7 @@ fn construct_response<'a>(
         next_processes, previous_processes,
         working_agents,
         trace, track,
+        composed_of_children,
      ): (
         Vec<EconomicEventAddress>, Vec<EconomicEventAddress>,
         Vec<EconomicEventAddress>,
@@ -110,6 +368,7 @@ fn construct_response<'a>(
         Vec<ProcessAddress>, Vec<ProcessAddress>,
         Vec<AgentAddress>,
         Vec<EconomicEventAddress>, Vec<EconomicEventAddress>,
+        Vec<ProcessAddress>,
     ),
 ) -> RecordAPIResult<ResponseData> {
     Ok(ResponseData {
@@ -126,9 +385,11 @@ fn construct_response<'a>(
             classified_as: e.classified_as.to_owned(),
             based_on: e.based_on.to_owned(),
             planned_within: e.planned_within.to_owned(),
+            composed_of_parent: e.composed_of_parent.to_owned(),
             note: e.note.to_owned(),
             in_scope_of: e.in_scope_of.to_owned(),
             finished: e.finished.to_owned(),
+            state: e.current_state(),
             deletable: true,    // :TODO:
 
             // link fields
@@ -144,6 +405,7 @@ fn construct_response<'a>(
             working_agents: working_agents.to_owned(),
             trace: trace.to_owned(),
             track: track.to_owned(),
+            child_count: composed_of_children.len(),
         }
     })
 }
@@ -173,6 +435,7 @@ fn get_link_fields(process: &ProcessAddress) -> RecordAPIResult<(
     Vec<AgentAddress>,
     Vec<EconomicEventAddress>,
     Vec<EconomicEventAddress>,
+    Vec<ProcessAddress>,
 )> {
     Ok((
         read_index!(process(process).observed_inputs)?,
@@ -187,5 +450,298 @@ fn get_link_fields(process: &ProcessAddress) -> RecordAPIResult<(
         vec![], // :TODO: working_agents
         vec![], // :TODO: trace
         vec![], // :TODO: track
+        read_index!(process(process).composed_of_children)?,
     ))
 }
+
+//---------------- WORK IN PROGRESS QUERY ----------------
+
+/// Upper bound on the number of processes returned by composite process queries
+pub const MAX_PROCESS_QUERY_RESULTS: usize = 30;
+
+/// Properties accessor for zome config
+fn read_economic_resource_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.process.economic_resource_index_zome
+}
+
+/// Find unfinished processes with outstanding input commitments, and determine whether
+/// on-hand inventory of each committed `ResourceSpecification` covers those inputs.
+///
+/// Unfinished processes are examined a page of `limit` at a time, following the `after` cursor,
+/// until `limit` results are found. Input commitments are read in one batch per planning DNA for
+/// each page, and inventory in one batch for all `ResourceSpecification`s involved. Processes whose
+/// inputs cannot be read are returned as `unchecked`. Pass the returned `next_cursor` as `after`
+/// to continue from where the results ended.
+///
+/// Each process is checked against inventory independently; inventory is not allocated between
+/// processes competing for it. Inputs without a `resourceConformsTo` or `resourceQuantity` cannot
+/// be checked and are assumed to be coverable.
+///
+/// Processes created before the `finished_state` index was introduced are found once
+/// `process_index.reindex_all` has been run, which resyncs the index for every process.
+///
+pub fn handle_query_startable_processes(agent: Option<AgentAddress>, after: Option<ProcessAddress>, limit: Option<usize>) -> RecordAPIResult<StartableProcesses>
+{
+    let limit = limit.unwrap_or(MAX_PROCESS_QUERY_RESULTS).clamp(1, MAX_PROCESS_QUERY_RESULTS);
+
+    let unfinished = read_unfinished_processes()?;
+    let remaining = processes_after(unfinished.as_slice(), after.as_ref())?;
+
+    let mut candidates: Vec<(ProcessAddress, Vec<CommitmentResponse>)> = vec![];
+    let mut unchecked: Vec<UncheckedProcess> = vec![];
+    let mut next_cursor = None;
+    let mut examined = 0;
+    'pages: for page in remaining.chunks(limit) {
+        let page_inputs = page.iter()
+            .map(|process| {
+                let inputs: Vec<CommitmentAddress> = read_index!(process(process).committed_inputs)?;
+                Ok((process.to_owned(), inputs))
+            })
+            .collect::<RecordAPIResult<Vec<(ProcessAddress, Vec<CommitmentAddress>)>>>()?;
+
+        let commitments = read_commitments_batch(
+            page_inputs.iter().flat_map(|(_, inputs)| inputs.iter().cloned()).collect()
+        );
+
+        // determine outstanding inputs for each process, filtered by agent involvement
+        for (process, inputs) in page_inputs {
+            examined += 1;
+            let cursor = process.to_owned();
+            match outstanding_inputs(inputs.as_slice(), commitments.as_slice()) {
+                Err(message) => unchecked.push(UncheckedProcess { process, message }),
+                Ok(outstanding) => {
+                    let involved = match &agent {
+                        None => true,
+                        Some(agent) => outstanding.iter().any(|c| &c.provider == agent || &c.receiver == agent),
+                    };
+                    if !outstanding.is_empty() && involved {
+                        candidates.push((process, outstanding));
+                    }
+                },
+            }
+            if candidates.len() + unchecked.len() >= limit {
+                if examined < remaining.len() {
+                    next_cursor = Some(cursor);
+                }
+                break 'pages;
+            }
+        }
+    }
+
+    let mut specs: Vec<ResourceSpecificationAddress> = vec![];
+    for spec in candidates.iter().flat_map(|(_, inputs)| inputs.iter().filter_map(|c| c.resource_conforms_to.to_owned())) {
+        if !specs.contains(&spec) {
+            specs.push(spec);
+        }
+    }
+    let inventory: Vec<InventorySummary> = match specs.is_empty() {
+        true => vec![],
+        false => call_local_zome_method(
+            read_economic_resource_index_zome, "read_inventory_summary",
+            InventorySummaryParams { conforms_to: specs, at_location: None },
+        )?,
+    };
+
+    let mut result = StartableProcesses { startable: vec![], blocked: vec![], unchecked, next_cursor };
+    for (process, inputs) in candidates {
+        let checkable: Vec<(CommitmentAddress, ResourceSpecificationAddress, QuantityValue)> = inputs.iter()
+            .filter_map(|c| match (&c.resource_conforms_to, &c.resource_quantity) {
+                (Some(spec), Some(qty)) => Some((c.id.to_owned(), spec.to_owned(), qty.to_owned())),
+                _ => None,
+            })
+            .collect();
+        let shortfalls = input_shortfalls(checkable.as_slice(), inventory.as_slice());
+        if shortfalls.is_empty() {
+            result.startable.push(process);
+        } else {
+            result.blocked.push(BlockedProcess { process, shortfalls });
+        }
+    }
+
+    Ok(result)
+}
+
+/// Read the identifiers of all processes not yet marked as finished
+///
+fn read_unfinished_processes() -> RecordAPIResult<Vec<ProcessAddress>>
+{
+    let anchors: Vec<ProcessStateId> = string_index_hashes(vec![PROCESS_STATE_UNFINISHED.to_string()])?;
+    let mut processes = vec![];
+    for anchor in anchors {
+        let mut found: Vec<ProcessAddress> = read_index!(process(&anchor).finished_state_internal)?;
+        processes.append(&mut found);
+    }
+    Ok(processes)
+}
+
+/// Select the `processes` following the `after` cursor, erroring if the cursor is not among them.
+///
+fn processes_after<'a>(processes: &'a [ProcessAddress], after: Option<&ProcessAddress>) -> RecordAPIResult<&'a [ProcessAddress]>
+{
+    match after {
+        None => Ok(processes),
+        Some(cursor) => match processes.iter().position(|p| p == cursor) {
+            Some(pos) => Ok(&processes[pos + 1..]),
+            None => Err(DataIntegrityError::UnknownCursor(cursor.to_string())),
+        },
+    }
+}
+
+/// Select the unfinished commitments among `inputs`, or describe the first which could not be read.
+///
+fn outstanding_inputs(
+    inputs: &[CommitmentAddress],
+    commitments: &[(CommitmentAddress, Result<CommitmentResponse, String>)],
+) -> Result<Vec<CommitmentResponse>, String>
+{
+    let mut outstanding = vec![];
+    for input in inputs {
+        match commitments.iter().find(|(id, _)| id == input) {
+            Some((_, Ok(c))) => if !c.finished {
+                outstanding.push(c.to_owned());
+            },
+            Some((_, Err(message))) => return Err(format!("input commitment {} could not be read: {}", input, message)),
+            None => return Err(format!("input commitment {} was not returned", input)),
+        }
+    }
+    Ok(outstanding)
+}
+
+/// Read commitments from their planning DNAs, making a single request to each DNA.
+/// Every requested address is returned alongside its record or a description of
+/// why it could not be loaded.
+///
+fn read_commitments_batch(addresses: Vec<CommitmentAddress>) -> Vec<(CommitmentAddress, Result<CommitmentResponse, String>)>
+{
+    let mut by_dna: Vec<(DnaHash, Vec<CommitmentAddress>)> = vec![];
+    for address in addresses {
+        let dna: &DnaHash = address.as_ref();
+        match by_dna.iter_mut().find(|(d, _)| d == dna) {
+            Some((_, batch)) => batch.push(address),
+            None => by_dna.push((dna.to_owned(), vec![address])),
+        }
+    }
+
+    let mut commitments = vec![];
+    for (_dna, batch) in by_dna {
+        let records: OtherCellResult<Vec<ByAddressResult<CommitmentAddress, CommitmentResponseData>>> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+            &batch[0],
+            &COMMITMENT_READ_BATCH_API_METHOD,
+            ByAddresses { addresses: batch.to_owned() },
+            LinkTypes::AvailableCapability,
+        );
+        match records {
+            Ok(records) => commitments.extend(records.into_iter().map(|r| {
+                (r.address.to_owned(), r.into_result().map(|r| r.commitment))
+            })),
+            Err(e) => commitments.extend(batch.into_iter().map(|address| (address, Err(e.to_string())))),
+        }
+    }
+    commitments
+}
+
+/// Compare committed input quantities against on-hand inventory, returning any
+/// shortfalls grouped by `ResourceSpecification` and unit.
+///
+fn input_shortfalls(
+    inputs: &[(CommitmentAddress, ResourceSpecificationAddress, QuantityValue)],
+    inventory: &[InventorySummary],
+) -> Vec<InputShortfall>
+{
+    let mut required: Vec<InputShortfall> = vec![];
+    for (commitment, spec, qty) in inputs {
+        match required.iter_mut().find(|r| &r.resource_conforms_to == spec && r.required.get_unit() == qty.get_unit()) {
+            Some(r) => {
+                r.commitments.push(commitment.to_owned());
+                // units are known to match, so addition cannot fail
+                if let Ok(total) = add(r.required.to_owned(), qty.to_owned()) {
+                    r.required = total;
+                }
+            },
+            None => required.push(InputShortfall {
+                resource_conforms_to: spec.to_owned(),
+                commitments: vec![commitment.to_owned()],
+                required: qty.to_owned(),
+                available: QuantityValue::new(0.0, qty.get_unit()),
+            }),
+        }
+    }
+
+    required.into_iter()
+        .map(|mut r| {
+            let available = inventory.iter()
+                .filter(|i| i.conforms_to == r.resource_conforms_to)
+                .flat_map(|i| i.onhand_quantities.iter())
+                .filter(|q| q.get_unit() == r.required.get_unit())
+                .map(|q| q.get_numerical_value())
+                .sum();
+            r.available = QuantityValue::new(available, r.required.get_unit());
+            r
+        })
+        .filter(|r| r.required.exceeds(&r.available))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hdk::prelude::EntryHash;
+
+    fn dna() -> DnaHash {
+        DnaHash::from_raw_36(vec![0xdb; 36])
+    }
+
+    fn spec(byte: u8) -> ResourceSpecificationAddress {
+        ResourceSpecificationAddress(dna(), EntryHash::from_raw_36(vec![byte; 36]))
+    }
+
+    fn commitment(byte: u8) -> CommitmentAddress {
+        CommitmentAddress(dna(), EntryHash::from_raw_36(vec![byte; 36]))
+    }
+
+    #[test]
+    fn test_processes_after() {
+        let process = |byte: u8| ProcessAddress(dna(), EntryHash::from_raw_36(vec![byte; 36]));
+        let processes = vec![process(1), process(2), process(3)];
+
+        assert_eq!(processes_after(processes.as_slice(), None).unwrap(), processes.as_slice());
+        assert_eq!(processes_after(processes.as_slice(), Some(&process(2))).unwrap(), &processes[2..]);
+        assert!(processes_after(processes.as_slice(), Some(&process(3))).unwrap().is_empty(), "nothing follows the final process");
+        assert!(matches!(processes_after(processes.as_slice(), Some(&process(9))), Err(DataIntegrityError::UnknownCursor(_))));
+    }
+
+    #[test]
+    fn test_input_shortfalls() {
+        let inventory = vec![
+            InventorySummary { conforms_to: spec(1), onhand_quantities: vec![QuantityValue::new(10.0, None)] },
+            InventorySummary { conforms_to: spec(2), onhand_quantities: vec![QuantityValue::new(2.0, None)] },
+        ];
+
+        let covered = vec![
+            (commitment(1), spec(1), QuantityValue::new(4.0, None)),
+            (commitment(2), spec(1), QuantityValue::new(6.0, None)),
+        ];
+        assert!(input_shortfalls(covered.as_slice(), inventory.as_slice()).is_empty(), "inputs exactly covered by inventory");
+
+        let short = vec![
+            (commitment(1), spec(1), QuantityValue::new(4.0, None)),
+            (commitment(2), spec(2), QuantityValue::new(2.0, None)),
+            (commitment(3), spec(2), QuantityValue::new(1.0, None)),
+            (commitment(4), spec(3), QuantityValue::new(1.0, None)),
+        ];
+        assert_eq!(input_shortfalls(short.as_slice(), inventory.as_slice()), vec![
+            InputShortfall {
+                resource_conforms_to: spec(2),
+                commitments: vec![commitment(2), commitment(3)],
+                required: QuantityValue::new(3.0, None),
+                available: QuantityValue::new(2.0, None),
+            },
+            InputShortfall {
+                resource_conforms_to: spec(3),
+                commitments: vec![commitment(4)],
+                required: QuantityValue::new(1.0, None),
+                available: QuantityValue::new(0.0, None),
+            },
+        ]);
+    }
+}
@@ -7,10 +7,16 @@ use serde_maybe_undefined::{
     MaybeUndefined,
     default_false,
 };
+use hdk_uuid_types::{ DnaHash, addressable_identifier };
+pub use vf_measurement::QuantityValue;
 pub use vf_attributes_hdk::{
-    ActionHash, ByAction, ByRevision, RecordMeta, RevisionMeta,
+    ErrorCode, ErrorEnvelope,
+    PatchOp, PatchParams,
+    DeleteParams, DeletionReport,
+    ActionHash, ByAction, ByAddress, ByAddresses, ByAddressResult, ByRevision, RecordMeta, RevisionMeta,
+    RevisionHistoryParams, RevisionHistory,
     ProcessAddress,
-    DateTime, FixedOffset,
+    DateTime, FixedOffset, Utc,
     ExternalURL,
     ProcessSpecificationAddress,
     PlanAddress,
@@ -18,35 +24,79 @@ pub use vf_attributes_hdk::{
     CommitmentAddress,
     IntentAddress,
     AgentAddress,
+    ResourceSpecificationAddress,
+    EntryHash,
 };
+use hc_zome_rea_economic_event_rpc::CreateRequest as EventCreateRequest;
+
+// internal type for indexing against finished / unfinished state
+addressable_identifier!(ProcessStateId => EntryHash);
 
 //---------------- EXTERNAL RECORD STRUCTURE ----------------
 
+/// Lifecycle state of a `Process`.
+///
+/// Processes may only move forward through these states; a state may be skipped
+/// (eg. `planned` → `finished`) but never returned to.
+///
+#[derive(Clone, Copy, Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum ProcessState {
+    Planned,
+    InProgress,
+    Finished,
+}
+
+impl ProcessState {
+    /// All states, in lifecycle order.
+    pub const ALL: [ProcessState; 3] = [ProcessState::Planned, ProcessState::InProgress, ProcessState::Finished];
+
+    /// State implied by the `finished` flag, for records predating the `state` field.
+    pub fn from_finished(finished: bool) -> Self {
+        if finished { ProcessState::Finished } else { ProcessState::Planned }
+    }
+
+    pub fn can_transition_to(&self, next: &ProcessState) -> bool {
+        self <= next
+    }
+}
+
 /// I/O struct to describe the complete output record, including all managed link fields
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     pub id: ProcessAddress,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     pub meta: RecordMeta,
     pub name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_beginning"))]
     pub has_beginning: Option<DateTime<FixedOffset>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_end"))]
     pub has_end: Option<DateTime<FixedOffset>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub before: Option<DateTime<FixedOffset>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub after: Option<DateTime<FixedOffset>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "classified_as"))]
     pub classified_as: Option<Vec<ExternalURL>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "based_on"))]
     pub based_on: Option<ProcessSpecificationAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "planned_within"))]
     pub planned_within: Option<PlanAddress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "composed_of_parent"))]
+    pub composed_of_parent: Option<ProcessAddress>,
     pub finished: bool,
+    pub state: ProcessState,
     pub deletable: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "in_scope_of"))]
     pub in_scope_of: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub note: Option<String>,
@@ -54,33 +104,43 @@ pub struct Response {
     // query edges
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "observed_inputs"))]
     pub observed_inputs: Vec<EconomicEventAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "observed_outputs"))]
     pub observed_outputs: Vec<EconomicEventAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "unplanned_economic_events"))]
     pub unplanned_economic_events: Vec<EconomicEventAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "committed_inputs"))]
     pub committed_inputs: Vec<CommitmentAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "committed_outputs"))]
     pub committed_outputs: Vec<CommitmentAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "intended_inputs"))]
     pub intended_inputs: Vec<IntentAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "intended_outputs"))]
     pub intended_outputs: Vec<IntentAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "next_processes"))]
     pub next_processes: Vec<ProcessAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "previous_processes"))]
     pub previous_processes: Vec<ProcessAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "working_agents"))]
     pub working_agents: Vec<AgentAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
@@ -88,6 +148,10 @@ pub struct Response {
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub track: Vec<EconomicEventAddress>,
+    // number of processes composed within this one
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "child_count"))]
+    pub child_count: usize,
 }
 
 /// I/O struct to describe what is returned outside the gateway
@@ -105,22 +169,34 @@ pub struct ResponseData {
 pub struct CreateRequest {
     pub name: String,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_beginning"))]
     pub has_beginning: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_end"))]
     pub has_end: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
     pub before: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
     pub after: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "classified_as"))]
     pub classified_as: MaybeUndefined<Vec<ExternalURL>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "based_on"))]
     pub based_on: MaybeUndefined<ProcessSpecificationAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "planned_within"))]
     pub planned_within: MaybeUndefined<PlanAddress>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "composed_of_parent"))]
+    pub composed_of_parent: MaybeUndefined<ProcessAddress>,
     #[serde(default = "default_false")]
     pub finished: MaybeUndefined<bool>,
+    // defaults to `finished` or `planned`, depending on `finished`
     #[serde(default)]
+    pub state: MaybeUndefined<ProcessState>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "in_scope_of"))]
     pub in_scope_of: MaybeUndefined<Vec<String>>,
     #[serde(default)]
     pub note: MaybeUndefined<String>,
@@ -136,26 +212,38 @@ impl<'a> CreateRequest {
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateRequest {
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     #[serde(default)]
     pub name: MaybeUndefined<String>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_beginning"))]
     pub has_beginning: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_end"))]
     pub has_end: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
     pub before: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
     pub after: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "classified_as"))]
     pub classified_as: MaybeUndefined<Vec<ExternalURL>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "based_on"))]
     pub based_on: MaybeUndefined<ProcessSpecificationAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "planned_within"))]
     pub planned_within: MaybeUndefined<PlanAddress>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "composed_of_parent"))]
+    pub composed_of_parent: MaybeUndefined<ProcessAddress>,
+    #[serde(default)]
     pub finished: MaybeUndefined<bool>,
     #[serde(default)]
+    pub state: MaybeUndefined<ProcessState>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "in_scope_of"))]
     pub in_scope_of: MaybeUndefined<Vec<String>>,
     #[serde(default)]
     pub note: MaybeUndefined<String>,
@@ -183,4 +271,132 @@ pub struct QueryParams {
     pub intended_outputs: Option<IntentAddress>,
     pub working_agents: Option<AgentAddress>,
     pub planned_within: Option<PlanAddress>,
+    pub composed_of_parent: Option<ProcessAddress>,
+    pub composed_of_children: Option<ProcessAddress>,
+
+    // `process.finished` or `process.unfinished`
+    pub finished_state: Option<String>,
+    pub finished_state_internal: Option<ProcessStateId>,
+}
+
+//---------------- BATCH CREATE ----------------
+
+/// Parameters for creating a `Process` together with its observed input & output `EconomicEvent`s.
+///
+/// The `input_of` / `output_of` fields of each event are set to the new process.
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWithEventsParams {
+    pub process: CreateRequest,
+    #[serde(default)]
+    pub inputs: Vec<EventCreateRequest>,
+    #[serde(default)]
+    pub outputs: Vec<EventCreateRequest>,
+}
+
+/// Addresses of the records written by `create_process_with_events`, with events in request order
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateWithEventsResponse {
+    pub process: ProcessAddress,
+    pub inputs: Vec<EconomicEventAddress>,
+    pub outputs: Vec<EconomicEventAddress>,
+}
+
+//---------------- STATE HISTORY ----------------
+
+/// Parameters for indexing a `Process` as having entered `state` at `timestamp`
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StateChangeParams {
+    pub address: ProcessAddress,
+    pub state: ProcessState,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// A state which a `Process` has entered, and when it did so
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StateChange {
+    pub state: ProcessState,
+    pub time: DateTime<Utc>,
+}
+
+/// Parameters for querying processes currently in the given state
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StateQueryParams {
+    pub state: ProcessState,
+    // resume from the `nextCursor` of a previous page
+    #[serde(default)]
+    pub after: Option<EntryHash>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+//---------------- WORK IN PROGRESS QUERY ----------------
+
+/// Parameters for querying unfinished processes which can be started from on-hand inventory
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StartableProcessesParams {
+    // only include processes with input commitments provided or received by this agent
+    #[serde(default)]
+    pub agent: Option<AgentAddress>,
+    // resume from the `next_cursor` of a previous page
+    #[serde(default)]
+    pub after: Option<ProcessAddress>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+/// Unfinished processes with outstanding input commitments, split by whether
+/// current inventory can cover all of their inputs.
+///
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StartableProcesses {
+    pub startable: Vec<ProcessAddress>,
+    pub blocked: Vec<BlockedProcess>,
+    // processes whose input commitments could not be read
+    pub unchecked: Vec<UncheckedProcess>,
+    pub next_cursor: Option<ProcessAddress>,
+}
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct UncheckedProcess {
+    pub process: ProcessAddress,
+    pub message: String,
+}
+
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockedProcess {
+    pub process: ProcessAddress,
+    pub shortfalls: Vec<InputShortfall>,
+}
+
+/// Committed input quantity of a `ResourceSpecification` which exceeds on-hand inventory
+///
+#[derive(Clone, PartialEq, Serialize, Deserialize, SerializedBytes, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct InputShortfall {
+    pub resource_conforms_to: ResourceSpecificationAddress,
+    pub commitments: Vec<CommitmentAddress>,
+    pub required: QuantityValue,
+    pub available: QuantityValue,
+}
+
+#[cfg(all(test, feature = "snake_case_aliases"))]
+mod tests {
+    use super::*;
+
+    vf_attributes_hdk::snake_case_alias_tests!(Response, CreateRequest, UpdateRequest);
 }
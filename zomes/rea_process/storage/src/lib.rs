@@ -24,7 +24,10 @@ use vf_attributes_hdk::{
     PlanAddress,
 };
 
-use hc_zome_rea_process_rpc::{ CreateRequest, UpdateRequest };
+use hc_zome_rea_process_rpc::{ CreateRequest, UpdateRequest, ProcessState };
+
+// :SHONK: needed as re-export in zome logic to allow validation logic to parse entries
+pub use hdk_records::record_interface::Identified;
 
 //--------------- ZOME CONFIGURATION ATTRIBUTES ----------------
 
@@ -38,6 +41,11 @@ pub struct DnaConfigSlice {
 pub struct ProcessZomeConfig {
     pub index_zome: String,
     pub plan_index_zome: Option<String>,
+    #[serde(default)]
+    pub economic_resource_index_zome: Option<String>,
+    // zome used to write observed events for `create_process_with_events`
+    #[serde(default)]
+    pub economic_event_zome: Option<String>,
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -52,12 +60,58 @@ pub struct EntryData {
     pub classified_as: Option<Vec<ExternalURL>>,
     pub based_on: Option<ProcessSpecificationAddress>,
     pub planned_within: Option<PlanAddress>,
+    // absent in records created prior to process composition
+    #[serde(default)]
+    pub composed_of_parent: Option<ProcessAddress>,
     pub finished: bool,
+    // absent in records created prior to state tracking, @see `EntryData::current_state`
+    #[serde(default)]
+    pub state: Option<ProcessState>,
     pub in_scope_of: Option<Vec<String>>,
     pub note: Option<String>,
     pub _nonce: Bytes,
 }
 
+/// Index paths for querying processes by whether they are finished
+pub const PROCESS_STATE_FINISHED: &str = "process.finished";
+pub const PROCESS_STATE_UNFINISHED: &str = "process.unfinished";
+
+impl EntryData {
+    /// Finished state path this process should be indexed under.
+    ///
+    pub fn finished_state(&self) -> Vec<String> {
+        vec![(if self.finished { PROCESS_STATE_FINISHED } else { PROCESS_STATE_UNFINISHED }).to_string()]
+    }
+
+    /// Lifecycle state of this process, falling back to the `finished` flag where no state
+    /// has been recorded.
+    ///
+    pub fn current_state(&self) -> ProcessState {
+        self.state.unwrap_or_else(|| ProcessState::from_finished(self.finished))
+    }
+
+    /// The `finished` flag must agree with the lifecycle state, where one is recorded.
+    ///
+    pub fn validate_state(&self) -> Result<(), String> {
+        match self.state {
+            Some(state) if (state == ProcessState::Finished) != self.finished =>
+                Err(format!("Process state {:?} conflicts with finished flag {}", state, self.finished)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Processes may only move forward through their lifecycle states.
+    ///
+    pub fn validate_state_transition(&self, next: &EntryData) -> Result<(), String> {
+        let (prev_state, next_state) = (self.current_state(), next.current_state());
+        if prev_state.can_transition_to(&next_state) {
+            Ok(())
+        } else {
+            Err(format!("Process cannot transition from {:?} to {:?}", prev_state, next_state))
+        }
+    }
+}
+
 generate_record_entry!(EntryData, ProcessAddress, EntryStorage);
 
 //---------------- Holochain App Entry And Link Types Setup ----------------
@@ -99,6 +153,10 @@ impl TryFrom<CreateRequest> for EntryData {
     type Error = DataIntegrityError;
 
     fn try_from(e: CreateRequest) -> RecordAPIResult<EntryData> {
+        let state = match e.state {
+            MaybeUndefined::Some(state) => state,
+            _ => ProcessState::from_finished(e.finished.to_option().unwrap()),
+        };
         Ok(EntryData {
             name: e.name.into(),
             has_beginning: e.has_beginning.into(),
@@ -108,7 +166,9 @@ impl TryFrom<CreateRequest> for EntryData {
             classified_as: e.classified_as.into(),
             based_on: e.based_on.into(),
             planned_within: e.planned_within.into(),
-            finished: e.finished.to_option().unwrap(),  // :NOTE: unsafe, would crash if not for "default_*" bindings via Serde
+            composed_of_parent: e.composed_of_parent.into(),
+            finished: state == ProcessState::Finished,
+            state: Some(state),
             in_scope_of: e.in_scope_of.into(),
             note: e.note.into(),
             _nonce: random_bytes(32)?,
@@ -121,6 +181,15 @@ impl TryFrom<CreateRequest> for EntryData {
 /// Handles update operations by merging any newly provided fields
 impl Updateable<UpdateRequest> for EntryData {
     fn update_with(&self, e: UpdateRequest) -> RecordAPIResult<EntryData> {
+        // an explicit state takes precedence over the `finished` flag.
+        // Unfinishing a finished process reopens it, which is rejected in validation.
+        let prev_state = self.current_state();
+        let state = match (&e.state, &e.finished) {
+            (MaybeUndefined::Some(state), _) => state.to_owned(),
+            (_, MaybeUndefined::Some(true)) => ProcessState::Finished,
+            (_, MaybeUndefined::Some(false)) if prev_state == ProcessState::Finished => ProcessState::InProgress,
+            _ => prev_state,
+        };
         Ok(EntryData {
             name: if !e.name.is_some() { self.name.to_owned() } else { e.name.to_owned().unwrap() },
             has_beginning: if e.has_beginning == MaybeUndefined::Undefined { self.has_beginning.to_owned() } else { e.has_beginning.to_owned().into() },
@@ -130,7 +199,9 @@ impl Updateable<UpdateRequest> for EntryData {
             classified_as: if e.classified_as == MaybeUndefined::Undefined { self.classified_as.to_owned() } else { e.classified_as.to_owned().into() },
             based_on: if e.based_on == MaybeUndefined::Undefined { self.based_on.to_owned() } else { e.based_on.to_owned().into() },
             planned_within: if e.planned_within == MaybeUndefined::Undefined { self.planned_within.to_owned() } else { e.planned_within.to_owned().into() },
-            finished: if e.finished == MaybeUndefined::Undefined { self.finished.to_owned() } else { e.finished.to_owned().to_option().unwrap() },
+            composed_of_parent: if e.composed_of_parent == MaybeUndefined::Undefined { self.composed_of_parent.to_owned() } else { e.composed_of_parent.to_owned().into() },
+            finished: state == ProcessState::Finished,
+            state: Some(state),
             in_scope_of: if e.in_scope_of == MaybeUndefined::Undefined { self.in_scope_of.to_owned() } else { e.in_scope_of.to_owned().into() },
             note: if e.note == MaybeUndefined::Undefined { self.note.to_owned() } else { e.note.to_owned().into() },
             _nonce: self._nonce.to_owned(),
@@ -6,3 +6,6 @@
  * @package hREA
  */
 pub const PROCESS_ENTRY_TYPE: &str = "process";
+
+// remote_auth permission ID for batch reads of processes from other DNAs
+pub const PROCESS_READ_BATCH_API_METHOD: &str = "read_processes";
@@ -24,6 +24,11 @@ fn create_process(CreateParams { process }: CreateParams) -> ExternResult<Respon
     Ok(handle_create_process(PROCESS_ENTRY_TYPE, process)?)
 }
 
+#[hdk_extern]
+fn create_process_with_events(params: CreateWithEventsParams) -> ExternResult<CreateWithEventsResponse> {
+    Ok(handle_create_process_with_events(PROCESS_ENTRY_TYPE, params)?)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 struct ReadParams {
     pub address: ProcessAddress,
@@ -34,11 +39,26 @@ fn get_process(ReadParams { address }: ReadParams) -> ExternResult<ResponseData>
     Ok(handle_get_process(address)?)
 }
 
+#[hdk_extern]
+fn get_processes(ByAddresses { addresses }: ByAddresses<ProcessAddress>) -> ExternResult<Vec<ByAddressResult<ProcessAddress, ResponseData>>> {
+    Ok(handle_get_processes(addresses)?)
+}
+
+#[hdk_extern]
+fn resync_record_indexes(ByAddress { address }: ByAddress<ProcessAddress>) -> ExternResult<bool> {
+    Ok(handle_resync_process_indexes(address)?)
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
 }
 
+#[hdk_extern]
+fn get_revision_history(params: RevisionHistoryParams<ProcessAddress>) -> ExternResult<RevisionHistory<ResponseData>> {
+    Ok(handle_get_process_revision_history(params)?)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct UpdateParams {
@@ -51,6 +71,16 @@ fn update_process(UpdateParams { process }: UpdateParams) -> ExternResult<Respon
 }
 
 #[hdk_extern]
-fn delete_process(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
-    Ok(handle_delete_process(revision_id)?)
+fn patch_record(PatchParams { address, ops }: PatchParams<ProcessAddress>) -> ExternResult<ResponseData> {
+    Ok(handle_patch_process(address, ops)?)
+}
+
+#[hdk_extern]
+fn delete_process(DeleteParams { revision_id, cascade }: DeleteParams) -> ExternResult<DeletionReport> {
+    Ok(handle_delete_process(revision_id, cascade)?)
+}
+
+#[hdk_extern]
+fn query_startable_processes(StartableProcessesParams { agent, after, limit }: StartableProcessesParams) -> ExternResult<StartableProcesses> {
+    Ok(handle_query_startable_processes(agent, after, limit)?)
 }
@@ -7,7 +7,7 @@
 use hdk_semantic_indexes_zome_derive::index_zome;
 use hc_zome_rea_process_rpc::*;
 
-#[index_zome(query_fn_name="query_processes",read_all_fn_name="read_all_processes")]
+#[index_zome(query_fn_name="query_processes",read_all_fn_name="read_all_processes",time_index_prefixes="state.")]
 struct Process {
     observed_inputs: Local<economic_event, input_of>,
     observed_outputs: Local<economic_event, output_of>,
@@ -16,4 +16,89 @@ struct Process {
     intended_inputs: Remote<intent, input_of>,
     intended_outputs: Remote<intent, output_of>,
     planned_within: Local<plan, processes>,
+    composed_of_parent: Local<process, composed_of_children>,
+    composed_of_children: Local<process, composed_of_parent>,
+
+    // exclusion index for querying unfinished processes
+    finished_state: Local<process, finished_state_internal>::String,
+    // :SHONK: redundant loopback index, @see zome_idx_agent
+    finished_state_internal: Local<process, finished_state>,
+}
+
+//---------------- STATE HISTORY INDEX ----------------
+
+/// Time index recording when processes entered each lifecycle state
+fn state_time_index_id(state: &ProcessState) -> String {
+    time_index_name(TIME_INDEX_RECORD_TYPE, match state {
+        ProcessState::Planned => "state.planned",
+        ProcessState::InProgress => "state.in_progress",
+        ProcessState::Finished => "state.finished",
+    })
+}
+
+#[hdk_extern]
+fn record_process_state_change(StateChangeParams { address, state, timestamp }: StateChangeParams) -> ExternResult<bool> {
+    register_time_indexes(&TIME_INDEXES);
+    throw_if_index_read_only(read_index_read_only)?;
+    Ok(append_to_time_index(&state_time_index_id(&state), &address, timestamp)?)
+}
+
+#[hdk_extern]
+fn remove_process_state_history(ByAddress { address }: ByAddress<ProcessAddress>) -> ExternResult<bool> {
+    register_time_indexes(&TIME_INDEXES);
+    throw_if_index_read_only(read_index_read_only)?;
+    let mut removed = false;
+    for state in ProcessState::ALL.iter() {
+        removed = remove_from_time_index(&state_time_index_id(state), &address)? || removed;
+    }
+    Ok(removed)
+}
+
+/// Read the states a process has entered and when, oldest first.
+///
+#[hdk_extern]
+fn read_process_state_history(ByAddress { address }: ByAddress<ProcessAddress>) -> ExternResult<Vec<StateChange>> {
+    register_time_indexes(&TIME_INDEXES);
+    throw_if_index_read_not_permitted(read_query_access, read_agent_zome, read_network_settings_zome)?;
+
+    let mut history = vec![];
+    for state in ProcessState::ALL.iter() {
+        if let Some(time) = read_time_index_entry_time(&state_time_index_id(state), &address)? {
+            history.push(StateChange { state: state.to_owned(), time });
+        }
+    }
+    history.sort_by(|a, b| a.time.cmp(&b.time));
+    Ok(history)
+}
+
+/// A page of processes in some state, with the cursor to continue from if any remain.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StateQueryResults {
+    pub results: QueryResults,
+    pub next_cursor: Option<EntryHash>,
+}
+
+/// Query processes currently in the given state, most recently transitioned first.
+/// Processes which have since moved on to a later state are skipped while paging.
+///
+#[hdk_extern]
+fn query_processes_in_state(StateQueryParams { state, after, limit }: StateQueryParams) -> ExternResult<StateQueryResults> {
+    register_time_indexes(&TIME_INDEXES);
+    throw_if_query_not_permitted(read_query_access, read_agent_zome, read_network_settings_zome)?;
+
+    // processes which have since moved on to a later state remain in the index
+    let matches = query_time_index_page_where::<ResponseData, ProcessAddress, _,_,_,_>(
+        &read_index_target_zome,
+        &QUERY_FN_NAME,
+        &state_time_index_id(&state),
+        |record: &ResponseData| record.process.state == state,
+        after,
+        limit.unwrap_or(PAGE_SIZE),
+    )?;
+
+    Ok(StateQueryResults {
+        results: handle_list_output(matches.records.as_slice(), 0)?,
+        next_cursor: matches.next_cursor,
+    })
 }
@@ -7,6 +7,7 @@
  * @package hREA
  */
 use hdk_records::{
+    generate_patch_builder,
     RecordAPIResult, SignedActionHashed,
     records::{
         create_record,
@@ -16,6 +17,11 @@ use hdk_records::{
         delete_record,
     },
     metadata::read_revision_metadata_abbreviated,
+    name_indexes::{
+        sync_name_index,
+        list_by_name,
+        NameIndexPage,
+    },
 };
 
 use hc_zome_rea_process_specification_storage::*;
@@ -26,10 +32,22 @@ fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
     Some(conf.process_specification.index_zome)
 }
 
+/// name index for alphabetical listings
+const NAME_ORDER_INDEX: &str = "process_specification.name_order";
+
+/// number of records returned per alphabetical listing page, unless otherwise specified
+const ALPHABETICAL_PAGE_SIZE: usize = 25;
+
+/// name extractor for `NAME_ORDER_INDEX`
+fn extract_sort_name(e: &EntryData) -> String {
+    e.name.to_owned()
+}
+
 pub fn handle_create_process_specification<S>(entry_def_id: S, process_specification: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
     let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, process_specification)?;
+    create_process_specification_indexes(&base_address, &entry_resp)?;
 
     construct_response(&base_address, &meta, &entry_resp)
 }
@@ -40,6 +58,23 @@ pub fn handle_get_process_specification(address: ProcessSpecificationAddress) ->
     construct_response(&base_address, &meta, &entry)
 }
 
+/// Index writes made for a `ProcessSpecification` when it is created, and re-run to resync its indexes.
+///
+fn create_process_specification_indexes(base_address: &ProcessSpecificationAddress, entry: &EntryData) -> RecordAPIResult<()>
+{
+    sync_name_index(LinkTypes::NameIndex, NAME_ORDER_INDEX, base_address, Some(entry), None, extract_sort_name)?;
+    Ok(())
+}
+
+/// Rebuild the indexes of a `ProcessSpecification` from its latest revision.
+///
+pub fn handle_resync_process_specification_indexes(address: ProcessSpecificationAddress) -> RecordAPIResult<bool>
+{
+    let (_meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    create_process_specification_indexes(&base_address, &entry)?;
+    Ok(true)
+}
+
 pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
 {
     let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
@@ -49,15 +84,65 @@ pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseD
 pub fn handle_update_process_specification(process_specification: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
     let old_revision = process_specification.get_revision_id();
-    let (meta, base_address, new_entry, _prev_entry): (_, ProcessSpecificationAddress, EntryData, EntryData) = update_record(old_revision, process_specification.to_owned())?;
+    let (meta, base_address, new_entry, prev_entry): (_, ProcessSpecificationAddress, EntryData, EntryData) = update_record(old_revision, process_specification.to_owned())?;
+
+    hdk_records::return_if_unchanged!(meta, *old_revision, construct_response(&base_address, &meta, &new_entry), process_specification);
+
+    sync_name_index(LinkTypes::NameIndex, NAME_ORDER_INDEX, &base_address, Some(&new_entry), Some(&prev_entry), extract_sort_name)?;
     construct_response(&base_address, &meta, &new_entry)
 }
 
+generate_patch_builder!(UpdateRequest {
+    name,
+    note
+});
+
+/// Apply a set of `PatchOp`s to the latest revision of a `ProcessSpecification`.
+/// The patch is converted to an `UpdateRequest` and applied via `handle_update_process_specification`.
+///
+pub fn handle_patch_process_specification(address: ProcessSpecificationAddress, ops: Vec<PatchOp>) -> RecordAPIResult<ResponseData>
+{
+    let revision_id = handle_get_process_specification(address)?.process_specification.revision_id;
+    handle_update_process_specification(build_patch_update(revision_id, ops)?)
+}
+
 pub fn handle_delete_process_specification(revision_id: ActionHash) -> RecordAPIResult<bool>
 {
+    // clear name index before the entry is removed
+    let (_meta, base_address, entry): (_, ProcessSpecificationAddress, EntryData) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    sync_name_index(LinkTypes::NameIndex, NAME_ORDER_INDEX, &base_address, None, Some(&entry), extract_sort_name)?;
+
     delete_record::<EntryStorage>(&revision_id)
 }
 
+/// Read a page of `ProcessSpecification`s in order of their names, optionally beginning
+/// at those whose names start with `starting_with`.
+///
+pub fn handle_list_process_specifications_alphabetical(params: NameIndexParams) -> RecordAPIResult<AlphabeticalConnection>
+{
+    let limit = params.limit.unwrap_or(ALPHABETICAL_PAGE_SIZE);
+    let page: NameIndexPage<ProcessSpecificationAddress> = list_by_name(LinkTypes::NameIndex, NAME_ORDER_INDEX, params.starting_with, params.cursor, limit)?;
+
+    let edges = page.entries.into_iter()
+        .map(|(cursor, address)| Ok(AlphabeticalEdge {
+            node: handle_get_process_specification(address)?.process_specification,
+            cursor,
+        }))
+        .collect::<RecordAPIResult<Vec<AlphabeticalEdge>>>()?;
+
+    Ok(AlphabeticalConnection {
+        page_info: PageInfo {
+            start_cursor: edges.first().map(|e| e.cursor.to_owned()).unwrap_or_else(|| "0".to_string()),
+            end_cursor: edges.last().map(|e| e.cursor.to_owned()).unwrap_or_else(|| "0".to_string()),
+            has_previous_page: page.has_previous_page,
+            has_next_page: page.has_next_page,
+            total_count: None,
+            page_limit: Some(limit),
+        },
+        edges,
+    })
+}
+
 /// Create response from input DHT primitives
 fn construct_response<'a>(
     address: &ProcessSpecificationAddress, meta: &SignedActionHashed, e: &EntryData,
@@ -9,9 +9,13 @@
 use holochain_serialized_bytes::prelude::*;
 use serde_maybe_undefined::MaybeUndefined;
 pub use vf_attributes_hdk::{
+    ErrorCode, ErrorEnvelope,
+    PatchOp, PatchParams,
     ActionHash, ByAddress, ByAction, ByRevision, RecordMeta, RevisionMeta,
     ProcessSpecificationAddress,
+    NameIndexParams,
 };
+pub use hdk_relay_pagination::PageInfo;
 
 // toplevel I/O structs for WASM API
 
@@ -34,6 +38,7 @@ pub struct UpdateParams {
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     pub id: ProcessSpecificationAddress,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     pub meta: RecordMeta,
     pub name: String,
@@ -70,6 +75,7 @@ impl<'a> CreateRequest {
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateRequest {
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     #[serde(default)]
     pub name: MaybeUndefined<String>,
@@ -91,3 +97,28 @@ impl<'a> UpdateRequest {
 #[serde(rename_all = "camelCase")]
 pub struct QueryParams {
 }
+
+//---------------- ALPHABETICAL LISTING ----------------
+
+/// Page of `ProcessSpecification`s in name order, in the same shape as index zome query results
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AlphabeticalConnection {
+    pub page_info: PageInfo,
+    pub edges: Vec<AlphabeticalEdge>,
+}
+
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AlphabeticalEdge {
+    pub node: Response,
+    pub cursor: String,
+}
+
+#[cfg(all(test, feature = "snake_case_aliases"))]
+mod tests {
+    use super::*;
+
+    vf_attributes_hdk::snake_case_alias_tests!(Response, CreateRequest, UpdateRequest);
+}
@@ -71,7 +71,10 @@ impl TryFrom<AvailableCapability> for EntryTypes {
 pub enum LinkTypes {
     // relates to dna-auth-resolver mixin
     // and remote authorizations
-    AvailableCapability
+    AvailableCapability,
+    // alphabetical listing of records by name
+    // @see hdk_records::name_indexes
+    NameIndex,
 }
 
 
@@ -23,6 +23,11 @@ fn get_process_specification(ByAddress { address }: ByAddress<ProcessSpecificati
     Ok(handle_get_process_specification(address)?)
 }
 
+#[hdk_extern]
+fn resync_record_indexes(ByAddress { address }: ByAddress<ProcessSpecificationAddress>) -> ExternResult<bool> {
+    Ok(handle_resync_process_specification_indexes(address)?)
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
@@ -33,6 +38,16 @@ fn update_process_specification(UpdateParams { process_specification }: UpdatePa
     Ok(handle_update_process_specification(process_specification)?)
 }
 
+#[hdk_extern]
+fn patch_record(PatchParams { address, ops }: PatchParams<ProcessSpecificationAddress>) -> ExternResult<ResponseData> {
+    Ok(handle_patch_process_specification(address, ops)?)
+}
+
+#[hdk_extern]
+fn list_process_specifications_alphabetical(params: NameIndexParams) -> ExternResult<AlphabeticalConnection> {
+    Ok(handle_list_process_specifications_alphabetical(params)?)
+}
+
 #[hdk_extern]
 fn delete_process_specification(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
     Ok(handle_delete_process_specification(revision_id)?)
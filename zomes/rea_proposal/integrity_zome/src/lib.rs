@@ -8,7 +8,10 @@
  * @package hREA
  */
 use hdi::prelude::*;
-pub use hc_zome_rea_proposal_storage::{EntryTypes, EntryTypesUnit, LinkTypes};
+use hdk_rate_limits::validate_creation_rate;
+use hc_zome_rea_proposal_storage_consts::PROPOSAL_ENTRY_TYPE;
+pub use hc_zome_rea_proposal_storage::{EntryStorage, EntryTypes, EntryTypesUnit, Identified, LinkTypes};
+use hc_zome_rea_proposal_storage::{Identifiable, check_validation_hooks};
 
 #[hdk_extern]
 pub fn entry_defs(_: ()) -> ExternResult<EntryDefsCallbackResult> {
@@ -28,3 +31,30 @@ pub fn __num_entry_types() -> u8 {
 pub fn __num_link_types() -> u8 {
     LinkTypes::len()
 }
+
+#[hdk_extern]
+fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+    match op {
+        Op::StoreRecord { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::StoreEntry(StoreEntry { entry, .. }) => validate_entry(entry),
+        Op::RegisterCreateLink { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterDeleteLink { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterUpdate { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterDelete { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterAgentActivity(RegisterAgentActivity { action, .. }) => validate_creation_rate(
+            &action, &ScopedEntryDefIndex::try_from(&EntryTypesUnit::Proposal)?, PROPOSAL_ENTRY_TYPE,
+        ),
+    }
+}
+
+fn validate_entry(entry: Entry) -> ExternResult<ValidateCallbackResult> {
+    match EntryStorage::try_from(&entry) {
+        Ok(proposal_storage) => {
+            let record = proposal_storage.entry();
+            check_validation_hooks(&record, &record.validation_hooks())
+                .and_then(|()| Ok(ValidateCallbackResult::Valid))
+                .or_else(|e| Ok(ValidateCallbackResult::Invalid(e)))
+        }
+        _ => Ok(ValidateCallbackResult::Valid),
+    }
+}
@@ -8,6 +8,7 @@
 */
 use paste::paste;
 use hdk_records::{
+    generate_patch_builder,
     RecordAPIResult, SignedActionHashed,
     records::{
         create_record,
@@ -15,8 +16,10 @@ use hdk_records::{
         read_record_entry,
         read_record_entry_by_action,
         update_record,
+        revision_changed,
     },
     metadata::read_revision_metadata_abbreviated,
+    rate_limits::throw_if_creation_rate_exceeded,
 };
 use hdk_semantic_indexes_client_lib::*;
 
@@ -32,6 +35,7 @@ fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
 pub fn handle_create_proposal<S>(entry_def_id: S, proposal: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
+    throw_if_creation_rate_exceeded(&EntryTypesUnit::Proposal, entry_def_id.as_ref())?;
     let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, proposal)?;
     construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
 }
@@ -42,6 +46,15 @@ pub fn handle_get_proposal(address: ProposalAddress) -> RecordAPIResult<Response
     construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
 }
 
+/// Resync the indexes of a `Proposal`. Proposals hold no field indexes of their own- their relationships
+/// are indexed by the records referencing them- so this only confirms the record is readable.
+///
+pub fn handle_resync_proposal_indexes(address: ProposalAddress) -> RecordAPIResult<bool>
+{
+    read_record_entry::<EntryData, EntryStorage, ProposalAddress>(address.as_ref())?;
+    Ok(true)
+}
+
 pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
 {
     let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
@@ -52,7 +65,28 @@ pub fn handle_update_proposal(proposal: UpdateRequest) -> RecordAPIResult<Respon
 {
     let old_revision = proposal.get_revision_id().to_owned();
     let (meta, base_address, new_entry, _prev_entry): (_, ProposalAddress, EntryData, EntryData) = update_record(&old_revision, proposal)?;
-    construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?)
+    let mut response = construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?)?;
+    response.proposal.meta.changed = revision_changed(&meta, &old_revision);
+    Ok(response)
+}
+
+generate_patch_builder!(UpdateRequest {
+    name,
+    has_beginning,
+    has_end,
+    unit_based,
+    note,
+    in_scope_of,
+    images
+});
+
+/// Apply a set of `PatchOp`s to the latest revision of a `Proposal`.
+/// The patch is converted to an `UpdateRequest` and applied via `handle_update_proposal`.
+///
+pub fn handle_patch_proposal(address: ProposalAddress, ops: Vec<PatchOp>) -> RecordAPIResult<ResponseData>
+{
+    let revision_id = handle_get_proposal(address)?.proposal.revision_id;
+    handle_update_proposal(build_patch_update(revision_id, ops)?)
 }
 
 pub fn handle_delete_proposal(address: ActionHash) -> RecordAPIResult<bool> {
@@ -82,6 +116,7 @@ fn construct_response<'a>(
             created: e.created.to_owned(),
             note: e.note.to_owned(),
             in_scope_of: e.in_scope_of.to_owned(),
+            images: e.images.to_owned(),
             // link fields
             publishes: publishes.to_owned(),
             published_to: published_to.to_owned(),
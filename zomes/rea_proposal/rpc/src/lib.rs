@@ -9,9 +9,12 @@
 use holochain_serialized_bytes::prelude::*;
 use serde_maybe_undefined::MaybeUndefined;
 pub use vf_attributes_hdk::{
+    ErrorCode, ErrorEnvelope,
+    PatchOp, PatchParams,
     ActionHash, ByAddress, ByAction, ByRevision, RecordMeta, RevisionMeta,
     ProposalAddress, ProposedIntentAddress, ProposedToAddress,
     DateTime, FixedOffset,
+    ExternalURL,
 };
 
 /// Toplevel I/O structs for WASM API
@@ -36,27 +39,35 @@ pub struct UpdateParams {
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     pub id: ProposalAddress,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     pub meta: RecordMeta,
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_beginning"))]
     pub has_beginning: Option<DateTime<FixedOffset>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_end"))]
     pub has_end: Option<DateTime<FixedOffset>>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "unit_based"))]
     pub unit_based: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub created: Option<DateTime<FixedOffset>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub note: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "in_scope_of"))]
     pub in_scope_of: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub images: Option<Vec<ExternalURL>>,
     // links:
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub publishes: Vec<ProposedIntentAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "published_to"))]
     pub published_to: Vec<ProposedToAddress>,
 }
 
@@ -80,17 +91,23 @@ pub struct CreateRequest {
     #[serde(default)]
     pub name: MaybeUndefined<String>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_beginning"))]
     pub has_beginning: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_end"))]
     pub has_end: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "unit_based"))]
     pub unit_based: MaybeUndefined<bool>,
     #[serde(default)]
     pub created: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
     pub note: MaybeUndefined<String>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "in_scope_of"))]
     pub in_scope_of: MaybeUndefined<Vec<String>>,
+    #[serde(default)]
+    pub images: MaybeUndefined<Vec<ExternalURL>>,
 }
 
 impl<'a> CreateRequest {
@@ -104,19 +121,26 @@ impl<'a> CreateRequest {
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateRequest {
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     #[serde(default)]
     pub name: MaybeUndefined<String>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_beginning"))]
     pub has_beginning: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "has_end"))]
     pub has_end: MaybeUndefined<DateTime<FixedOffset>>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "unit_based"))]
     pub unit_based: MaybeUndefined<bool>,
     #[serde(default)]
     pub note: MaybeUndefined<String>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "in_scope_of"))]
     pub in_scope_of: MaybeUndefined<Vec<String>>,
+    #[serde(default)]
+    pub images: MaybeUndefined<Vec<ExternalURL>>,
 }
 
 impl<'a> UpdateRequest {
@@ -135,3 +159,10 @@ pub struct QueryParams {
     pub publishes: Option<ProposedIntentAddress>,
     pub published_to: Option<ProposedToAddress>,
 }
+
+#[cfg(all(test, feature = "snake_case_aliases"))]
+mod tests {
+    use super::*;
+
+    vf_attributes_hdk::snake_case_alias_tests!(Response, CreateRequest, UpdateRequest);
+}
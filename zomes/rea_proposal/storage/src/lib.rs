@@ -15,10 +15,15 @@ use hdk_records::{
     generate_record_entry,
 };
 
-pub use vf_attributes_hdk::{ ProposalAddress, ProposedIntentAddress, ProposedToAddress, DateTime, FixedOffset };
+pub use vf_attributes_hdk::{ ProposalAddress, ProposedIntentAddress, ProposedToAddress, DateTime, FixedOffset, ExternalURL };
+use vf_attributes_hdk::{ VALIDATE_NOTE_LENGTH, VALIDATE_EXTERNAL_URLS };
 
 use hc_zome_rea_proposal_rpc::{CreateRequest, UpdateRequest};
 
+// :SHONK: needed as re-export in zome logic to allow validation logic to parse entries
+pub use hdk_records::record_interface::{Identified, Identifiable};
+pub use hdk_records::validation::check_validation_hooks;
+
 //--------------- ZOME CONFIGURATION ATTRIBUTES ----------------
 
 // :TODO: remove this, replace with reference to appropriate namespacing of zome config
@@ -43,13 +48,15 @@ pub struct EntryData {
     pub created: Option<DateTime<FixedOffset>>,
     pub note: Option<String>,
     pub in_scope_of: Option<Vec<String>>,
+    #[serde(default)]
+    pub images: Option<Vec<ExternalURL>>,
     //[TODO]:
     //eligibleLocation: SpatialThing
     //publishes: [ProposedIntent!]
     pub _nonce: Bytes,
 }
 
-generate_record_entry!(EntryData, ProposalAddress, EntryStorage);
+generate_record_entry!(EntryData, ProposalAddress, EntryStorage, validated_by [VALIDATE_NOTE_LENGTH, VALIDATE_EXTERNAL_URLS]);
 
 //---------------- Holochain App Entry And Link Types Setup ----------------
 
@@ -100,6 +107,7 @@ impl TryFrom<CreateRequest> for EntryData {
             created: e.created.into(),
             note: e.note.into(),
             in_scope_of: e.in_scope_of.to_option(),
+            images: e.images.to_option(),
             _nonce: random_bytes(32)?,
         })
     }
@@ -142,6 +150,11 @@ impl Updateable<UpdateRequest> for EntryData {
             } else {
                 e.in_scope_of.to_owned().to_option()
             },
+            images: if e.images == MaybeUndefined::Undefined {
+                self.images.to_owned()
+            } else {
+                e.images.to_owned().to_option()
+            },
             _nonce: self._nonce.to_owned(),
         })
     }
@@ -23,6 +23,11 @@ fn get_proposal(ByAddress { address }: ByAddress<ProposalAddress>) -> ExternResu
     Ok(handle_get_proposal(address)?)
 }
 
+#[hdk_extern]
+fn resync_record_indexes(ByAddress { address }: ByAddress<ProposalAddress>) -> ExternResult<bool> {
+    Ok(handle_resync_proposal_indexes(address)?)
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
@@ -33,6 +38,11 @@ fn update_proposal(UpdateParams { proposal }: UpdateParams) -> ExternResult<Resp
     Ok(handle_update_proposal(proposal)?)
 }
 
+#[hdk_extern]
+fn patch_record(PatchParams { address, ops }: PatchParams<ProposalAddress>) -> ExternResult<ResponseData> {
+    Ok(handle_patch_proposal(address, ops)?)
+}
+
 #[hdk_extern]
 fn delete_proposal(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
     Ok(handle_delete_proposal(revision_id)?)
@@ -33,14 +33,29 @@ pub fn handle_create_proposed_intent<S>(entry_def_id: S, proposed_intent: Create
     let (meta, base_address, entry_resp): (_, ProposedIntentAddress, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, proposed_intent.to_owned())?;
 
     // handle link fields
-    let r1 = create_index!(proposed_intent.published_in(&proposed_intent.published_in), proposal.publishes(&base_address));
-    hdk::prelude::debug!("handle_create_proposed_intent::published_in index {:?}", r1);
-    let r2 = create_index!(proposed_intent.publishes(proposed_intent.publishes.to_owned()), intent.proposed_in(&base_address));
-    hdk::prelude::debug!("handle_create_proposed_intent::publishes index {:?}", r2);
+    log_index_results("handle_create_proposed_intent", create_proposed_intent_indexes(&base_address, &entry_resp));
 
     construct_response(&base_address, &meta, &entry_resp)
 }
 
+/// Index writes made for a `ProposedIntent` when it is created, and re-run to resync its indexes.
+///
+fn create_proposed_intent_indexes(base_address: &ProposedIntentAddress, entry: &EntryData) -> RecordIndexResults
+{
+    vec![
+        ("published_in", create_index!(proposed_intent.published_in(&entry.published_in), proposal.publishes(base_address))),
+        ("publishes", create_index!(proposed_intent.publishes(&entry.publishes), intent.proposed_in(base_address))),
+    ]
+}
+
+/// Rebuild the indexes of a `ProposedIntent` from its latest revision.
+///
+pub fn handle_resync_proposed_intent_indexes(address: ProposedIntentAddress) -> RecordAPIResult<bool>
+{
+    let (_meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    throw_if_resync_failed(create_proposed_intent_indexes(&base_address, &entry))
+}
+
 pub fn handle_get_proposed_intent(address: ProposedIntentAddress) -> RecordAPIResult<ResponseData>
 {
     let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
@@ -8,6 +8,7 @@
  */
 use holochain_serialized_bytes::prelude::*;
 pub use vf_attributes_hdk::{
+    ErrorCode, ErrorEnvelope,
     ActionHash, ByAddress, ByAction, ByRevision, RecordMeta, RevisionMeta,
     ProposedIntentAddress, IntentAddress, ProposalAddress,
 };
@@ -19,9 +20,11 @@ pub use vf_attributes_hdk::{
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     pub id: ProposedIntentAddress,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     pub meta: RecordMeta,
     pub reciprocal: bool,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "published_in"))]
     pub published_in: ProposalAddress,
     pub publishes: IntentAddress,
 }
@@ -52,6 +55,7 @@ pub struct CreateParams {
 #[serde(rename_all = "camelCase")]
 pub struct CreateRequest {
     pub reciprocal: bool,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "published_in"))]
     pub published_in: ProposalAddress,
     pub publishes: IntentAddress,
 }
@@ -79,3 +83,10 @@ pub struct QueryParams {
     pub published_in: Option<ProposalAddress>,
     pub publishes: Option<IntentAddress>,
 }
+
+#[cfg(all(test, feature = "snake_case_aliases"))]
+mod tests {
+    use super::*;
+
+    vf_attributes_hdk::snake_case_alias_tests!(Response, CreateRequest);
+}
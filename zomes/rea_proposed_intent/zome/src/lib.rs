@@ -23,6 +23,11 @@ fn get_proposed_intent(ByAddress { address }: ByAddress<ProposedIntentAddress>)
     Ok(handle_get_proposed_intent(address)?)
 }
 
+#[hdk_extern]
+fn resync_record_indexes(ByAddress { address }: ByAddress<ProposedIntentAddress>) -> ExternResult<bool> {
+    Ok(handle_resync_proposed_intent_indexes(address)?)
+}
+
 #[hdk_extern]
 fn delete_proposed_intent(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
     Ok(handle_delete_proposed_intent(&revision_id)?)
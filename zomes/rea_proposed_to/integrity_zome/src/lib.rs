@@ -27,4 +27,30 @@ pub fn __num_entry_types() -> u8 {
 #[no_mangle]
 pub fn __num_link_types() -> u8 {
     LinkTypes::len()
-}
\ No newline at end of file
+}
+#[hdk_extern]
+fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+    match op {
+        Op::StoreRecord { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::StoreEntry { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterCreateLink { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterDeleteLink { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterUpdate { .. } => Ok(ValidateCallbackResult::Valid),
+        Op::RegisterDelete(RegisterDelete { delete }) => validate_delete_author(
+            &delete.hashed.content.author,
+            &delete.hashed.content.deletes_address,
+        ),
+        Op::RegisterAgentActivity { .. } => Ok(ValidateCallbackResult::Valid),
+    }
+}
+
+/// Only the agent who published a proposal to its recipient may retract it
+///
+fn validate_delete_author(author: &AgentPubKey, deletes_address: &ActionHash) -> ExternResult<ValidateCallbackResult> {
+    let original = must_get_action(deletes_address.to_owned())?;
+    if original.action().author() == author {
+        Ok(ValidateCallbackResult::Valid)
+    } else {
+        Ok(ValidateCallbackResult::Invalid("Only the author of a ProposedTo may delete it".to_string()))
+    }
+}
@@ -7,8 +7,9 @@
 * @package hREA
 */
 use paste::paste;
+use hdk::prelude::agent_info;
 use hdk_records::{
-    RecordAPIResult, SignedActionHashed,
+    RecordAPIResult, DataIntegrityError, SignedActionHashed,
     records::{
         create_record,
         delete_record,
@@ -34,14 +35,30 @@ pub fn handle_create_proposed_to<S>(entry_def_id: S, proposed_to: CreateRequest)
     let (meta, base_address, entry_resp): (_, ProposedToAddress, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, proposed_to.to_owned())?;
 
     // handle link fields
-    let r1 = create_index!(proposed_to.proposed(&proposed_to.proposed), proposal.published_to(&base_address));
-    hdk::prelude::debug!("handle_create_proposed_to::proposed index {:?}", r1);
+    log_index_results("handle_create_proposed_to", create_proposed_to_indexes(&base_address, &entry_resp));
 
     // :TODO: create index for retrieving all proposals for an agent
 
     construct_response(&base_address, &meta, &entry_resp)
 }
 
+/// Index writes made for a `ProposedTo` when it is created, and re-run to resync its indexes.
+///
+fn create_proposed_to_indexes(base_address: &ProposedToAddress, entry: &EntryData) -> RecordIndexResults
+{
+    vec![
+        ("proposed", create_index!(proposed_to.proposed(&entry.proposed), proposal.published_to(base_address))),
+    ]
+}
+
+/// Rebuild the indexes of a `ProposedTo` from its latest revision.
+///
+pub fn handle_resync_proposed_to_indexes(address: ProposedToAddress) -> RecordAPIResult<bool>
+{
+    let (_meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    throw_if_resync_failed(create_proposed_to_indexes(&base_address, &entry))
+}
+
 pub fn handle_get_proposed_to(address: ProposedToAddress) -> RecordAPIResult<ResponseData>
 {
     let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
@@ -58,6 +75,41 @@ pub fn handle_delete_proposed_to(revision_id: &ActionHash) -> RecordAPIResult<bo
     delete_record::<EntryStorage>(&revision_id)
 }
 
+/// Withdraw a proposal from its recipient by deleting the `ProposedTo` record and its
+/// index links. The proposal itself is left untouched.
+///
+pub fn handle_retract_proposed_to(address: ProposedToAddress) -> RecordAPIResult<bool>
+{
+    let (meta, _base_address, _entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    throw_if_not_author(&meta)?;
+
+    handle_delete_proposed_to(meta.as_hash())
+}
+
+/// Retract a `ProposedTo` and publish the same proposal to `new_agent` in its place.
+///
+pub fn handle_redirect_proposed_to<S>(entry_def_id: S, address: ProposedToAddress, new_agent: AgentAddress) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    let (meta, _base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    throw_if_not_author(&meta)?;
+
+    handle_delete_proposed_to(meta.as_hash())?;
+    handle_create_proposed_to(entry_def_id, CreateRequest {
+        proposed_to: new_agent,
+        proposed: entry.proposed,
+    })
+}
+
+/// Retraction is checked here to fail early; it is enforced by validation.
+///
+fn throw_if_not_author(meta: &SignedActionHashed) -> RecordAPIResult<()> {
+    if meta.action().author() != &agent_info()?.agent_initial_pubkey {
+        return Err(DataIntegrityError::NotRecordAuthor);
+    }
+    Ok(())
+}
+
 /// Create response from input DHT primitives
 fn construct_response<'a>(address: &ProposedToAddress, meta: &SignedActionHashed, e: &EntryData) -> RecordAPIResult<ResponseData> {
     Ok(ResponseData {
@@ -8,6 +8,7 @@
  */
 use holochain_serialized_bytes::prelude::*;
 pub use vf_attributes_hdk::{
+    ErrorCode, ErrorEnvelope,
     ActionHash, ByAddress, ByAction, ByRevision, RecordMeta, RevisionMeta,
     ProposedToAddress, AgentAddress, ProposalAddress,
 };
@@ -28,8 +29,10 @@ pub struct CreateParams {
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     pub id: ProposedToAddress,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     pub meta: RecordMeta,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "proposed_to"))]
     pub proposed_to: AgentAddress,
     pub proposed: ProposalAddress,
 }
@@ -51,6 +54,7 @@ pub struct ResponseData {
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateRequest {
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "proposed_to"))]
     pub proposed_to: AgentAddress,
     pub proposed: ProposalAddress,
 }
@@ -59,6 +63,17 @@ impl<'a> CreateRequest {
     // :TODO: accessors for field data
 }
 
+//---------------- REDIRECT REQUEST ----------------
+
+/// Parameters for replacing the recipient of a `ProposedTo`
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RedirectParams {
+    pub address: ProposedToAddress,
+    pub new_agent: AgentAddress,
+}
+
 //---------------- QUERY FILTER REQUEST ----------------
 
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
@@ -66,3 +81,10 @@ impl<'a> CreateRequest {
 pub struct QueryParams {
     pub proposed: Option<ProposalAddress>,
 }
+
+#[cfg(all(test, feature = "snake_case_aliases"))]
+mod tests {
+    use super::*;
+
+    vf_attributes_hdk::snake_case_alias_tests!(Response, CreateRequest);
+}
@@ -23,7 +23,22 @@ fn get_proposed_to(ByAddress { address }: ByAddress<ProposedToAddress>) -> Exter
     Ok(handle_get_proposed_to(address)?)
 }
 
+#[hdk_extern]
+fn resync_record_indexes(ByAddress { address }: ByAddress<ProposedToAddress>) -> ExternResult<bool> {
+    Ok(handle_resync_proposed_to_indexes(address)?)
+}
+
 #[hdk_extern]
 fn delete_proposed_to(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
     Ok(handle_delete_proposed_to(&revision_id)?)
 }
+
+#[hdk_extern]
+fn retract_proposed_to(ByAddress { address }: ByAddress<ProposedToAddress>) -> ExternResult<bool> {
+    Ok(handle_retract_proposed_to(address)?)
+}
+
+#[hdk_extern]
+fn redirect_proposed_to(RedirectParams { address, new_agent }: RedirectParams) -> ExternResult<ResponseData> {
+    Ok(handle_redirect_proposed_to(PROPOSED_TO_ENTRY_TYPE, address, new_agent)?)
+}
@@ -6,8 +6,14 @@
  *
  * @package hREA
  */
+use std::convert::{TryFrom, TryInto};
+use paste::paste;
 use hdk_records::{
-    RecordAPIResult, SignedActionHashed,
+    generate_patch_builder,
+    RecordAPIResult, DataIntegrityError, SignedActionHashed, OtherCellResult,
+    NonceMode, DnaAddressable, hash_entry,
+    record_interface::Identifiable,
+    rpc::call_zome_method,
     records::{
         create_record,
         read_record_entry,
@@ -16,11 +22,27 @@ use hdk_records::{
         delete_record,
     },
     metadata::read_revision_metadata_abbreviated,
+    bundles::{
+        export_record_bundle,
+        import_record_bundle,
+        bundle_index_targets,
+    },
+    field_indexes::{
+        sync_field_value_index,
+        find_by_field_value,
+    },
+    name_indexes::{
+        sync_name_index,
+        list_by_name,
+        NameIndexPage,
+    },
 };
+use hdk_semantic_indexes_client_lib::*;
 
 use vf_attributes_hdk::{
     EconomicResourceAddress,
 };
+use hc_zome_rea_network_settings_lib::NetworkSettingsReader;
 
 use hc_zome_rea_resource_specification_storage::*;
 use hc_zome_rea_resource_specification_rpc::*;
@@ -30,10 +52,76 @@ fn read_index_zome(conf: DnaConfigSlice) -> Option<String> {
     Some(conf.resource_specification.index_zome)
 }
 
+/// properties accessor for zome config
+fn read_resource_specification_index_zome(conf: DnaConfigSlice) -> Option<String> {
+    Some(conf.resource_specification.index_zome)
+}
+
+/// Properties accessor for zome config.
+fn read_network_settings_zome(conf: DnaConfigSlice) -> Option<String> {
+    conf.resource_specification.network_settings_zome
+}
+
+/// How new entries generate their `_nonce`, @see `NonceMode`
+fn read_nonce_mode() -> RecordAPIResult<NonceMode> {
+    let conf: DnaConfigSlice = hdk::prelude::dna_info()?.modifiers.properties.try_into()?;
+    Ok(conf.resource_specification.nonce_mode)
+}
+
+/// number of referencing records re-pointed per `merge_resource_specification` call, unless otherwise specified
+const MERGE_CHUNK_SIZE: usize = 50;
+
+// remote permission IDs used to re-point records in other DNAs
+const REASSIGN_RESOURCE_METHOD: &str = "reassign_resource_specification";
+const REASSIGN_INTENT_METHOD: &str = "reassign_intent_specification";
+const REASSIGN_COMMITMENT_METHOD: &str = "reassign_commitment_specification";
+const REASSIGN_EVENT_METHOD: &str = "reassign_event_specification";
+
+/// field value index for exact-match lookups of records by `name`
+const NAME_INDEX: &str = "resource_specification.name";
+
+/// field extractor for `NAME_INDEX`
+fn extract_name(e: &EntryData) -> Option<String> {
+    Some(e.name.to_owned())
+}
+
+/// name index for alphabetical listings
+const NAME_ORDER_INDEX: &str = "resource_specification.name_order";
+
+/// number of records returned per alphabetical listing page, unless otherwise specified
+const ALPHABETICAL_PAGE_SIZE: usize = 25;
+
+/// name extractor for `NAME_ORDER_INDEX`
+fn extract_sort_name(e: &EntryData) -> String {
+    e.name.to_owned()
+}
+
 pub fn handle_create_resource_specification<S>(entry_def_id: S, resource_specification: CreateRequest) -> RecordAPIResult<ResponseData>
     where S: AsRef<str> + std::fmt::Display,
 {
-    let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, resource_specification)?;
+    let nonce_mode = read_nonce_mode()?;
+
+    // content-addressed specifications resolve to any existing identical specification
+    if nonce_mode == NonceMode::ContentAddressed {
+        let entry = EntryData::try_from((resource_specification.to_owned(), nonce_mode))?;
+        let address = ResourceSpecificationAddress::new(hdk::prelude::dna_info()?.hash, hash_entry(entry.with_identity(None))?);
+        match handle_get_resource_specification(address) {
+            Ok(existing) => return Ok(existing),
+            Err(DataIntegrityError::EntryNotFound) => (),
+            Err(e) => return Err(e),
+        }
+    }
+
+    let (meta, base_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, (resource_specification.to_owned(), nonce_mode))?;
+
+    create_resource_specification_indexes(&base_address, &entry_resp)?;
+
+    // substitutability is symmetric- each index write links both specifications to one another
+    if let Some(substitutes) = resource_specification.get_substitutable_with() {
+        let substitutes = without_self(&base_address, substitutes);
+        let e = update_index!(resource_specification(&base_address).substitutable_with(substitutes.as_slice()));
+        hdk::prelude::debug!("handle_create_resource_specification::substitutable_with index {:?}", e);
+    }
 
     construct_response(&base_address, &meta, &entry_resp, get_link_fields(&base_address)?)
 }
@@ -44,6 +132,25 @@ pub fn handle_get_resource_specification(address: ResourceSpecificationAddress)
     construct_response(&address, &meta, &entry, get_link_fields(&base_address)?)
 }
 
+/// Index writes made for a `ResourceSpecification` when it is created, and re-run to resync its indexes.
+///
+fn create_resource_specification_indexes(base_address: &ResourceSpecificationAddress, entry: &EntryData) -> RecordAPIResult<()>
+{
+    sync_field_value_index(LinkTypes::FieldValueIndex, NAME_INDEX, base_address, Some(entry), None, extract_name)?;
+    sync_name_index(LinkTypes::NameIndex, NAME_ORDER_INDEX, base_address, Some(entry), None, extract_sort_name)?;
+    Ok(())
+}
+
+/// Rebuild the indexes of a `ResourceSpecification` from its latest revision.
+/// Substitutability is not recorded in the entry, so its links are not rebuilt.
+///
+pub fn handle_resync_resource_specification_indexes(address: ResourceSpecificationAddress) -> RecordAPIResult<bool>
+{
+    let (_meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
+    create_resource_specification_indexes(&base_address, &entry)?;
+    Ok(true)
+}
+
 pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
 {
     let (meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
@@ -53,15 +160,267 @@ pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseD
 pub fn handle_update_resource_specification(resource_specification: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
     let old_revision = resource_specification.get_revision_id();
-    let (meta, base_address, new_entry, _prev_entry): (_, ResourceSpecificationAddress, EntryData, EntryData) = update_record(old_revision, resource_specification.to_owned())?;
+    let (meta, base_address, new_entry, prev_entry): (_, ResourceSpecificationAddress, EntryData, EntryData) = update_record(old_revision, resource_specification.to_owned())?;
+
+    hdk_records::return_if_unchanged!(meta, *old_revision, construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?), resource_specification);
+
+    sync_field_value_index(LinkTypes::FieldValueIndex, NAME_INDEX, &base_address, Some(&new_entry), Some(&prev_entry), extract_name)?;
+    sync_name_index(LinkTypes::NameIndex, NAME_ORDER_INDEX, &base_address, Some(&new_entry), Some(&prev_entry), extract_sort_name)?;
+
+    if let Some(substitutes) = resource_specification.get_substitutable_with() {
+        let substitutes = without_self(&base_address, substitutes);
+        let prev_substitutes: Vec<ResourceSpecificationAddress> = read_index!(resource_specification(&base_address).substitutable_with)?;
+        let added: Vec<ResourceSpecificationAddress> = substitutes.iter().filter(|s| !prev_substitutes.contains(s)).cloned().collect();
+        let removed: Vec<ResourceSpecificationAddress> = prev_substitutes.iter().filter(|s| !substitutes.contains(s)).cloned().collect();
+        let e = update_index!(resource_specification(&base_address).substitutable_with(added.as_slice()).not(removed.as_slice()));
+        hdk::prelude::debug!("handle_update_resource_specification::substitutable_with index {:?}", e);
+    }
+
     construct_response(&base_address, &meta, &new_entry, get_link_fields(&base_address)?)
 }
 
+generate_patch_builder!(UpdateRequest {
+    name,
+    image,
+    note,
+    default_unit_of_effort,
+    default_unit_of_resource,
+    substitutable_with
+});
+
+/// Apply a set of `PatchOp`s to the latest revision of a `ResourceSpecification`.
+/// The patch is converted to an `UpdateRequest` and applied via `handle_update_resource_specification`.
+///
+pub fn handle_patch_resource_specification(address: ResourceSpecificationAddress, ops: Vec<PatchOp>) -> RecordAPIResult<ResponseData>
+{
+    let revision_id = handle_get_resource_specification(address)?.resource_specification.revision_id;
+    handle_update_resource_specification(build_patch_update(revision_id, ops)?)
+}
+
 pub fn handle_delete_resource_specification(revision_id: ActionHash) -> RecordAPIResult<bool>
 {
+    // clear field value indexes before the entry is removed
+    let (_meta, base_address, entry): (_, ResourceSpecificationAddress, EntryData) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+    sync_field_value_index(LinkTypes::FieldValueIndex, NAME_INDEX, &base_address, None, Some(&entry), extract_name)?;
+    sync_name_index(LinkTypes::NameIndex, NAME_ORDER_INDEX, &base_address, None, Some(&entry), extract_sort_name)?;
+
+    // tear down substitutability links in both directions
+    let substitutes: Vec<ResourceSpecificationAddress> = read_index!(resource_specification(&base_address).substitutable_with)?;
+    let e = update_index!(resource_specification(&base_address).substitutable_with.not(substitutes.as_slice()));
+    hdk::prelude::debug!("handle_delete_resource_specification::substitutable_with index {:?}", e);
+
     delete_record::<EntryStorage>(&revision_id)
 }
 
+/// Fold the `source` specification into `target`, re-pointing every record conforming to `source`
+/// and deleting `source` once no references remain. Only network admins may merge specifications.
+///
+/// Referencing records are processed `limit` at a time from `cursor`. Successfully re-pointed records
+/// drop out of the `source` indexes, so the returned `next_cursor` only skips past failures. In `dry_run`
+/// mode nothing is written and the cursor advances over every record reported.
+///
+pub fn handle_merge_resource_specification(params: MergeParams) -> RecordAPIResult<MergeReport>
+{
+    NetworkSettingsReader::new(read_network_settings_zome).throw_if_not_admin()?;
+
+    let MergeParams { source, target, cursor, limit, dry_run } = params;
+    if source == target {
+        return Err(DataIntegrityError::MergeIntoSelf);
+    }
+    // ensure the target exists before moving anything onto it
+    let _ = read_record_entry::<EntryData, EntryStorage, _>(target.as_ref())?;
+
+    let references = read_conforming_records(&source)?;
+    let total_references = references.len();
+    let offset = cursor.unwrap_or(0).min(total_references);
+    let chunk: Vec<ConformingRecord> = references.into_iter()
+        .skip(offset)
+        .take(limit.unwrap_or(MERGE_CHUNK_SIZE))
+        .collect();
+
+    if dry_run {
+        let next = offset + chunk.len();
+        return Ok(MergeReport {
+            dry_run,
+            reassigned: chunk,
+            failed: vec![],
+            total_references,
+            next_cursor: if next < total_references { Some(next) } else { None },
+            source_deleted: false,
+        });
+    }
+
+    let mut reassigned = vec![];
+    let mut failed = vec![];
+    for record in chunk {
+        match reassign_conforming_record(&record, &source, &target) {
+            Ok(true) => reassigned.push(record),
+            // stale index entry- the record already points elsewhere
+            Ok(false) => failed.push(MergeFailure { record, error: String::from("Record does not conform to the source specification") }),
+            Err(e) => failed.push(MergeFailure { record, error: e.to_string() }),
+        }
+    }
+
+    let next = offset + failed.len();
+    let remaining = total_references - reassigned.len();
+    let source_deleted = if remaining == 0 {
+        let (meta, _, _) = read_record_entry::<EntryData, EntryStorage, _>(source.as_ref())?;
+        handle_delete_resource_specification(meta.as_hash().to_owned())?
+    } else {
+        false
+    };
+
+    Ok(MergeReport {
+        dry_run,
+        reassigned,
+        failed,
+        total_references,
+        next_cursor: if next < remaining { Some(next) } else { None },
+        source_deleted,
+    })
+}
+
+/// Read all records conforming to a specification, in a stable order for cursor-based chunking
+fn read_conforming_records(address: &ResourceSpecificationAddress) -> RecordAPIResult<Vec<ConformingRecord>> {
+    let resources: Vec<EconomicResourceAddress> = read_index!(resource_specification(address).conforming_resources)?;
+    let intents: Vec<IntentAddress> = read_index!(resource_specification(address).conforming_intents)?;
+    let commitments: Vec<CommitmentAddress> = read_index!(resource_specification(address).conforming_commitments)?;
+    let events: Vec<EconomicEventAddress> = read_index!(resource_specification(address).conforming_economic_events)?;
+
+    Ok(resources.into_iter().map(ConformingRecord::EconomicResource)
+        .chain(intents.into_iter().map(ConformingRecord::Intent))
+        .chain(commitments.into_iter().map(ConformingRecord::Commitment))
+        .chain(events.into_iter().map(ConformingRecord::EconomicEvent))
+        .collect())
+}
+
+/// Request the DNA hosting a conforming record to re-point it from `from` to `to`
+fn reassign_conforming_record(record: &ConformingRecord, from: &ResourceSpecificationAddress, to: &ResourceSpecificationAddress) -> RecordAPIResult<bool> {
+    let resp: OtherCellResult<bool> = match record {
+        ConformingRecord::EconomicResource(address) => reassign_remote(address, REASSIGN_RESOURCE_METHOD, from, to),
+        ConformingRecord::Intent(address) => reassign_remote(address, REASSIGN_INTENT_METHOD, from, to),
+        ConformingRecord::Commitment(address) => reassign_remote(address, REASSIGN_COMMITMENT_METHOD, from, to),
+        ConformingRecord::EconomicEvent(address) => reassign_remote(address, REASSIGN_EVENT_METHOD, from, to),
+    };
+    Ok(resp?)
+}
+
+fn reassign_remote<A>(address: &A, method: &str, from: &ResourceSpecificationAddress, to: &ResourceSpecificationAddress) -> OtherCellResult<bool>
+    where A: AsRef<hdk::prelude::DnaHash> + Clone + hdk::prelude::Serialize + std::fmt::Debug,
+{
+    call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        address,
+        &String::from(method),
+        ReassignSpecificationParams { address: address.to_owned(), from: from.to_owned(), to: to.to_owned() },
+        LinkTypes::AvailableCapability,
+    )
+}
+
+/// Export a `ResourceSpecification` with its full revision history and index memberships,
+/// for backup or migration to another DNA.
+///
+/// Conforming record indexes are included for reference, but are owned by the conforming
+/// records themselves and only restored when those records are re-created.
+///
+pub fn handle_export_resource_specification<S>(entry_def_id: S, address: ResourceSpecificationAddress) -> RecordAPIResult<RecordBundle>
+    where S: AsRef<str>,
+{
+    let resources: Vec<EconomicResourceAddress> = read_index!(resource_specification(&address).conforming_resources)?;
+    let intents: Vec<IntentAddress> = read_index!(resource_specification(&address).conforming_intents)?;
+    let commitments: Vec<CommitmentAddress> = read_index!(resource_specification(&address).conforming_commitments)?;
+    let events: Vec<EconomicEventAddress> = read_index!(resource_specification(&address).conforming_economic_events)?;
+
+    export_record_bundle::<EntryData, EntryStorage, _, _>(entry_def_id, &address, vec![
+        ("substitutable_with", bundle_index_targets(&handle_get_substitutable_specifications(address.to_owned())?)?),
+        ("conforming_resources", bundle_index_targets(&resources)?),
+        ("conforming_intents", bundle_index_targets(&intents)?),
+        ("conforming_commitments", bundle_index_targets(&commitments)?),
+        ("conforming_economic_events", bundle_index_targets(&events)?),
+    ])
+}
+
+/// Recreate a `ResourceSpecification` exported via `handle_export_resource_specification`.
+///
+/// As the initial revision is written verbatim, the record retains its original identity hash
+/// in the destination DNA. Substitutability links are restored to the same specifications
+/// in the destination DNA.
+///
+pub fn handle_import_resource_specification<S>(entry_def_id: S, bundle: RecordBundle) -> RecordAPIResult<ResponseData>
+    where S: AsRef<str> + std::fmt::Display,
+{
+    if bundle.record_type != entry_def_id.as_ref() {
+        return Err(DataIntegrityError::EntryWrongType);
+    }
+
+    let (meta, base_address, entry): (_, ResourceSpecificationAddress, EntryData) = import_record_bundle::<EntryTypes,_,_,_,_,_,_,_>(
+        read_index_zome, &entry_def_id, &bundle,
+    )?;
+
+    sync_field_value_index(LinkTypes::FieldValueIndex, NAME_INDEX, &base_address, Some(&entry), None, extract_name)?;
+    sync_name_index(LinkTypes::NameIndex, NAME_ORDER_INDEX, &base_address, Some(&entry), None, extract_sort_name)?;
+
+    let substitutes = without_self(&base_address, bundle.index_targets("substitutable_with", base_address.as_ref())?);
+    if substitutes.len() > 0 {
+        update_index!(resource_specification(&base_address).substitutable_with(substitutes.as_slice()))?;
+    }
+
+    construct_response(&base_address, &meta, &entry, get_link_fields(&base_address)?)
+}
+
+/// Retrieve all `ResourceSpecification`s whose `name` exactly matches the given value
+///
+pub fn handle_find_resource_specifications_by_name<S>(name: S) -> RecordAPIResult<Vec<ResponseData>>
+    where S: AsRef<str>,
+{
+    let addresses: Vec<ResourceSpecificationAddress> = find_by_field_value(LinkTypes::FieldValueIndex, NAME_INDEX, name)?;
+    addresses.into_iter()
+        .map(handle_get_resource_specification)
+        .collect()
+}
+
+/// Read a page of `ResourceSpecification`s in order of their names, optionally beginning
+/// at those whose names start with `starting_with`.
+///
+pub fn handle_list_resource_specifications_alphabetical(params: NameIndexParams) -> RecordAPIResult<AlphabeticalConnection>
+{
+    let limit = params.limit.unwrap_or(ALPHABETICAL_PAGE_SIZE);
+    let page: NameIndexPage<ResourceSpecificationAddress> = list_by_name(LinkTypes::NameIndex, NAME_ORDER_INDEX, params.starting_with, params.cursor, limit)?;
+
+    let edges = page.entries.into_iter()
+        .map(|(cursor, address)| Ok(AlphabeticalEdge {
+            node: handle_get_resource_specification(address)?.resource_specification,
+            cursor,
+        }))
+        .collect::<RecordAPIResult<Vec<AlphabeticalEdge>>>()?;
+
+    Ok(AlphabeticalConnection {
+        page_info: PageInfo {
+            start_cursor: edges.first().map(|e| e.cursor.to_owned()).unwrap_or_else(|| "0".to_string()),
+            end_cursor: edges.last().map(|e| e.cursor.to_owned()).unwrap_or_else(|| "0".to_string()),
+            has_previous_page: page.has_previous_page,
+            has_next_page: page.has_next_page,
+            total_count: None,
+            page_limit: Some(limit),
+        },
+        edges,
+    })
+}
+
+/// Read the `ResourceSpecification`s directly substitutable with the given one.
+///
+/// Substitutability is not transitive: only specifications explicitly declared as
+/// substitutes (in either direction) are returned.
+///
+pub fn handle_get_substitutable_specifications(address: ResourceSpecificationAddress) -> RecordAPIResult<Vec<ResourceSpecificationAddress>>
+{
+    Ok(without_self(&address, read_index!(resource_specification(&address).substitutable_with)?))
+}
+
+/// A specification is implicitly substitutable for itself, so never needs to be indexed as such
+fn without_self(address: &ResourceSpecificationAddress, substitutes: Vec<ResourceSpecificationAddress>) -> Vec<ResourceSpecificationAddress> {
+    substitutes.into_iter().filter(|s| s != address).collect()
+}
+
 /// Create response from input DHT primitives
 fn construct_response<'a>(
     address: &ResourceSpecificationAddress,
@@ -70,8 +429,10 @@ fn construct_response<'a>(
     // :TODO: link conforming resources in associated link registry DNA module
     (
         _conforming_resources,
+        substitutable_with,
     ): (
         Vec<EconomicResourceAddress>,
+        Vec<ResourceSpecificationAddress>,
     )
 ) -> RecordAPIResult<ResponseData> {
     Ok(ResponseData {
@@ -85,6 +446,7 @@ fn construct_response<'a>(
             note: e.note.to_owned(),
             default_unit_of_effort: e.default_unit_of_effort.to_owned(),
             default_unit_of_resource: e.default_unit_of_resource.to_owned(),
+            substitutable_with: if substitutable_with.len() > 0 { Some(substitutable_with) } else { None },
 
             // conforming_resources: conforming_resources.map(Cow::into_owned),
         }
@@ -92,10 +454,12 @@ fn construct_response<'a>(
 }
 
 // @see construct_response
-fn get_link_fields(_address: &ResourceSpecificationAddress) -> RecordAPIResult<(
+fn get_link_fields(address: &ResourceSpecificationAddress) -> RecordAPIResult<(
     Vec<EconomicResourceAddress>,
+    Vec<ResourceSpecificationAddress>,
 )> {
     Ok((
         vec![],   // :TODO:
+        without_self(address, read_index!(resource_specification(address).substitutable_with)?),
     ))
 }
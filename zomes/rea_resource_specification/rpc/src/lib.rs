@@ -9,13 +9,22 @@
 use holochain_serialized_bytes::prelude::*;
 use serde_maybe_undefined::MaybeUndefined;
 pub use vf_attributes_hdk::{
+    ErrorCode, ErrorEnvelope,
+    PatchOp, PatchParams,
     ActionHash, ByAddress, ByAction, RecordMeta, RevisionMeta,
+    RecordBundle,
     ResourceSpecificationAddress,
     EconomicResourceAddress,
+    IntentAddress,
+    CommitmentAddress,
+    EconomicEventAddress,
+    ReassignSpecificationParams,
+    NameIndexParams,
     ExternalURL,
     UnitId,
     ByRevision,
 };
+pub use hdk_relay_pagination::PageInfo;
 
 // toplevel I/O structs for WASM API
 
@@ -39,6 +48,7 @@ pub struct UpdateParams {
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     pub id: ResourceSpecificationAddress,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     pub meta: RecordMeta,
     pub name: String,
@@ -47,9 +57,14 @@ pub struct Response {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub note: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "default_unit_of_effort"))]
     pub default_unit_of_effort: Option<UnitId>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "default_unit_of_resource"))]
     pub default_unit_of_resource: Option<UnitId>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "substitutable_with"))]
+    pub substitutable_with: Option<Vec<ResourceSpecificationAddress>>,
 }
 
 /// I/O struct to describe what is returned outside the gateway.
@@ -75,13 +90,22 @@ pub struct CreateRequest {
     #[serde(default)]
     pub note: MaybeUndefined<String>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "default_unit_of_effort"))]
     pub default_unit_of_effort: MaybeUndefined<UnitId>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "default_unit_of_resource"))]
     pub default_unit_of_resource: MaybeUndefined<UnitId>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "substitutable_with"))]
+    pub substitutable_with: MaybeUndefined<Vec<ResourceSpecificationAddress>>,
 }
 
 impl<'a> CreateRequest {
-    // :TODO: accessors for field data
+    // :TODO: accessors for other field data
+
+    pub fn get_substitutable_with(&'a self) -> Option<Vec<ResourceSpecificationAddress>> {
+        self.substitutable_with.to_owned().to_option()
+    }
 }
 
 //---------------- UPDATE REQUEST ----------------
@@ -91,6 +115,7 @@ impl<'a> CreateRequest {
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateRequest {
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     #[serde(default)]
     pub name: MaybeUndefined<String>,
@@ -99,9 +124,14 @@ pub struct UpdateRequest {
     #[serde(default)]
     pub note: MaybeUndefined<String>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "default_unit_of_effort"))]
     pub default_unit_of_effort: MaybeUndefined<UnitId>,
     #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "default_unit_of_resource"))]
     pub default_unit_of_resource: MaybeUndefined<UnitId>,
+    #[serde(default)]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "substitutable_with"))]
+    pub substitutable_with: MaybeUndefined<Vec<ResourceSpecificationAddress>>,
 }
 
 impl<'a> UpdateRequest {
@@ -109,6 +139,10 @@ impl<'a> UpdateRequest {
         &self.revision_id
     }
 
+    pub fn get_substitutable_with(&'a self) -> Option<Vec<ResourceSpecificationAddress>> {
+        self.substitutable_with.to_owned().to_option()
+    }
+
     // :TODO: accessors for other field data
 }
 
@@ -118,4 +152,87 @@ impl<'a> UpdateRequest {
 #[serde(rename_all = "camelCase")]
 pub struct QueryParams {
     pub conforming_resources: Option<EconomicResourceAddress>,
+    pub conforming_intents: Option<IntentAddress>,
+    pub conforming_commitments: Option<CommitmentAddress>,
+    pub conforming_economic_events: Option<EconomicEventAddress>,
+    pub substitutable_with: Option<ResourceSpecificationAddress>,
+}
+
+//---------------- MERGE REQUEST ----------------
+
+/// Parameters for folding duplicate `source` specification into `target`.
+///
+/// Referencing records are processed in chunks of `limit`, starting from `cursor`.
+/// Pass the `next_cursor` returned by the previous call to continue.
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeParams {
+    pub source: ResourceSpecificationAddress,
+    pub target: ResourceSpecificationAddress,
+    #[serde(default)]
+    pub cursor: Option<usize>,
+    #[serde(default)]
+    pub limit: Option<usize>,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+/// A record conforming to a resource specification, by type
+///
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "recordType", content = "id")]
+pub enum ConformingRecord {
+    EconomicResource(EconomicResourceAddress),
+    Intent(IntentAddress),
+    Commitment(CommitmentAddress),
+    EconomicEvent(EconomicEventAddress),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeFailure {
+    pub record: ConformingRecord,
+    pub error: String,
+}
+
+/// Outcome of a single `merge_resource_specification` chunk.
+///
+/// `next_cursor` is `None` once all references have been processed; `source_deleted`
+/// indicates whether the source specification has been removed.
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MergeReport {
+    pub dry_run: bool,
+    pub reassigned: Vec<ConformingRecord>,
+    pub failed: Vec<MergeFailure>,
+    pub total_references: usize,
+    pub next_cursor: Option<usize>,
+    pub source_deleted: bool,
+}
+
+//---------------- ALPHABETICAL LISTING ----------------
+
+/// Page of `ResourceSpecification`s in name order, in the same shape as index zome query results
+///
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AlphabeticalConnection {
+    pub page_info: PageInfo,
+    pub edges: Vec<AlphabeticalEdge>,
+}
+
+#[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AlphabeticalEdge {
+    pub node: Response,
+    pub cursor: String,
+}
+
+#[cfg(all(test, feature = "snake_case_aliases"))]
+mod tests {
+    use super::*;
+
+    vf_attributes_hdk::snake_case_alias_tests!(Response, CreateRequest, UpdateRequest);
 }
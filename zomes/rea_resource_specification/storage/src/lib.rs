@@ -11,6 +11,8 @@ use hdk::prelude::*;
 use hc_zome_dna_auth_resolver_core::AvailableCapability;
 use hdk_records::{
     RecordAPIResult, DataIntegrityError,
+    NonceMode,
+    entries::create_nonce,
     record_interface::Updateable,
     generate_record_entry,
 };
@@ -33,6 +35,10 @@ pub struct DnaConfigSlice {
 #[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Debug)]
 pub struct ResourceSpecificationZomeConfig {
     pub index_zome: String,
+    pub network_settings_zome: Option<String>,
+    // set to `content_addressed` so that identical specifications share a single entry
+    #[serde(default)]
+    pub nonce_mode: NonceMode,
 }
 
 //---------------- RECORD INTERNALS & VALIDATION ----------------
@@ -80,7 +86,13 @@ impl TryFrom<AvailableCapability> for EntryTypes {
 pub enum LinkTypes {
     // relates to dna-auth-resolver mixin
     // and remote authorizations
-    AvailableCapability
+    AvailableCapability,
+    // exact-match lookup of records by field value
+    // @see hdk_records::field_indexes
+    FieldValueIndex,
+    // alphabetical listing of records by name
+    // @see hdk_records::name_indexes
+    NameIndex,
 }
 
 //---------------- CREATE ----------------
@@ -90,13 +102,22 @@ impl TryFrom<CreateRequest> for EntryData {
     type Error = DataIntegrityError;
 
     fn try_from(e: CreateRequest) -> RecordAPIResult<EntryData> {
+        EntryData::try_from((e, NonceMode::Random))
+    }
+}
+
+/// As above, generating the entry's `_nonce` according to the configured `NonceMode`
+impl TryFrom<(CreateRequest, NonceMode)> for EntryData {
+    type Error = DataIntegrityError;
+
+    fn try_from((e, nonce_mode): (CreateRequest, NonceMode)) -> RecordAPIResult<EntryData> {
         Ok(EntryData {
             name: e.name.into(),
             image: e.image.into(),
             note: e.note.into(),
             default_unit_of_effort: e.default_unit_of_effort.into(),
             default_unit_of_resource: e.default_unit_of_resource.into(),
-            _nonce: random_bytes(32)?,
+            _nonce: create_nonce(nonce_mode)?,
         })
     }
 }
@@ -116,3 +137,34 @@ impl Updateable<UpdateRequest> for EntryData {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hdk_records::{MaybeUndefined, record_interface::Identifiable};
+
+    fn create_request(name: &str) -> CreateRequest {
+        CreateRequest {
+            name: name.to_string(),
+            image: MaybeUndefined::Undefined,
+            note: MaybeUndefined::Some("a note".to_string()),
+            default_unit_of_effort: MaybeUndefined::Undefined,
+            default_unit_of_resource: MaybeUndefined::Undefined,
+            substitutable_with: MaybeUndefined::Undefined,
+        }
+    }
+
+    fn stored(entry: EntryData) -> SerializedBytes {
+        SerializedBytes::try_from(entry.with_identity(None)).unwrap()
+    }
+
+    #[test]
+    fn test_identical_content_addressed_specs_share_entry() {
+        let first = EntryData::try_from((create_request("apples"), NonceMode::ContentAddressed)).unwrap();
+        let second = EntryData::try_from((create_request("apples"), NonceMode::ContentAddressed)).unwrap();
+        let different = EntryData::try_from((create_request("pears"), NonceMode::ContentAddressed)).unwrap();
+
+        assert_eq!(stored(first.to_owned()), stored(second), "identical specifications should serialize to the same entry");
+        assert_ne!(stored(first), stored(different));
+    }
+}
@@ -23,6 +23,11 @@ fn get_resource_specification(ByAddress { address }: ByAddress<ResourceSpecifica
     Ok(handle_get_resource_specification(address)?)
 }
 
+#[hdk_extern]
+fn resync_record_indexes(ByAddress { address }: ByAddress<ResourceSpecificationAddress>) -> ExternResult<bool> {
+    Ok(handle_resync_resource_specification_indexes(address)?)
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
@@ -33,6 +38,48 @@ fn update_resource_specification(UpdateParams { resource_specification }: Update
     Ok(handle_update_resource_specification(resource_specification)?)
 }
 
+#[hdk_extern]
+fn patch_record(PatchParams { address, ops }: PatchParams<ResourceSpecificationAddress>) -> ExternResult<ResponseData> {
+    Ok(handle_patch_resource_specification(address, ops)?)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ByName {
+    pub name: String,
+}
+
+#[hdk_extern]
+fn find_resource_specifications_by_name(ByName { name }: ByName) -> ExternResult<Vec<ResponseData>> {
+    Ok(handle_find_resource_specifications_by_name(name)?)
+}
+
+// used by other DNAs to determine substitutes when matching records conforming to specifications
+#[hdk_extern]
+fn get_substitutable_specifications(ByAddress { address }: ByAddress<ResourceSpecificationAddress>) -> ExternResult<Vec<ResourceSpecificationAddress>> {
+    Ok(handle_get_substitutable_specifications(address)?)
+}
+
+#[hdk_extern]
+fn export_resource_specification(ByAddress { address }: ByAddress<ResourceSpecificationAddress>) -> ExternResult<RecordBundle> {
+    Ok(handle_export_resource_specification(ECONOMIC_RESOURCE_SPECIFICATION_ENTRY_TYPE, address)?)
+}
+
+#[hdk_extern]
+fn import_resource_specification(bundle: RecordBundle) -> ExternResult<ResponseData> {
+    Ok(handle_import_resource_specification(ECONOMIC_RESOURCE_SPECIFICATION_ENTRY_TYPE, bundle)?)
+}
+
+// fold a duplicate specification into its replacement, re-pointing conforming records across DNAs
+#[hdk_extern]
+fn merge_resource_specification(params: MergeParams) -> ExternResult<MergeReport> {
+    Ok(handle_merge_resource_specification(params)?)
+}
+
+#[hdk_extern]
+fn list_resource_specifications_alphabetical(params: NameIndexParams) -> ExternResult<AlphabeticalConnection> {
+    Ok(handle_list_resource_specifications_alphabetical(params)?)
+}
+
 #[hdk_extern]
 fn delete_resource_specification(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
     Ok(handle_delete_resource_specification(revision_id)?)
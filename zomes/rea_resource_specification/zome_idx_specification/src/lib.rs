@@ -11,4 +11,8 @@ use hdk_semantic_indexes_zome_lib::ByAddress; // disambiguate from record query
 #[index_zome]
 struct ResourceSpecification {
     conforming_resources: Remote<economic_resource, conforms_to>,
+    conforming_intents: Remote<intent, resource_conforms_to>,
+    conforming_commitments: Remote<commitment, resource_conforms_to>,
+    conforming_economic_events: Remote<economic_event, resource_conforms_to>,
+    substitutable_with: Local<resource_specification, substitutable_with>,
 }
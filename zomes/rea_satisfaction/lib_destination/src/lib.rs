@@ -12,6 +12,7 @@
 use paste::paste;
 use hdk_records::{
     RecordAPIResult,
+    rpc::call_zome_method,
     records::{
         create_record,
         read_record_entry,
@@ -22,6 +23,7 @@ use hdk_records::{
 };
 use hdk_semantic_indexes_client_lib::*;
 
+use hc_zome_rea_satisfaction_storage_consts::*;
 use hc_zome_rea_satisfaction_storage::*;
 use hc_zome_rea_satisfaction_rpc::*;
 
@@ -38,8 +40,7 @@ pub fn handle_create_satisfaction<S>(entry_def_id: S, satisfaction: CreateReques
     let (meta, satisfaction_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, satisfaction.to_owned())?;
 
     // link entries in the local DNA
-    let r1 = create_index!(satisfaction.satisfied_by(satisfaction.get_satisfied_by()), economic_event.satisfies(&satisfaction_address));
-    hdk::prelude::debug!("handle_create_satisfaction::satisfied_by index (destination) {:?}", r1);
+    log_index_results("handle_create_satisfaction (destination)", create_satisfaction_indexes(&satisfaction_address, &entry_resp));
 
     // :TODO: figure out if necessary/desirable to do bidirectional bridging between observation and other planning DNAs
 
@@ -62,11 +63,13 @@ pub fn handle_update_satisfaction(satisfaction: UpdateRequest) -> RecordAPIResul
 {
     let (meta, base_address, new_entry, prev_entry): (_, SatisfactionAddress, EntryData, EntryData) = update_record(&satisfaction.get_revision_id(), satisfaction.to_owned())?;
 
+    hdk_records::return_if_unchanged!(meta, *satisfaction.get_revision_id(), construct_response(&base_address, &meta, &new_entry), satisfaction);
+
     if new_entry.satisfied_by != prev_entry.satisfied_by {
         let e = update_index!(
             satisfaction
-                .satisfied_by(&vec![new_entry.satisfied_by.to_owned()])
-                .not(&vec![prev_entry.satisfied_by]),
+                .satisfied_by(&satisfying_events(&new_entry))
+                .not(&satisfying_events(&prev_entry)),
             economic_event.satisfies(&base_address)
         );
         hdk::prelude::debug!("handle_update_satisfaction::satisfied_by index (destination) {:?}", e);
@@ -81,12 +84,59 @@ pub fn handle_delete_satisfaction(revision_id: ActionHash) -> RecordAPIResult<bo
     let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
 
     // handle link fields
-    let e = update_index!(satisfaction.satisfied_by.not(&vec![entry.satisfied_by]), economic_event.satisfies(&base_address));
+    let e = update_index!(satisfaction.satisfied_by.not(&satisfying_events(&entry)), economic_event.satisfies(&base_address));
     hdk::prelude::debug!("handle_delete_satisfaction::satisfied_by index (destination) {:?}", e);
 
     delete_record::<EntryStorage>(&revision_id)
 }
 
+/// Delete a `Satisfaction` from the observation side, when the event satisfying it is removed.
+///
+/// The originating record is removed from the planning DNA of the intent being satisfied first,
+/// so that it is not left orphaned. If that DNA cannot be reached the local copy is left in place.
+///
+pub fn handle_delete_satisfaction_from_event(address: SatisfactionAddress) -> RecordAPIResult<bool>
+{
+    let ResponseData { satisfaction } = handle_get_satisfaction(address.to_owned())?;
+
+    let origin_deleted: bool = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+        &satisfaction.satisfies,
+        &REPLICATE_DELETE_FROM_EVENT_API_METHOD,
+        ByAddress { address },
+        LinkTypes::AvailableCapability
+    )?;
+    hdk::prelude::debug!("handle_delete_satisfaction_from_event::call_zome_method::{:?} {:?}", REPLICATE_DELETE_FROM_EVENT_API_METHOD, origin_deleted);
+
+    handle_delete_satisfaction(satisfaction.revision_id)
+}
+
+/// Index writes made for a `Satisfaction` when it is created, and re-run to resync its indexes.
+///
+fn create_satisfaction_indexes(base_address: &SatisfactionAddress, entry: &EntryData) -> RecordIndexResults
+{
+    vec![
+        ("satisfied_by", update_index!(satisfaction.satisfied_by(&satisfying_events(entry)), economic_event.satisfies(base_address))),
+    ]
+}
+
+/// Rebuild the indexes of a `Satisfaction` from its latest revision.
+///
+pub fn handle_resync_satisfaction_indexes(address: SatisfactionAddress) -> RecordAPIResult<bool>
+{
+    let (_meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, SatisfactionAddress>(address.as_ref())?;
+    throw_if_resync_failed(create_satisfaction_indexes(&base_address, &entry))
+}
+
+/// The `EconomicEvent` referenced by `entry.satisfied_by`, if it references one.
+/// Only events are indexed in this DNA.
+///
+fn satisfying_events(entry: &EntryData) -> Vec<EconomicEventAddress> {
+    match &entry.satisfied_by {
+        RecordRef::EconomicEvent(event_address) => vec![event_address.to_owned()],
+        _ => vec![],
+    }
+}
+
 /// Properties accessor for zome config.
 fn read_satisfaction_index_zome(conf: DnaConfigSliceObservation) -> Option<String> {
     Some(conf.satisfaction.index_zome)
@@ -13,7 +13,8 @@ use paste::paste;
 use hdk::prelude::*;
 use crate::holo_hash::DnaHash;
 use hdk_records::{
-    RecordAPIResult, OtherCellResult,
+    generate_patch_builder,
+    RecordAPIResult, OtherCellResult, DataIntegrityError, DnaAddressable,
     MaybeUndefined,
     records::{
         create_record,
@@ -43,39 +44,34 @@ pub fn handle_create_satisfaction<S>(entry_def_id: S, satisfaction: CreateReques
     let (meta, satisfaction_address, entry_resp): (_,_, EntryData) = create_record::<EntryTypes,_,_,_,_,_,_,_,_>(read_index_zome, &entry_def_id, satisfaction.to_owned())?;
 
     // link entries in the local DNA
-    let r1 = create_index!(satisfaction.satisfies(satisfaction.get_satisfies()), intent.satisfied_by(&satisfaction_address));
-    hdk::prelude::debug!("handle_create_satisfaction::satisfies index (origin) {:?}", r1);
-
-    // link entries which may be local or remote
-    let event_or_commitment = satisfaction.get_satisfied_by();
-    if is_satisfiedby_local_commitment(event_or_commitment)? {
-      // links to local commitment, create link index pair
-      let r2 = create_index!(satisfaction.satisfied_by(event_or_commitment), commitment.satisfies(&satisfaction_address));
-      hdk::prelude::debug!("handle_create_satisfaction::satisfied_by index (origin) {:?}", r2);
-    } else {
-      // links to remote event, ping associated foreign DNA & fail if there's an error
-      // :TODO: consider the implications of this in loosely coordinated multi-network spaces
-      // we assign a type to the response so that call_zome_method can
-      // effectively deserialize the response without failing
-      let result: OtherCellResult<ResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
-        event_or_commitment,
-        &REPLICATE_CREATE_API_METHOD,
-        CreateParams { satisfaction: CreateRequest {
-            satisfied_by: entry_resp.satisfied_by.to_owned(),
-            satisfies: entry_resp.satisfies.to_owned(),
-            resource_quantity: entry_resp.resource_quantity.to_owned().into(),
-            effort_quantity: entry_resp.effort_quantity.to_owned().into(),
-            note: entry_resp.note.to_owned().into(),
-            nonce: MaybeUndefined::Some(entry_resp._nonce.to_owned()),
-        } },
-        LinkTypes::AvailableCapability
-      );
-      hdk::prelude::debug!("handle_create_satisfaction::call_zome_method::{:?} {:?}", REPLICATE_CREATE_API_METHOD, result);
-    }
+    log_index_results("handle_create_satisfaction (origin)", create_satisfaction_indexes(&satisfaction_address, &entry_resp));
+
+    // link entries in remote DNAs
+    replicate_to_satisfying_event(&entry_resp);
 
     construct_response(&satisfaction_address, &meta, &entry_resp)
 }
 
+/// Index writes made for a `Satisfaction` when it is created, and re-run to resync its local indexes.
+///
+fn create_satisfaction_indexes(base_address: &SatisfactionAddress, entry: &EntryData) -> RecordIndexResults
+{
+    let mut results = vec![
+        ("satisfies", create_index!(satisfaction.satisfies(&entry.satisfies), intent.satisfied_by(base_address))),
+    ];
+    results.append(&mut index_satisfied_by(base_address, entry));
+    results
+}
+
+/// Rebuild the local indexes of a `Satisfaction` from its latest revision. Copies replicated
+/// to observation DNAs resync their own indexes.
+///
+pub fn handle_resync_satisfaction_indexes(address: SatisfactionAddress) -> RecordAPIResult<bool>
+{
+    let (_meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, SatisfactionAddress>(address.as_ref())?;
+    throw_if_resync_failed(create_satisfaction_indexes(&base_address, &entry))
+}
+
 pub fn handle_get_satisfaction(address: SatisfactionAddress) -> RecordAPIResult<ResponseData>
 {
     let (meta, base_address, entry) = read_record_entry::<EntryData, EntryStorage, _>(address.as_ref())?;
@@ -92,6 +88,8 @@ pub fn handle_update_satisfaction(satisfaction: UpdateRequest) -> RecordAPIResul
 {
     let (meta, base_address, new_entry, prev_entry): (_, SatisfactionAddress, EntryData, EntryData) = update_record(&satisfaction.get_revision_id(), satisfaction.to_owned())?;
 
+    hdk_records::return_if_unchanged!(meta, *satisfaction.get_revision_id(), construct_response(&base_address, &meta, &new_entry), satisfaction);
+
     // update intent indexes in local DNA
     if new_entry.satisfies != prev_entry.satisfies {
         let e = update_index!(
@@ -105,67 +103,34 @@ pub fn handle_update_satisfaction(satisfaction: UpdateRequest) -> RecordAPIResul
 
     // update commitment / event indexes in local and/or remote DNA
     if new_entry.satisfied_by != prev_entry.satisfied_by {
-        let new_dna: &DnaHash = new_entry.satisfied_by.as_ref();
-        let prev_dna: &DnaHash = prev_entry.satisfied_by.as_ref();
-        let same_dna = *new_dna == *prev_dna;
-
-        if same_dna {
-            if is_satisfiedby_local_commitment(&prev_entry.satisfied_by)? {
-                // both values were local, update the index directly
+        match (&prev_entry.satisfied_by, &new_entry.satisfied_by) {
+            (RecordRef::Commitment(prev_commitment), RecordRef::Commitment(new_commitment))
+                if is_local_commitment(prev_commitment) && is_local_commitment(new_commitment) => {
+                // both values were local commitments, update the index directly
                 let e = update_index!(
                     satisfaction
-                        .satisfied_by(&vec![new_entry.satisfied_by.to_owned()])
-                        .not(&vec![prev_entry.satisfied_by]),
+                        .satisfied_by(&vec![new_commitment.to_owned()])
+                        .not(&vec![prev_commitment.to_owned()]),
                     commitment.satisfies(&base_address)
                 );
                 hdk::prelude::debug!("handle_update_satisfaction::satisfied_by index (origin) {:?}", e);
-            } else {
-                // both values were remote and in the same DNA, forward the update
+            },
+            (RecordRef::EconomicEvent(prev_event), RecordRef::EconomicEvent(new_event)) if is_same_dna(prev_event, new_event) => {
+                // both values were events in the same DNA, forward the update
                 let result: OtherCellResult<ResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
-                    &prev_entry.satisfied_by,
+                    prev_event,
                     &REPLICATE_UPDATE_API_METHOD,
                     UpdateParams { satisfaction: satisfaction.to_owned() },
                     LinkTypes::AvailableCapability
                 );
                 hdk::prelude::debug!("handle_update_satisfaction::call_zome_method::{:?} {:?}", REPLICATE_UPDATE_API_METHOD, result);
-            }
-        } else {
-            if is_satisfiedby_local_commitment(&prev_entry.satisfied_by)? {
-                // previous value was local, clear the index directly
-                let e = update_index!(satisfaction.satisfied_by.not(&vec![prev_entry.satisfied_by]), commitment.satisfies(&base_address));
-                hdk::prelude::debug!("handle_update_satisfaction::satisfied_by index (origin) {:?}", e);
-            } else {
-                // previous value was remote, handle the remote update as a deletion
-                let result: OtherCellResult<ResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
-                    &prev_entry.satisfied_by,
-                    &REPLICATE_DELETE_API_METHOD,
-                    ByAction { address: satisfaction.get_revision_id().to_owned() },
-                    LinkTypes::AvailableCapability
-                );
-                hdk::prelude::debug!("handle_update_satisfaction::call_zome_method::{:?} {:?}", REPLICATE_DELETE_API_METHOD, result);
-            }
-
-            if is_satisfiedby_local_commitment(&new_entry.satisfied_by)? {
-                // new value was local, add the index directly
-                let e = update_index!(satisfaction.satisfied_by(&vec![new_entry.satisfied_by.to_owned()]), commitment.satisfies(&base_address));
-                hdk::prelude::debug!("handle_update_satisfaction::satisfied_by index (origin) {:?}", e);
-            } else {
-                // new value was remote, handle the remote update as a creation
-                let result: OtherCellResult<ResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
-                    &new_entry.satisfied_by,
-                    &REPLICATE_CREATE_API_METHOD,
-                    CreateParams { satisfaction: CreateRequest {
-                        satisfied_by: new_entry.satisfied_by.to_owned(),
-                        satisfies: new_entry.satisfies.to_owned(),
-                        resource_quantity: new_entry.resource_quantity.to_owned().into(),
-                        effort_quantity: new_entry.effort_quantity.to_owned().into(),
-                        note: new_entry.note.to_owned().into(),
-                        nonce: MaybeUndefined::Some(new_entry._nonce.to_owned()),
-                    } },
-                    LinkTypes::AvailableCapability
-                );
-                hdk::prelude::debug!("handle_update_satisfaction::call_zome_method::{:?} {:?}", REPLICATE_CREATE_API_METHOD, result);
-            }
+            },
+            _ => {
+                // referenced record type or DNA changed, clear the previous reference & add the new one
+                unlink_satisfied_by(&base_address, &prev_entry.satisfied_by, satisfaction.get_revision_id());
+                log_index_results("handle_update_satisfaction (origin)", index_satisfied_by(&base_address, &new_entry));
+                replicate_to_satisfying_event(&new_entry);
+            },
         }
 
         // :TODO: ensure correct number of operations succeeded
@@ -174,6 +139,23 @@ pub fn handle_update_satisfaction(satisfaction: UpdateRequest) -> RecordAPIResul
     construct_response(&base_address, &meta, &new_entry)
 }
 
+generate_patch_builder!(UpdateRequest {
+    satisfied_by,
+    satisfies,
+    resource_quantity,
+    effort_quantity,
+    note
+});
+
+/// Apply a set of `PatchOp`s to the latest revision of a `Satisfaction`.
+/// The patch is converted to an `UpdateRequest` and applied via `handle_update_satisfaction`.
+///
+pub fn handle_patch_satisfaction(address: SatisfactionAddress, ops: Vec<PatchOp>) -> RecordAPIResult<ResponseData>
+{
+    let revision_id = handle_get_satisfaction(address)?.satisfaction.revision_id;
+    handle_update_satisfaction(build_patch_update(revision_id, ops)?)
+}
+
 pub fn handle_delete_satisfaction(revision_id: ActionHash) -> RecordAPIResult<bool>
 {
     let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
@@ -183,30 +165,113 @@ pub fn handle_delete_satisfaction(revision_id: ActionHash) -> RecordAPIResult<bo
     hdk::prelude::debug!("handle_delete_satisfaction::satisfies index (origin) {:?}", e);
 
     // update commitment & event indexes in local or remote DNAs
-    let event_or_commitment = entry.satisfied_by.to_owned();
-    if is_satisfiedby_local_commitment(&event_or_commitment)? {
-        let e = update_index!(satisfaction.satisfied_by.not(&vec![entry.satisfied_by]), commitment.satisfies(&base_address));
-        hdk::prelude::debug!("handle_delete_satisfaction::satisfied_by index (origin) {:?}", e);
-    } else {
-        // links to remote event, ping associated foreign DNA & fail if there's an error
-        // :TODO: consider the implications of this in loosely coordinated multi-network spaces
-        let result: OtherCellResult<ResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
-            &event_or_commitment,
-            &REPLICATE_DELETE_API_METHOD,
-            ByAction { address: revision_id.to_owned() },
-            LinkTypes::AvailableCapability
-        );
-        hdk::prelude::debug!("handle_delete_satisfaction::call_zome_method::{:?} {:?}", REPLICATE_DELETE_API_METHOD, result);
-    }
+    unlink_satisfied_by(&base_address, &entry.satisfied_by, &revision_id);
+
+    delete_record::<EntryStorage>(&revision_id)
+}
+
+/// Delete a `Satisfaction` requested from the observation DNA, when the event satisfying it is removed.
+/// Unlike `handle_delete_satisfaction`, the observation DNA is not pinged back since it removes its own copy.
+///
+/// Returns `false` if this DNA holds no record with the identity of `address`.
+///
+pub fn handle_delete_satisfaction_from_event(address: SatisfactionAddress) -> RecordAPIResult<bool>
+{
+    let identity_hash: &EntryHash = address.as_ref();
+    let local_address = SatisfactionAddress::new(dna_info()?.hash, identity_hash.to_owned());
+    let revision_id = match handle_get_satisfaction(local_address) {
+        Ok(ResponseData { satisfaction }) => satisfaction.revision_id,
+        Err(DataIntegrityError::EntryNotFound) => return Ok(false),
+        Err(e) => return Err(e),
+    };
+    let (_meta, base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
+
+    // update intent indexes in local DNA
+    let e = update_index!(satisfaction.satisfies.not(&vec![entry.satisfies]), intent.satisfied_by(&base_address));
+    hdk::prelude::debug!("handle_delete_satisfaction_from_event::satisfies index (origin) {:?}", e);
 
     delete_record::<EntryStorage>(&revision_id)
 }
 
-fn is_satisfiedby_local_commitment(event_or_commitment: &EventOrCommitmentAddress) -> RecordAPIResult<bool> {
-    let this_dna = dna_info()?.hash;
-    let target_dna: &DnaHash = event_or_commitment.as_ref();
+/// Index a local commitment referenced by `entry.satisfied_by`. Commitments in other planning
+/// DNAs are not indexed, as their indexes can only be written by their own DNA.
+///
+fn index_satisfied_by(base_address: &SatisfactionAddress, entry: &EntryData) -> RecordIndexResults {
+    match &entry.satisfied_by {
+        RecordRef::Commitment(commitment_address) if !is_local_commitment(commitment_address) => {
+            hdk::prelude::debug!("index_satisfied_by::satisfied_by commitment in remote DNA not indexed {:?}", commitment_address);
+            vec![]
+        },
+        RecordRef::Commitment(commitment_address) => vec![
+            ("satisfied_by", create_index!(satisfaction.satisfied_by(commitment_address), commitment.satisfies(base_address))),
+        ],
+        _ => vec![],
+    }
+}
+
+/// Replicate the satisfaction to the DNA of an event referenced by `entry.satisfied_by`, which
+/// indexes the event from its copy.
+///
+fn replicate_to_satisfying_event(entry: &EntryData) {
+    match &entry.satisfied_by {
+        RecordRef::EconomicEvent(event_address) => {
+            // :TODO: consider the implications of this in loosely coordinated multi-network spaces
+            // we assign a type to the response so that call_zome_method can
+            // effectively deserialize the response without failing
+            let result: OtherCellResult<ResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+                event_address,
+                &REPLICATE_CREATE_API_METHOD,
+                CreateParams { satisfaction: CreateRequest {
+                    satisfied_by: entry.satisfied_by.to_owned(),
+                    satisfies: entry.satisfies.to_owned(),
+                    resource_quantity: entry.resource_quantity.to_owned().into(),
+                    effort_quantity: entry.effort_quantity.to_owned().into(),
+                    note: entry.note.to_owned().into(),
+                    nonce: MaybeUndefined::Some(entry._nonce.to_owned()),
+                } },
+                LinkTypes::AvailableCapability
+            );
+            hdk::prelude::debug!("replicate_to_satisfying_event::call_zome_method::{:?} {:?}", REPLICATE_CREATE_API_METHOD, result);
+        },
+        // commitments are indexed locally, other record types are rejected when the entry is written
+        _ => (),
+    }
+}
+
+/// Remove the index of the record referenced by `satisfied_by`, unlinking local commitments directly and
+/// removing the replicated satisfaction of `revision_id` from the DNA of remote events.
+///
+fn unlink_satisfied_by(base_address: &SatisfactionAddress, satisfied_by: &RecordRef, revision_id: &ActionHash) {
+    match satisfied_by {
+        // never indexed, see `index_satisfied_by`
+        RecordRef::Commitment(commitment_address) if !is_local_commitment(commitment_address) => (),
+        RecordRef::Commitment(commitment_address) => {
+            let e = update_index!(satisfaction.satisfied_by.not(&vec![commitment_address.to_owned()]), commitment.satisfies(base_address));
+            hdk::prelude::debug!("unlink_satisfied_by::satisfied_by index (origin) {:?}", e);
+        },
+        RecordRef::EconomicEvent(event_address) => {
+            let result: OtherCellResult<ResponseData> = call_zome_method::<EntryTypes, _, _, _, _, _, _, _>(
+                event_address,
+                &REPLICATE_DELETE_API_METHOD,
+                ByAction { address: revision_id.to_owned() },
+                LinkTypes::AvailableCapability
+            );
+            hdk::prelude::debug!("unlink_satisfied_by::call_zome_method::{:?} {:?}", REPLICATE_DELETE_API_METHOD, result);
+        },
+        // other record types are rejected when the entry is written
+        _ => (),
+    }
+}
+
+fn is_local_commitment(commitment_address: &CommitmentAddress) -> bool {
+    let commitment_dna: &DnaHash = commitment_address.as_ref();
+    dna_info().map(|info| info.hash == *commitment_dna).unwrap_or(false)
+}
 
-    Ok(this_dna == *target_dna)
+fn is_same_dna(a: &EconomicEventAddress, b: &EconomicEventAddress) -> bool {
+    let a_dna: &DnaHash = a.as_ref();
+    let b_dna: &DnaHash = b.as_ref();
+    a_dna == b_dna
 }
 
 /// Properties accessor for zome config.
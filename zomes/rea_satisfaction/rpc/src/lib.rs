@@ -12,9 +12,11 @@ use serde_bytes::ByteBuf;
 use serde_maybe_undefined::{MaybeUndefined};
 use vf_measurement::QuantityValue;
 pub use vf_attributes_hdk::{
+    ErrorCode, ErrorEnvelope,
+    PatchOp, PatchParams,
     ActionHash, ByAction, ByAddress, ByRevision, RecordMeta, RevisionMeta,
     SatisfactionAddress,
-    EventOrCommitmentAddress,
+    RecordRef,
     EconomicEventAddress,
     CommitmentAddress,
     IntentAddress,
@@ -42,13 +44,17 @@ pub struct UpdateParams {
 #[serde(rename_all = "camelCase")]
 pub struct Response {
     pub id: SatisfactionAddress,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     pub meta: RecordMeta,
-    pub satisfied_by: EventOrCommitmentAddress,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "satisfied_by"))]
+    pub satisfied_by: RecordRef,
     pub satisfies: IntentAddress,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_quantity"))]
     pub resource_quantity: Option<QuantityValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "effort_quantity"))]
     pub effort_quantity: Option<QuantityValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub note: Option<String>,
@@ -71,13 +77,16 @@ pub struct ResponseData {
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateRequest {
-    pub satisfied_by: EventOrCommitmentAddress,
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "satisfied_by"))]
+    pub satisfied_by: RecordRef,
     pub satisfies: IntentAddress,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_quantity"))]
     pub resource_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "effort_quantity"))]
     pub effort_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
@@ -94,7 +103,7 @@ pub struct CreateRequest {
 impl<'a> CreateRequest {
     // :TODO: accessors for field data
 
-    pub fn get_satisfied_by(&'a self) -> &EventOrCommitmentAddress {
+    pub fn get_satisfied_by(&'a self) -> &RecordRef {
         &self.satisfied_by
     }
 
@@ -110,18 +119,22 @@ impl<'a> CreateRequest {
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateRequest {
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "revision_id"))]
     pub revision_id: ActionHash,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
-    pub satisfied_by: MaybeUndefined<EventOrCommitmentAddress>, // note this setup allows None to be passed but `update_with` ignores it
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "satisfied_by"))]
+    pub satisfied_by: MaybeUndefined<RecordRef>, // note this setup allows None to be passed but `update_with` ignores it
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
     pub satisfies: MaybeUndefined<IntentAddress>,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "resource_quantity"))]
     pub resource_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
+    #[cfg_attr(feature = "snake_case_aliases", serde(alias = "effort_quantity"))]
     pub effort_quantity: MaybeUndefined<QuantityValue>,
     #[serde(default)]
     #[serde(skip_serializing_if = "MaybeUndefined::is_undefined")]
@@ -144,3 +157,10 @@ pub struct QueryParams {
     pub satisfies: Option<IntentAddress>,
     pub satisfied_by: Option<CommitmentAddress>,
 }
+
+#[cfg(all(test, feature = "snake_case_aliases"))]
+mod tests {
+    use super::*;
+
+    vf_attributes_hdk::snake_case_alias_tests!(Response, CreateRequest, UpdateRequest);
+}
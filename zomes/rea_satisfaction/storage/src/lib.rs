@@ -19,8 +19,10 @@ use vf_measurement::QuantityValue;
 
 pub use vf_attributes_hdk::{
     SatisfactionAddress,
-    EventOrCommitmentAddress,
+    RecordRef,
     IntentAddress,
+    CommitmentAddress,
+    EconomicEventAddress,
 };
 
 use hc_zome_rea_satisfaction_rpc::{ CreateRequest, UpdateRequest };
@@ -57,7 +59,8 @@ pub struct SatisfactionZomeConfigPlanning {
 
 #[derive(Serialize, Deserialize, Debug, SerializedBytes, Clone)]
 pub struct EntryData {
-    pub satisfied_by: EventOrCommitmentAddress,
+    #[serde(deserialize_with = "deserialize_satisfied_by")]
+    pub satisfied_by: RecordRef,
     pub satisfies: IntentAddress,
     pub resource_quantity: Option<QuantityValue>,
     pub effort_quantity: Option<QuantityValue>,
@@ -67,6 +70,43 @@ pub struct EntryData {
 
 generate_record_entry!(EntryData, SatisfactionAddress, EntryStorage);
 
+/// Encodings of `EntryData.satisfied_by` found on the DHT.
+/// Entries written prior to `RecordRef` hold a bare identifier, untyped.
+///
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredSatisfiedBy {
+    Typed(RecordRef),
+    Legacy(DnaHash, EntryHash),
+}
+
+/// Decode `satisfied_by`, inferring the type of legacy untyped references from the DNA they
+/// reference. Only the planning DNA's own commitments and the observation DNA's own events are
+/// ever local, so a local reference is of the type native to the reading DNA and a remote one
+/// of the other type.
+///
+fn deserialize_satisfied_by<'de, D>(deserializer: D) -> Result<RecordRef, D::Error>
+    where D: serde::Deserializer<'de>,
+{
+    match StoredSatisfiedBy::deserialize(deserializer)? {
+        StoredSatisfiedBy::Typed(reference) => Ok(reference),
+        StoredSatisfiedBy::Legacy(dna, entry) => {
+            let info = dna_info().map_err(serde::de::Error::custom)?;
+            let is_local = info.hash == dna;
+            let in_observation_dna = DnaConfigSliceObservation::try_from(info.modifiers.properties).is_ok();
+            Ok(infer_legacy_satisfied_by(dna, entry, is_local, in_observation_dna))
+        },
+    }
+}
+
+fn infer_legacy_satisfied_by(dna: DnaHash, entry: EntryHash, is_local: bool, in_observation_dna: bool) -> RecordRef {
+    if is_local == in_observation_dna {
+        EconomicEventAddress(dna, entry).into()
+    } else {
+        CommitmentAddress(dna, entry).into()
+    }
+}
+
 //---------------- Holochain App Entry And Link Types Setup ----------------
 
 #[hdk_entry_defs(skip_hdk_extern = true)]
@@ -101,6 +141,15 @@ pub enum LinkTypes {
 }
 
 
+/// Ensure a `Satisfaction` is only ever satisfied by an `EconomicEvent` or `Commitment`.
+///
+fn validate_satisfied_by(satisfied_by: RecordRef) -> RecordAPIResult<RecordRef> {
+    match satisfied_by {
+        RecordRef::EconomicEvent(_) | RecordRef::Commitment(_) => Ok(satisfied_by),
+        other => Err(DataIntegrityError::UnsupportedRecordType(other.record_type().to_string())),
+    }
+}
+
 //---------------- CREATE ----------------
 
 /// Pick relevant fields out of I/O record into underlying DHT entry
@@ -109,7 +158,7 @@ impl TryFrom<CreateRequest> for EntryData {
 
     fn try_from(e: CreateRequest) -> RecordAPIResult<EntryData> {
         Ok(EntryData {
-            satisfied_by: e.satisfied_by.into(),
+            satisfied_by: validate_satisfied_by(e.satisfied_by)?,
             satisfies: e.satisfies.into(),
             resource_quantity: e.resource_quantity.into(),
             effort_quantity: e.effort_quantity.into(),
@@ -126,7 +175,7 @@ impl Updateable<UpdateRequest> for EntryData {
     fn update_with(&self, e: UpdateRequest) -> RecordAPIResult<EntryData> {
         Ok(EntryData {
             satisfied_by: match &e.satisfied_by {
-                MaybeUndefined::Some(satisfied_by) => satisfied_by.clone(),
+                MaybeUndefined::Some(satisfied_by) => validate_satisfied_by(satisfied_by.clone())?,
                 _ => self.satisfied_by.clone(),
             },
             satisfies: match &e.satisfies {
@@ -140,3 +189,57 @@ impl Updateable<UpdateRequest> for EntryData {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Stored {
+        #[serde(deserialize_with = "deserialize_stored")]
+        satisfied_by: StoredSatisfiedBy,
+    }
+
+    fn deserialize_stored<'de, D>(deserializer: D) -> Result<StoredSatisfiedBy, D::Error>
+        where D: serde::Deserializer<'de>,
+    {
+        StoredSatisfiedBy::deserialize(deserializer)
+    }
+
+    #[derive(Serialize)]
+    struct Legacy {
+        satisfied_by: (DnaHash, EntryHash),
+    }
+
+    #[derive(Serialize)]
+    struct Typed {
+        satisfied_by: RecordRef,
+    }
+
+    fn dna() -> DnaHash { DnaHash::from_raw_36(vec![0xdb; 36]) }
+    fn entry() -> EntryHash { EntryHash::from_raw_36(vec![0xab; 36]) }
+
+    #[test]
+    fn test_decodes_typed_and_legacy_references() {
+        let typed: RecordRef = CommitmentAddress(dna(), entry()).into();
+        let encoded = holochain_serialized_bytes::encode(&Typed { satisfied_by: typed.to_owned() }).unwrap();
+        match holochain_serialized_bytes::decode::<_, Stored>(&encoded).unwrap().satisfied_by {
+            StoredSatisfiedBy::Typed(reference) => assert_eq!(reference, typed),
+            StoredSatisfiedBy::Legacy(_, _) => panic!("typed reference decoded as legacy"),
+        }
+
+        let encoded = holochain_serialized_bytes::encode(&Legacy { satisfied_by: (dna(), entry()) }).unwrap();
+        match holochain_serialized_bytes::decode::<_, Stored>(&encoded).unwrap().satisfied_by {
+            StoredSatisfiedBy::Legacy(d, e) => assert_eq!((d, e), (dna(), entry())),
+            StoredSatisfiedBy::Typed(reference) => panic!("legacy reference decoded as {:?}", reference),
+        }
+    }
+
+    #[test]
+    fn test_infers_legacy_reference_types() {
+        assert_eq!(infer_legacy_satisfied_by(dna(), entry(), true, false).record_type(), "Commitment", "local to planning");
+        assert_eq!(infer_legacy_satisfied_by(dna(), entry(), false, false).record_type(), "EconomicEvent", "remote from planning");
+        assert_eq!(infer_legacy_satisfied_by(dna(), entry(), true, true).record_type(), "EconomicEvent", "local to observation");
+        assert_eq!(infer_legacy_satisfied_by(dna(), entry(), false, true).record_type(), "Commitment", "remote from observation");
+    }
+}
@@ -10,3 +10,4 @@ pub const SATISFACTION_ENTRY_TYPE: &str = "satisfaction";
 pub const REPLICATE_CREATE_API_METHOD: &str = "create_satisfaction";
 pub const REPLICATE_UPDATE_API_METHOD: &str = "update_satisfaction";
 pub const REPLICATE_DELETE_API_METHOD: &str = "delete_satisfaction";
+pub const REPLICATE_DELETE_FROM_EVENT_API_METHOD: &str = "delete_satisfaction_from_event";
@@ -44,3 +44,13 @@ fn satisfaction_updated(UpdateParams { satisfaction }: UpdateParams) -> ExternRe
 fn satisfaction_deleted(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
     Ok(handle_delete_satisfaction(revision_id)?)
 }
+
+#[hdk_extern]
+fn delete_satisfaction_from_event(ByAddress { address }: ByAddress<SatisfactionAddress>) -> ExternResult<bool> {
+    Ok(handle_delete_satisfaction_from_event(address)?)
+}
+
+#[hdk_extern]
+fn resync_record_indexes(ByAddress { address }: ByAddress<SatisfactionAddress>) -> ExternResult<bool> {
+    Ok(handle_resync_satisfaction_indexes(address)?)
+}
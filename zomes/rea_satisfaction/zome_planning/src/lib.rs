@@ -28,6 +28,11 @@ fn get_satisfaction(
     Ok(handle_get_satisfaction(address)?)
 }
 
+#[hdk_extern]
+fn resync_record_indexes(ByAddress { address }: ByAddress<SatisfactionAddress>) -> ExternResult<bool> {
+    Ok(handle_resync_satisfaction_indexes(address)?)
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
@@ -38,7 +43,17 @@ fn update_satisfaction(UpdateParams { satisfaction }: UpdateParams) -> ExternRes
     Ok(handle_update_satisfaction(satisfaction)?)
 }
 
+#[hdk_extern]
+fn patch_record(PatchParams { address, ops }: PatchParams<SatisfactionAddress>) -> ExternResult<ResponseData> {
+    Ok(handle_patch_satisfaction(address, ops)?)
+}
+
 #[hdk_extern]
 fn delete_satisfaction(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
     Ok(handle_delete_satisfaction(revision_id)?)
 }
+
+#[hdk_extern]
+fn satisfaction_from_event_deleted(ByAddress { address }: ByAddress<SatisfactionAddress>) -> ExternResult<bool> {
+    Ok(handle_delete_satisfaction_from_event(address)?)
+}
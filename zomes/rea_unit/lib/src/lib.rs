@@ -8,6 +8,7 @@
  */
 use hdk::prelude::*;
 use hdk_records::{
+    generate_patch_builder,
     RecordAPIResult,
     records_anchored::{
         create_anchored_record,
@@ -18,6 +19,7 @@ use hdk_records::{
     records::{
         read_record_entry,
         read_record_entry_by_action,
+        revision_changed,
     },
     metadata::read_revision_metadata_abbreviated,
 };
@@ -71,6 +73,15 @@ pub fn handle_get_unit_by_address(address: UnitInternalAddress) -> RecordAPIResu
     ), &meta, &entry)
 }
 
+/// Resync the indexes of a `Unit`. Units hold no field indexes, so this only confirms
+/// the record is readable.
+///
+pub fn handle_resync_unit_indexes(address: UnitInternalAddress) -> RecordAPIResult<bool>
+{
+    read_record_entry::<EntryData, EntryStorage, UnitInternalAddress>(address.as_ref())?;
+    Ok(true)
+}
+
 pub fn handle_get_revision(revision_id: ActionHash) -> RecordAPIResult<ResponseData>
 {
     let (meta, _base_address, entry) = read_record_entry_by_action::<EntryData, EntryStorage, _>(&revision_id)?;
@@ -84,7 +95,23 @@ pub fn handle_update_unit(unit: UpdateRequest) -> RecordAPIResult<ResponseData>
 {
     let revision_id = unit.get_revision_id().clone();
     let (meta, new_id, new_entry, _prev_entry): (_,UnitId,_,_) = update_anchored_record::<LinkTypes, EntryData, EntryStorage, UnitInternalAddress, _,_,_>(LinkTypes::UnitIdentifier, &revision_id, unit)?;
-    construct_response(&new_id, &meta, &new_entry)
+    let mut response = construct_response(&new_id, &meta, &new_entry)?;
+    response.unit.meta.changed = revision_changed(&meta, &revision_id);
+    Ok(response)
+}
+
+generate_patch_builder!(UpdateRequest {
+    label,
+    symbol
+});
+
+/// Apply a set of `PatchOp`s to the latest revision of a `Unit`.
+/// The patch is converted to an `UpdateRequest` and applied via `handle_update_unit`.
+///
+pub fn handle_patch_unit(address: UnitId, ops: Vec<PatchOp>) -> RecordAPIResult<ResponseData>
+{
+    let revision_id = handle_get_unit(address)?.unit.revision_id;
+    handle_update_unit(build_patch_update(revision_id, ops)?)
 }
 
 pub fn handle_delete_unit(revision_id: ActionHash) -> RecordAPIResult<bool> {
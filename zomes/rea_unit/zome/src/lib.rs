@@ -40,6 +40,11 @@ fn __internal_get_unit_by_hash(ByAddress { address }: ByAddress<UnitInternalAddr
     Ok(handle_get_unit_by_address(address)?)
 }
 
+#[hdk_extern]
+fn resync_record_indexes(ByAddress { address }: ByAddress<UnitInternalAddress>) -> ExternResult<bool> {
+    Ok(handle_resync_unit_indexes(address)?)
+}
+
 #[hdk_extern]
 fn get_revision(ByRevision { revision_id }: ByRevision) -> ExternResult<ResponseData> {
     Ok(handle_get_revision(revision_id)?)
@@ -56,6 +61,11 @@ fn update_unit(UpdateParams { unit }: UpdateParams) -> ExternResult<ResponseData
     Ok(handle_update_unit(unit)?)
 }
 
+#[hdk_extern]
+fn patch_record(PatchParams { address, ops }: PatchParams<UnitId>) -> ExternResult<ResponseData> {
+    Ok(handle_patch_unit(address, ops)?)
+}
+
 #[hdk_extern]
 fn delete_unit(ByRevision { revision_id }: ByRevision) -> ExternResult<bool> {
     Ok(handle_delete_unit(revision_id)?)